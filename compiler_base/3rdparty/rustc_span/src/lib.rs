@@ -700,6 +700,11 @@ pub struct SourceFile {
     pub normalized_pos: Vec<NormalizedPos>,
     /// A hash of the filename, used for speeding up hashing in incremental compilation.
     pub name_hash: u64,
+    /// The generation of this source in the `SourceMap`, which is unique among the files
+    /// added into the same `SourceMap`. The address space of a removed file may be reused
+    /// by a later file, and the generation tells them apart. See
+    /// `SourceMap::lookup_source_file_checked`.
+    pub generation: u32,
 }
 
 impl fmt::Debug for SourceFile {
@@ -741,6 +746,7 @@ impl SourceFile {
             non_narrow_chars,
             normalized_pos,
             name_hash,
+            generation: 0,
         }
     }
 
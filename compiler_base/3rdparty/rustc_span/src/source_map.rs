@@ -13,7 +13,6 @@ use crate::{CharPos, FileLines, LineInfo, Pos};
 
 use rustc_data_structures::fx::FxHashMap;
 use rustc_data_structures::sync::{AtomicU32, Lrc};
-use std::borrow::BorrowMut;
 use std::cell::RefCell;
 use std::clone::Clone;
 use std::collections::hash_map::DefaultHasher;
@@ -35,39 +34,6 @@ use super::{
 };
 use crate::span_encoding::Span;
 
-pub mod monotonic {
-    use std::ops::Deref;
-
-    /// A `MonotonicVec` is a `Vec` which can only be grown.
-    /// Once inserted, an element can never be removed or swapped,
-    /// guaranteeing that any indices into a `MonotonicVec` are stable
-    // This is declared in its own module to ensure that the private
-    // field is inaccessible
-    pub struct MonotonicVec<T>(Vec<T>);
-    impl<T> MonotonicVec<T> {
-        pub fn new(val: Vec<T>) -> MonotonicVec<T> {
-            MonotonicVec(val)
-        }
-
-        pub fn push(&mut self, val: T) {
-            self.0.push(val);
-        }
-    }
-
-    impl<T> Default for MonotonicVec<T> {
-        fn default() -> Self {
-            MonotonicVec::new(vec![])
-        }
-    }
-
-    impl<T> Deref for MonotonicVec<T> {
-        type Target = Vec<T>;
-        fn deref(&self) -> &Self::Target {
-            &self.0
-        }
-    }
-}
-
 #[derive(Clone, Debug, Copy)]
 pub struct Spanned<T> {
     pub node: T,
@@ -149,8 +115,63 @@ impl StableSourceFileId {
 
 #[derive(Default)]
 pub(super) struct SourceMapFiles {
-    source_files: monotonic::MonotonicVec<Lrc<SourceFile>>,
+    /// The files sorted by their start positions. A file may be removed from the
+    /// source map, thus the indices into `source_files` are not stable.
+    source_files: Vec<Lrc<SourceFile>>,
     stable_id_to_source_file: FxHashMap<StableSourceFileId, Lrc<SourceFile>>,
+    /// The sizes of the address space slots of the files, keyed by the start positions.
+    /// A slot may be larger than its file when the slot is reused by a smaller file.
+    slot_sizes: FxHashMap<BytePos, usize>,
+    /// The address space slots, i.e., the start positions and sizes, released by the
+    /// removed files, which are reused by the new files before allocating more address space.
+    free_slots: Vec<(BytePos, usize)>,
+    /// The generation of the next file added into the source map.
+    next_generation: u32,
+}
+
+impl SourceMapFiles {
+    /// Returns a new generation for the file being added into the source map.
+    fn new_generation(&mut self) -> u32 {
+        let generation = self.next_generation;
+        self.next_generation += 1;
+        generation
+    }
+
+    fn insert(&mut self, file_id: StableSourceFileId, source_file: Lrc<SourceFile>, size: usize) {
+        // The reused slots are lower than the last file, so keep `source_files` sorted
+        // for the binary search in `lookup_source_file_idx`.
+        let idx = self
+            .source_files
+            .partition_point(|sf| sf.start_pos < source_file.start_pos);
+        self.source_files.insert(idx, source_file.clone());
+        self.slot_sizes.insert(source_file.start_pos, size);
+        self.stable_id_to_source_file.insert(file_id, source_file);
+    }
+
+    fn remove(&mut self, file_id: StableSourceFileId) -> Option<Lrc<SourceFile>> {
+        let source_file = self.stable_id_to_source_file.remove(&file_id)?;
+        if let Ok(idx) = self
+            .source_files
+            .binary_search_by_key(&source_file.start_pos, |sf| sf.start_pos)
+        {
+            self.source_files.remove(idx);
+        }
+        let size = self
+            .slot_sizes
+            .remove(&source_file.start_pos)
+            .unwrap_or_else(|| (source_file.end_pos - source_file.start_pos).to_usize());
+        self.free_slots.push((source_file.start_pos, size));
+        Some(source_file)
+    }
+
+    /// Takes the first free slot which can hold `size` bytes.
+    fn take_free_slot(&mut self, size: usize) -> Option<(BytePos, usize)> {
+        let idx = self
+            .free_slots
+            .iter()
+            .position(|(_, slot_size)| *slot_size >= size)?;
+        Some(self.free_slots.swap_remove(idx))
+    }
 }
 
 pub struct SourceMap {
@@ -224,7 +245,8 @@ impl SourceMap {
     }
 
     // By returning a `Vec`, we ensure that consumers cannot invalidate
-    // any existing indices pointing into `files`.
+    // any existing indices pointing into `files`. Note that the indices may
+    // not match the source map after a file is removed or replaced.
     pub fn files(&self) -> Vec<Lrc<SourceFile>> {
         self.files.borrow().source_files.clone()
     }
@@ -300,25 +322,26 @@ impl SourceMap {
         let lrc_sf = match self.source_file_by_stable_id(file_id) {
             Some(lrc_sf) => lrc_sf,
             None => {
-                let start_pos = self.allocate_address_space(src.len())?;
+                let free_slot = self.files.borrow_mut().take_free_slot(src.len());
+                let (start_pos, size) = match free_slot {
+                    Some(slot) => slot,
+                    None => (
+                        Pos::from_usize(self.allocate_address_space(src.len())?),
+                        src.len(),
+                    ),
+                };
 
-                let source_file = Lrc::new(SourceFile::new(
-                    filename,
-                    src,
-                    Pos::from_usize(start_pos),
-                    self.hash_kind,
-                ));
+                let mut source_file = SourceFile::new(filename, src, start_pos, self.hash_kind);
+                source_file.generation = self.files.borrow_mut().new_generation();
+                let source_file = Lrc::new(source_file);
 
                 // Let's make sure the file_id we generated above actually matches
                 // the ID we generate for the SourceFile we just created.
                 debug_assert_eq!(StableSourceFileId::new(&source_file), file_id);
 
-                let mut files = self.files.borrow_mut();
-
-                files.source_files.push(source_file.clone());
-                files
-                    .stable_id_to_source_file
-                    .insert(file_id, source_file.clone());
+                self.files
+                    .borrow_mut()
+                    .insert(file_id, source_file.clone(), size);
 
                 source_file
             }
@@ -326,6 +349,70 @@ impl SourceMap {
         Ok(lrc_sf)
     }
 
+    /// Replaces the `SourceFile` with the same ID by a new one holding `src`, or creates
+    /// a new `SourceFile` if it doesn't exist. Unlike `new_source_file`, the returned file
+    /// always holds `src`, and the address space of the old file is reused when `src`
+    /// fits into it, thus the source map doesn't grow when a file is edited repeatedly
+    /// in a long-running process e.g., the language server.
+    ///
+    /// The old file is returned unmodified if its source is the same as `src`. Otherwise,
+    /// the spans pointing into the old file are stale, and the new file has a different
+    /// generation which is checked by `lookup_source_file_checked`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustc_span::{source_map::FilePathMapping, FileName, SourceMap};
+    ///
+    /// let sm = SourceMap::new(FilePathMapping::empty());
+    /// let filename = FileName::Custom("main.k".to_string());
+    /// let old = sm.new_source_file(filename.clone(), "a = 1\nb = 2\n".to_string());
+    /// let new = sm.replace_source_file(filename.clone(), "a = 2\n".to_string());
+    ///
+    /// assert_eq!(new.src.as_ref().unwrap().as_str(), "a = 2\n");
+    /// assert_eq!(new.start_pos, old.start_pos);
+    /// assert_eq!(sm.files().len(), 1);
+    /// ```
+    pub fn replace_source_file(&self, filename: FileName, src: String) -> Lrc<SourceFile> {
+        let (mapped_filename, _) = self.path_mapping.map_filename_prefix(&filename);
+        let file_id = StableSourceFileId::new_from_name(&mapped_filename);
+        if let Some(source_file) = self.source_file_by_stable_id(file_id) {
+            if source_file.src.is_some() && source_file.src_hash.matches(&src) {
+                return source_file;
+            }
+        }
+        self.files.borrow_mut().remove(file_id);
+        self.new_source_file(filename, src)
+    }
+
+    /// Removes the `SourceFile` with the filename from the source map, and its address
+    /// space is reused by the later files. Returns the removed file, or None if it
+    /// doesn't exist.
+    ///
+    /// The spans pointing into the removed file are stale, and `lookup_source_file_checked`
+    /// detects them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustc_span::{source_map::FilePathMapping, FileName, SourceMap};
+    ///
+    /// let sm = SourceMap::new(FilePathMapping::empty());
+    /// let filename = FileName::Custom("main.k".to_string());
+    /// let removed = sm.new_source_file(filename.clone(), "a = 1\n".to_string());
+    ///
+    /// assert!(sm.remove_source_file(&filename).is_some());
+    /// assert!(sm.get_source_file(&filename).is_none());
+    /// // The new file reuses the address space of the removed file.
+    /// let added = sm.new_source_file(FileName::Custom("b.k".to_string()), "b = 1\n".to_string());
+    /// assert_eq!(added.start_pos, removed.start_pos);
+    /// ```
+    pub fn remove_source_file(&self, filename: &FileName) -> Option<Lrc<SourceFile>> {
+        let (filename, _) = self.path_mapping.map_filename_prefix(filename);
+        let file_id = StableSourceFileId::new_from_name(&filename);
+        self.files.borrow_mut().remove(file_id)
+    }
+
     /// Allocates a new `SourceFile` representing a source file from an external
     /// crate. The source code of such an "imported `SourceFile`" is not available,
     /// but we still know enough to generate accurate debuginfo location
@@ -377,14 +464,14 @@ impl SourceMap {
             non_narrow_chars: file_local_non_narrow_chars,
             normalized_pos: file_local_normalized_pos,
             name_hash,
+            generation: self.files.borrow_mut().new_generation(),
         });
 
-        let mut files = self.files.borrow_mut();
-
-        files.borrow_mut().source_files.push(source_file.clone());
-        files
-            .stable_id_to_source_file
-            .insert(StableSourceFileId::new(&source_file), source_file.clone());
+        self.files.borrow_mut().insert(
+            StableSourceFileId::new(&source_file),
+            source_file.clone(),
+            source_len,
+        );
 
         source_file
     }
@@ -409,6 +496,46 @@ impl SourceMap {
         (*self.files.borrow().source_files)[idx].clone()
     }
 
+    /// Looks up the `SourceFile` containing `pos`, which is checked to be the file with
+    /// `generation`. Returns None if `pos` points into a removed file, or into a file taking
+    /// the address space of the file with `generation`, i.e., `pos` is stale.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rustc_span::{source_map::FilePathMapping, FileName, SourceMap};
+    ///
+    /// let sm = SourceMap::new(FilePathMapping::empty());
+    /// let filename = FileName::Custom("main.k".to_string());
+    /// let old = sm.new_source_file(filename.clone(), "a = 1\n".to_string());
+    /// assert!(sm.lookup_source_file_checked(old.start_pos, old.generation).is_some());
+    ///
+    /// let new = sm.replace_source_file(filename, "a = 2\n".to_string());
+    /// assert_eq!(new.start_pos, old.start_pos);
+    /// assert!(sm.lookup_source_file_checked(old.start_pos, old.generation).is_none());
+    /// assert!(sm.lookup_source_file_checked(new.start_pos, new.generation).is_some());
+    /// ```
+    pub fn lookup_source_file_checked(
+        &self,
+        pos: BytePos,
+        generation: u32,
+    ) -> Option<Lrc<SourceFile>> {
+        let files = self.files.borrow();
+        let idx = match files
+            .source_files
+            .binary_search_by_key(&pos, |key| key.start_pos)
+        {
+            Ok(idx) => idx,
+            Err(idx) => idx.checked_sub(1)?,
+        };
+        let sf = &files.source_files[idx];
+        if sf.generation == generation && pos <= sf.end_pos {
+            Some(sf.clone())
+        } else {
+            None
+        }
+    }
+
     /// Looks up source information about a `BytePos`.
     pub fn lookup_char_pos(&self, pos: BytePos) -> Loc {
         let sf = self.lookup_source_file(pos);
@@ -829,8 +956,7 @@ impl SourceMap {
     }
 
     // Returns the index of the `SourceFile` (in `self.files`) that contains `pos`.
-    // This index is guaranteed to be valid for the lifetime of this `SourceMap`,
-    // since `source_files` is a `MonotonicVec`
+    // This index is valid until a file is removed from or replaced in this `SourceMap`.
     pub fn lookup_source_file_idx(&self, pos: BytePos) -> usize {
         self.files
             .borrow()
@@ -1,7 +1,7 @@
 //! This crate provides `DiagnosticHandler` supports diagnostic messages to terminal stderr.
 //!
 //! `DiagnosticHandler` mainly consists of 4 parts：
-//! - Emitter: Emit the styled string to terminal stderr.
+//! - Emitter: Emit the styled string to terminal stderr, or to the custom `Emitter`.
 //! - Template Loader: Load template files locally and find messages from file contents.
 //! - A set for Diagnostics: All the diagnostic messages.
 //!
@@ -175,6 +175,61 @@ impl DiagnosticHandler {
         })
     }
 
+    /// Create a `DiagnosticHandler` with no (*.ftl) template files and a custom `Emitter`,
+    /// which receives the structured diagnostics instead of the terminal stderr, e.g., an
+    /// emitter forwarding the diagnostics to a language server or a log collector.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// # use compiler_base_error::diagnostic_handler::DiagnosticHandler;
+    /// # use compiler_base_error::errors::ComponentError;
+    /// # use compiler_base_error::{Component, Diagnostic, DiagnosticStyle, Emitter, StyledBuffer};
+    /// # use std::sync::{Arc, Mutex};
+    /// // 1. Define an emitter collecting the diagnostics.
+    /// struct CollectEmitter(Arc<Mutex<usize>>);
+    ///
+    /// impl Emitter<DiagnosticStyle> for CollectEmitter {
+    ///     fn format_diagnostic(
+    ///         &mut self,
+    ///         diag: &Diagnostic<DiagnosticStyle>,
+    ///     ) -> Result<StyledBuffer<DiagnosticStyle>, ComponentError> {
+    ///         let mut sb = StyledBuffer::<DiagnosticStyle>::new();
+    ///         let mut errs = vec![];
+    ///         diag.format(&mut sb, &mut errs);
+    ///         Ok(sb)
+    ///     }
+    ///
+    ///     fn emit_diagnostic(&mut self, _: &Diagnostic<DiagnosticStyle>) -> anyhow::Result<()> {
+    ///         *self.0.lock().unwrap() += 1;
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// // 2. Create the `DiagnosticHandler` with the emitter.
+    /// let count = Arc::new(Mutex::new(0));
+    /// let diag_handler = DiagnosticHandler::new_with_emitter(Box::new(CollectEmitter(count.clone())));
+    /// diag_handler.emit_error_diagnostic(Diagnostic::<DiagnosticStyle>::new()).unwrap();
+    /// assert_eq!(*count.lock().unwrap(), 1);
+    /// ```
+    pub fn new_with_emitter(emitter: Box<dyn Emitter<DiagnosticStyle>>) -> Self {
+        Self {
+            handler_inner: Mutex::new(DiagnosticHandlerInner::new_with_emitter(emitter)),
+        }
+    }
+
+    /// Replace the `Emitter` of the `DiagnosticHandler`, and the subsequent diagnostics are
+    /// emitted by the new emitter. See [`DiagnosticHandler::new_with_emitter`] for the custom emitters.
+    pub fn set_emitter(&self, emitter: Box<dyn Emitter<DiagnosticStyle>>) -> Result<&Self> {
+        match self.handler_inner.lock() {
+            Ok(mut inner) => {
+                inner.emitter = emitter;
+                Ok(self)
+            }
+            Err(_) => bail!("Set Emitter Failed."),
+        }
+    }
+
     /// Add a diagnostic generated from error to `DiagnosticHandler`.
     /// `DiagnosticHandler` contains a set of `Diagnostic<DiagnosticStyle>`
     ///
@@ -608,6 +663,14 @@ impl DiagnosticHandlerInner {
         }
     }
 
+    /// Create the inner handler with the custom `Emitter`.
+    pub(crate) fn new_with_emitter(emitter: Box<dyn Emitter<DiagnosticStyle>>) -> Self {
+        Self {
+            emitter,
+            ..Self::default()
+        }
+    }
+
     /// Load all (*.ftl) template files under directory `template_dir`.
     pub(crate) fn new_with_template_dir(template_dir: &str) -> Result<Self> {
         let template_loader = TemplateLoader::new_with_template_dir(template_dir)
@@ -1,5 +1,7 @@
 use anyhow::{Context, Result};
-use compiler_base_error::{diagnostic_handler::DiagnosticHandler, Diagnostic, DiagnosticStyle};
+use compiler_base_error::{
+    diagnostic_handler::DiagnosticHandler, Diagnostic, DiagnosticStyle, Emitter,
+};
 use compiler_base_span::{FilePathMapping, SourceMap};
 use std::{
     path::{Path, PathBuf},
@@ -126,6 +128,23 @@ impl Session {
         })
     }
 
+    /// Construct a `Session` with a custom `Emitter`, and the emitted diagnostics are
+    /// sent to the emitter as the structured diagnostics without the string round-trips,
+    /// e.g., an emitter forwarding the diagnostics to a language server.
+    ///
+    /// For more information about the custom `Emitter`, see the doc above
+    /// `DiagnosticHandler::new_with_emitter()`.
+    #[inline]
+    pub fn new_with_emitter(
+        sm: Arc<SourceMap>,
+        emitter: Box<dyn Emitter<DiagnosticStyle>>,
+    ) -> Self {
+        Self {
+            sm,
+            diag_handler: Arc::new(DiagnosticHandler::new_with_emitter(emitter)),
+        }
+    }
+
     /// Emit all diagnostics to terminal and abort.
     ///
     /// # Panics
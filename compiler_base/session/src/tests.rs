@@ -1,13 +1,18 @@
 mod test_session {
-    use std::{path::PathBuf, sync::Arc};
+    use std::{
+        path::PathBuf,
+        sync::{Arc, Mutex},
+    };
 
     use crate::{Session, SessionDiagnostic};
     use anyhow::Result;
     use compiler_base_error::{
         components::{CodeSnippet, Label},
-        Diagnostic, DiagnosticStyle,
+        emit_diagnostic_to_uncolored_text,
+        errors::ComponentError,
+        Component, Diagnostic, DiagnosticStyle, Emitter, StyledBuffer,
     };
-    use compiler_base_span::{span::new_byte_pos, Span};
+    use compiler_base_span::{span::new_byte_pos, FilePathMapping, SourceMap, Span};
 
     const CARGO_ROOT: &str = env!("CARGO_MANIFEST_DIR");
     #[test]
@@ -183,4 +188,41 @@ mod test_session {
         sess.add_warn(MyWarning {}).unwrap();
         assert_eq!(sess.diagnostics_count().unwrap(), 1);
     }
+
+    /// An emitter collecting the diagnostic texts.
+    struct CollectEmitter(Arc<Mutex<Vec<String>>>);
+
+    impl Emitter<DiagnosticStyle> for CollectEmitter {
+        fn format_diagnostic(
+            &mut self,
+            diag: &Diagnostic<DiagnosticStyle>,
+        ) -> Result<StyledBuffer<DiagnosticStyle>, ComponentError> {
+            let mut sb = StyledBuffer::<DiagnosticStyle>::new();
+            let mut errs = vec![];
+            diag.format(&mut sb, &mut errs);
+            Ok(sb)
+        }
+
+        fn emit_diagnostic(&mut self, diag: &Diagnostic<DiagnosticStyle>) -> Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(emit_diagnostic_to_uncolored_text(diag)?);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_new_session_with_emitter() {
+        let diags = Arc::new(Mutex::new(vec![]));
+        let sm = Arc::new(SourceMap::new(FilePathMapping::empty()));
+        let sess = Session::new_with_emitter(sm, Box::new(CollectEmitter(diags.clone())));
+        sess.add_err(MyError {}).unwrap();
+        sess.add_warn(MyWarning {}).unwrap();
+        sess.emit_stashed_diagnostics().unwrap();
+        assert_eq!(
+            *diags.lock().unwrap(),
+            vec!["error[error]".to_string(), "warning[warning]".to_string()]
+        );
+    }
 }
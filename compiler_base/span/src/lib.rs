@@ -15,6 +15,8 @@ pub type SourceMap = rustc_span::SourceMap;
 pub type SourceFile = rustc_span::SourceFile;
 pub type FilePathMapping = rustc_span::source_map::FilePathMapping;
 pub type Loc = rustc_span::Loc;
+pub use rustc_span::source_map::RealFileLoader;
+pub use rustc_span::{FileName, RealFileName, SourceFileHash, SourceFileHashAlgorithm};
 
 /// Get the filename from `SourceMap` by `Span`.
 ///
@@ -0,0 +1,104 @@
+// Copyright The KCL Authors. All rights reserved.
+
+// The stable C ABI of the KCL service, please keep same as 'kclvm/api/src/service/ffi.rs'.
+//
+// The arguments and the results are the JSON strings of the messages defined in
+// 'kclvm/spec/gpyrpc/gpyrpc.proto'. Each API writes the JSON result, or the error
+// message when the returned status is not KCLVM_CAPI_OK, to the output string,
+// which is owned by the caller and must be freed by 'kclvm_capi_free_string'.
+//
+// Example:
+//
+//     kclvm_service* serv = kclvm_service_new(0);
+//     char* output = NULL;
+//     kclvm_capi_status status = kclvm_capi_exec_program(
+//         serv, "{\"k_filename_list\": [\"main.k\"]}", &output);
+//     if (status == KCLVM_CAPI_OK) {
+//         // The output is the JSON of ExecProgramResult.
+//     }
+//     kclvm_capi_free_string(output);
+//     kclvm_service_delete(serv);
+
+#pragma once
+
+#ifndef _kclvm_capi_h_
+#define _kclvm_capi_h_
+
+#include <stdint.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif
+
+// The version of the C ABI, which is increased on every incompatible change.
+#define KCLVM_CAPI_ABI_VERSION 1
+
+typedef struct kclvm_service kclvm_service;
+
+typedef enum kclvm_capi_status {
+    // The call succeeded and the output is the JSON result.
+    KCLVM_CAPI_OK = 0,
+    // The pointers are null, or the arguments are not the valid JSON of the message.
+    KCLVM_CAPI_INVALID_ARGUMENT = 1,
+    // The call failed and the output is the error message.
+    KCLVM_CAPI_ERROR = 2,
+    // The call panicked and the output is the panic message.
+    KCLVM_CAPI_PANIC = 3,
+} kclvm_capi_status;
+
+// Returns the version of the C ABI, which should equal to KCLVM_CAPI_ABI_VERSION.
+uint32_t kclvm_capi_abi_version(void);
+
+// Create a service with the plugin agent function address, and 0 denotes no plugin agent.
+kclvm_service* kclvm_service_new(uint64_t plugin_agent);
+
+// Delete the service created by 'kclvm_service_new'.
+void kclvm_service_delete(kclvm_service* serv);
+
+// Free the output string of the APIs.
+void kclvm_capi_free_string(char* s);
+
+// Execute the KCL program with ExecProgramArgs, and the output is ExecProgramResult.
+kclvm_capi_status kclvm_capi_exec_program(const kclvm_service* serv, const char* args, char** output);
+
+// The callback receiving the JSON ExecProgram_Event of the streaming execution and the user
+// data, and the event string is only valid during the callback.
+typedef void (*kclvm_capi_event_callback)(const char* event, void* user_data);
+
+// Execute the KCL program with ExecProgramArgs like 'kclvm_capi_exec_program', and the progress
+// events and the final result event are passed to the callback on the calling thread as soon as
+// they happen.
+kclvm_capi_status kclvm_capi_exec_program_stream(const kclvm_service* serv, const char* args, kclvm_capi_event_callback callback, void* user_data, char** output);
+
+// Get the KCL version, the supported APIs, formats and features of the service with
+// GetCapabilitiesArgs, and the output is GetCapabilitiesResult.
+kclvm_capi_status kclvm_capi_get_capabilities(const kclvm_service* serv, const char* args, char** output);
+
+// Get the planned value, the inferred type and the definition position of a value path with
+// GetValueByPathArgs, and the output is GetValueByPathResult.
+kclvm_capi_status kclvm_capi_get_value_by_path(const kclvm_service* serv, const char* args, char** output);
+
+// Load the KCL package with LoadPackageArgs, and the output is LoadPackageResult.
+kclvm_capi_status kclvm_capi_load_package(const kclvm_service* serv, const char* args, char** output);
+
+// Override the KCL file with OverrideFileArgs, and the output is OverrideFileResult.
+kclvm_capi_status kclvm_capi_override_file(const kclvm_service* serv, const char* args, char** output);
+
+// Format the KCL code with FormatCodeArgs, and the output is FormatCodeResult.
+kclvm_capi_status kclvm_capi_format_code(const kclvm_service* serv, const char* args, char** output);
+
+// Format the KCL files under the path with FormatPathArgs, and the output is FormatPathResult.
+kclvm_capi_status kclvm_capi_format_path(const kclvm_service* serv, const char* args, char** output);
+
+// Lint the KCL files with LintPathArgs, and the output is LintPathResult.
+kclvm_capi_status kclvm_capi_lint_path(const kclvm_service* serv, const char* args, char** output);
+
+// Lint the KCL files or the source codes with LintArgs, and the output is LintResult with the
+// structured lint results.
+kclvm_capi_status kclvm_capi_lint(const kclvm_service* serv, const char* args, char** output);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif
+
+#endif // _kclvm_capi_h_
@@ -1,4 +1,6 @@
 use crate::service::capi::*;
+use crate::service::ffi::*;
+use crate::service::service_impl::KclvmServiceImpl;
 use crate::{call, gpyrpc::*};
 use kclvm_utils::path::PathPrefix;
 use once_cell::sync::Lazy;
@@ -171,6 +173,24 @@ fn test_c_api_validate_code_file() {
     );
 }
 
+#[test]
+fn test_c_api_diff_program() {
+    test_c_api_without_wrapper::<DiffProgramArgs, DiffProgramResult>(
+        "KclvmService.DiffProgram",
+        "diff-program.json",
+        "diff-program.response.json",
+    );
+}
+
+#[test]
+fn test_c_api_check_type() {
+    test_c_api_without_wrapper::<CheckTypeArgs, CheckTypeResult>(
+        "KclvmService.CheckType",
+        "check-type.json",
+        "check-type.response.json",
+    );
+}
+
 #[test]
 fn test_c_api_load_settings_files() {
     test_c_api_without_wrapper::<LoadSettingsFilesArgs, LoadSettingsFilesResult>(
@@ -193,17 +213,20 @@ fn test_c_api_rename() {
         "rename.json",
         "rename.response.json",
         |r| {
-            r.changed_files = r
-                .changed_files
-                .iter()
-                .map(|f| {
-                    PathBuf::from(f)
-                        .canonicalize()
-                        .unwrap()
-                        .display()
-                        .to_string()
-                })
-                .collect();
+            let canonicalize = |f: &str| {
+                PathBuf::from(f)
+                    .canonicalize()
+                    .unwrap()
+                    .display()
+                    .to_string()
+            };
+            r.changed_files = r.changed_files.iter().map(|f| canonicalize(f)).collect();
+            for edit in r.edits.iter_mut() {
+                edit.filename = canonicalize(&edit.filename);
+                for pos in [&mut edit.start, &mut edit.end].into_iter().flatten() {
+                    pos.filename = canonicalize(&pos.filename);
+                }
+            }
         },
     );
 
@@ -256,6 +279,13 @@ fn test_c_api_testing() {
         |r| {
             for i in &mut r.info {
                 i.duration = 0;
+                if let Some(pos) = &mut i.position {
+                    pos.filename = Path::new(&pos.filename)
+                        .file_name()
+                        .unwrap()
+                        .to_string_lossy()
+                        .to_string();
+                }
             }
         },
     );
@@ -269,6 +299,132 @@ where
     test_c_api::<A, R, _>(svc_name, input, output, |_| {})
 }
 
+#[test]
+fn test_c_api_json_exec_program() {
+    test_c_api_json::<ExecProgramResult>(
+        kclvm_capi_exec_program,
+        "exec-program.json",
+        "exec-program.response.json",
+    );
+}
+
+#[test]
+fn test_c_api_json_format_code() {
+    test_c_api_json::<FormatCodeResult>(
+        kclvm_capi_format_code,
+        "format-code.json",
+        "format-code.response.json",
+    );
+}
+
+#[test]
+fn test_c_api_json_status() {
+    assert_eq!(kclvm_capi_abi_version(), KCLVM_CAPI_ABI_VERSION);
+    let call = |args: &str| {
+        let _test_lock = TEST_MUTEX.lock().unwrap();
+        let serv = kclvm_service_new(0);
+        let args = CString::new(args).unwrap();
+        let mut output = std::ptr::null_mut();
+        let status = unsafe { kclvm_capi_exec_program(serv, args.as_ptr(), &mut output) };
+        let message = unsafe { CStr::from_ptr(output) }
+            .to_str()
+            .unwrap()
+            .to_string();
+        unsafe {
+            kclvm_capi_free_string(output);
+            kclvm_service_delete(serv);
+        }
+        (status, message)
+    };
+    let (status, message) = call("{");
+    assert_eq!(status, kclvm_capi_status::InvalidArgument);
+    assert!(message.starts_with("invalid arguments: "), "{message}");
+    let (status, message) = call(r#"{"k_filename_list": ["not_found.k"]}"#);
+    assert_eq!(status, kclvm_capi_status::Error);
+    assert!(message.contains("not_found.k"), "{message}");
+}
+
+extern "C" fn collect_event_kinds(event: *const c_char, user_data: *mut std::ffi::c_void) {
+    let kinds = unsafe { &mut *(user_data as *mut Vec<String>) };
+    let event = unsafe { CStr::from_ptr(event) }.to_str().unwrap();
+    let event: ExecProgramEvent = serde_json::from_str(event).unwrap();
+    kinds.push(event.kind);
+}
+
+#[test]
+fn test_c_api_json_exec_program_stream() {
+    let _test_lock = TEST_MUTEX.lock().unwrap();
+    let serv = kclvm_service_new(0);
+    let args = CString::new(
+        r#"{"k_filename_list": ["main.k"], "k_code_list": ["print('hello')\na = 1"]}"#,
+    )
+    .unwrap();
+    let mut kinds: Vec<String> = vec![];
+    let mut output = std::ptr::null_mut();
+    let status = unsafe {
+        kclvm_capi_exec_program_stream(
+            serv,
+            args.as_ptr(),
+            Some(collect_event_kinds),
+            &mut kinds as *mut Vec<String> as *mut std::ffi::c_void,
+            &mut output,
+        )
+    };
+    let result = unsafe { CStr::from_ptr(output) }.to_str().unwrap();
+    assert_eq!(status, kclvm_capi_status::Ok, "{result}");
+    let result: ExecProgramResult = serde_json::from_str(result).unwrap();
+    assert_eq!(result.yaml_result, "a: 1");
+    assert_eq!(kinds.first().unwrap(), "phase_started");
+    assert!(kinds.contains(&"phase_finished".to_string()));
+    assert!(kinds.contains(&"log".to_string()));
+    assert_eq!(kinds.last().unwrap(), "result");
+    unsafe {
+        kclvm_capi_free_string(output);
+        kclvm_service_delete(serv);
+    }
+}
+
+fn test_c_api_json<R>(
+    api: unsafe extern "C" fn(
+        *const KclvmServiceImpl,
+        *const c_char,
+        *mut *mut c_char,
+    ) -> kclvm_capi_status,
+    input: &str,
+    output: &str,
+) where
+    R: PartialEq + DeserializeOwned + std::fmt::Debug,
+{
+    let _test_lock = TEST_MUTEX.lock().unwrap();
+    let serv = kclvm_service_new(0);
+    let input_path = Path::new(TEST_DATA_PATH).join(input);
+    let input = fs::read_to_string(&input_path)
+        .unwrap_or_else(|_| panic!("Something went wrong reading {}", input_path.display()));
+    let args = CString::new(input).unwrap();
+    let mut result_ptr = std::ptr::null_mut();
+    let status = unsafe { api(serv, args.as_ptr(), &mut result_ptr) };
+    let result_json = unsafe { CStr::from_ptr(result_ptr) }
+        .to_str()
+        .unwrap()
+        .to_string();
+    assert_eq!(status, kclvm_capi_status::Ok, "{result_json}");
+    let result = serde_json::from_str::<R>(&result_json).unwrap();
+
+    let except_result_path = Path::new(TEST_DATA_PATH).join(output);
+    let except_result_json = fs::read_to_string(&except_result_path).unwrap_or_else(|_| {
+        panic!(
+            "Something went wrong reading {}",
+            except_result_path.display()
+        )
+    });
+    let except_result = serde_json::from_str::<R>(&except_result_json).unwrap();
+    assert_eq!(result, except_result, "\nresult json is {result_json}");
+    unsafe {
+        kclvm_service_delete(serv);
+        kclvm_capi_free_string(result_ptr);
+    }
+}
+
 fn test_c_api<A, R, F>(svc_name: &str, input: &str, output: &str, wrapper: F)
 where
     A: Message + DeserializeOwned,
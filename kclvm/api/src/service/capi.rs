@@ -159,12 +159,16 @@ pub(crate) fn kclvm_get_service_fn_ptr_by_name(name: &str) -> u64 {
     match name {
         "KclvmService.Ping" => ping as *const () as u64,
         "KclvmService.GetVersion" => get_version as *const () as u64,
+        "KclvmService.GetCapabilities" => get_capabilities as *const () as u64,
         "KclvmService.ParseFile" => parse_file as *const () as u64,
         "KclvmService.ParseProgram" => parse_program as *const () as u64,
         "KclvmService.LoadPackage" => load_package as *const () as u64,
         "KclvmService.ListOptions" => list_options as *const () as u64,
         "KclvmService.ListVariables" => list_variables as *const () as u64,
+        "KclvmService.GetValueByPath" => get_value_by_path as *const () as u64,
+        "KclvmService.GetProgramGraph" => get_program_graph as *const () as u64,
         "KclvmService.ExecProgram" => exec_program as *const () as u64,
+        "KclvmService.DiffProgram" => diff_program as *const () as u64,
         #[cfg(feature = "llvm")]
         "KclvmService.BuildProgram" => build_program as *const () as u64,
         #[cfg(feature = "llvm")]
@@ -177,7 +181,9 @@ pub(crate) fn kclvm_get_service_fn_ptr_by_name(name: &str) -> u64 {
         "KclvmService.FormatCode" => format_code as *const () as u64,
         "KclvmService.FormatPath" => format_path as *const () as u64,
         "KclvmService.LintPath" => lint_path as *const () as u64,
+        "KclvmService.Lint" => lint as *const () as u64,
         "KclvmService.ValidateCode" => validate_code as *const () as u64,
+        "KclvmService.CheckType" => check_type as *const () as u64,
         "KclvmService.LoadSettingsFiles" => load_settings_files as *const () as u64,
         "KclvmService.Rename" => rename as *const () as u64,
         "KclvmService.RenameCode" => rename_code as *const () as u64,
@@ -216,6 +222,23 @@ pub(crate) fn get_version(
     )
 }
 
+/// get_capabilities is used to get the supported APIs, formats and features of the kclvm service
+pub(crate) fn get_capabilities(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        GetCapabilitiesArgs,
+        get_capabilities
+    )
+}
+
 /// parse_file provides users with the ability to parse kcl single file
 ///
 /// # Parameters
@@ -372,6 +395,72 @@ pub(crate) fn list_variables(
     )
 }
 
+/// get_value_by_path provides users with the ability to execute the KCL program and get the
+/// planned value, the inferred type and the definition position of a value path.
+///
+/// # Parameters
+///
+/// `serv`: [*mut kclvm_service]
+///     The pointer of &\[[KclvmServiceImpl]]
+///
+///
+/// `args`: [*const c_char]
+///     the items and compile parameters selected by the user in the KCL CLI
+///     serialized as protobuf byte sequence
+///
+/// # Returns
+///
+/// result: [*const c_char]
+///     Result of the call serialized as protobuf byte sequence
+pub(crate) fn get_value_by_path(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        GetValueByPathArgs,
+        get_value_by_path
+    )
+}
+
+/// get_program_graph provides users with the ability to get the file graph and the package
+/// graph of the program.
+///
+/// # Parameters
+///
+/// `serv`: [*mut kclvm_service]
+///     The pointer of &\[[KclvmServiceImpl]]
+///
+///
+/// `args`: [*const c_char]
+///     the items and compile parameters selected by the user in the KCL CLI
+///     serialized as protobuf byte sequence
+///
+/// # Returns
+///
+/// result: [*const c_char]
+///     Result of the call serialized as protobuf byte sequence
+pub(crate) fn get_program_graph(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        GetProgramGraphArgs,
+        get_program_graph
+    )
+}
+
 /// exec_program provides users with the ability to execute KCL code
 ///
 /// # Parameters
@@ -404,6 +493,24 @@ pub(crate) fn exec_program(
     )
 }
 
+/// Service for executing two KCL programs and returning the structured diff of the
+/// planned values.
+pub(crate) fn diff_program(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(
+        serv,
+        args,
+        args_len,
+        result_len,
+        DiffProgramArgs,
+        diff_program
+    )
+}
+
 /// build_program provides users with the ability to build the KCL program to an artifact.
 ///
 /// # Parameters
@@ -597,6 +704,17 @@ pub(crate) fn lint_path(
     call!(serv, args, args_len, result_len, LintPathArgs, lint_path)
 }
 
+/// Service for KCL Lint API, lint the files or the source codes and returns the structured
+/// lint results with the rule names, the levels, the ranges and the fixes.
+pub(crate) fn lint(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(serv, args, args_len, result_len, LintArgs, lint)
+}
+
 /// Service for validating the data string using the schema code string, when the parameter
 /// `schema` is omitted, use the first schema appeared in the kcl code.
 pub(crate) fn validate_code(
@@ -615,6 +733,17 @@ pub(crate) fn validate_code(
     )
 }
 
+/// Service for checking the expression code against the schema type in the program
+/// without executing the program.
+pub(crate) fn check_type(
+    serv: *mut kclvm_service,
+    args: *const c_char,
+    args_len: usize,
+    result_len: *mut usize,
+) -> *const c_char {
+    call!(serv, args, args_len, result_len, CheckTypeArgs, check_type)
+}
+
 /// Service for building setting file config from args.
 pub(crate) fn load_settings_files(
     serv: *mut kclvm_service,
@@ -0,0 +1,219 @@
+//! The stable C ABI of the KCL service for embedding KCL in the non-Rust ecosystems
+//! e.g., C++ and Java via JNI, which is declared in `kclvm/api/include/kclvm_capi.h`.
+//!
+//! Different from [crate::service::capi], the arguments and the results are JSON strings
+//! of the messages in `kclvm/spec/gpyrpc/gpyrpc.proto`, each API is an exported function
+//! returning a [kclvm_capi_status] code, and the result or the error message is written
+//! to the output string, which must be freed by [kclvm_capi_free_string].
+use std::ffi::{CStr, CString};
+use std::os::raw::{c_char, c_void};
+use std::panic::AssertUnwindSafe;
+
+use serde::{de::DeserializeOwned, Serialize};
+
+use crate::gpyrpc::*;
+use crate::service::service_impl::KclvmServiceImpl;
+
+/// The version of the C ABI, which is increased on every incompatible change.
+pub const KCLVM_CAPI_ABI_VERSION: u32 = 1;
+
+/// The status code of the C API calls.
+#[allow(non_camel_case_types)]
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum kclvm_capi_status {
+    /// The call succeeded and the output is the JSON result.
+    Ok = 0,
+    /// The pointers are null, or the arguments are not the valid JSON of the message.
+    InvalidArgument = 1,
+    /// The call failed and the output is the error message.
+    Error = 2,
+    /// The call panicked and the output is the panic message.
+    Panic = 3,
+}
+
+/// The callback receiving the JSON [ExecProgramEvent] of the streaming execution and
+/// the user data passed to [kclvm_capi_exec_program_stream].
+#[allow(non_camel_case_types)]
+pub type kclvm_capi_event_callback = extern "C" fn(event: *const c_char, user_data: *mut c_void);
+
+/// Returns the version of the C ABI.
+#[no_mangle]
+pub extern "C" fn kclvm_capi_abi_version() -> u32 {
+    KCLVM_CAPI_ABI_VERSION
+}
+
+/// # Safety
+///
+/// This function should not be called twice on the same ptr.
+/// Free the output string of the C API calls.
+#[no_mangle]
+pub unsafe extern "C" fn kclvm_capi_free_string(s: *mut c_char) {
+    if !s.is_null() {
+        unsafe {
+            let _ = CString::from_raw(s);
+        }
+    }
+}
+
+/// Call the service API with the JSON arguments, and write the JSON result or the
+/// error message to the output.
+unsafe fn call_json<A, R>(
+    serv: *const KclvmServiceImpl,
+    args: *const c_char,
+    output: *mut *mut c_char,
+    f: impl FnOnce(&KclvmServiceImpl, &A) -> anyhow::Result<R>,
+) -> kclvm_capi_status
+where
+    A: DeserializeOwned,
+    R: Serialize,
+{
+    if output.is_null() {
+        return kclvm_capi_status::InvalidArgument;
+    }
+    let (status, message) = if serv.is_null() || args.is_null() {
+        (
+            kclvm_capi_status::InvalidArgument,
+            "the service and the arguments must not be null".to_string(),
+        )
+    } else {
+        let serv = unsafe { &*serv };
+        let args = unsafe { CStr::from_ptr(args) }.to_string_lossy();
+        match serde_json::from_str::<A>(&args) {
+            Ok(args) => match std::panic::catch_unwind(AssertUnwindSafe(|| f(serv, &args))) {
+                Ok(Ok(result)) => match serde_json::to_string(&result) {
+                    Ok(result) => (kclvm_capi_status::Ok, result),
+                    Err(err) => (kclvm_capi_status::Error, err.to_string()),
+                },
+                Ok(Err(err)) => (kclvm_capi_status::Error, err.to_string()),
+                Err(err) => (kclvm_capi_status::Panic, kclvm_error::err_to_str(err)),
+            },
+            Err(err) => (
+                kclvm_capi_status::InvalidArgument,
+                format!("invalid arguments: {}", err),
+            ),
+        }
+    };
+    // The JSON strings never contain the nul bytes, which are removed from the messages.
+    let message = CString::new(message.replace('\0', "")).unwrap_or_default();
+    unsafe { *output = message.into_raw() };
+    status
+}
+
+macro_rules! capi_fn {
+    ($(#[$doc:meta])* $name:ident, $arg_name:ident, $serv_name:ident) => {
+        $(#[$doc])*
+        ///
+        /// # Safety
+        ///
+        /// `serv` must be created by `kclvm_service_new`, `args` must be a nul-terminated
+        /// JSON string, and the output string must be freed by [kclvm_capi_free_string].
+        #[no_mangle]
+        pub unsafe extern "C" fn $name(
+            serv: *const KclvmServiceImpl,
+            args: *const c_char,
+            output: *mut *mut c_char,
+        ) -> kclvm_capi_status {
+            unsafe { call_json::<$arg_name, _>(serv, args, output, KclvmServiceImpl::$serv_name) }
+        }
+    };
+}
+
+capi_fn!(
+    /// Execute the KCL program with the JSON [ExecProgramArgs], and the output is the
+    /// JSON [ExecProgramResult].
+    kclvm_capi_exec_program,
+    ExecProgramArgs,
+    exec_program
+);
+capi_fn!(
+    /// Get the KCL version, the supported APIs, formats and features of the service with
+    /// the JSON [GetCapabilitiesArgs], and the output is the JSON [GetCapabilitiesResult].
+    kclvm_capi_get_capabilities,
+    GetCapabilitiesArgs,
+    get_capabilities
+);
+capi_fn!(
+    /// Get the planned value, the inferred type and the definition position of a value path
+    /// with the JSON [GetValueByPathArgs], and the output is the JSON [GetValueByPathResult].
+    kclvm_capi_get_value_by_path,
+    GetValueByPathArgs,
+    get_value_by_path
+);
+capi_fn!(
+    /// Load the KCL package with the JSON [LoadPackageArgs], and the output is the JSON
+    /// [LoadPackageResult].
+    kclvm_capi_load_package,
+    LoadPackageArgs,
+    load_package
+);
+capi_fn!(
+    /// Override the KCL file with the JSON [OverrideFileArgs], and the output is the JSON
+    /// [OverrideFileResult].
+    kclvm_capi_override_file,
+    OverrideFileArgs,
+    override_file
+);
+capi_fn!(
+    /// Format the KCL code with the JSON [FormatCodeArgs], and the output is the JSON
+    /// [FormatCodeResult].
+    kclvm_capi_format_code,
+    FormatCodeArgs,
+    format_code
+);
+capi_fn!(
+    /// Format the KCL files under the path with the JSON [FormatPathArgs], and the output
+    /// is the JSON [FormatPathResult].
+    kclvm_capi_format_path,
+    FormatPathArgs,
+    format_path
+);
+capi_fn!(
+    /// Lint the KCL files with the JSON [LintPathArgs], and the output is the JSON
+    /// [LintPathResult].
+    kclvm_capi_lint_path,
+    LintPathArgs,
+    lint_path
+);
+capi_fn!(
+    /// Lint the KCL files or the source codes with the JSON [LintArgs], and the output is the
+    /// JSON [LintResult] with the structured lint results.
+    kclvm_capi_lint,
+    LintArgs,
+    lint
+);
+
+/// Execute the KCL program with the JSON [ExecProgramArgs] like [kclvm_capi_exec_program],
+/// and the JSON [ExecProgramEvent] of the progress events and the final result event are
+/// passed to the callback on the calling thread as soon as they happen.
+///
+/// # Safety
+///
+/// `serv` must be created by `kclvm_service_new`, `args` must be a nul-terminated JSON
+/// string, and the output string must be freed by [kclvm_capi_free_string]. The event
+/// string passed to the callback is only valid during the callback.
+#[no_mangle]
+pub unsafe extern "C" fn kclvm_capi_exec_program_stream(
+    serv: *const KclvmServiceImpl,
+    args: *const c_char,
+    callback: Option<kclvm_capi_event_callback>,
+    user_data: *mut c_void,
+    output: *mut *mut c_char,
+) -> kclvm_capi_status {
+    // The user data is passed back to the callback as is.
+    let user_data = user_data as usize;
+    let emit = move |event: ExecProgramEvent| {
+        if let Some(callback) = callback {
+            if let Ok(event) = serde_json::to_string(&event) {
+                if let Ok(event) = CString::new(event) {
+                    callback(event.as_ptr(), user_data as *mut c_void);
+                }
+            }
+        }
+    };
+    unsafe {
+        call_json::<ExecProgramArgs, _>(serv, args, output, move |serv, args| {
+            serv.exec_program_stream(args, emit)
+        })
+    }
+}
@@ -1,11 +1,12 @@
 use crate::gpyrpc::{
-    CliConfig, Error, KeyValuePair, LoadSettingsFilesResult, Message, Position, Scope, ScopeIndex,
-    Symbol, SymbolIndex,
+    CliConfig, Error, ExecProgramEvent, KeyValuePair, LoadSettingsFilesResult, Message, Position,
+    Scope, ScopeIndex, Symbol, SymbolIndex,
 };
 use crate::service::ty::kcl_ty_to_pb_ty;
 use kclvm_config::settings::SettingsFile;
 use kclvm_error::Diagnostic;
 use kclvm_loader::{ScopeInfo, SymbolInfo};
+use kclvm_runner::ProgressEvent;
 use kclvm_sema::core::{scope::ScopeRef, symbol::SymbolRef};
 
 pub(crate) trait IntoLoadSettingsFiles {
@@ -17,6 +18,10 @@ pub(crate) trait IntoError {
     fn into_error(self) -> Error;
 }
 
+pub(crate) trait IntoExecProgramEvent {
+    fn into_exec_program_event(self) -> ExecProgramEvent;
+}
+
 pub(crate) trait IntoSymbolIndex {
     fn into_symbol_index(self) -> SymbolIndex;
 }
@@ -90,6 +95,37 @@ impl IntoError for Diagnostic {
     }
 }
 
+impl IntoExecProgramEvent for ProgressEvent {
+    fn into_exec_program_event(self) -> ExecProgramEvent {
+        match self {
+            ProgressEvent::PhaseStarted { phase } => ExecProgramEvent {
+                kind: "phase_started".to_string(),
+                phase,
+                ..Default::default()
+            },
+            ProgressEvent::PhaseFinished {
+                phase,
+                wall_time_ms,
+            } => ExecProgramEvent {
+                kind: "phase_finished".to_string(),
+                phase,
+                wall_time_ms,
+                ..Default::default()
+            },
+            ProgressEvent::Diagnostic(diag) => ExecProgramEvent {
+                kind: "diagnostic".to_string(),
+                diagnostic: Some(diag.into_error()),
+                ..Default::default()
+            },
+            ProgressEvent::Log(record) => ExecProgramEvent {
+                kind: "log".to_string(),
+                log_message: record.message,
+                ..Default::default()
+            },
+        }
+    }
+}
+
 impl IntoSymbolIndex for SymbolRef {
     fn into_symbol_index(self) -> SymbolIndex {
         let (index, generation) = self.get_id().into_raw_parts();
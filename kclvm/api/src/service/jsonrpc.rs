@@ -2,20 +2,40 @@ use crate::gpyrpc::*;
 use crate::service::service_impl::KclvmServiceImpl;
 use core::fmt::Display;
 use jsonrpc_stdio_server::jsonrpc_core::{Error, ErrorCode, IoHandler, Params};
-use jsonrpc_stdio_server::ServerBuilder;
 use serde::Serialize;
+use std::panic::AssertUnwindSafe;
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::sync::mpsc::{unbounded_channel, UnboundedSender};
 const KCLVM_SERVER_ERROR_CODE: i64 = 0x4B434C; // the ASCII code of "KCL"
 
 /// Start a json rpc server via Stdin/Stdout
 #[tokio::main]
 pub async fn start_stdio_server() -> Result<(), anyhow::Error> {
+    // The responses and the notifications are sent to the output channel and written
+    // to the stdout line by line by a single writer, thus they never interleave.
+    let (output, mut output_rx) = unbounded_channel::<String>();
+    let writer = tokio::spawn(async move {
+        let mut stdout = tokio::io::stdout();
+        while let Some(line) = output_rx.recv().await {
+            stdout.write_all(line.as_bytes()).await?;
+            stdout.write_all(b"\n").await?;
+            stdout.flush().await?;
+        }
+        Ok::<(), std::io::Error>(())
+    });
     let mut io = IoHandler::default();
     // KclvmService
-    register_kclvm_service(&mut io);
+    register_kclvm_service(&mut io, output.clone());
     // BuiltinService
     register_builtin_service(&mut io);
-    let server = ServerBuilder::new(io).build();
-    server.await;
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+    while let Some(request) = lines.next_line().await? {
+        if let Some(response) = io.handle_request(&request).await {
+            output.send(response)?;
+        }
+    }
+    drop(output);
+    writer.await??;
     Ok(())
 }
 
@@ -58,7 +78,38 @@ where
     }
 }
 
-fn register_kclvm_service(io: &mut IoHandler) {
+/// The service sending the progress events of the streaming ExecProgram as the
+/// notifications to the output channel of the server before the response. The sender
+/// holds no state broken by the panics caught in the service.
+struct ExecProgramStreamService(KclvmServiceImpl, AssertUnwindSafe<UnboundedSender<String>>);
+
+impl ExecProgramStreamService {
+    fn exec_program_stream(&self, args: &ExecProgramArgs) -> anyhow::Result<ExecProgramEvent> {
+        let result = self.0.exec_program_stream(args, |event| {
+            // The final result event is sent as the response.
+            if event.kind != "result" {
+                send_notification(&self.1, "ExecProgramEvent", &event);
+            }
+        })?;
+        Ok(ExecProgramEvent {
+            kind: "result".to_string(),
+            result: Some(result),
+            ..Default::default()
+        })
+    }
+}
+
+/// Send the JSON-RPC notification to the output channel of the server.
+fn send_notification<T: Serialize>(output: &UnboundedSender<String>, method: &str, params: &T) {
+    let notification = serde_json::json!({
+        "jsonrpc": "2.0",
+        "method": method,
+        "params": params,
+    });
+    let _ = output.send(notification.to_string());
+}
+
+fn register_kclvm_service(io: &mut IoHandler, output: UnboundedSender<String>) {
     io.add_method("KclvmService.Ping", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: PingArgs = match params.parse() {
@@ -75,6 +126,14 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, get_version))
     });
+    io.add_method("KclvmService.GetCapabilities", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: GetCapabilitiesArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, get_capabilities))
+    });
     io.add_method("KclvmService.ParseFile", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: ParseFileArgs = match params.parse() {
@@ -115,6 +174,22 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, list_variables))
     });
+    io.add_method("KclvmService.GetValueByPath", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: GetValueByPathArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, get_value_by_path))
+    });
+    io.add_method("KclvmService.GetProgramGraph", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: GetProgramGraphArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, get_program_graph))
+    });
     io.add_method("KclvmService.ExecProgram", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: ExecProgramArgs = match params.parse() {
@@ -123,6 +198,25 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, exec_program))
     });
+    io.add_method("KclvmService.ExecProgramStream", move |params: Params| {
+        let stream_service = ExecProgramStreamService(
+            KclvmServiceImpl::default(),
+            AssertUnwindSafe(output.clone()),
+        );
+        let args: ExecProgramArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(stream_service, args, exec_program_stream))
+    });
+    io.add_method("KclvmService.DiffProgram", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: DiffProgramArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, diff_program))
+    });
     #[cfg(feature = "llvm")]
     io.add_method("KclvmService.BuildProgram", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
@@ -196,6 +290,14 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, lint_path))
     });
+    io.add_method("KclvmService.Lint", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: LintArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, lint))
+    });
     io.add_method("KclvmService.ValidateCode", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: ValidateCodeArgs = match params.parse() {
@@ -204,6 +306,14 @@ fn register_kclvm_service(io: &mut IoHandler) {
         };
         futures::future::ready(catch!(kclvm_service_impl, args, validate_code))
     });
+    io.add_method("KclvmService.CheckType", |params: Params| {
+        let kclvm_service_impl = KclvmServiceImpl::default();
+        let args: CheckTypeArgs = match params.parse() {
+            Ok(val) => val,
+            Err(err) => return futures::future::ready(Err(err)),
+        };
+        futures::future::ready(catch!(kclvm_service_impl, args, check_type))
+    });
     io.add_method("KclvmService.LoadSettingsFiles", |params: Params| {
         let kclvm_service_impl = KclvmServiceImpl::default();
         let args: LoadSettingsFilesArgs = match params.parse() {
@@ -257,9 +367,14 @@ fn register_builtin_service(io: &mut IoHandler) {
             method_name_list: vec![
                 "KclvmService.Ping".to_owned(),
                 "KclvmService.GetVersion".to_owned(),
+                "KclvmService.GetCapabilities".to_owned(),
                 "KclvmService.ParseFile".to_owned(),
                 "KclvmService.ParseProgram".to_owned(),
                 "KclvmService.ExecProgram".to_owned(),
+                "KclvmService.ExecProgramStream".to_owned(),
+                "KclvmService.DiffProgram".to_owned(),
+                "KclvmService.GetValueByPath".to_owned(),
+                "KclvmService.GetProgramGraph".to_owned(),
                 "KclvmService.BuildProgram".to_owned(),
                 "KclvmService.ExecArtifact".to_owned(),
                 "KclvmService.OverrideFile".to_owned(),
@@ -269,7 +384,9 @@ fn register_builtin_service(io: &mut IoHandler) {
                 "KclvmService.FormatCode".to_owned(),
                 "KclvmService.FormatPath".to_owned(),
                 "KclvmService.LintPath".to_owned(),
+                "KclvmService.Lint".to_owned(),
                 "KclvmService.ValidateCode".to_owned(),
+                "KclvmService.CheckType".to_owned(),
                 "KclvmService.LoadSettingsFiles".to_owned(),
                 "KclvmService.Rename".to_owned(),
                 "KclvmService.RenameCode".to_owned(),
@@ -1,4 +1,5 @@
 pub mod capi;
+pub mod ffi;
 pub(crate) mod into;
 #[cfg(not(target_arch = "wasm32"))]
 pub mod jsonrpc;
@@ -2,43 +2,55 @@ use std::collections::HashMap;
 use std::io::Write;
 use std::path::PathBuf;
 use std::string::String;
+use std::sync::Arc;
 
 use crate::gpyrpc::{self, *};
 
 use kcl_language_server::rename;
 use kclvm_ast::ast::SerializeProgram;
+use kclvm_config::lint::{load_lint_config, LintConfig};
 use kclvm_config::settings::build_settings_pathbuf;
+use kclvm_error::Position as KCLPos;
 use kclvm_loader::option::list_options;
 use kclvm_loader::{load_packages_with_cache, LoadPackageOptions};
 use kclvm_parser::entry::{canonicalize_input_file, get_normalized_k_files_from_paths};
 use kclvm_parser::load_program;
-use kclvm_parser::parse_single_file;
+use kclvm_parser::parse_expr_with_diagnostics;
+use kclvm_parser::parse_single_file_with_opts;
+use kclvm_parser::program_graph::get_program_graph;
 use kclvm_parser::KCLModuleCache;
 use kclvm_parser::LoadProgramOptions;
 use kclvm_parser::ParseSessionRef;
+use kclvm_query::definition::{find_value_definition, get_json_value_by_path, json_value_type};
 use kclvm_query::override_file;
+use kclvm_query::path::parse_value_path;
 use kclvm_query::query::CompilationOptions;
 use kclvm_query::query::{get_full_schema_type, get_full_schema_type_under_path};
 use kclvm_query::selector::{list_variables, ListOptions};
 use kclvm_query::GetSchemaOption;
+use kclvm_runner::diff::exec_program_diff;
 use kclvm_runner::exec_program;
 #[cfg(feature = "llvm")]
 use kclvm_runner::{build_program, exec_artifact};
+use kclvm_runner::{PlanFormat, ProgressEvent, ProgressListener};
 use kclvm_sema::core::global_state::GlobalState;
+use kclvm_sema::resolver::check_type::check_type;
+use kclvm_sema::resolver::resolve_program;
 use kclvm_sema::resolver::scope::KCLScopeCache;
 use kclvm_sema::resolver::Options;
 use kclvm_tools::format::{format, format_source, FormatOptions};
-use kclvm_tools::lint::lint_files;
+use kclvm_tools::lint::{lint_files, lint_files_with_rule_names, LintRegistry};
 use kclvm_tools::testing;
 use kclvm_tools::testing::TestRun;
 use kclvm_tools::vet::validator::validate;
+use kclvm_tools::vet::validator::validate_with_errors;
 use kclvm_tools::vet::validator::LoaderKind;
 use kclvm_tools::vet::validator::ValidateOption;
 use tempfile::NamedTempFile;
 
 use super::into::*;
-use super::ty::kcl_schema_ty_to_pb_ty;
-use super::util::{transform_exec_para, transform_str_para};
+use super::ty::{kcl_schema_ty_to_pb_ty, kcl_schema_ty_to_pb_ty_with_inherited_attrs};
+use super::util::{enabled_features, supported_apis, transform_exec_para, transform_str_para};
 
 /// Specific implementation of calling service
 #[derive(Debug, Clone, Default)]
@@ -65,6 +77,24 @@ impl From<&kclvm_query::selector::Variable> for Variable {
     }
 }
 
+/// The progress listener sending the progress events to the stream callback.
+struct ExecProgramEventListener<F>(Arc<F>);
+
+impl<F> std::fmt::Debug for ExecProgramEventListener<F> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("ExecProgramEventListener")
+    }
+}
+
+impl<F> ProgressListener for ExecProgramEventListener<F>
+where
+    F: Fn(ExecProgramEvent) + Send + Sync,
+{
+    fn on_event(&self, event: ProgressEvent) {
+        (self.0)(event.into_exec_program_event())
+    }
+}
+
 impl KclvmServiceImpl {
     /// Ping KclvmService, return the same value as the parameter
     ///
@@ -112,6 +142,45 @@ impl KclvmServiceImpl {
         })
     }
 
+    /// GetCapabilities KclvmService, return the KCL version, the supported APIs, output
+    /// formats, diagnostic formats and feature flags of the service.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    /// let serv = KclvmServiceImpl::default();
+    /// let args = &GetCapabilitiesArgs::default();
+    /// let result = serv.get_capabilities(args).unwrap();
+    /// assert!(result.apis.contains(&"KclvmService.ExecProgram".to_string()));
+    /// assert!(result.output_formats.contains(&"json".to_string()));
+    /// assert!(result.diagnostic_formats.contains(&"sarif".to_string()));
+    /// ```
+    pub fn get_capabilities(
+        &self,
+        _args: &GetCapabilitiesArgs,
+    ) -> anyhow::Result<GetCapabilitiesResult> {
+        Ok(GetCapabilitiesResult {
+            version: kclvm_version::VERSION.to_string(),
+            apis: supported_apis(),
+            output_formats: [
+                PlanFormat::Yaml,
+                PlanFormat::Json,
+                PlanFormat::Toml,
+                PlanFormat::Hcl,
+            ]
+            .iter()
+            .map(|format| format.as_str().to_string())
+            .collect(),
+            diagnostic_formats: ["human", "json", "sarif", "html", "markdown"]
+                .iter()
+                .map(|format| format.to_string())
+                .collect(),
+            features: enabled_features(),
+        })
+    }
+
     /// Parse KCL program with entry files.
     ///
     /// # Examples
@@ -163,7 +232,8 @@ impl KclvmServiceImpl {
     }
 
     /// Parse KCL single file to Module AST JSON string with import
-    /// dependencies and parse errors.
+    /// dependencies and parse errors, and the external packages are
+    /// used to resolve the import dependencies.
     ///
     /// # Examples
     ///
@@ -183,7 +253,19 @@ impl KclvmServiceImpl {
     /// ```
     pub fn parse_file(&self, args: &ParseFileArgs) -> anyhow::Result<ParseFileResult> {
         let file = canonicalize_input_file(&args.path, "");
-        let result = parse_single_file(&file, transform_str_para(&args.source))?;
+        let package_maps = args
+            .external_pkgs
+            .iter()
+            .map(|p| (p.pkg_name.to_string(), p.pkg_path.to_string()))
+            .collect();
+        let result = parse_single_file_with_opts(
+            &file,
+            transform_str_para(&args.source),
+            LoadProgramOptions {
+                package_maps,
+                ..Default::default()
+            },
+        )?;
         let ast_json = serde_json::to_string(&result.module)?;
 
         Ok(ParseFileResult {
@@ -434,6 +516,84 @@ impl KclvmServiceImpl {
         });
     }
 
+    /// Execute the KCL program and get the planned value of the path e.g.,
+    /// `app.containers[0].image`, its inferred type and the position of the statement
+    /// or the config entry defining it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let args = &GetValueByPathArgs {
+    ///     exec_args: Some(ExecProgramArgs {
+    ///         k_filename_list: vec!["main.k".to_string()],
+    ///         k_code_list: vec!["app = {containers = [{image = \"nginx\"}]}".to_string()],
+    ///         ..Default::default()
+    ///     }),
+    ///     path: "app.containers[0].image".to_string(),
+    /// };
+    /// let result = serv.get_value_by_path(args).unwrap();
+    /// assert_eq!(result.value, "\"nginx\"");
+    /// assert_eq!(result.type_name, "str");
+    /// let position = result.position.unwrap();
+    /// assert_eq!((position.line, position.column), (1, 22));
+    ///
+    /// let args = &GetValueByPathArgs {
+    ///     path: "app.containers[1]".to_string(),
+    ///     ..args.clone()
+    /// };
+    /// let error = serv.get_value_by_path(args).unwrap_err();
+    /// assert_eq!(error.to_string(), "the value of the path 'app.containers[1]' is not found");
+    /// ```
+    pub fn get_value_by_path(
+        &self,
+        args: &GetValueByPathArgs,
+    ) -> anyhow::Result<GetValueByPathResult> {
+        let path = parse_value_path(&args.path)?;
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let result = exec_program(ParseSessionRef::default(), &exec_args)?;
+        if !result.err_message.is_empty() {
+            return Err(anyhow::anyhow!(result.err_message));
+        }
+        let planned: serde_json::Value = serde_json::from_str(&result.json_result)?;
+        let value = get_json_value_by_path(&planned, &path)
+            .ok_or_else(|| anyhow::anyhow!("the value of the path '{}' is not found", args.path))?;
+        // Resolve the program again to find the definition and the inferred type.
+        let paths: Vec<&str> = exec_args
+            .k_filename_list
+            .iter()
+            .map(|p| p.as_str())
+            .collect();
+        let mut program = load_program(
+            ParseSessionRef::default(),
+            &paths,
+            Some(exec_args.get_load_program_options()),
+            None,
+        )?
+        .program;
+        let scope = resolve_program(&mut program);
+        let definition = find_value_definition(&program, &scope, &path);
+        let type_name = match &definition {
+            // The inferred types of the expressions e.g., the function calls may be `any`.
+            Some(definition) if !definition.ty.is_empty() && definition.ty != "any" => {
+                definition.ty.clone()
+            }
+            _ => json_value_type(value).to_string(),
+        };
+        Ok(GetValueByPathResult {
+            value: serde_json::to_string(value)?,
+            type_name,
+            position: definition.map(|definition| Position {
+                line: definition.line as i64,
+                column: definition.column as i64,
+                filename: definition.filename,
+            }),
+        })
+    }
+
     /// Execute KCL file with arguments and return the JSON/YAML result.
     ///
     /// **Note that it is not thread safe when the llvm feature is enabled.**
@@ -492,6 +652,106 @@ impl KclvmServiceImpl {
         })
     }
 
+    /// Execute KCL file with args like [KclvmServiceImpl::exec_program], and the progress
+    /// events e.g., the phases, the diagnostics and the log messages are passed to the
+    /// callback as soon as they happen, then the final result event.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    /// use std::sync::{Arc, Mutex};
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let args = &ExecProgramArgs {
+    ///     k_filename_list: vec!["file.k".to_string()],
+    ///     k_code_list: vec!["print(\"hello\")\nalice = {age = 18}".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let kinds = Arc::new(Mutex::new(vec![]));
+    /// let events = kinds.clone();
+    /// let exec_result = serv
+    ///     .exec_program_stream(args, move |event| events.lock().unwrap().push(event.kind))
+    ///     .unwrap();
+    /// assert_eq!(exec_result.yaml_result, "alice:\n  age: 18");
+    /// let kinds = kinds.lock().unwrap();
+    /// assert_eq!(kinds.first().unwrap(), "phase_started");
+    /// assert!(kinds.contains(&"log".to_string()));
+    /// assert_eq!(kinds.last().unwrap(), "result");
+    /// ```
+    pub fn exec_program_stream<F>(
+        &self,
+        args: &ExecProgramArgs,
+        emit: F,
+    ) -> anyhow::Result<ExecProgramResult>
+    where
+        F: Fn(ExecProgramEvent) + Send + Sync + 'static,
+    {
+        let emit = Arc::new(emit);
+        let mut exec_args = transform_exec_para(&Some(args.clone()), self.plugin_agent)?;
+        exec_args.progress_listener = Some(Arc::new(ExecProgramEventListener(emit.clone())));
+        let sess = ParseSessionRef::default();
+        let result = exec_program(sess, &exec_args)?;
+        let result = ExecProgramResult {
+            json_result: result.json_result,
+            yaml_result: result.yaml_result,
+            log_message: result.log_message,
+            err_message: result.err_message,
+        };
+        emit(ExecProgramEvent {
+            kind: "result".to_string(),
+            result: Some(result.clone()),
+            ..Default::default()
+        });
+        Ok(result)
+    }
+
+    /// Execute two KCL programs, which are two entrypoints or the same entrypoint with
+    /// different options, and return the structured diff of the planned values.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let args = &DiffProgramArgs {
+    ///     old_args: Some(ExecProgramArgs {
+    ///         k_filename_list: vec!["file.k".to_string()],
+    ///         k_code_list: vec!["app = {replicas = 1}".to_string()],
+    ///         ..Default::default()
+    ///     }),
+    ///     new_args: Some(ExecProgramArgs {
+    ///         k_filename_list: vec!["file.k".to_string()],
+    ///         k_code_list: vec!["app = {replicas = 2}".to_string()],
+    ///         ..Default::default()
+    ///     }),
+    /// };
+    /// let result = serv.diff_program(args).unwrap();
+    /// assert_eq!(result.patch[0].path, "/app/replicas");
+    /// assert_eq!(result.patch[0].value, "2");
+    /// assert_eq!(result.tree_diff, "app:\n  ~ replicas: 1 => 2\n");
+    /// ```
+    pub fn diff_program(&self, args: &DiffProgramArgs) -> anyhow::Result<DiffProgramResult> {
+        let old_args = transform_exec_para(&args.old_args, self.plugin_agent)?;
+        let new_args = transform_exec_para(&args.new_args, self.plugin_agent)?;
+        let result = exec_program_diff(ParseSessionRef::default(), &old_args, &new_args)?;
+        Ok(DiffProgramResult {
+            patch: result
+                .patch
+                .into_iter()
+                .map(|op| JsonPatchOperation {
+                    op: op.op,
+                    path: op.path,
+                    value: op.value.map(|v| v.to_string()).unwrap_or_default(),
+                })
+                .collect(),
+            tree_diff: result.tree_diff,
+        })
+    }
+
     /// Build the KCL program to an artifact.
     ///
     /// # Examples
@@ -639,6 +899,23 @@ impl KclvmServiceImpl {
     ///     ..Default::default()
     /// }).unwrap();
     /// assert_eq!(result.schema_type_mapping.len(), 1);
+    ///
+    /// // Include the attributes inherited from the base schemas and the mixins.
+    /// let file = Path::new(".").join("src").join("testdata").join("get_schema_ty_inherited").join("main.k");
+    /// let result = serv.get_schema_type_mapping(&GetSchemaTypeMappingArgs {
+    ///     exec_args: Some(ExecProgramArgs {
+    ///         k_filename_list: vec![file.canonicalize().unwrap().display().to_string()],
+    ///         ..Default::default()
+    ///     }),
+    ///     schema_name: "child".to_string(),
+    ///     include_inherited: true,
+    /// }).unwrap();
+    /// let child = &result.schema_type_mapping["child"];
+    /// assert_eq!(child.properties.len(), 4);
+    /// assert_eq!(child.properties["name"].defined_in, "Base");
+    /// assert_eq!(child.properties["labels"].defined_in, "LabelMixin");
+    /// assert_eq!(child.properties["kind"].defined_in, "Child");
+    /// assert_eq!(child.required, vec!["name", "kind"]);
     /// ```
     pub fn get_schema_type_mapping(
         &self,
@@ -658,7 +935,12 @@ impl KclvmServiceImpl {
                 get_schema_opts: GetSchemaOption::default(),
             },
         )? {
-            type_mapping.insert(k, kcl_schema_ty_to_pb_ty(&schema_ty));
+            let ty = if args.include_inherited {
+                kcl_schema_ty_to_pb_ty_with_inherited_attrs(&schema_ty)
+            } else {
+                kcl_schema_ty_to_pb_ty(&schema_ty)
+            };
+            type_mapping.insert(k, ty);
         }
 
         Ok(GetSchemaTypeMappingResult {
@@ -724,7 +1006,11 @@ impl KclvmServiceImpl {
         )? {
             let mut tys = vec![];
             for schema_ty in schema_tys {
-                tys.push(kcl_schema_ty_to_pb_ty(&schema_ty));
+                tys.push(if args.include_inherited {
+                    kcl_schema_ty_to_pb_ty_with_inherited_attrs(&schema_ty)
+                } else {
+                    kcl_schema_ty_to_pb_ty(&schema_ty)
+                });
             }
             type_mapping.insert(k, gpyrpc::SchemaTypes { schema_type: tys });
         }
@@ -767,6 +1053,7 @@ impl KclvmServiceImpl {
                 is_stdout: false,
                 recursively: false,
                 omit_errors: true,
+                ..Default::default()
             },
         )?;
         Ok(FormatCodeResult {
@@ -804,6 +1091,7 @@ impl KclvmServiceImpl {
                 recursively,
                 is_stdout: false,
                 omit_errors: true,
+                ..Default::default()
             },
         )?;
         Ok(FormatPathResult { changed_paths })
@@ -846,6 +1134,93 @@ impl KclvmServiceImpl {
         Ok(LintPathResult { results })
     }
 
+    /// Service for KCL Lint API, lint the files or the source codes with the lint rules
+    /// configured in the work directory, and return the structured lint results with the
+    /// rule names, the levels, the ranges and the fixes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let result = serv.lint(&LintArgs {
+    ///     paths: vec!["main.k".to_string()],
+    ///     sources: vec!["import math\na = 1".to_string()],
+    ///     ..Default::default()
+    /// }).unwrap();
+    /// assert_eq!(result.results.len(), 1);
+    /// let diagnostic = &result.results[0];
+    /// assert_eq!(diagnostic.rule, "unused-import");
+    /// assert_eq!(diagnostic.level, "warning");
+    /// assert_eq!(diagnostic.message, "Module 'math' imported but unused");
+    /// assert_eq!(diagnostic.start.as_ref().unwrap().line, 1);
+    /// ```
+    pub fn lint(&self, args: &LintArgs) -> anyhow::Result<LintResult> {
+        let config = if args.work_dir.is_empty() {
+            LintConfig::default()
+        } else {
+            load_lint_config(&args.work_dir)?
+        };
+        let opts = LoadProgramOptions {
+            work_dir: args.work_dir.clone(),
+            k_code_list: args.sources.clone(),
+            package_maps: args
+                .external_pkgs
+                .iter()
+                .map(|p| (p.pkg_name.clone(), p.pkg_path.clone()))
+                .collect(),
+            ..Default::default()
+        };
+        let results = lint_files_with_rule_names(
+            &args.paths.iter().map(|p| p.as_str()).collect::<Vec<&str>>(),
+            Some(opts),
+            &LintRegistry::default(),
+            &config,
+        );
+        let position = |pos: &kclvm_error::Position| Position {
+            line: pos.line as i64,
+            column: pos.column.unwrap_or_default() as i64,
+            filename: pos.filename.clone(),
+        };
+        Ok(LintResult {
+            results: results
+                .into_iter()
+                .map(|result| {
+                    let diag = result.diagnostic;
+                    let message = diag.messages.first();
+                    LintDiagnostic {
+                        rule: result.rule.unwrap_or_default().to_string(),
+                        level: diag.level.to_string(),
+                        code: match &diag.code {
+                            Some(kclvm_error::DiagnosticId::Error(kind)) => kind.code(),
+                            Some(kclvm_error::DiagnosticId::Warning(kind)) => kind.code(),
+                            _ => String::new(),
+                        },
+                        message: message.map(|m| m.message.clone()).unwrap_or_default(),
+                        note: message.and_then(|m| m.note.clone()).unwrap_or_default(),
+                        start: message.map(|m| position(&m.range.0)),
+                        end: message.map(|m| position(&m.range.1)),
+                        fixes: diag
+                            .fixes
+                            .iter()
+                            .map(|fix| LintFix {
+                                edit: Some(TextEdit {
+                                    filename: fix.range.0.filename.clone(),
+                                    start: Some(position(&fix.range.0)),
+                                    end: Some(position(&fix.range.1)),
+                                    new_text: fix.replacement.clone(),
+                                }),
+                                applicability: format!("{:?}", fix.applicability),
+                            })
+                            .collect(),
+                    }
+                })
+                .collect(),
+        })
+    }
+
     /// Service for validating the data string using the schema code string, when the parameter
     /// `schema` is omitted, use the first schema appeared in the kcl code.
     ///
@@ -873,11 +1248,26 @@ impl KclvmServiceImpl {
     /// }
     /// "#.to_string();
     /// let result = serv.validate_code(&ValidateCodeArgs {
-    ///     code,
+    ///     code: code.clone(),
     ///     data,
     ///     ..Default::default()
     /// }).unwrap();
     /// assert_eq!(result.success, true);
+    ///
+    /// let data = r#"
+    /// {
+    ///     "name": "Alice",
+    ///     "age": 130
+    /// }
+    /// "#.to_string();
+    /// let result = serv.validate_code(&ValidateCodeArgs {
+    ///     code,
+    ///     data,
+    ///     ..Default::default()
+    /// }).unwrap();
+    /// assert_eq!(result.success, false);
+    /// assert_eq!(result.errors[0].path, "$");
+    /// assert_eq!(result.errors[0].rule, "0 < age < 120");
     /// ```
     pub fn validate_code(&self, args: &ValidateCodeArgs) -> anyhow::Result<ValidateCodeResult> {
         let mut file = NamedTempFile::new()?;
@@ -889,24 +1279,177 @@ impl KclvmServiceImpl {
             args.datafile.clone()
         };
 
-        let (success, err_message) = match validate(ValidateOption::new(
-            transform_str_para(&args.schema),
-            args.attribute_name.clone(),
-            file_path,
-            match args.format.to_lowercase().as_str() {
-                "yaml" | "yml" => LoaderKind::YAML,
-                "json" => LoaderKind::JSON,
-                _ => LoaderKind::JSON,
-            },
-            transform_str_para(&args.file),
-            transform_str_para(&args.code),
-        )) {
+        let option = || {
+            ValidateOption::new(
+                transform_str_para(&args.schema),
+                args.attribute_name.clone(),
+                file_path.clone(),
+                match args.format.to_lowercase().as_str() {
+                    "yaml" | "yml" => LoaderKind::YAML,
+                    "json" => LoaderKind::JSON,
+                    _ => LoaderKind::JSON,
+                },
+                transform_str_para(&args.file),
+                transform_str_para(&args.code),
+            )
+        };
+        let (success, err_message) = match validate(option()) {
             Ok(success) => (success, "".to_string()),
             Err(err) => (false, err.to_string()),
         };
+        // Trace the validation errors back to the data document.
+        let errors = if success {
+            vec![]
+        } else {
+            validate_with_errors(option())
+                .unwrap_or_default()
+                .into_iter()
+                .map(|err| ValidationError {
+                    path: err.path,
+                    position: Some(Position {
+                        line: err.line as i64,
+                        column: err.column as i64,
+                        filename: err.filename,
+                    }),
+                    rule: err.rule,
+                    message: err.message,
+                })
+                .collect()
+        };
         Ok(ValidateCodeResult {
             success,
             err_message,
+            errors,
+        })
+    }
+
+    /// Service for checking the expression code against the schema type in the program
+    /// without executing the program, and returns the parse and type errors of the code.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let exec_args = ExecProgramArgs {
+    ///     k_filename_list: vec!["./src/testdata/test.k".to_string()],
+    ///     ..Default::default()
+    /// };
+    /// let result = serv.check_type(&CheckTypeArgs {
+    ///     exec_args: Some(exec_args.clone()),
+    ///     code: "{age = 18}".to_string(),
+    ///     schema: "Person".to_string(),
+    /// }).unwrap();
+    /// assert!(result.success);
+    /// let result = serv.check_type(&CheckTypeArgs {
+    ///     exec_args: Some(exec_args),
+    ///     code: "{age = \"18\"}".to_string(),
+    ///     schema: "Person".to_string(),
+    /// }).unwrap();
+    /// assert!(!result.success);
+    /// assert_eq!(result.errors[0].messages[0].pos.as_ref().unwrap().filename, "<check_type>");
+    /// ```
+    pub fn check_type(&self, args: &CheckTypeArgs) -> anyhow::Result<CheckTypeResult> {
+        let (expr, parse_errors) = parse_expr_with_diagnostics(&args.code, "<check_type>");
+        let expr = match expr {
+            Some(expr) if parse_errors.is_empty() => expr,
+            _ => {
+                return Ok(CheckTypeResult {
+                    success: false,
+                    errors: parse_errors.into_iter().map(|e| e.into_error()).collect(),
+                })
+            }
+        };
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let paths: Vec<&str> = exec_args
+            .k_filename_list
+            .iter()
+            .map(|p| p.as_str())
+            .collect();
+        let program = load_program(
+            ParseSessionRef::default(),
+            &paths,
+            Some(exec_args.get_load_program_options()),
+            None,
+        )?
+        .program;
+        let errors = match check_type(&program, expr, &args.schema) {
+            Ok(()) => vec![],
+            Err(errors) => errors,
+        };
+        Ok(CheckTypeResult {
+            success: errors.is_empty(),
+            errors: errors.into_iter().map(|e| e.into_error()).collect(),
+        })
+    }
+
+    /// Service for getting the file graph and the package graph of the program with the
+    /// nodes, the edges, the import cycles and the external packages.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_api::service::service_impl::KclvmServiceImpl;
+    /// use kclvm_api::gpyrpc::*;
+    ///
+    /// let serv = KclvmServiceImpl::default();
+    /// let args = &GetProgramGraphArgs {
+    ///     exec_args: Some(ExecProgramArgs {
+    ///         k_filename_list: vec!["main.k".to_string()],
+    ///         k_code_list: vec!["import math\na = 1".to_string()],
+    ///         ..Default::default()
+    ///     }),
+    /// };
+    /// let result = serv.get_program_graph(args).unwrap();
+    /// let pkg_graph = result.pkg_graph.unwrap();
+    /// assert_eq!(pkg_graph.nodes, vec!["__main__"]);
+    /// assert!(pkg_graph.edges.is_empty());
+    /// assert_eq!(result.pkg_graph_dot, "digraph {\n    \"__main__\";\n}\n");
+    /// ```
+    pub fn get_program_graph(
+        &self,
+        args: &GetProgramGraphArgs,
+    ) -> anyhow::Result<GetProgramGraphResult> {
+        let exec_args = transform_exec_para(&args.exec_args, self.plugin_agent)?;
+        let paths: Vec<&str> = exec_args
+            .k_filename_list
+            .iter()
+            .map(|p| p.as_str())
+            .collect();
+        let graph = get_program_graph(&paths, Some(exec_args.get_load_program_options()))?;
+        let into_graph = |graph: &kclvm_parser::program_graph::DependencyGraph| DependencyGraph {
+            nodes: graph.nodes.clone(),
+            edges: graph
+                .edges
+                .iter()
+                .map(|(from, to)| DependencyEdge {
+                    from: from.clone(),
+                    to: to.clone(),
+                })
+                .collect(),
+            cycles: graph
+                .cycles
+                .iter()
+                .map(|nodes| DependencyCycle {
+                    nodes: nodes.clone(),
+                })
+                .collect(),
+        };
+        Ok(GetProgramGraphResult {
+            file_graph: Some(into_graph(&graph.file_graph)),
+            pkg_graph: Some(into_graph(&graph.pkg_graph)),
+            external_pkgs: graph
+                .external_pkgs
+                .iter()
+                .map(|(pkg_name, pkg_path)| ExternalPkg {
+                    pkg_name: pkg_name.clone(),
+                    pkg_path: pkg_path.clone(),
+                })
+                .collect(),
+            file_graph_dot: graph.file_graph.to_dot(),
+            pkg_graph_dot: graph.pkg_graph.to_dot(),
         })
     }
 
@@ -950,7 +1493,10 @@ impl KclvmServiceImpl {
     }
 
     /// Service for renaming all the occurrences of the target symbol in the files. This API will rewrite files if they contain symbols to be renamed.
-    /// return the file paths got changed.
+    /// return the file paths got changed and the text edits.
+    ///
+    /// The target symbol is selected by the symbol path, or the position of the symbol or one of its
+    /// references when the position is set, and the files are not rewritten when `dry_run` is set.
     ///
     /// # Examples
     ///
@@ -972,8 +1518,27 @@ impl KclvmServiceImpl {
     ///     symbol_path: "a".to_string(),
     ///     file_paths: vec!["./src/testdata/rename_doc/main.k".to_string()],
     ///     new_name: "a2".to_string(),
+    ///     ..Default::default()
     /// }).unwrap();
     /// assert_eq!(result.changed_files.len(), 1);
+    /// assert_eq!(result.edits.len(), 2);
+    ///
+    /// // Rename the symbol referenced at `b = a2` without rewriting the file.
+    /// let result = serv.rename(&RenameArgs {
+    ///     package_root: "./src/testdata/rename_doc".to_string(),
+    ///     file_paths: vec!["./src/testdata/rename_doc/main.k".to_string()],
+    ///     new_name: "a3".to_string(),
+    ///     position: Some(Position {
+    ///         line: 2,
+    ///         column: 4,
+    ///         filename: "./src/testdata/rename_doc/main.k".to_string(),
+    ///     }),
+    ///     dry_run: true,
+    ///     ..Default::default()
+    /// }).unwrap();
+    /// assert_eq!(result.edits.len(), 2);
+    /// assert_eq!(result.edits[1].new_text, "a3");
+    /// # assert_eq!(fs::read_to_string(path.clone()).unwrap(), "a2 = 1\nb = a2");
     ///
     /// # // after test, restore template from .bak
     /// # fs::remove_file(path.clone()).unwrap();
@@ -983,19 +1548,57 @@ impl KclvmServiceImpl {
             .canonicalize()?
             .display()
             .to_string();
-        let symbol_path = args.symbol_path.clone();
         let mut file_paths = vec![];
         for path in args.file_paths.iter() {
             file_paths.push(PathBuf::from(path).canonicalize()?.display().to_string());
         }
         let new_name = args.new_name.clone();
+        let pos = match &args.position {
+            Some(position) if !position.filename.is_empty() => Some(KCLPos {
+                filename: PathBuf::from(&position.filename)
+                    .canonicalize()?
+                    .display()
+                    .to_string(),
+                line: position.line as u64,
+                column: Some(position.column as u64),
+            }),
+            _ => None,
+        };
+        let target = match &pos {
+            Some(pos) => rename::RenameTarget::Position(pos),
+            None => rename::RenameTarget::SymbolPath(&args.symbol_path),
+        };
+        let changes =
+            rename::rename_on_file(&pkg_root, target, &file_paths, new_name, args.dry_run)?;
+        let mut changed_files: Vec<String> = changes.keys().cloned().collect();
+        changed_files.sort();
+        let mut edits: Vec<TextEdit> = changes
+            .into_iter()
+            .flat_map(|(filename, edits)| {
+                // The LSP line numbers are 0 based, and the KCL line numbers are 1 based.
+                edits.into_iter().map(move |edit| TextEdit {
+                    filename: filename.clone(),
+                    start: Some(Position {
+                        line: edit.range.start.line as i64 + 1,
+                        column: edit.range.start.character as i64,
+                        filename: filename.clone(),
+                    }),
+                    end: Some(Position {
+                        line: edit.range.end.line as i64 + 1,
+                        column: edit.range.end.character as i64,
+                        filename: filename.clone(),
+                    }),
+                    new_text: edit.new_text,
+                })
+            })
+            .collect();
+        edits.sort_by_key(|edit| {
+            let start = edit.start.clone().unwrap_or_default();
+            (edit.filename.clone(), start.line, start.column)
+        });
         Ok(RenameResult {
-            changed_files: rename::rename_symbol_on_file(
-                &pkg_root,
-                &symbol_path,
-                &file_paths,
-                new_name,
-            )?,
+            changed_files,
+            edits,
         })
     }
 
@@ -1029,7 +1632,8 @@ impl KclvmServiceImpl {
         })
     }
 
-    /// Service for the testing tool.
+    /// Service for the testing tool, which discovers the test lambdas in the `*_test.k` files,
+    /// runs them and returns the results with the durations and the failure diagnostics.
     ///
     /// # Examples
     ///
@@ -1047,6 +1651,9 @@ impl KclvmServiceImpl {
     /// assert!(result.info[0].error.is_empty());
     /// // Failed case
     /// assert!(result.info[1].error.is_empty());
+    /// assert!(result.info[1].diagnostics.is_empty());
+    /// // The position of the test case definition
+    /// assert_eq!(result.info[1].position.as_ref().unwrap().line, 5);
     /// ```
     pub fn test(&self, args: &TestArgs) -> anyhow::Result<TestResult> {
         let mut result = TestResult::default();
@@ -1055,12 +1662,14 @@ impl KclvmServiceImpl {
             exec_args,
             run_regexp: args.run_regexp.clone(),
             fail_fast: args.fail_fast,
+            update_snapshots: args.update_snapshots,
         };
         for pkg in &args.pkg_list {
             let suites = testing::load_test_suites(pkg, &opts)?;
             for suite in &suites {
                 let suite_result = suite.run(&opts)?;
                 for (name, info) in &suite_result.info {
+                    let case = suite.cases.get(name);
                     result.info.push(TestCaseInfo {
                         name: name.clone(),
                         error: info
@@ -1070,6 +1679,16 @@ impl KclvmServiceImpl {
                             .unwrap_or_default(),
                         duration: info.duration.as_micros() as u64,
                         log_message: info.log_message.clone(),
+                        position: case.map(|case| Position {
+                            line: case.line as i64,
+                            column: case.column as i64,
+                            filename: case.filename.clone(),
+                        }),
+                        diagnostics: info
+                            .diagnostic
+                            .iter()
+                            .map(|diag| diag.clone().into_error())
+                            .collect(),
                     })
                 }
             }
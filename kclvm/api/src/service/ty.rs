@@ -1,6 +1,6 @@
-use crate::gpyrpc::{Decorator, Example, KclType};
+use crate::gpyrpc::{Decorator, Deprecation, Example, KclType};
 use indexmap::IndexSet;
-use kclvm_sema::ty::{DictType, SchemaType, Type};
+use kclvm_sema::ty::{DictType, SchemaAttr, SchemaType, Type};
 use std::collections::HashMap;
 
 /// Convert the kcl sematic type to the kcl protobuf type.
@@ -55,10 +55,63 @@ pub(crate) fn kcl_schema_ty_to_pb_ty(schema_ty: &SchemaType) -> KclType {
             .base
             .as_ref()
             .map(|base| Box::new(kcl_schema_ty_to_pb_ty(&**base))),
+        deprecation: schema_ty
+            .get_deprecation()
+            .map(kcl_deprecation_to_pb_deprecation),
         ..Default::default()
     }
 }
 
+/// Convert the kcl sematic type to the kcl protobuf type, and the schema properties
+/// include the attributes inherited from the base schemas and the mixins.
+pub(crate) fn kcl_schema_ty_to_pb_ty_with_inherited_attrs(schema_ty: &SchemaType) -> KclType {
+    let attrs = schema_ty.get_inherited_attrs();
+    let mut properties = HashMap::new();
+    let mut required = vec![];
+    for (line, (key, inherited)) in attrs.iter().enumerate() {
+        let mut ty = kcl_schema_attr_to_pb_ty(&inherited.attr);
+        ty.line = line as i32 + 1;
+        ty.defined_in = inherited.defined_in.clone();
+        properties.insert(key.to_string(), ty);
+        if !inherited.attr.is_optional {
+            required.push(key.to_string());
+        }
+    }
+    KclType {
+        properties,
+        required,
+        ..kcl_schema_ty_to_pb_ty(schema_ty)
+    }
+}
+
+fn kcl_schema_attr_to_pb_ty(attr: &SchemaAttr) -> KclType {
+    let mut ty = kcl_ty_to_pb_ty(&attr.ty);
+    ty.description = attr.doc.clone().unwrap_or_default();
+    ty.decorators = attr
+        .decorators
+        .iter()
+        .map(|d| Decorator {
+            name: d.name.clone(),
+            arguments: d.arguments.clone(),
+            keywords: d.keywords.clone(),
+        })
+        .collect();
+    ty.default = attr.default.clone().unwrap_or_default();
+    ty.deprecation = attr
+        .get_deprecation()
+        .map(kcl_deprecation_to_pb_deprecation);
+    ty
+}
+
+fn kcl_deprecation_to_pb_deprecation(deprecation: &kclvm_sema::ty::Deprecation) -> Deprecation {
+    Deprecation {
+        since: deprecation.since.clone(),
+        reason: deprecation.reason.clone(),
+        replacement: deprecation.replacement.clone(),
+        strict: deprecation.strict,
+    }
+}
+
 fn get_schema_ty_examples(schema_ty: &SchemaType) -> HashMap<String, Example> {
     let mut examples = HashMap::new();
     for (key, example) in &schema_ty.examples {
@@ -80,19 +133,8 @@ fn get_schema_ty_attributes(schema_ty: &SchemaType, line: &mut i32) -> HashMap<S
     };
     let mut type_mapping = HashMap::new();
     for (key, attr) in &schema_ty.attrs {
-        let mut ty = kcl_ty_to_pb_ty(&attr.ty);
+        let mut ty = kcl_schema_attr_to_pb_ty(attr);
         ty.line = *line;
-        ty.description = attr.doc.clone().unwrap_or_default();
-        ty.decorators = attr
-            .decorators
-            .iter()
-            .map(|d| Decorator {
-                name: d.name.clone(),
-                arguments: d.arguments.clone(),
-                keywords: d.keywords.clone(),
-            })
-            .collect();
-        ty.default = attr.default.clone().unwrap_or_default();
         type_mapping.insert(key.to_string(), ty);
         *line += 1
     }
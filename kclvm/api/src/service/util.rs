@@ -25,3 +25,53 @@ pub(crate) fn transform_exec_para(
     args.plugin_agent = plugin_agent;
     Ok(args)
 }
+
+/// Returns the names of the KclvmService APIs supported by the current build, the
+/// native backend APIs require the `llvm` feature.
+pub(crate) fn supported_apis() -> Vec<String> {
+    let mut apis = vec![
+        "Ping",
+        "GetVersion",
+        "GetCapabilities",
+        "ParseFile",
+        "ParseProgram",
+        "LoadPackage",
+        "ListOptions",
+        "ListVariables",
+        "GetValueByPath",
+        "GetProgramGraph",
+        "ExecProgram",
+        "ExecProgramStream",
+        "DiffProgram",
+        "OverrideFile",
+        "GetSchemaTypeMapping",
+        "FormatCode",
+        "FormatPath",
+        "LintPath",
+        "Lint",
+        "ValidateCode",
+        "CheckType",
+        "LoadSettingsFiles",
+        "Rename",
+        "RenameCode",
+        "Test",
+    ];
+    if cfg!(feature = "llvm") {
+        apis.extend(["BuildProgram", "ExecArtifact"]);
+    }
+    if cfg!(not(target_arch = "wasm32")) {
+        apis.push("UpdateDependencies");
+    }
+    apis.iter()
+        .map(|api| format!("KclvmService.{api}"))
+        .collect()
+}
+
+/// Returns the feature flags enabled in the current build.
+pub(crate) fn enabled_features() -> Vec<String> {
+    let mut features = vec![];
+    if cfg!(feature = "llvm") {
+        features.push("llvm".to_string());
+    }
+    features
+}
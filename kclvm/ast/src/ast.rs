@@ -687,6 +687,9 @@ pub struct ImportStmt {
 ///
 /// protocol ProtocolExample:
 ///     attr: int
+///
+/// schema Box<T>:
+///     value: T
 /// ```
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 pub struct SchemaStmt {
@@ -696,6 +699,9 @@ pub struct SchemaStmt {
     pub for_host_name: Option<NodeRef<Identifier>>,
     pub is_mixin: bool,
     pub is_protocol: bool,
+    /// The type parameters of the generic schema e.g., `T` in `schema Box<T>`.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub type_params: Vec<NodeRef<String>>,
     pub args: Option<NodeRef<Arguments>>,
     pub mixins: Vec<NodeRef<Identifier>>,
     pub body: Vec<NodeRef<Stmt>>,
@@ -805,6 +811,16 @@ impl SchemaAttr {
     pub fn is_ident_attr(&self) -> bool {
         self.name.end_column - self.name.column <= self.name.node.chars().count() as u64
     }
+
+    /// Whether the attribute has the decorator with the name e.g., `secret` for `@secret`.
+    pub fn has_decorator(&self, name: &str) -> bool {
+        self.decorators
+            .iter()
+            .any(|decorator| match &decorator.node.func.node {
+                Expr::Identifier(ident) => ident.names.len() == 1 && ident.names[0].node == name,
+                _ => false,
+            })
+    }
 }
 
 /// RuleStmt, e.g.
@@ -1831,6 +1847,7 @@ pub enum Type {
     Union(UnionType),
     Literal(LiteralType),
     Function(FunctionType),
+    Generic(GenericType),
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
@@ -1863,6 +1880,13 @@ pub struct UnionType {
     pub type_elements: Vec<NodeRef<Type>>,
 }
 
+/// GenericType is the generic schema type with the type arguments, e.g. `Box<str>`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct GenericType {
+    pub name: Identifier,
+    pub args: Vec<NodeRef<Type>>,
+}
+
 #[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
 #[serde(tag = "type", content = "value")]
 pub enum LiteralType {
@@ -1967,6 +1991,17 @@ impl ToString for Type {
                         to_str(&ret.node, w);
                     }
                 }
+                Type::Generic(x) => {
+                    w.push_str(&x.name.get_name());
+                    w.push('<');
+                    for (i, arg) in x.args.iter().enumerate() {
+                        if i > 0 {
+                            w.push_str(", ");
+                        }
+                        to_str(&arg.node, w);
+                    }
+                    w.push('>');
+                }
             }
         }
 
@@ -1976,6 +2011,42 @@ impl ToString for Type {
     }
 }
 
+impl Type {
+    /// Erase the type arguments of the generic schema types e.g., `Box<str>` to `Box`,
+    /// and the type parameters in `type_params` to the any type, because the generic
+    /// types are only checked by the resolver.
+    pub fn erase_generics(&mut self, type_params: &[String]) {
+        match self {
+            Type::Generic(generic_ty) => *self = Type::Named(generic_ty.name.clone()),
+            Type::Named(identifier)
+                if identifier.names.len() == 1
+                    && type_params.contains(&identifier.names[0].node) =>
+            {
+                *self = Type::Any
+            }
+            Type::List(list_ty) => {
+                if let Some(ty) = &mut list_ty.inner_type {
+                    ty.node.erase_generics(type_params)
+                }
+            }
+            Type::Dict(dict_ty) => {
+                if let Some(ty) = &mut dict_ty.key_type {
+                    ty.node.erase_generics(type_params)
+                }
+                if let Some(ty) = &mut dict_ty.value_type {
+                    ty.node.erase_generics(type_params)
+                }
+            }
+            Type::Union(union_ty) => {
+                for ty in &mut union_ty.type_elements {
+                    ty.node.erase_generics(type_params)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
 impl From<String> for Type {
     /// Build a named type from the string.
     fn from(value: String) -> Self {
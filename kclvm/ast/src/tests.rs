@@ -264,6 +264,7 @@ fn gen_schema_stmt(count: i32) -> Vec<NodeRef<ast::Stmt>> {
             for_host_name: None,
             is_mixin: false,
             is_protocol: false,
+            type_params: vec![],
             args: None,
             mixins: vec![],
             body: vec![],
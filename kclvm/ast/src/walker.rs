@@ -327,6 +327,13 @@ pub trait MutSelfMutWalker<'ctx> {
                     .iter_mut()
                     .for_each(|ty| self.walk_type(&mut ty.node));
             }
+            ast::Type::Generic(generic_ty) => {
+                self.walk_identifier(&mut generic_ty.name);
+                generic_ty
+                    .args
+                    .iter_mut()
+                    .for_each(|ty| self.walk_type(&mut ty.node));
+            }
             _ => {}
         }
     }
@@ -31,6 +31,14 @@ pub struct Config {
     pub indent_len: usize,
     pub use_spaces: bool,
     pub write_comments: bool,
+    /// The preferred quote style of the string literals.
+    pub quote_style: QuoteStyle,
+    /// The max line width, and the one-line lists and configs exceeding it are printed
+    /// in multiple lines. 0 denotes no limit.
+    pub max_line_width: usize,
+    /// Print the lists and configs with a trailing comma after the last item in the
+    /// source in multiple lines, which requires the source set by [Printer::set_source].
+    pub magic_trailing_comma: bool,
 }
 
 impl Default for Config {
@@ -40,10 +48,24 @@ impl Default for Config {
             indent_len: 4,
             use_spaces: true,
             write_comments: true,
+            quote_style: QuoteStyle::Preserve,
+            max_line_width: 0,
+            magic_trailing_comma: false,
         }
     }
 }
 
+/// The preferred quote style of the string literals, and the strings containing any
+/// quote are printed as is.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Keep the quotes written in the source.
+    #[default]
+    Preserve,
+    Double,
+    Single,
+}
+
 #[derive(Copy, Clone)]
 pub struct NoHook;
 
@@ -70,6 +92,21 @@ pub struct Printer<'p> {
     pub hook: &'p (dyn PrinterHook + 'p),
     /// Last AST expr/stmt line, default is 0.
     last_ast_line: u64,
+    /// The source lines used to find the magic trailing commas.
+    source_lines: Vec<&'p str>,
+    /// Whether to print the next list or config in multiple lines.
+    force_multiline: bool,
+    /// The end line of the next list or config, which is used to keep the comments
+    /// at the end of it.
+    collection_end_line: u64,
+}
+
+/// The printer state saved before trying to print an expression in one line.
+pub(crate) struct Checkpoint {
+    out_len: usize,
+    indent: usize,
+    comments: VecDeque<ast::NodeRef<ast::Comment>>,
+    last_ast_line: u64,
 }
 
 impl Default for Printer<'_> {
@@ -82,6 +119,9 @@ impl Default for Printer<'_> {
             comments: Default::default(),
             import_spec: Default::default(),
             last_ast_line: Default::default(),
+            source_lines: Default::default(),
+            force_multiline: Default::default(),
+            collection_end_line: Default::default(),
         }
     }
 }
@@ -96,9 +136,20 @@ impl<'p> Printer<'p> {
             import_spec: IndexMap::default(),
             hook,
             last_ast_line: 0,
+            source_lines: vec![],
+            force_multiline: false,
+            collection_end_line: 0,
         }
     }
 
+    /// Set the source of the printed AST, which is used by the options depending on the
+    /// source text e.g., [Config::magic_trailing_comma]. With the source, the inline
+    /// comments are kept at the end of the lines, the comments at the end of the lists
+    /// and configs are kept inside them, and the blank lines around the comments are kept.
+    pub fn set_source(&mut self, source: &'p str) {
+        self.source_lines = source.lines().collect();
+    }
+
     // --------------------------
     // Write functions
     // --------------------------
@@ -196,8 +247,12 @@ impl<'p> Printer<'p> {
     pub fn write_module(&mut self, module: &ast::Module) {
         self.walk_module(module);
         while let Some(comment) = self.comments.pop_front() {
-            self.writeln(&comment.node.text);
-            self.fill("");
+            if self.is_inline_comment(&comment) {
+                self.write_inline_comment(&comment.node.text);
+            } else {
+                self.writeln(&comment.node.text);
+                self.fill("");
+            }
         }
     }
 
@@ -206,15 +261,98 @@ impl<'p> Printer<'p> {
         if !self.cfg.write_comments {
             return false;
         }
-        let mut index = None;
-        for (i, comment) in self.comments.iter().enumerate() {
-            if comment.line <= node.line {
-                index = Some(i);
-            } else {
+        self.comments
+            .front()
+            .map_or(false, |comment| self.is_comment_before_node(comment, node))
+    }
+
+    /// Whether the comment is printed before the node. With the source, the comments
+    /// after the start of the node on the same line are printed at the end of the line,
+    /// otherwise they are printed before the node.
+    fn is_comment_before_node<T>(
+        &self,
+        comment: &ast::NodeRef<ast::Comment>,
+        node: &ast::NodeRef<T>,
+    ) -> bool {
+        if self.source_lines.is_empty() {
+            comment.line <= node.line
+        } else {
+            (comment.line, comment.column) < (node.line, node.column)
+        }
+    }
+
+    /// Whether the comment follows the code on the same line in the source e.g.,
+    /// `a = 1  # comment`.
+    fn is_inline_comment(&self, comment: &ast::NodeRef<ast::Comment>) -> bool {
+        match self
+            .source_lines
+            .get((comment.line as usize).wrapping_sub(1))
+        {
+            Some(line) => line
+                .chars()
+                .take(comment.column as usize)
+                .any(|c| !c.is_whitespace()),
+            None => false,
+        }
+    }
+
+    /// Write the inline comment at the end of the last written line.
+    fn write_inline_comment(&mut self, text: &str) {
+        let end = self.out.trim_end().len();
+        self.out
+            .insert_str(end, &format!("{}{}", WHITESPACE.repeat(2), text));
+    }
+
+    /// Write the comment before the node at the line, and the blank line between the
+    /// comment and the next comment or node is kept when the source is set.
+    fn write_comment(&mut self, comment: &ast::NodeRef<ast::Comment>, node_line: u64) {
+        if self.is_inline_comment(comment) {
+            self.write_inline_comment(&comment.node.text);
+            return;
+        }
+        self.write(&comment.node.text);
+        self.write_newline_without_fill();
+        let next_line = self
+            .comments
+            .front()
+            .map_or(node_line, |next| next.line.min(node_line));
+        if !self.source_lines.is_empty() && next_line >= comment.line + 2 {
+            self.write_newline_without_fill();
+        }
+        self.fill("");
+    }
+
+    /// Returns the line of the first comment printed on its own line before the node.
+    pub(crate) fn first_comment_line_before_node<T>(&self, node: &ast::NodeRef<T>) -> Option<u64> {
+        if !self.cfg.write_comments {
+            return None;
+        }
+        self.comments
+            .iter()
+            .take_while(|comment| self.is_comment_before_node(comment, node))
+            .find(|comment| !self.is_inline_comment(comment))
+            .map(|comment| comment.line)
+    }
+
+    /// Write the comments before the line e.g., the comments at the end of a multiline
+    /// list or config, which are kept inside it when the source is set.
+    pub(crate) fn write_comments_before_line(&mut self, line: u64) {
+        if !self.cfg.write_comments || self.source_lines.is_empty() {
+            return;
+        }
+        while let Some(comment) = self.comments.front() {
+            if comment.line >= line {
                 break;
             }
+            let text = comment.node.text.clone();
+            if self.is_inline_comment(comment) {
+                self.write_inline_comment(&text);
+            } else {
+                self.write_newline();
+                self.write(&text);
+            }
+            self.comments.pop_front();
         }
-        index.is_some()
     }
 
     /// Print ast comments.
@@ -231,24 +369,12 @@ impl<'p> Printer<'p> {
         }
         if node.line > self.last_ast_line {
             self.last_ast_line = node.line;
-            let mut index = None;
-            for (i, comment) in self.comments.iter().enumerate() {
-                if comment.line <= node.line {
-                    index = Some(i);
-                } else {
+            while let Some(comment) = self.comments.front() {
+                if !self.is_comment_before_node(comment, node) {
                     break;
                 }
-            }
-            if let Some(index) = index {
-                let mut count = index as isize;
-                while count >= 0 {
-                    match self.comments.pop_front() {
-                        Some(comment) => {
-                            self.writeln(&comment.node.text);
-                        }
-                        None => break,
-                    }
-                    count -= 1;
+                if let Some(comment) = self.comments.pop_front() {
+                    self.write_comment(&comment, node.line);
                 }
             }
         }
@@ -267,6 +393,104 @@ impl<'p> Printer<'p> {
     pub fn leave(&mut self) {
         self.indent -= 1;
     }
+
+    // --------------------------
+    // Line width functions
+    // --------------------------
+
+    /// Save the printer state to try printing an expression in one line.
+    pub(crate) fn checkpoint(&self) -> Checkpoint {
+        Checkpoint {
+            out_len: self.out.len(),
+            indent: self.indent,
+            comments: self.comments.clone(),
+            last_ast_line: self.last_ast_line,
+        }
+    }
+
+    /// Restore the printer state and discard the output since the checkpoint.
+    pub(crate) fn rollback(&mut self, checkpoint: Checkpoint) {
+        self.out.truncate(checkpoint.out_len);
+        self.indent = checkpoint.indent;
+        self.comments = checkpoint.comments;
+        self.last_ast_line = checkpoint.last_ast_line;
+    }
+
+    /// Whether the lines written since the checkpoint exceed the max line width.
+    pub(crate) fn exceeds_line_width(&self, checkpoint: &Checkpoint) -> bool {
+        if self.cfg.max_line_width == 0 {
+            return false;
+        }
+        let line_start = self.out[..checkpoint.out_len]
+            .rfind(NEWLINE)
+            .map(|i| i + 1)
+            .unwrap_or(0);
+        self.out[line_start..]
+            .lines()
+            .any(|line| line.chars().count() > self.cfg.max_line_width)
+    }
+
+    /// Whether the list or config node has a trailing comma after the last item in the
+    /// source e.g., `[1, 2,]`.
+    pub(crate) fn has_magic_trailing_comma<T>(&self, node: &ast::NodeRef<T>) -> bool {
+        if node.line == 0 || node.end_line as usize > self.source_lines.len() {
+            return false;
+        }
+        for line in (node.line..=node.end_line).rev() {
+            let text: Vec<char> = self.source_lines[line as usize - 1].chars().collect();
+            // Skip the open and the close brackets.
+            let start = if line == node.line {
+                node.column as usize + 1
+            } else {
+                0
+            };
+            let end = if line == node.end_line {
+                (node.end_column as usize).saturating_sub(1).min(text.len())
+            } else {
+                text.len()
+            };
+            if start >= end {
+                continue;
+            }
+            if let Some(c) = text[start..end].iter().rev().find(|c| !c.is_whitespace()) {
+                return *c == ',';
+            }
+        }
+        false
+    }
+
+    /// Requote the raw string literal with the preferred quote style.
+    pub(crate) fn requote(&self, raw_value: &str) -> String {
+        let (from, to) = match self.cfg.quote_style {
+            QuoteStyle::Preserve => return raw_value.to_string(),
+            QuoteStyle::Double => ('\'', '"'),
+            QuoteStyle::Single => ('"', '\''),
+        };
+        // The string prefix e.g., `r` of the raw strings.
+        let quote_start = match raw_value.find(from) {
+            Some(index) => index,
+            None => return raw_value.to_string(),
+        };
+        let (prefix, quoted) = raw_value.split_at(quote_start);
+        if !prefix.chars().all(|c| c.is_ascii_alphabetic()) {
+            return raw_value.to_string();
+        }
+        let long_quote = from.to_string().repeat(3);
+        let quote_len = if quoted.len() >= 6 && quoted.starts_with(&long_quote) {
+            3
+        } else {
+            1
+        };
+        if quoted.len() < quote_len * 2 || !quoted.ends_with(from) {
+            return raw_value.to_string();
+        }
+        let content = &quoted[quote_len..quoted.len() - quote_len];
+        if content.contains(from) || content.contains(to) {
+            return raw_value.to_string();
+        }
+        let quote = to.to_string().repeat(quote_len);
+        format!("{prefix}{quote}{content}{quote}")
+    }
 }
 
 /// Print AST to string. The default format is according to the KCL code style defined here: https://kcl-lang.io/docs/reference/lang/spec/codestyle
@@ -276,6 +500,15 @@ pub fn print_ast_module(module: &Module) -> String {
     printer.out
 }
 
+/// Print AST to string with the printer config, and the source of the AST is used by
+/// the options depending on the source text e.g., [Config::magic_trailing_comma].
+pub fn print_ast_module_with_config(module: &Module, source: &str, cfg: Config) -> String {
+    let mut printer = Printer::new(cfg, &NoHook);
+    printer.set_source(source);
+    printer.write_module(module);
+    printer.out
+}
+
 /// Print AST to string
 pub fn print_ast_node(node: ASTNode) -> String {
     let mut printer = Printer::default();
@@ -180,6 +180,15 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
             self.write("schema ");
         }
         self.write(&schema_stmt.name.node);
+        if !schema_stmt.type_params.is_empty() {
+            self.write("<");
+            interleave!(
+                || self.write(", "),
+                |type_param: &ast::NodeRef<String>| self.write(&type_param.node),
+                schema_stmt.type_params
+            );
+            self.write(">");
+        }
         if let Some(args) = &schema_stmt.args {
             self.write("[");
             self.walk_arguments(&args.node);
@@ -460,6 +469,8 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
     }
 
     fn walk_list_expr(&mut self, list_expr: &'ctx ast::ListExpr) -> Self::Result {
+        let force_multiline = std::mem::take(&mut self.force_multiline);
+        let end_line = std::mem::take(&mut self.collection_end_line);
         let mut line_set = list_expr
             .elts
             .iter()
@@ -477,32 +488,23 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
                 .map(|e| self.has_comments_on_node(e))
                 .all(|r| r);
         // When there are comments in the configuration block, print them as multiline configurations.
-        let mut in_one_line = line_set.len() <= 1 && !has_comment;
+        let mut in_one_line = line_set.len() <= 1 && !has_comment && !force_multiline;
         if let Some(elt) = list_expr.elts.first() {
             if let ast::Expr::ListIfItem(_) = &elt.node {
                 in_one_line = false;
             }
         }
-        self.write_token(TokenKind::OpenDelim(DelimToken::Bracket));
-        if !in_one_line {
-            self.write_indentation(Indentation::IndentWithNewline);
-        }
-        interleave!(
-            || if in_one_line {
-                self.write(COMMA_WHITESPACE);
-            } else {
-                self.write_newline();
-            },
-            |elt| {
-                self.write_comments_before_node(elt);
-                self.expr(elt);
-            },
-            list_expr.elts
-        );
-        if !in_one_line {
-            self.write_indentation(Indentation::DedentWithNewline);
+        // Print the list in multiple lines when it exceeds the max line width.
+        if in_one_line && !list_expr.elts.is_empty() && self.cfg.max_line_width > 0 {
+            let checkpoint = self.checkpoint();
+            self.write_list_items(list_expr, true, end_line);
+            if !self.exceeds_line_width(&checkpoint) {
+                return;
+            }
+            self.rollback(checkpoint);
+            in_one_line = false;
         }
-        self.write_token(TokenKind::CloseDelim(DelimToken::Bracket));
+        self.write_list_items(list_expr, in_one_line, end_line);
     }
 
     fn walk_list_comp(&mut self, list_comp: &'ctx ast::ListComp) -> Self::Result {
@@ -642,6 +644,8 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
     }
 
     fn walk_config_expr(&mut self, config_expr: &'ctx ast::ConfigExpr) -> Self::Result {
+        let force_multiline = std::mem::take(&mut self.force_multiline);
+        let end_line = std::mem::take(&mut self.collection_end_line);
         let mut line_set: HashSet<u64> = config_expr
             .items
             .iter()
@@ -659,7 +663,7 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
                 .map(|item| self.has_comments_on_node(item))
                 .all(|r| r);
         // When there are comments in the configuration block, print them as multiline configurations.
-        let mut in_one_line = line_set.len() <= 1 && !has_comment;
+        let mut in_one_line = line_set.len() <= 1 && !has_comment && !force_multiline;
         // When there are complex configuration blocks in the configuration block, print them as multiline configurations.
         if config_expr.items.len() == 1 && in_one_line {
             if let Some(item) = config_expr.items.first() {
@@ -671,25 +675,17 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
                 }
             }
         }
-        self.write_token(TokenKind::OpenDelim(DelimToken::Brace));
-        if !config_expr.items.is_empty() {
-            if !in_one_line {
-                self.write_indentation(Indentation::IndentWithNewline);
-            }
-            interleave!(
-                || if in_one_line {
-                    self.write(COMMA_WHITESPACE);
-                } else {
-                    self.write_newline();
-                },
-                |entry: &ast::NodeRef<ast::ConfigEntry>| self.write_entry(entry),
-                config_expr.items
-            );
-            if !in_one_line {
-                self.write_indentation(Indentation::DedentWithNewline);
+        // Print the config in multiple lines when it exceeds the max line width.
+        if in_one_line && !config_expr.items.is_empty() && self.cfg.max_line_width > 0 {
+            let checkpoint = self.checkpoint();
+            self.write_config_items(config_expr, true, end_line);
+            if !self.exceeds_line_width(&checkpoint) {
+                return;
             }
+            self.rollback(checkpoint);
+            in_one_line = false;
         }
-        self.write_token(TokenKind::CloseDelim(DelimToken::Brace));
+        self.write_config_items(config_expr, in_one_line, end_line);
     }
 
     fn walk_check_expr(&mut self, check_expr: &'ctx ast::CheckExpr) -> Self::Result {
@@ -812,7 +808,7 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
 
     fn walk_string_lit(&mut self, string_lit: &'ctx ast::StringLit) -> Self::Result {
         if !string_lit.raw_value.is_empty() {
-            self.write(&string_lit.raw_value)
+            self.write(&self.requote(&string_lit.raw_value))
         } else {
             self.write(&if string_lit.is_long_string {
                 format!("\"\"\"{}\"\"\"", string_lit.value.replace('\"', "\\\""))
@@ -832,7 +828,7 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
 
     fn walk_joined_string(&mut self, joined_string: &'ctx ast::JoinedString) -> Self::Result {
         if !joined_string.raw_value.is_empty() {
-            self.write(&joined_string.raw_value)
+            self.write(&self.requote(&joined_string.raw_value))
         } else {
             let quote_str = if joined_string.is_long_string {
                 "\"\"\""
@@ -872,6 +868,62 @@ impl<'p, 'ctx> MutSelfTypedResultWalker<'ctx> for Printer<'p> {
 }
 
 impl<'p> Printer<'p> {
+    /// Print the list items in one line or multiple lines, and the comments before the
+    /// end line are kept inside the multiline list.
+    fn write_list_items(&mut self, list_expr: &ast::ListExpr, in_one_line: bool, end_line: u64) {
+        self.write_token(TokenKind::OpenDelim(DelimToken::Bracket));
+        if !in_one_line {
+            self.write_indentation(Indentation::IndentWithNewline);
+        }
+        interleave!(
+            || if in_one_line {
+                self.write(COMMA_WHITESPACE);
+            } else {
+                self.write_newline();
+            },
+            |elt| {
+                self.write_comments_before_node(elt);
+                self.expr(elt);
+            },
+            list_expr.elts
+        );
+        if !in_one_line {
+            self.write_comments_before_line(end_line);
+            self.write_indentation(Indentation::DedentWithNewline);
+        }
+        self.write_token(TokenKind::CloseDelim(DelimToken::Bracket));
+    }
+
+    /// Print the config entries in one line or multiple lines, and the comments before
+    /// the end line are kept inside the multiline config.
+    fn write_config_items(
+        &mut self,
+        config_expr: &ast::ConfigExpr,
+        in_one_line: bool,
+        end_line: u64,
+    ) {
+        self.write_token(TokenKind::OpenDelim(DelimToken::Brace));
+        if !config_expr.items.is_empty() {
+            if !in_one_line {
+                self.write_indentation(Indentation::IndentWithNewline);
+            }
+            interleave!(
+                || if in_one_line {
+                    self.write(COMMA_WHITESPACE);
+                } else {
+                    self.write_newline();
+                },
+                |entry: &ast::NodeRef<ast::ConfigEntry>| self.write_entry(entry),
+                config_expr.items
+            );
+            if !in_one_line {
+                self.write_comments_before_line(end_line);
+                self.write_indentation(Indentation::DedentWithNewline);
+            }
+        }
+        self.write_token(TokenKind::CloseDelim(DelimToken::Brace));
+    }
+
     pub fn write_args_and_kwargs(
         &mut self,
         args: &[ast::NodeRef<ast::Expr>],
@@ -963,6 +1015,11 @@ impl<'p> Printer<'p> {
     pub fn expr(&mut self, expr: &ast::NodeRef<ast::Expr>) {
         self.hook.pre(self, super::ASTNode::Expr(expr));
         self.update_last_ast_line(expr);
+        if matches!(expr.node, ast::Expr::List(_) | ast::Expr::Config(_)) {
+            self.force_multiline =
+                self.cfg.magic_trailing_comma && self.has_magic_trailing_comma(expr);
+            self.collection_end_line = expr.end_line;
+        }
         self.walk_expr(&expr.node);
         self.hook.post(self, super::ASTNode::Expr(expr));
     }
@@ -993,9 +1050,17 @@ impl<'p> Printer<'p> {
             // Do not format out user-reserved blank lines: which does not mean that to preserve all user-written blank lines.
             // For situations where there are more than two blank lines, we only keep one blank line.
             let need_newline = if let Some(prev_stmt) = prev_stmt {
-                stmt.line > 0
-                    && stmt.line >= prev_stmt.end_line + 2
-                    && !self.has_comments_on_node(stmt)
+                if self.source_lines.is_empty() {
+                    stmt.line > 0
+                        && stmt.line >= prev_stmt.end_line + 2
+                        && !self.has_comments_on_node(stmt)
+                } else {
+                    // Keep the blank line before the comments of the statement.
+                    let line = self
+                        .first_comment_line_before_node(stmt)
+                        .unwrap_or(stmt.line);
+                    stmt.line > 0 && line >= prev_stmt.end_line + 2
+                }
             } else {
                 false
             };
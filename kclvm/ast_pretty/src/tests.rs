@@ -9,6 +9,7 @@ const FILE_OUTPUT_SUFFIX: &str = ".output";
 const TEST_CASES: &[&str] = &[
     "arguments",
     "empty",
+    "generic",
     "if_stmt",
     "import",
     "unary",
@@ -0,0 +1,18 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_error::explain::{codes, explain};
+use std::io::Write;
+
+/// Run the KCL explain command, which shows the explanation of the error or warning
+/// code, or lists all the codes when the code is not given.
+pub fn explain_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    match matches.get_one::<String>("code") {
+        Some(code) => writeln!(writer, "{}", explain(code)?)?,
+        None => {
+            for code in codes() {
+                writeln!(writer, "{}", code)?;
+            }
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,15 @@
+use anyhow::Result;
+use clap::ArgMatches;
+use kclvm_runner::ArtifactInfo;
+use std::io::Write;
+
+/// Run the KCL inspect command, which shows the metadata embedded in the built artifact
+/// without loading it.
+pub fn inspect_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()> {
+    let artifact = matches
+        .get_one::<String>("artifact")
+        .ok_or(anyhow::anyhow!("the artifact path is required"))?;
+    let info = ArtifactInfo::from_path(artifact)?;
+    writeln!(writer, "{}", info.to_json_string()?)?;
+    Ok(())
+}
@@ -3,6 +3,8 @@
 #[macro_use]
 extern crate clap;
 
+pub mod explain;
+pub mod inspect;
 pub mod run;
 pub mod settings;
 pub(crate) mod util;
@@ -15,6 +17,8 @@ use clap::{ArgAction, Command};
 use std::io;
 
 use anyhow::Result;
+use explain::explain_command;
+use inspect::inspect_command;
 use run::run_command;
 
 /// Run the KCL main command.
@@ -23,6 +27,8 @@ pub fn main(args: &[&str]) -> Result<()> {
     // Sub commands
     match matches.subcommand() {
         Some(("run", sub_matches)) => run_command(sub_matches, &mut io::stdout()),
+        Some(("explain", sub_matches)) => explain_command(sub_matches, &mut io::stdout()),
+        Some(("inspect", sub_matches)) => inspect_command(sub_matches, &mut io::stdout()),
         Some(("version", _)) => {
             println!("{}", kclvm_version::get_version_info());
             Ok(())
@@ -52,13 +58,52 @@ pub fn app() -> Command {
             .arg(arg!(sort_keys: -k --sort_keys "Sort result keys"))
             .arg(arg!(show_hidden: -H --show_hidden "Display hidden attributes"))
             .arg(arg!(fast_eval: -K --fast_eval "Use the fast evaluation mode"))
+            .arg(arg!(big_int: --big_int "Promote the ints overflowing 64 bits to the arbitrary-precision ints"))
             .arg(arg!(arguments: -D --argument <arguments> ... "Specify the top-level argument").num_args(1..))
             .arg(arg!(path_selector: -S --path_selector <path_selector> ... "Specify the path selector").num_args(1..))
             .arg(arg!(overrides: -O --overrides <overrides> ... "Specify the configuration override path and value").num_args(1..))
             .arg(arg!(target: --target <target> "Specify the target type"))
             .arg(arg!(recursive: -R --recursive "Compile the files directory recursively"))
+            .arg(arg!(diagnostic_format: --"diagnostic-format" <diagnostic_format> "Specify the diagnostic output format").value_parser(["human", "json", "sarif", "html", "markdown"]))
+            .arg(arg!(decimal_places: --decimal_places <decimal_places> "Round the float values to the decimal places in the outputs").value_parser(value_parser!(u32)))
+            .arg(arg!(timeout_ms: --timeout_ms <timeout_ms> "Abort the evaluation after the timeout in milliseconds").value_parser(value_parser!(u64)))
+            .arg(arg!(max_call_depth: --max_call_depth <max_call_depth> "Specify the maximum depth of the lambda, schema and rule calls").value_parser(value_parser!(usize)))
+            .arg(arg!(max_allocations: --max_allocations <max_allocations> "Specify the maximum number of the allocated list and dict items").value_parser(value_parser!(usize)))
+            .arg(arg!(random_seed: --random_seed <random_seed> "Specify the seed of the generated random values").value_parser(value_parser!(u64)))
+            .arg(arg!(show_secrets: --show_secrets "Display the secret values instead of masking them"))
+            .arg(arg!(externalize_secrets: --externalize_secrets "Move the secret values to a separate secrets document"))
+            .arg(arg!(output_format: --output_format <output_format> "Specify the output format").value_parser(["yaml", "json", "toml", "hcl"]))
+            .arg(arg!(output_dir: --output_dir <output_dir> "Write each document or top-level config to its own file under the directory"))
+            .arg(arg!(filename_template: --filename_template <filename_template> "Specify the filename template of the files under the output directory"))
+            .arg(arg!(key_order: --key_order <key_order> "Specify the key order of the output").value_parser(["declaration", "alphabetical", "schema"]))
+            .arg(arg!(backend: --backend <backend> "Specify the execution backend").value_parser(["auto", "interpreter", "native"]))
+            .arg(arg!(no_cache: --"no-cache" "Compile all the packages without using the compiled object cache"))
+            .arg(arg!(profile: --profile "Print the wall time and peak memory of the execution phases to the stderr"))
+            .arg(arg!(watch: --watch "Watch the source files and run the program again on every change"))
+            .arg(arg!(dump_dir: --dump_dir <dump_dir> "Specify the directory to dump the intermediate representations"))
+            .arg(arg!(dump_ast: --dump_ast "Dump the resolved AST in JSON to the dump directory"))
+            .arg(arg!(dump_llvm_ir: --dump_llvm_ir "Dump the LLVM IR of each package to the dump directory"))
+            .arg(arg!(dump_lib: --dump_lib "Dump the linked library to the dump directory"))
+            .arg(arg!(option_files: --option_files <option_files> ... "Specify the YAML or JSON files of the top-level options").num_args(1..))
+            .arg(arg!(option_env_prefix: --option_env_prefix <option_env_prefix> "Specify the prefix of the environment variables of the top-level options"))
+            .arg(arg!(provenance: --provenance <provenance> "Annotate the schema instances in the output with the schema type and source location").value_parser(["none", "comment", "sidecar"]))
+            .arg(arg!(secret_paths: --secret_paths <secret_paths> ... "Specify the key paths of the secret values").num_args(1..))
+            .arg(arg!(max_errors: --max_errors <max_errors> "Specify the maximum number of the reported errors").value_parser(value_parser!(usize)))
+            .arg(arg!(locale: --locale <locale> "Specify the locale of the diagnostics").value_parser(["en-US", "zh-CN"]))
+            .arg(arg!(color: --color <color> "Specify when to use colors in the diagnostics").value_parser(["auto", "always", "never"]))
+            .arg(arg!(path_prefix_map: --"path-prefix-map" <path_prefix_map> ... "Remap the path prefix FROM to TO in the outputs, in the format FROM=TO").num_args(1..))
             .arg(arg!(package_map: -E --external <package_map> ... "Mapping of package name and path where the package is located").num_args(1..)),
         )
+    .subcommand(
+        Command::new("explain")
+            .about("Show the explanation of an error or warning code")
+            .arg(arg!([code] "Specify the error or warning code, e.g., E2G22")),
+    )
+    .subcommand(
+        Command::new("inspect")
+            .about("Show the metadata of a built artifact")
+            .arg(arg!(<artifact> "Specify the artifact path")),
+    )
     .subcommand(Command::new("server").about("Start a rpc server for APIs"))
     .subcommand(Command::new("version").about("Show the KCL version"))
 }
@@ -1,9 +1,16 @@
 use anyhow::Result;
 use clap::ArgMatches;
+use kclvm_error::format::{format_error_message, FormattedDiagnostics};
 use kclvm_error::StringError;
 use kclvm_parser::ParseSession;
-use kclvm_runner::exec_program;
+#[cfg(not(target_arch = "wasm32"))]
+use kclvm_parser::ParseSessionRef;
+use kclvm_runner::{exec_program, ExecProgramArgs};
+#[cfg(not(target_arch = "wasm32"))]
+use kclvm_runner::{watch_program, ExecProgramResult};
 use std::io::Write;
+#[cfg(not(target_arch = "wasm32"))]
+use std::ops::ControlFlow;
 use std::sync::Arc;
 
 use crate::settings::must_build_settings;
@@ -14,7 +21,13 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
     let settings = must_build_settings(matches);
     let output = settings.output();
     let sess = Arc::new(ParseSession::default());
-    match exec_program(sess.clone(), &settings.try_into()?) {
+    let args: ExecProgramArgs = settings.try_into()?;
+    #[cfg(not(target_arch = "wasm32"))]
+    if matches.get_flag("watch") {
+        return watch_command(sess, &args, output, writer);
+    }
+    let format = args.diagnostic_format;
+    match exec_program(sess.clone(), &args) {
         Ok(result) => {
             // Output log message
             if !result.log_message.is_empty() {
@@ -22,10 +35,28 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
             }
             // Output execute error message
             if !result.err_message.is_empty() {
-                if !sess.0.diag_handler.has_errors()? {
-                    sess.0.add_err(StringError(result.err_message))?;
+                if format.is_structured() {
+                    let output = format_error_message(&result.err_message, format)?;
+                    return abort_with_output(writer, &output);
+                }
+                if !sess.session.diag_handler.has_errors()? {
+                    sess.session.add_err(StringError(result.err_message))?;
+                }
+                sess.session.emit_stashed_diagnostics_and_abort()?;
+            }
+            // The provenance sidecar is written next to the output file, or to the
+            // stderr to keep the stdout output unchanged.
+            if !result.provenance_result.is_empty() {
+                match &output {
+                    Some(o) => {
+                        std::fs::write(format!("{o}.provenance.json"), &result.provenance_result)?
+                    }
+                    None => eprintln!("{}", result.provenance_result),
                 }
-                sess.0.emit_stashed_diagnostics_and_abort()?;
+            }
+            // The profile table is written to the stderr as well.
+            if let Some(profile) = &result.profile {
+                eprint!("{}", profile.to_table_string());
             }
             if !result.yaml_result.is_empty() {
                 match output {
@@ -38,11 +69,93 @@ pub fn run_command<W: Write>(matches: &ArgMatches, writer: &mut W) -> Result<()>
         }
         // Other error message
         Err(msg) => {
-            if !sess.0.diag_handler.has_errors()? {
-                sess.0.add_err(StringError(msg.to_string()))?;
+            if format.is_structured() {
+                let output = match msg.downcast_ref::<FormattedDiagnostics>() {
+                    Some(diags) => diags.0.clone(),
+                    None => format_error_message(&msg.to_string(), format)?,
+                };
+                return abort_with_output(writer, &output);
+            }
+            if !sess.session.diag_handler.has_errors()? {
+                sess.session.add_err(StringError(msg.to_string()))?;
             }
-            sess.0.emit_stashed_diagnostics_and_abort()?;
+            sess.session.emit_stashed_diagnostics_and_abort()?;
         }
     }
     Ok(())
 }
+
+/// Run the program on every change of the source files until it is interrupted. The
+/// errors are written to the stderr instead of aborting, thus the watch goes on after
+/// the errors are fixed.
+#[cfg(not(target_arch = "wasm32"))]
+fn watch_command<W: Write>(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    output: Option<String>,
+    writer: &mut W,
+) -> Result<()> {
+    let mut write_result = Ok(());
+    watch_program(sess, args, |result| {
+        write_result = match result {
+            Ok(result) => write_watch_result(&result, &output, writer),
+            Err(err) => {
+                eprintln!("{}", err);
+                Ok(())
+            }
+        };
+        if write_result.is_ok() {
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })?;
+    write_result
+}
+
+/// Write the result of a watched execution.
+#[cfg(not(target_arch = "wasm32"))]
+fn write_watch_result<W: Write>(
+    result: &ExecProgramResult,
+    output: &Option<String>,
+    writer: &mut W,
+) -> Result<()> {
+    if !result.log_message.is_empty() {
+        write!(writer, "{}", result.log_message)?;
+    }
+    if !result.err_message.is_empty() {
+        eprintln!("{}", result.err_message);
+        return Ok(());
+    }
+    if let Some(profile) = &result.profile {
+        eprint!("{}", profile.to_table_string());
+    }
+    if !result.yaml_result.is_empty() {
+        match output {
+            Some(o) => std::fs::write(o, &result.yaml_result)?,
+            None => writeln!(writer, "{}", result.yaml_result)?,
+        }
+    }
+    writer.flush()?;
+    Ok(())
+}
+
+/// The error of the run command whose structured diagnostics output has already been
+/// written, thus the caller only exits with the error code without printing it again.
+#[derive(Debug)]
+pub struct OutputWrittenError(pub String);
+
+impl std::fmt::Display for OutputWrittenError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for OutputWrittenError {}
+
+/// Write the structured diagnostics output and return the error carrying it.
+fn abort_with_output<W: Write>(writer: &mut W, output: &str) -> Result<()> {
+    writeln!(writer, "{}", output)?;
+    writer.flush()?;
+    Err(OutputWrittenError(output.to_string()).into())
+}
@@ -54,6 +54,48 @@ pub(crate) fn build_settings(matches: &ArgMatches) -> Result<SettingsPathBuf> {
                 sort_keys: bool_from_matches(matches, "sort_keys"),
                 show_hidden: bool_from_matches(matches, "show_hidden"),
                 fast_eval: bool_from_matches(matches, "fast_eval"),
+                big_int: bool_from_matches(matches, "big_int"),
+                diagnostic_format: matches
+                    .get_one::<String>("diagnostic_format")
+                    .map(|v| v.to_string()),
+                max_errors: matches.get_one::<usize>("max_errors").copied(),
+                decimal_places: matches.get_one::<u32>("decimal_places").copied(),
+                timeout_ms: matches.get_one::<u64>("timeout_ms").copied(),
+                max_call_depth: matches.get_one::<usize>("max_call_depth").copied(),
+                max_allocations: matches.get_one::<usize>("max_allocations").copied(),
+                random_seed: matches.get_one::<u64>("random_seed").copied(),
+                show_secrets: bool_from_matches(matches, "show_secrets"),
+                externalize_secrets: bool_from_matches(matches, "externalize_secrets"),
+                secret_paths: strings_from_matches(matches, "secret_paths"),
+                output_format: matches
+                    .get_one::<String>("output_format")
+                    .map(|v| v.to_string()),
+                output_dir: matches
+                    .get_one::<String>("output_dir")
+                    .map(|v| v.to_string()),
+                filename_template: matches
+                    .get_one::<String>("filename_template")
+                    .map(|v| v.to_string()),
+                key_order: matches
+                    .get_one::<String>("key_order")
+                    .map(|v| v.to_string()),
+                provenance: matches
+                    .get_one::<String>("provenance")
+                    .map(|v| v.to_string()),
+                backend: matches.get_one::<String>("backend").map(|v| v.to_string()),
+                no_cache: bool_from_matches(matches, "no_cache"),
+                profile: bool_from_matches(matches, "profile"),
+                dump_dir: matches.get_one::<String>("dump_dir").map(|v| v.to_string()),
+                dump_ast: bool_from_matches(matches, "dump_ast"),
+                dump_llvm_ir: bool_from_matches(matches, "dump_llvm_ir"),
+                dump_lib: bool_from_matches(matches, "dump_lib"),
+                option_files: strings_from_matches(matches, "option_files"),
+                option_env_prefix: matches
+                    .get_one::<String>("option_env_prefix")
+                    .map(|v| v.to_string()),
+                locale: matches.get_one::<String>("locale").map(|v| v.to_string()),
+                color: matches.get_one::<String>("color").map(|v| v.to_string()),
+                path_prefix_map: strings_from_matches(matches, "path_prefix_map"),
                 package_maps,
                 ..Default::default()
             }),
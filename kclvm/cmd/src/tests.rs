@@ -1,6 +1,7 @@
 use std::{
     env,
     fs::{self, remove_file},
+    io::Write,
     path::{Path, PathBuf},
     sync::Arc,
 };
@@ -11,7 +12,9 @@ use kclvm_runner::{exec_program, MapErrorResult};
 
 use crate::{
     app,
-    run::run_command,
+    explain::explain_command,
+    inspect::inspect_command,
+    run::{run_command, OutputWrittenError},
     settings::{build_settings, must_build_settings},
     util::hashmaps_from_matches,
 };
@@ -107,6 +110,39 @@ fn test_version_cmd() {
     assert!(matches.subcommand_matches("version").is_some())
 }
 
+#[test]
+fn test_explain_cmd() {
+    let matches = app().get_matches_from(&[ROOT_CMD, "explain", "E2G22"]);
+    let matches = matches.subcommand_matches("explain").unwrap();
+    let mut buf = Vec::new();
+    explain_command(matches, &mut buf).unwrap();
+    assert!(String::from_utf8(buf)
+        .unwrap()
+        .starts_with("### TypeError (E2G22)"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "explain"]);
+    let matches = matches.subcommand_matches("explain").unwrap();
+    let mut buf = Vec::new();
+    explain_command(matches, &mut buf).unwrap();
+    assert!(String::from_utf8(buf).unwrap().contains("W2008\n"));
+
+    let matches = app().get_matches_from(&[ROOT_CMD, "explain", "E0000"]);
+    let matches = matches.subcommand_matches("explain").unwrap();
+    assert!(explain_command(matches, &mut Vec::new()).is_err());
+}
+
+#[test]
+fn test_inspect_cmd() {
+    let artifact = work_dir().join("kcl.yaml").display().to_string();
+    let matches = app().get_matches_from(&[ROOT_CMD, "inspect", &artifact]);
+    let matches = matches.subcommand_matches("inspect").unwrap();
+    let err = inspect_command(matches, &mut Vec::new()).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        format!("the artifact '{}' has no metadata", artifact)
+    );
+}
+
 #[test]
 fn test_multi_external_cmd() {
     let matches = app().get_matches_from(&[
@@ -187,6 +223,7 @@ fn test_run_command() {
     test_error_message_fuzz_matched();
     test_error_message_fuzz_unmatched();
     test_keyword_argument_error_message();
+    test_structured_error_output();
 }
 
 fn test_run_command_with_import() {
@@ -587,3 +624,60 @@ fn test_keyword_argument_error_message() {
         }
     }
 }
+
+fn test_structured_error_output() {
+    let test_case_path = PathBuf::from("./src/test_data/failed/keyword_argument_error.k");
+    let matches = app().arg_required_else_help(true).get_matches_from(&[
+        ROOT_CMD,
+        "run",
+        &test_case_path.canonicalize().unwrap().display().to_string(),
+        "--diagnostic-format",
+        "json",
+    ]);
+    let mut buf = Vec::new();
+    let err = run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap_err();
+    let output = String::from_utf8(buf).unwrap();
+    assert!(output.contains("keyword argument 'ID' not found"));
+    let err = err.downcast_ref::<OutputWrittenError>().unwrap();
+    assert_eq!(format!("{}\n", err.0), output);
+}
+
+/// The environment variable which runs the test as the child process reading the KCL
+/// source from the piped stdin, see [test_run_command_with_stdin].
+const STDIN_CHILD_ENV: &str = "KCL_TEST_STDIN_CHILD";
+
+#[test]
+fn test_run_command_with_stdin() {
+    if env::var(STDIN_CHILD_ENV).is_ok() {
+        let matches = app()
+            .arg_required_else_help(true)
+            .get_matches_from(&[ROOT_CMD, "run", "-"]);
+        let mut buf = Vec::new();
+        run_command(matches.subcommand_matches("run").unwrap(), &mut buf).unwrap();
+        std::io::stdout().write_all(&buf).unwrap();
+        return;
+    }
+    // The stdin of the test process can't be piped, thus run the test itself as a
+    // child process with the piped stdin.
+    let mut child = std::process::Command::new(env::current_exe().unwrap())
+        .args([
+            "--exact",
+            "tests::test_run_command_with_stdin",
+            "--nocapture",
+        ])
+        .env(STDIN_CHILD_ENV, "1")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    child
+        .stdin
+        .take()
+        .unwrap()
+        .write_all(b"a = 1\nb = a + 1\n")
+        .unwrap();
+    let output = child.wait_with_output().unwrap();
+    assert!(output.status.success());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains("a: 1\nb: 2\n"), "{}", stdout);
+}
@@ -31,12 +31,12 @@ use kclvm_sema::plugin;
 
 use crate::codegen::abi::Align;
 use crate::codegen::llvm::utils;
-use crate::codegen::OBJECT_FILE_SUFFIX;
 use crate::codegen::{error as kcl_error, EmitOptions};
 use crate::codegen::{
     traits::*, ENTRY_NAME, GLOBAL_VAL_ALIGNMENT, MODULE_NAME, PKG_INIT_FUNCTION_SUFFIX,
 };
 use crate::codegen::{CodeGenContext, GLOBAL_LEVEL};
+use crate::codegen::{LL_FILE_SUFFIX, OBJECT_FILE_SUFFIX};
 use crate::value;
 
 /// SCALAR_KEY denotes the temp scalar key for the global variable json plan process.
@@ -1458,32 +1458,50 @@ impl<'ctx> LLVMCodeGenContext<'ctx> {
             if opt.no_link {
                 let modules = self.modules.borrow_mut();
                 for (index, (_, module)) in modules.iter().enumerate() {
-                    let path = if modules.len() == 1 {
-                        format!("{}{}", path_str, OBJECT_FILE_SUFFIX)
+                    let (path, ir_path) = if modules.len() == 1 {
+                        (
+                            format!("{}{}", path_str, OBJECT_FILE_SUFFIX),
+                            opt.ir_path.map(|p| format!("{}{}", p, LL_FILE_SUFFIX)),
+                        )
                     } else {
-                        format!("{}_{}{}", path_str, index, OBJECT_FILE_SUFFIX)
+                        (
+                            format!("{}_{}{}", path_str, index, OBJECT_FILE_SUFFIX),
+                            opt.ir_path
+                                .map(|p| format!("{}_{}{}", p, index, LL_FILE_SUFFIX)),
+                        )
                     };
                     let path = std::path::Path::new(&path);
+                    if let Some(ir_path) = ir_path {
+                        module.borrow().inner.print_to_file(ir_path)?;
+                    }
                     // Build LLVM module to a `.o` object file.
-                    self.build_object_file(&module.borrow().inner, path)?;
+                    self.build_object_file(&module.borrow().inner, path, opt.target)?;
                 }
             } else {
+                if let Some(ir_path) = opt.ir_path {
+                    self.module
+                        .print_to_file(format!("{}{}", ir_path, LL_FILE_SUFFIX))?;
+                }
                 // Build LLVM module to a `.o` object file.
-                self.build_object_file(&self.module, path)?;
+                self.build_object_file(&self.module, path, opt.target)?;
             }
         }
         Ok(())
     }
 
-    /// Build LLVM module to a `.o` object file.
+    /// Build LLVM module to a `.o` object file of the target triple, default is the host triple.
     ///
-    /// TODO: WASM and cross platform build.
+    /// TODO: WASM build.
     fn build_object_file(
         self: &LLVMCodeGenContext<'ctx>,
         module: &Module,
         path: &Path,
+        target: Option<&str>,
     ) -> Result<(), LLVMString> {
-        let triple = inkwell::targets::TargetMachine::get_default_triple();
+        let triple = match target {
+            Some(target) => inkwell::targets::TargetTriple::create(target),
+            None => inkwell::targets::TargetMachine::get_default_triple(),
+        };
         let target = inkwell::targets::Target::from_triple(&triple)?;
         // Convert LLVM module to ll file.
         module.print_to_file(path)?;
@@ -12,6 +12,7 @@ use crate::codegen::{EmitOptions, MODULE_NAME};
 use super::context::LLVMCodeGenContext;
 
 static LLVM_INIT: OnceCell<()> = OnceCell::new();
+static LLVM_INIT_ALL: OnceCell<()> = OnceCell::new();
 static RUNTIME_LLVM_BC: &[u8] = include_bytes!("../../../../runtime/src/_kclvm.bc");
 
 /// Load runtime libraries and parse it to a module.
@@ -38,6 +39,10 @@ pub fn emit_code(
         #[cfg(not(any(target_os = "linux", target_arch = "wasm32")))]
         inkwell::targets::Target::initialize_all(&Default::default());
     });
+    // Init all LLVM targets for the cross-target build.
+    if opts.target.is_some() {
+        LLVM_INIT_ALL.get_or_init(|| inkwell::targets::Target::initialize_all(&Default::default()));
+    }
     // Create a LLVM context
     let context = Context::create();
     // Create a LLVM module using an exist LLVM bitcode file
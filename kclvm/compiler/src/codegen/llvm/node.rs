@@ -10,7 +10,7 @@ use inkwell::values::{BasicValueEnum, CallableValue, FunctionValue};
 use inkwell::{AddressSpace, IntPredicate};
 use kclvm_ast::ast::{self, CallExpr, ConfigEntry, NodeRef};
 use kclvm_ast::walker::TypedResultWalker;
-use kclvm_runtime::{ApiFunc, PKG_PATH_PREFIX};
+use kclvm_runtime::{ApiFunc, PKG_PATH_PREFIX, SECRET_DECORATOR};
 use kclvm_sema::pkgpath_without_prefix;
 use kclvm_sema::ty::{ANY_TYPE_STR, STR_TYPE_STR};
 
@@ -1469,6 +1469,12 @@ impl<'ctx> TypedResultWalker<'ctx> for LLVMCodeGenContext<'ctx> {
             &ApiFunc::kclvm_config_attr_map.name(),
             &[schema_value, string_ptr_value, type_str_ptr_value],
         );
+        if schema_attr.has_decorator(SECRET_DECORATOR) {
+            self.build_void_call(
+                &ApiFunc::kclvm_config_secret_attr.name(),
+                &[schema_value, string_ptr_value],
+            );
+        }
         let has_key = self
             .build_call(
                 &ApiFunc::kclvm_dict_has_value.name(),
@@ -49,6 +49,10 @@ pub struct EmitOptions<'a> {
     pub emit_path: Option<&'a str>,
     /// no_link indicates whether to link the generated code of different KCL packages to the same module.
     pub no_link: bool,
+    /// The target triple of the object files e.g., `x86_64-unknown-linux-gnu`, default is the host triple.
+    pub target: Option<&'a str>,
+    /// Path to emit the LLVM IR text (`.ll`) of the module besides the object file.
+    pub ir_path: Option<&'a str>,
 }
 
 /// Emit code with the options using CodeGenContext.
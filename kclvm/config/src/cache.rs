@@ -17,6 +17,7 @@ use kclvm_version as version;
 const LOCK_SUFFIX: &str = ".lock";
 const DEFAULT_CACHE_DIR: &str = ".kclvm/cache";
 const CACHE_INFO_FILENAME: &str = "info";
+const CONTENT_CACHE_DIR: &str = "content";
 const KCL_SUFFIX_PATTERN: &str = "*.k";
 pub const KCL_CACHE_PATH_ENV_VAR: &str = "KCL_CACHE_PATH";
 
@@ -276,6 +277,62 @@ where
     Ok(())
 }
 
+/// Return the hash of the content parts used as the key of the content cache.
+pub fn get_content_hash(parts: &[&[u8]]) -> CacheInfo {
+    let mut md5 = Md5::new();
+    for part in parts {
+        md5.input((part.len() as u64).to_le_bytes());
+        md5.input(part);
+    }
+    md5.result().to_vec()
+}
+
+#[inline]
+fn get_content_cache_filename(cache_dir: &str, key: &CacheInfo) -> String {
+    let name: String = key.iter().map(|b| format!("{:02x}", b)).collect();
+    Path::new(cache_dir)
+        .join(format!("{}-{}", version::VERSION, version::CHECK_SUM))
+        .join(CONTENT_CACHE_DIR)
+        .join(format!("{}.json", name))
+        .display()
+        .to_string()
+}
+
+/// Load the data cached by the content hash `key` in the cache directory. The cache
+/// files are independent of the file paths, thus they can be shared across processes
+/// and workspaces.
+pub fn load_content_cache<T>(cache_dir: &str, key: &CacheInfo) -> Option<T>
+where
+    T: DeserializeOwned,
+{
+    let filename = get_content_cache_filename(cache_dir, key);
+    let file = File::open(filename).ok()?;
+    serde_json::from_reader(std::io::BufReader::new(file)).ok()
+}
+
+/// Save the data cached by the content hash `key` into the cache directory.
+///
+/// The data is written to a temp file and then renamed, thus the readers in other
+/// processes never see a partially written cache file.
+pub fn save_content_cache<T>(cache_dir: &str, key: &CacheInfo, data: &T) -> Result<()>
+where
+    T: Serialize,
+{
+    let dst_filename = get_content_cache_filename(cache_dir, key);
+    let dir = Path::new(&dst_filename)
+        .parent()
+        .ok_or(anyhow::anyhow!("invalid cache file {}", dst_filename))?;
+    create_dir_all(dir)?;
+    let tmp_filename = temp_file(&dir.display().to_string(), "");
+    let file = File::create(&tmp_filename)?;
+    let mut writer = std::io::BufWriter::new(file);
+    serde_json::to_writer(&mut writer, data)?;
+    writer.flush()?;
+    drop(writer);
+    std::fs::rename(&tmp_filename, &dst_filename)?;
+    Ok(())
+}
+
 #[inline]
 fn temp_file(cache_dir: &str, pkgpath: &str) -> String {
     let timestamp = chrono::Local::now()
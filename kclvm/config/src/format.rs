@@ -0,0 +1,94 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+pub const KCL_FORMAT_FILE: &str = ".kcl-format.toml";
+
+/// FormatConfig is the content of '.kcl-format.toml', which configures the code style
+/// of the KCL formatter e.g.,
+///
+/// ```toml
+/// indent_width = 2
+/// quote_style = "single"
+/// max_line_width = 100
+/// magic_trailing_comma = true
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+#[serde(deny_unknown_fields)]
+pub struct FormatConfig {
+    /// The number of spaces of each indent level, default is 4.
+    pub indent_width: Option<usize>,
+    /// Whether to indent with the tabs instead of the spaces, default is false.
+    pub use_tabs: Option<bool>,
+    /// The preferred quote style of the string literals, default is `preserve`.
+    pub quote_style: Option<QuoteStyle>,
+    /// The max width of the lines, and the one-line lists and configs exceeding it are
+    /// wrapped into multiple lines. Default is 0, which denotes no limit.
+    pub max_line_width: Option<usize>,
+    /// Whether to keep the lists and configs with a trailing comma after the last item
+    /// in multiple lines, default is false.
+    pub magic_trailing_comma: Option<bool>,
+}
+
+/// QuoteStyle is the preferred quote style of the string literals, and the strings
+/// containing the preferred quote are kept as is.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum QuoteStyle {
+    /// Keep the quotes written in the source.
+    #[default]
+    Preserve,
+    /// e.g., `"foo"`
+    Double,
+    /// e.g., `'foo'`
+    Single,
+}
+
+/// Find the '.kcl-format.toml' in the path and its parent directories and load the
+/// format config, otherwise the default one. The path can be a file or a directory.
+pub fn load_format_config<P: AsRef<Path>>(path: P) -> Result<FormatConfig> {
+    let path = path.as_ref();
+    if path.as_os_str().is_empty() {
+        return Ok(FormatConfig::default());
+    }
+    let path = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let dir = if path.is_file() {
+        path.parent().unwrap_or(&path)
+    } else {
+        &path
+    };
+    for dir in dir.ancestors() {
+        let file_path = dir.join(KCL_FORMAT_FILE);
+        if file_path.is_file() {
+            let content = std::fs::read_to_string(&file_path)?;
+            return toml::from_str(&content)
+                .map_err(|e| anyhow::anyhow!("invalid {}: {}", file_path.display(), e));
+        }
+    }
+    Ok(FormatConfig::default())
+}
+
+#[cfg(test)]
+mod format_test {
+    use crate::format::*;
+
+    #[test]
+    fn test_load_format_config() {
+        let expected = FormatConfig {
+            indent_width: Some(2),
+            quote_style: Some(QuoteStyle::Single),
+            max_line_width: Some(40),
+            magic_trailing_comma: Some(true),
+            ..Default::default()
+        };
+        let config = load_format_config("./src/testdata/format").unwrap();
+        assert_eq!(config, expected);
+        // Find the config in the parent directories of the file.
+        let config = load_format_config("./src/testdata/format/pkg/main.k").unwrap();
+        assert_eq!(config, expected);
+        let config = load_format_config("").unwrap();
+        assert_eq!(config, FormatConfig::default());
+    }
+}
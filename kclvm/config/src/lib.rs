@@ -1,6 +1,8 @@
 //! Copyright The KCL Authors. All rights reserved.
 
 pub mod cache;
+pub mod format;
+pub mod lint;
 pub mod modfile;
 pub mod path;
 pub mod settings;
@@ -0,0 +1,119 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::{collections::HashMap, path::Path};
+
+use crate::modfile::load_mod_file;
+
+pub const KCL_LINT_FILE: &str = "kcl-lint.toml";
+
+/// LintConfig is the content of 'kcl-lint.toml' and the lint section of 'kcl.mod',
+/// which configures the level of each lint rule e.g.,
+///
+/// ```toml
+/// [rules]
+/// unused-import = "off"
+/// reimport = "error"
+///
+/// [naming]
+/// attribute = "snake_case"
+/// ```
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct LintConfig {
+    /// The lint rule names and their levels.
+    pub rules: Option<HashMap<String, LintLevel>>,
+    /// The naming conventions checked by the naming lint rules.
+    pub naming: Option<NamingConfig>,
+}
+
+/// NamingConfig configures the naming styles of the names in KCL code, and the
+/// default style is used for the unset ones.
+#[derive(Debug, Default, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct NamingConfig {
+    /// The style of the schema and rule names, default is `PascalCase`.
+    pub schema: Option<NamingStyle>,
+    /// The style of the schema attribute names, default is `camelCase`.
+    pub attribute: Option<NamingStyle>,
+    /// The style of the package names in the import paths, default is `snake_case`.
+    pub package: Option<NamingStyle>,
+    /// The style of the constants, which are the top level variables starting with an
+    /// uppercase letter, default is `UPPER_SNAKE_CASE`.
+    pub constant: Option<NamingStyle>,
+}
+
+/// NamingStyle is the naming style of the identifiers.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum NamingStyle {
+    /// e.g., `PersonInfo`
+    PascalCase,
+    /// e.g., `personInfo`
+    CamelCase,
+    /// e.g., `person_info`
+    SnakeCase,
+    /// e.g., `PERSON_INFO`
+    UpperSnakeCase,
+}
+
+/// LintLevel is the configured level of a lint rule.
+#[derive(Debug, Serialize, Deserialize, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum LintLevel {
+    /// Disable the rule.
+    Off,
+    /// Report the diagnostics of the rule as warnings.
+    Warning,
+    /// Report the diagnostics of the rule as errors.
+    Error,
+}
+
+impl LintConfig {
+    /// Get the configured level of the rule.
+    #[inline]
+    pub fn get_level(&self, rule: &str) -> Option<LintLevel> {
+        self.rules
+            .as_ref()
+            .and_then(|rules| rules.get(rule).copied())
+    }
+
+    /// Get the naming config, or the default one when it is not set.
+    #[inline]
+    pub fn get_naming(&self) -> NamingConfig {
+        self.naming.clone().unwrap_or_default()
+    }
+}
+
+/// Load the lint config from the 'kcl-lint.toml' in the path, or the lint section of
+/// the 'kcl.mod' in the path if the former doesn't exist, otherwise the default one.
+pub fn load_lint_config<P: AsRef<Path>>(path: P) -> Result<LintConfig> {
+    let file_path = path.as_ref().join(KCL_LINT_FILE);
+    if file_path.is_file() {
+        let content = std::fs::read_to_string(file_path)?;
+        return toml::from_str(&content).map_err(|e| anyhow::anyhow!(e));
+    }
+    if path.as_ref().join(crate::modfile::KCL_MOD_FILE).is_file() {
+        return Ok(load_mod_file(path)?.lint.unwrap_or_default());
+    }
+    Ok(LintConfig::default())
+}
+
+#[cfg(test)]
+mod lint_test {
+    use crate::lint::*;
+
+    #[test]
+    fn test_load_lint_config() {
+        let config = load_lint_config("./src/testdata/lint").unwrap();
+        assert_eq!(config.get_level("unused-import"), Some(LintLevel::Off));
+        assert_eq!(config.get_level("reimport"), Some(LintLevel::Error));
+        assert_eq!(config.get_level("import-position"), None);
+        let naming = config.get_naming();
+        assert_eq!(naming.attribute, Some(NamingStyle::SnakeCase));
+        assert_eq!(naming.schema, None);
+        let config = load_lint_config("./src/testdata/lint/mod").unwrap();
+        assert_eq!(config.get_level("reimport"), Some(LintLevel::Warning));
+        let config = load_lint_config("./src/testdata/not_exist").unwrap();
+        assert_eq!(config, LintConfig::default());
+    }
+}
@@ -12,6 +12,7 @@ use std::{
 use toml;
 use url::Url;
 
+use crate::lint::LintConfig;
 use crate::path::ModRelativePath;
 
 pub const KCL_MOD_FILE: &str = "kcl.mod";
@@ -30,6 +31,7 @@ pub struct ModFile {
     pub package: Option<Package>,
     pub profile: Option<Profile>,
     pub dependencies: Option<Dependencies>,
+    pub lint: Option<LintConfig>,
 }
 
 /// ModLockFile is kcl package file 'kc.mod.lock'.
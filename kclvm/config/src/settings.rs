@@ -68,6 +68,63 @@ pub struct Config {
     pub package_maps: Option<HashMap<String, String>>,
     /// Use the evaluator to execute the AST program instead of AOT.
     pub fast_eval: Option<bool>,
+    /// The output format of the diagnostics, one of `human`, `json`, `sarif`, `html` and `markdown`.
+    pub diagnostic_format: Option<String>,
+    /// The maximum number of the reported compile errors.
+    pub max_errors: Option<usize>,
+    /// The locale of the compile diagnostics e.g., `zh-CN`.
+    pub locale: Option<String>,
+    /// The color mode of the compile diagnostics, one of `auto`, `always` and `never`.
+    pub color: Option<String>,
+    /// The path prefix mappings in the format `FROM=TO`, which remap the absolute build
+    /// paths in the outputs, and the later mappings take precedence.
+    pub path_prefix_map: Option<Vec<String>>,
+    /// Whether to promote the int operations overflowing 64 bits to the arbitrary-precision ints.
+    pub big_int: Option<bool>,
+    /// The decimal places to round the float values to in the outputs.
+    pub decimal_places: Option<u32>,
+    /// The evaluation timeout in milliseconds.
+    pub timeout_ms: Option<u64>,
+    /// The maximum depth of the lambda, schema and rule calls.
+    pub max_call_depth: Option<usize>,
+    /// The maximum number of the list and dict items allocated in the evaluation.
+    pub max_allocations: Option<usize>,
+    /// The seed of the generated random values.
+    pub random_seed: Option<u64>,
+    /// Whether to emit the secret values as is instead of masking them.
+    pub show_secrets: Option<bool>,
+    /// Whether to move the secret values to a separate secrets document in the outputs.
+    pub externalize_secrets: Option<bool>,
+    /// The key paths of the secret values besides the `@secret` schema attributes.
+    pub secret_paths: Option<Vec<String>>,
+    /// The output format e.g., `yaml`, `json`, `toml` and `hcl`.
+    pub output_format: Option<String>,
+    /// The output directory of the files split from the plan result.
+    pub output_dir: Option<String>,
+    /// The filename template of the files under the output directory.
+    pub filename_template: Option<String>,
+    /// The key order e.g., `declaration`, `alphabetical` and `schema`.
+    pub key_order: Option<String>,
+    /// The provenance mode e.g., `none`, `comment` and `sidecar`.
+    pub provenance: Option<String>,
+    /// The execution backend e.g., `auto`, `interpreter` and `native`.
+    pub backend: Option<String>,
+    /// Whether to disable the compiled object cache of the native backend.
+    pub no_cache: Option<bool>,
+    /// Whether to profile the execution phases.
+    pub profile: Option<bool>,
+    /// The directory to dump the intermediate representations.
+    pub dump_dir: Option<String>,
+    /// Whether to dump the resolved AST in JSON.
+    pub dump_ast: Option<bool>,
+    /// Whether to dump the LLVM IR of each package.
+    pub dump_llvm_ir: Option<bool>,
+    /// Whether to dump the linked library.
+    pub dump_lib: Option<bool>,
+    /// The YAML or JSON files of the top-level options.
+    pub option_files: Option<Vec<String>>,
+    /// The prefix of the environment variables of the top-level options.
+    pub option_env_prefix: Option<String>,
 }
 
 impl SettingsFile {
@@ -88,6 +145,34 @@ impl SettingsFile {
                 fast_eval: Some(false),
                 include_schema_type_path: Some(false),
                 package_maps: Some(HashMap::default()),
+                diagnostic_format: None,
+                max_errors: None,
+                locale: None,
+                color: None,
+                path_prefix_map: None,
+                big_int: None,
+                decimal_places: None,
+                timeout_ms: None,
+                max_call_depth: None,
+                max_allocations: None,
+                random_seed: None,
+                show_secrets: None,
+                externalize_secrets: None,
+                secret_paths: None,
+                output_format: None,
+                output_dir: None,
+                filename_template: None,
+                key_order: None,
+                provenance: None,
+                backend: None,
+                no_cache: None,
+                profile: None,
+                dump_dir: None,
+                dump_ast: None,
+                dump_llvm_ir: None,
+                dump_lib: None,
+                option_files: None,
+                option_env_prefix: None,
             }),
             kcl_options: Some(vec![]),
         }
@@ -394,6 +479,34 @@ pub fn merge_settings(settings: &[SettingsFile]) -> SettingsFile {
                     kcl_cli_configs
                 );
                 set_if!(result_kcl_cli_configs, package_maps, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, diagnostic_format, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, max_errors, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, locale, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, color, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, path_prefix_map, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, big_int, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, decimal_places, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, timeout_ms, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, max_call_depth, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, max_allocations, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, random_seed, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, show_secrets, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, externalize_secrets, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, secret_paths, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, output_format, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, output_dir, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, filename_template, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, key_order, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, provenance, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, backend, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, no_cache, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, profile, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, dump_dir, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, dump_ast, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, dump_llvm_ir, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, dump_lib, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, option_files, kcl_cli_configs);
+                set_if!(result_kcl_cli_configs, option_env_prefix, kcl_cli_configs);
             }
         }
         if let Some(kcl_options) = &setting.kcl_options {
@@ -0,0 +1,7 @@
+[package]
+name = "lint"
+edition = "0.0.1"
+version = "0.0.1"
+
+[lint]
+rules = { reimport = "warning" }
@@ -1,5 +1,7 @@
-use indexmap::IndexSet;
+use anyhow::{bail, Result};
+use indexmap::{IndexMap, IndexSet};
 use kclvm_span::Loc;
+use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::hash::Hash;
 
@@ -13,6 +15,15 @@ pub struct Diagnostic {
     pub level: Level,
     pub messages: Vec<Message>,
     pub code: Option<DiagnosticId>,
+    /// The source edits which fix the diagnostic.
+    pub fixes: Vec<FixIt>,
+    /// The secondary labels pointing at the related locations of the first message
+    /// e.g., the first definition of a duplicate definition.
+    pub labels: Vec<SecondaryLabel>,
+    /// The hex encoded sha256 hash of the file source which the diagnostic points at,
+    /// thus the caching layers and the code review bots can verify whether the
+    /// diagnostic still applies to the file version they are looking at.
+    pub source_hash: Option<String>,
 }
 
 /// Position describes an arbitrary source position including the filename,
@@ -125,13 +136,79 @@ impl Diagnostic {
                 suggested_replacement: suggestions,
             }],
             code,
+            fixes: vec![],
+            labels: vec![],
+            source_hash: None,
         }
     }
 
+    /// Add a secondary label pointing at the related location to the diagnostic.
+    pub fn with_label(mut self, range: Range, message: &str) -> Self {
+        self.labels.push(SecondaryLabel {
+            range,
+            message: message.to_string(),
+        });
+        self
+    }
+
+    /// Add a fix-it to the diagnostic.
+    pub fn with_fix(mut self, fix: FixIt) -> Self {
+        self.fixes.push(fix);
+        self
+    }
+
+    /// Set the source hash of the diagnostic from the file hashes keyed by the filenames,
+    /// and the file is the one which the first message points at.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use indexmap::IndexMap;
+    /// use kclvm_error::{Diagnostic, Level, Position};
+    ///
+    /// let pos = Position { filename: "main.k".to_string(), line: 1, column: None };
+    /// let hashes = IndexMap::from([("main.k".to_string(), "5d41402a".to_string())]);
+    /// let diag = Diagnostic::new(Level::Error, "error", (pos.clone(), pos))
+    ///     .with_source_hashes(&hashes);
+    /// assert_eq!(diag.source_hash.as_deref(), Some("5d41402a"));
+    /// ```
+    pub fn with_source_hashes(mut self, hashes: &IndexMap<String, String>) -> Self {
+        self.source_hash = self
+            .messages
+            .first()
+            .and_then(|msg| hashes.get(&msg.range.0.filename))
+            .cloned();
+        self
+    }
+
+    /// Get the fix-its which can be applied without the user review.
+    pub fn machine_applicable_fixes(&self) -> Vec<&FixIt> {
+        self.fixes
+            .iter()
+            .filter(|fix| fix.applicability == Applicability::MachineApplicable)
+            .collect()
+    }
+
     #[inline]
     pub fn is_error(&self) -> bool {
         matches!(self.level, Level::Error)
     }
+
+    /// Get the suggested replacements of the messages with their ranges, which can be
+    /// applied by the editors as quick fixes e.g., replacing a mistyped config attribute
+    /// with the closest schema attribute.
+    pub fn suggested_replacements(&self) -> Vec<(&Range, &str)> {
+        self.messages
+            .iter()
+            .flat_map(|msg| {
+                msg.suggested_replacement
+                    .iter()
+                    .flatten()
+                    .filter(|s| !s.is_empty())
+                    .map(move |s| (&msg.range, s.as_str()))
+            })
+            .collect()
+    }
 }
 
 pub type Range = (Position, Position);
@@ -151,6 +228,111 @@ pub struct Message {
     pub suggested_replacement: Option<Vec<String>>,
 }
 
+/// SecondaryLabel denotes a related location of the diagnostic with its message, which
+/// is rendered along with the code snippet of the first message.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct SecondaryLabel {
+    pub range: Range,
+    pub message: String,
+}
+
+/// Applicability indicates how confident we are that a fix-it is what the user wants,
+/// and only the machine applicable fix-its are applied without the user review e.g.,
+/// by `kcl fix`.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Applicability {
+    /// The fix is definitely what the user wants.
+    MachineApplicable,
+    /// The fix may be what the user wants but needs the user review e.g., one of the
+    /// names similar to an undefined name.
+    MaybeIncorrect,
+    /// The fix contains the placeholders e.g., `<value>` which need to be filled.
+    HasPlaceholders,
+    /// The applicability of the fix is unknown.
+    Unspecified,
+}
+
+/// FixIt denotes a source edit which replaces the range with the replacement, and an
+/// empty replacement removes the range.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct FixIt {
+    pub range: Range,
+    pub replacement: String,
+    pub applicability: Applicability,
+}
+
+impl FixIt {
+    pub fn new(range: Range, replacement: &str, applicability: Applicability) -> Self {
+        Self {
+            range,
+            replacement: replacement.to_string(),
+            applicability,
+        }
+    }
+}
+
+/// Apply the fix-its of the file to its source code. The fix-its of the other files
+/// are ignored, and the overlapping fix-its are reported as an error.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::diagnostic::{apply_fixes, Applicability, FixIt, Position};
+///
+/// let pos = |line, column| Position { filename: "main.k".to_string(), line, column: Some(column) };
+/// let fix = FixIt::new((pos(1, 0), pos(1, 6)), "import", Applicability::MachineApplicable);
+/// assert_eq!(apply_fixes("main.k", "improt a", &[&fix]).unwrap(), "import a");
+/// ```
+pub fn apply_fixes(filename: &str, source: &str, fixes: &[&FixIt]) -> Result<String> {
+    let mut edits = vec![];
+    for fix in fixes.iter().filter(|fix| fix.range.0.filename == filename) {
+        let start = byte_offset(source, &fix.range.0);
+        let end = byte_offset(source, &fix.range.1);
+        match (start, end) {
+            (Some(start), Some(end)) if start <= end => {
+                edits.push((start, end, fix.replacement.as_str()))
+            }
+            _ => bail!("invalid fix range {:?} in the file {}", fix.range, filename),
+        }
+    }
+    edits.sort_by_key(|(start, end, _)| (*start, *end));
+    for pair in edits.windows(2) {
+        if pair[0].1 > pair[1].0 {
+            bail!("overlapping fixes in the file {}", filename);
+        }
+    }
+    let mut result = source.to_string();
+    for (start, end, replacement) in edits.into_iter().rev() {
+        result.replace_range(start..end, replacement);
+    }
+    Ok(result)
+}
+
+/// Get the byte offset of the position whose line is 1-based and column is the
+/// 0-based char offset in the line. The position without a column denotes the start
+/// of the line.
+fn byte_offset(source: &str, pos: &Position) -> Option<usize> {
+    if pos.line == 0 {
+        return None;
+    }
+    let mut offset = 0;
+    for (i, line) in source.split_inclusive('\n').enumerate() {
+        if i as u64 + 1 == pos.line {
+            let column = pos.column.unwrap_or(0) as usize;
+            let content = line.trim_end_matches(|c| c == '\n' || c == '\r');
+            return match content.char_indices().nth(column) {
+                Some((idx, _)) => Some(offset + idx),
+                None if column == content.chars().count() => Some(offset + content.len()),
+                None => None,
+            };
+        }
+        offset += line.len();
+    }
+    // The position at the end of the source.
+    (pos.line == source.split_inclusive('\n').count() as u64 + 1 && pos.column.unwrap_or(0) == 0)
+        .then_some(source.len())
+}
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash)]
 pub enum DiagnosticId {
     Error(ErrorKind),
@@ -0,0 +1,66 @@
+//! The color mode of the human readable diagnostics, which controls whether the
+//! ANSI styles are emitted, thus the diagnostics written into the CI logs or the
+//! files are not corrupted by the escape sequences.
+use std::io::IsTerminal;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use compiler_base_error::{ColorChoice, Destination, EmitterWriter};
+use serde::{Deserialize, Serialize};
+
+/// The environment variable to disable the colors, see <https://no-color.org>.
+pub const NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
+/// The color mode of the human readable diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ColorMode {
+    /// Emit colors only when the stderr is a terminal and `NO_COLOR` is not set.
+    #[default]
+    Auto,
+    /// Always emit colors.
+    Always,
+    /// Never emit colors, i.e., the plain text.
+    Never,
+}
+
+impl ColorMode {
+    /// Whether the diagnostics are emitted with colors.
+    pub fn enabled(&self) -> bool {
+        match self {
+            ColorMode::Auto => {
+                std::env::var_os(NO_COLOR_ENV_VAR).map_or(true, |v| v.is_empty())
+                    && std::io::stderr().is_terminal()
+            }
+            ColorMode::Always => true,
+            ColorMode::Never => false,
+        }
+    }
+}
+
+impl FromStr for ColorMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(ColorMode::Auto),
+            "always" => Ok(ColorMode::Always),
+            "never" => Ok(ColorMode::Never),
+            _ => bail!(
+                "invalid color mode '{}', expected one of 'auto', 'always' and 'never'",
+                s
+            ),
+        }
+    }
+}
+
+/// New a stderr emitter, which emits the plain text without the ANSI styles when
+/// `color` is false.
+pub(crate) fn stderr_emitter(color: bool) -> EmitterWriter<'static> {
+    let choice = if color {
+        ColorChoice::Always
+    } else {
+        ColorChoice::Never
+    };
+    EmitterWriter::new_with_writer(Destination::from_stderr(choice))
+}
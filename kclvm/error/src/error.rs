@@ -1,6 +1,10 @@
 //! This module is used to gather all error codes into one place,
 //! the goal being to make their maintenance easier.
 
+use serde::{Deserialize, Serialize};
+
+use crate::locale::{get_message, Locale};
+
 macro_rules! register_errors {
     ($($ecode:ident: $kind:expr, $message:expr,)*) => (
         pub static ERRORS: &[(&str, Error)] = &[
@@ -51,13 +55,21 @@ register_errors! {
     E2L28: ErrorKind::UniqueKeyError, include_str!("./error_codes/E2L28.md"),
     E2D34: ErrorKind::IllegalInheritError, include_str!("./error_codes/E2D34.md"),
     // E3XXX Runtime Errors
-    E3M38: ErrorKind::EvaluationError, include_str!("./error_codes/E2D34.md"),
+    E3M38: ErrorKind::EvaluationError, include_str!("./error_codes/E3M38.md"),
 }
 
 // Error messages for WXXXX errors. Each message should start and end with a
 // new line.
 register_warnings! {
     W1001: WarningKind::CompilerWarning, include_str!("./warning_codes/W1001.md"),
+    W2001: WarningKind::UnusedVariableWarning, include_str!("./warning_codes/W2001.md"),
+    W2002: WarningKind::UnusedSchemaWarning, include_str!("./warning_codes/W2002.md"),
+    W2003: WarningKind::UnusedParameterWarning, include_str!("./warning_codes/W2003.md"),
+    W2004: WarningKind::NonExhaustiveWarning, include_str!("./warning_codes/W2004.md"),
+    W2005: WarningKind::UnreachableBranchWarning, include_str!("./warning_codes/W2005.md"),
+    W2006: WarningKind::DeprecatedWarning, include_str!("./warning_codes/W2006.md"),
+    W2007: WarningKind::NamingConventionWarning, include_str!("./warning_codes/W2007.md"),
+    W2008: WarningKind::ConstantConditionWarning, include_str!("./warning_codes/W2008.md"),
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -119,6 +131,10 @@ impl ErrorKind {
     pub fn name(&self) -> String {
         format!("{self:?}")
     }
+    /// Returns the localized error title, whose fluent index is the error name.
+    pub fn title(&self, locale: Locale) -> String {
+        get_message(locale, &self.name(), None).unwrap_or_else(|| self.name())
+    }
     /// Returns the error code.
     pub fn code(&self) -> String {
         match ERRORS.iter().find(|&error_pair| error_pair.1.kind == *self) {
@@ -137,13 +153,21 @@ pub struct Warning {
 }
 
 // Kind of KCL warning.
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum WarningKind {
     // Compile Warnings
     CompilerWarning,
     UnusedImportWarning,
     ReimportWarning,
     ImportPositionWarning,
+    UnusedVariableWarning,
+    UnusedSchemaWarning,
+    UnusedParameterWarning,
+    NonExhaustiveWarning,
+    UnreachableBranchWarning,
+    DeprecatedWarning,
+    NamingConventionWarning,
+    ConstantConditionWarning,
 }
 
 /// Test warning `fmt`
@@ -202,6 +226,10 @@ impl WarningKind {
     pub fn name(&self) -> String {
         format!("{self:?}")
     }
+    /// Returns the localized warning title, whose fluent index is the warning name.
+    pub fn title(&self, locale: Locale) -> String {
+        get_message(locale, &self.name(), None).unwrap_or_else(|| self.name())
+    }
     /// Returns the warning code.
     pub fn code(&self) -> String {
         match WARNINGS
@@ -0,0 +1,46 @@
+//! The long-form explanations of the error and warning codes, which are the markdown
+//! documents under `error_codes` and `warning_codes` registered with the codes.
+use anyhow::{bail, Result};
+
+use crate::error::{ERRORS, WARNINGS};
+
+/// Get the explanation of the error or warning code e.g., `E2G22`, and the kind name
+/// e.g., `TypeError` is also accepted.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::explain::explain;
+///
+/// assert!(explain("E2G22").unwrap().contains("TypeError"));
+/// assert!(explain("e2g22").unwrap().contains("TypeError"));
+/// assert!(explain("TypeError").unwrap().contains("E2G22"));
+/// assert!(explain("E9999").is_err());
+/// ```
+pub fn explain(code: &str) -> Result<String> {
+    let code = code.trim();
+    let errors = ERRORS.iter().map(|(c, e)| (*c, e.kind.name(), e.message));
+    let warnings = WARNINGS.iter().map(|(c, w)| (*c, w.kind.name(), w.message));
+    for (c, name, message) in errors.chain(warnings) {
+        if c.eq_ignore_ascii_case(code) || name == code {
+            return match message {
+                Some(message) => Ok(message.trim().to_string()),
+                None => bail!("no explanation for the code '{}'", c),
+            };
+        }
+    }
+    bail!(
+        "unknown error or warning code '{}', available codes are: {}",
+        code,
+        codes().join(", ")
+    )
+}
+
+/// Get all the registered error and warning codes.
+pub fn codes() -> Vec<&'static str> {
+    ERRORS
+        .iter()
+        .map(|(c, _)| *c)
+        .chain(WARNINGS.iter().map(|(c, _)| *c))
+        .collect()
+}
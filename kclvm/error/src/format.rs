@@ -0,0 +1,457 @@
+//! Structured diagnostic formats, which render the diagnostics as JSON or as
+//! [SARIF 2.1.0](https://docs.oasis-open.org/sarif/sarif/v2.1.0/sarif-v2.1.0.html)
+//! logs, thus the results can be consumed by the tools such as the CI pipelines
+//! and GitHub code scanning, or as HTML and markdown documents with the code
+//! snippets, see [crate::render].
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use compiler_base_session::{Session, SessionDiagnostic};
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+use thiserror::Error;
+
+use crate::diagnostic::{
+    dummy_range, Applicability, Diagnostic, DiagnosticId, Level, Position, Range,
+};
+use crate::locale::Locale;
+use crate::{render, EmittedDiagnostic};
+
+const SARIF_SCHEMA: &str = "https://json.schemastore.org/sarif-2.1.0.json";
+const SARIF_VERSION: &str = "2.1.0";
+const TOOL_NAME: &str = "kcl";
+const TOOL_INFORMATION_URI: &str = "https://kcl-lang.io";
+
+/// The output format of the diagnostics.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum DiagnosticFormat {
+    /// The human readable text with the code snippets.
+    #[default]
+    Human,
+    /// A JSON array of the diagnostics.
+    Json,
+    /// A SARIF 2.1.0 log.
+    Sarif,
+    /// An HTML document with the styled code snippets.
+    Html,
+    /// A markdown document with the code snippets in the code fences.
+    Markdown,
+}
+
+impl DiagnosticFormat {
+    /// Whether the format is a machine readable format or a document format, whose
+    /// output is written as is.
+    #[inline]
+    pub fn is_structured(&self) -> bool {
+        !matches!(self, DiagnosticFormat::Human)
+    }
+}
+
+impl FromStr for DiagnosticFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "human" => Ok(DiagnosticFormat::Human),
+            "json" => Ok(DiagnosticFormat::Json),
+            "sarif" => Ok(DiagnosticFormat::Sarif),
+            "html" => Ok(DiagnosticFormat::Html),
+            "markdown" => Ok(DiagnosticFormat::Markdown),
+            _ => bail!(
+                "invalid diagnostic format '{}', expected one of 'human', 'json', 'sarif', 'html' and 'markdown'",
+                s
+            ),
+        }
+    }
+}
+
+/// The diagnostics already rendered in a structured format, which are returned as the
+/// error by the runner and should be written to the output as is.
+#[derive(Debug, Error)]
+#[error("{0}")]
+pub struct FormattedDiagnostics(pub String);
+
+#[derive(Serialize)]
+struct JsonDiagnostic<'a> {
+    level: &'static str,
+    code: Option<String>,
+    name: Option<String>,
+    messages: Vec<JsonMessage<'a>>,
+    labels: Vec<JsonLabel<'a>>,
+    fixes: Vec<JsonFix<'a>>,
+    source_hash: Option<&'a str>,
+}
+
+#[derive(Serialize)]
+struct JsonLabel<'a> {
+    message: &'a str,
+    filename: &'a str,
+    start: JsonPosition,
+    end: JsonPosition,
+}
+
+#[derive(Serialize)]
+struct JsonFix<'a> {
+    filename: &'a str,
+    start: JsonPosition,
+    end: JsonPosition,
+    replacement: &'a str,
+    applicability: Applicability,
+}
+
+#[derive(Serialize)]
+struct JsonMessage<'a> {
+    message: &'a str,
+    note: Option<&'a str>,
+    filename: &'a str,
+    start: JsonPosition,
+    end: JsonPosition,
+    suggestions: Vec<&'a str>,
+}
+
+/// The 1-based line and the 1-based column.
+#[derive(Serialize)]
+struct JsonPosition {
+    line: u64,
+    column: Option<u64>,
+}
+
+impl From<&Position> for JsonPosition {
+    fn from(pos: &Position) -> Self {
+        JsonPosition {
+            line: pos.line,
+            column: pos.column.map(|c| c + 1),
+        }
+    }
+}
+
+/// Render the diagnostics as a JSON array. Each diagnostic contains its level, code,
+/// name, messages, secondary labels, fix-its and source hash, and each message contains its text, note, source range
+/// and suggested replacements. The lines and the columns are 1-based.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::format::to_json;
+/// use kclvm_error::{Handler, Position};
+///
+/// let mut handler = Handler::default();
+/// handler.add_type_error("expected int, got str", (Position::dummy_pos(), Position::dummy_pos()));
+/// let output = to_json(&handler.diagnostics).unwrap();
+/// assert!(output.contains("\"code\":\"E2G22\""));
+/// ```
+pub fn to_json<'a>(diags: impl IntoIterator<Item = &'a Diagnostic>) -> Result<String> {
+    let diags: Vec<JsonDiagnostic> = diags
+        .into_iter()
+        .map(|diag| JsonDiagnostic {
+            level: diag.level.to_str(),
+            code: code(diag),
+            name: name(diag),
+            messages: diag
+                .messages
+                .iter()
+                .map(|msg| JsonMessage {
+                    message: &msg.message,
+                    note: msg.note.as_deref(),
+                    filename: &msg.range.0.filename,
+                    start: (&msg.range.0).into(),
+                    end: (&msg.range.1).into(),
+                    suggestions: msg
+                        .suggested_replacement
+                        .iter()
+                        .flatten()
+                        .map(|s| s.as_str())
+                        .collect(),
+                })
+                .collect(),
+            labels: diag
+                .labels
+                .iter()
+                .map(|label| JsonLabel {
+                    message: &label.message,
+                    filename: &label.range.0.filename,
+                    start: (&label.range.0).into(),
+                    end: (&label.range.1).into(),
+                })
+                .collect(),
+            fixes: diag
+                .fixes
+                .iter()
+                .map(|fix| JsonFix {
+                    filename: &fix.range.0.filename,
+                    start: (&fix.range.0).into(),
+                    end: (&fix.range.1).into(),
+                    replacement: &fix.replacement,
+                    applicability: fix.applicability,
+                })
+                .collect(),
+            source_hash: diag.source_hash.as_deref(),
+        })
+        .collect();
+    Ok(serde_json::to_string(&diags)?)
+}
+
+/// Render the diagnostics as a SARIF 2.1.0 log with one run. The first message of each
+/// diagnostic is the result location, the other messages and the secondary labels are
+/// the related locations, and the fix-its and the suggested replacements are the fixes.
+/// The source hashes of the diagnostics are the sha-256 hashes of the run artifacts.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::format::to_sarif;
+/// use kclvm_error::{Handler, Position};
+///
+/// let mut handler = Handler::default();
+/// handler.add_type_error("expected int, got str", (Position::dummy_pos(), Position::dummy_pos()));
+/// let output = to_sarif(&handler.diagnostics).unwrap();
+/// assert!(output.contains("\"ruleId\":\"E2G22\""));
+/// ```
+pub fn to_sarif<'a>(diags: impl IntoIterator<Item = &'a Diagnostic>) -> Result<String> {
+    let mut rules: IndexMap<String, Value> = IndexMap::new();
+    let mut artifacts: IndexMap<String, Value> = IndexMap::new();
+    let mut results = vec![];
+    for diag in diags {
+        let (first, rest) = match diag.messages.split_first() {
+            Some(messages) => messages,
+            None => continue,
+        };
+        if let Some(hash) = &diag.source_hash {
+            let uri = to_uri(&first.range.0.filename);
+            artifacts.entry(uri.clone()).or_insert_with(|| {
+                json!({
+                    "location": { "uri": uri },
+                    "hashes": { "sha-256": hash },
+                })
+            });
+        }
+        let mut result = json!({
+            "level": sarif_level(diag.level),
+            "message": { "text": message_text(&first.message, first.note.as_deref()) },
+        });
+        if let Some(location) = sarif_location(&first.range) {
+            result["locations"] = json!([location]);
+        }
+        if let Some(code) = code(diag) {
+            rules.entry(code.clone()).or_insert_with(|| {
+                json!({
+                    "id": code,
+                    "name": name(diag),
+                })
+            });
+            result["ruleId"] = json!(code);
+        }
+        let related: Vec<Value> = rest
+            .iter()
+            .map(|msg| (&msg.range, message_text(&msg.message, msg.note.as_deref())))
+            .chain(
+                diag.labels
+                    .iter()
+                    .map(|label| (&label.range, label.message.clone())),
+            )
+            .filter_map(|(range, text)| {
+                let mut location = sarif_location(range)?;
+                location["message"] = json!({ "text": text });
+                Some(location)
+            })
+            .collect();
+        if !related.is_empty() {
+            result["relatedLocations"] = json!(related);
+        }
+        let mut edits: Vec<(&Range, &str)> = diag
+            .fixes
+            .iter()
+            .map(|fix| (&fix.range, fix.replacement.as_str()))
+            .collect();
+        for edit in diag.suggested_replacements() {
+            if !edits.contains(&edit) {
+                edits.push(edit);
+            }
+        }
+        let fixes: Vec<Value> = edits
+            .into_iter()
+            .filter_map(|(range, replacement)| {
+                let region = sarif_region(range)?;
+                Some(json!({
+                    "description": { "text": if replacement.is_empty() {
+                        "Remove the code".to_string()
+                    } else {
+                        format!("Replace with '{}'", replacement)
+                    } },
+                    "artifactChanges": [{
+                        "artifactLocation": { "uri": to_uri(&range.0.filename) },
+                        "replacements": [{
+                            "deletedRegion": region,
+                            "insertedContent": { "text": replacement },
+                        }],
+                    }],
+                }))
+            })
+            .collect();
+        if !fixes.is_empty() {
+            result["fixes"] = json!(fixes);
+        }
+        results.push(result);
+    }
+    let rules: Vec<Value> = rules.into_values().collect();
+    let mut log = json!({
+        "$schema": SARIF_SCHEMA,
+        "version": SARIF_VERSION,
+        "runs": [{
+            "tool": {
+                "driver": {
+                    "name": TOOL_NAME,
+                    "informationUri": TOOL_INFORMATION_URI,
+                    "rules": rules,
+                }
+            },
+            "results": results,
+        }],
+    });
+    if !artifacts.is_empty() {
+        let artifacts: Vec<Value> = artifacts.into_values().collect();
+        log["runs"][0]["artifacts"] = json!(artifacts);
+    }
+    Ok(serde_json::to_string(&log)?)
+}
+
+/// Render the diagnostics in the format, and the human readable format is rendered
+/// without the code snippets. Use [crate::Handler::emit_to_string_with_format] to
+/// render the code snippets.
+pub fn format_diagnostics<'a>(
+    diags: impl IntoIterator<Item = &'a Diagnostic>,
+    format: DiagnosticFormat,
+) -> Result<String> {
+    match format {
+        DiagnosticFormat::Human => Ok(diags
+            .into_iter()
+            .flat_map(|diag| {
+                diag.messages.iter().map(|msg| {
+                    let info = msg.range.0.info();
+                    if info.is_empty() {
+                        format!("{}: {}", diag.level, msg.message)
+                    } else {
+                        format!("{}: {}\n{}", diag.level, msg.message, info)
+                    }
+                })
+            })
+            .collect::<Vec<String>>()
+            .join("\n")),
+        DiagnosticFormat::Json => to_json(diags),
+        DiagnosticFormat::Sarif => to_sarif(diags),
+        DiagnosticFormat::Html | DiagnosticFormat::Markdown => {
+            render_diagnostics(diags, &Session::default(), Locale::default(), format)
+        }
+    }
+}
+
+/// Render the diagnostics with the code snippets in the document formats i.e., HTML
+/// and markdown. The session is used to render the code snippets of the anonymous
+/// sources e.g., `<stdin>`.
+pub(crate) fn render_diagnostics<'a>(
+    diags: impl IntoIterator<Item = &'a Diagnostic>,
+    sess: &Session,
+    locale: Locale,
+    format: DiagnosticFormat,
+) -> Result<String> {
+    let diags = diags
+        .into_iter()
+        .map(|diag| EmittedDiagnostic::new(diag.clone(), locale, false).into_diagnostic(sess))
+        .collect::<Result<Vec<_>>>()?;
+    match format {
+        DiagnosticFormat::Html => render::to_html(&diags),
+        DiagnosticFormat::Markdown => render::to_markdown(&diags),
+        _ => bail!("'{:?}' is not a document format", format),
+    }
+}
+
+/// Render the error message without any source location e.g., the runtime error
+/// message in the format.
+pub fn format_error_message(message: &str, format: DiagnosticFormat) -> Result<String> {
+    let diag = Diagnostic::new(Level::Error, message, dummy_range());
+    match format {
+        DiagnosticFormat::Human => Ok(message.to_string()),
+        _ => format_diagnostics(&[diag], format),
+    }
+}
+
+fn code(diag: &Diagnostic) -> Option<String> {
+    match &diag.code {
+        Some(DiagnosticId::Error(kind)) => Some(kind.code()),
+        Some(DiagnosticId::Warning(kind)) => Some(kind.code()),
+        _ => None,
+    }
+}
+
+fn name(diag: &Diagnostic) -> Option<String> {
+    match &diag.code {
+        Some(DiagnosticId::Error(kind)) => Some(kind.name()),
+        Some(DiagnosticId::Warning(kind)) => Some(kind.name()),
+        _ => None,
+    }
+}
+
+fn message_text(message: &str, note: Option<&str>) -> String {
+    match note {
+        Some(note) if !note.is_empty() => format!("{}\n{}", message, note),
+        _ => message.to_string(),
+    }
+}
+
+fn sarif_level(level: Level) -> &'static str {
+    match level {
+        Level::Error => "error",
+        Level::Warning => "warning",
+        Level::Note | Level::Suggestions => "note",
+    }
+}
+
+/// The physical location of the range, which is None for the dummy ranges without
+/// a filename.
+fn sarif_location(range: &Range) -> Option<Value> {
+    if range.0.filename.is_empty() {
+        return None;
+    }
+    let mut location = json!({ "artifactLocation": { "uri": to_uri(&range.0.filename) } });
+    if let Some(region) = sarif_region(range) {
+        location["region"] = region;
+    }
+    Some(json!({ "physicalLocation": location }))
+}
+
+/// The SARIF region of the range, whose lines and columns are 1-based.
+fn sarif_region(range: &Range) -> Option<Value> {
+    let (start, end) = range;
+    if !start.is_valid() || start.filename.is_empty() {
+        return None;
+    }
+    let mut region = json!({ "startLine": start.line });
+    if let Some(column) = start.column {
+        region["startColumn"] = json!(column + 1);
+    }
+    if end.is_valid() && end.line >= start.line {
+        region["endLine"] = json!(end.line);
+        if let Some(column) = end.column {
+            region["endColumn"] = json!(column + 1);
+        }
+    }
+    Some(region)
+}
+
+/// Convert the filename to a URI, and the relative paths are kept as the relative
+/// references which are resolved against the root of the repository.
+fn to_uri(filename: &str) -> String {
+    let path = filename.replace('\\', "/");
+    if Path::new(filename).is_absolute() {
+        if path.starts_with('/') {
+            format!("file://{}", path)
+        } else {
+            format!("file:///{}", path)
+        }
+    } else {
+        path
+    }
+}
@@ -4,7 +4,14 @@
 //! We can use `Handler` to create and emit diagnostics.
 
 pub mod diagnostic;
+pub mod emitter;
 mod error;
+pub mod explain;
+pub mod format;
+pub mod locale;
+pub mod render;
+#[cfg(test)]
+mod tests;
 
 use annotate_snippets::{
     display_list::DisplayList,
@@ -16,18 +23,24 @@ use compiler_base_error::errors::ComponentFormatError;
 use compiler_base_error::StyledBuffer;
 use compiler_base_error::{
     components::{CodeSnippet, Label},
-    Component, Diagnostic as DiagnosticTrait, DiagnosticStyle,
+    Component, Diagnostic as DiagnosticTrait, DiagnosticStyle, Emitter,
 };
 use compiler_base_session::{Session, SessionDiagnostic};
 use compiler_base_span::{span::new_byte_pos, Span};
 use diagnostic::Range;
-use indexmap::IndexSet;
+use indexmap::{IndexMap, IndexSet};
 use kclvm_runtime::PanicInfo;
+use kclvm_utils::path::is_anonymous_filename;
 use std::{any::Any, sync::Arc};
 use thiserror::Error;
 
-pub use diagnostic::{Diagnostic, DiagnosticId, Level, Message, Position, Style};
+pub use diagnostic::{
+    Applicability, Diagnostic, DiagnosticId, FixIt, Level, Message, Position, SecondaryLabel, Style,
+};
+pub use emitter::ColorMode;
 pub use error::*;
+pub use format::DiagnosticFormat;
+pub use locale::Locale;
 
 /// A handler deals with errors and other compiler output.
 /// Certain errors (error, bug) may cause immediate exit,
@@ -35,6 +48,18 @@ pub use error::*;
 #[derive(Clone, Debug, PartialEq, Eq, Default)]
 pub struct Handler {
     pub diagnostics: IndexSet<Diagnostic>,
+    /// The maximum number of the errors collected by the handler, and the errors beyond
+    /// it are dropped and summarized. None denotes no limit.
+    pub max_errors: Option<usize>,
+    /// The number of the errors dropped because of `max_errors`.
+    pub dropped_errors: usize,
+    /// The number of the errors stored by `add_diagnostic`, which is checked against
+    /// `max_errors` without counting the diagnostics on each insert.
+    pub added_errors: usize,
+    /// The locale of the emitted diagnostic titles and the messages of the handler.
+    pub locale: Locale,
+    /// The color mode of the emitted human readable diagnostics.
+    pub color: ColorMode,
 }
 
 impl Handler {
@@ -42,9 +67,98 @@ impl Handler {
     pub fn new() -> Self {
         Self {
             diagnostics: Default::default(),
+            max_errors: None,
+            dropped_errors: 0,
+            added_errors: 0,
+            locale: Locale::default(),
+            color: ColorMode::default(),
+        }
+    }
+
+    /// New a handler which collects at most `max_errors` errors.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use kclvm_error::*;
+    /// let mut handler = Handler::with_max_errors(1);
+    /// for msg in ["error 1", "error 2", "error 3"] {
+    ///     handler.add_compile_error(msg, (Position::dummy_pos(), Position::dummy_pos()));
+    /// }
+    /// assert_eq!(handler.error_count(), 1);
+    /// assert_eq!(handler.summary().unwrap().messages[0].message, "and 2 more errors");
+    /// ```
+    pub fn with_max_errors(max_errors: usize) -> Self {
+        Self {
+            max_errors: Some(max_errors),
+            ..Self::new()
         }
     }
 
+    /// Get the number of the collected errors.
+    #[inline]
+    pub fn error_count(&self) -> usize {
+        self.diagnostics
+            .iter()
+            .filter(|diag| diag.is_error())
+            .count()
+    }
+
+    /// The summary diagnostic of the errors dropped because of `max_errors`, e.g.,
+    /// "and 412 more errors".
+    pub fn summary(&self) -> Option<Diagnostic> {
+        if self.dropped_errors == 0 {
+            return None;
+        }
+        let mut args = locale::FluentArgs::new();
+        args.set("count", self.dropped_errors);
+        let message = locale::get_message(self.locale, "more-errors", Some(&args))
+            .unwrap_or_else(|| format!("and {} more errors", self.dropped_errors));
+        Some(Diagnostic::new(
+            Level::Note,
+            &message,
+            diagnostic::dummy_range(),
+        ))
+    }
+
+    /// Attach the handler locale and color mode to the diagnostic for emitting.
+    #[inline]
+    fn to_emitted(&self, diag: Diagnostic) -> EmittedDiagnostic {
+        EmittedDiagnostic {
+            diag,
+            locale: self.locale,
+            color: self.color.enabled(),
+        }
+    }
+
+    /// Group the diagnostics with the identical level, code and messages, which are
+    /// usually reported in different files, into one diagnostic with all their locations.
+    pub fn group_identical_diagnostics(&mut self) {
+        let mut groups: IndexMap<(Level, Option<DiagnosticId>, Vec<String>), Diagnostic> =
+            IndexMap::new();
+        for diag in self.diagnostics.drain(..) {
+            let key = (
+                diag.level,
+                diag.code.clone(),
+                diag.messages
+                    .iter()
+                    .map(|msg| msg.message.clone())
+                    .collect(),
+            );
+            match groups.get_mut(&key) {
+                Some(group) => {
+                    group.messages.extend(diag.messages);
+                    group.labels.extend(diag.labels);
+                    group.fixes.extend(diag.fixes);
+                }
+                None => {
+                    groups.insert(key, diag);
+                }
+            }
+        }
+        self.diagnostics = groups.into_values().collect();
+    }
+
     /// Panic program and report a bug
     #[inline]
     pub fn bug(&self, msg: &str) -> ! {
@@ -60,19 +174,76 @@ impl Handler {
 
     /// Emit all diagnostics and return whether has errors.
     pub fn emit(&mut self) -> Result<bool> {
-        let sess = Session::default();
-        for diag in &self.diagnostics {
-            sess.add_err(diag.clone())?;
+        let mut emitter = emitter::stderr_emitter(self.color.enabled());
+        self.emit_with_emitter(&Session::default(), &mut emitter)
+    }
+
+    /// Emit all diagnostics to the custom emitter and return whether has errors. The
+    /// emitter receives the structured diagnostics, e.g., an emitter forwarding the
+    /// diagnostics to the language server, and the session is used to render the code
+    /// snippets of the anonymous sources e.g., `<stdin>`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use compiler_base_error::errors::ComponentError;
+    /// use compiler_base_error::{
+    ///     emit_diagnostic_to_uncolored_text, Component, Diagnostic as StyledDiagnostic,
+    ///     DiagnosticStyle, Emitter, StyledBuffer,
+    /// };
+    /// use compiler_base_session::Session;
+    /// use kclvm_error::*;
+    ///
+    /// struct CollectEmitter(Vec<String>);
+    ///
+    /// impl Emitter<DiagnosticStyle> for CollectEmitter {
+    ///     fn format_diagnostic(
+    ///         &mut self,
+    ///         diag: &StyledDiagnostic<DiagnosticStyle>,
+    ///     ) -> Result<StyledBuffer<DiagnosticStyle>, ComponentError> {
+    ///         let mut sb = StyledBuffer::<DiagnosticStyle>::new();
+    ///         diag.format(&mut sb, &mut vec![]);
+    ///         Ok(sb)
+    ///     }
+    ///
+    ///     fn emit_diagnostic(&mut self, diag: &StyledDiagnostic<DiagnosticStyle>) -> anyhow::Result<()> {
+    ///         self.0.push(emit_diagnostic_to_uncolored_text(diag)?);
+    ///         Ok(())
+    ///     }
+    /// }
+    ///
+    /// let mut handler = Handler::default();
+    /// handler.add_compile_error("error", (Position::dummy_pos(), Position::dummy_pos()));
+    /// let mut emitter = CollectEmitter(vec![]);
+    /// assert!(handler.emit_with_emitter(&Session::default(), &mut emitter).unwrap());
+    /// assert!(emitter.0[0].starts_with("error[E2L23]: CompileError"));
+    /// ```
+    pub fn emit_with_emitter(
+        &mut self,
+        sess: &Session,
+        emitter: &mut dyn Emitter<DiagnosticStyle>,
+    ) -> Result<bool> {
+        for diag in self.diagnostics.iter().cloned().chain(self.summary()) {
+            emitter.emit_diagnostic(&self.to_emitted(diag).into_diagnostic(sess)?)?;
         }
-        sess.emit_stashed_diagnostics()?;
         Ok(self.has_errors())
     }
 
     /// Emit diagnostic to string.
+    #[inline]
     pub fn emit_to_string(&mut self) -> Result<String> {
-        let sess = Session::default();
-        for diag in &self.diagnostics {
-            sess.add_err(diag.clone())?;
+        self.emit_to_string_with_session(&Session::default())
+    }
+
+    /// Emit diagnostic to string with the session whose source map holds the
+    /// anonymous sources e.g., `<stdin>`, thus their code snippets can be rendered.
+    pub fn emit_to_string_with_session(&mut self, sess: &Session) -> Result<String> {
+        let sess = Session {
+            sm: sess.sm.clone(),
+            ..Default::default()
+        };
+        for diag in self.diagnostics.iter().cloned().chain(self.summary()) {
+            sess.add_err(self.to_emitted(diag))?;
         }
         let errors = sess.emit_all_diags_into_string()?;
         let mut error_strings = vec![];
@@ -82,6 +253,29 @@ impl Handler {
         Ok(error_strings.join("\n"))
     }
 
+    /// Emit diagnostic to string in the format. The session is only used to render
+    /// the code snippets of the human readable and the document formats, see
+    /// [Handler::emit_to_string_with_session].
+    pub fn emit_to_string_with_format(
+        &mut self,
+        sess: &Session,
+        format: DiagnosticFormat,
+    ) -> Result<String> {
+        match format {
+            DiagnosticFormat::Human => self.emit_to_string_with_session(sess),
+            DiagnosticFormat::Html | DiagnosticFormat::Markdown => format::render_diagnostics(
+                self.diagnostics.iter().chain(self.summary().as_ref()),
+                sess,
+                self.locale,
+                format,
+            ),
+            _ => format::format_diagnostics(
+                self.diagnostics.iter().chain(self.summary().as_ref()),
+                format,
+            ),
+        }
+    }
+
     /// Emit all diagnostics and abort if has any errors.
     pub fn abort_if_any_errors(&mut self) {
         match self.emit() {
@@ -175,6 +369,9 @@ impl Handler {
             level: Level::Error,
             messages: msgs.to_owned(),
             code: Some(DiagnosticId::Error(err)),
+            fixes: vec![],
+            labels: vec![],
+            source_hash: None,
         };
         self.add_diagnostic(diag);
 
@@ -193,6 +390,9 @@ impl Handler {
                     suggested_replacement: None,
                 }],
                 code: Some(DiagnosticId::Suggestions),
+                fixes: vec![],
+                labels: vec![],
+                source_hash: None,
             });
         });
 
@@ -218,12 +418,36 @@ impl Handler {
             level: Level::Warning,
             messages: msgs.to_owned(),
             code: Some(DiagnosticId::Warning(warning)),
+            fixes: vec![],
+            labels: vec![],
+            source_hash: None,
         };
         self.add_diagnostic(diag);
 
         self
     }
 
+    /// Promote the warnings of the kinds to errors.
+    pub fn promote_warnings_to_errors(&mut self, kinds: &[WarningKind]) {
+        if kinds.is_empty() {
+            return;
+        }
+        self.diagnostics = self
+            .diagnostics
+            .drain(..)
+            .map(|mut diag| {
+                if let (Level::Warning, Some(DiagnosticId::Warning(kind))) =
+                    (&diag.level, &diag.code)
+                {
+                    if kinds.contains(kind) {
+                        diag.level = Level::Error;
+                    }
+                }
+                diag
+            })
+            .collect();
+    }
+
     /// Classify diagnostics into errors and warnings.
     pub fn classification(&self) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
         let (mut errs, mut warnings) = (IndexSet::new(), IndexSet::new());
@@ -239,7 +463,8 @@ impl Handler {
         (errs, warnings)
     }
 
-    /// Store a diagnostics into the handler.
+    /// Store a diagnostics into the handler, and the errors beyond `max_errors` are
+    /// dropped and counted in `dropped_errors`.
     ///
     /// # Example
     ///
@@ -250,7 +475,19 @@ impl Handler {
     /// ```
     #[inline]
     pub fn add_diagnostic(&mut self, diagnostic: Diagnostic) -> &mut Self {
-        self.diagnostics.insert(diagnostic);
+        let is_error = diagnostic.is_error();
+        if let Some(max_errors) = self.max_errors {
+            if is_error
+                && self.added_errors >= max_errors
+                && !self.diagnostics.contains(&diagnostic)
+            {
+                self.dropped_errors += 1;
+                return self;
+            }
+        }
+        if self.diagnostics.insert(diagnostic) && is_error {
+            self.added_errors += 1;
+        }
 
         self
     }
@@ -348,6 +585,10 @@ pub enum ParseErrorMessage {
     InvalidJoinedStringSpec,
     #[error("invalid joined string")]
     InvalidJoinedStringExpr,
+    #[error("expression is nested too deeply, the maximum expression depth is {0}")]
+    ExprTooDeep(usize),
+    #[error("schema or config is nested too deeply, the maximum nesting depth is {0}")]
+    SchemaNestingTooDeep(usize),
 }
 
 #[derive(Debug, Clone)]
@@ -473,106 +714,127 @@ impl Component<DiagnosticStyle> for SuggestionsLabel {
 }
 
 impl SessionDiagnostic for Diagnostic {
-    fn into_diagnostic(self, _: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
+    fn into_diagnostic(self, sess: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
+        EmittedDiagnostic {
+            diag: self,
+            locale: Locale::default(),
+            color: ColorMode::default().enabled(),
+        }
+        .into_diagnostic(sess)
+    }
+}
+
+/// The diagnostic emitted with the localized titles and help messages, and the code
+/// snippets are rendered without the ANSI styles when `color` is false.
+pub(crate) struct EmittedDiagnostic {
+    diag: Diagnostic,
+    locale: Locale,
+    color: bool,
+}
+
+impl EmittedDiagnostic {
+    #[inline]
+    pub(crate) fn new(diag: Diagnostic, locale: Locale, color: bool) -> Self {
+        Self {
+            diag,
+            locale,
+            color,
+        }
+    }
+}
+
+impl SessionDiagnostic for EmittedDiagnostic {
+    fn into_diagnostic(self, sess: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
         let mut diag = DiagnosticTrait::<DiagnosticStyle>::new();
-        match self.code {
+        match &self.diag.code {
             Some(id) => match id {
                 DiagnosticId::Error(error) => {
                     diag.append_component(Box::new(Label::Error(error.code())));
-                    diag.append_component(Box::new(format!(": {}\n", error.name())));
+                    diag.append_component(Box::new(format!(": {}\n", error.title(self.locale))));
                 }
                 DiagnosticId::Warning(warning) => {
                     diag.append_component(Box::new(Label::Warning(warning.code())));
-                    diag.append_component(Box::new(format!(": {}\n", warning.name())));
+                    diag.append_component(Box::new(format!(": {}\n", warning.title(self.locale))));
                 }
                 DiagnosticId::Suggestions => {
                     diag.append_component(Box::new(SuggestionsLabel));
                 }
             },
-            None => match self.level {
+            None => match self.diag.level {
                 Level::Error => {
-                    diag.append_component(Box::new(format!("{}\n", ErrorKind::EvaluationError)));
+                    diag.append_component(Box::new(format!(
+                        "{}\n",
+                        ErrorKind::EvaluationError.title(self.locale)
+                    )));
                 }
                 Level::Warning => {
-                    diag.append_component(Box::new(format!("{}\n", WarningKind::CompilerWarning)));
+                    diag.append_component(Box::new(format!(
+                        "{}\n",
+                        WarningKind::CompilerWarning.title(self.locale)
+                    )));
                 }
                 Level::Note => {
                     diag.append_component(Box::new(Label::Note));
+                    diag.append_component(Box::new(": ".to_string()));
                 }
                 Level::Suggestions => {
                     diag.append_component(Box::new(SuggestionsLabel));
                 }
             },
         }
-        for msg in &self.messages {
-            match Session::new_with_file_and_code(&msg.range.0.filename, None) {
-                Ok(sess) => {
-                    let source = sess.sm.lookup_source_file(new_byte_pos(0));
-                    let line = source.get_line(
-                        (if msg.range.0.line >= 1 {
-                            msg.range.0.line - 1
-                        } else {
-                            0
-                        }) as usize,
-                    );
-                    match line.as_ref() {
-                        Some(content) => {
-                            let length = content.chars().count();
-                            let snippet = Snippet {
-                                title: None,
-                                footer: vec![],
-                                slices: vec![Slice {
-                                    source: content,
-                                    line_start: msg.range.0.line as usize,
-                                    origin: Some(&msg.range.0.filename),
-                                    annotations: vec![SourceAnnotation {
-                                        range: match msg.range.0.column {
-                                            Some(column) if length >= 1 => {
-                                                let column = column as usize;
-                                                // If the position exceeds the length of the content,
-                                                // put the annotation at the end of the line.
-                                                if column >= length {
-                                                    (length - 1, length)
-                                                } else {
-                                                    (column, column + 1)
-                                                }
-                                            }
-                                            _ => (0, 0),
-                                        },
-                                        label: &msg.message,
-                                        annotation_type: AnnotationType::Error,
-                                    }],
-                                    fold: true,
-                                }],
-                                opt: FormatOptions {
-                                    color: true,
-                                    anonymized_line_numbers: false,
-                                    margin: None,
-                                },
-                            };
-                            let dl = DisplayList::from(snippet);
-                            diag.append_component(Box::new(format!("{dl}\n")));
-                        }
-                        None => {
-                            let info = msg.range.0.info();
-                            if !info.is_empty() {
-                                diag.append_component(Box::new(format!(
-                                    "{}: {}\n",
-                                    info, msg.message
-                                )));
-                            } else {
-                                diag.append_component(Box::new(format!("{}\n", msg.message)));
-                            }
+        for (idx, msg) in self.diag.messages.iter().enumerate() {
+            // The secondary labels are rendered along with the first message.
+            let labels: &[SecondaryLabel] = if idx == 0 { &self.diag.labels } else { &[] };
+            match get_source_line(sess, &msg.range.0).as_deref() {
+                Some(content) => {
+                    let label_sources: Vec<(&SecondaryLabel, Option<String>)> = labels
+                        .iter()
+                        .map(|label| (label, get_source_line(sess, &label.range.0)))
+                        .collect();
+                    let mut slices = vec![source_slice(
+                        content,
+                        &msg.range.0,
+                        &msg.message,
+                        AnnotationType::Error,
+                    )];
+                    for (label, source) in &label_sources {
+                        if let Some(source) = source {
+                            slices.push(source_slice(
+                                source,
+                                &label.range.0,
+                                &label.message,
+                                AnnotationType::Info,
+                            ));
                         }
+                    }
+                    let snippet = Snippet {
+                        title: None,
+                        footer: vec![],
+                        slices,
+                        opt: FormatOptions {
+                            color: self.color,
+                            anonymized_line_numbers: false,
+                            margin: None,
+                        },
                     };
+                    let dl = DisplayList::from(snippet);
+                    diag.append_component(Box::new(format!("{dl}\n")));
+                    for (label, source) in &label_sources {
+                        if source.is_none() {
+                            diag.append_component(Box::new(label_text(label)));
+                        }
+                    }
                 }
-                Err(_) => {
+                None => {
                     let info = msg.range.0.info();
                     if !info.is_empty() {
                         diag.append_component(Box::new(format!("{}: {}\n", info, msg.message)));
                     } else {
                         diag.append_component(Box::new(format!("{}\n", msg.message)));
                     }
+                    for label in labels {
+                        diag.append_component(Box::new(label_text(label)));
+                    }
                 }
             };
             if let Some(note) = &msg.note {
@@ -582,10 +844,93 @@ impl SessionDiagnostic for Diagnostic {
             // Append a new line.
             diag.append_component(Box::new(String::from("\n")));
         }
+        for fix in &self.diag.fixes {
+            diag.append_component(Box::new(Label::Help));
+            diag.append_component(Box::new(format!(": {}\n", fix_help(fix, self.locale))));
+        }
         Ok(diag)
     }
 }
 
+/// The annotated source line of the position in a code snippet.
+fn source_slice<'a>(
+    source: &'a str,
+    pos: &'a Position,
+    label: &'a str,
+    annotation_type: AnnotationType,
+) -> Slice<'a> {
+    let length = source.chars().count();
+    Slice {
+        source,
+        line_start: pos.line as usize,
+        origin: Some(&pos.filename),
+        annotations: vec![SourceAnnotation {
+            range: match pos.column {
+                Some(column) if length >= 1 => {
+                    let column = column as usize;
+                    // If the position exceeds the length of the content,
+                    // put the annotation at the end of the line.
+                    if column >= length {
+                        (length - 1, length)
+                    } else {
+                        (column, column + 1)
+                    }
+                }
+                _ => (0, 0),
+            },
+            label,
+            annotation_type,
+        }],
+        fold: true,
+    }
+}
+
+/// The text of the secondary label whose source line can't be found.
+fn label_text(label: &SecondaryLabel) -> String {
+    let info = label.range.0.info();
+    if info.is_empty() {
+        format!("{}\n", label.message)
+    } else {
+        format!("{}: {}\n", info, label.message)
+    }
+}
+
+/// The help message of the fix-it e.g., "consider replacing the code at main.k:1:1 with `a`".
+fn fix_help(fix: &FixIt, locale: Locale) -> String {
+    let pos = &fix.range.0;
+    let loc = match pos.column {
+        Some(column) => format!("{}:{}:{}", pos.filename, pos.line, column + 1),
+        None => format!("{}:{}", pos.filename, pos.line),
+    };
+    let mut args = locale::FluentArgs::new();
+    args.set("location", loc.as_str());
+    let index = if fix.replacement.is_empty() {
+        "fix-remove"
+    } else {
+        args.set("replacement", fix.replacement.as_str());
+        "fix-replace"
+    };
+    locale::get_message(locale, index, Some(&args)).unwrap_or_default()
+}
+
+/// Get the source line of the position. The anonymous sources e.g., `<stdin>` can't be
+/// read from the disk, so they are looked up in the source map of the session.
+fn get_source_line(sess: &Session, pos: &Position) -> Option<String> {
+    let line = (if pos.line >= 1 { pos.line - 1 } else { 0 }) as usize;
+    if is_anonymous_filename(&pos.filename) {
+        sess.sm
+            .files()
+            .iter()
+            .find(|f| f.name.prefer_remapped().to_string() == pos.filename)
+            .and_then(|f| f.get_line(line).map(|l| l.to_string()))
+    } else {
+        let sess = Session::new_with_file_and_code(&pos.filename, None).ok()?;
+        let source = sess.sm.lookup_source_file(new_byte_pos(0));
+        let line = source.get_line(line).map(|l| l.to_string());
+        line
+    }
+}
+
 impl SessionDiagnostic for StringError {
     fn into_diagnostic(self, _: &Session) -> Result<DiagnosticTrait<DiagnosticStyle>> {
         let mut diag = DiagnosticTrait::<DiagnosticStyle>::new();
@@ -0,0 +1,130 @@
+//! Localized diagnostic messages, which are loaded from the fluent templates (`*.ftl`)
+//! in the `locales` directory. Each locale holds one template file, and the messages
+//! are looked up by their fluent indices e.g., the error kind name `TypeError`, and
+//! the messages missing in a locale fall back to `en-US`.
+//!
+//! For more information about the fluent templates, see <https://projectfluent.org/>.
+use std::str::FromStr;
+
+use anyhow::{bail, Result};
+use fluent::concurrent::FluentBundle;
+use fluent::FluentResource;
+use once_cell::sync::Lazy;
+use serde::{Deserialize, Serialize};
+use unic_langid::{langid, LanguageIdentifier};
+
+pub use fluent::FluentArgs;
+
+/// The locale of the diagnostic messages.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default, Serialize, Deserialize)]
+pub enum Locale {
+    /// English (United States).
+    #[default]
+    #[serde(rename = "en-US")]
+    EnUS,
+    /// Simplified Chinese.
+    #[serde(rename = "zh-CN")]
+    ZhCN,
+}
+
+impl Locale {
+    /// Returns the language identifier of the locale e.g., `zh-CN`.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Locale::EnUS => "en-US",
+            Locale::ZhCN => "zh-CN",
+        }
+    }
+}
+
+impl std::fmt::Display for Locale {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for Locale {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.replace('_', "-").to_lowercase().as_str() {
+            "en" | "en-us" => Ok(Locale::EnUS),
+            "zh" | "zh-cn" => Ok(Locale::ZhCN),
+            _ => bail!(
+                "invalid locale '{}', expected one of 'en-US' and 'zh-CN'",
+                s
+            ),
+        }
+    }
+}
+
+type Bundle = FluentBundle<FluentResource>;
+
+static EN_US: Lazy<Bundle> = Lazy::new(|| {
+    new_bundle(
+        langid!("en-US"),
+        include_str!("./locales/en-US/kclvm_error.ftl"),
+    )
+});
+
+static ZH_CN: Lazy<Bundle> = Lazy::new(|| {
+    new_bundle(
+        langid!("zh-CN"),
+        include_str!("./locales/zh-CN/kclvm_error.ftl"),
+    )
+});
+
+fn new_bundle(lang: LanguageIdentifier, template: &str) -> Bundle {
+    let mut bundle = Bundle::new_concurrent(vec![lang]);
+    // Don't wrap the arguments with the unicode isolation marks, which are printed
+    // as is in the terminal.
+    bundle.set_use_isolating(false);
+    let resource = FluentResource::try_new(template.to_string())
+        .expect("Failed to parse the diagnostic message templates");
+    bundle
+        .add_resource(resource)
+        .expect("Failed to add the diagnostic message templates");
+    bundle
+}
+
+fn get_bundle(locale: Locale) -> &'static Bundle {
+    match locale {
+        Locale::EnUS => &EN_US,
+        Locale::ZhCN => &ZH_CN,
+    }
+}
+
+/// Get the localized message by its fluent index and arguments, and the message
+/// missing in the locale falls back to `en-US`. Returns None if the index doesn't
+/// exist in any locale.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::locale::{get_message, FluentArgs, Locale};
+///
+/// assert_eq!(get_message(Locale::ZhCN, "TypeError", None).unwrap(), "类型错误");
+/// let mut args = FluentArgs::new();
+/// args.set("count", 2);
+/// assert_eq!(
+///     get_message(Locale::EnUS, "more-errors", Some(&args)).unwrap(),
+///     "and 2 more errors"
+/// );
+/// assert!(get_message(Locale::EnUS, "not-exist", None).is_none());
+/// ```
+pub fn get_message(locale: Locale, index: &str, args: Option<&FluentArgs>) -> Option<String> {
+    let (bundle, msg) = match get_bundle(locale).get_message(index) {
+        Some(msg) => (get_bundle(locale), msg),
+        None => (
+            get_bundle(Locale::EnUS),
+            get_bundle(Locale::EnUS).get_message(index)?,
+        ),
+    };
+    let pattern = msg.value()?;
+    let mut errors = vec![];
+    Some(
+        bundle
+            .format_pattern(pattern, args, &mut errors)
+            .into_owned(),
+    )
+}
@@ -0,0 +1,58 @@
+## The titles of the error kinds.
+InvalidSyntax = InvalidSyntax
+TabError = TabError
+IndentationError = IndentationError
+IllegalArgumentSyntax = IllegalArgumentSyntax
+CannotFindModule = CannotFindModule
+RecursiveLoad = RecursiveLoad
+FloatOverflow = FloatOverflow
+FloatUnderflow = FloatUnderflow
+IntOverflow = IntOverflow
+InvalidDocstring = InvalidDocstring
+Deprecated = Deprecated
+UnKnownDecorator = UnKnownDecorator
+InvalidDecoratorTarget = InvalidDecoratorTarget
+InvalidFormatSpec = InvalidFormatSpec
+SchemaCheckFailure = SchemaCheckFailure
+IndexSignatureError = IndexSignatureError
+TypeError = TypeError
+NameError = NameError
+ValueError = ValueError
+KeyError = KeyError
+AttributeError = AttributeError
+AssertionError = AssertionError
+ImmutableError = ImmutableError
+MultiInheritError = MultiInheritError
+CycleInheritError = CycleInheritError
+IllegalInheritError = IllegalInheritError
+IllegalAttributeError = IllegalAttributeError
+IllegalParameterError = IllegalParameterError
+RecursionError = RecursionError
+PlanError = PlanError
+CannotAddMembers = CannotAddMembers
+CompileError = CompileError
+EvaluationError = EvaluationError
+UniqueKeyError = UniqueKeyError
+
+## The titles of the warning kinds.
+CompilerWarning = CompilerWarning
+UnusedImportWarning = UnusedImportWarning
+ReimportWarning = ReimportWarning
+ImportPositionWarning = ImportPositionWarning
+UnusedVariableWarning = UnusedVariableWarning
+UnusedSchemaWarning = UnusedSchemaWarning
+UnusedParameterWarning = UnusedParameterWarning
+NonExhaustiveWarning = NonExhaustiveWarning
+UnreachableBranchWarning = UnreachableBranchWarning
+DeprecatedWarning = DeprecatedWarning
+NamingConventionWarning = NamingConventionWarning
+ConstantConditionWarning = ConstantConditionWarning
+
+## The messages of the handler.
+more-errors =
+    { $count ->
+        [one] and { $count } more error
+       *[other] and { $count } more errors
+    }
+fix-replace = consider replacing the code at { $location } with `{ $replacement }`
+fix-remove = consider removing the code at { $location }
@@ -0,0 +1,54 @@
+## The titles of the error kinds.
+InvalidSyntax = 语法错误
+TabError = 制表符错误
+IndentationError = 缩进错误
+IllegalArgumentSyntax = 非法参数语法
+CannotFindModule = 无法找到模块
+RecursiveLoad = 递归加载
+FloatOverflow = 浮点数上溢
+FloatUnderflow = 浮点数下溢
+IntOverflow = 整数溢出
+InvalidDocstring = 非法文档字符串
+Deprecated = 已废弃
+UnKnownDecorator = 未知装饰器
+InvalidDecoratorTarget = 非法装饰器目标
+InvalidFormatSpec = 非法格式说明符
+SchemaCheckFailure = 模型检查失败
+IndexSignatureError = 索引签名错误
+TypeError = 类型错误
+NameError = 名称错误
+ValueError = 值错误
+KeyError = 键错误
+AttributeError = 属性错误
+AssertionError = 断言错误
+ImmutableError = 不可变错误
+MultiInheritError = 多继承错误
+CycleInheritError = 循环继承错误
+IllegalInheritError = 非法继承错误
+IllegalAttributeError = 非法属性错误
+IllegalParameterError = 非法参数错误
+RecursionError = 递归错误
+PlanError = 输出错误
+CannotAddMembers = 无法添加成员
+CompileError = 编译错误
+EvaluationError = 求值错误
+UniqueKeyError = 唯一键错误
+
+## The titles of the warning kinds.
+CompilerWarning = 编译警告
+UnusedImportWarning = 未使用的导入
+ReimportWarning = 重复导入
+ImportPositionWarning = 导入位置警告
+UnusedVariableWarning = 未使用的变量
+UnusedSchemaWarning = 未使用的模型
+UnusedParameterWarning = 未使用的参数
+NonExhaustiveWarning = 未穷尽的条件
+UnreachableBranchWarning = 不可达的分支
+DeprecatedWarning = 已废弃警告
+NamingConventionWarning = 命名规范警告
+ConstantConditionWarning = 常量条件警告
+
+## The messages of the handler.
+more-errors = 以及另外 { $count } 个错误
+fix-replace = 考虑将 { $location } 处的代码替换为 `{ $replacement }`
+fix-remove = 考虑删除 { $location } 处的代码
@@ -0,0 +1,137 @@
+//! Render the styled diagnostics as HTML or markdown, thus the web consoles and the
+//! PR bots can show the same code snippets as the terminal.
+//!
+//! In HTML, each styled text is wrapped in a `<span>` with the class of its style
+//! e.g., `<span class="kcl-need-fix">error</span>`, and the page can define
+//! the colors of the classes. In markdown, each diagnostic is rendered as a plain
+//! text code fence.
+use anyhow::{bail, Result};
+use compiler_base_error::errors::ComponentFormatError;
+use compiler_base_error::{Component, Diagnostic, DiagnosticStyle, StyledBuffer, StyledString};
+
+/// Render the diagnostics as HTML, and each diagnostic is a `<pre>` element.
+///
+/// # Examples
+///
+/// ```
+/// use compiler_base_error::{components::Label, Diagnostic};
+/// use kclvm_error::render::to_html;
+///
+/// let mut diag = Diagnostic::new();
+/// diag.append_component(Box::new(Label::Error("E2G22".to_string())));
+/// diag.append_component(Box::new(": expected <int>".to_string()));
+/// let html = to_html(&[diag]).unwrap();
+/// assert!(html.contains("<span class=\"kcl-need-fix\">error</span>"));
+/// assert!(html.contains("<span class=\"kcl-helpful\">[E2G22]</span>: expected &lt;int&gt;</pre>"));
+/// ```
+pub fn to_html(diags: &[Diagnostic<DiagnosticStyle>]) -> Result<String> {
+    let mut html = String::from("<div class=\"kcl-diagnostics\">\n");
+    for diag in diags {
+        html.push_str("<pre class=\"kcl-diagnostic\">");
+        let lines = render_lines(diag)?;
+        for (idx, line) in lines.iter().enumerate() {
+            if idx > 0 {
+                html.push('\n');
+            }
+            for styled in line {
+                let text = escape_html(&styled.text);
+                match &styled.style {
+                    Some(style) => html.push_str(&format!(
+                        "<span class=\"{}\">{}</span>",
+                        style_class(style),
+                        text
+                    )),
+                    None => html.push_str(&text),
+                }
+            }
+        }
+        html.push_str("</pre>\n");
+    }
+    html.push_str("</div>\n");
+    Ok(html)
+}
+
+/// Render the diagnostics as markdown, and each diagnostic is a code fence.
+///
+/// # Examples
+///
+/// ```
+/// use compiler_base_error::{components::Label, Diagnostic};
+/// use kclvm_error::render::to_markdown;
+///
+/// let mut diag = Diagnostic::new();
+/// diag.append_component(Box::new(Label::Error("E2G22".to_string())));
+/// diag.append_component(Box::new(": expected int".to_string()));
+/// assert_eq!(
+///     to_markdown(&[diag]).unwrap(),
+///     "```text\nerror[E2G22]: expected int\n```\n"
+/// );
+/// ```
+pub fn to_markdown(diags: &[Diagnostic<DiagnosticStyle>]) -> Result<String> {
+    let mut blocks = vec![];
+    for diag in diags {
+        let text = render_lines(diag)?
+            .iter()
+            .map(|line| {
+                line.iter()
+                    .map(|styled| styled.text.as_str())
+                    .collect::<String>()
+            })
+            .collect::<Vec<String>>()
+            .join("\n");
+        let text = text.trim_end_matches('\n');
+        let fence = "`".repeat(max_backtick_run(text).max(2) + 1);
+        blocks.push(format!("{fence}text\n{text}\n{fence}\n"));
+    }
+    Ok(blocks.join("\n"))
+}
+
+fn render_lines(
+    diag: &Diagnostic<DiagnosticStyle>,
+) -> Result<Vec<Vec<StyledString<DiagnosticStyle>>>> {
+    let mut sb = StyledBuffer::<DiagnosticStyle>::new();
+    let mut errs: Vec<ComponentFormatError> = vec![];
+    diag.format(&mut sb, &mut errs);
+    if !errs.is_empty() {
+        bail!("failed to render the diagnostic: {:?}", errs);
+    }
+    Ok(sb.render())
+}
+
+/// The HTML class of the diagnostic style.
+fn style_class(style: &DiagnosticStyle) -> &'static str {
+    match style {
+        DiagnosticStyle::Logo => "kcl-logo",
+        DiagnosticStyle::NeedFix => "kcl-need-fix",
+        DiagnosticStyle::NeedAttention => "kcl-need-attention",
+        DiagnosticStyle::Helpful => "kcl-helpful",
+        DiagnosticStyle::Important => "kcl-important",
+        DiagnosticStyle::Url => "kcl-url",
+    }
+}
+
+/// Escape the HTML special characters in the text, which is used in the HTML element
+/// contents and the quoted attribute values.
+pub fn escape_html(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+/// The longest run of the backticks in the text, and the code fence must be longer
+/// than it.
+fn max_backtick_run(text: &str) -> usize {
+    text.split(|c| c != '`')
+        .map(|run| run.len())
+        .max()
+        .unwrap_or(0)
+}
@@ -0,0 +1,130 @@
+use crate::explain::explain;
+use crate::locale::{get_message, Locale};
+use crate::*;
+
+const ERROR_KINDS: &[ErrorKind] = &[
+    ErrorKind::InvalidSyntax,
+    ErrorKind::TabError,
+    ErrorKind::IndentationError,
+    ErrorKind::IllegalArgumentSyntax,
+    ErrorKind::CannotFindModule,
+    ErrorKind::RecursiveLoad,
+    ErrorKind::FloatOverflow,
+    ErrorKind::FloatUnderflow,
+    ErrorKind::IntOverflow,
+    ErrorKind::InvalidDocstring,
+    ErrorKind::Deprecated,
+    ErrorKind::UnKnownDecorator,
+    ErrorKind::InvalidDecoratorTarget,
+    ErrorKind::InvalidFormatSpec,
+    ErrorKind::SchemaCheckFailure,
+    ErrorKind::IndexSignatureError,
+    ErrorKind::TypeError,
+    ErrorKind::NameError,
+    ErrorKind::ValueError,
+    ErrorKind::KeyError,
+    ErrorKind::AttributeError,
+    ErrorKind::AssertionError,
+    ErrorKind::ImmutableError,
+    ErrorKind::MultiInheritError,
+    ErrorKind::CycleInheritError,
+    ErrorKind::IllegalInheritError,
+    ErrorKind::IllegalAttributeError,
+    ErrorKind::IllegalParameterError,
+    ErrorKind::RecursionError,
+    ErrorKind::PlanError,
+    ErrorKind::CannotAddMembers,
+    ErrorKind::CompileError,
+    ErrorKind::EvaluationError,
+    ErrorKind::UniqueKeyError,
+];
+
+const WARNING_KINDS: &[WarningKind] = &[
+    WarningKind::CompilerWarning,
+    WarningKind::UnusedImportWarning,
+    WarningKind::ReimportWarning,
+    WarningKind::ImportPositionWarning,
+    WarningKind::UnusedVariableWarning,
+    WarningKind::UnusedSchemaWarning,
+    WarningKind::UnusedParameterWarning,
+    WarningKind::NonExhaustiveWarning,
+    WarningKind::UnreachableBranchWarning,
+    WarningKind::DeprecatedWarning,
+    WarningKind::NamingConventionWarning,
+    WarningKind::ConstantConditionWarning,
+];
+
+fn check_docs(code: &str, name: &str) {
+    let doc = explain(code).unwrap_or_else(|err| panic!("{name}: {err}"));
+    assert!(
+        doc.contains(code) && doc.contains(name),
+        "the explanation of {code} should describe {name}"
+    );
+    assert_eq!(explain(name).unwrap(), doc);
+    assert!(
+        get_message(Locale::EnUS, name, None).is_some(),
+        "the en-US title of {name} is missing"
+    );
+    assert!(
+        get_message(Locale::ZhCN, name, None).unwrap() != name,
+        "the zh-CN title of {name} is missing"
+    );
+}
+
+#[test]
+fn test_error_kinds_registered() {
+    for kind in ERROR_KINDS {
+        let code = kind.code();
+        match ERRORS.iter().filter(|(_, e)| e.kind == *kind).count() {
+            0 => assert_eq!(code, "E1001", "{kind} without a code should keep E1001"),
+            1 => check_docs(&code, &kind.name()),
+            _ => panic!("{kind} should be registered once"),
+        }
+    }
+}
+
+#[test]
+fn test_warning_kinds_registered() {
+    for kind in WARNING_KINDS {
+        let code = kind.code();
+        match WARNINGS.iter().filter(|(_, w)| w.kind == *kind).count() {
+            0 => assert_eq!(code, "W1001", "{kind} without a code should keep W1001"),
+            1 => check_docs(&code, &kind.name()),
+            _ => panic!("{kind} should be registered once"),
+        }
+    }
+}
+
+#[test]
+fn test_error_codes_stable() {
+    assert_eq!(ErrorKind::InvalidSyntax.code(), "E1001");
+    assert_eq!(ErrorKind::ImmutableError.code(), "E1001");
+    assert_eq!(ErrorKind::TypeError.code(), "E2G22");
+    assert_eq!(ErrorKind::CompileError.code(), "E2L23");
+    assert_eq!(WarningKind::CompilerWarning.code(), "W1001");
+    assert_eq!(WarningKind::UnusedImportWarning.code(), "W1001");
+    assert_eq!(WarningKind::UnusedVariableWarning.code(), "W2001");
+}
+
+#[test]
+fn test_handler_max_errors() {
+    let range = (Position::dummy_pos(), Position::dummy_pos());
+    let mut handler = Handler::with_max_errors(2);
+    for msg in ["error 1", "error 1", "error 2", "error 3", "error 2"] {
+        handler.add_compile_error(msg, range.clone());
+    }
+    handler.add_warning(
+        WarningKind::UnusedImportWarning,
+        &[Message {
+            range,
+            style: Style::Line,
+            message: "warning".to_string(),
+            note: None,
+            suggested_replacement: None,
+        }],
+    );
+    assert_eq!(handler.added_errors, 2);
+    assert_eq!(handler.error_count(), 2);
+    assert_eq!(handler.dropped_errors, 1);
+    assert_eq!(handler.diagnostics.len(), 3);
+}
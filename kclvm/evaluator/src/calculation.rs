@@ -189,11 +189,17 @@ impl<'ctx> Evaluator<'ctx> {
     /// Append a item into the list.
     #[inline]
     pub(crate) fn list_append(&self, list: &mut ValueRef, item: &ValueRef) {
+        self.record_allocations(1);
         list.list_append(item)
     }
     /// Append a list item and unpack it into the list.
     #[inline]
     pub(crate) fn list_append_unpack(&self, list: &mut ValueRef, item: &ValueRef) {
+        self.record_allocations(if item.is_list_or_config() {
+            item.len()
+        } else {
+            1
+        });
         list.list_append_unpack(item)
     }
     #[inline]
@@ -216,6 +222,7 @@ impl<'ctx> Evaluator<'ctx> {
             ast::ConfigEntryOperation::Override => ConfigEntryOperationKind::Override,
             ast::ConfigEntryOperation::Insert => ConfigEntryOperationKind::Insert,
         };
+        self.record_allocations(1);
         self.dict_merge_key_value_pair(dict, key, value, op, insert_index, true);
     }
 
@@ -251,16 +251,17 @@ impl<'ctx> Evaluator<'ctx> {
 
     pub(crate) fn push_backtrace(&self, frame: &Frame) {
         let ctx = &mut self.runtime_ctx.borrow_mut();
-        if ctx.cfg.debug_mode {
+        if ctx.is_backtrace_enabled() {
             let backtrace_frame = BacktraceFrame::from_panic_info(&ctx.panic_info);
             ctx.backtrace.push(backtrace_frame);
             ctx.panic_info.kcl_func = frame.proxy.get_name();
+            ctx.check_call_depth();
         }
     }
 
     pub(crate) fn pop_backtrace(&self) {
         let ctx = &mut self.runtime_ctx.borrow_mut();
-        if ctx.cfg.debug_mode {
+        if ctx.is_backtrace_enabled() {
             if let Some(backtrace_frame) = ctx.backtrace.pop() {
                 ctx.panic_info.kcl_func = backtrace_frame.func;
                 ctx.panic_info.kcl_line = backtrace_frame.line;
@@ -269,6 +270,18 @@ impl<'ctx> Evaluator<'ctx> {
         }
     }
 
+    /// Check the timeout limit of the evaluation.
+    #[inline]
+    pub(crate) fn check_deadline(&self) {
+        self.runtime_ctx.borrow_mut().check_deadline();
+    }
+
+    /// Record the allocated list or dict items for the allocation limit.
+    #[inline]
+    pub(crate) fn record_allocations(&self, n: usize) {
+        self.runtime_ctx.borrow_mut().record_allocations(n);
+    }
+
     #[inline]
     pub(crate) fn push_backtrack_meta(&self, setter: &Setter) {
         let meta = &mut self.backtrack_meta.borrow_mut();
@@ -8,7 +8,7 @@ use kclvm_runtime::ValueRef;
 use scopeguard::defer;
 
 use crate::proxy::Proxy;
-use crate::ty::type_pack_and_check;
+use crate::ty::{runtime_type_str, type_pack_and_check};
 use crate::Evaluator;
 use crate::{error as kcl_error, EvalContext};
 
@@ -153,7 +153,7 @@ pub fn func_body(
         .walk_stmts(&ctx.node.body)
         .expect(kcl_error::RUNTIME_ERROR_MSG);
     if let Some(ty) = &ctx.node.return_ty {
-        result = type_pack_and_check(s, &result, vec![&ty.node.to_string()], false);
+        result = type_pack_and_check(s, &result, vec![&runtime_type_str(s, &ty.node)], false);
     }
     result
 }
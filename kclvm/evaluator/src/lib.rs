@@ -25,16 +25,18 @@ extern crate kclvm_error;
 use func::FunctionEvalContextRef;
 use generational_arena::{Arena, Index};
 use indexmap::IndexMap;
-use kclvm_runtime::val_plan::KCL_PRIVATE_VAR_PREFIX;
+use kclvm_runtime::val_plan::{PlanFormat, ProvenanceMode, KCL_PRIVATE_VAR_PREFIX};
 use lazy::{BacktrackMeta, LazyEvalScope};
 use proxy::{Frame, Proxy};
 use rule::RuleEvalContextRef;
 use schema::SchemaEvalContextRef;
 use scope::Scope;
 use std::collections::{HashMap, HashSet};
+use std::io::Write;
 use std::panic::RefUnwindSafe;
 use std::rc::Rc;
 use std::str;
+use std::time::Duration;
 use std::{cell::RefCell, panic::UnwindSafe};
 
 use crate::error as kcl_error;
@@ -89,6 +91,9 @@ pub struct Evaluator<'ctx> {
     pub backtrack_meta: RefCell<Vec<BacktrackMeta>>,
     /// Current AST id for the evaluator walker.
     pub ast_id: RefCell<AstIndex>,
+    /// The evaluation time of the imported packages in the import order, and the time
+    /// of a package includes the nested imports evaluated for the first time.
+    pub pkg_eval_times: RefCell<Vec<(String, Duration)>>,
 }
 
 #[derive(Clone)]
@@ -147,17 +152,45 @@ impl<'ctx> Evaluator<'ctx> {
             local_vars: RefCell::new(Default::default()),
             backtrack_meta: RefCell::new(Default::default()),
             ast_id: RefCell::new(AstIndex::default()),
+            pkg_eval_times: RefCell::new(Default::default()),
         }
     }
 
     /// Evaluate the program and return the JSON and YAML result.
     pub fn run(self: &Evaluator<'ctx>) -> Result<(String, String)> {
         let modules = self.program.get_modules_for_pkg(kclvm_ast::MAIN_PKG);
+        self.runtime_ctx.borrow_mut().start_deadline();
         self.init_scope(kclvm_ast::MAIN_PKG);
         self.compile_ast_modules(&modules);
         Ok(self.plan_globals_to_string())
     }
 
+    /// Evaluate the program and write the planned JSON or YAML documents to the
+    /// writer one by one instead of returning the whole result string, which is
+    /// useful for the programs producing very large plans.
+    pub fn run_to_writer(
+        self: &Evaluator<'ctx>,
+        format: PlanFormat,
+        w: &mut dyn Write,
+    ) -> Result<()> {
+        let modules = self.program.get_modules_for_pkg(kclvm_ast::MAIN_PKG);
+        self.runtime_ctx.borrow_mut().start_deadline();
+        self.init_scope(kclvm_ast::MAIN_PKG);
+        self.compile_ast_modules(&modules);
+        self.plan_value_to_writer(&self.globals_value(), format, w)?;
+        Ok(())
+    }
+
+    /// Evaluate the program and split the plan result into the files with the filename
+    /// template, see [ValueRef::plan_to_files].
+    pub fn run_to_files(self: &Evaluator<'ctx>, template: &str) -> Result<Vec<(String, String)>> {
+        let modules = self.program.get_modules_for_pkg(kclvm_ast::MAIN_PKG);
+        self.runtime_ctx.borrow_mut().start_deadline();
+        self.init_scope(kclvm_ast::MAIN_PKG);
+        self.compile_ast_modules(&modules);
+        Ok(self.plan_value_to_files(&self.globals_value(), template))
+    }
+
     /// Evaluate the program with the function mode and return the JSON and YAML result,
     /// which means treating the files in the entire main package as a function run to
     /// return the result of the function run, rather than a dictionary composed of each
@@ -167,6 +200,7 @@ impl<'ctx> Evaluator<'ctx> {
         if modules.is_empty() {
             ValueRef::undefined()
         } else {
+            self.runtime_ctx.borrow_mut().start_deadline();
             self.init_scope(kclvm_ast::MAIN_PKG);
             self.compile_ast_modules(&modules)
         }
@@ -174,6 +208,11 @@ impl<'ctx> Evaluator<'ctx> {
 
     /// Plan globals to a planed json and yaml string.
     pub(crate) fn plan_globals_to_string(&self) -> (String, String) {
+        self.plan_value(&self.globals_value())
+    }
+
+    /// Returns the config value composed of the global scalars and variables to plan.
+    fn globals_value(&self) -> ValueRef {
         let current_pkgpath = self.current_pkgpath();
         let pkg_scopes = &self.pkg_scopes.borrow();
         let scopes = pkg_scopes
@@ -187,7 +226,7 @@ impl<'ctx> Evaluator<'ctx> {
         let mut global_dict = self.dict_value();
         // Plan empty dict result.
         if scalars.is_empty() && globals.is_empty() {
-            return self.plan_value(&global_dict);
+            return global_dict;
         }
         // Deal scalars
         for scalar in scalars.iter() {
@@ -204,11 +243,9 @@ impl<'ctx> Evaluator<'ctx> {
             self.dict_insert_merge_value(&mut value_dict, name.as_str(), value);
             self.dict_insert_merge_value(&mut global_dict, SCALAR_KEY, &value_dict);
         }
-        // Plan result to JSON and YAML string.
-        match global_dict.dict_get_value(SCALAR_KEY) {
-            Some(value) => self.plan_value(&value),
-            None => self.plan_value(&self.dict_value()),
-        }
+        global_dict
+            .dict_get_value(SCALAR_KEY)
+            .unwrap_or_else(|| self.dict_value())
     }
 
     /// Get evaluator default ok result
@@ -219,15 +256,47 @@ impl<'ctx> Evaluator<'ctx> {
 
     pub fn plan_value(&self, value: &ValueRef) -> (String, String) {
         let mut ctx = self.runtime_ctx.borrow_mut();
-        let value = match ctx.buffer.custom_manifests_output.clone() {
-            Some(output) => ValueRef::from_yaml_stream(&mut ctx, &output).unwrap(),
-            None => value.clone(),
-        };
+        let value = manifests_or_value(&mut ctx, value);
         let (json_string, yaml_string) = value.plan(&ctx);
         ctx.json_result = json_string.clone();
         ctx.yaml_result = yaml_string.clone();
+        if ctx.plan_opts.provenance == ProvenanceMode::Sidecar {
+            ctx.provenance_result = value.plan_provenance_json(&ctx);
+        }
         (json_string, yaml_string)
     }
+
+    /// Plan the value and write the JSON or YAML documents to the writer, and the
+    /// plan result is not kept in the runtime context.
+    pub fn plan_value_to_writer(
+        &self,
+        value: &ValueRef,
+        format: PlanFormat,
+        w: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        let value = manifests_or_value(&mut ctx, value);
+        value.plan_to_writer(&ctx, format, w)
+    }
+
+    /// Plan the value and split the result into the files, and each document of the
+    /// custom manifests output is a file.
+    pub fn plan_value_to_files(&self, value: &ValueRef, template: &str) -> Vec<(String, String)> {
+        let mut ctx = self.runtime_ctx.borrow_mut();
+        let value = match ctx.buffer.custom_manifests_output.clone() {
+            Some(output) => ValueRef::list_from_yaml_stream(&mut ctx, &output).unwrap(),
+            None => value.clone(),
+        };
+        value.plan_to_files(&ctx, template)
+    }
+}
+
+/// Returns the custom manifests output value if exists, otherwise the value itself.
+fn manifests_or_value(ctx: &mut Context, value: &ValueRef) -> ValueRef {
+    match ctx.buffer.custom_manifests_output.clone() {
+        Some(output) => ValueRef::from_yaml_stream(ctx, &output).unwrap(),
+        None => value.clone(),
+    }
 }
 
 impl UnwindSafe for Evaluator<'_> {}
@@ -3,6 +3,7 @@
 use std::cell::RefCell;
 use std::rc::Rc;
 use std::sync::{Arc, RwLock};
+use std::time::Instant;
 
 use anyhow::Ok;
 use generational_arena::Index;
@@ -10,7 +11,7 @@ use kclvm_ast::ast::{self, CallExpr, ConfigEntry, Module, NodeRef};
 use kclvm_ast::walker::TypedResultWalker;
 use kclvm_runtime::{
     schema_assert, schema_runtime_type, ConfigEntryOperationKind, DecoratorValue, RuntimeErrorType,
-    UnionOptions, ValueRef, PKG_PATH_PREFIX,
+    UnionOptions, ValueRef, PKG_PATH_PREFIX, SECRET_DECORATOR,
 };
 use kclvm_sema::{builtin, pkgpath_without_prefix, plugin};
 use scopeguard::defer;
@@ -22,7 +23,7 @@ use crate::proxy::Proxy;
 use crate::rule::{rule_body, rule_check, RuleCaller, RuleEvalContext};
 use crate::runtime::invoke_function;
 use crate::schema::{schema_body, schema_check, SchemaCaller, SchemaEvalContext};
-use crate::ty::type_pack_and_check;
+use crate::ty::{runtime_type_str, type_pack_and_check};
 use crate::union::union_entry;
 use crate::{backtrack_break_here, backtrack_update_break};
 use crate::{error as kcl_error, GLOBAL_LEVEL, INNER_LEVEL};
@@ -108,7 +109,8 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         let mut value = self.walk_expr(&assign_stmt.value)?;
         // Runtime type cast if exists the type annotation.
         if let Some(ty) = &assign_stmt.ty {
-            value = type_pack_and_check(self, &value, vec![&ty.node.to_string()], false);
+            value =
+                type_pack_and_check(self, &value, vec![&runtime_type_str(self, &ty.node)], false);
         }
         if assign_stmt.targets.len() == 1 {
             // Store the single target
@@ -231,6 +233,12 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         } else {
             let pkgpath = format!("{}{}", PKG_PATH_PREFIX, import_stmt.path.node);
             if let Some(modules) = self.program.pkgs.get(&import_stmt.path.node) {
+                // Record the evaluation time of the package in the profiling mode.
+                let start = if self.runtime_ctx.borrow().cfg.profile {
+                    Some(Instant::now())
+                } else {
+                    None
+                };
                 self.push_pkgpath(&pkgpath);
                 self.init_scope(&pkgpath);
                 let modules: Vec<Arc<RwLock<Module>>> = modules
@@ -245,6 +253,11 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
                     .collect();
                 self.compile_ast_modules(&modules);
                 self.pop_pkgpath();
+                if let Some(start) = start {
+                    self.pkg_eval_times
+                        .borrow_mut()
+                        .push((import_stmt.path.node.clone(), start.elapsed()));
+                }
             }
         }
         self.mark_imported(pkgpath);
@@ -372,6 +385,7 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         // Start block
         while let Some((next_value, key, value)) = iter_value.next_with_key_value(&iter_host_value)
         {
+            self.check_deadline();
             // Next value block
             let variables = &quant_expr.variables;
             for v in variables {
@@ -456,7 +470,10 @@ impl<'ctx> TypedResultWalker<'ctx> for Evaluator<'ctx> {
         let (mut schema_value, config_value, _) = self
             .get_schema_or_rule_config_info()
             .expect(kcl_error::INTERNAL_ERROR_MSG);
-        schema_value.update_attr_map(name, &schema_attr.ty.node.to_string());
+        schema_value.update_attr_map(name, &runtime_type_str(self, &schema_attr.ty.node));
+        if schema_attr.has_decorator(SECRET_DECORATOR) {
+            schema_value.add_secret_attr(name);
+        }
         if let Some(entry) = config_value.dict_get_entry(name) {
             let is_override_attr = {
                 let is_override_op = matches!(
@@ -1464,8 +1481,12 @@ impl<'ctx> Evaluator<'ctx> {
                 self.none_value()
             };
             if let Some(ty) = arg_type {
-                arg_value =
-                    type_pack_and_check(self, &arg_value, vec![&ty.node.to_string()], false);
+                arg_value = type_pack_and_check(
+                    self,
+                    &arg_value,
+                    vec![&runtime_type_str(self, &ty.node)],
+                    false,
+                );
             }
             // Arguments are immutable, so we place them in different scopes.
             let name = arg_name.get_name();
@@ -1487,8 +1508,12 @@ impl<'ctx> Evaluator<'ctx> {
                     None => self.undefined_value(),
                 };
                 if let Some(ty) = arg_type {
-                    arg_value =
-                        type_pack_and_check(self, &arg_value, vec![&ty.node.to_string()], false);
+                    arg_value = type_pack_and_check(
+                        self,
+                        &arg_value,
+                        vec![&runtime_type_str(self, &ty.node)],
+                        false,
+                    );
                 }
                 self.store_variable(&arg_name.names[0].node, arg_value);
             } else {
@@ -1525,6 +1550,7 @@ impl<'ctx> Evaluator<'ctx> {
 
         while let Some((next_value, key, value)) = iter_value.next_with_key_value(&iter_host_value)
         {
+            self.check_deadline();
             for v in targets {
                 self.add_local_var(&v.node.names[0].node)
             }
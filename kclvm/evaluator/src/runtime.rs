@@ -64,7 +64,11 @@ pub fn runtime_catch(s: &Evaluator, args: &ValueRef, kwargs: &ValueRef) -> Value
         }));
         return match result {
             Ok(_) => ValueRef::undefined(),
-            Err(err) => ValueRef::str(&kclvm_error::err_to_str(err)),
+            Err(err) => {
+                // Clear the recorded backtrace of the caught execution limit error.
+                s.runtime_ctx.borrow_mut().panic_info.backtrace.clear();
+                ValueRef::str(&kclvm_error::err_to_str(err))
+            }
         };
     }
     panic!("catch() takes exactly one argument (0 given)");
@@ -12,7 +12,7 @@ use scopeguard::defer;
 use crate::lazy::{merge_variables_and_setters, LazyEvalScope, LazyEvalScopeRef};
 use crate::proxy::{call_schema_body, call_schema_check};
 use crate::rule::RuleEvalContext;
-use crate::ty::type_pack_and_check;
+use crate::ty::{runtime_type_str, type_pack_and_check};
 use crate::{error as kcl_error, Proxy};
 use crate::{Evaluator, INNER_LEVEL};
 
@@ -722,8 +722,8 @@ fn update_schema_relaxed_attr(
                 &schema_name,
                 &index_sign_value,
                 key_name,
-                index_signature.node.key_ty.node.to_string().as_str(),
-                index_signature.node.value_ty.node.to_string().as_str(),
+                &runtime_type_str(s, &index_signature.node.key_ty.node),
+                &runtime_type_str(s, &index_signature.node.value_ty.node),
             );
         } else {
             schema_relaxed_attr_update_and_check(
@@ -448,6 +448,23 @@ config: Config {
 	provider_values.attr1 = "foobar"
 }
 "#}
+evaluator_snapshot! {generic_schema_0, r#"
+schema Box<T>:
+    value: T
+
+schema Pair<K, V>:
+    key: K
+    value: V
+    boxes?: [Box<V>]
+
+a = Box {value = "a"}
+b: Box<int> = {value = 1}
+pair: Pair<str, int> = Pair {
+    key = "replicas"
+    value = 3
+    boxes = [{value = 1}, {value = 2}]
+}
+"#}
 evaluator_snapshot! {lazy_scope_0, r#"
 b = a + c
 a = 1
@@ -1,3 +1,4 @@
+use kclvm_ast::ast;
 use kclvm_runtime::{
     check_type, dereference_type, is_dict_type, is_list_type, is_schema_type, is_type_union,
     schema_config_meta, schema_runtime_type, separate_kv, split_type_union, val_plan,
@@ -7,7 +8,7 @@ use scopeguard::defer;
 
 use crate::error as kcl_error;
 use crate::schema::SchemaEvalContext;
-use crate::{proxy::Proxy, Evaluator};
+use crate::{proxy::Proxy, EvalContext, Evaluator};
 
 /// Use the schema instance to build a new schema instance using the schema construct function
 pub fn resolve_schema(s: &Evaluator, schema: &ValueRef, keys: &[String]) -> ValueRef {
@@ -58,6 +59,31 @@ pub fn resolve_schema(s: &Evaluator, schema: &ValueRef, keys: &[String]) -> Valu
     schema.clone()
 }
 
+/// Returns the runtime type string of the type annotation. The generic types are only
+/// checked by the resolver, thus they are erased here for the programs resolved without
+/// the type erasure e.g., `Box<int>` to `Box` and the type parameters of the generic
+/// schemas being evaluated to `any`.
+pub fn runtime_type_str(s: &Evaluator, ty: &ast::Type) -> String {
+    let type_params: Vec<String> = s
+        .schema_stack
+        .borrow()
+        .iter()
+        .filter_map(|ctx| match ctx {
+            EvalContext::Schema(schema) => Some(schema.borrow().node.type_params.clone()),
+            EvalContext::Rule(_) => None,
+        })
+        .flatten()
+        .map(|type_param| type_param.node)
+        .collect();
+    let ty_str = ty.to_string();
+    if type_params.is_empty() && !ty_str.contains('<') {
+        return ty_str;
+    }
+    let mut ty = ty.clone();
+    ty.erase_generics(&type_params);
+    ty.to_string()
+}
+
 /// Type pack and check ValueRef with the expected type vector
 pub fn type_pack_and_check(
     s: &Evaluator,
@@ -6,7 +6,7 @@ pub mod util;
 
 use anyhow::Result;
 use indexmap::{IndexMap, IndexSet};
-use kclvm_ast::ast::Program;
+use kclvm_ast::ast::{AstIndex, Program};
 use kclvm_error::{diagnostic::Range, Diagnostic};
 use kclvm_parser::{load_program, KCLModuleCache, LoadProgramOptions, ParseSessionRef};
 use kclvm_sema::{
@@ -18,8 +18,8 @@ use kclvm_sema::{
     },
     namer::Namer,
     resolver::{
-        resolve_program_with_opts,
-        scope::{KCLScopeCache, NodeKey},
+        scope::{KCLScopeCache, NodeKey, NodeTyMap},
+        try_resolve_program_with_opts,
     },
     ty::{Type, TypeRef},
 };
@@ -69,6 +69,37 @@ pub struct Packages {
     pub symbol_node_map: IndexMap<SymbolRef, NodeKey>,
     /// Fully qualified name mapping
     pub fully_qualified_name_map: IndexMap<String, SymbolRef>,
+    /// Definition-References mapping, including the references from all the loaded
+    /// packages e.g., the vendored external packages, sorted by their positions.
+    pub references: IndexMap<SymbolRef, Vec<SymbolRef>>,
+    /// AST Node-Type mapping, which contains the inferred types of the AST nodes
+    /// e.g., the expressions and the assignment targets.
+    pub node_ty_map: NodeTyMap,
+}
+
+impl Packages {
+    /// Get the references of the symbol across the packages e.g., the config keys and
+    /// the attribute accesses which use a schema attribute. When the symbol itself is a
+    /// reference, the references of its definition are returned.
+    pub fn references_of(&self, symbol_ref: &SymbolRef) -> &[SymbolRef] {
+        let def_ref = match self.symbols.get(symbol_ref).and_then(|info| info.def) {
+            Some(def_ref) => def_ref,
+            None => *symbol_ref,
+        };
+        self.references
+            .get(&def_ref)
+            .map(|refs| refs.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the inferred type of the AST node with the node id in the package.
+    #[inline]
+    pub fn get_node_ty(&self, pkgpath: &str, id: &AstIndex) -> Option<&TypeRef> {
+        self.node_ty_map.get(&NodeKey {
+            pkgpath: pkgpath.to_string(),
+            id: id.clone(),
+        })
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -112,6 +143,9 @@ pub enum ScopeKind {
 
 /// load_package provides users with the ability to parse kcl program and sematic model
 /// information including symbols, types, definitions, etc.
+///
+/// The loading can be abandoned by the `cancel_token` of the [`LoadProgramOptions`],
+/// which returns a [`kclvm_utils::cancel::Cancelled`] error.
 pub fn load_packages(opts: &LoadPackageOptions) -> Result<Packages> {
     load_packages_with_cache(
         opts,
@@ -138,29 +172,48 @@ pub fn load_packages_with_cache(
         Some(module_cache),
     )?;
     let parse_errors = parse_result.errors;
-    let (program, type_errors, gs) = if opts.resolve_ast {
+    let cancel_token = opts
+        .load_opts
+        .as_ref()
+        .map(|opts| opts.cancel_token.clone())
+        .unwrap_or_default();
+    let (program, type_errors, node_ty_map, gs) = if opts.resolve_ast {
         let mut program = parse_result.program;
-        let prog_scope = resolve_program_with_opts(
+        let prog_scope = try_resolve_program_with_opts(
             &mut program,
             kclvm_sema::resolver::Options {
                 merge_program: false,
                 type_erasure: false,
+                cancel_token: cancel_token.clone(),
                 ..Default::default()
             },
             Some(scope_cache),
-        );
+        )?;
         let node_ty_map = prog_scope.node_ty_map;
         Namer::find_symbols(&program, gs);
+        cancel_token.check()?;
         AdvancedResolver::resolve_program(&program, gs, node_ty_map.clone())?;
-        (program, prog_scope.handler.diagnostics.clone(), gs)
+        let node_ty_map = node_ty_map.borrow().clone();
+        (
+            program,
+            prog_scope.handler.diagnostics.clone(),
+            node_ty_map,
+            gs,
+        )
     } else {
-        (parse_result.program, IndexSet::default(), gs)
+        (
+            parse_result.program,
+            IndexSet::default(),
+            NodeTyMap::default(),
+            gs,
+        )
     };
     let mut packages = Packages {
         program,
         paths: parse_result.paths,
         parse_errors,
         type_errors,
+        node_ty_map,
         ..Default::default()
     };
     if !opts.resolve_ast {
@@ -243,6 +296,28 @@ pub fn load_packages_with_cache(
     packages.node_symbol_map = symbols.get_node_symbol_map().clone();
     packages.symbol_node_map = symbols.get_symbol_node_map().clone();
     packages.fully_qualified_name_map = symbols.get_fully_qualified_name_map().clone();
+    // Reverse reference index built by the advanced resolver
+    for symbol_ref in packages.symbols.keys() {
+        if let Some(symbol) = symbols.get_symbol(*symbol_ref) {
+            if symbol.get_definition() != Some(*symbol_ref) {
+                continue;
+            }
+            let mut refs: Vec<(Range, SymbolRef)> = symbol
+                .get_references()
+                .into_iter()
+                .filter_map(|r| symbols.get_symbol(r).map(|s| (s.get_range(), r)))
+                .collect();
+            if refs.is_empty() {
+                continue;
+            }
+            refs.sort_by(|(a, _), (b, _)| {
+                (&a.0.filename, a.0.line, a.0.column).cmp(&(&b.0.filename, b.0.line, b.0.column))
+            });
+            packages
+                .references
+                .insert(*symbol_ref, refs.into_iter().map(|(_, r)| r).collect());
+        }
+    }
     Ok(packages)
 }
 
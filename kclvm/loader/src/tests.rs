@@ -1,6 +1,8 @@
 use crate::option::list_options;
 use crate::{load_packages, LoadPackageOptions};
+use kclvm_ast::{ast, MAIN_PKG};
 use kclvm_parser::LoadProgramOptions;
+use kclvm_sema::core::symbol::SymbolKind;
 
 #[macro_export]
 macro_rules! load_package_snapshot {
@@ -74,3 +76,62 @@ if True:
 list_options_snapshot! {list_options_3, r#"
 a = option("key1", type="int", required=False, default=123, help="help me")
 "#}
+
+#[test]
+fn test_references_of() {
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec![r#"schema Person:
+    name: str
+
+alice = Person {name = "Alice"}
+name = alice.name
+"#
+            .to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let (attr_ref, _) = p
+        .symbols
+        .iter()
+        .find(|(symbol_ref, info)| {
+            symbol_ref.get_kind() == SymbolKind::Attribute && info.name == "name"
+        })
+        .unwrap();
+    let refs = p.references_of(attr_ref);
+    let lines: Vec<u64> = refs.iter().map(|r| p.symbols[r].range.0.line).collect();
+    assert_eq!(lines, vec![4, 5]);
+    // The references of a reference are the ones of its definition.
+    assert_eq!(p.references_of(&refs[0]), refs);
+}
+
+#[test]
+fn test_get_node_ty() {
+    let p = load_packages(&LoadPackageOptions {
+        paths: vec!["test.k".to_string()],
+        load_opts: Some(LoadProgramOptions {
+            k_code_list: vec!["a = 1 + 1\nb = a > 1\n".to_string()],
+            ..Default::default()
+        }),
+        load_builtin: false,
+        ..Default::default()
+    })
+    .unwrap();
+    let module = p.program.get_main_package_first_module().unwrap();
+    let tys: Vec<String> = module
+        .body
+        .iter()
+        .map(|stmt| match &stmt.node {
+            ast::Stmt::Assign(assign_stmt) => p
+                .get_node_ty(MAIN_PKG, &assign_stmt.value.id)
+                .unwrap()
+                .ty_str(),
+            _ => unreachable!(),
+        })
+        .collect();
+    assert_eq!(tys, vec!["int", "bool"]);
+}
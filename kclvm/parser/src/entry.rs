@@ -1,16 +1,17 @@
 use anyhow::Result;
 use glob::glob;
-use kclvm_config::modfile::get_pkg_root;
-use kclvm_config::modfile::KCL_FILE_SUFFIX;
+use kclvm_config::modfile::{KCL_FILE_SUFFIX, KCL_MOD_FILE};
 use kclvm_config::path::ModRelativePath;
 use kclvm_utils::path::PathPrefix;
-use kclvm_utils::path::{is_absolute, is_dir, path_exist};
-use std::collections::VecDeque;
+use kclvm_utils::path::{is_absolute, is_anonymous_filename};
+use std::collections::{HashMap, VecDeque};
 use std::fs;
+use std::io::Read;
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::OnceLock;
 
-use crate::LoadProgramOptions;
+use crate::{anonymous_filename, LoadProgramOptions, STDIN_INPUT, STDIN_SOURCE_NAME};
 
 /// [`Entries`] is a map of package name to package root path for one compilation
 /// # note
@@ -173,6 +174,12 @@ impl Entry {
         }
     }
 
+    /// [`push_k_file_and_code`] will push the k file and its k code into [`Entry`].
+    pub fn push_k_file_and_code(&mut self, k_file: String, k_code: Option<String>) {
+        self.k_files.push(k_file);
+        self.k_code_lists.push(k_code);
+    }
+
     /// [`push_k_code`] will push the k code of [`Entry`] to the given k code.
     pub fn push_k_code(&mut self, k_code: Option<String>) {
         self.k_code_lists.push(k_code);
@@ -298,8 +305,32 @@ pub fn get_compile_entries_from_paths(
         return Err(anyhow::anyhow!("No input KCL files or paths"));
     }
     let mut result = Entries::default();
+    let (anonymous_files, file_paths) = split_anonymous_inputs(file_paths, opts)?;
+    if file_paths.is_empty() {
+        // Only the anonymous sources are inputted, the working directory is the package root.
+        let root = get_pkg_root_with_provider(opts.file_provider.as_ref(), &opts.work_dir)
+            .unwrap_or_else(|| opts.work_dir.to_string());
+        let mut entry = Entry::new(kclvm_ast::MAIN_PKG.to_string(), root.clone());
+        for (file, code) in anonymous_files {
+            entry.push_k_file_and_code(file, Some(code));
+        }
+        result.root_path = root;
+        result.push_entry(entry);
+        return Ok(result);
+    }
     let mut k_code_queue = VecDeque::from(opts.k_code_list.clone());
-    let file_paths = expand_input_files(file_paths);
+    // The relative file patterns are matched from the working directory.
+    let file_paths: Vec<String> = file_paths
+        .iter()
+        .map(|file| {
+            if is_file_pattern(file) && !opts.work_dir.is_empty() && !is_absolute(file) {
+                Path::new(&opts.work_dir).join(file).display().to_string()
+            } else {
+                file.to_string()
+            }
+        })
+        .collect();
+    let file_paths = expand_input_files(&file_paths);
     for file in &file_paths {
         let file = canonicalize_input_file(file, &opts.work_dir);
         let path = ModRelativePath::from(file.to_string());
@@ -313,7 +344,7 @@ pub fn get_compile_entries_from_paths(
         }) {
             // Replace the mod relative path prefix '${<pkg_name>:KCL_MOD}' with the real path.
             let file = path.canonicalize_by_root_path(pkg_path)?;
-            if let Some(root) = get_pkg_root(&file) {
+            if let Some(root) = get_pkg_root_with_provider(opts.file_provider.as_ref(), &file) {
                 let mut entry: Entry = Entry::new(pkg_name.clone(), root.clone());
                 entry.extend_k_files_and_codes(
                     get_main_files_from_pkg_path(&file, &root, &pkg_name, opts)?,
@@ -329,7 +360,7 @@ pub fn get_compile_entries_from_paths(
             entry.push_k_code(k_code_queue.pop_front());
             result.push_entry(entry);
             continue;
-        } else if let Some(root) = get_pkg_root(&file) {
+        } else if let Some(root) = get_pkg_root_with_provider(opts.file_provider.as_ref(), &file) {
             // If the path is a normal path.
             let mut entry: Entry = Entry::new(kclvm_ast::MAIN_PKG.to_string(), root.clone());
             entry.extend_k_files_and_codes(
@@ -368,7 +399,9 @@ pub fn get_compile_entries_from_paths(
             .to_string()
     } else if main_pkg_paths_count > 1 && !opts.work_dir.is_empty() {
         // If the 'kcl.mod' can be found more than once, the package root path will be the 'work_dir'.
-        if let Some(root_work_dir) = get_pkg_root(&opts.work_dir) {
+        if let Some(root_work_dir) =
+            get_pkg_root_with_provider(opts.file_provider.as_ref(), &opts.work_dir)
+        {
             root_work_dir
         } else {
             opts.work_dir.to_string()
@@ -391,10 +424,88 @@ pub fn get_compile_entries_from_paths(
         }
         Ok(())
     })?;
+    // The anonymous sources belong to the main package.
+    if let Some(entry) = result
+        .entries
+        .iter_mut()
+        .find(|entry| entry.name() == kclvm_ast::MAIN_PKG)
+    {
+        for (file, code) in anonymous_files {
+            entry.push_k_file_and_code(file, Some(code));
+        }
+    }
 
     Ok(result)
 }
 
+/// Split the anonymous sources which are not in the file system from the input paths,
+/// and returns the anonymous filenames with their source code and the other paths.
+/// The input path `-` denotes the source read from the stdin and the input path
+/// `<name>` denotes the source `name` in [`LoadProgramOptions::anonymous_sources`].
+fn split_anonymous_inputs(
+    file_paths: &[String],
+    opts: &LoadProgramOptions,
+) -> Result<(Vec<(String, String)>, Vec<String>)> {
+    let mut anonymous_files = vec![];
+    let mut paths = vec![];
+    for file in file_paths {
+        if file == STDIN_INPUT {
+            let code = match opts.anonymous_sources.get(STDIN_SOURCE_NAME) {
+                Some(code) => code.clone(),
+                None => read_stdin()?,
+            };
+            anonymous_files.push((anonymous_filename(STDIN_SOURCE_NAME), code));
+        } else if is_anonymous_filename(file) {
+            let name = &file[1..file.len() - 1];
+            match opts.anonymous_sources.get(name) {
+                Some(code) => anonymous_files.push((file.to_string(), code.clone())),
+                None => {
+                    return Err(anyhow::anyhow!(
+                        "Cannot find the anonymous KCL source {file}"
+                    ))
+                }
+            }
+        } else {
+            paths.push(file.to_string());
+        }
+    }
+    Ok((anonymous_files, paths))
+}
+
+/// Read the KCL source code from the stdin. The stdin is read only once, thus the
+/// entries can be resolved more than once.
+fn read_stdin() -> Result<String> {
+    static STDIN_SOURCE: OnceLock<String> = OnceLock::new();
+    if let Some(code) = STDIN_SOURCE.get() {
+        return Ok(code.clone());
+    }
+    let mut code = String::new();
+    std::io::stdin().read_to_string(&mut code)?;
+    Ok(STDIN_SOURCE.get_or_init(|| code).clone())
+}
+
+/// Get the package root path which contains the `kcl.mod` file in the file system
+/// [`FileProvider`]. If the `kcl.mod` file is not found, the parent directory of the
+/// KCL file will be returned.
+fn get_pkg_root_with_provider(provider: &dyn FileProvider, k_file_path: &str) -> Option<String> {
+    if k_file_path.is_empty() {
+        return None;
+    }
+    let path = provider.canonicalize(Path::new(k_file_path)).ok()?;
+    // Search by the kcl.mod file
+    let mut module_path = Some(path.as_path());
+    while let Some(p) = module_path {
+        if provider.is_file(&p.join(KCL_MOD_FILE)) {
+            return Some(p.adjust_canonicalization());
+        }
+        module_path = p.parent();
+    }
+    if k_file_path.ends_with(KCL_FILE_SUFFIX) {
+        return path.parent().map(|p| p.adjust_canonicalization());
+    }
+    None
+}
+
 /// Get files in the main package with the package root.
 fn get_main_files_from_pkg_path(
     pkg_path: &str,
@@ -417,14 +528,15 @@ fn get_main_files_from_pkg_path(
             return Err(anyhow::anyhow!("Can not find {} in the path: {}", s, root));
         }
     }
+    let provider = opts.file_provider.as_ref();
     if !root.is_empty() && !is_absolute(s.as_str()) {
         let p = std::path::Path::new(s.as_str());
-        if let Ok(x) = std::fs::canonicalize(p) {
+        if let Ok(x) = provider.canonicalize(p) {
             s = x.adjust_canonicalization();
         }
     }
 
-    match PathBuf::from(s.clone()).canonicalize() {
+    match provider.canonicalize(&PathBuf::from(s.clone())) {
         Ok(path) => {
             path_list.push(path.to_str().unwrap().to_string());
         }
@@ -437,12 +549,12 @@ fn get_main_files_from_pkg_path(
 
     for (i, path) in path_list.iter().enumerate() {
         // read dir/*.k
-        if !path.is_empty() && is_dir(path) {
+        if !path.is_empty() && provider.is_dir(Path::new(path)) {
             if opts.k_code_list.len() > i {
                 return Err(anyhow::anyhow!("Invalid code list for the path {}", path));
             }
             // k_code_list
-            for s in get_dir_files(path, false)? {
+            for s in get_dir_files_with_provider(provider, path, false)? {
                 k_files.push(s);
             }
             continue;
@@ -461,7 +573,7 @@ fn get_main_files_from_pkg_path(
             continue;
         }
 
-        if !path_exist(filename.as_str()) {
+        if !provider.exists(Path::new(filename)) {
             return Err(anyhow::anyhow!(
                 "Cannot find the kcl file, please check the file path {}",
                 filename.as_str(),
@@ -472,8 +584,18 @@ fn get_main_files_from_pkg_path(
 }
 
 /// Get file list in the directory.
+#[inline]
 pub fn get_dir_files(dir: &str, is_recursive: bool) -> Result<Vec<String>> {
-    if !std::path::Path::new(dir).exists() {
+    get_dir_files_with_provider(&OsFileProvider, dir, is_recursive)
+}
+
+/// Get file list in the directory of the file system [`FileProvider`].
+pub fn get_dir_files_with_provider(
+    provider: &dyn FileProvider,
+    dir: &str,
+    is_recursive: bool,
+) -> Result<Vec<String>> {
+    if !provider.exists(Path::new(dir)) {
         return Ok(Vec::new());
     }
 
@@ -483,17 +605,14 @@ pub fn get_dir_files(dir: &str, is_recursive: bool) -> Result<Vec<String>> {
     // BFS all the files in the directory.
     while let Some(path) = queue.pop_front() {
         let path = Path::new(&path);
-        if path.is_dir() {
-            match fs::read_dir(path) {
+        if provider.is_dir(path) {
+            match provider.read_dir(path) {
                 Ok(entries) => {
-                    for entry in entries {
-                        if let Ok(entry) = entry {
-                            let path = entry.path();
-                            if path.is_dir() && is_recursive {
-                                queue.push_back(path.to_string_lossy().to_string());
-                            } else if !is_ignored_file(&path.display().to_string()) {
-                                list.push(path.display().to_string());
-                            }
+                    for path in entries {
+                        if provider.is_dir(&path) && is_recursive {
+                            queue.push_back(path.to_string_lossy().to_string());
+                        } else if !is_ignored_file(&path.display().to_string()) {
+                            list.push(path.display().to_string());
                         }
                     }
                 }
@@ -549,12 +668,32 @@ pub fn canonicalize_input_file(file: &str, work_dir: &str) -> String {
     abs_path.unwrap_or(file.to_string())
 }
 
-/// Expand the single file pattern to a list of files.
+/// Whether the input file is a glob pattern e.g. `./apps/**/prod`.
+#[inline]
+pub fn is_file_pattern(file: &str) -> bool {
+    file.contains(['*', '?', '['])
+}
+
+/// Expand the single file pattern to a list of files. The files and directories matched
+/// by the pattern are excluded if they are ignored by the `.kclignore` files.
 pub fn expand_if_file_pattern(file_pattern: String) -> Result<Vec<String>> {
     let paths = glob(&file_pattern)?;
+    let is_pattern = is_file_pattern(&file_pattern);
+    let mut ignores: HashMap<PathBuf, KclIgnore> = HashMap::new();
     let mut matched_files = vec![];
 
     for path in paths.flatten() {
+        if is_pattern {
+            let abs_path = path.canonicalize().unwrap_or_else(|_| path.clone());
+            if let Some(parent) = abs_path.parent() {
+                let ignore = ignores
+                    .entry(parent.to_path_buf())
+                    .or_insert_with(|| KclIgnore::load(parent));
+                if ignore.is_ignored(&abs_path, abs_path.is_dir()) {
+                    continue;
+                }
+            }
+        }
         matched_files.push(path.to_string_lossy().to_string());
     }
 
@@ -577,3 +716,279 @@ pub fn expand_input_files(k_files: &[String]) -> Vec<String> {
     }
     res
 }
+
+/// The file contains the patterns of the files and directories ignored by the compiler.
+pub const KCL_IGNORE_FILE: &str = ".kclignore";
+
+#[derive(Debug, Clone)]
+struct IgnoreRule {
+    /// The directory of the `.kclignore` file which contains the rule.
+    base: PathBuf,
+    pattern: glob::Pattern,
+    /// The rule starts with `!` re-includes the paths ignored by the previous rules.
+    negated: bool,
+    /// The rule ends with `/` only matches directories.
+    dir_only: bool,
+    /// The rule contains `/` is matched with the path relative to the `base`, else it
+    /// is matched with the file name at any depth.
+    anchored: bool,
+}
+
+/// [`KclIgnore`] is the ignore rules in the `.kclignore` files, which follows a subset of
+/// the `.gitignore` syntax: blank lines and lines start with `#` are skipped, `!` negates
+/// a rule, a trailing `/` only matches directories, a rule contains `/` is relative to
+/// the directory of the `.kclignore` file and the glob syntax `*`, `?`, `[...]` and `**`
+/// are supported. The last matched rule wins.
+#[derive(Debug, Clone, Default)]
+pub struct KclIgnore {
+    rules: Vec<IgnoreRule>,
+}
+
+impl KclIgnore {
+    /// Load the `.kclignore` files in the directory and its parent directories until the
+    /// package root which contains the `kcl.mod` file.
+    pub fn load<P: AsRef<Path>>(dir: P) -> Self {
+        let dir = dir.as_ref();
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let mut dirs = vec![];
+        let mut current = Some(dir.as_path());
+        while let Some(d) = current {
+            dirs.push(d);
+            if d.join(KCL_MOD_FILE).is_file() {
+                break;
+            }
+            current = d.parent();
+        }
+        let mut ignore = KclIgnore::default();
+        // The rules in the inner directories have the higher priority.
+        for d in dirs.iter().rev() {
+            if let Ok(content) = fs::read_to_string(d.join(KCL_IGNORE_FILE)) {
+                ignore.add_rules(d, &content);
+            }
+        }
+        ignore
+    }
+
+    /// Add the rules in the content of a `.kclignore` file located in the `base` directory.
+    pub fn add_rules(&mut self, base: &Path, content: &str) {
+        for line in content.lines() {
+            let line = line.trim();
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let (negated, line) = match line.strip_prefix('!') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let (dir_only, line) = match line.strip_suffix('/') {
+                Some(line) => (true, line),
+                None => (false, line),
+            };
+            let anchored = line.contains('/');
+            if let Ok(pattern) = glob::Pattern::new(line.trim_start_matches('/')) {
+                self.rules.push(IgnoreRule {
+                    base: base.to_path_buf(),
+                    pattern,
+                    negated,
+                    dir_only,
+                    anchored,
+                });
+            }
+        }
+    }
+
+    /// Whether there is no ignore rule.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.rules.is_empty()
+    }
+
+    /// Whether the absolute path is ignored. A path is also ignored when any of its parent
+    /// directories is ignored.
+    pub fn is_ignored<P: AsRef<Path>>(&self, path: P, is_dir: bool) -> bool {
+        if self.rules.is_empty() {
+            return false;
+        }
+        let path = path.as_ref();
+        let mut ancestors: Vec<&Path> = path.ancestors().skip(1).collect();
+        ancestors.reverse();
+        ancestors.into_iter().any(|dir| self.matches(dir, true)) || self.matches(path, is_dir)
+    }
+
+    fn matches(&self, path: &Path, is_dir: bool) -> bool {
+        let options = glob::MatchOptions {
+            case_sensitive: true,
+            require_literal_separator: true,
+            require_literal_leading_dot: false,
+        };
+        let mut ignored = false;
+        for rule in &self.rules {
+            if rule.dir_only && !is_dir {
+                continue;
+            }
+            let rel_path = match path.strip_prefix(&rule.base) {
+                Ok(rel_path) if rel_path.as_os_str().len() > 0 => rel_path,
+                _ => continue,
+            };
+            let matched = if rule.anchored {
+                rule.pattern.matches_path_with(rel_path, options)
+            } else {
+                rel_path
+                    .file_name()
+                    .and_then(|name| name.to_str())
+                    .map(|name| rule.pattern.matches_with(name, options))
+                    .unwrap_or_default()
+            };
+            if matched {
+                ignored = !rule.negated;
+            }
+        }
+        ignored
+    }
+}
+
+/// [`FileProvider`] is the file system used by the loader to find and read KCL files,
+/// thus callers can compile in-memory sources, e.g., the unsaved buffers in the LSP,
+/// tests and WASM, by supplying a virtual or overlay file system.
+pub trait FileProvider: Send + Sync + std::fmt::Debug {
+    /// Read the entire contents of the file into a string.
+    fn read_to_string(&self, path: &Path) -> std::io::Result<String>;
+    /// Returns the paths of the entries in the directory.
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>>;
+    /// Returns the canonical, absolute form of the path.
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf>;
+    /// Returns `true` if the path points at an existing file.
+    fn is_file(&self, path: &Path) -> bool;
+    /// Returns `true` if the path points at an existing directory.
+    fn is_dir(&self, path: &Path) -> bool;
+    /// Returns `true` if the path points at an existing file or directory.
+    fn exists(&self, path: &Path) -> bool {
+        self.is_file(path) || self.is_dir(path)
+    }
+}
+
+/// [`FileProviderRef`] is the shared reference of the [`FileProvider`].
+pub type FileProviderRef = std::sync::Arc<dyn FileProvider>;
+
+/// [`OsFileProvider`] is the [`FileProvider`] of the local file system.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct OsFileProvider;
+
+impl FileProvider for OsFileProvider {
+    #[inline]
+    fn read_to_string(&self, path: &Path) -> std::io::Result<String> {
+        fs::read_to_string(path)
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        Ok(fs::read_dir(path)?
+            .flatten()
+            .map(|entry| entry.path())
+            .collect())
+    }
+
+    #[inline]
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+        path.canonicalize()
+    }
+
+    #[inline]
+    fn is_file(&self, path: &Path) -> bool {
+        path.is_file()
+    }
+
+    #[inline]
+    fn is_dir(&self, path: &Path) -> bool {
+        path.is_dir()
+    }
+}
+
+/// [`OverlayFileProvider`] overlays in-memory files on a base [`FileProvider`]. The
+/// in-memory files shadow the files with the same paths in the base file system, and
+/// the parent directories of the in-memory files are treated as existing directories.
+#[derive(Debug, Clone)]
+pub struct OverlayFileProvider {
+    base: FileProviderRef,
+    files: std::collections::HashMap<PathBuf, String>,
+}
+
+impl Default for OverlayFileProvider {
+    fn default() -> Self {
+        Self::new(std::sync::Arc::new(OsFileProvider))
+    }
+}
+
+impl OverlayFileProvider {
+    /// New an overlay file system on the base file system.
+    pub fn new(base: FileProviderRef) -> Self {
+        Self {
+            base,
+            files: Default::default(),
+        }
+    }
+
+    /// Add or replace the in-memory file.
+    pub fn insert_file<P: AsRef<Path>>(&mut self, path: P, code: String) {
+        self.files.insert(path.as_ref().to_path_buf(), code);
+    }
+
+    /// Remove the in-memory file and return its source code if it exists.
+    pub fn remove_file<P: AsRef<Path>>(&mut self, path: P) -> Option<String> {
+        self.files.remove(path.as_ref())
+    }
+
+    /// Whether the path is an ancestor directory of any in-memory file.
+    fn is_virtual_dir(&self, path: &Path) -> bool {
+        self.files
+            .keys()
+            .any(|file| file != path && file.starts_with(path))
+    }
+}
+
+impl FileProvider for OverlayFileProvider {
+    fn read_to_string(&self, path: &Path) -> std::io::Result<String> {
+        match self.files.get(path) {
+            Some(code) => Ok(code.clone()),
+            None => self.base.read_to_string(path),
+        }
+    }
+
+    fn read_dir(&self, path: &Path) -> std::io::Result<Vec<PathBuf>> {
+        let mut paths = if self.base.is_dir(path) {
+            self.base.read_dir(path)?
+        } else if self.is_virtual_dir(path) {
+            vec![]
+        } else {
+            return self.base.read_dir(path);
+        };
+        for file in self.files.keys() {
+            // The direct child of the directory, which is an in-memory file or the
+            // ancestor directory of an in-memory file.
+            if let Ok(rest) = file.strip_prefix(path) {
+                if let Some(name) = rest.components().next() {
+                    let child = path.join(name);
+                    if !paths.contains(&child) {
+                        paths.push(child);
+                    }
+                }
+            }
+        }
+        Ok(paths)
+    }
+
+    fn canonicalize(&self, path: &Path) -> std::io::Result<PathBuf> {
+        if self.files.contains_key(path) || self.is_virtual_dir(path) {
+            Ok(path.to_path_buf())
+        } else {
+            self.base.canonicalize(path)
+        }
+    }
+
+    fn is_file(&self, path: &Path) -> bool {
+        self.files.contains_key(path) || self.base.is_file(path)
+    }
+
+    fn is_dir(&self, path: &Path) -> bool {
+        self.is_virtual_dir(path) || self.base.is_dir(path)
+    }
+}
@@ -0,0 +1,182 @@
+//! The public token stream API for the external tools e.g., syntax highlighters and
+//! formatters, thus they do not need to re-implement the KCL lexer.
+//!
+//! The KCL tokens depend on the symbols interned in the session globals, so the
+//! tokens are converted to [`LexedToken`]s which own their text and can be used
+//! without the session globals.
+
+use std::path::PathBuf;
+
+use kclvm_ast::token::{CommentKind, LitKind, Token, TokenKind};
+use kclvm_span::create_session_globals_then;
+use kclvm_span::symbol::{kw, reserved, sym, Symbol};
+
+use crate::lexer::parse_token_streams;
+use crate::ParseSessionRef;
+
+/// The kind of a lexed token.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LexedTokenKind {
+    /// A reserved keyword e.g., `schema`, `import`, `if`.
+    Keyword,
+    /// An identifier.
+    Ident,
+    /// A literal e.g., string, number, `True`, `None` and `Undefined`.
+    Literal(LitKind),
+    /// A line comment starts with `#`.
+    Comment,
+    /// An operator e.g., `+`, `==`, `+=` and `not`.
+    Operator,
+    /// A punctuation e.g., `.`, `,`, `:`, `->`, `=` and `@`.
+    Punctuation,
+    /// An opening delimiter `(`, `[` or `{`.
+    OpenDelim,
+    /// A closing delimiter `)`, `]` or `}`.
+    CloseDelim,
+    /// An indent.
+    Indent,
+    /// A dedent.
+    Dedent,
+    /// A line break.
+    Newline,
+    /// The end of the source.
+    Eof,
+}
+
+/// A lexed token which owns its text.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LexedToken {
+    pub kind: LexedTokenKind,
+    /// The source text of the token, which is empty for the indent, dedent and
+    /// eof tokens.
+    pub text: String,
+}
+
+/// The position range of a lexed token in the source code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TokenSpan {
+    /// The start byte offset (inclusive).
+    pub lo: usize,
+    /// The end byte offset (exclusive).
+    pub hi: usize,
+    /// The 1-based line of the start position.
+    pub start_line: u64,
+    /// The 0-based char column of the start position.
+    pub start_column: u64,
+    /// The 1-based line of the end position.
+    pub end_line: u64,
+    /// The 0-based char column of the end position.
+    pub end_column: u64,
+}
+
+/// Lex the KCL source code to the tokens with their spans, including the indent,
+/// dedent, newline, comment and eof tokens. The invalid characters are skipped by
+/// the lexer.
+///
+/// # Examples
+/// ```
+/// use kclvm_parser::{lex_file, LexedTokenKind};
+///
+/// let tokens = lex_file("schema A:\n    a: int  # comment\n");
+/// let kinds: Vec<LexedTokenKind> = tokens.iter().map(|(t, _)| t.kind).collect();
+/// assert_eq!(kinds[0], LexedTokenKind::Keyword);
+/// assert_eq!(tokens[0].0.text, "schema");
+/// assert!(kinds.contains(&LexedTokenKind::Indent));
+/// assert!(kinds.contains(&LexedTokenKind::Comment));
+/// assert_eq!(tokens[1].1.start_column, 7);
+/// assert_eq!(kinds.last(), Some(&LexedTokenKind::Eof));
+/// ```
+pub fn lex_file(src: &str) -> Vec<(LexedToken, TokenSpan)> {
+    let sess = ParseSessionRef::default();
+    create_session_globals_then(|| {
+        let sf = sess
+            .session
+            .sm
+            .new_source_file(PathBuf::from("").into(), src.to_string());
+        let src = sf.src.as_ref().map(|s| s.as_str()).unwrap_or_default();
+        let stream = parse_token_streams(&sess, src, sf.start_pos);
+        let tokens: &[Token] = &stream;
+        tokens
+            .iter()
+            .map(|token| {
+                let lo = (token.span.lo() - sf.start_pos).0 as usize;
+                let hi = (token.span.hi() - sf.start_pos).0 as usize;
+                let start = sess.session.sm.lookup_char_pos(token.span.lo());
+                let end = sess.session.sm.lookup_char_pos(token.span.hi());
+                let kind = lexed_token_kind(token.kind);
+                let text = match kind {
+                    LexedTokenKind::Indent | LexedTokenKind::Dedent | LexedTokenKind::Eof => {
+                        String::new()
+                    }
+                    _ => src.get(lo..hi).unwrap_or_default().to_string(),
+                };
+                (
+                    LexedToken { kind, text },
+                    TokenSpan {
+                        lo,
+                        hi,
+                        start_line: start.line as u64,
+                        start_column: start.col.0 as u64,
+                        end_line: end.line as u64,
+                        end_column: end.col.0 as u64,
+                    },
+                )
+            })
+            .collect()
+    })
+}
+
+fn lexed_token_kind(kind: TokenKind) -> LexedTokenKind {
+    match kind {
+        TokenKind::UnaryOp(_)
+        | TokenKind::BinOp(_)
+        | TokenKind::BinOpEq(_)
+        | TokenKind::BinCmp(_) => LexedTokenKind::Operator,
+        TokenKind::At
+        | TokenKind::Dot
+        | TokenKind::DotDotDot
+        | TokenKind::Comma
+        | TokenKind::Colon
+        | TokenKind::RArrow
+        | TokenKind::Dollar
+        | TokenKind::Question
+        | TokenKind::Assign => LexedTokenKind::Punctuation,
+        TokenKind::OpenDelim(_) => LexedTokenKind::OpenDelim,
+        TokenKind::CloseDelim(_) => LexedTokenKind::CloseDelim,
+        TokenKind::Literal(lit) => LexedTokenKind::Literal(lit.kind),
+        TokenKind::Ident(symbol) => {
+            let name = symbol.as_str();
+            // The constants and the keyword operators e.g., `True`, `None`, `and` and `in`
+            // are lexed as identifiers.
+            if symbol.is_bool_lit() {
+                LexedTokenKind::Literal(LitKind::Bool)
+            } else if symbol == kw::None {
+                LexedTokenKind::Literal(LitKind::None)
+            } else if symbol == kw::Undefined {
+                LexedTokenKind::Literal(LitKind::Undefined)
+            } else if is_keyword_operator(&name) {
+                LexedTokenKind::Operator
+            } else if reserved::is_reserved_word(&name) && !is_builtin_type(symbol) {
+                LexedTokenKind::Keyword
+            } else {
+                LexedTokenKind::Ident
+            }
+        }
+        TokenKind::DocComment(CommentKind::Line(_)) => LexedTokenKind::Comment,
+        TokenKind::Indent(_) => LexedTokenKind::Indent,
+        TokenKind::Dedent(_) => LexedTokenKind::Dedent,
+        TokenKind::Newline => LexedTokenKind::Newline,
+        TokenKind::Dummy | TokenKind::Eof => LexedTokenKind::Eof,
+    }
+}
+
+#[inline]
+fn is_keyword_operator(name: &str) -> bool {
+    matches!(name, "and" | "or" | "not" | "in" | "is")
+}
+
+/// The builtin type names e.g., `int` and `str` are reserved words but not keywords.
+#[inline]
+fn is_builtin_type(symbol: Symbol) -> bool {
+    [sym::bool, sym::float, sym::int, sym::str].contains(&symbol)
+}
@@ -5,19 +5,17 @@ use compiler_base_error::diagnostic_handler::DiagnosticHandler;
 use compiler_base_session::Session;
 use compiler_base_span::{span::new_byte_pos, FilePathMapping, SourceMap};
 use expect_test::{expect, Expect};
-use kclvm_error::Handler;
 use kclvm_span::create_session_globals_then;
-use parking_lot::RwLock;
 use std::path::PathBuf;
 use std::sync::Arc;
 
 impl ParseSession {
     #[inline]
     pub(crate) fn with_source_map(sm: Arc<SourceMap>) -> Self {
-        Self(
-            Arc::new(Session::new(sm, Arc::new(DiagnosticHandler::default()))),
-            RwLock::new(Handler::default()),
-        )
+        Self::with_session(Arc::new(Session::new(
+            sm,
+            Arc::new(DiagnosticHandler::default()),
+        )))
     }
 }
 
@@ -29,7 +27,7 @@ fn lex(src: &str) -> (String, String) {
 
     // preprocess the input str by [`SourceFile`]
     let sf = sess
-        .0
+        .session
         .sm
         .new_source_file(PathBuf::from("").into(), src.to_string());
 
@@ -48,7 +46,7 @@ fn lex(src: &str) -> (String, String) {
     });
 
     let err_msgs = sess
-        .0
+        .session
         .emit_all_diags_into_string()
         .unwrap()
         .iter()
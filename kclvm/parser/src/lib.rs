@@ -2,9 +2,13 @@
 
 pub mod entry;
 pub mod file_graph;
+mod lex;
 mod lexer;
 mod parser;
+pub mod program_graph;
+mod scan;
 mod session;
+pub mod trivia;
 
 #[cfg(test)]
 mod tests;
@@ -12,28 +16,35 @@ mod tests;
 extern crate kclvm_error;
 
 use crate::entry::get_compile_entries_from_paths;
+use crate::entry::{FileProvider, FileProviderRef, KclIgnore, OsFileProvider};
+pub use crate::lex::{lex_file, LexedToken, LexedTokenKind, TokenSpan};
+pub use crate::scan::scan_imports;
 pub use crate::session::{ParseSession, ParseSessionRef};
 use compiler_base_macros::bug;
-use compiler_base_session::Session;
-use compiler_base_span::span::new_byte_pos;
 use file_graph::{toposort, Pkg, PkgFile, PkgFileGraph, PkgMap};
-use indexmap::IndexMap;
+use indexmap::{IndexMap, IndexSet};
 use kclvm_ast::ast::Module;
+use kclvm_ast::token::TokenKind;
 use kclvm_ast::{ast, MAIN_PKG};
+use kclvm_config::cache::{get_content_hash, load_content_cache, save_content_cache};
 use kclvm_config::modfile::{get_vendor_home, KCL_FILE_EXTENSION, KCL_FILE_SUFFIX, KCL_MOD_FILE};
 use kclvm_error::diagnostic::{Errors, Range};
-use kclvm_error::{ErrorKind, Message, Position, Style};
+use kclvm_error::{Diagnostic, ErrorKind, Message, Position, Style};
 use kclvm_sema::plugin::PLUGIN_MODULE_PREFIX;
-use kclvm_utils::path::PathPrefix;
+use kclvm_utils::cancel::CancellationToken;
+use kclvm_utils::path::{is_anonymous_filename, PathPrefix};
 use kclvm_utils::pkgpath::parse_external_pkg_name;
 use kclvm_utils::pkgpath::rm_external_pkg_name;
+use rustc_span::FileName;
 
 use anyhow::Result;
 use lexer::parse_token_streams;
 use parser::Parser;
+use rayon::prelude::*;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::path::{Path, PathBuf};
 use std::sync::{Arc, RwLock};
+use trivia::TriviaMap;
 
 use kclvm_span::create_session_globals_then;
 
@@ -72,6 +83,32 @@ impl PkgInfo {
 pub enum ParseMode {
     Null,
     ParseComments,
+    /// Error-tolerant mode for IDE usage, the source code which can not be parsed
+    /// into statements is kept in the AST as placeholder nodes with missing expressions.
+    /// The comments are parsed as the [ParseMode::ParseComments] mode.
+    Recover,
+}
+
+/// The nesting limits of the parser, the nested source code exceeds the limits is
+/// reported as a syntax error instead of overflowing the stack of the recursive
+/// descent parser. The limits are opt-in i.e., unlimited by default, thus the valid
+/// deeply nested configs e.g., the generated ones are never rejected, and the services
+/// parsing the untrusted sources e.g., the language server can set the limits.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseLimits {
+    /// The maximum nesting depth of expressions.
+    pub max_expr_depth: usize,
+    /// The maximum nesting depth of schema and config expressions.
+    pub max_schema_nesting: usize,
+}
+
+impl Default for ParseLimits {
+    fn default() -> Self {
+        Self {
+            max_expr_depth: usize::MAX,
+            max_schema_nesting: usize::MAX,
+        }
+    }
 }
 
 /// LoadProgramResult denotes the result of the whole program and a topological
@@ -84,6 +121,8 @@ pub struct LoadProgramResult {
     pub errors: Errors,
     /// The topological ordering of all known files.
     pub paths: Vec<PathBuf>,
+    /// The hex encoded sha256 hashes of the compiled file sources keyed by the filenames.
+    pub file_hashes: IndexMap<String, String>,
 }
 
 /// ParseFileResult denotes the result of a single file including AST,
@@ -100,6 +139,16 @@ pub struct ParseFileResult {
 
 /// Parse a KCL file to the AST module with parse errors.
 pub fn parse_single_file(filename: &str, code: Option<String>) -> Result<ParseFileResult> {
+    parse_single_file_with_opts(filename, code, LoadProgramOptions::default())
+}
+
+/// Parse a KCL file to the AST module with parse errors, and the load options e.g., the
+/// external package maps are used to resolve the import dependencies.
+pub fn parse_single_file_with_opts(
+    filename: &str,
+    code: Option<String>,
+    opts: LoadProgramOptions,
+) -> Result<ParseFileResult> {
     let filename = filename.adjust_canonicalization();
     let sess = Arc::new(ParseSession::default());
     let mut loader = Loader::new(
@@ -112,7 +161,7 @@ pub fn parse_single_file(filename: &str, code: Option<String>) -> Result<ParseFi
             } else {
                 vec![]
             },
-            ..Default::default()
+            ..opts
         }),
         None,
     );
@@ -146,9 +195,9 @@ pub fn parse_single_file(filename: &str, code: Option<String>) -> Result<ParseFi
 pub fn parse_file_force_errors(filename: &str, code: Option<String>) -> Result<ast::Module> {
     let sess = Arc::new(ParseSession::default());
     let result = parse_file_with_global_session(sess.clone(), filename, code);
-    if sess.0.diag_handler.has_errors()? {
+    if sess.session.diag_handler.has_errors()? {
         let err = sess
-            .0
+            .session
             .emit_nth_diag_into_string(0)?
             .unwrap_or(Ok(ErrorKind::InvalidSyntax.name()))?;
         Err(anyhow::anyhow!(err))
@@ -163,6 +212,94 @@ pub fn parse_file_with_session(
     filename: &str,
     code: Option<String>,
 ) -> Result<ast::Module> {
+    parse_file_with_mode(sess, filename, code, ParseMode::ParseComments)
+}
+
+/// Parse a KCL file to the AST module with the parse session and the parser mode.
+#[inline]
+pub fn parse_file_with_mode(
+    sess: ParseSessionRef,
+    filename: &str,
+    code: Option<String>,
+    mode: ParseMode,
+) -> Result<ast::Module> {
+    parse_file_with_limits(sess, filename, code, mode, &ParseLimits::default())
+}
+
+/// Parse a KCL file to the AST module with the parse session, the parser mode and the
+/// nesting limits of the parser.
+#[inline]
+pub fn parse_file_with_limits(
+    sess: ParseSessionRef,
+    filename: &str,
+    code: Option<String>,
+    mode: ParseMode,
+    limits: &ParseLimits,
+) -> Result<ast::Module> {
+    Ok(parse_file_impl(sess, filename, code, mode, limits, false)?.0)
+}
+
+/// Parse a KCL file to the AST module and the trivia map which records the whitespace,
+/// blank lines and comments of the source code, thus the file can be round-tripped.
+///
+/// # Examples
+/// ```
+/// use kclvm_parser::{parse_file_with_trivia, ParseSessionRef};
+///
+/// let code = "# comment\na = 1  # trailing comment\n\nb = 2\n";
+/// let (module, trivia) =
+///     parse_file_with_trivia(ParseSessionRef::default(), "main.k", Some(code.to_string()))
+///         .unwrap();
+/// assert_eq!(trivia.to_source(), code);
+/// assert_eq!(trivia.blank_lines_before(&module.body[1]), 1);
+/// assert_eq!(trivia.node_comments(&module.body[0]).len(), 2);
+/// ```
+#[inline]
+pub fn parse_file_with_trivia(
+    sess: ParseSessionRef,
+    filename: &str,
+    code: Option<String>,
+) -> Result<(ast::Module, TriviaMap)> {
+    let (m, trivia) = parse_file_impl(
+        sess,
+        filename,
+        code,
+        ParseMode::ParseComments,
+        &ParseLimits::default(),
+        true,
+    )?;
+    Ok((m, trivia.unwrap_or_default()))
+}
+
+/// The input path which denotes the KCL source code read from the stdin.
+pub const STDIN_INPUT: &str = "-";
+/// The name of the anonymous KCL source read from the stdin.
+pub const STDIN_SOURCE_NAME: &str = "stdin";
+
+/// Get the filename of the anonymous source e.g., `<stdin>`.
+#[inline]
+pub fn anonymous_filename(name: &str) -> String {
+    format!("<{name}>")
+}
+
+/// Get the file name in the source map. The anonymous sources e.g., `<stdin>` are mapped
+/// to the custom file names, which are displayed as the same filenames in diagnostics.
+pub(crate) fn source_map_filename(filename: &str) -> FileName {
+    if is_anonymous_filename(filename) {
+        FileName::Custom(filename[1..filename.len() - 1].to_string())
+    } else {
+        PathBuf::from(filename).into()
+    }
+}
+
+fn parse_file_impl(
+    sess: ParseSessionRef,
+    filename: &str,
+    code: Option<String>,
+    mode: ParseMode,
+    limits: &ParseLimits,
+    with_trivia: bool,
+) -> Result<(ast::Module, Option<TriviaMap>)> {
     // Code source.
     let src = if let Some(s) = code {
         s
@@ -178,10 +315,7 @@ pub fn parse_file_with_session(
     };
 
     // Build a source map to store file sources.
-    let sf = sess
-        .0
-        .sm
-        .new_source_file(PathBuf::from(filename).into(), src);
+    let sf = sess.add_source_file(filename, src);
 
     let src_from_sf = match sf.src.as_ref() {
         Some(src) => src,
@@ -194,12 +328,18 @@ pub fn parse_file_with_session(
 
     // Lexer
     let stream = lexer::parse_token_streams(&sess, src_from_sf.as_str(), sf.start_pos);
+    // Trivia
+    let trivia = if with_trivia {
+        Some(TriviaMap::new(src_from_sf.as_str(), &stream, sf.start_pos))
+    } else {
+        None
+    };
     // Parser
-    let mut p = parser::Parser::new(&sess, stream);
+    let mut p = parser::Parser::new_with_limits(&sess, stream, mode, limits.clone());
     let mut m = p.parse_module();
     m.filename = filename.to_string().adjust_canonicalization();
 
-    Ok(m)
+    Ok((m, trivia))
 }
 
 /// Parse a KCL file to the AST module with the parse session and the global session
@@ -224,30 +364,59 @@ pub fn parse_file_with_global_session(
 /// let expr = parse_expr("");
 /// assert!(matches!(expr, None));
 /// ```
+#[inline]
 pub fn parse_expr(src: &str) -> Option<ast::NodeRef<ast::Expr>> {
-    if src.is_empty() {
-        None
-    } else {
-        let sess = Arc::new(Session::default());
-        let sf = sess
-            .sm
-            .new_source_file(PathBuf::from("").into(), src.to_string());
-        let src_from_sf = match sf.src.as_ref() {
-            Some(src) => src,
-            None => {
-                bug!("Internal Bug: Failed to load KCL file.");
-            }
-        };
+    parse_expr_with_diagnostics(src, "").0
+}
 
-        let sess = &&ParseSession::with_session(sess);
+/// Parse a source string to a expression with the filename of the source, and return
+/// the parse errors with their positions in the source. When input empty string, it
+/// will return [None] and no errors.
+///
+/// # Examples
+/// ```
+/// use kclvm_ast::ast;
+/// use kclvm_parser::parse_expr_with_diagnostics;
+///
+/// let (expr, errors) = parse_expr_with_diagnostics("a.b[0]", "<override>");
+/// assert!(matches!(expr.unwrap().node, ast::Expr::Subscript(_)));
+/// assert!(errors.is_empty());
+/// let (_, errors) = parse_expr_with_diagnostics("a +", "<override>");
+/// assert_eq!(errors[0].messages[0].range.0.filename, "<override>");
+/// ```
+pub fn parse_expr_with_diagnostics(
+    src: &str,
+    filename: &str,
+) -> (Option<ast::NodeRef<ast::Expr>>, Errors) {
+    if src.is_empty() {
+        return (None, Errors::default());
+    }
+    let sess = ParseSessionRef::default();
+    let sf = sess
+        .session
+        .sm
+        .new_source_file(source_map_filename(filename), src.to_string());
+    let src_from_sf = match sf.src.as_ref() {
+        Some(src) => src,
+        None => {
+            bug!("Internal Bug: Failed to load KCL file.");
+        }
+    };
 
-        let expr: Option<ast::NodeRef<ast::Expr>> = Some(create_session_globals_then(|| {
-            let stream = parse_token_streams(sess, src_from_sf.as_str(), new_byte_pos(0));
-            let mut parser = Parser::new(sess, stream);
-            parser.parse_expr()
-        }));
+    let expr = create_session_globals_then(|| {
+        let stream = parse_token_streams(&sess, src_from_sf.as_str(), sf.start_pos);
+        let mut parser = Parser::new(&sess, stream);
+        let expr = parser.parse_expr();
+        // The tokens after the expression are not allowed.
+        parser.skip_newlines();
+        if !matches!(parser.token.kind, TokenKind::Eof) {
+            let tok: String = parser.token.into();
+            sess.struct_span_error(&format!("unexpected token '{}'", tok), parser.token.span);
+        }
         expr
-    }
+    });
+    let errors = sess.handler.read().diagnostics.clone();
+    (Some(expr), errors)
 }
 
 #[derive(Debug, Clone)]
@@ -262,6 +431,33 @@ pub struct LoadProgramOptions {
     pub load_packages: bool,
     /// Whether to load plugins
     pub load_plugins: bool,
+    /// Whether to parse the files of the same import depth in parallel.
+    pub parallel: bool,
+    /// The directory of the persistent AST cache keyed by the file content hash,
+    /// which is shared by the `load_program` calls across processes. `None` means
+    /// the persistent AST cache is disabled.
+    pub cache_dir: Option<String>,
+    /// The file system used to find and read the KCL files, which is the local file
+    /// system by default.
+    pub file_provider: FileProviderRef,
+    /// The token checked between the files, thus the caller can abandon a long running
+    /// compilation which returns a [`Cancelled`](kclvm_utils::cancel::Cancelled) error.
+    pub cancel_token: CancellationToken,
+    /// The nesting limits of the parser.
+    pub parse_limits: ParseLimits,
+    /// The in-memory KCL sources without files keyed by the source names. The source
+    /// `name` is loaded when the filename `<name>` is in the input paths, and the
+    /// input path `-` loads the source `stdin` which is read from the stdin if it is
+    /// not set.
+    pub anonymous_sources: IndexMap<String, String>,
+    /// The path prefix mappings `(from, to)` e.g., `("/home/ci/build", "/src")`, which
+    /// remap the absolute build paths in the diagnostics, the AST node file names and
+    /// thus the compiled artifacts and the runtime information to make the builds
+    /// reproducible. The later mappings take precedence.
+    pub path_prefix_map: Vec<(String, String)>,
+    /// The maximum number of the parse errors collected by the session, and the errors
+    /// beyond it are dropped and summarized. None denotes no limit.
+    pub max_errors: Option<usize>,
 }
 
 impl Default for LoadProgramOptions {
@@ -274,6 +470,14 @@ impl Default for LoadProgramOptions {
             mode: ParseMode::ParseComments,
             load_packages: true,
             load_plugins: false,
+            parallel: false,
+            cache_dir: None,
+            file_provider: Arc::new(OsFileProvider),
+            cancel_token: Default::default(),
+            parse_limits: Default::default(),
+            anonymous_sources: Default::default(),
+            path_prefix_map: Default::default(),
+            max_errors: None,
         }
     }
 }
@@ -335,6 +539,114 @@ impl ModuleCache {
             }
         }
     }
+
+    /// Replace the text in the byte `range` of the file source with `new_text` and
+    /// re-parse the file. The source which is not cached is read from the file provider
+    /// of the last compile input. See [`ModuleCache::update_source`] for details.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use kclvm_parser::ModuleCache;
+    /// use std::path::PathBuf;
+    ///
+    /// let mut module_cache = ModuleCache::default();
+    /// let path = PathBuf::from("main.k");
+    /// module_cache.update_source(&path, "a = 1\n".to_string()).unwrap();
+    /// let errors = module_cache.apply_edit(&path, 4..5, "2").unwrap();
+    /// assert!(errors.is_empty());
+    /// assert_eq!(module_cache.source_code[&path], "a = 2\n");
+    /// ```
+    pub fn apply_edit(
+        &mut self,
+        path: &PathBuf,
+        range: std::ops::Range<usize>,
+        new_text: &str,
+    ) -> Result<Errors> {
+        let mut src = match self.source_code.get(path) {
+            Some(src) => src.clone(),
+            // Read the file from the file provider of the last compile input, thus the
+            // in-memory files e.g., the unsaved files of the language server are edited.
+            None => {
+                let opts = self.last_compile_input.1.clone().unwrap_or_default();
+                read_file_source(&path.to_string_lossy(), None, &opts)?
+            }
+        };
+        if range.start > range.end
+            || !src.is_char_boundary(range.start)
+            || !src.is_char_boundary(range.end)
+        {
+            return Err(anyhow::anyhow!(
+                "Invalid edit range {range:?} of the KCL file '{}'",
+                path.display()
+            ));
+        }
+        src.replace_range(range, new_text);
+        self.update_source(path, src)
+    }
+
+    /// Update the source code of the file and re-parse only this file, then return the
+    /// parse errors of the file. The dependencies of the file are kept when its import
+    /// statements are not changed, thus the file graph of the next [`load_program`] can
+    /// be rebuilt from the cache instead of re-parsing the whole entry. The parse mode
+    /// and limits are the ones of the last compile input.
+    pub fn update_source(&mut self, path: &PathBuf, src: String) -> Result<Errors> {
+        if self.ast_cache.contains_key(path) && self.source_code.get(path) == Some(&src) {
+            return Ok(Errors::default());
+        }
+        let opts = self.last_compile_input.1.clone().unwrap_or_default();
+        let sess = ParseSessionRef::default();
+        let filename = path.to_string_lossy().to_string();
+        let m = create_session_globals_then(|| {
+            parse_file_with_limits(
+                sess.clone(),
+                &filename,
+                Some(src.clone()),
+                opts.mode.clone(),
+                &opts.parse_limits,
+            )
+        })?;
+        let imports_changed = match self.ast_cache.get(path) {
+            Some(old) => match old.read() {
+                Ok(old) => get_import_paths(&old) != get_import_paths(&m),
+                Err(_) => true,
+            },
+            None => true,
+        };
+        // The dependencies are re-computed by the loader when the imports are changed.
+        if imports_changed {
+            if let Some(pkgs) = self.file_pkg.get(path) {
+                for pkg in pkgs {
+                    self.dep_cache.remove(pkg);
+                }
+            }
+        }
+        match self.ast_cache.get(path) {
+            // Update the module in place, thus the shared module references are updated.
+            Some(old) => match old.write() {
+                Ok(mut old) => *old = m,
+                Err(e) => return Err(anyhow::anyhow!("Update the module cache failed: {e}")),
+            },
+            None => {
+                self.ast_cache
+                    .insert(path.clone(), Arc::new(RwLock::new(m)));
+            }
+        }
+        self.source_code.insert(path.clone(), src);
+        let errors = sess.handler.read().diagnostics.clone();
+        Ok(errors)
+    }
+}
+
+/// Get the import paths of the module in the source order.
+fn get_import_paths(m: &Module) -> Vec<&str> {
+    m.body
+        .iter()
+        .filter_map(|stmt| match &stmt.node {
+            ast::Stmt::Import(import_stmt) => Some(import_stmt.path.node.as_str()),
+            _ => None,
+        })
+        .collect()
 }
 struct Loader {
     sess: ParseSessionRef,
@@ -448,7 +760,7 @@ fn find_packages(
     // plugin pkgs
     if is_plugin_pkg(pkg_path) {
         if !opts.load_plugins {
-            sess.1.write().add_error(
+            sess.handler.write().add_error(
                 ErrorKind::CannotFindModule,
                 &[Message {
                     range: Into::<Range>::into(pos),
@@ -468,13 +780,13 @@ fn find_packages(
     }
 
     // 1. Look for in the current package's directory.
-    let is_internal = is_internal_pkg(pkg_name, pkg_root, pkg_path)?;
+    let is_internal = is_internal_pkg(pkg_name, pkg_root, pkg_path, opts)?;
     // 2. Look for in the vendor path.
     let is_external = is_external_pkg(pkg_path, opts)?;
 
     // 3. Internal and external packages cannot be duplicated
     if is_external.is_some() && is_internal.is_some() {
-        sess.1.write().add_error(
+        sess.handler.write().add_error(
             ErrorKind::CannotFindModule,
             &[Message {
                 range: Into::<Range>::into(pos),
@@ -495,7 +807,7 @@ fn find_packages(
     match is_internal.or(is_external) {
         Some(pkg_info) => Ok(Some(pkg_info)),
         None => {
-            sess.1.write().add_error(
+            sess.handler.write().add_error(
                 ErrorKind::CannotFindModule,
                 &[Message {
                     range: Into::<Range>::into(pos),
@@ -516,7 +828,7 @@ fn find_packages(
                     ),
                 );
             }
-            sess.1.write().add_suggestions(suggestions);
+            sess.handler.write().add_suggestions(suggestions);
             Ok(None)
         }
     }
@@ -528,19 +840,19 @@ fn find_packages(
 ///
 /// All paths in [`pkgpath`] must contain the kcl.mod file.
 /// It returns the parent directory of kcl.mod if present, or none if not.
-fn pkg_exists(pkgroots: &[String], pkgpath: &str) -> Option<String> {
+fn pkg_exists(provider: &dyn FileProvider, pkgroots: &[String], pkgpath: &str) -> Option<String> {
     pkgroots
         .into_iter()
-        .find(|root| pkg_exists_in_path(root, pkgpath))
+        .find(|root| pkg_exists_in_path(provider, root, pkgpath))
         .cloned()
 }
 
 /// Search for [`pkgpath`] under [`path`].
 /// It only returns [`true`] if [`path`]/[`pkgpath`] or [`path`]/[`pkgpath.k`] exists.
-fn pkg_exists_in_path(path: &str, pkgpath: &str) -> bool {
+fn pkg_exists_in_path(provider: &dyn FileProvider, path: &str, pkgpath: &str) -> bool {
     let mut pathbuf = PathBuf::from(path);
     pkgpath.split('.').for_each(|s| pathbuf.push(s));
-    pathbuf.exists() || pathbuf.with_extension(KCL_FILE_EXTENSION).exists()
+    provider.exists(&pathbuf) || provider.exists(&pathbuf.with_extension(KCL_FILE_EXTENSION))
 }
 
 /// Look for [`pkgpath`] in the current package's [`pkgroot`].
@@ -549,15 +861,21 @@ fn pkg_exists_in_path(path: &str, pkgpath: &str) -> bool {
 /// # Error
 ///
 /// [`is_internal_pkg`] will return an error if the package's source files cannot be found.
-fn is_internal_pkg(pkg_name: &str, pkg_root: &str, pkg_path: &str) -> Result<Option<PkgInfo>> {
-    match pkg_exists(&[pkg_root.to_string()], pkg_path) {
+fn is_internal_pkg(
+    pkg_name: &str,
+    pkg_root: &str,
+    pkg_path: &str,
+    opts: &LoadProgramOptions,
+) -> Result<Option<PkgInfo>> {
+    let provider = opts.file_provider.as_ref();
+    match pkg_exists(provider, &[pkg_root.to_string()], pkg_path) {
         Some(internal_pkg_root) => {
             let fullpath = if pkg_name == kclvm_ast::MAIN_PKG {
                 pkg_path.to_string()
             } else {
                 format!("{}.{}", pkg_name, pkg_path)
             };
-            let k_files = get_pkg_kfile_list(pkg_root, pkg_path)?;
+            let k_files = get_pkg_kfile_list(provider, pkg_root, pkg_path)?;
             Ok(Some(PkgInfo::new(
                 pkg_name.to_string(),
                 internal_pkg_root,
@@ -569,7 +887,11 @@ fn is_internal_pkg(pkg_name: &str, pkg_root: &str, pkg_path: &str) -> Result<Opt
     }
 }
 
-fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
+fn get_pkg_kfile_list(
+    provider: &dyn FileProvider,
+    pkgroot: &str,
+    pkgpath: &str,
+) -> Result<Vec<String>> {
     // plugin pkgs
     if is_plugin_pkg(pkgpath) {
         return Ok(Vec::new());
@@ -591,16 +913,16 @@ fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
         pathbuf.push(s);
     }
 
-    let abspath = match pathbuf.canonicalize() {
+    let abspath = match provider.canonicalize(&pathbuf) {
         Ok(p) => p.to_str().unwrap().to_string(),
         Err(_) => pathbuf.as_path().to_str().unwrap().to_string(),
     };
-    if std::path::Path::new(abspath.as_str()).exists() {
-        return get_dir_files(abspath.as_str());
+    if provider.exists(Path::new(abspath.as_str())) {
+        return get_dir_files(provider, abspath.as_str());
     }
 
     let as_k_path = abspath + KCL_FILE_SUFFIX;
-    if std::path::Path::new((as_k_path).as_str()).exists() {
+    if provider.exists(Path::new(as_k_path.as_str())) {
         return Ok(vec![as_k_path]);
     }
 
@@ -608,30 +930,28 @@ fn get_pkg_kfile_list(pkgroot: &str, pkgpath: &str) -> Result<Vec<String>> {
 }
 
 /// Get file list in the directory.
-fn get_dir_files(dir: &str) -> Result<Vec<String>> {
-    if !std::path::Path::new(dir).exists() {
+fn get_dir_files(provider: &dyn FileProvider, dir: &str) -> Result<Vec<String>> {
+    if !provider.exists(Path::new(dir)) {
         return Ok(Vec::new());
     }
 
     let mut list = Vec::new();
-    for path in std::fs::read_dir(dir)? {
-        let path = path?;
-        if !path
-            .file_name()
-            .to_str()
-            .unwrap()
-            .ends_with(KCL_FILE_SUFFIX)
-        {
+    for path in provider.read_dir(Path::new(dir))? {
+        let file_name = match path.file_name().and_then(|name| name.to_str()) {
+            Some(file_name) => file_name,
+            None => continue,
+        };
+        if !file_name.ends_with(KCL_FILE_SUFFIX) {
             continue;
         }
-        if path.file_name().to_str().unwrap().ends_with("_test.k") {
+        if file_name.ends_with("_test.k") {
             continue;
         }
-        if path.file_name().to_str().unwrap().starts_with('_') {
+        if file_name.starts_with('_') {
             continue;
         }
 
-        let s = format!("{}", path.path().display());
+        let s = format!("{}", path.display());
         list.push(s);
     }
 
@@ -647,24 +967,26 @@ fn get_dir_files(dir: &str) -> Result<Vec<String>> {
 /// - [`is_external_pkg`] will return an error if the package's source files cannot be found.
 /// - The name of the external package could not be resolved from [`pkg_path`].
 fn is_external_pkg(pkg_path: &str, opts: &LoadProgramOptions) -> Result<Option<PkgInfo>> {
+    let provider = opts.file_provider.as_ref();
     let pkg_name = parse_external_pkg_name(pkg_path)?;
     let external_pkg_root = if let Some(root) = opts.package_maps.get(&pkg_name) {
         PathBuf::from(root).join(KCL_MOD_FILE)
     } else {
-        match pkg_exists(&opts.vendor_dirs, pkg_path) {
+        match pkg_exists(provider, &opts.vendor_dirs, pkg_path) {
             Some(path) => PathBuf::from(path).join(&pkg_name).join(KCL_MOD_FILE),
             None => return Ok(None),
         }
     };
 
-    if external_pkg_root.exists() {
+    if provider.exists(&external_pkg_root) {
         return Ok(Some(match external_pkg_root.parent() {
             Some(root) => {
-                let abs_root: String = match root.canonicalize() {
+                let abs_root: String = match provider.canonicalize(root) {
                     Ok(p) => p.to_str().unwrap().to_string(),
                     Err(_) => root.display().to_string(),
                 };
-                let k_files = get_pkg_kfile_list(&abs_root, &rm_external_pkg_name(pkg_path)?)?;
+                let k_files =
+                    get_pkg_kfile_list(provider, &abs_root, &rm_external_pkg_name(pkg_path)?)?;
                 PkgInfo::new(
                     pkg_name.to_string(),
                     abs_root,
@@ -694,13 +1016,153 @@ pub fn parse_file(
 ) -> Result<Vec<PkgFile>> {
     let src = match src {
         Some(src) => Some(src),
-        None => match &module_cache.read() {
-            Ok(cache) => cache.source_code.get(file.get_path()),
-            Err(_) => None,
+        None => get_cached_source_code(&file, &module_cache),
+    };
+    let m = parse_file_with_cache(sess.clone(), file.get_path().to_str().unwrap(), src, opts)?;
+    update_parsed_file(sess, file, m, module_cache, pkgs, pkgmap, file_graph, opts)
+}
+
+/// Get the source code of the file from the module cache if it exists.
+fn get_cached_source_code(file: &PkgFile, module_cache: &KCLModuleCache) -> Option<String> {
+    match &module_cache.read() {
+        Ok(cache) => cache.source_code.get(file.get_path()),
+        Err(_) => None,
+    }
+    .cloned()
+}
+
+/// Returns the source code of the file, which is read from `opts.file_provider` when
+/// it is not provided.
+fn read_file_source(
+    filename: &str,
+    src: Option<String>,
+    opts: &LoadProgramOptions,
+) -> Result<String> {
+    match src {
+        Some(src) => Ok(src),
+        None => opts
+            .file_provider
+            .read_to_string(Path::new(filename))
+            .map_err(|err| {
+                anyhow::anyhow!("Failed to load KCL file '{filename}'. Because '{err}'")
+            }),
+    }
+}
+
+/// Parse the file read from `opts.file_provider` with the persistent AST cache in
+/// `opts.cache_dir`, thus the module is not re-parsed when the content of the file
+/// is not changed.
+fn parse_file_with_cache(
+    sess: ParseSessionRef,
+    filename: &str,
+    src: Option<String>,
+    opts: &LoadProgramOptions,
+) -> Result<Module> {
+    opts.cancel_token.check()?;
+    let src = read_file_source(filename, src, opts)?;
+    let cache_dir = match &opts.cache_dir {
+        Some(cache_dir) => cache_dir,
+        None => {
+            return parse_file_with_limits(
+                sess,
+                filename,
+                Some(src),
+                opts.mode.clone(),
+                &opts.parse_limits,
+            )
         }
-        .cloned(),
     };
-    let m = parse_file_with_session(sess.clone(), file.get_path().to_str().unwrap(), src)?;
+    let parse_opts = format!("{:?} {:?}", opts.mode, opts.parse_limits);
+    let key = get_content_hash(&[filename.as_bytes(), parse_opts.as_bytes(), src.as_bytes()]);
+    if let Some(m) = load_content_cache::<Module>(cache_dir, &key) {
+        // The source code is still required by the diagnostics of the later compile stages.
+        sess.add_source_file(filename, src);
+        return Ok(m);
+    }
+    // The errors beyond `max_errors` are dropped by the session but still counted.
+    let diagnostic_count = || {
+        let handler = sess.handler.read();
+        handler.diagnostics.len() + handler.dropped_errors
+    };
+    let errors = diagnostic_count();
+    let m = parse_file_with_limits(
+        sess.clone(),
+        filename,
+        Some(src),
+        opts.mode.clone(),
+        &opts.parse_limits,
+    )?;
+    // Modules with syntax errors are not cached, thus the errors are always reported.
+    if diagnostic_count() == errors {
+        if let Err(err) = save_content_cache(cache_dir, &key, &m) {
+            tracing::warn!("failed to save the AST cache of {filename}: {err}");
+        }
+    }
+    Ok(m)
+}
+
+/// Parse files concurrently and return the modules in the same order as the input files.
+///
+/// The compiler session is not thread safe, thus each file is parsed on a worker thread
+/// with its own parse session, and the file sources and the parse errors are merged into
+/// `sess` in the input order so that the result is the same as parsing files one by one.
+pub fn parse_files_parallel(
+    sess: ParseSessionRef,
+    files: Vec<(PkgFile, Option<String>)>,
+    module_cache: KCLModuleCache,
+    opts: &LoadProgramOptions,
+) -> Result<Vec<(PkgFile, Module)>> {
+    let files: Vec<(PkgFile, Option<String>)> = files
+        .into_iter()
+        .map(|(file, src)| {
+            let src = match src {
+                Some(src) => Some(src),
+                None => get_cached_source_code(&file, &module_cache),
+            };
+            (file, src)
+        })
+        .collect();
+    let results: Vec<(Result<(Module, String)>, IndexSet<Diagnostic>)> = files
+        .par_iter()
+        .map(|(file, src)| {
+            let file_sess = Arc::new(ParseSession::default());
+            let filename = file.get_path().to_str().unwrap().to_string();
+            let m = read_file_source(&filename, src.clone(), opts).and_then(|src| {
+                let m = create_session_globals_then(|| {
+                    parse_file_with_cache(file_sess.clone(), &filename, Some(src.clone()), opts)
+                })?;
+                Ok((m, src))
+            });
+            let diagnostics = file_sess.handler.read().diagnostics.clone();
+            (m, diagnostics)
+        })
+        .collect();
+    let mut modules = Vec::with_capacity(files.len());
+    for ((file, _), (m, diagnostics)) in files.into_iter().zip(results) {
+        for diag in diagnostics {
+            sess.add_diagnostic(diag)?;
+        }
+        let (m, src) = m?;
+        // The sources are required by the diagnostics of the later compile stages, which
+        // are looked up in the source map of `sess` instead of the worker sessions.
+        sess.add_source_file(file.get_path().to_str().unwrap(), src);
+        modules.push((file, m));
+    }
+    Ok(modules)
+}
+
+/// Record the parsed module into the module cache and the file graph, and
+/// return the dependent files of the module.
+fn update_parsed_file(
+    sess: ParseSessionRef,
+    file: PkgFile,
+    m: Module,
+    module_cache: KCLModuleCache,
+    pkgs: &mut HashMap<String, Vec<String>>,
+    pkgmap: &mut PkgMap,
+    file_graph: FileGraphCache,
+    opts: &LoadProgramOptions,
+) -> Result<Vec<PkgFile>> {
     let deps = get_deps(&file, &m, pkgs, pkgmap, opts, sess)?;
     let dep_files = deps.keys().map(|f| f.clone()).collect();
     pkgmap.extend(deps.clone());
@@ -793,6 +1255,22 @@ pub fn parse_pkg(
     opts: &LoadProgramOptions,
 ) -> Result<Vec<PkgFile>> {
     let mut dependent = vec![];
+    if opts.parallel {
+        for (file, m) in parse_files_parallel(sess.clone(), files, module_cache.clone(), opts)? {
+            let deps = update_parsed_file(
+                sess.clone(),
+                file,
+                m,
+                module_cache.clone(),
+                pkgs,
+                pkgmap,
+                file_graph.clone(),
+                opts,
+            )?;
+            dependent.extend(deps);
+        }
+        return Ok(dependent);
+    }
     for (file, src) in files {
         let deps = parse_file(
             sess.clone(),
@@ -847,73 +1325,95 @@ pub fn parse_entry(
     let mut unparsed_file: VecDeque<PkgFile> = dependent_paths.into();
 
     // Bfs unparsed and import files
-    while let Some(file) = unparsed_file.pop_front() {
-        match &mut module_cache.write() {
-            Ok(m_cache) => match m_cache.file_pkg.get_mut(file.get_path()) {
-                Some(s) => {
-                    // The module ast has been parsed, but does not belong to the same package
-                    if s.insert(file.clone()) {
+    while !unparsed_file.is_empty() {
+        opts.cancel_token.check()?;
+        // In the parallel mode, all the files of the same import depth are parsed together,
+        // otherwise files are parsed one by one.
+        let batch: Vec<PkgFile> = if opts.parallel {
+            unparsed_file.drain(..).collect()
+        } else {
+            unparsed_file.pop_front().into_iter().collect()
+        };
+        let mut pending_files: Vec<(PkgFile, Option<String>)> = vec![];
+        for file in batch {
+            // The same file may be imported by different packages, parse it only once
+            // and deal the other ones in the next round with the module cache.
+            if pending_files
+                .iter()
+                .any(|(f, _)| f.get_path() == file.get_path())
+            {
+                unparsed_file.push_back(file);
+                continue;
+            }
+            match &mut module_cache.write() {
+                Ok(m_cache) => match m_cache.file_pkg.get_mut(file.get_path()) {
+                    Some(s) => {
+                        // The module ast has been parsed, but does not belong to the same package
+                        if s.insert(file.clone()) {
+                            new_files.insert(file.clone());
+                        }
+                    }
+                    None => {
+                        let mut s = HashSet::new();
+                        s.insert(file.clone());
+                        m_cache.file_pkg.insert(file.get_path().clone(), s);
                         new_files.insert(file.clone());
                     }
-                }
-                None => {
-                    let mut s = HashSet::new();
-                    s.insert(file.clone());
-                    m_cache.file_pkg.insert(file.get_path().clone(), s);
-                    new_files.insert(file.clone());
-                }
-            },
-            Err(e) => return Err(anyhow::anyhow!("Parse file failed: {e}")),
-        }
+                },
+                Err(e) => return Err(anyhow::anyhow!("Parse file failed: {e}")),
+            }
 
-        let module_cache_read = module_cache.read();
-        match &module_cache_read {
-            Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
-                Some(m) => {
-                    let deps = m_cache.dep_cache.get(&file).cloned().unwrap_or_else(|| {
-                        get_deps(&file, &m.read().unwrap(), pkgs, pkgmap, opts, sess.clone())
-                            .unwrap()
-                    });
-                    let dep_files: Vec<PkgFile> = deps.keys().map(|f| f.clone()).collect();
-                    pkgmap.extend(deps.clone());
-
-                    match &mut file_graph.write() {
-                        Ok(file_graph) => {
-                            file_graph.update_file(&file, &dep_files);
-
-                            for dep in dep_files {
-                                if parsed_file.insert(dep.clone()) {
-                                    unparsed_file.push_back(dep.clone());
+            let module_cache_read = module_cache.read();
+            match &module_cache_read {
+                Ok(m_cache) => match m_cache.ast_cache.get(file.get_path()) {
+                    Some(m) => {
+                        let deps = m_cache.dep_cache.get(&file).cloned().unwrap_or_else(|| {
+                            get_deps(&file, &m.read().unwrap(), pkgs, pkgmap, opts, sess.clone())
+                                .unwrap()
+                        });
+                        let dep_files: Vec<PkgFile> = deps.keys().map(|f| f.clone()).collect();
+                        pkgmap.extend(deps.clone());
+
+                        match &mut file_graph.write() {
+                            Ok(file_graph) => {
+                                file_graph.update_file(&file, &dep_files);
+
+                                for dep in dep_files {
+                                    if parsed_file.insert(dep.clone()) {
+                                        unparsed_file.push_back(dep.clone());
+                                    }
                                 }
-                            }
 
-                            continue;
+                                continue;
+                            }
+                            Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
                         }
-                        Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
                     }
-                }
-                None => {
-                    new_files.insert(file.clone());
-                    drop(module_cache_read);
-                    let deps = parse_file(
-                        sess.clone(),
-                        file,
-                        None,
-                        module_cache.clone(),
-                        pkgs,
-                        pkgmap,
-                        file_graph.clone(),
-                        &opts,
-                    )?;
-                    for dep in deps {
-                        if parsed_file.insert(dep.clone()) {
-                            unparsed_file.push_back(dep.clone());
-                        }
+                    None => {
+                        new_files.insert(file.clone());
+                        pending_files.push((file, None));
                     }
-                }
-            },
-            Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
-        };
+                },
+                Err(e) => return Err(anyhow::anyhow!("Parse entry failed: {e}")),
+            };
+        }
+        if pending_files.is_empty() {
+            continue;
+        }
+        let deps = parse_pkg(
+            sess.clone(),
+            pending_files,
+            module_cache.clone(),
+            pkgs,
+            pkgmap,
+            file_graph.clone(),
+            opts,
+        )?;
+        for dep in deps {
+            if parsed_file.insert(dep.clone()) {
+                unparsed_file.push_back(dep.clone());
+            }
+        }
     }
     Ok(new_files)
 }
@@ -927,6 +1427,8 @@ pub fn parse_program(
     parsed_file: &mut HashSet<PkgFile>,
     opts: &LoadProgramOptions,
 ) -> Result<LoadProgramResult> {
+    sess.set_path_prefix_map(&opts.path_prefix_map);
+    sess.set_max_errors(opts.max_errors);
     let compile_entries = get_compile_entries_from_paths(&paths, &opts)?;
     let workdir = compile_entries
         .get_root_path()
@@ -961,7 +1463,7 @@ pub fn parse_program(
                     .map(|file| format!("- {}\n", file.to_string_lossy()))
                     .collect::<String>();
 
-                sess.1.write().add_error(
+                sess.handler.write().add_error(
                     ErrorKind::RecursiveLoad,
                     &[Message {
                         range: (Position::dummy_pos(), Position::dummy_pos()),
@@ -1017,10 +1519,16 @@ pub fn parse_program(
         modules_not_imported: HashMap::new(),
     };
 
+    let file_hashes = sess.file_hashes();
     Ok(LoadProgramResult {
         program,
-        errors: sess.1.read().diagnostics.clone(),
+        errors: sess
+            .sorted_diagnostics()
+            .into_iter()
+            .map(|diag| sess.remap_diagnostic(diag).with_source_hashes(&file_hashes))
+            .collect(),
         paths: files.iter().map(|file| file.get_path().clone()).collect(),
+        file_hashes,
     })
 }
 
@@ -1047,7 +1555,7 @@ pub fn load_all_files_under_paths(
             &loader.opts,
         ) {
             Ok(res) => {
-                let diag = sess.1.read().diagnostics.clone();
+                let diag = sess.handler.read().diagnostics.clone();
                 let mut res = res.clone();
                 let k_files_from_import = res.paths.clone();
                 let mut paths = paths.to_vec();
@@ -1187,7 +1695,7 @@ pub fn load_all_files_under_paths(
                         }
                     }
                 }
-                sess.1.write().diagnostics = diag;
+                sess.handler.write().diagnostics = diag;
                 return Ok(res);
             }
             e => return e,
@@ -1290,15 +1798,34 @@ pub fn get_files_from_path(
     Ok((k_files_under_path, pkgmap))
 }
 
-/// Get kcl files from path.
+/// Get kcl files from path. The files and directories ignored by the `.kclignore`
+/// files are skipped.
 pub fn get_kcl_files<P: AsRef<std::path::Path>>(path: P, recursively: bool) -> Result<Vec<String>> {
     let mut files = vec![];
+    let path = path.as_ref();
     let walkdir = if recursively {
         walkdir::WalkDir::new(path)
     } else {
         walkdir::WalkDir::new(path).max_depth(1)
     };
-    for entry in walkdir.into_iter().filter_map(|e| e.ok()) {
+    let root = path.canonicalize().unwrap_or_else(|_| path.to_path_buf());
+    let ignore = KclIgnore::load(if root.is_dir() {
+        root.as_path()
+    } else {
+        root.parent().unwrap_or(&root)
+    });
+    let walkdir = walkdir.into_iter().filter_entry(|e| {
+        if ignore.is_empty() || e.depth() == 0 {
+            return true;
+        }
+        // Map the entry path onto the canonical root to match the ignore rules.
+        let abs_path = match e.path().strip_prefix(path) {
+            Ok(rel_path) => root.join(rel_path),
+            Err(_) => e.path().to_path_buf(),
+        };
+        !ignore.is_ignored(abs_path, e.file_type().is_dir())
+    });
+    for entry in walkdir.filter_map(|e| e.ok()) {
         let path = entry.path();
         if path.is_file() {
             let file = path.to_str().unwrap();
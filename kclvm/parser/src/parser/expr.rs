@@ -17,6 +17,7 @@ use kclvm_ast::ast::*;
 use kclvm_ast::node_ref;
 use kclvm_ast::token;
 use kclvm_ast::token::{BinOpToken, DelimToken, TokenKind, VALID_SPACES_LENGTH};
+use kclvm_error::ParseErrorMessage;
 use kclvm_span::symbol::kw;
 
 /// Parser implementation of expressions, which consists of sub-expressions,
@@ -286,6 +287,22 @@ impl<'a> Parser<'a> {
     /// primary_expr: operand | primary_expr select_suffix | primary_expr call_suffix | primary_expr slice_suffix
     /// Note: we need to look ahead 2 tokens to match select_suffix and slice_suffix, which actually breaks LL1 rule.
     fn parse_primary_expr(&mut self) -> NodeRef<Expr> {
+        let token = self.token;
+        if self.expr_depth >= self.limits.max_expr_depth {
+            let hi =
+                self.skip_nested_expr(ParseErrorMessage::ExprTooDeep(self.limits.max_expr_depth));
+            return Box::new(Node::node(
+                Expr::Missing(MissingExpr),
+                self.sess.struct_token_loc(token, hi),
+            ));
+        }
+        self.expr_depth += 1;
+        let expr = self.do_parse_primary_expr();
+        self.expr_depth -= 1;
+        expr
+    }
+
+    fn do_parse_primary_expr(&mut self) -> NodeRef<Expr> {
         let lo = self.token;
         let mut operand = self.parse_operand_expr();
 
@@ -1289,6 +1306,23 @@ impl<'a> Parser<'a> {
     /// config_entries: config_entry ((COMMA [NEWLINE] | NEWLINE) config_entry)* [COMMA] [NEWLINE]
     /// config_comp: LEFT_BRACE (config_entry comp_clause+ | NEWLINE _INDENT config_entry comp_clause+ _DEDENT) RIGHT_BRACE
     fn parse_config_expr(&mut self) -> NodeRef<Expr> {
+        let token = self.token;
+        if self.schema_nesting >= self.limits.max_schema_nesting {
+            let hi = self.skip_nested_expr(ParseErrorMessage::SchemaNestingTooDeep(
+                self.limits.max_schema_nesting,
+            ));
+            return Box::new(Node::node(
+                Expr::Config(ConfigExpr { items: vec![] }),
+                self.sess.struct_token_loc(token, hi),
+            ));
+        }
+        self.schema_nesting += 1;
+        let expr = self.do_parse_config_expr();
+        self.schema_nesting -= 1;
+        expr
+    }
+
+    fn do_parse_config_expr(&mut self) -> NodeRef<Expr> {
         let token = self.token;
         // LEFT_BRACE
         self.bump();
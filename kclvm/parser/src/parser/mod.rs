@@ -25,13 +25,16 @@ mod tests;
 mod ty;
 
 use crate::session::ParseSession;
+use crate::{ParseLimits, ParseMode};
 
 use compiler_base_span::span::{new_byte_pos, BytePos};
-use kclvm_ast::ast::{Comment, NodeRef, PosTuple};
-use kclvm_ast::token::{CommentKind, Token, TokenKind};
+use kclvm_ast::ast::{Comment, Expr, ExprStmt, MissingExpr, NodeRef, PosTuple, Stmt};
+use kclvm_ast::node_ref;
+use kclvm_ast::token::{BinCmpToken, BinOpToken, CommentKind, Token, TokenKind};
 use kclvm_ast::token_stream::{Cursor, TokenStream};
 use kclvm_error::ParseErrorMessage;
 use kclvm_span::symbol::Symbol;
+use kclvm_span::Span;
 use kclvm_utils::path::PathPrefix;
 
 /// The parser is built on top of the [`kclvm_parser::lexer`], and ordering KCL tokens
@@ -47,6 +50,17 @@ pub struct Parser<'a> {
     comments: Vec<NodeRef<Comment>>,
     /// parse-time session
     pub sess: &'a ParseSession,
+    /// The parser mode.
+    mode: ParseMode,
+    /// Whether to attach the comments to the AST nodes e.g., the schema attribute docs,
+    /// which is independent of the error-tolerant mode.
+    parse_comments: bool,
+    /// The nesting limits.
+    limits: ParseLimits,
+    /// The current nesting depth of expressions.
+    expr_depth: usize,
+    /// The current nesting depth of schema and config expressions.
+    schema_nesting: usize,
 }
 
 /// The DropMarker is used to mark whether to discard the token Mark whether to discard the token.
@@ -57,6 +71,22 @@ pub(crate) struct DropMarker(usize);
 
 impl<'a> Parser<'a> {
     pub fn new(sess: &'a ParseSession, stream: TokenStream) -> Self {
+        Self::new_with_mode(sess, stream, ParseMode::ParseComments)
+    }
+
+    /// New a parser with the parser mode.
+    #[inline]
+    pub fn new_with_mode(sess: &'a ParseSession, stream: TokenStream, mode: ParseMode) -> Self {
+        Self::new_with_limits(sess, stream, mode, ParseLimits::default())
+    }
+
+    /// New a parser with the parser mode and the nesting limits.
+    pub fn new_with_limits(
+        sess: &'a ParseSession,
+        stream: TokenStream,
+        mode: ParseMode,
+        limits: ParseLimits,
+    ) -> Self {
         let (non_comment_tokens, comments) = Parser::split_token_stream(sess, stream);
 
         let mut parser = Parser {
@@ -65,6 +95,11 @@ impl<'a> Parser<'a> {
             cursor: TokenStream::new(non_comment_tokens).cursor(),
             comments,
             sess,
+            parse_comments: !matches!(mode, ParseMode::Null),
+            mode,
+            limits,
+            expr_depth: 0,
+            schema_nesting: 0,
         };
 
         // bump to the first token
@@ -134,12 +169,164 @@ impl<'a> Parser<'a> {
         self.bump();
     }
 
+    /// Bump the closing angle bracket of the type parameters and the type arguments, where
+    /// the tokens `>>` and `>=` are split e.g., `Box<Box<int>>` and `b: Box<int>= ...`.
+    pub(crate) fn bump_close_angle(&mut self) {
+        let (lo, hi) = (self.token.span.lo(), self.token.span.hi());
+        let rest = match self.token.kind {
+            TokenKind::BinCmp(BinCmpToken::Gt) => {
+                self.bump();
+                return;
+            }
+            TokenKind::BinOp(BinOpToken::Shr) => TokenKind::BinCmp(BinCmpToken::Gt),
+            TokenKind::BinCmp(BinCmpToken::GtEq) => TokenKind::Assign,
+            _ => {
+                self.sess
+                    .struct_token_error(&[TokenKind::BinCmp(BinCmpToken::Gt).into()], self.token);
+                return;
+            }
+        };
+        let mid = lo + new_byte_pos(1);
+        self.prev_token = Token {
+            kind: TokenKind::BinCmp(BinCmpToken::Gt),
+            span: Span::new(lo, mid),
+        };
+        self.token = Token {
+            kind: rest,
+            span: Span::new(mid, hi),
+        };
+    }
+
     pub(crate) fn skip_newlines(&mut self) {
         while let TokenKind::Newline = self.token.kind {
             self.bump();
         }
     }
 
+    /// Skip the tokens of an expression which can not be parsed e.g., nested too deeply,
+    /// until the end of the first balanced delimiter group or a token which ends the
+    /// expression at the current nesting level.
+    pub(crate) fn skip_nested_tokens(&mut self) {
+        let mut depth = 0usize;
+        loop {
+            match self.token.kind {
+                TokenKind::Eof => break,
+                TokenKind::OpenDelim(_) => depth += 1,
+                TokenKind::CloseDelim(_) => {
+                    if depth == 0 {
+                        break;
+                    }
+                    depth -= 1;
+                    if depth == 0 {
+                        self.bump();
+                        break;
+                    }
+                }
+                TokenKind::Newline
+                | TokenKind::Comma
+                | TokenKind::Colon
+                | TokenKind::Indent(_)
+                | TokenKind::Dedent(_)
+                    if depth == 0 =>
+                {
+                    break
+                }
+                _ => {}
+            }
+            self.bump();
+        }
+    }
+
+    /// Report the nesting error on the expression starts at the current token and skip
+    /// it, returns the last token of the skipped expression.
+    pub(crate) fn skip_nested_expr(&mut self, msg: ParseErrorMessage) -> Token {
+        let token = self.token;
+        self.skip_nested_tokens();
+        let hi = if self.prev_token.span.hi() > token.span.lo() {
+            self.prev_token
+        } else {
+            token
+        };
+        self.sess
+            .struct_message_error(msg, Span::new(token.span.lo(), hi.span.hi()));
+        hi
+    }
+
+    /// Whether the parser is in the error-tolerant mode.
+    #[inline]
+    pub(crate) fn is_recover_mode(&self) -> bool {
+        matches!(self.mode, ParseMode::Recover)
+    }
+
+    /// Discard the current token which can not start a statement. In the error-tolerant
+    /// mode, the range of the continuous discarded tokens is recorded in `invalid`.
+    pub(crate) fn skip_invalid_stmt_token(&mut self, invalid: &mut Option<(Token, Token)>) {
+        let token = self.token;
+        self.bump();
+        if self.is_recover_mode() && !matches!(token.kind, TokenKind::Eof) {
+            match invalid {
+                Some((_, hi)) => *hi = token,
+                None => *invalid = Some((token, token)),
+            }
+        }
+    }
+
+    /// Discard the schema body statement starting from the token `lo` which can not be
+    /// parsed. In the error-tolerant mode, the tokens consumed by the statement, or the
+    /// current token when no token is consumed, are kept as a placeholder statement at the
+    /// end of `body` instead, and the placeholder of the continuous discarded tokens is
+    /// extended in place because the schema body statements are pushed in many places.
+    pub(crate) fn skip_invalid_body_stmt(
+        &mut self,
+        lo: Token,
+        marker: DropMarker,
+        body: &mut Vec<NodeRef<Stmt>>,
+        invalid: &mut Option<(Token, usize)>,
+    ) {
+        if !self.is_recover_mode() {
+            self.bump();
+            return;
+        }
+        let hi = if marker.0 == self.cursor.index() {
+            let token = self.token;
+            self.bump();
+            token
+        } else {
+            self.prev_token
+        };
+        let lo = match invalid {
+            // No statement is parsed after the last discarded tokens.
+            Some((start, index)) if *index + 1 == body.len() => {
+                body.pop();
+                *start
+            }
+            _ => lo,
+        };
+        *invalid = Some((lo, body.len()));
+        let stmt = self.invalid_stmt(lo, hi);
+        body.push(stmt);
+    }
+
+    /// Take the recorded invalid token range and build a placeholder statement for it,
+    /// thus the discarded source code is not missing in the AST.
+    pub(crate) fn take_invalid_stmt(
+        &mut self,
+        invalid: &mut Option<(Token, Token)>,
+    ) -> Option<NodeRef<Stmt>> {
+        invalid.take().map(|(lo, hi)| self.invalid_stmt(lo, hi))
+    }
+
+    /// Build a placeholder statement with a missing expression for the token range.
+    fn invalid_stmt(&mut self, lo: Token, hi: Token) -> NodeRef<Stmt> {
+        let pos = self.token_span_pos(lo, hi);
+        node_ref!(
+            Stmt::Expr(ExprStmt {
+                exprs: vec![node_ref!(Expr::Missing(MissingExpr), pos.clone())],
+            }),
+            pos
+        )
+    }
+
     /// Mark the token index.
     pub(crate) fn mark(&mut self) -> DropMarker {
         DropMarker(self.cursor.index())
@@ -48,19 +48,22 @@ impl<'a> Parser<'a> {
 
     fn parse_body(&mut self) -> Vec<NodeRef<Stmt>> {
         let mut stmts = Vec::new();
+        let mut invalid = None;
         loop {
             if matches!(self.token.kind, TokenKind::Eof) {
+                stmts.extend(self.take_invalid_stmt(&mut invalid));
                 self.bump();
                 break;
             }
 
             if let Some(stmt) = self.parse_stmt() {
+                stmts.extend(self.take_invalid_stmt(&mut invalid));
                 stmts.push(stmt);
             } else {
                 // Error recovery from panic mode: Once an error is detected (the statement is None),
                 // the symbols in the input are continuously discarded (one symbol at a time), until the
                 // "synchronous lexical unit" is found (the statement start token e.g., import, schema, etc).
-                self.bump();
+                self.skip_invalid_stmt_token(&mut invalid);
             }
         }
         stmts
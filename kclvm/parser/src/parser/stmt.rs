@@ -3,7 +3,7 @@
 
 use compiler_base_span::{span::new_byte_pos, BytePos, Span};
 use kclvm_ast::token::VALID_SPACES_LENGTH;
-use kclvm_ast::token::{CommentKind, DelimToken, LitKind, Token, TokenKind};
+use kclvm_ast::token::{BinCmpToken, CommentKind, DelimToken, LitKind, Token, TokenKind};
 use kclvm_ast::{ast::*, expr_as, node_ref};
 use kclvm_error::ParseErrorMessage;
 use kclvm_span::symbol::kw;
@@ -137,27 +137,31 @@ impl<'a> Parser<'a> {
         close_tok: TokenKind,
     ) -> Vec<NodeRef<Stmt>> {
         let mut stmt_list = Vec::new();
+        let mut invalid = None;
         self.validate_dedent();
         self.bump_token(open_tok);
         loop {
             if self.token.kind == TokenKind::Eof {
+                stmt_list.extend(self.take_invalid_stmt(&mut invalid));
                 self.bump();
                 break;
             }
 
             self.validate_dedent();
             if self.token.kind == close_tok {
+                stmt_list.extend(self.take_invalid_stmt(&mut invalid));
                 self.bump_token(close_tok);
                 break;
             }
 
             if let Some(stmt) = self.parse_stmt() {
+                stmt_list.extend(self.take_invalid_stmt(&mut invalid));
                 stmt_list.push(stmt);
             } else {
                 // Error recovery from panic mode: Once an error is detected (the statement is None),
                 // the symbols in the input are continuously discarded (one symbol at a time), until the
                 // "synchronous lexical unit" is found (the statement start token e.g., import, schema, etc).
-                self.bump();
+                self.skip_invalid_stmt_token(&mut invalid);
             }
         }
 
@@ -682,6 +686,7 @@ impl<'a> Parser<'a> {
 
     /// Syntax:
     /// schema_stmt: [decorators] (SCHEMA|MIXIN|PROTOCOL) NAME
+    ///   [LESS_THAN NAME (COMMA NAME)* GREATER_THAN]
     ///   [LEFT_BRACKETS [schema_arguments] RIGHT_BRACKETS]
     ///   [LEFT_PARENTHESES identifier (COMMA identifier)* RIGHT_PARENTHESES]
     ///   [for_host] COLON NEWLINE [schema_body]
@@ -727,6 +732,23 @@ impl<'a> Parser<'a> {
             is_protocol = true;
         }
 
+        // schema Name<T, ...>
+        let mut type_params = vec![];
+        if let TokenKind::BinCmp(BinCmpToken::Lt) = self.token.kind {
+            self.bump();
+            loop {
+                let type_param = self.parse_identifier();
+                let type_param_pos = type_param.pos();
+                type_params.push(node_ref!(type_param.node.get_name(), type_param_pos));
+                if let TokenKind::Comma = self.token.kind {
+                    self.bump();
+                } else {
+                    break;
+                }
+            }
+            self.bump_close_angle();
+        }
+
         // schema Name[args...]
         let args = if let TokenKind::OpenDelim(DelimToken::Bracket) = self.token.kind {
             self.parse_parameters(
@@ -786,6 +808,7 @@ impl<'a> Parser<'a> {
                     for_host_name,
                     is_mixin,
                     is_protocol,
+                    type_params,
                     args,
                     mixins: body.mixins,
                     body: body.body,
@@ -805,6 +828,7 @@ impl<'a> Parser<'a> {
                     for_host_name,
                     is_mixin,
                     is_protocol,
+                    type_params,
                     args,
                     mixins: vec![],
                     body: vec![],
@@ -987,10 +1011,12 @@ impl<'a> Parser<'a> {
         // body
         let mut body_body = Vec::new();
         let mut body_index_signature = None;
+        let mut invalid = None;
 
         loop {
             let marker = self.mark();
             self.validate_dedent();
+            let stmt_token = self.token;
             if matches!(
                 self.token.kind,
                 TokenKind::Dedent(VALID_SPACES_LENGTH) | TokenKind::Eof
@@ -1112,11 +1138,15 @@ impl<'a> Parser<'a> {
                 body_body.push(x);
             } else {
                 // Error recovery from panic mode: Once an error is detected (the statement is None).
-                self.bump();
+                self.skip_invalid_body_stmt(stmt_token, marker, &mut body_body, &mut invalid);
             }
             self.drop(marker);
         }
 
+        if self.parse_comments {
+            self.attach_schema_attr_docs(&mut body_body);
+        }
+
         // check_block
         let body_checks = self.parse_schema_check_block();
         self.validate_dedent();
@@ -1143,6 +1173,7 @@ impl<'a> Parser<'a> {
             for_host_name: None,
             is_mixin: false,
             is_protocol: false,
+            type_params: Vec::new(),
             args: None,
             decorators: Vec::new(),
         }
@@ -1215,6 +1246,43 @@ impl<'a> Parser<'a> {
         mixins
     }
 
+    /// Attach the line comments immediately preceding the schema attributes with the
+    /// same indentation to the attribute docs e.g.,
+    ///
+    /// ```kcl
+    /// schema Person:
+    ///     # The name of the person.
+    ///     name: str
+    /// ```
+    fn attach_schema_attr_docs(&self, body: &mut [NodeRef<Stmt>]) {
+        for stmt in body.iter_mut() {
+            let (line, column) = (stmt.line, stmt.column);
+            if let Stmt::SchemaAttr(attr) = &mut stmt.node {
+                if attr.doc.is_empty() {
+                    attr.doc = self.preceding_comments_doc(line, column);
+                }
+            }
+        }
+    }
+
+    /// Get the doc from the contiguous line comments which end at the previous line
+    /// of `line` and start at `column`.
+    fn preceding_comments_doc(&self, line: u64, column: u64) -> String {
+        let end = self.comments.partition_point(|comment| comment.line < line);
+        let mut expected_line = line;
+        let mut docs = vec![];
+        for comment in self.comments[..end].iter().rev() {
+            if comment.line + 1 != expected_line || comment.column != column {
+                break;
+            }
+            let text = comment.node.text.trim_start_matches('#');
+            docs.push(text.strip_prefix(' ').unwrap_or(text).trim_end());
+            expected_line = comment.line;
+        }
+        docs.reverse();
+        docs.join("\n")
+    }
+
     /// Syntax:
     /// schema_attribute_stmt: attribute_stmt NEWLINE
     /// attribute_stmt: [decorators] (identifier | string) [QUESTION] COLON type [(ASSIGN|COMP_OR) test]
@@ -1602,6 +1670,11 @@ impl<'a> Parser<'a> {
                 cursor: stream.cursor(),
                 comments: Vec::new(),
                 sess: this.sess,
+                parse_comments: this.parse_comments,
+                mode: this.mode.clone(),
+                limits: this.limits.clone(),
+                expr_depth: this.expr_depth,
+                schema_nesting: this.schema_nesting,
             };
 
             // bump to the first token
@@ -4,7 +4,7 @@ use super::Parser;
 
 use kclvm_ast::ast::{Expr, Node, NodeRef, Type};
 use kclvm_ast::token;
-use kclvm_ast::token::{BinOpToken, DelimToken, TokenKind};
+use kclvm_ast::token::{BinCmpToken, BinOpToken, DelimToken, TokenKind};
 use kclvm_ast::{ast, expr_as};
 use kclvm_span::symbol::{kw, sym};
 
@@ -14,7 +14,7 @@ impl<'a> Parser<'a> {
     /// type: type_element (OR type_element)*
     /// type_element: schema_type | function_type | basic_type | compound_type | literal_type
     /// function_type: LEFT_PARENTHESES [type_element (COMMA type_element)*] RIGHT_PARENTHESES [RIGHT_ARROW type_element]
-    /// schema_type: identifier
+    /// schema_type: identifier [LESS_THAN type (COMMA type)* GREATER_THAN]
     /// basic_type: STRING_TYPE | INT_TYPE | FLOAT_TYPE | BOOL_TYPE | ANY_TYPE
     /// compound_type: list_type | dict_type
     /// list_type: LEFT_BRACKETS (type)? RIGHT_BRACKETS
@@ -108,7 +108,19 @@ impl<'a> Parser<'a> {
         if let TokenKind::Ident(_) = self.token.kind {
             let ident = self.parse_identifier_expr();
             let ident = expr_as!(ident, Expr::Identifier).unwrap();
-            let t = Type::Named(ident);
+            // generic schema type e.g., `Box<str>`
+            let t = if let TokenKind::BinCmp(BinCmpToken::Lt) = self.token.kind {
+                self.bump();
+                let mut args = vec![self.parse_type_annotation()];
+                while let TokenKind::Comma = self.token.kind {
+                    self.bump();
+                    args.push(self.parse_type_annotation());
+                }
+                self.bump_close_angle();
+                Type::Generic(ast::GenericType { name: ident, args })
+            } else {
+                Type::Named(ident)
+            };
             return Box::new(Node::node(
                 t,
                 self.sess.struct_token_loc(token, self.prev_token),
@@ -0,0 +1,132 @@
+//! The dependency graphs of the files and the packages of a program, which are built by
+//! scanning the import statements like [crate::scan_imports], thus the build systems can
+//! integrate and visualize them without a full compilation.
+
+use std::collections::BTreeSet;
+use std::fmt::Write;
+
+use anyhow::Result;
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::MAIN_PKG;
+use kclvm_span::create_session_globals_then;
+use petgraph::graph::DiGraph;
+use serde::{Deserialize, Serialize};
+
+use crate::scan::scan_imports_with_session;
+use crate::{LoadProgramOptions, ParseSessionRef};
+
+/// DependencyGraph denotes a directed graph whose edges are from the dependents to the
+/// dependencies, and the nodes and the edges are sorted.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DependencyGraph {
+    pub nodes: Vec<String>,
+    pub edges: Vec<(String, String)>,
+    /// The import cycles, and each cycle is the sorted nodes of a strongly connected
+    /// component.
+    pub cycles: Vec<Vec<String>>,
+}
+
+impl DependencyGraph {
+    fn new(nodes: BTreeSet<String>, edges: BTreeSet<(String, String)>) -> Self {
+        let mut graph = DiGraph::<&str, ()>::new();
+        let indices: IndexMap<&str, _> = nodes
+            .iter()
+            .map(|node| (node.as_str(), graph.add_node(node.as_str())))
+            .collect();
+        for (from, to) in &edges {
+            graph.add_edge(indices[from.as_str()], indices[to.as_str()], ());
+        }
+        let mut cycles: Vec<Vec<String>> = petgraph::algo::tarjan_scc(&graph)
+            .into_iter()
+            .filter(|component| {
+                component.len() > 1 || graph.contains_edge(component[0], component[0])
+            })
+            .map(|component| {
+                let mut cycle: Vec<String> =
+                    component.iter().map(|n| graph[*n].to_string()).collect();
+                cycle.sort();
+                cycle
+            })
+            .collect();
+        cycles.sort();
+        Self {
+            nodes: nodes.into_iter().collect(),
+            edges: edges.into_iter().collect(),
+            cycles,
+        }
+    }
+
+    /// Returns the graph in the Graphviz DOT format.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph {\n");
+        for node in &self.nodes {
+            let _ = writeln!(dot, "    {:?};", node);
+        }
+        for (from, to) in &self.edges {
+            let _ = writeln!(dot, "    {:?} -> {:?};", from, to);
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+/// ProgramGraph denotes the file graph and the package graph of a program.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ProgramGraph {
+    /// The graph of the absolute file paths.
+    pub file_graph: DependencyGraph,
+    /// The graph of the package paths, and the main package is `__main__`.
+    pub pkg_graph: DependencyGraph,
+    /// The external packages e.g., the dependencies in `kcl.mod` used by the program,
+    /// which are the package names and the package root paths.
+    pub external_pkgs: IndexMap<String, String>,
+}
+
+/// Returns the file graph and the package graph of the program with the entries.
+///
+/// # Examples
+/// ```no_run
+/// use kclvm_parser::program_graph::get_program_graph;
+///
+/// let graph = get_program_graph(&["main.k"], None).unwrap();
+/// println!("{}", graph.pkg_graph.to_dot());
+/// ```
+pub fn get_program_graph(paths: &[&str], opts: Option<LoadProgramOptions>) -> Result<ProgramGraph> {
+    let opts = opts.unwrap_or_default();
+    let sess = ParseSessionRef::default();
+    let (graph, pkgmap) =
+        create_session_globals_then(move || scan_imports_with_session(sess, paths, &opts))?;
+
+    let main_pkg_names: IndexSet<&String> = pkgmap
+        .iter()
+        .filter(|(file, _)| file.pkg_path == MAIN_PKG)
+        .map(|(_, pkg)| &pkg.pkg_name)
+        .collect();
+    let mut external_pkgs = IndexMap::new();
+    for pkg in pkgmap.values() {
+        if !main_pkg_names.contains(&pkg.pkg_name) {
+            external_pkgs.insert(pkg.pkg_name.clone(), pkg.pkg_root.clone());
+        }
+    }
+
+    let mut files = BTreeSet::new();
+    let mut file_edges = BTreeSet::new();
+    let mut pkgs = BTreeSet::new();
+    let mut pkg_edges = BTreeSet::new();
+    for file in graph.paths() {
+        let path = file.get_path().display().to_string();
+        files.insert(path.clone());
+        pkgs.insert(file.pkg_path.clone());
+        for dep in graph.dependencies_of(&file) {
+            file_edges.insert((path.clone(), dep.get_path().display().to_string()));
+            if dep.pkg_path != file.pkg_path {
+                pkg_edges.insert((file.pkg_path.clone(), dep.pkg_path.clone()));
+            }
+        }
+    }
+    Ok(ProgramGraph {
+        file_graph: DependencyGraph::new(files, file_edges),
+        pkg_graph: DependencyGraph::new(pkgs, pkg_edges),
+        external_pkgs,
+    })
+}
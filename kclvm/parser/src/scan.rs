@@ -0,0 +1,171 @@
+//! The fast scan of the import graph, which only lexes the source code and collects
+//! the import statements instead of parsing the whole files, thus build tools can
+//! compute the dependencies of KCL files without a full compilation.
+
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::path::Path;
+
+use anyhow::Result;
+use kclvm_ast::ast::{ImportStmt, Module, Node, Stmt};
+use kclvm_ast::token::{Token, TokenKind};
+use kclvm_ast::MAIN_PKG;
+use kclvm_span::create_session_globals_then;
+use kclvm_span::symbol::kw;
+use kclvm_utils::path::PathPrefix;
+
+use crate::entry::get_compile_entries_from_paths;
+use crate::file_graph::{Pkg, PkgFile, PkgFileGraph, PkgMap};
+use crate::lexer::parse_token_streams;
+use crate::{get_deps, LoadProgramOptions, ParseSessionRef};
+
+/// Scan the import statements of the KCL files and their dependent files to build the
+/// file dependency graph. Only the import statements are lexed, and the import paths
+/// are resolved in the same way as [`crate::load_program`].
+///
+/// # Examples
+/// ```no_run
+/// use kclvm_parser::scan_imports;
+///
+/// let graph = scan_imports(&["main.k"], None).unwrap();
+/// let files = graph.toposort().unwrap_or_else(|_| graph.paths());
+/// ```
+pub fn scan_imports(paths: &[&str], opts: Option<LoadProgramOptions>) -> Result<PkgFileGraph> {
+    let opts = opts.unwrap_or_default();
+    let sess = ParseSessionRef::default();
+    create_session_globals_then(move || scan_imports_with_session(sess, paths, &opts))
+        .map(|(graph, _)| graph)
+}
+
+/// Scan the import graph like [scan_imports], and returns the package information of
+/// the files as well.
+pub(crate) fn scan_imports_with_session(
+    sess: ParseSessionRef,
+    paths: &[&str],
+    opts: &LoadProgramOptions,
+) -> Result<(PkgFileGraph, PkgMap)> {
+    let paths: Vec<String> = paths.iter().map(|p| p.to_string()).collect();
+    let compile_entries = get_compile_entries_from_paths(&paths, opts)?;
+    let mut graph = PkgFileGraph::default();
+    let mut pkgs: HashMap<String, Vec<String>> = HashMap::new();
+    let mut pkgmap = PkgMap::new();
+    let mut visited: HashSet<PkgFile> = HashSet::new();
+    let mut unscanned: VecDeque<(PkgFile, Option<String>)> = VecDeque::new();
+    for entry in compile_entries.iter() {
+        let maybe_k_codes = entry.get_k_codes();
+        for (i, f) in entry.get_k_files().iter().enumerate() {
+            let file = PkgFile::new(f.adjust_canonicalization().into(), MAIN_PKG.to_string());
+            pkgmap.insert(
+                file.clone(),
+                Pkg {
+                    pkg_name: entry.name().clone(),
+                    pkg_root: entry.path().into(),
+                },
+            );
+            if visited.insert(file.clone()) {
+                let code = maybe_k_codes.get(i).cloned().flatten();
+                unscanned.push_back((file, code));
+            }
+        }
+    }
+    // Bfs the files and the imported files.
+    while let Some((file, code)) = unscanned.pop_front() {
+        opts.cancel_token.check()?;
+        let filename = file.get_path().to_str().unwrap().to_string();
+        let src = match code {
+            Some(code) => code,
+            None => match opts.file_provider.read_to_string(Path::new(&filename)) {
+                Ok(src) => src,
+                Err(err) => {
+                    return Err(anyhow::anyhow!(
+                        "Failed to load KCL file '{filename}'. Because '{err}'"
+                    ));
+                }
+            },
+        };
+        let m = scan_module_imports(&sess, &filename, src);
+        let deps = get_deps(&file, &m, &mut pkgs, &pkgmap, opts, sess.clone())?;
+        graph.update_file(&file, deps.keys());
+        for (dep, pkg) in deps {
+            pkgmap.insert(dep.clone(), pkg);
+            if visited.insert(dep.clone()) {
+                unscanned.push_back((dep, None));
+            }
+        }
+    }
+    Ok((graph, pkgmap))
+}
+
+/// Lex the source code and returns a module which only contains the import statements.
+fn scan_module_imports(sess: &ParseSessionRef, filename: &str, src: String) -> Module {
+    let sf = sess.add_source_file(filename, src);
+    let src = sf.src.as_ref().map(|s| s.as_str()).unwrap_or_default();
+    let stream = parse_token_streams(sess, src, sf.start_pos);
+    let tokens: &[Token] = &stream;
+
+    let mut body = vec![];
+    let mut at_stmt_start = true;
+    let mut i = 0;
+    while i < tokens.len() {
+        let token = tokens[i];
+        i += 1;
+        if !(at_stmt_start && token.is_keyword(kw::Import)) {
+            at_stmt_start = matches!(
+                token.kind,
+                TokenKind::Newline | TokenKind::Indent(_) | TokenKind::Dedent(_)
+            );
+            continue;
+        }
+        at_stmt_start = false;
+        // import_stmt: IMPORT dot_name (AS NAME)?
+        let path_start = i;
+        let mut path = String::new();
+        while let Some(TokenKind::Dot | TokenKind::DotDotDot) = tokens.get(i).map(|t| t.kind) {
+            path.push_str(if tokens[i].kind == TokenKind::Dot {
+                "."
+            } else {
+                "..."
+            });
+            i += 1;
+        }
+        let mut name = None;
+        while let Some(TokenKind::Ident(symbol)) = tokens.get(i).map(|t| t.kind) {
+            let ident = symbol.as_str();
+            path.push_str(&ident);
+            name = Some(ident);
+            i += 1;
+            match (tokens.get(i), tokens.get(i + 1)) {
+                (Some(dot), Some(next))
+                    if dot.kind == TokenKind::Dot && matches!(next.kind, TokenKind::Ident(_)) =>
+                {
+                    path.push('.');
+                    i += 1;
+                }
+                _ => break,
+            }
+        }
+        let name = match name {
+            Some(name) => name,
+            // Invalid import statements are reported by the parser.
+            None => continue,
+        };
+        let path_node = Node::node(
+            path.clone(),
+            sess.struct_token_loc(tokens[path_start], tokens[i - 1]),
+        );
+        body.push(Box::new(Node::node(
+            Stmt::Import(ImportStmt {
+                path: path_node,
+                rawpath: path,
+                name,
+                asname: None,
+                pkg_name: String::new(),
+            }),
+            sess.struct_token_loc(token, tokens[i - 1]),
+        )));
+    }
+    Module {
+        filename: filename.to_string(),
+        body,
+        ..Default::default()
+    }
+}
@@ -1,37 +1,169 @@
 use anyhow::Result;
+use compiler_base_error::diagnostic_handler::DiagnosticHandler;
+use compiler_base_error::{DiagnosticStyle, Emitter};
 use compiler_base_macros::bug;
 use compiler_base_session::Session;
-use indexmap::IndexSet;
+use compiler_base_span::{FilePathMapping, SourceMap};
+use indexmap::{IndexMap, IndexSet};
 use kclvm_ast::token::Token;
-use kclvm_error::{Diagnostic, Handler, ParseError, ParseErrorMessage};
-use kclvm_span::{BytePos, Loc, Span};
+use kclvm_error::diagnostic::Message;
+use kclvm_error::{Diagnostic, DiagnosticId, Handler, Level, ParseError, ParseErrorMessage};
+use kclvm_span::{BytePos, Loc, SourceFile, Span};
 use parking_lot::RwLock;
+use rustc_span::source_map::RealFileLoader;
+use rustc_span::{FileName, RealFileName, SourceFileHash, SourceFileHashAlgorithm};
+use std::path::PathBuf;
 use std::sync::Arc;
 
 pub type ParseSessionRef = Arc<ParseSession>;
 
 /// ParseSession represents the data associated with a parse session such as the
-/// source map and the error handler.
-#[derive(Default)]
-pub struct ParseSession(pub Arc<Session>, pub RwLock<Handler>);
+/// source map, the error handler, the path prefix mappings of the file names and the
+/// keys of the reported diagnostics.
+pub struct ParseSession {
+    /// The global session holding the source map.
+    pub session: Arc<Session>,
+    /// The error handler collecting the parse errors.
+    pub handler: RwLock<Handler>,
+    /// The path prefix mappings of the file names.
+    path_mapping: RwLock<FilePathMapping>,
+    /// The keys of the reported diagnostics, which deduplicate the diagnostics.
+    diagnostic_keys: RwLock<IndexSet<DiagnosticKey>>,
+}
+
+impl Default for ParseSession {
+    /// New a parse session whose source map hashes the file sources with sha256,
+    /// see [ParseSession::file_hashes].
+    fn default() -> Self {
+        Self::with_session(Arc::new(Session::new(
+            Arc::new(sha256_source_map()),
+            Arc::new(DiagnosticHandler::default()),
+        )))
+    }
+}
+
+/// New a source map which hashes the file sources with sha256.
+fn sha256_source_map() -> SourceMap {
+    SourceMap::with_file_loader_and_hash_kind(
+        Box::new(RealFileLoader),
+        FilePathMapping::empty(),
+        SourceFileHashAlgorithm::Sha256,
+    )
+}
 
 impl ParseSession {
     /// New a parse session with the global session.
     #[inline]
     pub fn with_session(sess: Arc<Session>) -> Self {
-        Self(sess, RwLock::new(Handler::default()))
+        Self {
+            session: sess,
+            handler: RwLock::new(Handler::default()),
+            path_mapping: RwLock::new(FilePathMapping::empty()),
+            diagnostic_keys: RwLock::new(IndexSet::default()),
+        }
+    }
+
+    /// Set the path prefix mappings `(from, to)` of the files added into the source map
+    /// later, and the later mappings take precedence. The file names in the AST nodes,
+    /// the diagnostics and the runtime information are remapped, thus the outputs don't
+    /// depend on the absolute build paths.
+    pub fn set_path_prefix_map(&self, map: &[(String, String)]) {
+        *self.path_mapping.write() = FilePathMapping::new(
+            map.iter()
+                .map(|(from, to)| (PathBuf::from(from), PathBuf::from(to)))
+                .collect(),
+        );
+    }
+
+    /// Remap the path prefix of the file name with the path prefix mappings, and returns
+    /// the file name unchanged if no mapping matches it.
+    pub fn remap_path(&self, filename: &str) -> String {
+        let (path, _) = self.path_mapping.read().map_prefix(PathBuf::from(filename));
+        path.display().to_string()
+    }
+
+    /// Remap the file names of the diagnostic ranges, see [ParseSession::remap_path].
+    pub fn remap_diagnostic(&self, mut diag: Diagnostic) -> Diagnostic {
+        let ranges = diag
+            .messages
+            .iter_mut()
+            .map(|msg| &mut msg.range)
+            .chain(diag.labels.iter_mut().map(|label| &mut label.range))
+            .chain(diag.fixes.iter_mut().map(|fix| &mut fix.range));
+        for range in ranges {
+            if !range.0.filename.is_empty() {
+                range.0.filename = self.remap_path(&range.0.filename);
+                range.1.filename = self.remap_path(&range.1.filename);
+            }
+        }
+        diag
+    }
+
+    /// Add the file source into the source map and returns the source file, and the file
+    /// name is remapped with the path prefix mappings.
+    #[inline]
+    pub fn add_source_file(&self, filename: &str, src: String) -> Arc<SourceFile> {
+        let filename = match crate::source_map_filename(filename) {
+            FileName::Real(RealFileName::LocalPath(path)) => {
+                match self.path_mapping.read().map_prefix(path.clone()) {
+                    (virtual_name, true) => FileName::Real(RealFileName::Remapped {
+                        local_path: Some(path),
+                        virtual_name,
+                    }),
+                    _ => FileName::Real(RealFileName::LocalPath(path)),
+                }
+            }
+            filename => filename,
+        };
+        self.session.sm.new_source_file(filename, src)
+    }
+
+    /// Emit the diagnostics of the parse session to the custom emitter instead of the
+    /// terminal stderr and return whether has errors, e.g., an emitter forwarding the
+    /// structured diagnostics to a language server or a log collector.
+    #[inline]
+    pub fn emit_with_emitter(&self, emitter: &mut dyn Emitter<DiagnosticStyle>) -> Result<bool> {
+        self.handler
+            .write()
+            .emit_with_emitter(&self.session, emitter)
+    }
+
+    /// Returns the hex encoded sha256 hashes of the file sources in the source map keyed
+    /// by the filenames, and the hashes are computed before any source normalization
+    /// e.g., the BOM removal, thus they match the file contents on the disk.
+    pub fn file_hashes(&self) -> IndexMap<String, String> {
+        self.session
+            .sm
+            .files()
+            .iter()
+            .filter_map(|sf| {
+                let hash = if sf.src_hash.kind == SourceFileHashAlgorithm::Sha256 {
+                    sf.src_hash
+                } else {
+                    // The source map of a global session may use the other algorithms,
+                    // and then the hash is computed from the normalized source.
+                    SourceFileHash::new(SourceFileHashAlgorithm::Sha256, sf.src.as_ref()?)
+                };
+                let hex: String = hash
+                    .hash_bytes()
+                    .iter()
+                    .map(|b| format!("{:02x}", b))
+                    .collect();
+                Some((sf.name.prefer_remapped().to_string(), hex))
+            })
+            .collect()
     }
 
     /// Lookup char pos from span.
     #[inline]
     pub(crate) fn lookup_char_pos(&self, pos: BytePos) -> Loc {
-        self.0.sm.lookup_char_pos(pos)
+        self.session.sm.lookup_char_pos(pos)
     }
 
     /// Returns the source snippet as [String] corresponding to the given [Span].
     #[inline]
     pub fn span_to_snippet(&self, span: Span) -> String {
-        self.0.sm.span_to_snippet(span).unwrap()
+        self.session.sm.span_to_snippet(span).unwrap()
     }
 
     /// Struct an loc of first and last valid tokens in an expr, returns a loc tuple
@@ -95,11 +227,7 @@ impl ParseSession {
     /// Add a error into the session.
     #[inline]
     fn add_parse_err(&self, err: ParseError) {
-        let add_error = || -> Result<()> {
-            self.0.add_err(err.clone().into_diag(&self.0)?)?;
-            self.1.write().add_diagnostic(err.into_diag(&self.0)?);
-            Ok(())
-        };
+        let add_error = || -> Result<()> { self.add_diagnostic(err.into_diag(&self.session)?) };
         if let Err(err) = add_error() {
             bug!(
                 "compiler session internal error occurs: {}",
@@ -108,16 +236,83 @@ impl ParseSession {
         }
     }
 
+    /// Set the maximum number of the errors collected by the session, and the errors
+    /// beyond it are dropped and counted in the `dropped_errors` of the session handler.
+    #[inline]
+    pub fn set_max_errors(&self, max_errors: Option<usize>) {
+        self.handler.write().max_errors = max_errors;
+    }
+
+    /// Add a diagnostic into the session. The diagnostic is skipped when there is
+    /// already a diagnostic with the same level, code and messages including the spans
+    /// in the session, e.g., the same file with syntax errors is parsed into multiple
+    /// packages.
+    pub fn add_diagnostic(&self, diagnostic: Diagnostic) -> Result<()> {
+        if !self
+            .diagnostic_keys
+            .write()
+            .insert(DiagnosticKey::new(&diagnostic))
+        {
+            return Ok(());
+        }
+        self.session.add_err(diagnostic.clone())?;
+        self.handler.write().add_diagnostic(diagnostic);
+        Ok(())
+    }
+
     /// Append diagnostics into the parse session.
     pub fn append_diagnostic(&self, diagnostics: IndexSet<Diagnostic>) -> &Self {
         for diagnostic in diagnostics {
-            self.1.write().add_diagnostic(diagnostic);
+            self.diagnostic_keys
+                .write()
+                .insert(DiagnosticKey::new(&diagnostic));
+            self.handler.write().add_diagnostic(diagnostic);
         }
         self
     }
 
+    /// Returns the diagnostics of the session in a stable order which is sorted by
+    /// the file and the position, and the diagnostics at the same position keep
+    /// their reporting order.
+    pub fn sorted_diagnostics(&self) -> IndexSet<Diagnostic> {
+        let mut diagnostics: Vec<Diagnostic> =
+            self.handler.read().diagnostics.iter().cloned().collect();
+        diagnostics.sort_by(|a, b| {
+            let pos = |diag: &Diagnostic| {
+                diag.messages.first().map(|msg| {
+                    (
+                        msg.range.0.filename.clone(),
+                        msg.range.0.line,
+                        msg.range.0.column,
+                    )
+                })
+            };
+            pos(a).cmp(&pos(b))
+        });
+        diagnostics.into_iter().collect()
+    }
+
     /// Classify diagnostics into errors and warnings.
     pub fn classification(&self) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
-        self.1.read().classification()
+        self.handler.read().classification()
+    }
+}
+
+/// The diagnostic key which consists of the level, the code and the messages with
+/// their file and span, which is used to deduplicate the diagnostics.
+#[derive(PartialEq, Eq, Hash)]
+struct DiagnosticKey {
+    level: Level,
+    code: Option<DiagnosticId>,
+    messages: Vec<Message>,
+}
+
+impl DiagnosticKey {
+    fn new(diag: &Diagnostic) -> Self {
+        Self {
+            level: diag.level,
+            code: diag.code.clone(),
+            messages: diag.messages.clone(),
+        }
     }
 }
@@ -5,7 +5,7 @@ use std::{
 };
 
 use compiler_base_span::{FilePathMapping, SourceMap};
-use entry::expand_input_files;
+use entry::{expand_input_files, OverlayFileProvider};
 use kclvm_config::modfile::{get_vendor_home, KCL_PKG_PATH};
 
 use crate::*;
@@ -842,6 +842,61 @@ fn test_expand_input_files() {
     assert_eq!(input, expected_files);
 }
 
+#[test]
+#[cfg(not(windows))]
+fn test_kclignore() {
+    let path = Path::new("./testdata/kclignore").canonicalize().unwrap();
+    let file_names = |files: Vec<String>| -> Vec<String> {
+        files
+            .iter()
+            .map(|f| {
+                Path::new(f)
+                    .strip_prefix(&path)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect()
+    };
+    // The ignored files and directories are skipped when walking the directory.
+    let files = get_kcl_files(&path, true).unwrap();
+    assert_eq!(
+        file_names(files),
+        vec![
+            "apps/x/prod/main.k",
+            "apps/y/prod/main.k",
+            "keep_gen.k",
+            "main.k"
+        ]
+    );
+    // The ignored files are filtered from the matched files of the glob patterns.
+    let mut files = expand_input_files(&[path.join("**").join("*.k").display().to_string()]);
+    files.sort();
+    assert_eq!(
+        file_names(files),
+        vec![
+            "apps/x/prod/main.k",
+            "apps/y/prod/main.k",
+            "keep_gen.k",
+            "main.k"
+        ]
+    );
+    // The relative glob patterns are matched from the working directory.
+    let opts = LoadProgramOptions {
+        work_dir: path.display().to_string(),
+        ..Default::default()
+    };
+    let entries = get_compile_entries_from_paths(&["./apps/**/prod".to_string()], &opts).unwrap();
+    let mut files = file_names(
+        entries
+            .iter()
+            .flat_map(|entry| entry.get_k_files().clone())
+            .collect(),
+    );
+    files.sort();
+    assert_eq!(files, vec!["apps/x/prod/main.k", "apps/y/prod/main.k"]);
+}
+
 #[test]
 fn parse_all_file_under_path() {
     let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
@@ -870,3 +925,852 @@ fn parse_all_file_under_path() {
 
     assert_eq!(res.paths.len(), 1);
 }
+
+#[test]
+fn test_load_program_parallel() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse")
+        .join("main.k");
+    let path = testpath.to_str().unwrap();
+
+    let load = |parallel: bool| {
+        let sess = ParseSessionRef::default();
+        let opts = LoadProgramOptions {
+            parallel,
+            ..Default::default()
+        };
+        let res = load_program(sess.clone(), &[path], Some(opts), None).unwrap();
+        (res, sess)
+    };
+    let (expected, expected_sess) = load(false);
+    let (got, got_sess) = load(true);
+
+    let mut expected_pkgs: Vec<&String> = expected.program.pkgs.keys().collect();
+    let mut got_pkgs: Vec<&String> = got.program.pkgs.keys().collect();
+    expected_pkgs.sort();
+    got_pkgs.sort();
+    assert_eq!(got_pkgs, vec!["__main__", "pkg1", "pkg2"]);
+    assert_eq!(got_pkgs, expected_pkgs);
+    assert_eq!(got.program.modules.len(), 5);
+    assert_eq!(got.paths, expected.paths);
+    assert_eq!(got.errors, expected.errors);
+    assert!(!got.errors.is_empty());
+    assert_eq!(
+        got_sess.session.diag_handler.has_errors().unwrap(),
+        expected_sess.session.diag_handler.has_errors().unwrap()
+    );
+    // The sources of the files parsed on the worker threads are in the source map.
+    let sorted_hashes = |sess: &ParseSessionRef| {
+        let mut hashes: Vec<(String, String)> = sess.file_hashes().into_iter().collect();
+        hashes.sort();
+        hashes
+    };
+    assert_eq!(sorted_hashes(&got_sess).len(), 5);
+    assert_eq!(sorted_hashes(&got_sess), sorted_hashes(&expected_sess));
+}
+
+#[test]
+fn test_load_program_with_ast_cache() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse")
+        .join("main.k");
+    let path = testpath.to_str().unwrap();
+    let cache_dir = std::env::temp_dir().join(format!("kcl_ast_cache_{}", std::process::id()));
+    let _ = std::fs::remove_dir_all(&cache_dir);
+
+    let load = |cache_dir: Option<String>| {
+        let opts = LoadProgramOptions {
+            cache_dir,
+            ..Default::default()
+        };
+        load_program(ParseSessionRef::default(), &[path], Some(opts), None).unwrap()
+    };
+    let dump = |res: &LoadProgramResult| {
+        let mut modules: Vec<String> = res
+            .program
+            .modules
+            .values()
+            .map(|m| serde_json::to_string(&*m.read().unwrap()).unwrap())
+            .collect();
+        modules.sort();
+        modules
+    };
+    let expected = load(None);
+    let cache_dir_str = cache_dir.to_str().unwrap().to_string();
+    // Cold cache.
+    let first = load(Some(cache_dir_str.clone()));
+    // Warm cache.
+    let second = load(Some(cache_dir_str));
+
+    let cached_files: Vec<PathBuf> = glob::glob(&format!("{}/**/*.json", cache_dir.display()))
+        .unwrap()
+        .flatten()
+        .collect();
+    // The module with syntax errors is not cached.
+    assert_eq!(cached_files.len(), 4);
+    assert_eq!(dump(&first), dump(&expected));
+    assert_eq!(dump(&second), dump(&expected));
+    assert_eq!(second.errors, expected.errors);
+    assert!(!second.errors.is_empty());
+    let _ = std::fs::remove_dir_all(&cache_dir);
+}
+
+#[test]
+fn test_parse_session_deduplicated_sorted_diagnostics() {
+    let sess = ParseSessionRef::default();
+    let parse = |filename: &str, code: &str| {
+        parse_file_with_session(sess.clone(), filename, Some(code.to_string())).unwrap();
+        sess.handler.read().diagnostics.len()
+    };
+    let count = create_session_globals_then(|| {
+        parse("b.k", "b = 1 +");
+        let count = parse("a.k", "a = 1 +");
+        // The same syntax errors of the file parsed twice are reported once.
+        assert_eq!(parse("b.k", "b = 1 +"), count);
+        count
+    });
+    assert_eq!(
+        sess.session.emit_all_diags_into_string().unwrap().len(),
+        count
+    );
+    let mut filenames: Vec<String> = sess
+        .sorted_diagnostics()
+        .iter()
+        .map(|diag| diag.messages[0].range.0.filename.clone())
+        .collect();
+    filenames.dedup();
+    assert_eq!(filenames, vec!["a.k", "b.k"]);
+}
+
+#[test]
+fn test_parse_session_distinct_diagnostics_at_same_span() {
+    use kclvm_error::{Diagnostic, Level, Position};
+
+    let sess = ParseSessionRef::default();
+    let pos = Position {
+        filename: "main.k".to_string(),
+        line: 1,
+        column: Some(0),
+    };
+    let diag = |message: &str| {
+        Diagnostic::new_with_code(
+            Level::Error,
+            message,
+            None,
+            (pos.clone(), pos.clone()),
+            None,
+            None,
+        )
+    };
+    sess.add_diagnostic(diag("invalid syntax")).unwrap();
+    sess.add_diagnostic(diag("unexpected token")).unwrap();
+    sess.add_diagnostic(diag("invalid syntax")).unwrap();
+    let messages: Vec<String> = sess
+        .handler
+        .read()
+        .diagnostics
+        .iter()
+        .map(|diag| diag.messages[0].message.clone())
+        .collect();
+    assert_eq!(messages, vec!["invalid syntax", "unexpected token"]);
+}
+
+#[test]
+fn test_parse_schema_attr_comment_doc() {
+    let code = "schema A:\n    # doc of a\n    a: int\n    b: str  # not a doc\n    c: str\n";
+    let parse = |mode: ParseMode| {
+        let m = create_session_globals_then(|| {
+            parse_file_with_mode(
+                ParseSessionRef::default(),
+                "test.k",
+                Some(code.to_string()),
+                mode,
+            )
+        })
+        .unwrap();
+        match &m.body[0].node {
+            ast::Stmt::Schema(schema_stmt) => schema_stmt
+                .body
+                .iter()
+                .map(|stmt| match &stmt.node {
+                    ast::Stmt::SchemaAttr(attr) => attr.doc.clone(),
+                    _ => panic!("invalid schema attribute"),
+                })
+                .collect::<Vec<String>>(),
+            _ => panic!("invalid schema statement"),
+        }
+    };
+    assert_eq!(parse(ParseMode::ParseComments), vec!["doc of a", "", ""]);
+    assert_eq!(parse(ParseMode::Recover), vec!["doc of a", "", ""]);
+    assert_eq!(parse(ParseMode::Null), vec!["", "", ""]);
+}
+
+#[test]
+fn test_lex_file() {
+    let src = "import a\n\nschema Person:\n    name: str = \"\" # name\n\nb = True and None\n";
+    let tokens: Vec<(LexedTokenKind, String)> = lex_file(src)
+        .into_iter()
+        .map(|(token, span)| {
+            assert_eq!(&src[span.lo..span.hi], token.text);
+            (token.kind, token.text)
+        })
+        .collect();
+    let expected = vec![
+        (LexedTokenKind::Keyword, "import"),
+        (LexedTokenKind::Ident, "a"),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Keyword, "schema"),
+        (LexedTokenKind::Ident, "Person"),
+        (LexedTokenKind::Punctuation, ":"),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Indent, ""),
+        (LexedTokenKind::Ident, "name"),
+        (LexedTokenKind::Punctuation, ":"),
+        (LexedTokenKind::Ident, "str"),
+        (LexedTokenKind::Punctuation, "="),
+        (
+            LexedTokenKind::Literal(kclvm_ast::token::LitKind::Str {
+                is_long_string: false,
+                is_raw: false,
+            }),
+            "\"\"",
+        ),
+        (LexedTokenKind::Comment, "# name"),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Dedent, ""),
+        (LexedTokenKind::Ident, "b"),
+        (LexedTokenKind::Punctuation, "="),
+        (
+            LexedTokenKind::Literal(kclvm_ast::token::LitKind::Bool),
+            "True",
+        ),
+        (LexedTokenKind::Operator, "and"),
+        (
+            LexedTokenKind::Literal(kclvm_ast::token::LitKind::None),
+            "None",
+        ),
+        (LexedTokenKind::Newline, "\n"),
+        (LexedTokenKind::Eof, ""),
+    ];
+    let expected: Vec<(LexedTokenKind, String)> = expected
+        .into_iter()
+        .map(|(kind, text)| (kind, text.to_string()))
+        .collect();
+    assert_eq!(tokens, expected);
+}
+
+#[test]
+fn test_module_cache_apply_edit() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse")
+        .join("main.k");
+    let path = testpath.to_str().unwrap();
+    let module_cache = KCLModuleCache::default();
+    load_program(
+        ParseSessionRef::default(),
+        &[path],
+        None,
+        Some(module_cache.clone()),
+    )
+    .unwrap();
+    let get_path = |name: &str| {
+        let cache = module_cache.read().unwrap();
+        cache
+            .file_pkg
+            .keys()
+            .find(|p| p.ends_with(name))
+            .unwrap()
+            .clone()
+    };
+    let has_deps = |path: &PathBuf| {
+        let cache = module_cache.read().unwrap();
+        cache.file_pkg[path]
+            .iter()
+            .all(|file| cache.dep_cache.contains_key(file))
+    };
+    let pkg1_a = get_path("pkg1/a.k");
+    let pkg2_a = get_path("pkg2/a.k");
+    assert!(has_deps(&pkg1_a));
+    assert!(has_deps(&pkg2_a));
+
+    // The dependencies are kept when the imports are not changed.
+    let errors = module_cache
+        .write()
+        .unwrap()
+        .apply_edit(&pkg1_a, 4..5, "2")
+        .unwrap();
+    assert!(errors.is_empty());
+    assert!(has_deps(&pkg1_a));
+    // The dependencies are invalidated when the imports are changed.
+    let errors = module_cache
+        .write()
+        .unwrap()
+        .apply_edit(&pkg2_a, 0..12, "")
+        .unwrap();
+    assert!(errors.is_empty());
+    assert!(!has_deps(&pkg2_a));
+    // Invalid edit ranges.
+    assert!(module_cache
+        .write()
+        .unwrap()
+        .apply_edit(&pkg1_a, 5..4, "")
+        .is_err());
+    assert!(module_cache
+        .write()
+        .unwrap()
+        .apply_edit(&pkg1_a, 0..100, "")
+        .is_err());
+
+    // The edited module is used by the next loading.
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[path],
+        None,
+        Some(module_cache.clone()),
+    )
+    .unwrap();
+    let m = res
+        .program
+        .modules
+        .iter()
+        .find(|(filename, _)| Path::new(filename).ends_with("pkg1/a.k"))
+        .unwrap()
+        .1
+        .read()
+        .unwrap()
+        .clone();
+    match &m.body[0].node {
+        ast::Stmt::Assign(assign) => match &assign.value.node {
+            ast::Expr::NumberLit(number) => {
+                assert_eq!(number.value, ast::NumberLitValue::Int(2))
+            }
+            _ => panic!("invalid assign value"),
+        },
+        _ => panic!("invalid stmt"),
+    }
+}
+
+#[test]
+fn test_module_cache_apply_edit_with_file_provider() {
+    let path = PathBuf::from("/kcl_virtual_fs").join("main.k");
+    let mut provider = OverlayFileProvider::default();
+    provider.insert_file(&path, "a = 1\n".to_string());
+    let mut module_cache = ModuleCache::default();
+    module_cache.last_compile_input.1 = Some(LoadProgramOptions {
+        file_provider: Arc::new(provider),
+        ..Default::default()
+    });
+    let errors = module_cache.apply_edit(&path, 4..5, "2").unwrap();
+    assert!(errors.is_empty());
+    assert_eq!(module_cache.source_code[&path], "a = 2\n");
+}
+
+#[test]
+fn test_load_program_with_file_provider() {
+    let root = PathBuf::from("/kcl_virtual_fs");
+    let mut provider = OverlayFileProvider::default();
+    provider.insert_file(root.join("kcl.mod"), "".to_string());
+    provider.insert_file(root.join("main.k"), "import pkg\n\na = pkg.b\n".to_string());
+    provider.insert_file(root.join("pkg").join("b.k"), "b = 1\n".to_string());
+    provider.insert_file(root.join("pkg").join("b_test.k"), "c = 1\n".to_string());
+    let opts = LoadProgramOptions {
+        file_provider: Arc::new(provider),
+        ..Default::default()
+    };
+    let main = root.join("main.k");
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[main.to_str().unwrap()],
+        Some(opts),
+        None,
+    )
+    .unwrap();
+    assert!(res.errors.is_empty(), "{:?}", res.errors);
+    let mut pkgs: Vec<&String> = res.program.pkgs.keys().collect();
+    pkgs.sort();
+    assert_eq!(pkgs, vec!["__main__", "pkg"]);
+    assert_eq!(
+        res.program.pkgs["pkg"],
+        vec![root.join("pkg").join("b.k").display().to_string()]
+    );
+}
+
+#[test]
+fn test_load_program_cancelled() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse")
+        .join("main.k");
+    let opts = LoadProgramOptions::default();
+    opts.cancel_token.cancel();
+    let err = load_program(
+        ParseSessionRef::default(),
+        &[testpath.to_str().unwrap()],
+        Some(opts),
+        None,
+    )
+    .unwrap_err();
+    assert!(err.is::<kclvm_utils::cancel::Cancelled>());
+}
+
+#[test]
+fn test_parse_file_with_limits() {
+    let parse = |code: String| {
+        let sess = ParseSessionRef::default();
+        let limits = ParseLimits {
+            max_expr_depth: 32,
+            max_schema_nesting: 8,
+        };
+        let m = parse_file_with_limits(
+            sess.clone(),
+            "test.k",
+            Some(code),
+            ParseMode::ParseComments,
+            &limits,
+        )
+        .unwrap();
+        let messages: Vec<String> = sess
+            .handler
+            .read()
+            .diagnostics
+            .iter()
+            .flat_map(|d| d.messages.iter().map(|m| m.message.clone()))
+            .collect();
+        (m, messages)
+    };
+    // Within the limits.
+    let (m, messages) = parse(format!("a = {}1{}\n", "[".repeat(16), "]".repeat(16)));
+    assert_eq!(m.body.len(), 1);
+    assert!(messages.is_empty(), "{:?}", messages);
+    // Deeply nested expressions.
+    let (m, messages) = parse(format!(
+        "a = {}1{}\nb = 1\n",
+        "[".repeat(1000),
+        "]".repeat(1000)
+    ));
+    assert_eq!(m.body.len(), 2);
+    assert_eq!(
+        messages,
+        vec!["expression is nested too deeply, the maximum expression depth is 32"]
+    );
+    // Deeply nested configs.
+    let (m, messages) = parse(format!(
+        "a = {}1{}\nb = 1\n",
+        "{k: ".repeat(10),
+        "}".repeat(10)
+    ));
+    assert_eq!(m.body.len(), 2);
+    assert_eq!(
+        messages,
+        vec!["schema or config is nested too deeply, the maximum nesting depth is 8"]
+    );
+}
+
+#[test]
+fn test_parse_deep_configs_with_default_limits() {
+    // The deepest configs in the grammar tests.
+    let grammar = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("..")
+        .join("test")
+        .join("grammar")
+        .join("schema");
+    for path in [
+        grammar.join("type").join("dict_5").join("main.k"),
+        grammar
+            .join("stmt_block")
+            .join("stmt_block_21")
+            .join("main.k"),
+    ] {
+        let sess = ParseSessionRef::default();
+        let result = load_program(sess.clone(), &[path.to_str().unwrap()], None, None).unwrap();
+        assert!(
+            result.errors.is_empty(),
+            "{}: {:?}",
+            path.display(),
+            result.errors
+        );
+    }
+    // The deeply nested generated config, and a larger stack is used for the recursive
+    // descent parser in the debug builds.
+    std::thread::Builder::new()
+        .stack_size(64 * 1024 * 1024)
+        .spawn(|| {
+            let code = format!("a = {}1{}\n", "{k: ".repeat(200), "}".repeat(200));
+            let sess = ParseSessionRef::default();
+            let m = parse_file_with_limits(
+                sess.clone(),
+                "deep.k",
+                Some(code),
+                ParseMode::ParseComments,
+                &ParseLimits::default(),
+            )
+            .unwrap();
+            assert_eq!(m.body.len(), 1);
+            assert!(sess.handler.read().diagnostics.is_empty());
+        })
+        .unwrap()
+        .join()
+        .unwrap();
+}
+
+#[test]
+fn test_scan_imports() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("parallel_parse");
+    let main = testpath.join("main.k");
+    let graph = scan_imports(&[main.to_str().unwrap()], None).unwrap();
+    let file = |pkg_path: &str, path: PathBuf| PkgFile::new(path, pkg_path.to_string());
+    let main = file(MAIN_PKG, main);
+    let pkg1_a = file("pkg1", testpath.join("pkg1").join("a.k"));
+    let pkg1_b = file("pkg1", testpath.join("pkg1").join("b.k"));
+    let pkg2_a = file("pkg2", testpath.join("pkg2").join("a.k"));
+    let pkg2_b = file("pkg2", testpath.join("pkg2").join("b.k"));
+
+    let deps = |file: &PkgFile| {
+        let mut deps = graph.dependencies_of(file);
+        deps.sort_by(|a, b| a.get_path().cmp(b.get_path()));
+        deps
+    };
+    assert_eq!(graph.paths().len(), 5);
+    assert_eq!(
+        deps(&main),
+        vec![
+            pkg1_a.clone(),
+            pkg1_b.clone(),
+            pkg2_a.clone(),
+            pkg2_b.clone()
+        ]
+    );
+    assert_eq!(deps(&pkg2_a), vec![pkg1_a, pkg1_b]);
+    assert!(deps(&pkg2_b).is_empty());
+
+    // The import graph is the same as the one of the full compilation.
+    let res = load_program(
+        ParseSessionRef::default(),
+        &[testpath.join("main.k").to_str().unwrap()],
+        None,
+        None,
+    )
+    .unwrap();
+    let mut scanned: Vec<PathBuf> = graph
+        .toposort()
+        .unwrap()
+        .iter()
+        .map(|f| f.get_path().clone())
+        .collect();
+    let mut loaded = res.paths.clone();
+    scanned.sort();
+    loaded.sort();
+    assert_eq!(scanned, loaded);
+}
+
+#[test]
+fn test_get_program_graph() {
+    let testpath = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+        .join("testdata")
+        .join("program_graph");
+    let mut opts = LoadProgramOptions::default();
+    opts.package_maps.insert(
+        "ext".to_string(),
+        testpath.join("external").join("ext").display().to_string(),
+    );
+    let graph =
+        program_graph::get_program_graph(&[testpath.join("main.k").to_str().unwrap()], Some(opts))
+            .unwrap();
+    let edge = |from: &str, to: &str| (from.to_string(), to.to_string());
+
+    assert_eq!(graph.pkg_graph.nodes, vec!["__main__", "a", "b", "ext"]);
+    assert_eq!(
+        graph.pkg_graph.edges,
+        vec![
+            edge("__main__", "a"),
+            edge("__main__", "ext"),
+            edge("a", "b"),
+            edge("b", "a")
+        ]
+    );
+    assert_eq!(graph.pkg_graph.cycles, vec![vec!["a", "b"]]);
+    assert_eq!(graph.file_graph.nodes.len(), 4);
+    assert_eq!(graph.file_graph.edges.len(), 4);
+    assert_eq!(graph.file_graph.cycles.len(), 1);
+    assert_eq!(graph.external_pkgs.keys().collect::<Vec<_>>(), vec!["ext"]);
+    assert_eq!(
+        graph.pkg_graph.to_dot(),
+        r#"digraph {
+    "__main__";
+    "a";
+    "b";
+    "ext";
+    "__main__" -> "a";
+    "__main__" -> "ext";
+    "a" -> "b";
+    "b" -> "a";
+}
+"#
+    );
+}
+
+#[test]
+fn test_parse_expr_with_diagnostics() {
+    let (expr, errors) = parse_expr_with_diagnostics("{a = 1}", "expr.k");
+    let expr = expr.unwrap();
+    assert!(matches!(expr.node, ast::Expr::Config(_)));
+    assert_eq!(expr.filename, "expr.k");
+    assert!(errors.is_empty());
+
+    let (expr, errors) = parse_expr_with_diagnostics("a b", "expr.k");
+    assert!(expr.is_some());
+    assert_eq!(errors.len(), 1);
+    let range = &errors[0].messages[0].range;
+    assert_eq!(range.0.filename, "expr.k");
+    assert_eq!(range.0.line, 1);
+    assert_eq!(range.0.column, Some(2));
+
+    let (expr, errors) = parse_expr_with_diagnostics("", "expr.k");
+    assert!(expr.is_none());
+    assert!(errors.is_empty());
+}
+
+#[test]
+fn test_parse_file_with_recover_mode() {
+    let src = "a = 1\n: :\nb = 2\n";
+    let m = parse_file_with_mode(
+        ParseSessionRef::default(),
+        "test.k",
+        Some(src.to_string()),
+        ParseMode::ParseComments,
+    )
+    .unwrap();
+    assert_eq!(m.body.len(), 2);
+
+    let sess = ParseSessionRef::default();
+    let m = parse_file_with_mode(
+        sess.clone(),
+        "test.k",
+        Some(src.to_string()),
+        ParseMode::Recover,
+    )
+    .unwrap();
+    assert!(sess.handler.read().has_errors());
+    assert_eq!(m.body.len(), 3);
+    assert!(matches!(m.body[0].node, ast::Stmt::Assign(_)));
+    assert!(matches!(m.body[2].node, ast::Stmt::Assign(_)));
+    match &m.body[1].node {
+        ast::Stmt::Expr(expr_stmt) => {
+            assert_eq!(expr_stmt.exprs.len(), 1);
+            assert!(matches!(expr_stmt.exprs[0].node, ast::Expr::Missing(_)));
+        }
+        stmt => panic!("expect a placeholder statement, got {stmt:?}"),
+    }
+    assert_eq!(m.body[1].line, 2);
+    assert_eq!(m.body[1].column, 0);
+    assert_eq!(m.body[1].end_line, 2);
+    assert_eq!(m.body[1].end_column, 3);
+}
+
+#[test]
+fn test_parse_schema_body_with_recover_mode() {
+    let src = "schema A:\n    a: int\n    1: int\n    b: str\n";
+    let sess = ParseSessionRef::default();
+    let m = create_session_globals_then(|| {
+        parse_file_with_mode(
+            sess.clone(),
+            "test.k",
+            Some(src.to_string()),
+            ParseMode::Recover,
+        )
+    })
+    .unwrap();
+    assert!(sess.handler.read().has_errors());
+    let body = match &m.body[0].node {
+        ast::Stmt::Schema(schema_stmt) => &schema_stmt.body,
+        stmt => panic!("expect a schema statement, got {stmt:?}"),
+    };
+    assert_eq!(body.len(), 3);
+    assert!(matches!(&body[0].node, ast::Stmt::SchemaAttr(attr) if attr.name.node == "a"));
+    assert!(matches!(&body[2].node, ast::Stmt::SchemaAttr(attr) if attr.name.node == "b"));
+    match &body[1].node {
+        ast::Stmt::Expr(expr_stmt) => {
+            assert!(matches!(expr_stmt.exprs[0].node, ast::Expr::Missing(_)));
+        }
+        stmt => panic!("expect a placeholder statement, got {stmt:?}"),
+    }
+    assert_eq!((body[1].line, body[1].column), (3, 4));
+}
+
+#[test]
+fn test_parse_file_with_trivia_round_trip() {
+    let files = [
+        "testdata/assert-if-0.k",
+        "testdata/config_expr-04.k",
+        "testdata/hello_win.k",
+        "testdata/if-03.k",
+        "testdata/import-01.k",
+        "testdata/type-01.k",
+    ];
+    for file in files {
+        let code = std::fs::read_to_string(file).unwrap();
+        let (_, trivia) =
+            parse_file_with_trivia(ParseSessionRef::default(), file, Some(code.clone())).unwrap();
+        assert_eq!(trivia.to_source(), code, "file: {file}");
+    }
+
+    let code = "schema Person:\n    # The person name\n    name: str  # required\n\n    age: int = 1 \\\n        + 1\n";
+    let (m, trivia) =
+        parse_file_with_trivia(ParseSessionRef::default(), "main.k", Some(code.to_string()))
+            .unwrap();
+    assert_eq!(trivia.to_source(), code);
+    assert_eq!(trivia.comments().len(), 2);
+    let schema = match &m.body[0].node {
+        ast::Stmt::Schema(schema) => schema,
+        stmt => panic!("expect a schema statement, got {stmt:?}"),
+    };
+    let name = &schema.body[0];
+    let age = &schema.body[1];
+    let comments: Vec<&str> = trivia
+        .node_comments(name)
+        .iter()
+        .map(|c| c.text.as_str())
+        .collect();
+    assert_eq!(comments, vec!["# The person name", "# required"]);
+    assert_eq!(trivia.blank_lines_before(age), 1);
+    assert!(trivia
+        .pieces()
+        .iter()
+        .any(|p| matches!(p, trivia::SourcePiece::Trivia(t) if t.kind == trivia::TriviaKind::LineContinuation)));
+}
+
+#[test]
+fn test_load_program_with_anonymous_sources() {
+    let sess = ParseSessionRef::default();
+    let work_dir = Path::new("./testdata").canonicalize().unwrap();
+    let mut anonymous_sources = IndexMap::new();
+    anonymous_sources.insert("main".to_string(), "a = 1\n".to_string());
+    anonymous_sources.insert(STDIN_SOURCE_NAME.to_string(), "b = (\n".to_string());
+    let opts = LoadProgramOptions {
+        work_dir: work_dir.display().to_string(),
+        anonymous_sources,
+        ..Default::default()
+    };
+    let result = load_program(sess.clone(), &["<main>", STDIN_INPUT], Some(opts), None).unwrap();
+    let main_files = result.program.get_main_files();
+    assert_eq!(main_files, vec!["<main>", "<stdin>"]);
+    // The sha256 hashes of the file sources.
+    assert_eq!(
+        result.file_hashes["<main>"],
+        "cb78bd8a17f7b751fe0d4663366dcbc257204033ef7ddd64b1f2969573b5b2e2"
+    );
+    assert!(result
+        .errors
+        .iter()
+        .all(|e| e.source_hash.as_ref() == Some(&result.file_hashes["<stdin>"])));
+    // The diagnostics of the anonymous sources use the anonymous filenames.
+    assert!(!result.errors.is_empty());
+    assert!(result
+        .errors
+        .iter()
+        .all(|e| e.messages[0].range.0.filename == "<stdin>"));
+    // The code snippets of the anonymous sources are rendered from the source map.
+    let msg = sess
+        .handler
+        .write()
+        .emit_to_string_with_session(&sess.session)
+        .unwrap();
+    assert!(msg.contains("b = ("), "{msg}");
+    // The unknown anonymous source is reported.
+    let err = load_program(
+        ParseSessionRef::default(),
+        &["<unknown>"],
+        Some(LoadProgramOptions {
+            work_dir: work_dir.display().to_string(),
+            ..Default::default()
+        }),
+        None,
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot find the anonymous KCL source <unknown>"));
+}
+
+#[test]
+fn test_parse_single_file_with_external_pkgs() {
+    let testdata = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("testdata");
+    let main = testdata
+        .join("parse_single_file")
+        .join("main.k")
+        .display()
+        .to_string();
+    let result = parse_single_file(&main, None).unwrap();
+    assert!(!result.errors.is_empty());
+    assert!(result.deps.is_empty());
+
+    let mut opts = LoadProgramOptions::default();
+    opts.package_maps.insert(
+        "ext".to_string(),
+        testdata
+            .join("program_graph")
+            .join("external")
+            .join("ext")
+            .display()
+            .to_string(),
+    );
+    let result = parse_single_file_with_opts(&main, None, opts).unwrap();
+    assert!(result.errors.is_empty());
+    assert_eq!(result.deps.len(), 1);
+    assert!(result.deps[0]
+        .get_path()
+        .ends_with(PathBuf::from("ext").join("main.k")));
+}
+
+#[test]
+fn test_parse_session_with_emitter() {
+    use compiler_base_error::errors::ComponentError;
+    use compiler_base_error::{
+        emit_diagnostic_to_uncolored_text, Component, Diagnostic as StyledDiagnostic,
+        DiagnosticStyle, Emitter, StyledBuffer,
+    };
+    use std::sync::Mutex;
+
+    /// An emitter collecting the diagnostic texts.
+    struct CollectEmitter(Arc<Mutex<Vec<String>>>);
+
+    impl Emitter<DiagnosticStyle> for CollectEmitter {
+        fn format_diagnostic(
+            &mut self,
+            diag: &StyledDiagnostic<DiagnosticStyle>,
+        ) -> Result<StyledBuffer<DiagnosticStyle>, ComponentError> {
+            let mut sb = StyledBuffer::<DiagnosticStyle>::new();
+            diag.format(&mut sb, &mut vec![]);
+            Ok(sb)
+        }
+
+        fn emit_diagnostic(&mut self, diag: &StyledDiagnostic<DiagnosticStyle>) -> Result<()> {
+            self.0
+                .lock()
+                .unwrap()
+                .push(emit_diagnostic_to_uncolored_text(diag)?);
+            Ok(())
+        }
+    }
+
+    let diags = Arc::new(Mutex::new(vec![]));
+    let sess = ParseSessionRef::default();
+    create_session_globals_then(|| {
+        parse_file_with_session(sess.clone(), "main.k", Some("a = 1 +".to_string()))
+    })
+    .unwrap();
+    assert!(sess
+        .emit_with_emitter(&mut CollectEmitter(diags.clone()))
+        .unwrap());
+    let diags = diags.lock().unwrap();
+    assert!(!diags.is_empty());
+    assert!(diags[0].starts_with("error["), "{}", diags[0]);
+}
@@ -0,0 +1,326 @@
+//! The trivia map records the source code pieces which are not a part of the AST,
+//! e.g., whitespace, blank lines, line continuations and comments, so that tools
+//! such as the formatter and refactoring tools can round-trip a file without
+//! destroying the user layout.
+//!
+//! Trivia are attached to the non-trivia tokens following the [Roslyn] convention:
+//! the trivia after a token on the same line (including the line break) is the
+//! trailing trivia of the token, and the others are the leading trivia of the next
+//! token. Because AST nodes record the start position of their first token and the
+//! end position of their last token, the trivia of a node can be looked up with
+//! its position.
+//!
+//! [Roslyn]: https://github.com/dotnet/roslyn/wiki/Roslyn-Overview#syntax-trivia
+
+use indexmap::IndexMap;
+use kclvm_ast::ast::Node;
+use kclvm_ast::token::{Token, TokenKind};
+use kclvm_ast::token_stream::TokenStream;
+use kclvm_span::BytePos;
+
+/// The kind of a trivia piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TriviaKind {
+    /// Spaces and tabs.
+    Whitespace,
+    /// A line break `\n` or `\r\n`.
+    Newline,
+    /// A line comment starts with `#`.
+    Comment,
+    /// A backslash line continuation.
+    LineContinuation,
+    /// Source code skipped by the lexer e.g., invalid characters.
+    Skipped,
+}
+
+/// A trivia piece with its text and start position.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Trivia {
+    pub kind: TriviaKind,
+    pub text: String,
+    /// The line number starts from 1.
+    pub line: u64,
+    /// The column number starts from 0.
+    pub column: u64,
+}
+
+/// A source code piece in the lossless representation of a file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SourcePiece {
+    /// The source text of a non-trivia token.
+    Token(String),
+    Trivia(Trivia),
+}
+
+/// TriviaMap records all the trivia of a file keyed by the positions of tokens.
+#[derive(Debug, Clone, Default)]
+pub struct TriviaMap {
+    /// All the source code pieces in the source order.
+    pieces: Vec<SourcePiece>,
+    /// Leading trivia keyed by the start position (line, column) of the token.
+    leading: IndexMap<(u64, u64), Vec<Trivia>>,
+    /// Trailing trivia keyed by the end position (line, column) of the token.
+    trailing: IndexMap<(u64, u64), Vec<Trivia>>,
+    /// The end positions of all the non-trivia tokens in the source order.
+    token_ends: Vec<(u64, u64)>,
+    /// Trivia at the end of the file which is not trailing trivia of any token.
+    end_of_file: Vec<Trivia>,
+}
+
+impl TriviaMap {
+    /// Build the trivia map from the source code and the token stream lexed from it,
+    /// `start_pos` is the byte position of the first char of `src` in the source map.
+    pub fn new(src: &str, stream: &TokenStream, start_pos: BytePos) -> Self {
+        let mut map = TriviaMap::default();
+        let mut cursor = SourceCursor::new(src);
+        let mut prev_end: Option<(u64, u64)> = None;
+        for token in stream.iter().filter(|tok| is_solid_token(tok)) {
+            let lo = (token.span.lo() - start_pos).0 as usize;
+            let hi = (token.span.hi() - start_pos).0 as usize;
+            // Tokens overlapped with the previous ones only occur in the error recovery.
+            if lo < cursor.offset || hi > src.len() {
+                continue;
+            }
+            let trivia = cursor.take_trivia(lo);
+            let start = cursor.position();
+            map.attach_trivia(prev_end, Some(start), trivia);
+            let text = cursor.take_text(hi);
+            map.pieces.push(SourcePiece::Token(text));
+            prev_end = Some(cursor.position());
+            map.token_ends.push(cursor.position());
+        }
+        let trivia = cursor.take_trivia(src.len());
+        map.attach_trivia(prev_end, None, trivia);
+        map
+    }
+
+    /// Split the trivia between two tokens into the trailing trivia of the previous
+    /// token and the leading trivia of the next token.
+    fn attach_trivia(
+        &mut self,
+        prev_end: Option<(u64, u64)>,
+        next_start: Option<(u64, u64)>,
+        trivia: Vec<Trivia>,
+    ) {
+        self.pieces
+            .extend(trivia.iter().cloned().map(SourcePiece::Trivia));
+        let split = match prev_end {
+            Some(_) => trivia
+                .iter()
+                .position(|t| t.kind == TriviaKind::Newline)
+                .map(|i| i + 1)
+                .unwrap_or(trivia.len()),
+            None => 0,
+        };
+        let (trailing, leading) = trivia.split_at(split);
+        if let Some(end) = prev_end {
+            if !trailing.is_empty() {
+                self.trailing.insert(end, trailing.to_vec());
+            }
+        }
+        match next_start {
+            Some(start) => {
+                if !leading.is_empty() {
+                    self.leading.insert(start, leading.to_vec());
+                }
+            }
+            None => self.end_of_file = leading.to_vec(),
+        }
+    }
+
+    /// Get the leading trivia of the token starts at the position.
+    pub fn leading_trivia(&self, line: u64, column: u64) -> &[Trivia] {
+        self.leading
+            .get(&(line, column))
+            .map(|t| t.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the trailing trivia of the last token ends at or before the position.
+    ///
+    /// Note that the end position of some statements includes the line breaks after
+    /// them, thus the nearest token is used instead of the exact position.
+    pub fn trailing_trivia(&self, end_line: u64, end_column: u64) -> &[Trivia] {
+        let index = self
+            .token_ends
+            .partition_point(|end| *end <= (end_line, end_column));
+        if index == 0 {
+            return &[];
+        }
+        self.trailing
+            .get(&self.token_ends[index - 1])
+            .map(|t| t.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the leading trivia of the AST node.
+    #[inline]
+    pub fn node_leading_trivia<T>(&self, node: &Node<T>) -> &[Trivia] {
+        self.leading_trivia(node.line, node.column)
+    }
+
+    /// Get the trailing trivia of the AST node.
+    #[inline]
+    pub fn node_trailing_trivia<T>(&self, node: &Node<T>) -> &[Trivia] {
+        self.trailing_trivia(node.end_line, node.end_column)
+    }
+
+    /// Get the comments attached to the AST node, including the comments on the lines
+    /// above the node and the comment at the end of the last line of the node.
+    pub fn node_comments<T>(&self, node: &Node<T>) -> Vec<&Trivia> {
+        self.node_leading_trivia(node)
+            .iter()
+            .chain(self.node_trailing_trivia(node).iter())
+            .filter(|t| t.kind == TriviaKind::Comment)
+            .collect()
+    }
+
+    /// Get the number of blank lines before the AST node.
+    pub fn blank_lines_before<T>(&self, node: &Node<T>) -> usize {
+        let mut blank_lines = 0;
+        let mut is_blank = true;
+        for trivia in self.node_leading_trivia(node) {
+            match trivia.kind {
+                TriviaKind::Newline => {
+                    if is_blank {
+                        blank_lines += 1;
+                    }
+                    is_blank = true;
+                }
+                TriviaKind::Whitespace => {}
+                _ => is_blank = false,
+            }
+        }
+        blank_lines
+    }
+
+    /// Get the trivia at the end of the file.
+    #[inline]
+    pub fn end_of_file_trivia(&self) -> &[Trivia] {
+        &self.end_of_file
+    }
+
+    /// Get all the source code pieces in the source order.
+    #[inline]
+    pub fn pieces(&self) -> &[SourcePiece] {
+        &self.pieces
+    }
+
+    /// Get all the comments in the file.
+    pub fn comments(&self) -> Vec<&Trivia> {
+        self.pieces
+            .iter()
+            .filter_map(|p| match p {
+                SourcePiece::Trivia(t) if t.kind == TriviaKind::Comment => Some(t),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Reconstruct the source code, which is the same as the input source code.
+    pub fn to_source(&self) -> String {
+        self.pieces
+            .iter()
+            .map(|p| match p {
+                SourcePiece::Token(text) => text.as_str(),
+                SourcePiece::Trivia(t) => t.text.as_str(),
+            })
+            .collect()
+    }
+}
+
+/// Whether the token has source text which is not trivia.
+fn is_solid_token(token: &Token) -> bool {
+    !matches!(
+        token.kind,
+        TokenKind::Newline
+            | TokenKind::Indent(_)
+            | TokenKind::Dedent(_)
+            | TokenKind::DocComment(_)
+            | TokenKind::Dummy
+            | TokenKind::Eof
+    )
+}
+
+/// A cursor on the source code tracks the byte offset and the (line, column) position.
+struct SourceCursor<'a> {
+    src: &'a str,
+    offset: usize,
+    line: u64,
+    column: u64,
+}
+
+impl<'a> SourceCursor<'a> {
+    fn new(src: &'a str) -> Self {
+        Self {
+            src,
+            offset: 0,
+            line: 1,
+            column: 0,
+        }
+    }
+
+    #[inline]
+    fn position(&self) -> (u64, u64) {
+        (self.line, self.column)
+    }
+
+    /// Take the source text until the byte offset `end`.
+    fn take_text(&mut self, end: usize) -> String {
+        let text = &self.src[self.offset..end];
+        for c in text.chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.offset = end;
+        text.to_string()
+    }
+
+    /// Split the source text until the byte offset `end` into trivia pieces.
+    fn take_trivia(&mut self, end: usize) -> Vec<Trivia> {
+        let mut trivia = vec![];
+        while self.offset < end {
+            let rest = &self.src[self.offset..end];
+            let (kind, len) = if rest.starts_with("\r\n") {
+                (TriviaKind::Newline, 2)
+            } else if rest.starts_with('\n') {
+                (TriviaKind::Newline, 1)
+            } else if rest.starts_with("\\\r\n") {
+                (TriviaKind::LineContinuation, 3)
+            } else if rest.starts_with("\\\n") {
+                (TriviaKind::LineContinuation, 2)
+            } else if rest.starts_with('#') {
+                (
+                    TriviaKind::Comment,
+                    rest.find(['\r', '\n']).unwrap_or(rest.len()),
+                )
+            } else if rest.starts_with([' ', '\t', '\x0c']) {
+                (
+                    TriviaKind::Whitespace,
+                    rest.find(|c| !matches!(c, ' ' | '\t' | '\x0c'))
+                        .unwrap_or(rest.len()),
+                )
+            } else {
+                (
+                    TriviaKind::Skipped,
+                    rest.find([' ', '\t', '\x0c', '\r', '\n', '#', '\\'])
+                        .filter(|i| *i > 0)
+                        .unwrap_or_else(|| rest.chars().next().map(|c| c.len_utf8()).unwrap_or(1)),
+                )
+            };
+            let (line, column) = self.position();
+            let text = self.take_text(self.offset + len);
+            trivia.push(Trivia {
+                kind,
+                text,
+                line,
+                column,
+            });
+        }
+        trivia
+    }
+}
@@ -0,0 +1,4 @@
+[package]
+name = "program_graph"
+edition = "v0.9.0"
+version = "0.0.1"
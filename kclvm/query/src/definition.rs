@@ -0,0 +1,315 @@
+//! Find the definition of a value path e.g., `app.containers[0].image` in the main
+//! package, which is the position and the inferred type of the expression defining the
+//! planned value, select the expression source and span of the path without executing
+//! the program, and get the planned value of the path.
+use anyhow::Result;
+use kclvm_ast::ast::{self, Program};
+use kclvm_ast::MAIN_PKG;
+use kclvm_ast_pretty::{print_ast_node, print_schema_expr, ASTNode};
+use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+use kclvm_sema::resolver::resolve_program;
+use kclvm_sema::resolver::scope::{NodeKey, ProgramScope};
+use kclvm_sema::ty::{
+    DictType, Type, TypeKind, BOOL_TYPE_STR, FLOAT_TYPE_STR, INT_TYPE_STR, STR_TYPE_STR,
+};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::sync::Arc;
+
+use crate::path::{parse_value_path, PathSegment};
+
+/// ValueDefinition denotes the definition of a value path.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ValueDefinition {
+    /// The inferred type of the value expression e.g., `str` and `App`, and the literal
+    /// types are widened e.g., `str("nginx")` is `str`.
+    pub ty: String,
+    /// The position of the top-level statement, the config entry or the list item
+    /// defining the value.
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+}
+
+/// Returns the definition of the value path in the main package, which is the last
+/// top-level statement or config entry defining it. It returns None when the value is
+/// not defined by the literal config and list expressions e.g., it is returned by a
+/// function call.
+pub fn find_value_definition(
+    program: &Program,
+    scope: &ProgramScope,
+    path: &[PathSegment],
+) -> Option<ValueDefinition> {
+    find_value_expr(program, path, |expr, pos| with_type(scope, expr.id(), pos))
+}
+
+/// SelectedExpr denotes the expression defining the value of a path, which is selected
+/// from the AST without executing the program.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SelectedExpr {
+    /// The expression source printed from the AST e.g., `{cpu = "1"}`.
+    pub source: String,
+    /// The inferred type of the expression like [ValueDefinition::ty].
+    pub ty: String,
+    /// The span of the expression, the lines are 1-based and the columns are 0-based.
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+    pub end_line: u64,
+    pub end_column: u64,
+}
+
+/// Returns the expression defining the value path in the main package, which is the
+/// value of the last top-level statement or config entry defining it like
+/// [find_value_definition].
+pub fn select_value_expr(
+    program: &Program,
+    scope: &ProgramScope,
+    path: &[PathSegment],
+) -> Option<SelectedExpr> {
+    find_value_expr(program, path, |expr, _| {
+        let (filename, line, column, end_line, end_column) = expr.pos();
+        SelectedExpr {
+            source: expr.source(),
+            ty: node_ty_str(scope, expr.id()),
+            filename,
+            line,
+            column,
+            end_line,
+            end_column,
+        }
+    })
+}
+
+/// Load and resolve the program of the files, and select the expression defining the
+/// value of the selector e.g., `appConfig.resources.limits` without executing the
+/// program.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_query::definition::select_expr;
+///
+/// let expr = select_expr(&["main.k"], "appConfig.resources.limits", None).unwrap();
+/// if let Some(expr) = expr {
+///     println!("{}:{}:{} {}", expr.filename, expr.line, expr.column, expr.source);
+/// }
+/// ```
+pub fn select_expr(
+    files: &[&str],
+    selector: &str,
+    opts: Option<LoadProgramOptions>,
+) -> Result<Option<SelectedExpr>> {
+    let path = parse_value_path(selector)?;
+    let mut program = load_program(Arc::new(ParseSession::default()), files, opts, None)?.program;
+    let scope = resolve_program(&mut program);
+    Ok(select_value_expr(&program, &scope, &path))
+}
+
+/// The expression defining a value, which is the value of an assignment, a config entry
+/// or a list item, or the schema expression of a unification statement.
+enum ValueExpr<'a> {
+    Expr(&'a ast::NodeRef<ast::Expr>),
+    Schema(&'a ast::NodeRef<ast::SchemaExpr>),
+}
+
+impl ValueExpr<'_> {
+    fn id(&self) -> &ast::AstIndex {
+        match self {
+            ValueExpr::Expr(expr) => &expr.id,
+            ValueExpr::Schema(schema_expr) => &schema_expr.id,
+        }
+    }
+
+    fn pos(&self) -> ast::PosTuple {
+        match self {
+            ValueExpr::Expr(expr) => expr.pos(),
+            ValueExpr::Schema(schema_expr) => schema_expr.pos(),
+        }
+    }
+
+    fn source(&self) -> String {
+        match self {
+            ValueExpr::Expr(expr) => print_ast_node(ASTNode::Expr(expr)),
+            ValueExpr::Schema(schema_expr) => print_schema_expr(&schema_expr.node),
+        }
+    }
+}
+
+/// Find the expression defining the value path in the main package, and `f` maps the
+/// expression and the definition position to the result.
+fn find_value_expr<T>(
+    program: &Program,
+    path: &[PathSegment],
+    mut f: impl FnMut(ValueExpr, ValueDefinition) -> T,
+) -> Option<T> {
+    let (name, rest) = match path.split_first()? {
+        (PathSegment::Key(name), rest) => (name, rest),
+        _ => return None,
+    };
+    let mut definition = None;
+    for filename in program.pkgs.get(MAIN_PKG)? {
+        let module = match program.get_module(filename) {
+            Ok(Some(module)) => module,
+            _ => continue,
+        };
+        for stmt in &module.body {
+            let found = match &stmt.node {
+                ast::Stmt::Assign(assign_stmt)
+                    if assign_stmt
+                        .targets
+                        .iter()
+                        .any(|t| t.node.paths.is_empty() && t.node.get_name() == name.as_str()) =>
+                {
+                    find_in_expr(&assign_stmt.value, position(stmt), rest)
+                }
+                ast::Stmt::Unification(unification_stmt)
+                    if unification_stmt.target.node.get_name() == *name =>
+                {
+                    let schema_expr = &unification_stmt.value;
+                    if rest.is_empty() {
+                        Some((ValueExpr::Schema(schema_expr), position(stmt)))
+                    } else {
+                        find_in_expr(&schema_expr.node.config, position(stmt), rest)
+                    }
+                }
+                _ => None,
+            };
+            if let Some((expr, pos)) = found {
+                definition = Some(f(expr, pos));
+            }
+        }
+    }
+    definition
+}
+
+/// Returns the value of the path in the planned JSON value.
+pub fn get_json_value_by_path<'a>(value: &'a Value, path: &[PathSegment]) -> Option<&'a Value> {
+    path.iter().try_fold(value, |value, segment| match segment {
+        PathSegment::Key(key) => value.as_object()?.get(key),
+        PathSegment::Index(index) => value.as_array()?.get(*index),
+    })
+}
+
+/// Returns the KCL type name of the planned JSON value.
+pub fn json_value_type(value: &Value) -> &'static str {
+    match value {
+        Value::Null => "NoneType",
+        Value::Bool(_) => "bool",
+        Value::Number(number) if number.is_f64() => "float",
+        Value::Number(_) => "int",
+        Value::String(_) => "str",
+        Value::Array(_) => "list",
+        Value::Object(_) => "dict",
+    }
+}
+
+/// Find the expression of the path under the expression, and `pos` is the definition of
+/// the expression itself.
+fn find_in_expr<'a>(
+    expr: &'a ast::NodeRef<ast::Expr>,
+    pos: ValueDefinition,
+    path: &[PathSegment],
+) -> Option<(ValueExpr<'a>, ValueDefinition)> {
+    let (segment, rest) = match path.split_first() {
+        Some(first) => first,
+        None => return Some((ValueExpr::Expr(expr), pos)),
+    };
+    match (&expr.node, segment) {
+        (ast::Expr::Paren(paren_expr), _) => find_in_expr(&paren_expr.expr, pos, path),
+        (ast::Expr::Schema(schema_expr), _) => find_in_expr(&schema_expr.config, pos, path),
+        (ast::Expr::Config(config_expr), PathSegment::Key(key)) => {
+            // The latter entries override or are unioned with the former ones.
+            config_expr.items.iter().rev().find_map(|entry| {
+                match entry
+                    .node
+                    .key
+                    .as_ref()
+                    .and_then(|key| config_key(&key.node))
+                {
+                    Some(entry_key) if entry_key == *key => {
+                        find_in_expr(&entry.node.value, position(entry), rest)
+                    }
+                    _ => None,
+                }
+            })
+        }
+        (ast::Expr::List(list_expr), PathSegment::Index(index)) => {
+            // The items after a spread or an if item are not at the literal indices.
+            let item = list_expr
+                .elts
+                .iter()
+                .take_while(|item| {
+                    !matches!(item.node, ast::Expr::Starred(_) | ast::Expr::ListIfItem(_))
+                })
+                .nth(*index)?;
+            find_in_expr(item, position(item), rest)
+        }
+        _ => None,
+    }
+}
+
+/// Returns the key name of the config entry.
+fn config_key(key: &ast::Expr) -> Option<String> {
+    match key {
+        ast::Expr::Identifier(identifier) => Some(identifier.get_name()),
+        ast::Expr::StringLit(string_lit) => Some(string_lit.value.clone()),
+        _ => None,
+    }
+}
+
+/// Returns the definition with the position of the node.
+fn position<T>(node: &ast::Node<T>) -> ValueDefinition {
+    ValueDefinition {
+        ty: String::new(),
+        filename: node.filename.clone(),
+        line: node.line,
+        column: node.column,
+    }
+}
+
+/// Set the resolved type of the expression to the definition.
+fn with_type(scope: &ProgramScope, id: &ast::AstIndex, pos: ValueDefinition) -> ValueDefinition {
+    ValueDefinition {
+        ty: node_ty_str(scope, id),
+        ..pos
+    }
+}
+
+/// Returns the resolved type string of the expression in the main package.
+fn node_ty_str(scope: &ProgramScope, id: &ast::AstIndex) -> String {
+    let key = NodeKey {
+        pkgpath: MAIN_PKG.to_string(),
+        id: id.clone(),
+    };
+    scope
+        .node_ty_map
+        .borrow()
+        .get(&key)
+        .map(|ty| base_ty_str(ty))
+        .unwrap_or_default()
+}
+
+/// Returns the type string with the literal types widened to their base types.
+fn base_ty_str(ty: &Type) -> String {
+    match &ty.kind {
+        TypeKind::BoolLit(_) => BOOL_TYPE_STR.to_string(),
+        TypeKind::IntLit(_) => INT_TYPE_STR.to_string(),
+        TypeKind::FloatLit(_) => FLOAT_TYPE_STR.to_string(),
+        TypeKind::StrLit(_) => STR_TYPE_STR.to_string(),
+        TypeKind::List(item_ty) => format!("[{}]", base_ty_str(item_ty)),
+        TypeKind::Dict(DictType { key_ty, val_ty, .. }) => {
+            format!("{{{}:{}}}", base_ty_str(key_ty), base_ty_str(val_ty))
+        }
+        TypeKind::Union(types) => {
+            let mut ty_strs: Vec<String> = vec![];
+            for ty_str in types.iter().map(|ty| base_ty_str(ty)) {
+                if !ty_strs.contains(&ty_str) {
+                    ty_strs.push(ty_str);
+                }
+            }
+            ty_strs.join(" | ")
+        }
+        _ => ty.ty_str(),
+    }
+}
@@ -3,6 +3,7 @@
 //! function to modify the file. The main principle is to parse the AST according to the
 //! input file name, and according to the ast::OverrideSpec transforms the nodes in the
 //! AST, recursively modifying or deleting the values of the nodes in the AST.
+pub mod definition;
 pub mod node;
 pub mod r#override;
 pub mod path;
@@ -34,6 +35,9 @@ pub use r#override::{apply_override_on_module, apply_overrides};
 ///     List of specs that need to be overridden.
 ///     Each spec string satisfies the form: <pkgpath>:<field_path>=<filed_value> or <pkgpath>:<field_path>-
 ///     When the pkgpath is '__main__', `<pkgpath>:` can be omitted.
+///     The field path can contain the list indices e.g., `app.containers[0].image` and the
+///     wildcards `*` matching all the variables, config entries or list items e.g.,
+///     `*.metadata.labels.team`.
 ///
 /// `import_paths`: &\[[String]\]
 ///     List of import paths that are need to be added.
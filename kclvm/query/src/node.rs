@@ -174,6 +174,14 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
                     self.walk_type(&mut ty.node)
                 });
             }
+            ast::Type::Generic(generic_ty) => {
+                self.walk_identifier(&mut generic_ty.name);
+                generic_ty.args.iter_mut().for_each(|ty| {
+                    ty.line += self.line_offset as u64;
+                    ty.end_line += self.line_offset as u64;
+                    self.walk_type(&mut ty.node)
+                });
+            }
             _ => {}
         }
     }
@@ -197,6 +205,11 @@ impl<'ctx> MutSelfMutWalker<'ctx> for AstNodeMover {
             None => (),
         }
 
+        for type_param in schema_stmt.type_params.iter_mut() {
+            type_param.line += self.line_offset as u64;
+            type_param.end_line += self.line_offset as u64;
+        }
+
         for arg in schema_stmt.args.iter_mut() {
             arg.line += self.line_offset as u64;
             arg.end_line += self.line_offset as u64;
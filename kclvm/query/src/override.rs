@@ -13,7 +13,8 @@ use kclvm_ast_pretty::print_ast_module;
 use kclvm_parser::parse_expr;
 use kclvm_sema::pre_process::{fix_config_expr_nest_attr, transform_multi_assign};
 
-use crate::{node::AstNodeMover, path::parse_attribute_path};
+use crate::node::AstNodeMover;
+use crate::path::{parse_attribute_path, parse_value_path, PathSegment};
 
 use super::util::invalid_spec_error;
 
@@ -107,7 +108,20 @@ pub fn apply_override_on_module(
 ) -> Result<bool> {
     // Apply import paths on AST module.
     apply_import_paths_on_module(m, import_paths)?;
+    let spec = o;
     let o = parse_override_spec(o)?;
+    // The paths with the list indices or the wildcards e.g., `a.containers[0].image` and
+    // `*.metadata.labels` are matched on the config and list expressions directly.
+    if let Ok(segments) = parse_value_path(&o.field_path) {
+        if segments
+            .iter()
+            .any(|s| matches!(s, PathSegment::Index(_)) || is_wildcard(s))
+        {
+            fix_config_expr_nest_attr(m);
+            transform_multi_assign(m);
+            return apply_path_override_on_module(m, spec, &segments, &o);
+        }
+    }
     let ss = parse_attribute_path(&o.field_path)?;
     let default = String::default();
     let target_id = ss.get(0).unwrap_or(&default);
@@ -945,3 +959,213 @@ fn replace_config_with_path_parts(
     }
     return changed;
 }
+
+/// Returns whether the path segment is the wildcard `*` matching all the config entries
+/// or the list items.
+#[inline]
+fn is_wildcard(segment: &PathSegment) -> bool {
+    matches!(segment, PathSegment::Key(key) if key == "*")
+}
+
+/// Apply the override spec whose path contains the list indices or the wildcards on the
+/// top-level variables of the module, and returns whether the module is changed.
+fn apply_path_override_on_module(
+    m: &mut ast::Module,
+    spec: &str,
+    segments: &[PathSegment],
+    o: &ast::OverrideSpec,
+) -> Result<bool> {
+    let (target, rest) = match segments.split_first() {
+        Some((target @ PathSegment::Key(_), rest)) if !rest.is_empty() => (target, rest),
+        _ => return Err(invalid_spec_error(spec)),
+    };
+    let value = build_expr_from_string(&o.field_value);
+    if o.action == ast::OverrideAction::CreateOrUpdate && value.is_none() {
+        return Err(invalid_spec_error(spec));
+    }
+    let matches_target =
+        |name: &str| is_wildcard(target) || *target == PathSegment::Key(name.to_string());
+    let mut changed = false;
+    for stmt in m.body.iter_mut() {
+        match &mut stmt.node {
+            ast::Stmt::Assign(assign_stmt)
+                if assign_stmt.targets.len() == 1
+                    && matches_target(&get_target_path(&assign_stmt.targets[0].node)) =>
+            {
+                changed |= override_expr_with_path(&mut assign_stmt.value, rest, o, &value);
+            }
+            ast::Stmt::Unification(unification_stmt)
+                if unification_stmt
+                    .target
+                    .node
+                    .names
+                    .first()
+                    .map_or(false, |name| matches_target(&name.node)) =>
+            {
+                changed |= override_expr_with_path(
+                    &mut unification_stmt.value.node.config,
+                    rest,
+                    o,
+                    &value,
+                );
+            }
+            _ => {}
+        }
+    }
+    Ok(changed)
+}
+
+/// Override the value of the path under the expression, and the segments are not empty.
+fn override_expr_with_path(
+    expr: &mut ast::NodeRef<ast::Expr>,
+    segments: &[PathSegment],
+    o: &ast::OverrideSpec,
+    value: &Option<ast::NodeRef<ast::Expr>>,
+) -> bool {
+    let (segment, rest) = match segments.split_first() {
+        Some(first) => first,
+        None => return false,
+    };
+    match &mut expr.node {
+        ast::Expr::Paren(paren_expr) => {
+            override_expr_with_path(&mut paren_expr.expr, segments, o, value)
+        }
+        ast::Expr::Schema(schema_expr) => {
+            override_expr_with_path(&mut schema_expr.config, segments, o, value)
+        }
+        ast::Expr::Config(config_expr) => {
+            // The remaining attribute paths are replaced or created in the same way as the
+            // paths without the list indices and the wildcards.
+            if let Some(parts) = segments
+                .iter()
+                .map(|s| match s {
+                    PathSegment::Key(key) if !is_wildcard(s) => Some(key.as_str()),
+                    _ => None,
+                })
+                .collect::<Option<Vec<&str>>>()
+            {
+                return replace_config_with_path_parts(
+                    config_expr,
+                    &parts,
+                    &o.action,
+                    &o.operation,
+                    value,
+                );
+            }
+            let mut changed = false;
+            match segment {
+                PathSegment::Key(key) => {
+                    let wildcard = is_wildcard(segment);
+                    if rest.is_empty() && o.action == ast::OverrideAction::Delete {
+                        let len = config_expr.items.len();
+                        config_expr.items.retain(|item| {
+                            item.node.key.is_none()
+                                || (!wildcard && get_key_path(&item.node.key) != *key)
+                        });
+                        return config_expr.items.len() != len;
+                    }
+                    for item in config_expr.items.iter_mut() {
+                        if item.node.key.is_some()
+                            && (wildcard || get_key_path(&item.node.key) == *key)
+                        {
+                            changed |= if rest.is_empty() {
+                                override_expr_value(&mut item.node.value, o, value)
+                            } else {
+                                override_expr_with_path(&mut item.node.value, rest, o, value)
+                            };
+                        }
+                    }
+                }
+                // The list index can't be applied on the config expression.
+                PathSegment::Index(_) => {}
+            }
+            changed
+        }
+        ast::Expr::List(list_expr) => {
+            // The items after a spread or an if item are not at the literal indices.
+            let len = list_expr
+                .elts
+                .iter()
+                .take_while(|item| {
+                    !matches!(item.node, ast::Expr::Starred(_) | ast::Expr::ListIfItem(_))
+                })
+                .count();
+            let indices: Vec<usize> = match segment {
+                PathSegment::Index(index) if *index < len => vec![*index],
+                PathSegment::Key(_) if is_wildcard(segment) => (0..len).collect(),
+                _ => return false,
+            };
+            if rest.is_empty() && o.action == ast::OverrideAction::Delete {
+                for index in indices.iter().rev() {
+                    list_expr.elts.remove(*index);
+                }
+                return !indices.is_empty();
+            }
+            let mut changed = false;
+            for index in indices {
+                let item = &mut list_expr.elts[index];
+                changed |= if rest.is_empty() {
+                    override_expr_value(item, o, value)
+                } else {
+                    override_expr_with_path(item, rest, o, value)
+                };
+            }
+            changed
+        }
+        _ => false,
+    }
+}
+
+/// Override the expression itself with the value according to the operation.
+fn override_expr_value(
+    expr: &mut ast::NodeRef<ast::Expr>,
+    o: &ast::OverrideSpec,
+    value: &Option<ast::NodeRef<ast::Expr>>,
+) -> bool {
+    let mut value = match value {
+        Some(value) => value.clone(),
+        None => return false,
+    };
+    // Use position information that needs to override the expression.
+    value.set_pos(expr.pos());
+    match &o.operation {
+        ast::ConfigEntryOperation::Union => {
+            let merged_config_expr = match &value.node {
+                ast::Expr::Config(merged_config_expr) => Some(merged_config_expr),
+                ast::Expr::Schema(merged_schema_expr) => {
+                    match (&merged_schema_expr.config.node, &expr.node) {
+                        (ast::Expr::Config(merged_config_expr), ast::Expr::Schema(schema_expr))
+                            if schema_expr.name.node.get_name()
+                                == merged_schema_expr.name.node.get_name() =>
+                        {
+                            Some(merged_config_expr)
+                        }
+                        _ => None,
+                    }
+                }
+                _ => None,
+            };
+            if let Some(merged_config_expr) = merged_config_expr {
+                return match try_get_config_expr_mut(&mut expr.node) {
+                    Some(config_expr) => {
+                        merge_config_expr(config_expr, merged_config_expr, &o.action)
+                    }
+                    None => false,
+                };
+            }
+        }
+        ast::ConfigEntryOperation::Insert => {
+            return match (&value.node, &mut expr.node) {
+                (ast::Expr::List(insert_list_expr), ast::Expr::List(list_expr)) => {
+                    list_expr.elts.extend(insert_list_expr.elts.iter().cloned());
+                    true
+                }
+                _ => false,
+            };
+        }
+        ast::ConfigEntryOperation::Override => {}
+    }
+    // Override the node value.
+    *expr = value;
+    true
+}
@@ -58,3 +58,61 @@ pub fn parse_attribute_path(path: &str) -> Result<Vec<String>> {
 
     Ok(parts)
 }
+
+/// PathSegment denotes a segment of the value path.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PathSegment {
+    /// The attribute or the dict key e.g., `a` in `a.b` and `k` in `a["k"]`.
+    Key(String),
+    /// The list index e.g., `0` in `a[0]`.
+    Index(usize),
+}
+
+/// Parse the value path with the list indices e.g., `app.containers[0].image` and
+/// `data["a.b"][1]` into the path segments.
+pub fn parse_value_path(path: &str) -> Result<Vec<PathSegment>> {
+    let mut segments = vec![];
+    let mut current = String::new();
+    let mut chars = path.chars();
+    while let Some(ch) = chars.next() {
+        match ch {
+            '.' | '[' => {
+                if !current.is_empty() {
+                    segments.push(PathSegment::Key(std::mem::take(&mut current)));
+                }
+                if ch == '.' {
+                    continue;
+                }
+                let mut content = String::new();
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(ch) => content.push(ch),
+                        None => return Err(anyhow::anyhow!("Expected closing bracket")),
+                    }
+                }
+                let quoted = content.len() >= 2
+                    && (content.starts_with('"') && content.ends_with('"')
+                        || content.starts_with('\'') && content.ends_with('\''));
+                if quoted {
+                    segments.push(PathSegment::Key(content[1..content.len() - 1].to_string()));
+                } else {
+                    let index = content
+                        .trim()
+                        .parse::<usize>()
+                        .map_err(|_| anyhow::anyhow!("Invalid list index '{}'", content))?;
+                    segments.push(PathSegment::Index(index));
+                }
+            }
+            ']' => return Err(anyhow::anyhow!("Unmatched closing bracket")),
+            _ => current.push(ch),
+        }
+    }
+    if !current.is_empty() {
+        segments.push(PathSegment::Key(current));
+    }
+    if segments.is_empty() {
+        return Err(anyhow::anyhow!("Empty value path"));
+    }
+    Ok(segments)
+}
@@ -2,10 +2,16 @@ use std::{fs, path::PathBuf};
 
 use super::{r#override::apply_override_on_module, *};
 use crate::{
-    path::parse_attribute_path, r#override::parse_override_spec, selector::list_variables,
+    definition::{
+        find_value_definition, get_json_value_by_path, select_expr, SelectedExpr, ValueDefinition,
+    },
+    path::{parse_attribute_path, parse_value_path, PathSegment},
+    r#override::parse_override_spec,
+    selector::list_variables,
 };
 use kclvm_error::{DiagnosticId, ErrorKind, Level};
-use kclvm_parser::parse_file_force_errors;
+use kclvm_parser::{load_program, parse_file_force_errors, ParseSession};
+use kclvm_sema::resolver::resolve_program;
 use kclvm_utils::path::PathPrefix;
 use pretty_assertions::assert_eq;
 use selector::ListOptions;
@@ -236,6 +242,57 @@ var4: AppConfiguration {
     );
 }
 
+/// Test override with the list indices and the wildcards.
+#[test]
+fn test_override_with_list_index_and_wildcard() {
+    let code = r#"
+frontend = {
+    containers = [
+        {name = "web", image = "web:v1"}
+        {name = "sidecar", image = "sidecar:v1"}
+    ]
+    metadata: {labels: {team: "a"}}
+}
+backend = {
+    containers = [{name = "api", image = "api:v1"}]
+    metadata: {labels: {team: "b", tier: "api"}}
+}
+version = 1
+"#;
+    let mut module = parse_file_force_errors("main.k", Some(code.to_string())).unwrap();
+    let specs = [
+        r#"frontend.containers[0].image="web:v2""#,
+        "frontend.containers[1]-",
+        r#"backend.containers+=[{name = "log", image = "log:v1"}]"#,
+        r#"backend.containers.*.name="app""#,
+        r#"*.metadata.labels.team="platform""#,
+        "backend.metadata.labels.tier-",
+    ];
+    for spec in specs {
+        assert!(
+            apply_override_on_module(&mut module, spec, &[]).unwrap(),
+            "{spec}"
+        );
+    }
+    // The index out of range is not overridden.
+    assert!(
+        !apply_override_on_module(&mut module, "frontend.containers[5].image=\"x\"", &[]).unwrap()
+    );
+    // The wildcard can't be used to delete the top-level variables.
+    assert!(apply_override_on_module(&mut module, "*-", &[]).is_err());
+
+    let code = print_ast_module(&module);
+    assert!(code.contains(r#"image = "web:v2""#), "{code}");
+    assert!(!code.contains("sidecar"), "{code}");
+    assert!(code.contains(r#"image = "log:v1""#), "{code}");
+    assert!(!code.contains(r#"name = "api""#), "{code}");
+    assert!(!code.contains(r#"name = "log""#), "{code}");
+    assert_eq!(code.matches(r#"name = "app""#).count(), 2, "{code}");
+    assert_eq!(code.matches(r#"team: "platform""#).count(), 2, "{code}");
+    assert!(!code.contains("tier"), "{code}");
+    assert!(code.contains("version = 1"), "{code}");
+}
+
 /// Test override spec parser.
 #[test]
 fn test_parse_override_spec_invalid() {
@@ -284,6 +341,115 @@ fn test_parse_property_path() {
     assert!(parse_attribute_path(r#"a.[b.c]-d.e"#).is_err(),);
 }
 
+#[test]
+fn test_parse_value_path() {
+    assert_eq!(
+        parse_value_path("app.containers[0].image").unwrap(),
+        vec![
+            PathSegment::Key("app".to_string()),
+            PathSegment::Key("containers".to_string()),
+            PathSegment::Index(0),
+            PathSegment::Key("image".to_string()),
+        ]
+    );
+    assert_eq!(
+        parse_value_path(r#"data["a.b"]['c'][1]"#).unwrap(),
+        vec![
+            PathSegment::Key("data".to_string()),
+            PathSegment::Key("a.b".to_string()),
+            PathSegment::Key("c".to_string()),
+            PathSegment::Index(1),
+        ]
+    );
+    assert!(parse_value_path("").is_err());
+    assert!(parse_value_path("a[0").is_err());
+    assert!(parse_value_path("a[b]").is_err());
+    assert!(parse_value_path("a]").is_err());
+}
+
+#[test]
+fn test_find_value_definition() {
+    let file = get_test_dir("value_definition/main.k".to_string())
+        .display()
+        .to_string();
+    let mut program = load_program(
+        std::sync::Arc::new(ParseSession::default()),
+        &[&file],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program(&mut program);
+    let cases = [
+        ("app", "App", 9, 0),
+        ("app.name", "str", 10, 4),
+        ("app.containers", "[Container]", 11, 4),
+        ("app.containers[0]", "Container", 12, 8),
+        ("app.containers[0].image", "str", 12, 19),
+        (r#"config["a.b"]"#, "int", 16, 4),
+        ("config.data[1].key", "bool", 17, 18),
+    ];
+    for (path, ty, line, column) in cases {
+        let path = parse_value_path(path).unwrap();
+        assert_eq!(
+            find_value_definition(&program, &scope, &path),
+            Some(ValueDefinition {
+                ty: ty.to_string(),
+                filename: file.clone(),
+                line,
+                column,
+            })
+        );
+    }
+    for path in ["app.containers[1]", "app.labels", "config.data.key", "a"] {
+        let path = parse_value_path(path).unwrap();
+        assert_eq!(find_value_definition(&program, &scope, &path), None);
+    }
+}
+
+#[test]
+fn test_select_expr() {
+    let file = get_test_dir("value_definition/main.k".to_string())
+        .display()
+        .to_string();
+    let cases = [
+        ("app.name", r#""app""#, "str", (10, 11, 10, 16)),
+        ("config.data[1].key", "True", "bool", (17, 24, 17, 28)),
+    ];
+    for (path, source, ty, (line, column, end_line, end_column)) in cases {
+        assert_eq!(
+            select_expr(&[&file], path, None).unwrap(),
+            Some(SelectedExpr {
+                source: source.to_string(),
+                ty: ty.to_string(),
+                filename: file.clone(),
+                line,
+                column,
+                end_line,
+                end_column,
+            })
+        );
+    }
+    let expr = select_expr(&[&file], "app", None).unwrap().unwrap();
+    assert!(expr.source.starts_with("App {"));
+    assert_eq!(expr.ty, "App");
+    assert_eq!(select_expr(&[&file], "app.labels", None).unwrap(), None);
+    assert!(select_expr(&[&file], "app[", None).is_err());
+}
+
+#[test]
+fn test_get_json_value_by_path() {
+    let value = serde_json::json!({"app": {"containers": [{"image": "nginx"}]}});
+    let path = parse_value_path("app.containers[0].image").unwrap();
+    assert_eq!(
+        get_json_value_by_path(&value, &path),
+        Some(&serde_json::json!("nginx"))
+    );
+    let path = parse_value_path("app.containers.image").unwrap();
+    assert_eq!(get_json_value_by_path(&value, &path), None);
+}
+
 #[test]
 fn test_list_variables() {
     let file = PathBuf::from("./src/test_data/test_list_variables/supported.k")
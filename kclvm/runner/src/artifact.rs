@@ -0,0 +1,165 @@
+//! The metadata embedded in the built artifacts, which includes the KCL version, the
+//! compile options, the packages with the source hashes and the schema index, thus the
+//! artifacts can be inspected before they are loaded.
+use anyhow::Result;
+use kclvm_ast::ast::Program;
+use kclvm_config::cache::get_content_hash;
+use serde::{Deserialize, Serialize};
+use std::path::Path;
+
+use crate::{Artifact, ExecProgramArgs};
+
+/// The marker prefixed to the JSON metadata in the artifact.
+const ARTIFACT_INFO_MARKER: &[u8] = b"KCLVM_ARTIFACT_INFO:";
+/// The exported symbol of the metadata in the artifact.
+const ARTIFACT_INFO_SYMBOL: &str = "kclvm_artifact_info";
+/// The suffix of the C source file of the metadata, which is compiled and linked into
+/// the artifact.
+const ARTIFACT_INFO_SOURCE_SUFFIX: &str = ".info.c";
+
+/// ArtifactInfo denotes the metadata of a built artifact.
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct ArtifactInfo {
+    /// The KCL version string including the checksum.
+    pub version: String,
+    /// The target triple of the artifact.
+    pub target: String,
+    /// The compile options of the artifact.
+    pub options: ExecProgramArgs,
+    /// The packages compiled into the artifact, sorted by the package path.
+    pub packages: Vec<PackageInfo>,
+    /// The schemas defined in the packages.
+    pub schemas: Vec<SchemaInfo>,
+}
+
+/// PackageInfo denotes a package compiled into the artifact.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PackageInfo {
+    pub pkgpath: String,
+    /// The source files of the package, sorted by the filename.
+    pub files: Vec<SourceFileInfo>,
+}
+
+/// SourceFileInfo denotes a source file and the hash of its content.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SourceFileInfo {
+    pub filename: String,
+    pub hash: String,
+}
+
+/// SchemaInfo denotes a schema defined in the packages.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct SchemaInfo {
+    pub pkgpath: String,
+    pub name: String,
+    pub filename: String,
+    pub line: u64,
+}
+
+impl ArtifactInfo {
+    /// Returns the metadata of the artifact built from the program with the options.
+    pub fn new(args: &ExecProgramArgs, program: &Program) -> Result<Self> {
+        let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+        pkgpaths.sort();
+        let mut packages = vec![];
+        let mut schemas = vec![];
+        for pkgpath in pkgpaths {
+            let mut filenames = program.pkgs[pkgpath].clone();
+            filenames.sort();
+            let mut files = vec![];
+            for filename in filenames {
+                let module = program.get_module(&filename)?.ok_or(anyhow::anyhow!(
+                    "Internal error: module {} not found",
+                    filename
+                ))?;
+                // The sources from the code list are not files, and their ASTs are hashed.
+                let content = match std::fs::read(&filename) {
+                    Ok(content) => content,
+                    Err(_) => serde_json::to_vec(&*module)?,
+                };
+                let hash = get_content_hash(&[&content]);
+                files.push(SourceFileInfo {
+                    filename: filename.clone(),
+                    hash: hash.iter().map(|b| format!("{:02x}", b)).collect(),
+                });
+                for schema_stmt in module.filter_schema_stmt_from_module() {
+                    schemas.push(SchemaInfo {
+                        pkgpath: pkgpath.clone(),
+                        name: schema_stmt.node.name.node.clone(),
+                        filename: filename.clone(),
+                        line: schema_stmt.line,
+                    });
+                }
+            }
+            packages.push(PackageInfo {
+                pkgpath: pkgpath.clone(),
+                files,
+            });
+        }
+        Ok(Self {
+            version: kclvm_version::get_version_string(),
+            target: args
+                .target
+                .clone()
+                .unwrap_or_else(|| env!("KCLVM_DEFAULT_TARGET").to_string()),
+            options: args.clone(),
+            packages,
+            schemas,
+        })
+    }
+
+    /// Read the metadata from the artifact file without loading it.
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Result<Self> {
+        let path = path.as_ref();
+        Self::from_bytes(&std::fs::read(path)?).ok_or(anyhow::anyhow!(
+            "the artifact '{}' has no metadata",
+            path.display()
+        ))
+    }
+
+    /// Find the metadata in the bytes of the artifact.
+    pub(crate) fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        let start = bytes
+            .windows(ARTIFACT_INFO_MARKER.len())
+            .position(|w| w == ARTIFACT_INFO_MARKER)?
+            + ARTIFACT_INFO_MARKER.len();
+        let len = bytes[start..].iter().position(|b| *b == 0)?;
+        serde_json::from_slice(&bytes[start..start + len]).ok()
+    }
+
+    /// Returns the pretty JSON string of the metadata.
+    pub fn to_json_string(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Write the C source file defining the metadata symbol, which is linked into the
+    /// artifact, and returns the source file path.
+    pub(crate) fn write_source(&self, code_file: &str) -> Result<String> {
+        let path = format!("{}{}", code_file, ARTIFACT_INFO_SOURCE_SUFFIX);
+        let mut data = ARTIFACT_INFO_MARKER.to_vec();
+        data.extend(serde_json::to_vec(self)?);
+        data.push(0);
+        let source = format!(
+            "#ifdef _WIN32\n__declspec(dllexport)\n#endif\nconst unsigned char {}[] = {};\n",
+            ARTIFACT_INFO_SYMBOL,
+            c_array_initializer(&data)
+        );
+        std::fs::write(&path, source)?;
+        Ok(path)
+    }
+}
+
+impl Artifact {
+    /// Returns the metadata embedded in the artifact.
+    #[inline]
+    pub fn info(&self) -> Result<ArtifactInfo> {
+        ArtifactInfo::from_path(self.get_path())
+    }
+}
+
+/// Returns the C array initializer of the bytes. The array initializer is used instead of
+/// the string literal, whose length is limited by some compilers e.g., MSVC.
+pub(crate) fn c_array_initializer(bytes: &[u8]) -> String {
+    let items: Vec<String> = bytes.iter().map(|b| b.to_string()).collect();
+    format!("{{{}}}", items.join(","))
+}
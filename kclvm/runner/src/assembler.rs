@@ -3,19 +3,24 @@ use compiler_base_macros::bug;
 use indexmap::IndexMap;
 use kclvm_ast::ast::{self, Program};
 use kclvm_compiler::codegen::{emit_code, EmitOptions, OBJECT_FILE_SUFFIX};
-use kclvm_config::cache::{load_pkg_cache, save_pkg_cache, CacheOption, KCL_CACHE_PATH_ENV_VAR};
+use kclvm_config::cache::{get_content_hash, CacheInfo, KCL_CACHE_PATH_ENV_VAR};
 use kclvm_sema::resolver::scope::ProgramScope;
 use kclvm_utils::fslock::open_lock_file;
 use std::{
     collections::HashMap,
     env,
     path::{Path, PathBuf},
+    time::Instant,
 };
 
-use crate::ExecProgramArgs;
+use crate::profile::{PackageProfile, CODEGEN_PHASE};
+use crate::{dump, temp_file, ExecProgramArgs};
 
 /// IR code file suffix.
 const DEFAULT_IR_FILE: &str = "_a.out";
+/// The directory of the compiled object files under the cache directory, and the object
+/// files are named by the content hashes of the packages.
+const OBJECT_CACHE_DIR: &str = "objects";
 
 /// LibAssembler trait is used to indicate the general interface
 /// that must be implemented when different intermediate codes are assembled
@@ -155,6 +160,12 @@ impl LibAssembler for LlvmLibAssembler {
         // Clean the existed "*.o" object file.
         clean_path(object_file_path)?;
 
+        let pkgpath = compile_prog
+            .pkgs
+            .keys()
+            .next()
+            .ok_or(anyhow::anyhow!("Internal error: empty program"))?;
+        let ir_path = dump::llvm_ir_dump_path(arg, pkgpath)?;
         // Compile KCL code into ".o" object file.
         emit_code(
             compile_prog,
@@ -164,6 +175,8 @@ impl LibAssembler for LlvmLibAssembler {
                 from_path: None,
                 emit_path: Some(code_file),
                 no_link: true,
+                target: arg.target.as_deref(),
+                ir_path: ir_path.as_deref(),
             },
         )
         .map_err(|e| {
@@ -202,7 +215,6 @@ pub(crate) struct KclvmAssembler {
     entry_file: String,
     single_file_assembler: KclvmLibAssembler,
     target: String,
-    external_pkgs: HashMap<String, String>,
 }
 
 impl KclvmAssembler {
@@ -214,7 +226,6 @@ impl KclvmAssembler {
         scope: ProgramScope,
         entry_file: String,
         single_file_assembler: KclvmLibAssembler,
-        external_pkgs: HashMap<String, String>,
     ) -> Self {
         Self {
             program,
@@ -222,7 +233,6 @@ impl KclvmAssembler {
             entry_file,
             single_file_assembler,
             target: env!("KCLVM_DEFAULT_TARGET").to_string(),
-            external_pkgs,
         }
     }
 
@@ -268,6 +278,51 @@ impl KclvmAssembler {
             .join(&self.target)
     }
 
+    /// Returns the content hash of the package, which is computed from the AST modules
+    /// and the import names of the package, the work directory and the content hashes of
+    /// the imported packages. Thus the cached object file of a package is invalidated
+    /// when the package or any package it depends on is changed.
+    pub(crate) fn pkg_content_hash(
+        &self,
+        pkgpath: &str,
+        work_dir: &str,
+        hashes: &mut HashMap<String, CacheInfo>,
+    ) -> Result<CacheInfo> {
+        if let Some(hash) = hashes.get(pkgpath) {
+            return Ok(hash.clone());
+        }
+        // Break the import cycles, which have been reported by the resolver.
+        hashes.insert(pkgpath.to_string(), vec![]);
+        let mut files = self.program.pkgs.get(pkgpath).cloned().unwrap_or_default();
+        files.sort();
+        let mut parts = vec![work_dir.as_bytes().to_vec(), pkgpath.as_bytes().to_vec()];
+        let mut imported_pkgpaths = vec![];
+        for file in &files {
+            let module = self
+                .program
+                .get_module(file)?
+                .ok_or(anyhow::anyhow!("Internal error: module {} not found", file))?;
+            parts.push(serde_json::to_vec(&*module)?);
+            if let Some(import_names) = self.scope.import_names.get(file) {
+                for (name, path) in import_names {
+                    parts.push(format!("{}={}", name, path).into_bytes());
+                    imported_pkgpaths.push(path.clone());
+                }
+            }
+        }
+        imported_pkgpaths.sort();
+        imported_pkgpaths.dedup();
+        for imported_pkgpath in &imported_pkgpaths {
+            if self.program.pkgs.contains_key(imported_pkgpath) {
+                parts.push(self.pkg_content_hash(imported_pkgpath, work_dir, hashes)?);
+            }
+        }
+        let parts: Vec<&[u8]> = parts.iter().map(|p| p.as_slice()).collect();
+        let hash = get_content_hash(&parts);
+        hashes.insert(pkgpath.to_string(), hash.clone());
+        Ok(hash)
+    }
+
     /// Generate the dynamic link libraries and return file paths.
     ///
     /// In the method, multiple threads will be created to concurrently generate dynamic link libraries
@@ -278,12 +333,35 @@ impl KclvmAssembler {
     ///
     /// `gen_libs` will create multiple threads and call the method provided by [KclvmLibAssembler] in each thread
     /// to generate the dynamic link library in parallel.
+    ///
+    /// The object files are cached under the cache directory by the content hashes of the
+    /// packages and reused across the executions, unless the `no_cache` option is set.
     pub(crate) fn gen_libs(self, args: &ExecProgramArgs) -> Result<Vec<String>> {
+        Ok(self.gen_libs_with_profile(args)?.0)
+    }
+
+    /// Generate the libs like [KclvmAssembler::gen_libs], and returns the codegen time of
+    /// each package as well.
+    pub(crate) fn gen_libs_with_profile(
+        mut self,
+        args: &ExecProgramArgs,
+    ) -> Result<(Vec<String>, Vec<PackageProfile>)> {
+        if let Some(target) = &args.target {
+            self.target = target.clone();
+        }
         self.clean_path_for_genlibs(
             DEFAULT_IR_FILE,
             &self.single_file_assembler.get_code_file_suffix(),
         )?;
         let cache_dir = self.load_cache_dir(&self.program.root)?;
+        let mut pkg_hashes = HashMap::new();
+        // The cache is skipped to dump the LLVM IR of all the packages.
+        if !args.no_cache && !args.dump_llvm_ir {
+            let work_dir = args.work_dir.clone().unwrap_or_default();
+            for pkgpath in self.program.pkgs.keys() {
+                self.pkg_content_hash(pkgpath, &work_dir, &mut pkg_hashes)?;
+            }
+        }
         let mut compile_progs: IndexMap<
             String,
             (
@@ -312,7 +390,9 @@ impl KclvmAssembler {
             );
         }
         let mut lib_paths = vec![];
+        let mut packages = vec![];
         for (pkgpath, (compile_prog, import_names, cache_dir)) in compile_progs {
+            let start = Instant::now();
             // Clone a single file assembler for one thread.
             let assembler = self.single_file_assembler.clone();
             // Generate paths for some intermediate files (*.o, *.lock).
@@ -321,9 +401,14 @@ impl KclvmAssembler {
             let file = if is_main_pkg {
                 // The path to the generated files(*.o or *.lock) when the main package is compiled.
                 PathBuf::from(entry_file)
-            } else {
+            } else if pkg_hashes.contains_key(&pkgpath) {
                 // The path to the generated files(*.o or *.lock) when the non-main package is compiled.
                 cache_dir.join(&pkgpath)
+            } else {
+                // The object files which are not cached are generated next to the entry file
+                // of the run, thus they are never overwritten by the other programs executed
+                // concurrently before linking.
+                PathBuf::from(format!("{}_{}", entry_file, pkgpath))
             };
             let code_file = file
                 .to_str()
@@ -331,56 +416,21 @@ impl KclvmAssembler {
                 .to_string();
             let code_file_path = assembler.add_code_file_suffix(&code_file);
             let lock_file_path = format!("{}.lock", code_file_path);
-            let target = self.target.clone();
             {
                 // Locking file for parallel code generation.
                 let mut file_lock = open_lock_file(&lock_file_path)?;
                 file_lock.lock()?;
 
-                let root = &compile_prog.root;
-                // The main package does not perform cache reading and writing,
-                // and other packages perform read and write caching. Because
-                // KCL supports multi-file compilation, it is impossible to
-                // specify a standard entry for these multi-files and cannot
-                // be shared, so the cache of the main package is not read and
-                // written.
-                let file_path = if is_main_pkg {
-                    // generate dynamic link library for single file kcl program
-                    assembler.assemble(
-                        &compile_prog,
-                        import_names,
-                        &code_file,
-                        &code_file_path,
-                        args,
-                    )?
-                } else {
-                    // Read the lib path cache
-                    let file_relative_path: Option<String> = load_pkg_cache(
-                        root,
-                        &target,
-                        &pkgpath,
-                        CacheOption::default(),
-                        &self.external_pkgs,
-                    );
-                    let file_abs_path = match file_relative_path {
-                        Some(file_relative_path) => {
-                            let path = if file_relative_path.starts_with('.') {
-                                file_relative_path.replacen('.', root, 1)
-                            } else {
-                                file_relative_path
-                            };
-                            if Path::new(&path).exists() {
-                                Some(path)
-                            } else {
-                                None
-                            }
-                        }
-                        None => None,
-                    };
-                    match file_abs_path {
-                        Some(path) => path,
-                        None => {
-                            // Generate the object file for single file kcl program.
+                let file_path = match pkg_hashes.get(&pkgpath) {
+                    Some(hash) => {
+                        let cached_path =
+                            object_cache_file(&cache_dir, hash, &assembler.get_code_file_suffix());
+                        // The cached object files are never rewritten once saved, thus they
+                        // are linked directly instead of the object files of the packages,
+                        // which may be overwritten by the other programs executed concurrently.
+                        if cached_path.exists() {
+                            cached_path.display().to_string()
+                        } else {
                             let file_path = assembler.assemble(
                                 &compile_prog,
                                 import_names,
@@ -388,29 +438,56 @@ impl KclvmAssembler {
                                 &code_file_path,
                                 args,
                             )?;
-                            let lib_relative_path = file_path.replacen(root, ".", 1);
-                            let _ = save_pkg_cache(
-                                root,
-                                &target,
-                                &pkgpath,
-                                lib_relative_path,
-                                CacheOption::default(),
-                                &self.external_pkgs,
-                            );
-                            file_path
+                            match save_object_cache(&file_path, &cached_path) {
+                                Ok(_) => cached_path.display().to_string(),
+                                Err(_) => file_path,
+                            }
                         }
                     }
+                    None => assembler.assemble(
+                        &compile_prog,
+                        import_names,
+                        &code_file,
+                        &code_file_path,
+                        args,
+                    )?,
                 };
                 file_lock.unlock()?;
                 lib_paths.push(file_path);
             };
+            packages.push(PackageProfile {
+                pkgpath,
+                phase: CODEGEN_PHASE.to_string(),
+                wall_time_ms: start.elapsed().as_secs_f64() * 1000.0,
+            });
         }
         self.single_file_assembler
             .clean_lock_file(&self.entry_file)?;
-        Ok(lib_paths)
+        Ok((lib_paths, packages))
     }
 }
 
+/// Returns the path of the cached object file of the package content hash.
+pub(crate) fn object_cache_file(cache_dir: &Path, hash: &CacheInfo, suffix: &str) -> PathBuf {
+    let name: String = hash.iter().map(|b| format!("{:02x}", b)).collect();
+    cache_dir
+        .join(OBJECT_CACHE_DIR)
+        .join(format!("{}{}", name, suffix))
+}
+
+/// Save the object file into the object cache. The object file is copied to a temp file
+/// and then renamed, thus the other processes never read a partially written object file.
+fn save_object_cache(file_path: &str, cached_path: &Path) -> Result<()> {
+    let dir = cached_path.parent().ok_or(anyhow::anyhow!(
+        "invalid cache file {}",
+        cached_path.display()
+    ))?;
+    let tmp_path = format!("{}.tmp", temp_file(&dir.display().to_string())?);
+    std::fs::copy(file_path, &tmp_path)?;
+    std::fs::rename(&tmp_path, cached_path)?;
+    Ok(())
+}
+
 #[inline]
 pub(crate) fn clean_path(path: &str) -> Result<()> {
     if Path::new(path).exists() {
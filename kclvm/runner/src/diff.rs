@@ -0,0 +1,236 @@
+//! Structured diff between the planned values of two KCL program executions, which
+//! is returned as a JSON Patch ([RFC 6902](https://www.rfc-editor.org/rfc/rfc6902))
+//! and a human readable tree diff for the preview workflows like `kcl diff`.
+use std::fmt::Write;
+
+use anyhow::Result;
+use kclvm_parser::ParseSessionRef;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::{exec_program, ExecProgramArgs, MapErrorResult};
+
+const TREE_DIFF_INDENT: &str = "  ";
+
+/// A JSON Patch operation, which is one of `add`, `remove` and `replace`.
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PatchOperation {
+    pub op: String,
+    /// The JSON Pointer of the changed value e.g., `/app/replicas`.
+    pub path: String,
+    /// The new value, which is None for the `remove` operation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<Value>,
+}
+
+/// DiffResult denotes the structured diff of the planned values.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct DiffResult {
+    /// The JSON Patch operations which transform the old value to the new value.
+    pub patch: Vec<PatchOperation>,
+    /// The human readable tree diff, in which the changed values are prefixed with `~`,
+    /// the added values with `+` and the removed values with `-`.
+    pub tree_diff: String,
+}
+
+impl DiffResult {
+    /// Whether the planned values are the same.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.patch.is_empty()
+    }
+}
+
+/// Execute the old and new programs, which are two entrypoints or the same entrypoint
+/// with different options, and returns the structured diff of the planned values.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{diff::exec_program_diff, ExecProgramArgs};
+/// use kclvm_parser::ParseSession;
+/// use std::sync::Arc;
+///
+/// let mut old_args = ExecProgramArgs::default();
+/// old_args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+/// let mut new_args = old_args.clone();
+/// new_args.path_selector = vec!["alice.age".to_string()];
+///
+/// let result = exec_program_diff(Arc::new(ParseSession::default()), &old_args, &new_args).unwrap();
+/// assert_eq!(result.patch[0].op, "replace");
+/// assert_eq!(result.patch[0].path, "");
+/// ```
+pub fn exec_program_diff(
+    sess: ParseSessionRef,
+    old_args: &ExecProgramArgs,
+    new_args: &ExecProgramArgs,
+) -> Result<DiffResult> {
+    let old = exec_program(sess.clone(), old_args).map_err_to_result()?;
+    let new = exec_program(sess, new_args).map_err_to_result()?;
+    Ok(diff(
+        &parse_json_result(&old.json_result)?,
+        &parse_json_result(&new.json_result)?,
+    ))
+}
+
+/// Returns the structured diff of the old and new values, and the keys of the dicts
+/// are compared in the sorted order.
+pub fn diff(old: &Value, new: &Value) -> DiffResult {
+    let mut patch = vec![];
+    diff_patch(old, new, "", &mut patch);
+    let mut tree_diff = String::new();
+    diff_tree(old, new, None, 0, &mut tree_diff);
+    DiffResult { patch, tree_diff }
+}
+
+/// Parse the JSON result, and the JSON stream of multiple documents is parsed to a list.
+fn parse_json_result(json_result: &str) -> Result<Value> {
+    let mut documents = serde_json::Deserializer::from_str(json_result)
+        .into_iter::<Value>()
+        .collect::<Result<Vec<Value>, _>>()?;
+    Ok(match documents.len() {
+        0 => Value::Null,
+        1 => documents.remove(0),
+        _ => Value::Array(documents),
+    })
+}
+
+/// Escape the reference token of the JSON Pointer, see RFC 6901.
+fn escape_pointer_token(token: &str) -> String {
+    token.replace('~', "~0").replace('/', "~1")
+}
+
+fn diff_patch(old: &Value, new: &Value, path: &str, patch: &mut Vec<PatchOperation>) {
+    match (old, new) {
+        (Value::Object(old_values), Value::Object(new_values)) => {
+            for (key, old_value) in old_values {
+                let path = format!("{}/{}", path, escape_pointer_token(key));
+                match new_values.get(key) {
+                    Some(new_value) => diff_patch(old_value, new_value, &path, patch),
+                    None => patch.push(PatchOperation {
+                        op: "remove".to_string(),
+                        path,
+                        value: None,
+                    }),
+                }
+            }
+            for (key, new_value) in new_values {
+                if !old_values.contains_key(key) {
+                    patch.push(PatchOperation {
+                        op: "add".to_string(),
+                        path: format!("{}/{}", path, escape_pointer_token(key)),
+                        value: Some(new_value.clone()),
+                    });
+                }
+            }
+        }
+        (Value::Array(old_values), Value::Array(new_values)) => {
+            for (i, (old_value, new_value)) in old_values.iter().zip(new_values).enumerate() {
+                diff_patch(old_value, new_value, &format!("{}/{}", path, i), patch);
+            }
+            // Remove the items from the end to keep the indices of the rest items valid.
+            for i in (new_values.len()..old_values.len()).rev() {
+                patch.push(PatchOperation {
+                    op: "remove".to_string(),
+                    path: format!("{}/{}", path, i),
+                    value: None,
+                });
+            }
+            for (i, new_value) in new_values.iter().enumerate().skip(old_values.len()) {
+                patch.push(PatchOperation {
+                    op: "add".to_string(),
+                    path: format!("{}/{}", path, i),
+                    value: Some(new_value.clone()),
+                });
+            }
+        }
+        _ => {
+            if old != new {
+                patch.push(PatchOperation {
+                    op: "replace".to_string(),
+                    path: path.to_string(),
+                    value: Some(new.clone()),
+                });
+            }
+        }
+    }
+}
+
+/// Write the tree diff of the changed values, and the unchanged values are omitted.
+fn diff_tree(old: &Value, new: &Value, label: Option<&str>, level: usize, w: &mut String) {
+    let children_level = if label.is_some() { level + 1 } else { level };
+    let mut children = String::new();
+    match (old, new) {
+        (Value::Object(old_values), Value::Object(new_values)) => {
+            for (key, old_value) in old_values {
+                match new_values.get(key) {
+                    Some(new_value) => diff_tree(
+                        old_value,
+                        new_value,
+                        Some(key),
+                        children_level,
+                        &mut children,
+                    ),
+                    None => write_tree_line(&mut children, children_level, '-', key, old_value),
+                }
+            }
+            for (key, new_value) in new_values {
+                if !old_values.contains_key(key) {
+                    write_tree_line(&mut children, children_level, '+', key, new_value);
+                }
+            }
+        }
+        (Value::Array(old_values), Value::Array(new_values)) => {
+            for i in 0..old_values.len().max(new_values.len()) {
+                let label = format!("[{}]", i);
+                match (old_values.get(i), new_values.get(i)) {
+                    (Some(old_value), Some(new_value)) => diff_tree(
+                        old_value,
+                        new_value,
+                        Some(&label),
+                        children_level,
+                        &mut children,
+                    ),
+                    (Some(old_value), None) => {
+                        write_tree_line(&mut children, children_level, '-', &label, old_value)
+                    }
+                    (None, Some(new_value)) => {
+                        write_tree_line(&mut children, children_level, '+', &label, new_value)
+                    }
+                    (None, None) => {}
+                }
+            }
+        }
+        _ => {
+            if old != new {
+                let label = label.map(|l| format!("{}: ", l)).unwrap_or_default();
+                let _ = writeln!(
+                    w,
+                    "{}~ {}{} => {}",
+                    TREE_DIFF_INDENT.repeat(level),
+                    label,
+                    old,
+                    new
+                );
+            }
+            return;
+        }
+    }
+    if !children.is_empty() {
+        if let Some(label) = label {
+            let _ = writeln!(w, "{}{}:", TREE_DIFF_INDENT.repeat(level), label);
+        }
+        w.push_str(&children);
+    }
+}
+
+fn write_tree_line(w: &mut String, level: usize, sign: char, label: &str, value: &Value) {
+    let _ = writeln!(
+        w,
+        "{}{} {}: {}",
+        TREE_DIFF_INDENT.repeat(level),
+        sign,
+        label,
+        value
+    );
+}
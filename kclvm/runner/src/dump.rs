@@ -0,0 +1,84 @@
+//! Dump the intermediate representations of the program execution, which are the
+//! resolved AST, the LLVM IR of each package and the linked library, to the dump
+//! directory for the compiler bug reports and the downstream tools.
+use std::collections::BTreeMap;
+use std::path::PathBuf;
+
+use anyhow::Result;
+use kclvm_ast::ast::{set_should_serialize_id, Program, SerializeProgram};
+use kclvm_sema::resolver::scope::ProgramScope;
+use serde::Serialize;
+
+use crate::ExecProgramArgs;
+
+/// The file name of the dumped AST.
+pub const AST_DUMP_FILE: &str = "ast.json";
+/// The file stem of the dumped library, and the file extension is the library suffix.
+pub const LIB_DUMP_FILE_STEM: &str = "main";
+
+/// The dumped AST, in which the AST nodes are serialized with the ids, thus the
+/// types of the nodes can be found in the node type map.
+#[derive(Serialize)]
+struct AstDump {
+    program: SerializeProgram,
+    /// The type strings of the AST nodes keyed by the node ids.
+    node_types: BTreeMap<String, String>,
+}
+
+/// Returns the dump directory, which is created if not exists.
+fn dump_dir(args: &ExecProgramArgs) -> Result<PathBuf> {
+    let dir = args.dump_dir.as_ref().ok_or(anyhow::anyhow!(
+        "the dump directory is required to dump the intermediate representations"
+    ))?;
+    std::fs::create_dir_all(dir)?;
+    Ok(PathBuf::from(dir))
+}
+
+/// Dump the resolved AST and the node types to the dump directory when the `dump_ast`
+/// option is set.
+pub(crate) fn dump_ast(
+    args: &ExecProgramArgs,
+    program: &Program,
+    scope: &ProgramScope,
+) -> Result<()> {
+    if !args.dump_ast {
+        return Ok(());
+    }
+    let path = dump_dir(args)?.join(AST_DUMP_FILE);
+    let node_types = scope
+        .node_ty_map
+        .borrow()
+        .iter()
+        .map(|(key, ty)| (key.id.to_string(), ty.ty_str()))
+        .collect();
+    let dump = AstDump {
+        program: program.clone().into(),
+        node_types,
+    };
+    // The node ids are only serialized in the dumped AST.
+    set_should_serialize_id(true);
+    let content = serde_json::to_string_pretty(&dump);
+    set_should_serialize_id(false);
+    std::fs::write(path, content?)?;
+    Ok(())
+}
+
+/// Returns the path prefix of the dumped LLVM IR file of the package without the `.ll`
+/// suffix when the `dump_llvm_ir` option is set.
+pub(crate) fn llvm_ir_dump_path(args: &ExecProgramArgs, pkgpath: &str) -> Result<Option<String>> {
+    if !args.dump_llvm_ir {
+        return Ok(None);
+    }
+    Ok(Some(dump_dir(args)?.join(pkgpath).display().to_string()))
+}
+
+/// Copy the linked library to the dump directory when the `dump_lib` option is set.
+#[cfg(feature = "llvm")]
+pub(crate) fn dump_lib(args: &ExecProgramArgs, lib_path: &str, lib_suffix: &str) -> Result<()> {
+    if !args.dump_lib {
+        return Ok(());
+    }
+    let path = dump_dir(args)?.join(format!("{}{}", LIB_DUMP_FILE_STEM, lib_suffix));
+    std::fs::copy(lib_path, path)?;
+    Ok(())
+}
@@ -1,33 +1,65 @@
+//! The KCL program runner, which executes the programs by the evaluator or the native
+//! libs built by the LLVM backend. Multiple programs can be executed concurrently on
+//! different threads in one process.
+
 use std::{
     collections::HashMap,
     ffi::OsStr,
+    io::Write,
     path::Path,
-    sync::{Arc, RwLock},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, RwLock,
+    },
 };
 
 use anyhow::{anyhow, bail, Result};
+pub use artifact::ArtifactInfo;
 use assembler::KclvmLibAssembler;
 use kclvm_ast::{
     ast::{Module, Program},
     MAIN_PKG,
 };
 use kclvm_config::cache::KCL_CACHE_PATH_ENV_VAR;
+use kclvm_error::format::FormattedDiagnostics;
+use kclvm_error::{ColorMode, Handler, Level, Locale};
 use kclvm_parser::{load_program, KCLModuleCache, ParseSessionRef};
 use kclvm_query::apply_overrides;
+use kclvm_runtime::val_plan::ProvenanceMode;
+pub use kclvm_runtime::{
+    register_plugin_fn, unregister_plugin_fn, val_plan::PlanFormat, PluginFunction, PluginFunctions,
+};
 use kclvm_sema::resolver::{
     resolve_program, resolve_program_with_opts, scope::ProgramScope, Options,
 };
 use kclvm_utils::fslock::open_lock_file;
 use linker::Command;
-pub use runner::{Artifact, ExecProgramArgs, ExecProgramResult, MapErrorResult};
+pub use profile::ProfileResult;
+use profile::{Profiler, PARSE_PHASE, RESOLVE_PHASE, RUN_PHASE};
+#[cfg(feature = "llvm")]
+use profile::{CODEGEN_PHASE, LINK_PHASE};
+#[cfg(feature = "llvm")]
+use progress::emit_progress;
+pub use progress::{ProgressEvent, ProgressListener};
+pub use runner::{Artifact, Backend, ExecProgramArgs, ExecProgramResult, MapErrorResult};
 use runner::{FastRunner, RunnerOptions};
 #[cfg(feature = "llvm")]
 use runner::{LibRunner, ProgramRunner};
 use tempfile::tempdir;
+#[cfg(not(target_arch = "wasm32"))]
+pub use watch::watch_program;
 
+pub mod artifact;
 pub mod assembler;
+pub mod diff;
+pub mod dump;
 pub mod linker;
+pub mod options;
+pub mod profile;
+pub mod progress;
 pub mod runner;
+#[cfg(not(target_arch = "wasm32"))]
+pub mod watch;
 
 #[cfg(test)]
 pub mod tests;
@@ -60,8 +92,6 @@ pub const KCL_FAST_EVAL_ENV_VAR: &str = "KCL_FAST_EVAL";
 ///
 /// At last, KclLibRunner will be constructed and call method "run" to execute the kcl program.
 ///
-/// **Note that it is not thread safe.**
-///
 /// # Examples
 ///
 /// ```
@@ -80,6 +110,48 @@ pub const KCL_FAST_EVAL_ENV_VAR: &str = "KCL_FAST_EVAL";
 /// let result = exec_program(sess, &args).unwrap();
 /// ```
 pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
+    let mut profiler = Profiler::new(args);
+    let program = profiler.phase(PARSE_PHASE, || {
+        load_program_with_overrides(sess.clone(), args)
+    })?;
+    let mut result = execute(sess, program, args)?;
+    profiler.merge_into(&mut result);
+    Ok(result)
+}
+
+/// Execute the KCL program like [exec_program], but the planned JSON or YAML documents
+/// are written to the writer one by one as they are planned instead of being buffered
+/// in the returned [ExecProgramResult], whose JSON and YAML results are always empty.
+/// It avoids holding the whole result string in memory for the very large plans.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_runner::{exec_program_to_writer, ExecProgramArgs, PlanFormat};
+/// use kclvm_parser::ParseSession;
+/// use std::sync::Arc;
+///
+/// let sess = Arc::new(ParseSession::default());
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+///
+/// let mut buf = vec![];
+/// let result = exec_program_to_writer(sess, &args, PlanFormat::Yaml, &mut buf).unwrap();
+/// assert!(result.yaml_result.is_empty());
+/// assert!(!buf.is_empty());
+/// ```
+pub fn exec_program_to_writer(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    format: PlanFormat,
+    w: &mut dyn Write,
+) -> Result<ExecProgramResult> {
+    let program = load_program_with_overrides(sess.clone(), args)?;
+    execute_to_writer(sess, program, args, format, w)
+}
+
+/// Load the KCL program from the args and apply the overrides on it.
+fn load_program_with_overrides(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Program> {
     // parse args from json string
     let opts = args.get_load_program_options();
     let kcl_paths_str = args
@@ -95,13 +167,19 @@ pub fn exec_program(sess: ParseSessionRef, args: &ExecProgramArgs) -> Result<Exe
         Some(module_cache),
     )?
     .program;
+    apply_args_overrides(&mut program, args)?;
+    Ok(program)
+}
+
+/// Apply the overrides in the args on the program.
+pub(crate) fn apply_args_overrides(program: &mut Program, args: &ExecProgramArgs) -> Result<()> {
     apply_overrides(
-        &mut program,
+        program,
         &args.overrides,
         &[],
         args.print_override_ast || args.debug > 0,
     )?;
-    execute(sess, program, args)
+    Ok(())
 }
 
 /// Execute the KCL artifact with args.
@@ -147,8 +225,6 @@ pub fn exec_artifact<P: AsRef<OsStr>>(
 ///
 /// At last, KclLibRunner will be constructed and call method "run" to execute the kcl program.
 ///
-/// **Note that it is not thread safe.**
-///
 /// # Examples
 ///
 /// ```
@@ -176,81 +252,217 @@ pub fn execute(
     mut program: Program,
     args: &ExecProgramArgs,
 ) -> Result<ExecProgramResult> {
+    let mut profiler = Profiler::new(args);
     // If the user only wants to compile the kcl program, the following code will only resolve ast.
     if args.compile_only {
         let mut resolve_opts = Options::default();
         resolve_opts.merge_program = false;
+        resolve_opts.warnings_as_errors = args.warnings_as_errors.clone();
         // Resolve ast
-        let scope = resolve_program_with_opts(&mut program, resolve_opts, None);
-        emit_compile_diag_to_string(sess, &scope, args.compile_only)?;
-        return Ok(ExecProgramResult::default());
+        let scope = profiler.phase(RESOLVE_PHASE, || {
+            resolve_program_with_opts(&mut program, resolve_opts, None)
+        });
+        dump::dump_ast(args, &program, &scope)?;
+        emit_compile_diag_to_string(sess, &scope, args.compile_only, args)?;
+        let mut result = ExecProgramResult::default();
+        profiler.merge_into(&mut result);
+        return Ok(result);
     }
     // Resolve ast
-    let scope = resolve_program(&mut program);
+    let scope = profiler.phase(RESOLVE_PHASE, || {
+        resolve_program_with_opts(
+            &mut program,
+            Options {
+                warnings_as_errors: args.warnings_as_errors.clone(),
+                ..Default::default()
+            },
+            None,
+        )
+    });
+    // Dump the AST before emitting the errors, thus it can be dumped for the invalid programs.
+    dump::dump_ast(args, &program, &scope)?;
     // Emit parse and resolve errors if exists.
-    emit_compile_diag_to_string(sess, &scope, false)?;
-    Ok(
-        // Use the fast evaluator to run the kcl program.
-        if args.fast_eval || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok() {
+    emit_compile_diag_to_string(sess, &scope, false, args)?;
+    options::check_option_types(args, &program, &scope)?;
+    // Use the fast evaluator to run the kcl program with the interpreter backend.
+    let mut result = if use_interpreter(args)? {
+        profiler.phase(RUN_PHASE, || {
             FastRunner::new(Some(RunnerOptions {
                 plugin_agent_ptr: args.plugin_agent,
             }))
-            .run(&program, args)?
-        } else {
-            // Compile the kcl program to native lib and run it.
-            #[cfg(feature = "llvm")]
-            {
-                // Create a temp entry file and the temp dir will be delete automatically
-                let temp_dir = tempdir()?;
-                let temp_dir_path = temp_dir.path().to_str().ok_or(anyhow!(
-                    "Internal error: {}: No such file or directory",
-                    temp_dir.path().display()
-                ))?;
-                let temp_entry_file = temp_file(temp_dir_path)?;
-
-                // Generate libs
-                let lib_paths = assembler::KclvmAssembler::new(
+            .run(&program, args)
+        })?
+    } else {
+        // Compile the kcl program to native lib and run it.
+        #[cfg(feature = "llvm")]
+        {
+            if is_cross_target(args) {
+                bail!(
+                    "the program of the target '{}' can't be run on the host, use build_program instead",
+                    args.target.as_deref().unwrap_or_default()
+                );
+            }
+            // Create a temp entry file and the temp dir will be delete automatically
+            let temp_dir = tempdir()?;
+            let temp_dir_path = temp_dir.path().to_str().ok_or(anyhow!(
+                "Internal error: {}: No such file or directory",
+                temp_dir.path().display()
+            ))?;
+            let temp_entry_file = temp_file(temp_dir_path)?;
+
+            // Generate libs
+            let (lib_paths, packages) = profiler.phase(CODEGEN_PHASE, || {
+                assembler::KclvmAssembler::new(
                     program,
                     scope,
                     temp_entry_file.clone(),
                     KclvmLibAssembler::LLVM,
-                    args.get_package_maps_from_external_pkg(),
                 )
-                .gen_libs(args)?;
-
-                // Link libs into one library
-                let lib_suffix = Command::get_lib_suffix();
-                let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
-                let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)?;
+                .gen_libs_with_profile(args)
+            })?;
+            profiler.add_packages(packages);
 
-                // Run the library
-                let runner = LibRunner::new(Some(RunnerOptions {
-                    plugin_agent_ptr: args.plugin_agent,
-                }));
-                let result = runner.run(&lib_path, args)?;
+            // Link libs into one library
+            let lib_suffix = Command::get_lib_suffix();
+            let temp_out_lib_file = format!("{}{}", temp_entry_file, lib_suffix);
+            let lib_path = profiler.phase(LINK_PHASE, || {
+                linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)
+            })?;
+            dump::dump_lib(args, &lib_path, &lib_suffix)?;
 
-                remove_file(&lib_path)?;
-                clean_tmp_files(&temp_entry_file, &lib_suffix)?;
-                result
+            // Run the library
+            let runner = LibRunner::new(Some(RunnerOptions {
+                plugin_agent_ptr: args.plugin_agent,
+            }));
+            let result = profiler.phase(RUN_PHASE, || runner.run(&lib_path, args))?;
+            // The log records of the native lib are emitted after the run.
+            for record in &result.log_records {
+                emit_progress(&args.progress_listener, ProgressEvent::Log(record.clone()));
             }
-            // If we don't enable llvm feature, the default running path is through the evaluator.
-            #[cfg(not(feature = "llvm"))]
-            {
+
+            remove_file(&lib_path)?;
+            clean_tmp_files(&temp_entry_file, &lib_suffix)?;
+            result
+        }
+        // If we don't enable llvm feature, the default running path is through the evaluator.
+        #[cfg(not(feature = "llvm"))]
+        {
+            profiler.phase(RUN_PHASE, || {
                 FastRunner::new(Some(RunnerOptions {
                     plugin_agent_ptr: args.plugin_agent,
                 }))
-                .run(&program, args)?
+                .run(&program, args)
+            })?
+        }
+    };
+    profiler.merge_into(&mut result);
+    Ok(result)
+}
+
+/// Whether the artifacts are built for a target other than the host.
+fn is_cross_target(args: &ExecProgramArgs) -> bool {
+    args.target
+        .as_deref()
+        .map_or(false, |target| target != env!("KCLVM_DEFAULT_TARGET"))
+}
+
+/// Returns whether the program is run by the interpreter backend. The sandbox policy,
+/// the secret paths, the output directory, the provenance sidecar and the native plugin
+/// functions are only supported by the evaluator, thus they fail the native backend
+/// and select the interpreter backend automatically. Likewise, the LLVM IR and library
+/// dumps fail the interpreter backend and select the native backend automatically.
+fn use_interpreter(args: &ExecProgramArgs) -> Result<bool> {
+    let interpreter_only_option = if args.sandbox.is_enabled() {
+        Some("sandbox policy")
+    } else if !args.secret_paths.is_empty() {
+        Some("secret paths")
+    } else if args.output_dir.is_some() {
+        Some("output directory")
+    } else if args.provenance == ProvenanceMode::Sidecar {
+        Some("provenance sidecar")
+    } else if args.has_native_plugin_fns() {
+        Some("native plugin functions")
+    } else {
+        None
+    };
+    let native_only_option = if args.dump_llvm_ir {
+        Some("LLVM IR dump")
+    } else if args.dump_lib {
+        Some("library dump")
+    } else {
+        None
+    };
+    // The native only options select the native backend in the auto mode.
+    let backend = match args.backend {
+        Backend::Auto if native_only_option.is_some() => Backend::Native,
+        backend => backend,
+    };
+    match backend {
+        Backend::Interpreter => {
+            if let Some(option) = native_only_option {
+                bail!("the {option} is only supported by the native backend");
+            }
+            Ok(true)
+        }
+        Backend::Native => {
+            if let Some(option) = interpreter_only_option {
+                bail!("the {option} is only supported by the interpreter backend");
             }
+            if cfg!(target_arch = "wasm32") {
+                bail!("the native backend is not supported on the wasm32 target, use the interpreter backend instead")
+            } else if cfg!(feature = "llvm") {
+                Ok(false)
+            } else {
+                bail!("the native backend requires the 'llvm' feature, use the interpreter backend instead")
+            }
+        }
+        Backend::Auto => Ok(args.fast_eval
+            || interpreter_only_option.is_some()
+            || std::env::var(KCL_FAST_EVAL_ENV_VAR).is_ok()
+            || cfg!(not(feature = "llvm"))
+            || cfg!(target_arch = "wasm32")),
+    }
+}
+
+/// Execute the KCL program like [execute], and write the planned JSON or YAML documents
+/// to the writer one by one. The program is always run by the fast evaluator because
+/// the native lib runner can only return the whole result string.
+pub fn execute_to_writer(
+    sess: ParseSessionRef,
+    mut program: Program,
+    args: &ExecProgramArgs,
+    format: PlanFormat,
+    w: &mut dyn Write,
+) -> Result<ExecProgramResult> {
+    // Resolve ast, and the program is not merged when it is only compiled.
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            merge_program: !args.compile_only,
+            warnings_as_errors: args.warnings_as_errors.clone(),
+            ..Default::default()
         },
-    )
+        None,
+    );
+    // Emit parse and resolve errors if exists.
+    emit_compile_diag_to_string(sess, &scope, args.compile_only, args)?;
+    if args.compile_only {
+        return Ok(ExecProgramResult::default());
+    }
+    options::check_option_types(args, &program, &scope)?;
+    if args.backend == Backend::Native {
+        bail!("writing the plan documents is only supported by the interpreter backend");
+    }
+    FastRunner::new(Some(RunnerOptions {
+        plugin_agent_ptr: args.plugin_agent,
+    }))
+    .run_to_writer(&program, args, format, w)
 }
 
 /// `execute_module` can directly execute the ast `Module`.
 /// `execute_module` constructs `Program` with default pkg name `MAIN_PKG`,
 /// and calls method `execute` with default `plugin_agent` and `ExecProgramArgs`.
 /// For more information, see doc above method `execute`.
-///
-/// **Note that it is not thread safe.**
 pub fn execute_module(m: Module) -> Result<ExecProgramResult> {
     let mut pkgs = HashMap::new();
     let mut modules = HashMap::new();
@@ -290,7 +502,7 @@ pub fn build_program<P: AsRef<Path>>(
     // Resolve program.
     let scope = resolve_program(&mut program);
     // Emit parse and resolve errors if exists.
-    emit_compile_diag_to_string(sess, &scope, false)?;
+    emit_compile_diag_to_string(sess, &scope, false, args)?;
     // When set the common package cache path, lock the package to prevent the
     // data competition during compilation of different modules.
     if let Ok(cache_path) = std::env::var(KCL_CACHE_PATH_ENV_VAR) {
@@ -334,7 +546,10 @@ fn build<P: AsRef<Path>>(
     let temp_entry_file = temp_file(temp_dir_path)?;
 
     // Link libs into one library.
-    let lib_suffix = Command::get_lib_suffix();
+    let lib_suffix = match &args.target {
+        Some(target) => Command::get_target_lib_suffix(target),
+        None => Command::get_lib_suffix(),
+    };
     // Temporary output of linker
     let temp_out_lib_file = if let Some(output) = output {
         output
@@ -345,19 +560,29 @@ fn build<P: AsRef<Path>>(
     } else {
         format!("{}{}", temp_entry_file, lib_suffix)
     };
+    // Generate the metadata source, which is linked into the library.
+    let info_source = ArtifactInfo::new(args, &program)?.write_source(&temp_entry_file)?;
     // Generate native libs.
-    let lib_paths = assembler::KclvmAssembler::new(
+    let mut lib_paths = assembler::KclvmAssembler::new(
         program,
         scope,
         temp_entry_file.clone(),
         KclvmLibAssembler::LLVM,
-        args.get_package_maps_from_external_pkg(),
     )
     .gen_libs(args)?;
-    let lib_path = linker::KclvmLinker::link_all_libs(lib_paths, temp_out_lib_file)?;
+    lib_paths.push(info_source);
+    let lib_path = linker::KclvmLinker::link_all_libs_with_target(
+        lib_paths,
+        temp_out_lib_file,
+        args.target.as_deref(),
+    )?;
 
-    // Return the library artifact.
-    Artifact::from_path(lib_path)
+    // Return the library artifact, and the library of another target can't be loaded.
+    if is_cross_target(args) {
+        Ok(Artifact::from_target_path(lib_path))
+    } else {
+        Artifact::from_path(lib_path)
+    }
 }
 
 /// Clean all the tmp files generated during lib generating and linking.
@@ -379,11 +604,15 @@ fn remove_file(file: &str) -> Result<()> {
 
 /// Returns a temporary file name consisting of timestamp and process id.
 fn temp_file(dir: &str) -> Result<String> {
+    // The counter keeps the file names unique when the programs are compiled on
+    // multiple threads at the same timestamp.
+    static TEMP_FILE_COUNTER: AtomicUsize = AtomicUsize::new(0);
     let timestamp = chrono::Local::now()
         .timestamp_nanos_opt()
         .unwrap_or_default();
     let id = std::process::id();
-    let file = format!("{}_{}", id, timestamp);
+    let count = TEMP_FILE_COUNTER.fetch_add(1, Ordering::Relaxed);
+    let file = format!("{}_{}_{}", id, timestamp, count);
     std::fs::create_dir_all(dir)?;
     Ok(Path::new(dir)
         .join(file)
@@ -392,14 +621,40 @@ fn temp_file(dir: &str) -> Result<String> {
         .to_string())
 }
 
-// [`emit_compile_diag_to_string`] will emit compile diagnostics to string, including parsing and resolving diagnostics.
+// [`emit_compile_diag_to_string`] will emit compile diagnostics to string, including parsing and resolving diagnostics,
+// which are emitted to the progress listener as well.
 fn emit_compile_diag_to_string(
     sess: ParseSessionRef,
     scope: &ProgramScope,
     include_warnings: bool,
+    args: &ExecProgramArgs,
 ) -> Result<()> {
-    let mut res_str = sess.1.write().emit_to_string()?;
-    let sema_err = scope.emit_diagnostics_to_string(sess.0.clone(), include_warnings);
+    if let Some(listener) = &args.progress_listener {
+        for diag in sess.handler.read().diagnostics.iter().chain(
+            scope
+                .handler
+                .diagnostics
+                .iter()
+                .filter(|diag| include_warnings || !matches!(diag.level, Level::Warning)),
+        ) {
+            listener.on_event(ProgressEvent::Diagnostic(
+                sess.remap_diagnostic(diag.clone()),
+            ));
+        }
+    }
+    if args.diagnostic_format.is_structured()
+        || args.max_errors.is_some()
+        || args.locale != Locale::default()
+        || args.color != ColorMode::default()
+        || !args.path_prefix_map.is_empty()
+    {
+        return emit_compile_diag_with_handler(sess, scope, include_warnings, args);
+    }
+    let mut res_str = sess
+        .handler
+        .write()
+        .emit_to_string_with_session(&sess.session)?;
+    let sema_err = scope.emit_diagnostics_to_string(sess.session.clone(), include_warnings);
     if let Err(err) = &sema_err {
         #[cfg(not(target_os = "windows"))]
         res_str.push('\n');
@@ -413,3 +668,54 @@ fn emit_compile_diag_to_string(
         .then(|| Ok(()))
         .unwrap_or_else(|| bail!(res_str))
 }
+
+// [`emit_compile_diag_with_handler`] will collect compile diagnostics into one handler with the `max_errors` limit,
+// the locale, the color mode and the remapped file names, and emit them in the diagnostic format if there are any errors. The output in the structured format e.g., JSON
+// and SARIF is returned as [`FormattedDiagnostics`].
+fn emit_compile_diag_with_handler(
+    sess: ParseSessionRef,
+    scope: &ProgramScope,
+    include_warnings: bool,
+    args: &ExecProgramArgs,
+) -> Result<()> {
+    let mut diags = Handler::default();
+    let file_hashes = sess.file_hashes();
+    for diag in sess.handler.read().diagnostics.iter().chain(
+        scope
+            .handler
+            .diagnostics
+            .iter()
+            .filter(|diag| include_warnings || !matches!(diag.level, Level::Warning)),
+    ) {
+        diags.add_diagnostic(
+            sess.remap_diagnostic(diag.clone())
+                .with_source_hashes(&file_hashes),
+        );
+    }
+    // The parse errors beyond `max_errors` are already dropped by the session.
+    let dropped_errors = sess.handler.read().dropped_errors;
+    if !diags.has_errors() && dropped_errors == 0 {
+        return Ok(());
+    }
+    let mut handler = match args.max_errors {
+        Some(max_errors) => {
+            // Group the identical errors before limiting them, which are usually cascading
+            // errors reported in different files.
+            diags.group_identical_diagnostics();
+            Handler::with_max_errors(max_errors)
+        }
+        None => Handler::default(),
+    };
+    handler.locale = args.locale;
+    handler.color = args.color;
+    for diag in diags.diagnostics {
+        handler.add_diagnostic(diag);
+    }
+    handler.dropped_errors += dropped_errors;
+    let output = handler.emit_to_string_with_format(&sess.session, args.diagnostic_format)?;
+    if args.diagnostic_format.is_structured() {
+        Err(FormattedDiagnostics(output).into())
+    } else {
+        bail!(output)
+    }
+}
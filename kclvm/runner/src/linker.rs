@@ -13,9 +13,21 @@ pub struct KclvmLinker;
 impl KclvmLinker {
     /// Link the libs generated by method "gen_bc_or_ll_file".
     pub fn link_all_libs(lib_paths: Vec<String>, lib_path: String) -> Result<String> {
+        Self::link_all_libs_with_target(lib_paths, lib_path, None)
+    }
+
+    /// Link the libs into one library of the target triple e.g., `x86_64-unknown-linux-gnu`,
+    /// default is the host triple. The C compiler of the target is found by the cc crate, which
+    /// can be configured by the `CC_<target>` environment variable, and the kclvm runtime
+    /// library of the target is found in the `KCLVM_LIB_LINK_PATH` directory.
+    pub fn link_all_libs_with_target(
+        lib_paths: Vec<String>,
+        lib_path: String,
+        target: Option<&str>,
+    ) -> Result<String> {
         // In the final stage of link, we can't ignore any undefined symbols and do
         // not allow external mounting of the implementation.
-        Command::new()?.link_libs_with_cc(&lib_paths, &lib_path)
+        Command::new()?.link_libs_with_cc(&lib_paths, &lib_path, target)
     }
 }
 
@@ -32,8 +44,16 @@ impl Command {
     }
 
     /// Link dynamic libraries into one library using cc-rs lib.
-    pub(crate) fn link_libs_with_cc(&mut self, libs: &[String], lib_path: &str) -> Result<String> {
-        let lib_suffix = Self::get_lib_suffix();
+    pub(crate) fn link_libs_with_cc(
+        &mut self,
+        libs: &[String],
+        lib_path: &str,
+        target: Option<&str>,
+    ) -> Result<String> {
+        let lib_suffix = match target {
+            Some(target) => Self::get_target_lib_suffix(target),
+            None => Self::get_lib_suffix(),
+        };
         let lib_path = if lib_path.is_empty() {
             format!("{}{}", "_a.out", lib_suffix)
         } else if !lib_path.ends_with(&lib_suffix) {
@@ -43,10 +63,14 @@ impl Command {
         };
 
         #[cfg(not(target_os = "windows"))]
-        let target = format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS);
+        let host = format!("{}-{}", std::env::consts::ARCH, std::env::consts::OS);
 
         #[cfg(target_os = "windows")]
-        let target = format!("{}-{}", std::env::consts::ARCH, Self::cc_env_windows());
+        let host = format!("{}-{}", std::env::consts::ARCH, Self::cc_env_windows());
+
+        let target = target
+            .map(|t| t.to_string())
+            .unwrap_or_else(|| host.clone());
 
         let mut build = cc::Build::new();
 
@@ -57,7 +81,7 @@ impl Command {
             .shared_flag(true)
             .opt_level(0)
             .target(&target)
-            .host(&target)
+            .host(&host)
             .flag("-o")
             .flag(&lib_path);
 
@@ -65,7 +89,7 @@ impl Command {
 
         // Run command with cc.
         let mut cmd = build.try_get_compiler()?.to_command();
-        self.add_args(libs, lib_path.to_string(), &mut cmd)?;
+        self.add_args(libs, lib_path.to_string(), &target, &mut cmd)?;
         let result = cmd.output()?;
         if !result.status.success() {
             anyhow::bail!(
@@ -79,24 +103,22 @@ impl Command {
         Ok(path.adjust_canonicalization())
     }
 
-    /// Add args for cc.
+    /// Add args for cc according to the target.
     pub(crate) fn add_args(
         &self,
         libs: &[String],
         lib_path: String,
+        target: &str,
         cmd: &mut std::process::Command,
     ) -> Result<()> {
-        #[cfg(not(target_os = "windows"))]
-        self.unix_args(libs, lib_path, cmd)?;
-
-        #[cfg(target_os = "windows")]
-        self.msvc_win_args(libs, lib_path, cmd)?;
-
-        Ok(())
+        if target.contains("msvc") {
+            self.msvc_win_args(libs, lib_path, cmd)
+        } else {
+            self.unix_args(libs, lib_path, cmd)
+        }
     }
 
     /// Add args for cc on unix os.
-    #[cfg(not(target_os = "windows"))]
     pub(crate) fn unix_args(
         &self,
         libs: &[String],
@@ -113,7 +135,6 @@ impl Command {
     }
 
     // Add args for cc on windows os.
-    #[cfg(target_os = "windows")]
     pub(crate) fn msvc_win_args(
         &self,
         libs: &[String],
@@ -207,6 +228,18 @@ impl Command {
         DLL_SUFFIX.to_string()
     }
 
+    /// Specifies the filename suffix used for shared libraries on the target triple.
+    pub(crate) fn get_target_lib_suffix(target: &str) -> String {
+        if target.contains("windows") {
+            ".dll"
+        } else if target.contains("apple") || target.contains("darwin") {
+            ".dylib"
+        } else {
+            ".so"
+        }
+        .to_string()
+    }
+
     fn is_windows() -> bool {
         cfg!(target_os = "windows")
     }
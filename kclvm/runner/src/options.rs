@@ -0,0 +1,235 @@
+//! The top-level options of the program, which are declared by the `option()` calls
+//! and set by the `-D` arguments or the structured options. The option values are
+//! checked against the declared types before the execution.
+use anyhow::{anyhow, bail, Result};
+use kclvm_ast::ast::{self, Program};
+use kclvm_ast::walker::MutSelfWalker;
+use kclvm_runtime::{Context, ValueRef};
+use kclvm_sema::builtin::BUILTIN_FUNCTIONS;
+use kclvm_sema::resolver::scope::{NodeKey, NodeTyMap, ProgramScope};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::ExecProgramArgs;
+
+/// OptionProvider provides the top-level options programmatically e.g., from a secret
+/// store or a remote configuration service.
+pub trait OptionProvider: std::fmt::Debug + Send + Sync {
+    /// Returns the option names and the values.
+    fn options(&self) -> Result<Vec<(String, Value)>>;
+}
+
+/// The name of the builtin option function.
+const OPTION_FUNCTION: &str = "option";
+
+/// OptionDecl denotes an `option()` call with a literal option name in the program.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct OptionDecl {
+    pub name: String,
+    /// The type of the option e.g., `int` and `str`, which is empty if not declared.
+    pub ty: String,
+    pub required: bool,
+    /// The default value, which is None if not declared or not a literal.
+    pub default: Option<Value>,
+    pub help: String,
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+}
+
+/// Returns the `option()` calls in the program, and the calls of the functions named
+/// `option` other than the builtin one are skipped by the resolved types.
+pub fn list_option_decls(program: &Program, scope: &ProgramScope) -> Vec<OptionDecl> {
+    let node_ty_map = scope.node_ty_map.borrow();
+    let mut extractor = OptionDeclExtractor {
+        pkgpath: String::new(),
+        decls: vec![],
+        node_ty_map: &node_ty_map,
+    };
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    for pkgpath in pkgpaths {
+        extractor.pkgpath = pkgpath.clone();
+        for filename in &program.pkgs[pkgpath] {
+            if let Ok(Some(module)) = program.get_module(filename) {
+                extractor.walk_module(&module);
+            }
+        }
+    }
+    extractor.decls
+}
+
+/// Check the `-D` arguments and the structured options against the types of the
+/// `option()` calls, thus the coercion errors are reported before the execution.
+pub(crate) fn check_option_types(
+    args: &ExecProgramArgs,
+    program: &Program,
+    scope: &ProgramScope,
+) -> Result<()> {
+    let option_args = args.option_args()?;
+    if option_args.is_empty() {
+        return Ok(());
+    }
+    let mut ctx = Context::new();
+    for decl in list_option_decls(program, scope) {
+        if decl.ty.is_empty() {
+            continue;
+        }
+        // The latter option overrides the former one with the same name.
+        if let Some(arg) = option_args.iter().rev().find(|arg| arg.name == decl.name) {
+            let value = match ValueRef::from_json(&mut ctx, &arg.value) {
+                Ok(value) => value,
+                Err(_) => ValueRef::str(&arg.value),
+            };
+            if let Err(err) = value.option_to_type(&decl.ty) {
+                bail!(
+                    "invalid value of the option '{}' declared at {}:{}:{}, {}",
+                    decl.name,
+                    decl.filename,
+                    decl.line,
+                    decl.column + 1,
+                    err
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Returns the option argument of the JSON value.
+pub(crate) fn json_option_arg(name: String, value: &Value) -> ast::Argument {
+    ast::Argument {
+        name,
+        value: value.to_string(),
+    }
+}
+
+/// Load the options from the YAML or JSON file, in which the top-level value is a dict.
+pub(crate) fn load_option_file(file: &str) -> Result<Vec<ast::Argument>> {
+    let content = std::fs::read_to_string(file)
+        .map_err(|err| anyhow!("failed to read the option file '{}': {}", file, err))?;
+    let mut ctx = Context::new();
+    // The YAML parser also parses the JSON files.
+    let value = ValueRef::from_yaml(&mut ctx, &content)
+        .map_err(|err| anyhow!("failed to parse the option file '{}': {}", file, err))?;
+    if value.is_none() {
+        return Ok(vec![]);
+    }
+    if !value.is_dict() {
+        bail!("the option file '{}' must be a dict", file);
+    }
+    let dict = value.as_dict_ref();
+    Ok(dict
+        .values
+        .iter()
+        .map(|(name, value)| ast::Argument {
+            name: name.clone(),
+            value: value.to_json_string(),
+        })
+        .collect())
+}
+
+/// Returns the options of the environment variables with the prefix in the order of the
+/// names, and the values are parsed like the `-D` options.
+pub(crate) fn env_option_args(prefix: &str) -> Vec<ast::Argument> {
+    let mut option_args: Vec<ast::Argument> = std::env::vars()
+        .filter_map(|(key, value)| {
+            key.strip_prefix(prefix)
+                .filter(|name| !name.is_empty())
+                .map(|name| ast::Argument {
+                    name: name.to_string(),
+                    value,
+                })
+        })
+        .collect();
+    option_args.sort_by(|a, b| a.name.cmp(&b.name));
+    option_args
+}
+
+struct OptionDeclExtractor<'a> {
+    pkgpath: String,
+    decls: Vec<OptionDecl>,
+    node_ty_map: &'a NodeTyMap,
+}
+
+impl<'a> OptionDeclExtractor<'a> {
+    /// Whether the function is the builtin option function.
+    fn is_builtin_option(&self, func: &ast::NodeRef<ast::Expr>) -> bool {
+        match &func.node {
+            ast::Expr::Identifier(identifier)
+                if identifier.get_name() == OPTION_FUNCTION && identifier.pkgpath.is_empty() =>
+            {
+                let key = NodeKey {
+                    pkgpath: self.pkgpath.clone(),
+                    id: func.id.clone(),
+                };
+                match (
+                    self.node_ty_map.get(&key),
+                    BUILTIN_FUNCTIONS.get(OPTION_FUNCTION),
+                ) {
+                    (Some(ty), Some(option_ty)) => ty.ty_str() == option_ty.ty_str(),
+                    _ => false,
+                }
+            }
+            _ => false,
+        }
+    }
+}
+
+impl<'a> MutSelfWalker for OptionDeclExtractor<'a> {
+    fn walk_call_expr(&mut self, call_expr: &ast::CallExpr) {
+        if self.is_builtin_option(&call_expr.func) {
+            if let Some(Value::String(name)) = call_arg_literal(call_expr, 0, "key") {
+                let string_arg =
+                    |index: usize, key: &str| match call_arg_literal(call_expr, index, key) {
+                        Some(Value::String(value)) => value,
+                        _ => String::new(),
+                    };
+                let required = call_arg_literal(call_expr, 2, "required");
+                self.decls.push(OptionDecl {
+                    name,
+                    ty: string_arg(1, "type"),
+                    required: required == Some(Value::Bool(true)),
+                    default: call_arg_literal(call_expr, 3, "default"),
+                    help: string_arg(4, "help"),
+                    filename: call_expr.func.filename.clone(),
+                    line: call_expr.func.line,
+                    column: call_expr.func.column,
+                });
+            }
+        }
+        self.walk_expr(&call_expr.func.node);
+        for arg in &call_expr.args {
+            self.walk_expr(&arg.node);
+        }
+        for keyword in &call_expr.keywords {
+            self.walk_keyword(&keyword.node);
+        }
+    }
+}
+
+/// Returns the literal value of the call argument by the keyword or the position.
+fn call_arg_literal(call_expr: &ast::CallExpr, index: usize, key: &str) -> Option<Value> {
+    let arg = call_expr
+        .keywords
+        .iter()
+        .find(|keyword| keyword.node.arg.node.get_name() == key)
+        .and_then(|keyword| keyword.node.value.as_ref())
+        .or_else(|| call_expr.args.get(index))?;
+    match &arg.node {
+        ast::Expr::StringLit(string_lit) => Some(Value::String(string_lit.value.clone())),
+        ast::Expr::NumberLit(number_lit) if number_lit.binary_suffix.is_none() => {
+            match number_lit.value {
+                ast::NumberLitValue::Int(value) => Some(Value::from(value)),
+                ast::NumberLitValue::Float(value) => Some(Value::from(value)),
+            }
+        }
+        ast::Expr::NameConstantLit(name_constant_lit) => match name_constant_lit.value {
+            ast::NameConstant::True => Some(Value::Bool(true)),
+            ast::NameConstant::False => Some(Value::Bool(false)),
+            ast::NameConstant::None => Some(Value::Null),
+            ast::NameConstant::Undefined => None,
+        },
+        _ => None,
+    }
+}
@@ -0,0 +1,199 @@
+//! Phase-level profiling of the program execution, which records the wall time and the
+//! peak memory of the parse, resolve, codegen, link and run phases, and the time of each
+//! package, thus users can tell whether the slowness comes from the imports or the evaluation.
+use std::fmt::Write;
+use std::sync::Arc;
+use std::time::Instant;
+
+use serde::{Deserialize, Serialize};
+
+use crate::progress::{emit_progress, ProgressEvent, ProgressListener};
+use crate::{ExecProgramArgs, ExecProgramResult};
+
+pub const PARSE_PHASE: &str = "parse";
+pub const RESOLVE_PHASE: &str = "resolve";
+pub const CODEGEN_PHASE: &str = "codegen";
+pub const LINK_PHASE: &str = "link";
+pub const RUN_PHASE: &str = "run";
+
+/// ProfileResult denotes the profile of a program execution.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct ProfileResult {
+    /// The phases in the execution order.
+    pub phases: Vec<PhaseProfile>,
+    /// The packages compiled in the codegen phase of the native backend, or evaluated
+    /// in the run phase of the interpreter backend.
+    pub packages: Vec<PackageProfile>,
+}
+
+/// PhaseProfile denotes the profile of an execution phase.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PhaseProfile {
+    pub name: String,
+    pub wall_time_ms: f64,
+    /// The peak resident memory in bytes during the phase, which is only recorded on
+    /// Linux and is the peak of the whole process.
+    pub peak_memory: Option<u64>,
+}
+
+/// PackageProfile denotes the time spent on a package in a phase.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PackageProfile {
+    pub pkgpath: String,
+    pub phase: String,
+    /// The wall time of the package, and the time of an imported package in the run
+    /// phase includes the nested imports evaluated for the first time.
+    pub wall_time_ms: f64,
+}
+
+impl ProfileResult {
+    /// Returns the total wall time of all the phases.
+    pub fn total_wall_time_ms(&self) -> f64 {
+        self.phases.iter().map(|p| p.wall_time_ms).sum()
+    }
+
+    /// Returns the human readable table of the phases and the packages.
+    pub fn to_table_string(&self) -> String {
+        let mut s = String::new();
+        let _ = writeln!(s, "{:<24} {:>12} {:>12}", "PHASE", "TIME(ms)", "PEAK MEM");
+        for phase in &self.phases {
+            let _ = writeln!(
+                s,
+                "{:<24} {:>12.3} {:>12}",
+                phase.name,
+                phase.wall_time_ms,
+                phase.peak_memory.map_or("-".to_string(), format_bytes)
+            );
+        }
+        let _ = writeln!(s, "{:<24} {:>12.3}", "total", self.total_wall_time_ms());
+        if !self.packages.is_empty() {
+            let _ = writeln!(s);
+            let _ = writeln!(s, "{:<24} {:>12} {:>12}", "PACKAGE", "PHASE", "TIME(ms)");
+            for pkg in &self.packages {
+                let _ = writeln!(
+                    s,
+                    "{:<24} {:>12} {:>12.3}",
+                    pkg.pkgpath, pkg.phase, pkg.wall_time_ms
+                );
+            }
+        }
+        s
+    }
+}
+
+/// Format the bytes with the binary units e.g., `12.5MiB`.
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{}{}", bytes, UNITS[0])
+    } else {
+        format!("{:.1}{}", value, UNITS[unit])
+    }
+}
+
+/// Profiler records the phases when the profiling is enabled and emits the phase events
+/// to the progress listener if any, otherwise it only runs them.
+pub(crate) struct Profiler {
+    enabled: bool,
+    listener: Option<Arc<dyn ProgressListener>>,
+    result: ProfileResult,
+}
+
+impl Profiler {
+    pub(crate) fn new(args: &ExecProgramArgs) -> Self {
+        Self {
+            enabled: args.profile,
+            listener: args.progress_listener.clone(),
+            result: ProfileResult::default(),
+        }
+    }
+
+    /// Run the phase and record its wall time and peak memory.
+    pub(crate) fn phase<T>(&mut self, name: &str, f: impl FnOnce() -> T) -> T {
+        if !self.enabled && self.listener.is_none() {
+            return f();
+        }
+        emit_progress(
+            &self.listener,
+            ProgressEvent::PhaseStarted {
+                phase: name.to_string(),
+            },
+        );
+        if self.enabled {
+            reset_peak_memory();
+        }
+        let start = Instant::now();
+        let value = f();
+        let wall_time_ms = start.elapsed().as_secs_f64() * 1000.0;
+        emit_progress(
+            &self.listener,
+            ProgressEvent::PhaseFinished {
+                phase: name.to_string(),
+                wall_time_ms,
+            },
+        );
+        if self.enabled {
+            self.result.phases.push(PhaseProfile {
+                name: name.to_string(),
+                wall_time_ms,
+                peak_memory: read_peak_memory(),
+            });
+        }
+        value
+    }
+
+    /// Record the time of the packages.
+    pub(crate) fn add_packages(&mut self, packages: Vec<PackageProfile>) {
+        self.result.packages.extend(packages);
+    }
+
+    /// Put the recorded phases and packages before the ones in the result, thus the
+    /// outer phases e.g., the parse phase are in front of the inner ones.
+    pub(crate) fn merge_into(self, result: &mut ExecProgramResult) {
+        if !self.enabled {
+            return;
+        }
+        let mut profile = self.result;
+        if let Some(inner) = result.profile.take() {
+            profile.phases.extend(inner.phases);
+            profile.packages.extend(inner.packages);
+        }
+        result.profile = Some(profile);
+    }
+}
+
+/// Reset the peak resident memory of the process, thus the peak memory of each phase
+/// can be read after it.
+#[cfg(target_os = "linux")]
+fn reset_peak_memory() {
+    let _ = std::fs::write("/proc/self/clear_refs", "5");
+}
+
+#[cfg(not(target_os = "linux"))]
+fn reset_peak_memory() {}
+
+/// Read the peak resident memory of the process in bytes.
+#[cfg(target_os = "linux")]
+fn read_peak_memory() -> Option<u64> {
+    let status = std::fs::read_to_string("/proc/self/status").ok()?;
+    let line = status.lines().find(|l| l.starts_with("VmHWM:"))?;
+    let kib: u64 = line
+        .trim_start_matches("VmHWM:")
+        .trim()
+        .trim_end_matches("kB")
+        .trim()
+        .parse()
+        .ok()?;
+    Some(kib * 1024)
+}
+
+#[cfg(not(target_os = "linux"))]
+fn read_peak_memory() -> Option<u64> {
+    None
+}
@@ -0,0 +1,43 @@
+//! The progress events of the program execution, which are emitted to the progress
+//! listener as soon as they happen, thus the clients e.g., the IDEs can show the
+//! feedback of the long compilations instead of waiting for the result.
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use kclvm_error::Diagnostic;
+use kclvm_runtime::{LogListener, LogRecord};
+
+/// ProgressEvent denotes an event during the program execution.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ProgressEvent {
+    /// An execution phase e.g., the parse phase is started.
+    PhaseStarted { phase: String },
+    /// An execution phase is finished with the wall time in milliseconds.
+    PhaseFinished { phase: String, wall_time_ms: f64 },
+    /// A parse or resolve diagnostic, which is emitted after the resolve phase.
+    Diagnostic(Diagnostic),
+    /// A log record of the `print` function, which is emitted as soon as it is logged by
+    /// the interpreter backend, or after the run phase by the native backend.
+    Log(LogRecord),
+}
+
+/// ProgressListener receives the progress events of the program execution, and it may
+/// be called on the thread executing the program.
+pub trait ProgressListener: Debug + Send + Sync {
+    fn on_event(&self, event: ProgressEvent);
+}
+
+/// Emit the event to the progress listener of the args if any.
+#[inline]
+pub(crate) fn emit_progress(listener: &Option<Arc<dyn ProgressListener>>, event: ProgressEvent) {
+    if let Some(listener) = listener {
+        listener.on_event(event);
+    }
+}
+
+/// Returns the runtime log listener forwarding the log records to the progress listener.
+pub(crate) fn log_listener(listener: &Option<Arc<dyn ProgressListener>>) -> Option<LogListener> {
+    listener.clone().map(|listener| -> LogListener {
+        Arc::new(move |record: &LogRecord| listener.on_event(ProgressEvent::Log(record.clone())))
+    })
+}
@@ -1,24 +1,36 @@
-use anyhow::{anyhow, Result};
+use anyhow::{anyhow, bail, Result};
 use kclvm_evaluator::Evaluator;
-use std::collections::HashMap;
-use std::{cell::RefCell, rc::Rc};
+use std::collections::{BTreeMap, HashMap};
+use std::{cell::RefCell, rc::Rc, time::Duration};
 
 use kclvm_ast::ast;
 use kclvm_config::{
     modfile::get_vendor_home,
     settings::{SettingsFile, SettingsPathBuf},
 };
-use kclvm_error::{Diagnostic, Handler};
+use kclvm_error::{ColorMode, Diagnostic, DiagnosticFormat, Handler, Locale, WarningKind};
 #[cfg(not(target_arch = "wasm32"))]
-use kclvm_runtime::kclvm_plugin_init;
+use kclvm_runtime::plugin_handler_from_ptr;
 #[cfg(feature = "llvm")]
 use kclvm_runtime::FFIRunOptions;
-use kclvm_runtime::{Context, PanicInfo, RuntimePanicRecord};
-#[cfg(target_arch = "wasm32")]
-use once_cell::sync::Lazy;
+use kclvm_runtime::{
+    catch_runtime_panic, get_plugin_fns, log_handler_from_ptr,
+    val_plan::{KeyOrder, PlanFormat, ProvenanceMode, DEFAULT_FILENAME_TEMPLATE},
+    Context, LogRecord, PanicInfo, PluginFunctions, RuntimeLimits, RuntimePanicRecord,
+    SandboxPolicy,
+};
 use serde::{Deserialize, Serialize};
 use std::ffi::OsStr;
+use std::io::Write;
 use std::os::raw::c_char;
+use std::panic::{AssertUnwindSafe, UnwindSafe};
+use std::path::{Component, Path};
+use std::str::FromStr;
+use std::sync::Arc;
+
+use crate::options::{env_option_args, json_option_arg, load_option_file, OptionProvider};
+use crate::profile::{PackageProfile, ProfileResult, RUN_PHASE};
+use crate::progress::{log_listener, ProgressListener};
 
 const RESULT_SIZE: usize = 2048 * 2048;
 const KCL_DEBUG_ERROR_ENV_VAR: &str = "KCL_DEBUG_ERROR";
@@ -68,10 +80,196 @@ pub struct ExecProgramArgs {
     /// plugin_agent is the address of plugin.
     #[serde(skip)]
     pub plugin_agent: u64,
+    /// The native plugin functions of the execution, which are called in KCL as the
+    /// functions of the `kcl_plugin` modules. The program is always executed by the
+    /// evaluator when they are set.
+    #[serde(skip)]
+    pub plugin_functions: PluginFunctions,
+    /// Whether to fall back to the native plugin functions registered globally by
+    /// `register_plugin_fn` for the names not in `plugin_functions`. The program is
+    /// always executed by the evaluator when it is set.
+    #[serde(skip)]
+    pub use_global_plugin_fns: bool,
     /// fast_eval denotes directly executing at the AST level to obtain
     /// the result without any form of compilation.
     #[serde(skip)]
     pub fast_eval: bool,
+    /// The warning kinds reported as errors which fail the execution.
+    #[serde(default)]
+    pub warnings_as_errors: Vec<WarningKind>,
+    /// The output format of the compile diagnostics, default is the human readable text.
+    #[serde(default)]
+    pub diagnostic_format: DiagnosticFormat,
+    /// The maximum number of the reported compile errors, and the identical errors in
+    /// different files are grouped when it is set.
+    #[serde(default)]
+    pub max_errors: Option<usize>,
+    /// The locale of the compile diagnostics e.g., `zh-CN`.
+    #[serde(default)]
+    pub locale: Locale,
+    /// The color mode of the human readable compile diagnostics, and the colors are
+    /// disabled in the auto mode when the `NO_COLOR` environment variable is set.
+    #[serde(default)]
+    pub color: ColorMode,
+    /// The path prefix mappings `(from, to)`, which remap the absolute build paths in
+    /// the diagnostics, the artifacts and the `__META__` runtime information.
+    #[serde(default)]
+    pub path_prefix_map: Vec<(String, String)>,
+    /// Whether to promote the int operations overflowing 64 bits to the arbitrary-precision
+    /// ints instead of raising the overflow errors.
+    #[serde(default)]
+    pub big_int: bool,
+    /// Round the float values to the decimal places with the rounding half to even
+    /// in the JSON and YAML outputs.
+    #[serde(default)]
+    pub decimal_places: Option<u32>,
+    /// The evaluation timeout in milliseconds. The execution limits are enforced by the
+    /// evaluator and abort the evaluation with an evaluation error including the backtrace.
+    #[serde(default)]
+    pub timeout_ms: Option<u64>,
+    /// The maximum depth of the lambda, schema and rule calls.
+    #[serde(default)]
+    pub max_call_depth: Option<usize>,
+    /// The maximum number of the list and dict items allocated in the evaluation.
+    #[serde(default)]
+    pub max_allocations: Option<usize>,
+    /// The sandbox policy of the file, environment and plugin accesses for the services
+    /// executing the untrusted KCL code. The program is always executed by the evaluator
+    /// when the policy is enabled.
+    #[serde(default)]
+    pub sandbox: SandboxPolicy,
+    /// The seed of the random values generated by the `random` and `uuid` system modules
+    /// to make the outputs reproducible.
+    #[serde(default)]
+    pub random_seed: Option<u64>,
+    /// Whether to emit the secret values as is instead of masking them with `******`.
+    #[serde(default)]
+    pub show_secrets: bool,
+    /// Whether to move the secret values to a separate secrets document at the end of
+    /// the outputs instead of masking them.
+    #[serde(default)]
+    pub externalize_secrets: bool,
+    /// The key paths e.g., `app.password` of the secret values besides the schema
+    /// attributes with the `@secret` decorator. The program is always executed by the
+    /// evaluator when the secret paths are set.
+    #[serde(default)]
+    pub secret_paths: Vec<String>,
+    /// The format of the `yaml_result`, which is one of YAML, JSON, TOML and HCL, and
+    /// TOML and HCL require the program output to be a single dict document.
+    #[serde(default)]
+    pub output_format: PlanFormat,
+    /// The output directory, and each document of the YAML stream or each top-level
+    /// config is written to its own file under the directory instead of the JSON and
+    /// YAML results. The program is always executed by the evaluator when it is set.
+    #[serde(default)]
+    pub output_dir: Option<String>,
+    /// The filename template of the files under the output directory, default is
+    /// `{key}.{ext}`. The placeholders are the top-level key or the document index
+    /// `{key}`, the document index `{index}`, the schema name `{schema}`, the extension
+    /// `{ext}` of the output format and the attribute paths e.g., `{metadata.name}`.
+    #[serde(default)]
+    pub filename_template: Option<String>,
+    /// The key order strategy of the JSON and YAML results, which is the declaration
+    /// order, the alphabetical order or the schema attribute declaration order.
+    #[serde(default)]
+    pub key_order: KeyOrder,
+    /// Annotate the planned schema instances with the schema type, source file and line
+    /// as the YAML comments or the sidecar map in the `provenance_result`. The program
+    /// is always executed by the evaluator in the sidecar mode.
+    #[serde(default)]
+    pub provenance: ProvenanceMode,
+    /// The execution backend of the program, default is to compile the program to the
+    /// native lib when the `llvm` feature is enabled, otherwise to interpret it.
+    #[serde(default)]
+    pub backend: Backend,
+    /// Whether to compile all the packages without reading and writing the compiled
+    /// object cache of the native backend.
+    #[serde(default)]
+    pub no_cache: bool,
+    /// The target triple of the artifact built by `build_program` e.g., `x86_64-unknown-linux-gnu`,
+    /// default is the host triple. The artifacts of the other targets can't be run on the host.
+    #[serde(default)]
+    pub target: Option<String>,
+    /// Whether to record the source positions of the log messages in the log records.
+    #[serde(default)]
+    pub log_positions: bool,
+    /// log_agent is the address of the log handler, which receives the JSON string of
+    /// each log record as soon as it is logged.
+    #[serde(skip)]
+    pub log_agent: u64,
+    /// Whether to profile the execution phases, and the profile is returned in the result.
+    #[serde(default)]
+    pub profile: bool,
+    /// The directory to dump the intermediate representations selected by the dump
+    /// options, which is created if not exists.
+    #[serde(default)]
+    pub dump_dir: Option<String>,
+    /// Whether to dump the resolved AST and the node types in JSON to `ast.json`.
+    #[serde(default)]
+    pub dump_ast: bool,
+    /// Whether to dump the LLVM IR of each package to `<pkgpath>.ll`, which is only
+    /// supported by the native backend and compiles all the packages without the cache.
+    #[serde(default)]
+    pub dump_llvm_ir: bool,
+    /// Whether to dump the linked library to `main<lib suffix>` e.g., `main.so`, which
+    /// is only supported by the native backend.
+    #[serde(default)]
+    pub dump_lib: bool,
+    /// The structured top-level options, which are set after the `-D` options in `args`
+    /// and override the ones with the same names.
+    #[serde(default)]
+    pub options: BTreeMap<String, serde_json::Value>,
+    /// The YAML or JSON files of the top-level options, and each top-level key of the
+    /// dict in the file is an option.
+    #[serde(default)]
+    pub option_files: Vec<String>,
+    /// The prefix of the environment variables of the top-level options e.g., the
+    /// environment variable `KCL_OPT_replicas=3` sets the option `replicas` with the
+    /// prefix `KCL_OPT_`.
+    #[serde(default)]
+    pub option_env_prefix: Option<String>,
+    /// The providers of the top-level options, which are set in order after the
+    /// environment variables.
+    #[serde(skip)]
+    pub option_providers: Vec<Arc<dyn OptionProvider>>,
+    /// The listener of the progress events e.g., the phases, the diagnostics and the
+    /// logs, which are emitted during the execution.
+    #[serde(skip)]
+    pub progress_listener: Option<Arc<dyn ProgressListener>>,
+}
+
+/// The execution backend of the KCL program.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Backend {
+    /// The native backend when the `llvm` feature is enabled, and the interpreter
+    /// backend for the options only supported by it e.g., the sandbox policy. It is
+    /// always the interpreter backend on the wasm32 target.
+    #[default]
+    Auto,
+    /// Evaluate the program at the AST level without any form of compilation, which
+    /// does not require the LLVM toolchain at runtime.
+    Interpreter,
+    /// Compile the program to the native lib with LLVM, link and run it, which requires
+    /// the `llvm` feature and is not supported on the wasm32 target, where the libs
+    /// can't be loaded.
+    Native,
+}
+
+impl FromStr for Backend {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto" => Ok(Backend::Auto),
+            "interpreter" => Ok(Backend::Interpreter),
+            "native" => Ok(Backend::Native),
+            _ => bail!(
+                "invalid backend '{}', expected one of 'auto', 'interpreter' and 'native'",
+                s
+            ),
+        }
+    }
 }
 
 impl ExecProgramArgs {
@@ -100,9 +298,20 @@ impl ExecProgramArgs {
 #[derive(Serialize, Deserialize, Debug, Default, Clone)]
 pub struct ExecProgramResult {
     pub json_result: String,
+    /// The result in the output format of [ExecProgramArgs], default is YAML.
     pub yaml_result: String,
+    /// The JSON map from the JSONPath of each planned schema instance to its provenance,
+    /// which is only set in the sidecar provenance mode.
+    pub provenance_result: String,
     pub log_message: String,
+    /// The log records of the `print` calls in order.
+    pub log_records: Vec<LogRecord>,
     pub err_message: String,
+    /// The structured runtime error of the failed execution e.g., the position and the
+    /// message of the failed assertion, which is [None] when the program succeeds.
+    pub panic_info: Option<PanicInfo>,
+    /// The profile of the execution phases, which is only set in the profiling mode.
+    pub profile: Option<ProfileResult>,
 }
 
 pub trait MapErrorResult {
@@ -153,6 +362,41 @@ impl ExecProgramArgs {
         serde_json::ser::to_string(self).unwrap()
     }
 
+    /// Returns the top-level options in the order of the precedence, which is the option
+    /// files, the environment variables, the option providers, the `-D` options and the
+    /// structured options, and the latter ones override the former ones with the same
+    /// names. The option values except the `-D` options are the JSON strings.
+    pub fn option_args(&self) -> Result<Vec<ast::Argument>> {
+        let mut option_args = vec![];
+        for file in &self.option_files {
+            option_args.extend(load_option_file(file)?);
+        }
+        if let Some(prefix) = &self.option_env_prefix {
+            option_args.extend(env_option_args(prefix));
+        }
+        for provider in &self.option_providers {
+            option_args.extend(
+                provider
+                    .options()?
+                    .into_iter()
+                    .map(|(name, value)| json_option_arg(name, &value)),
+            );
+        }
+        option_args.extend(self.args.iter().cloned());
+        option_args.extend(
+            self.options
+                .iter()
+                .map(|(name, value)| json_option_arg(name.clone(), value)),
+        );
+        Ok(option_args)
+    }
+
+    /// Whether the program calls the native plugin functions of the execution or the
+    /// global registry.
+    pub fn has_native_plugin_fns(&self) -> bool {
+        !self.plugin_functions.is_empty() || self.use_global_plugin_fns
+    }
+
     /// Get the input file list.
     pub fn get_files(&self) -> Vec<&str> {
         self.k_filename_list.iter().map(|s| s.as_str()).collect()
@@ -165,7 +409,9 @@ impl ExecProgramArgs {
             vendor_dirs: vec![get_vendor_home()],
             package_maps: self.get_package_maps_from_external_pkg(),
             k_code_list: self.k_code_list.clone(),
-            load_plugins: self.plugin_agent > 0,
+            load_plugins: self.plugin_agent > 0 || self.has_native_plugin_fns(),
+            path_prefix_map: self.path_prefix_map.clone(),
+            max_errors: self.max_errors,
             ..Default::default()
         }
     }
@@ -187,12 +433,64 @@ impl TryFrom<SettingsFile> for ExecProgramArgs {
             args.sort_keys = cli_configs.sort_keys.unwrap_or_default();
             args.show_hidden = cli_configs.show_hidden.unwrap_or_default();
             args.fast_eval = cli_configs.fast_eval.unwrap_or_default();
+            args.big_int = cli_configs.big_int.unwrap_or_default();
+            args.no_cache = cli_configs.no_cache.unwrap_or_default();
+            args.profile = cli_configs.profile.unwrap_or_default();
+            args.dump_ast = cli_configs.dump_ast.unwrap_or_default();
+            args.dump_llvm_ir = cli_configs.dump_llvm_ir.unwrap_or_default();
+            args.dump_lib = cli_configs.dump_lib.unwrap_or_default();
             args.include_schema_type_path =
                 cli_configs.include_schema_type_path.unwrap_or_default();
             for override_str in cli_configs.overrides.unwrap_or_default() {
                 args.overrides.push(override_str);
             }
             args.path_selector = cli_configs.path_selector.unwrap_or_default();
+            args.max_errors = cli_configs.max_errors;
+            args.decimal_places = cli_configs.decimal_places;
+            args.timeout_ms = cli_configs.timeout_ms;
+            args.max_call_depth = cli_configs.max_call_depth;
+            args.max_allocations = cli_configs.max_allocations;
+            args.random_seed = cli_configs.random_seed;
+            args.show_secrets = cli_configs.show_secrets.unwrap_or_default();
+            args.externalize_secrets = cli_configs.externalize_secrets.unwrap_or_default();
+            args.secret_paths = cli_configs.secret_paths.unwrap_or_default();
+            args.output_dir = cli_configs.output_dir;
+            args.filename_template = cli_configs.filename_template;
+            args.dump_dir = cli_configs.dump_dir;
+            args.option_files = cli_configs.option_files.unwrap_or_default();
+            args.option_env_prefix = cli_configs.option_env_prefix;
+            if let Some(locale) = cli_configs.locale {
+                args.locale = locale.parse()?;
+            }
+            if let Some(color) = cli_configs.color {
+                args.color = color.parse()?;
+            }
+            if let Some(format) = cli_configs.diagnostic_format {
+                args.diagnostic_format = format.parse()?;
+            }
+            if let Some(format) = cli_configs.output_format {
+                args.output_format = format.parse()?;
+            }
+            if let Some(key_order) = cli_configs.key_order {
+                args.key_order = key_order.parse()?;
+            }
+            if let Some(provenance) = cli_configs.provenance {
+                args.provenance = provenance.parse()?;
+            }
+            if let Some(backend) = cli_configs.backend {
+                args.backend = backend.parse()?;
+            }
+            for mapping in cli_configs.path_prefix_map.unwrap_or_default() {
+                match mapping.split_once('=') {
+                    Some((from, to)) => args
+                        .path_prefix_map
+                        .push((from.to_string(), to.to_string())),
+                    None => bail!(
+                        "invalid path prefix mapping '{}', expected the format 'FROM=TO'",
+                        mapping
+                    ),
+                }
+            }
             args.set_external_pkg_from_package_maps(
                 cli_configs.package_maps.unwrap_or(HashMap::default()),
             )
@@ -226,7 +524,8 @@ pub struct RunnerOptions {
 
 #[cfg(feature = "llvm")]
 /// A public struct named [Artifact] which wraps around the native library [libloading::Library].
-pub struct Artifact(libloading::Library, String);
+/// The library built for another target is not loaded.
+pub struct Artifact(Option<libloading::Library>, String);
 #[cfg(not(feature = "llvm"))]
 pub struct Artifact(String);
 
@@ -240,8 +539,11 @@ impl ProgramRunner for Artifact {
     fn run(&self, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         #[cfg(feature = "llvm")]
         unsafe {
-            LibRunner::lib_kclvm_plugin_init(&self.0, args.plugin_agent)?;
-            LibRunner::lib_kcl_run(&self.0, args)
+            let lib = self.0.as_ref().ok_or(anyhow::anyhow!(
+                "the artifact '{}' is built for another target and can't be run on the host",
+                self.1
+            ))?;
+            LibRunner::lib_kcl_run(lib, args, args.plugin_agent)
         }
         #[cfg(not(feature = "llvm"))]
         {
@@ -257,7 +559,14 @@ impl Artifact {
     pub fn from_path<P: AsRef<OsStr>>(path: P) -> Result<Self> {
         let path = path.as_ref().to_str().unwrap().to_string();
         let lib = unsafe { libloading::Library::new(&path)? };
-        Ok(Self(lib, path))
+        Ok(Self(Some(lib), path))
+    }
+
+    /// Returns the artifact of the library built for another target, which is not loaded.
+    #[inline]
+    pub fn from_target_path<P: AsRef<OsStr>>(path: P) -> Self {
+        let path = path.as_ref().to_str().unwrap().to_string();
+        Self(None, path)
     }
 
     #[inline]
@@ -274,6 +583,13 @@ impl Artifact {
         Ok(Self(path))
     }
 
+    /// Returns the artifact of the library built for another target, which is not loaded.
+    #[inline]
+    pub fn from_target_path<P: AsRef<OsStr>>(path: P) -> Self {
+        let path = path.as_ref().to_str().unwrap().to_string();
+        Self(path)
+    }
+
     #[inline]
     pub fn get_path(&self) -> &String {
         &self.0
@@ -298,51 +614,19 @@ impl LibRunner {
     pub fn run(&self, lib_path: &str, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
         unsafe {
             let lib = libloading::Library::new(std::path::PathBuf::from(lib_path).canonicalize()?)?;
-            Self::lib_kclvm_plugin_init(&lib, self.opts.plugin_agent_ptr)?;
-            Self::lib_kcl_run(&lib, args)
+            Self::lib_kcl_run(&lib, args, self.opts.plugin_agent_ptr)
         }
     }
 }
 
 #[cfg(feature = "llvm")]
 impl LibRunner {
-    unsafe fn lib_kclvm_plugin_init(
-        lib: &libloading::Library,
-        plugin_method_ptr: u64,
-    ) -> Result<()> {
-        // get kclvm_plugin_init
-        let kclvm_plugin_init: libloading::Symbol<
-            unsafe extern "C" fn(
-                fn_ptr: extern "C" fn(
-                    method: *const c_char,
-                    args_json: *const c_char,
-                    kwargs_json: *const c_char,
-                ) -> *const c_char,
-            ),
-        > = lib.get(b"kclvm_plugin_init")?;
-
-        // get plugin_method
-        let plugin_method_ptr = plugin_method_ptr;
-        let plugin_method_ptr = (plugin_method_ptr as *const u64) as *const ()
-            as *const extern "C" fn(
-                method: *const c_char,
-                args: *const c_char,
-                kwargs: *const c_char,
-            ) -> *const c_char;
-        let plugin_method: extern "C" fn(
-            method: *const c_char,
-            args: *const c_char,
-            kwargs: *const c_char,
-        ) -> *const c_char = std::mem::transmute(plugin_method_ptr);
-
-        // register plugin agent
-        kclvm_plugin_init(plugin_method);
-        Ok(())
-    }
-
+    /// Run the library with the plugin agent, which is set to the runtime context of the
+    /// program instead of the global plugin agent of the library.
     unsafe fn lib_kcl_run(
         lib: &libloading::Library,
         args: &ExecProgramArgs,
+        plugin_agent: u64,
     ) -> Result<ExecProgramResult> {
         let kcl_run: libloading::Symbol<
             unsafe extern "C" fn(
@@ -360,6 +644,8 @@ impl LibRunner {
                 err_buffer: *mut kclvm_char_t,
                 log_buffer_len: *mut kclvm_size_t,
                 log_buffer: *mut kclvm_char_t,
+                log_records_buffer_len: *mut kclvm_size_t,
+                log_records_buffer: *mut kclvm_char_t,
             ) -> kclvm_size_t,
         > = lib.get(b"_kcl_run")?;
 
@@ -367,11 +653,11 @@ impl LibRunner {
         let kclvm_main: libloading::Symbol<u64> = lib.get(b"kclvm_main")?;
         let kclvm_main_ptr = kclvm_main.into_raw().into_raw() as u64;
 
+        let option_args = args.option_args()?;
         // CLI configs option len
-        let option_len = args.args.len() as kclvm_size_t;
+        let option_len = option_args.len() as kclvm_size_t;
         // CLI configs option keys
-        let cstr_argv: Vec<_> = args
-            .args
+        let cstr_argv: Vec<_> = option_args
             .iter()
             .map(|arg| std::ffi::CString::new(arg.name.as_str()).unwrap())
             .collect();
@@ -382,8 +668,7 @@ impl LibRunner {
         p_argv.push(std::ptr::null());
         let option_keys = p_argv.as_ptr();
         // CLI configs option values
-        let cstr_argv: Vec<_> = args
-            .args
+        let cstr_argv: Vec<_> = option_args
             .iter()
             .map(|arg| std::ffi::CString::new(arg.value.as_str()).unwrap())
             .collect();
@@ -415,11 +700,23 @@ impl LibRunner {
             show_hidden: args.show_hidden as i32,
             debug_mode: args.debug,
             include_schema_type_path: args.include_schema_type_path as i32,
+            big_int: args.big_int as i32,
+            decimal_places: args.decimal_places.map_or(-1, |v| v as i32),
+            random_seed: args.random_seed.map_or(-1, |v| v as i64),
+            show_secrets: args.show_secrets as i32,
+            externalize_secrets: args.externalize_secrets as i32,
+            output_format: args.output_format as i32,
+            key_order: args.key_order as i32,
+            provenance: args.provenance as i32,
+            plugin_agent,
+            log_positions: args.log_positions as i32,
+            log_agent: args.log_agent,
         };
         let mut json_buffer = Buffer::make();
         let mut yaml_buffer = Buffer::make();
         let mut log_buffer = Buffer::make();
         let mut err_buffer = Buffer::make();
+        let mut log_records_buffer = Buffer::make();
         // Input the main function, options and return the exec result
         // including JSON and YAML result, log message and error message.
         kcl_run(
@@ -437,14 +734,21 @@ impl LibRunner {
             err_buffer.mut_ptr(),
             log_buffer.mut_len(),
             log_buffer.mut_ptr(),
+            log_records_buffer.mut_len(),
+            log_records_buffer.mut_ptr(),
         );
         // Convert runtime result to ExecProgramResult
         let mut result = ExecProgramResult {
             yaml_result: yaml_buffer.to_string()?,
             json_result: json_buffer.to_string()?,
             log_message: log_buffer.to_string()?,
+            log_records: serde_json::from_str(&log_records_buffer.to_string()?).unwrap_or_default(),
             err_message: err_buffer.to_string()?,
+            ..Default::default()
         };
+        if !result.err_message.is_empty() {
+            result.panic_info = Some(PanicInfo::from(result.err_message.as_str()));
+        }
         // Wrap runtime JSON Panic error string into diagnostic style string.
         if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
             result.err_message = match Handler::default()
@@ -462,33 +766,10 @@ impl LibRunner {
 }
 
 thread_local! {
+    /// The panic record of the last program executed on the current thread.
     pub static KCL_RUNTIME_PANIC_RECORD: RefCell<RuntimePanicRecord> = RefCell::new(RuntimePanicRecord::default())
 }
 
-#[cfg(target_arch = "wasm32")]
-static ONCE_PANIC_HOOK: Lazy<()> = Lazy::new(|| {
-    std::panic::set_hook(Box::new(|info: &std::panic::PanicInfo| {
-        KCL_RUNTIME_PANIC_RECORD.with(|record| {
-            let mut record = record.borrow_mut();
-            record.kcl_panic_info = true;
-            record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-                (*s).clone()
-            } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                (*s).clone()
-            } else {
-                "unknown runtime error".to_string()
-            };
-            if let Some(location) = info.location() {
-                record.rust_file = location.file().to_string();
-                record.rust_line = location.line() as i32;
-                record.rust_col = location.column() as i32;
-            }
-        })
-    }));
-});
-
 pub struct FastRunner {
     opts: RunnerOptions,
 }
@@ -501,59 +782,80 @@ impl FastRunner {
         }
     }
 
-    /// Run kcl library with exec arguments.
+    /// Run kcl library with exec arguments, and the plan result is split into the files
+    /// under the output directory when it is set.
     pub fn run(&self, program: &ast::Program, args: &ExecProgramArgs) -> Result<ExecProgramResult> {
-        let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)));
-        let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
-        #[cfg(target_arch = "wasm32")]
-        // Ensure the panic hook is set (this will only happen once) for the WASM target,
-        // because it is single threaded.
-        Lazy::force(&ONCE_PANIC_HOOK);
-        #[cfg(not(target_arch = "wasm32"))]
-        let prev_hook = std::panic::take_hook();
-        #[cfg(not(target_arch = "wasm32"))]
-        std::panic::set_hook(Box::new(|info: &std::panic::PanicInfo| {
-            KCL_RUNTIME_PANIC_RECORD.with(|record| {
-                let mut record = record.borrow_mut();
-                record.kcl_panic_info = true;
-                record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                    s.to_string()
-                } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-                    (*s).clone()
-                } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                    (*s).clone()
-                } else {
-                    "unknown runtime error".to_string()
-                };
-                if let Some(location) = info.location() {
-                    record.rust_file = location.file().to_string();
-                    record.rust_line = location.line() as i32;
-                    record.rust_col = location.column() as i32;
-                }
-            })
-        }));
-        let evaluator_result = std::panic::catch_unwind(|| {
-            if self.opts.plugin_agent_ptr > 0 {
-                #[cfg(not(target_arch = "wasm32"))]
-                unsafe {
-                    let plugin_method: extern "C" fn(
-                        method: *const c_char,
-                        args: *const c_char,
-                        kwargs: *const c_char,
-                    ) -> *const c_char = std::mem::transmute(self.opts.plugin_agent_ptr);
-                    kclvm_plugin_init(plugin_method);
-                }
+        match &args.output_dir {
+            Some(output_dir) => {
+                let template = args
+                    .filename_template
+                    .as_deref()
+                    .unwrap_or(DEFAULT_FILENAME_TEMPLATE);
+                self.run_with(program, args, move |evaluator| {
+                    let files = evaluator.run_to_files(template)?;
+                    write_plan_files(Path::new(output_dir), &files)?;
+                    Ok((String::new(), String::new()))
+                })
             }
-            evaluator.run()
-        });
+            None => self.run_with(program, args, |evaluator| evaluator.run()),
+        }
+    }
+
+    /// Run kcl library with exec arguments and write the planned JSON or YAML documents
+    /// to the writer one by one, thus the JSON and YAML results in the returned
+    /// [ExecProgramResult] are always empty.
+    pub fn run_to_writer(
+        &self,
+        program: &ast::Program,
+        args: &ExecProgramArgs,
+        format: PlanFormat,
+        w: &mut dyn Write,
+    ) -> Result<ExecProgramResult> {
+        let w = AssertUnwindSafe(w);
+        self.run_with(program, args, move |evaluator| {
+            evaluator.run_to_writer(format, w.0)?;
+            Ok((String::new(), String::new()))
+        })
+    }
+
+    fn run_with<F>(
+        &self,
+        program: &ast::Program,
+        args: &ExecProgramArgs,
+        run: F,
+    ) -> Result<ExecProgramResult>
+    where
+        F: FnOnce(&Evaluator) -> Result<(String, String)> + UnwindSafe,
+    {
+        let ctx = Rc::new(RefCell::new(args_to_ctx(program, args)?));
         #[cfg(not(target_arch = "wasm32"))]
-        std::panic::set_hook(prev_hook);
-        KCL_RUNTIME_PANIC_RECORD.with(|record| {
-            let record = record.borrow();
-            ctx.borrow_mut().set_panic_info(&record);
-        });
+        {
+            // SAFETY: the plugin agent address is given by the FFI callers e.g., the
+            // language SDKs, which pass the address of their plugin agent function.
+            ctx.borrow_mut().plugin_handler =
+                unsafe { plugin_handler_from_ptr(self.opts.plugin_agent_ptr) };
+        }
+        let evaluator = Evaluator::new_with_runtime_ctx(program, ctx.clone());
+        let (evaluator_result, record) = catch_runtime_panic(|| run(&evaluator));
+        ctx.borrow_mut().set_panic_info(&record);
+        KCL_RUNTIME_PANIC_RECORD.with(|last_record| *last_record.borrow_mut() = record);
         let mut result = ExecProgramResult {
+            profile: args.profile.then(|| ProfileResult {
+                packages: evaluator
+                    .pkg_eval_times
+                    .borrow()
+                    .iter()
+                    .map(|(pkgpath, time)| PackageProfile {
+                        pkgpath: pkgpath.clone(),
+                        phase: RUN_PHASE.to_string(),
+                        wall_time_ms: time.as_secs_f64() * 1000.0,
+                    })
+                    .collect(),
+                ..Default::default()
+            }),
             log_message: ctx.borrow().log_message.clone(),
+            log_records: ctx.borrow().log_records.clone(),
+            provenance_result: ctx.borrow().provenance_result.clone(),
             ..Default::default()
         };
         let is_err = evaluator_result.is_err();
@@ -577,6 +879,9 @@ impl FastRunner {
                 };
             }
         }
+        if !result.err_message.is_empty() {
+            result.panic_info = Some(PanicInfo::from(result.err_message.as_str()));
+        }
         // Wrap runtime JSON Panic error string into diagnostic style string.
         if !result.err_message.is_empty() && std::env::var(KCL_DEBUG_ERROR_ENV_VAR).is_err() {
             result.err_message = match Handler::default()
@@ -596,21 +901,74 @@ impl FastRunner {
     }
 }
 
-pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Context {
+/// Write the planned files under the output directory, and the file names must be
+/// the relative paths in the directory.
+fn write_plan_files(output_dir: &Path, files: &[(String, String)]) -> Result<()> {
+    for (name, content) in files {
+        let name_path = Path::new(name);
+        if name.is_empty()
+            || !name_path
+                .components()
+                .all(|c| matches!(c, Component::Normal(_)))
+        {
+            bail!(
+                "invalid output file name '{}', expected a relative path in the output directory",
+                name
+            );
+        }
+        let path = output_dir.join(name_path);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(&path, format!("{content}\n"))?;
+    }
+    Ok(())
+}
+
+pub(crate) fn args_to_ctx(program: &ast::Program, args: &ExecProgramArgs) -> Result<Context> {
     let mut ctx = Context::new();
     ctx.cfg.strict_range_check = args.strict_range_check;
     ctx.cfg.debug_mode = args.debug != 0;
+    ctx.cfg.big_int = args.big_int;
+    ctx.cfg.limits = RuntimeLimits {
+        timeout: args.timeout_ms.map(Duration::from_millis),
+        max_call_depth: args.max_call_depth,
+        max_allocations: args.max_allocations,
+    };
+    // The network, the file writes and the environment variables are not available
+    // on the wasm32 target.
+    ctx.cfg.sandbox = args.sandbox.clone().with_target_defaults();
+    ctx.cfg.random_seed = args.random_seed;
+    ctx.cfg.log_positions = args.log_positions;
+    ctx.cfg.profile = args.profile;
+    // SAFETY: the log agent address is given by the FFI callers e.g., the language
+    // SDKs, which pass the address of their log agent function.
+    ctx.log_handler = unsafe { log_handler_from_ptr(args.log_agent) };
+    ctx.log_listener = log_listener(&args.progress_listener);
+    // The plugin functions of the execution take precedence over the global ones.
+    if args.use_global_plugin_fns {
+        ctx.plugin_functions = get_plugin_fns();
+    }
+    ctx.plugin_functions
+        .extend(args.plugin_functions.clone().into_inner());
     ctx.plan_opts.disable_none = args.disable_none;
     ctx.plan_opts.show_hidden = args.show_hidden;
     ctx.plan_opts.sort_keys = args.sort_keys;
     ctx.plan_opts.include_schema_type_path = args.include_schema_type_path;
     ctx.plan_opts.query_paths = args.path_selector.clone();
-    for arg in &args.args {
+    ctx.plan_opts.decimal_places = args.decimal_places;
+    ctx.plan_opts.show_secrets = args.show_secrets;
+    ctx.plan_opts.externalize_secrets = args.externalize_secrets;
+    ctx.plan_opts.secret_paths = args.secret_paths.clone();
+    ctx.plan_opts.output_format = args.output_format;
+    ctx.plan_opts.key_order = args.key_order;
+    ctx.plan_opts.provenance = args.provenance;
+    for arg in &args.option_args()? {
         ctx.builtin_option_init(&arg.name, &arg.value);
     }
     ctx.set_kcl_workdir(&args.work_dir.clone().unwrap_or_default());
     ctx.set_kcl_module_path(&program.root);
-    ctx
+    Ok(ctx)
 }
 
 #[repr(C)]
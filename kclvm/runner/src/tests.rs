@@ -1,15 +1,26 @@
 #[cfg(feature = "llvm")]
 use crate::assembler::clean_path;
 #[cfg(feature = "llvm")]
+use crate::assembler::object_cache_file;
+#[cfg(feature = "llvm")]
 use crate::assembler::KclvmAssembler;
 #[cfg(feature = "llvm")]
 use crate::assembler::KclvmLibAssembler;
 #[cfg(feature = "llvm")]
 use crate::assembler::LibAssembler;
+use crate::diff;
+use crate::dump;
 use crate::exec_program;
+use crate::linker::Command;
+use crate::options::{list_option_decls, OptionDecl, OptionProvider};
+use crate::profile::{PackageProfile, PhaseProfile, ProfileResult};
+use crate::progress::{ProgressEvent, ProgressListener};
 #[cfg(feature = "llvm")]
 use crate::temp_file;
-use crate::{execute, runner::ExecProgramArgs};
+use crate::watch_program;
+use crate::ArtifactInfo;
+use crate::{exec_program_to_writer, PlanFormat};
+use crate::{execute, runner::ExecProgramArgs, Backend};
 #[cfg(feature = "llvm")]
 use anyhow::Context;
 use anyhow::Result;
@@ -17,22 +28,27 @@ use kclvm_ast::ast::{Module, Program};
 #[cfg(feature = "llvm")]
 use kclvm_compiler::codegen::OBJECT_FILE_SUFFIX;
 use kclvm_config::settings::load_file;
+use kclvm_error::format::FormattedDiagnostics;
+use kclvm_error::{ColorMode, DiagnosticFormat, Locale};
 use kclvm_parser::load_program;
 use kclvm_parser::ParseSession;
-#[cfg(feature = "llvm")]
+use kclvm_runtime::val_plan::ProvenanceMode;
+use kclvm_runtime::LogRecord;
 use kclvm_sema::resolver::resolve_program;
 use kclvm_utils::path::PathPrefix;
-use serde_json::Value;
+use serde_json::{json, Value};
 #[cfg(feature = "llvm")]
 use std::fs::create_dir_all;
+use std::ops::ControlFlow;
+use std::os::raw::c_char;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::sync::Mutex;
 use std::sync::RwLock;
 use std::{
     collections::HashMap,
     fs::{self, File},
 };
-#[cfg(feature = "llvm")]
 use tempfile::tempdir;
 use uuid::Uuid;
 use walkdir::WalkDir;
@@ -244,7 +260,6 @@ fn gen_assembler(entry_file: &str, test_kcl_case_path: &str) -> KclvmAssembler {
         scope,
         entry_file.to_string(),
         KclvmLibAssembler::LLVM,
-        HashMap::new(),
     )
 }
 
@@ -263,16 +278,14 @@ fn gen_libs_for_test(entry_file: &str, test_kcl_case_path: &str) {
 
     assert_eq!(lib_paths.len(), expected_pkg_paths.len());
 
-    for pkg_path in &expected_pkg_paths {
-        assert_eq!(pkg_path.exists(), true);
+    // The object files of the packages may be reused from the object cache.
+    for lib_path in &lib_paths {
+        assert_eq!(Path::new(lib_path).exists(), true);
     }
 
-    let tmp_main_lib_path =
-        fs::canonicalize(format!("{}{}", entry_file, OBJECT_FILE_SUFFIX)).unwrap();
-    assert_eq!(tmp_main_lib_path.exists(), true);
-
-    clean_path(tmp_main_lib_path.to_str().unwrap()).unwrap();
-    assert_eq!(tmp_main_lib_path.exists(), false);
+    let tmp_main_lib_path = format!("{}{}", entry_file, OBJECT_FILE_SUFFIX);
+    clean_path(&tmp_main_lib_path).unwrap();
+    assert_eq!(Path::new(&tmp_main_lib_path).exists(), false);
 }
 
 #[cfg(feature = "llvm")]
@@ -375,6 +388,57 @@ fn test_gen_libs() {
     }
 }
 
+#[test]
+#[cfg(feature = "llvm")]
+fn test_gen_libs_with_object_cache() {
+    let kcl_path = gen_full_path(
+        Path::new(&test_case_path())
+            .join("multi_file_compilation")
+            .join("import_abs_path")
+            .join("app-main")
+            .join(KCL_FILE_NAME)
+            .display()
+            .to_string(),
+    )
+    .unwrap();
+    let temp_dir = tempdir().unwrap();
+    let temp_entry_file = temp_file(temp_dir.path().to_str().unwrap()).unwrap();
+
+    let assembler = gen_assembler(&temp_entry_file, &kcl_path);
+    let cache_dir = assembler.construct_cache_dir(&parse_program(&kcl_path).root);
+    let mut hashes = HashMap::new();
+    let hash = assembler
+        .pkg_content_hash(MAIN_PKG_NAME, "", &mut hashes)
+        .unwrap();
+    // The imported packages are hashed along with the main package.
+    assert!(hashes.len() > 1);
+    assert_ne!(
+        hash,
+        assembler
+            .pkg_content_hash(MAIN_PKG_NAME, "work_dir", &mut HashMap::new())
+            .unwrap()
+    );
+    let cached_path = object_cache_file(&cache_dir, &hash, OBJECT_FILE_SUFFIX);
+    let lib_paths = assembler.gen_libs(&ExecProgramArgs::default()).unwrap();
+    assert!(cached_path.exists());
+    // The cached object files are linked directly.
+    assert!(lib_paths.contains(&cached_path.display().to_string()));
+
+    // The cached object files are neither read nor written with the no_cache option.
+    let mut args = ExecProgramArgs::default();
+    args.no_cache = true;
+    let no_cache_entry_file = format!("{}{}", temp_entry_file, "no_cache");
+    let lib_paths = gen_assembler(&no_cache_entry_file, &kcl_path)
+        .gen_libs(&args)
+        .unwrap();
+    assert_eq!(lib_paths.len(), hashes.len());
+    assert!(Path::new(&format!("{}{}", no_cache_entry_file, OBJECT_FILE_SUFFIX)).exists());
+    // The object files of the run are not shared with the other runs.
+    assert!(lib_paths
+        .iter()
+        .all(|lib_path| lib_path.starts_with(&no_cache_entry_file)));
+}
+
 #[test]
 #[cfg(feature = "llvm")]
 fn test_clean_path_for_genlibs() {
@@ -390,13 +454,7 @@ fn test_clean_path_for_genlibs() {
             .to_string(),
     );
     let scope = resolve_program(&mut prog);
-    let assembler = KclvmAssembler::new(
-        prog,
-        scope,
-        String::new(),
-        KclvmLibAssembler::LLVM,
-        HashMap::new(),
-    );
+    let assembler = KclvmAssembler::new(prog, scope, String::new(), KclvmLibAssembler::LLVM);
 
     let temp_dir = tempdir().unwrap();
     let temp_dir_path = temp_dir.path().to_str().unwrap();
@@ -695,6 +753,128 @@ fn test_uuid() {
     }
 }
 
+#[test]
+fn test_exec_concurrently() {
+    const THREAD_NUM: usize = 8;
+    // Copy the test cases to a temp dir, thus the cache dir is not cleaned by other tests.
+    let temp_dir = tempdir().unwrap();
+    let mut files = vec![];
+    for file in get_files(exec_data_path(), false, true, ".k")
+        .iter()
+        .chain(&get_files(exec_err_data_path(), false, true, ".k"))
+    {
+        let path = temp_dir.path().join(Path::new(file).file_name().unwrap());
+        fs::copy(file, &path).unwrap();
+        files.push(path.display().to_string());
+    }
+    fs::copy(
+        Path::new(&exec_data_path()).join("kcl.mod"),
+        temp_dir.path().join("kcl.mod"),
+    )
+    .unwrap();
+    let expected: Vec<Result<String, String>> = files.iter().map(|file| exec(file)).collect();
+    assert!(expected.iter().any(|r| r.is_err()));
+
+    let handles: Vec<_> = (0..THREAD_NUM)
+        .map(|i| {
+            let mut files = files.clone();
+            // Execute the programs in different orders on each thread.
+            files.rotate_left(i % files.len());
+            std::thread::spawn(move || {
+                files
+                    .into_iter()
+                    .map(|file| {
+                        let result = exec(&file);
+                        (file, result)
+                    })
+                    .collect::<Vec<_>>()
+            })
+        })
+        .collect();
+    for handle in handles {
+        for (file, result) in handle.join().unwrap() {
+            let i = files.iter().position(|f| *f == file).unwrap();
+            assert_eq!(result, expected[i], "test case {} failed", file);
+        }
+    }
+}
+
+#[test]
+fn test_artifact_info() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/init_check_order_0/main.k".to_string());
+    let program = parse_program(&args.k_filename_list[0]);
+    let info = ArtifactInfo::new(&args, &program).unwrap();
+    assert_eq!(info.version, kclvm_version::get_version_string());
+    assert_eq!(info.options.k_filename_list, args.k_filename_list);
+    assert_eq!(info.packages.len(), 1);
+    assert_eq!(info.packages[0].pkgpath, "__main__");
+    assert_eq!(info.packages[0].files.len(), 1);
+    assert_eq!(info.packages[0].files[0].hash.len(), 32);
+    assert_eq!(info.schemas.len(), 1);
+    assert_eq!(info.schemas[0].name, "Person");
+    assert_eq!(info.schemas[0].line, 1);
+
+    // The metadata is found in the bytes of the artifact by the marker.
+    let mut bytes = b"\x7fELF".to_vec();
+    bytes.extend(b"KCLVM_ARTIFACT_INFO:");
+    bytes.extend(info.to_json_string().unwrap().as_bytes());
+    bytes.extend(b"\0\x01");
+    let found = ArtifactInfo::from_bytes(&bytes).unwrap();
+    assert_eq!(found.packages, info.packages);
+    assert_eq!(found.schemas, info.schemas);
+    assert!(ArtifactInfo::from_bytes(b"\x7fELF").is_none());
+
+    let temp_dir = tempdir().unwrap();
+    let code_file = temp_dir.path().join("main").display().to_string();
+    let source = fs::read_to_string(info.write_source(&code_file).unwrap()).unwrap();
+    assert!(source.contains("const unsigned char kclvm_artifact_info[] = {75,67,76,86,77,"));
+    assert!(source.trim_end().ends_with(",0};"));
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn test_build_program_with_artifact_info() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/init_check_order_0/main.k".to_string());
+    let temp_dir = tempdir().unwrap();
+    let output = temp_dir.path().join("main");
+    let artifact =
+        crate::build_program(Arc::new(ParseSession::default()), &args, Some(&output)).unwrap();
+    let info = artifact.info().unwrap();
+    assert_eq!(info.target, env!("KCLVM_DEFAULT_TARGET"));
+    assert_eq!(info.schemas[0].name, "Person");
+}
+
+#[test]
+fn test_exec_program_to_writer() {
+    for kcl_file in get_files(custom_manifests_data_path(), false, true, ".k") {
+        let mut args = ExecProgramArgs::default();
+        args.k_filename_list.push(kcl_file.to_string());
+        args.fast_eval = true;
+        let expected = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        for (format, expected) in [
+            (PlanFormat::Json, &expected.json_result),
+            (PlanFormat::Yaml, &expected.yaml_result),
+        ] {
+            let mut buf = vec![];
+            let result =
+                exec_program_to_writer(Arc::new(ParseSession::default()), &args, format, &mut buf)
+                    .unwrap();
+            assert!(result.err_message.is_empty(), "{}", result.err_message);
+            assert!(result.json_result.is_empty() && result.yaml_result.is_empty());
+            assert_eq!(
+                &String::from_utf8(buf).unwrap(),
+                expected,
+                "test case {} failed",
+                kcl_file
+            );
+        }
+    }
+}
+
 #[test]
 fn test_compile_with_symbolic_link() {
     let main_test_path = PathBuf::from("./src/test_symbolic_link/test_pkg/bbb/main.k");
@@ -735,3 +915,802 @@ fn test_kcl_issue_1799() {
         )
     );
 }
+
+#[test]
+fn test_exec_with_diagnostic_format() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_diagnostic_format/main.k".to_string());
+    args.diagnostic_format = DiagnosticFormat::Json;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    let diags: Value = serde_json::from_str(&output.0).unwrap();
+    assert_eq!(diags[0]["level"], "error");
+    assert_eq!(diags[0]["code"], "E2G22");
+    assert_eq!(diags[0]["messages"][0]["start"]["line"], 5);
+    // The sha256 hash of the file which the diagnostic points at.
+    assert_eq!(diags[0]["source_hash"].as_str().unwrap().len(), 64);
+
+    args.diagnostic_format = DiagnosticFormat::Sarif;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    let log: Value = serde_json::from_str(&output.0).unwrap();
+    assert_eq!(log["version"], "2.1.0");
+    let result = &log["runs"][0]["results"][0];
+    assert_eq!(result["ruleId"], "E2G22");
+    assert_eq!(result["level"], "error");
+    assert_eq!(
+        result["locations"][0]["physicalLocation"]["region"]["startLine"],
+        5
+    );
+    assert_eq!(
+        log["runs"][0]["artifacts"][0]["hashes"]["sha-256"],
+        diags[0]["source_hash"]
+    );
+
+    args.diagnostic_format = DiagnosticFormat::Markdown;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    assert!(output.0.starts_with("```text\nerror[E2G22]: TypeError"));
+
+    args.diagnostic_format = DiagnosticFormat::Html;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    assert!(output
+        .0
+        .contains("<span class=\"kcl-helpful\">[E2G22]</span>: TypeError"));
+}
+
+#[test]
+fn test_exec_with_path_prefix_map() {
+    let build_dir = Path::new("./src/test_diagnostic_format")
+        .canonicalize()
+        .unwrap()
+        .adjust_canonicalization();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_diagnostic_format/main.k".to_string());
+    args.diagnostic_format = DiagnosticFormat::Json;
+    args.path_prefix_map = vec![(build_dir, "/src".to_string())];
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    let diags: Value = serde_json::from_str(&output.0).unwrap();
+    let filename = diags[0]["messages"][0]["filename"].as_str().unwrap();
+    assert_eq!(Path::new(filename), Path::new("/src/main.k"));
+    // The hash of the remapped file is still reported.
+    assert!(diags[0]["source_hash"].is_string());
+}
+
+#[test]
+fn test_exec_with_max_errors() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_max_errors/main.k".to_string());
+    args.max_errors = Some(1);
+    args.diagnostic_format = DiagnosticFormat::Json;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    let output = err.downcast_ref::<FormattedDiagnostics>().unwrap();
+    let diags: Value = serde_json::from_str(&output.0).unwrap();
+    let diags = diags.as_array().unwrap();
+    // The identical errors of the first three names are grouped into one error.
+    assert_eq!(diags.len(), 2);
+    assert_eq!(diags[0]["messages"].as_array().unwrap().len(), 3);
+    assert_eq!(diags[1]["level"], "note");
+    assert_eq!(diags[1]["messages"][0]["message"], "and 1 more error");
+
+    args.diagnostic_format = DiagnosticFormat::Human;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err.to_string().contains("and 1 more error"));
+}
+
+#[test]
+fn test_exec_with_locale() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_diagnostic_format/main.k".to_string());
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err.to_string().contains("error[E2G22]: TypeError"));
+
+    args.locale = Locale::ZhCN;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err.to_string().contains("error[E2G22]: 类型错误"));
+}
+
+#[test]
+fn test_exec_with_color_mode() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_diagnostic_format/main.k".to_string());
+    args.color = ColorMode::Always;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err.to_string().contains('\u{1b}'));
+
+    args.color = ColorMode::Never;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(!err.to_string().contains('\u{1b}'));
+}
+
+#[test]
+fn test_exec_with_runtime_limits() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_runtime_limits/main.k".to_string());
+    args.fast_eval = true;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+
+    args.max_call_depth = Some(1);
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result
+        .err_message
+        .contains("maximum call depth exceeded: the call depth exceeds the limit of 1"));
+    assert!(result.err_message.contains("backtrace:"));
+
+    args.max_call_depth = None;
+    args.max_allocations = Some(5);
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result
+        .err_message
+        .contains("allocation limit exceeded: the evaluation allocates more than 5"));
+
+    args.max_allocations = None;
+    args.timeout_ms = Some(0);
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.contains("evaluation timeout"));
+}
+
+#[test]
+fn test_exec_with_sandbox_policy() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_sandbox/main.k".to_string());
+    args.sandbox.allow_read_paths = Some(vec!["./src/test_sandbox".to_string()]);
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+
+    args.sandbox.deny_read_paths = vec!["./src/test_sandbox/main.k".to_string()];
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result
+        .err_message
+        .contains("the sandbox policy denies the access to"));
+}
+
+#[test]
+fn test_exec_with_sandbox_deny_network() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_sandbox/network.k".to_string());
+    args.sandbox.deny_network = true;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result
+        .err_message
+        .contains("the sandbox policy denies the network access of 'net.fqdn'"));
+}
+
+#[test]
+fn test_exec_with_native_plugin() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_native_plugin/main.k".to_string());
+    args.plugin_functions
+        .register("native_testing.add", native_testing_add);
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    assert_eq!(result.yaml_result, "sum: 3");
+}
+
+#[test]
+fn test_exec_with_global_native_plugin() {
+    // The global registry is only used by the executions opting in to it.
+    crate::register_plugin_fn("native_testing.add", native_testing_add);
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_native_plugin/main.k".to_string());
+    args.use_global_plugin_fns = true;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(crate::unregister_plugin_fn("native_testing.add"));
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    assert_eq!(result.yaml_result, "sum: 3");
+}
+
+fn native_testing_add(
+    _: &kclvm_runtime::Context,
+    args: &kclvm_runtime::ValueRef,
+    _: &kclvm_runtime::ValueRef,
+) -> anyhow::Result<kclvm_runtime::ValueRef> {
+    let a = args
+        .arg_i_int(0, Some(0))
+        .ok_or(anyhow::anyhow!("expect int value for the first param"))?;
+    let b = args
+        .arg_i_int(1, Some(0))
+        .ok_or(anyhow::anyhow!("expect int value for the second param"))?;
+    Ok((a + b).into())
+}
+
+#[test]
+fn test_exec_with_random_seed() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_random_seed/main.k".to_string());
+    args.random_seed = Some(42);
+    let first = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(first.err_message.is_empty(), "{}", first.err_message);
+    let second = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(first.yaml_result, second.yaml_result);
+    args.random_seed = Some(43);
+    let third = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_ne!(first.yaml_result, third.yaml_result);
+}
+
+#[test]
+fn test_exec_with_output_dir() {
+    let temp_dir = tempdir().unwrap();
+    let output_dir = temp_dir.path().join("manifests");
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_output_dir/main.k".to_string());
+    args.output_dir = Some(output_dir.display().to_string());
+    args.filename_template = Some("{schema}/{metadata.name}.{ext}".to_string());
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    assert!(result.yaml_result.is_empty());
+    assert_eq!(
+        fs::read_to_string(output_dir.join("Deployment").join("web.yaml")).unwrap(),
+        "metadata:\n  name: web\nreplicas: 2\n"
+    );
+    assert_eq!(
+        fs::read_to_string(output_dir.join("Service").join("web.yaml")).unwrap(),
+        "metadata:\n  name: web\nport: 80\n"
+    );
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn test_exec_with_backends() {
+    let kcl_files = get_files(exec_data_path(), false, true, ".k")
+        .into_iter()
+        .chain(get_files(custom_manifests_data_path(), false, true, ".k"));
+    for kcl_file in kcl_files {
+        let mut args = ExecProgramArgs::default();
+        args.k_filename_list.push(kcl_file.to_string());
+        args.backend = Backend::Native;
+        let native = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        args.backend = Backend::Interpreter;
+        let interpreter = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(
+            native.json_result, interpreter.json_result,
+            "test case {} failed",
+            kcl_file
+        );
+        assert_eq!(
+            native.yaml_result, interpreter.yaml_result,
+            "test case {} failed",
+            kcl_file
+        );
+        assert_eq!(
+            native.err_message.is_empty(),
+            interpreter.err_message.is_empty(),
+            "test case {} failed",
+            kcl_file
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn test_exec_secret_with_backends() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_secret/main.k".to_string());
+    args.backend = Backend::Native;
+    let native = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(native.err_message.is_empty(), "{}", native.err_message);
+    assert!(native.yaml_result.contains("password: '******'"));
+    args.backend = Backend::Interpreter;
+    let interpreter = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(native.yaml_result, interpreter.yaml_result);
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn test_exec_with_cross_target_fail() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/init_check_order_0/main.k".to_string());
+    args.backend = Backend::Native;
+    args.target = Some("riscv64gc-unknown-none-elf".to_string());
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the program of the target 'riscv64gc-unknown-none-elf' can't be run on the host, use build_program instead"
+    );
+}
+
+#[test]
+fn test_exec_with_profile() {
+    let mut backends = vec![(Backend::Interpreter, vec!["parse", "resolve", "run"], "run")];
+    if cfg!(feature = "llvm") {
+        backends.push((
+            Backend::Native,
+            vec!["parse", "resolve", "codegen", "link", "run"],
+            "codegen",
+        ));
+    }
+    for (backend, phases, pkg_phase) in backends {
+        let mut args = ExecProgramArgs::default();
+        args.k_filename_list.push(
+            "./src/test_datas/multi_file_compilation/import_abs_path/app-main/main.k".to_string(),
+        );
+        args.backend = backend;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.profile.is_none());
+
+        args.profile = true;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        let profile = result.profile.unwrap();
+        let names: Vec<&str> = profile.phases.iter().map(|p| p.name.as_str()).collect();
+        assert_eq!(names, phases, "{:?}", backend);
+        assert!(!profile.packages.is_empty(), "{:?}", backend);
+        assert!(profile.packages.iter().all(|p| p.phase == pkg_phase));
+        #[cfg(target_os = "linux")]
+        assert!(profile.phases.iter().all(|p| p.peak_memory.is_some()));
+    }
+}
+
+#[test]
+fn test_list_option_decls() {
+    let file = "./src/test_datas/options/main.k";
+    let mut program = load_program(Arc::new(ParseSession::default()), &[file], None, None)
+        .unwrap()
+        .program;
+    let scope = resolve_program(&mut program);
+    let decls = list_option_decls(&program, &scope);
+    let names: Vec<(&str, &str, bool)> = decls
+        .iter()
+        .map(|d| (d.name.as_str(), d.ty.as_str(), d.required))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            ("name", "str", true),
+            ("replicas", "int", false),
+            ("labels", "dict", false)
+        ]
+    );
+    assert_eq!(
+        decls[1],
+        OptionDecl {
+            name: "replicas".to_string(),
+            ty: "int".to_string(),
+            required: false,
+            default: Some(json!(1)),
+            help: "".to_string(),
+            filename: decls[1].filename.clone(),
+            line: 2,
+            column: 11,
+        }
+    );
+    assert_eq!(decls[0].help, "The app name");
+    assert_eq!(decls[2].default, None);
+}
+
+#[test]
+fn test_exec_with_structured_options() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/options/main.k".to_string());
+    args.args.push(kclvm_ast::ast::Argument {
+        name: "replicas".to_string(),
+        value: "2".to_string(),
+    });
+    args.options.insert("name".to_string(), json!("app"));
+    args.options
+        .insert("labels".to_string(), json!({"env": "prod"}));
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: app\nreplicas: 2\nlabels:\n  env: prod\n"
+    );
+    // The structured options override the `-D` options.
+    args.options.insert("replicas".to_string(), json!(3));
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: app\nreplicas: 3\nlabels:\n  env: prod\n"
+    );
+    // The option values are checked against the declared types before the execution.
+    args.options.insert("replicas".to_string(), json!("three"));
+    let err = exec_program(Arc::new(ParseSession::default()), &args)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.starts_with("invalid value of the option 'replicas' declared at "),
+        "{err}"
+    );
+    assert!(
+        err.ends_with("main.k:2:12, cannot use 'three' as type 'int'"),
+        "{err}"
+    );
+    args.options.insert("replicas".to_string(), json!(3));
+    args.options.insert("labels".to_string(), json!([1]));
+    let err = exec_program(Arc::new(ParseSession::default()), &args)
+        .unwrap_err()
+        .to_string();
+    assert!(
+        err.ends_with("main.k:3:10, cannot use '[1]' as type 'dict'"),
+        "{err}"
+    );
+}
+
+#[derive(Debug)]
+struct TestOptionProvider;
+
+impl OptionProvider for TestOptionProvider {
+    fn options(&self) -> Result<Vec<(String, Value)>> {
+        Ok(vec![("labels".to_string(), json!({"env": "prod"}))])
+    }
+}
+
+#[test]
+fn test_exec_with_option_providers() {
+    std::env::set_var("KCL_RUNNER_TEST_OPT_replicas", "2");
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/options/main.k".to_string());
+    args.option_files
+        .push("./src/test_datas/options/options.yaml".to_string());
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: from-file\nreplicas: 1\nlabels:\n  env: dev\n"
+    );
+    // The options are overridden in the order of the files, the environment variables,
+    // the providers and the `-D` options.
+    args.option_env_prefix = Some("KCL_RUNNER_TEST_OPT_".to_string());
+    args.option_providers.push(Arc::new(TestOptionProvider));
+    args.args.push(kclvm_ast::ast::Argument {
+        name: "name".to_string(),
+        value: "from-arg".to_string(),
+    });
+    let names: Vec<String> = args
+        .option_args()
+        .unwrap()
+        .into_iter()
+        .map(|arg| format!("{}={}", arg.name, arg.value))
+        .collect();
+    assert_eq!(
+        names,
+        vec![
+            "name=\"from-file\"",
+            "replicas=1",
+            "labels={\"env\": \"dev\"}",
+            "replicas=2",
+            "labels={\"env\":\"prod\"}",
+            "name=from-arg",
+        ]
+    );
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(
+        result.yaml_result,
+        "name: from-arg\nreplicas: 2\nlabels:\n  env: prod\n"
+    );
+
+    args.option_files = vec!["./src/test_datas/options/not_found.yaml".to_string()];
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert!(err
+        .to_string()
+        .starts_with("failed to read the option file './src/test_datas/options/not_found.yaml': "));
+}
+
+#[test]
+fn test_exec_with_dump_ast() {
+    let dump_dir = tempdir().unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(
+        "./src/test_datas/multi_file_compilation/import_abs_path/app-main/main.k".to_string(),
+    );
+    args.backend = Backend::Interpreter;
+    args.dump_ast = true;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the dump directory is required to dump the intermediate representations"
+    );
+
+    args.dump_dir = Some(dump_dir.path().join("dump").display().to_string());
+    exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    let content =
+        std::fs::read_to_string(dump_dir.path().join("dump").join(dump::AST_DUMP_FILE)).unwrap();
+    let dump: Value = serde_json::from_str(&content).unwrap();
+    let main_modules = dump["program"]["pkgs"]["__main__"].as_array().unwrap();
+    assert_eq!(main_modules.len(), 1);
+    let node_types = dump["node_types"].as_object().unwrap();
+    assert!(!node_types.is_empty());
+    // The node ids of the dumped AST are the keys of the node types.
+    let first_stmt_id = main_modules[0]["body"][0]["id"].as_str().unwrap();
+    assert!(!first_stmt_id.is_empty());
+    assert!(node_types
+        .keys()
+        .any(|id| content.contains(&format!("\"id\": \"{}\"", id))));
+
+    args.dump_llvm_ir = true;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the LLVM IR dump is only supported by the native backend"
+    );
+}
+
+#[test]
+#[cfg(feature = "llvm")]
+fn test_exec_with_dump_llvm_ir_and_lib() {
+    let dump_dir = tempdir().unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list.push(
+        "./src/test_datas/multi_file_compilation/import_abs_path/app-main/main.k".to_string(),
+    );
+    args.dump_dir = Some(dump_dir.path().display().to_string());
+    args.dump_llvm_ir = true;
+    args.dump_lib = true;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    for pkgpath in ["__main__", "some0.pkg1"] {
+        let ir = std::fs::read_to_string(dump_dir.path().join(format!("{}.ll", pkgpath))).unwrap();
+        assert!(ir.contains("define"), "{}", pkgpath);
+    }
+    assert!(dump_dir
+        .path()
+        .join(format!(
+            "{}{}",
+            dump::LIB_DUMP_FILE_STEM,
+            Command::get_lib_suffix()
+        ))
+        .exists());
+}
+
+#[test]
+fn test_profile_table() {
+    let profile = ProfileResult {
+        phases: vec![
+            PhaseProfile {
+                name: "parse".to_string(),
+                wall_time_ms: 1.5,
+                peak_memory: Some(2 * 1024 * 1024),
+            },
+            PhaseProfile {
+                name: "run".to_string(),
+                wall_time_ms: 2.0,
+                peak_memory: None,
+            },
+        ],
+        packages: vec![PackageProfile {
+            pkgpath: "pkg".to_string(),
+            phase: "run".to_string(),
+            wall_time_ms: 0.25,
+        }],
+    };
+    assert_eq!(profile.total_wall_time_ms(), 3.5);
+    assert_eq!(
+        profile.to_table_string(),
+        r#"PHASE                        TIME(ms)     PEAK MEM
+parse                           1.500       2.0MiB
+run                             2.000            -
+total                           3.500
+
+PACKAGE                         PHASE     TIME(ms)
+pkg                               run        0.250
+"#
+    );
+}
+
+static LOGGED_RECORDS: Mutex<Vec<String>> = Mutex::new(vec![]);
+
+extern "C" fn log_agent_for_test(record_json: *const c_char) {
+    let record = unsafe { std::ffi::CStr::from_ptr(record_json) };
+    LOGGED_RECORDS
+        .lock()
+        .unwrap()
+        .push(record.to_str().unwrap().to_string());
+}
+
+#[test]
+fn test_exec_with_log_records() {
+    let mut backends = vec![Backend::Interpreter];
+    if cfg!(feature = "llvm") {
+        backends.push(Backend::Native);
+    }
+    for backend in backends {
+        let mut args = ExecProgramArgs::default();
+        args.k_filename_list
+            .push("./src/test_datas/log_records/main.k".to_string());
+        args.backend = backend;
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert_eq!(result.log_message, "hello\na = 1");
+        let messages: Vec<&str> = result
+            .log_records
+            .iter()
+            .map(|r| r.message.as_str())
+            .collect();
+        assert_eq!(messages, vec!["hello\n", "a = 1"]);
+        assert!(result.log_records.iter().all(|r| r.filename.is_empty()));
+
+        // Record the source positions and stream the log records to the log agent.
+        args.log_positions = true;
+        args.log_agent = log_agent_for_test as *const () as u64;
+        LOGGED_RECORDS.lock().unwrap().clear();
+        let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+        assert!(result.log_records[0].filename.ends_with("main.k"));
+        assert_eq!(result.log_records[0].line, 1);
+        assert_eq!(result.log_records[1].line, 3);
+        let logged: Vec<LogRecord> = LOGGED_RECORDS
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|r| serde_json::from_str(r).unwrap())
+            .collect();
+        assert_eq!(logged, result.log_records, "{:?}", backend);
+    }
+}
+
+#[derive(Debug, Default)]
+struct TestProgressListener {
+    events: Mutex<Vec<String>>,
+}
+
+impl ProgressListener for TestProgressListener {
+    fn on_event(&self, event: ProgressEvent) {
+        let event = match event {
+            ProgressEvent::PhaseStarted { phase } => format!("started {phase}"),
+            ProgressEvent::PhaseFinished { phase, .. } => format!("finished {phase}"),
+            ProgressEvent::Diagnostic(diag) => format!("diagnostic {:?}", diag.level),
+            ProgressEvent::Log(record) => format!("log {:?}", record.message),
+        };
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[test]
+fn test_exec_with_progress_listener() {
+    let listener = Arc::new(TestProgressListener::default());
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/log_records/main.k".to_string());
+    args.backend = Backend::Interpreter;
+    args.progress_listener = Some(listener.clone());
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert_eq!(result.log_message, "hello\na = 1");
+    assert_eq!(
+        *listener.events.lock().unwrap(),
+        vec![
+            "started parse",
+            "finished parse",
+            "started resolve",
+            "finished resolve",
+            "started run",
+            "log \"hello\\n\"",
+            "log \"a = 1\"",
+            "finished run",
+        ]
+    );
+}
+
+#[test]
+fn test_target_lib_suffix() {
+    assert_eq!(
+        Command::get_target_lib_suffix("x86_64-unknown-linux-gnu"),
+        ".so"
+    );
+    assert_eq!(
+        Command::get_target_lib_suffix("aarch64-apple-darwin"),
+        ".dylib"
+    );
+    assert_eq!(
+        Command::get_target_lib_suffix("x86_64-pc-windows-msvc"),
+        ".dll"
+    );
+}
+
+#[test]
+fn test_exec_with_native_backend_fail() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_datas/init_check_order_0/main.k".to_string());
+    args.backend = Backend::Native;
+    args.sandbox.deny_env = true;
+    let err = exec_program(Arc::new(ParseSession::default()), &args).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "the sandbox policy is only supported by the interpreter backend"
+    );
+    assert_eq!(
+        "interpreter".parse::<Backend>().unwrap(),
+        Backend::Interpreter
+    );
+    assert!("jit".parse::<Backend>().is_err());
+}
+
+#[test]
+fn test_exec_with_provenance_sidecar() {
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list
+        .push("./src/test_output_dir/main.k".to_string());
+    args.provenance = ProvenanceMode::Sidecar;
+    let result = exec_program(Arc::new(ParseSession::default()), &args).unwrap();
+    assert!(result.err_message.is_empty(), "{}", result.err_message);
+    assert!(!result.yaml_result.contains("provenance"));
+    let provenances: Value = serde_json::from_str(&result.provenance_result).unwrap();
+    assert_eq!(provenances["$.deployment"]["schema"], "Deployment");
+    assert_eq!(provenances["$.deployment"]["line"], 9);
+    assert_eq!(provenances["$.service"]["schema"], "Service");
+    assert_eq!(provenances["$.service"]["line"], 13);
+}
+
+#[test]
+fn test_diff() {
+    let old = json!({
+        "app": {"replicas": 1, "port": 80, "labels": {"a/b": "x"}},
+        "zones": ["a", "b", "c"],
+    });
+    let new = json!({
+        "app": {"replicas": 2, "labels": {"a/b": "x"}, "image": "nginx"},
+        "zones": ["a", "d"],
+    });
+    let result = diff::diff(&old, &new);
+    let ops: Vec<(&str, &str)> = result
+        .patch
+        .iter()
+        .map(|op| (op.op.as_str(), op.path.as_str()))
+        .collect();
+    assert_eq!(
+        ops,
+        vec![
+            ("remove", "/app/port"),
+            ("replace", "/app/replicas"),
+            ("add", "/app/image"),
+            ("replace", "/zones/1"),
+            ("remove", "/zones/2"),
+        ]
+    );
+    assert_eq!(result.patch[2].value, Some(json!("nginx")));
+    assert_eq!(
+        result.tree_diff,
+        r#"app:
+  - port: 80
+  ~ replicas: 1 => 2
+  + image: "nginx"
+zones:
+  ~ [1]: "b" => "d"
+  - [2]: "c"
+"#
+    );
+    assert!(diff::diff(&old, &old).is_empty());
+    assert_eq!(
+        diff::diff(&json!({"a/b~": 1}), &json!({})).patch[0].path,
+        "/a~1b~0"
+    );
+    assert_eq!(
+        diff::diff(&json!(1), &json!("1")).tree_diff,
+        "~ 1 => \"1\"\n"
+    );
+}
+
+#[test]
+fn test_watch_program() {
+    let temp_dir = tempdir().unwrap();
+    let main_file = temp_dir.path().join("main.k");
+    fs::write(&main_file, "a = 1\n").unwrap();
+    let mut args = ExecProgramArgs::default();
+    args.k_filename_list = vec![main_file.display().to_string()];
+    let mut yaml_results = vec![];
+    watch_program(Arc::new(ParseSession::default()), &args, |result| {
+        yaml_results.push(result.unwrap().yaml_result);
+        if yaml_results.len() == 1 {
+            fs::write(&main_file, "a = 2\n").unwrap();
+            ControlFlow::Continue(())
+        } else {
+            ControlFlow::Break(())
+        }
+    })
+    .unwrap();
+    assert_eq!(yaml_results, vec!["a: 1", "a: 2"]);
+}
@@ -0,0 +1,212 @@
+//! Watch the source files of the program and execute it again on every change, which
+//! powers `kcl run --watch` and the hot reloading of the development servers. The parsed
+//! modules are kept in the module cache between the executions, thus only the changed
+//! files are parsed again.
+use std::collections::{HashMap, HashSet};
+use std::ops::ControlFlow;
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, RecvTimeoutError};
+use std::sync::{Arc, RwLock};
+use std::time::Duration;
+
+use anyhow::{anyhow, Result};
+use kclvm_ast::ast::Program;
+use kclvm_config::modfile::{KCL_FILE_EXTENSION, KCL_MOD_FILE};
+use kclvm_parser::{load_program, KCLModuleCache, ModuleCache, ParseSession, ParseSessionRef};
+use notify::{Event, EventKind, RecursiveMode, Watcher};
+
+use crate::profile::{Profiler, PARSE_PHASE};
+use crate::{apply_args_overrides, execute, ExecProgramArgs, ExecProgramResult};
+
+/// The time to wait for the following events after a change, thus the events of an
+/// editor save or a git checkout are handled in one execution.
+const DEBOUNCE_TIMEOUT: Duration = Duration::from_millis(100);
+
+/// Execute the program like [crate::exec_program], then watch the source files and
+/// execute the program again on every change until the callback returns
+/// [ControlFlow::Break]. The callback is invoked with the result of each execution,
+/// and the errors of the executions are passed to it instead of stopping the watch.
+///
+/// Only the changed files are parsed again, and the whole module cache is cleared when
+/// a KCL file is created or removed, or `kcl.mod` is changed, because the files of the
+/// packages may be changed. The given session is used by the first execution, and each
+/// following execution uses a new session, thus the diagnostics are not accumulated.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_runner::{watch_program, ExecProgramArgs};
+/// use kclvm_parser::ParseSession;
+/// use std::ops::ControlFlow;
+/// use std::sync::Arc;
+///
+/// let sess = Arc::new(ParseSession::default());
+/// let mut args = ExecProgramArgs::default();
+/// args.k_filename_list = vec!["./src/test_datas/init_check_order_0/main.k".to_string()];
+///
+/// watch_program(sess, &args, |result| {
+///     match result {
+///         Ok(result) => println!("{}", result.yaml_result),
+///         Err(err) => eprintln!("{}", err),
+///     }
+///     ControlFlow::Continue(())
+/// })
+/// .unwrap();
+/// ```
+pub fn watch_program<F>(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    mut callback: F,
+) -> Result<()>
+where
+    F: FnMut(Result<ExecProgramResult>) -> ControlFlow<()>,
+{
+    let module_cache = KCLModuleCache::default();
+    let (tx, rx) = channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mut watched_dirs = HashSet::new();
+    let mut sess = sess;
+    loop {
+        let result = exec_program_with_cache(sess, args, module_cache.clone());
+        let files = source_files(args, &module_cache)?;
+        for dir in files.keys().filter_map(|file| file.parent()) {
+            if dir.is_dir() && watched_dirs.insert(dir.to_path_buf()) {
+                watcher.watch(dir, RecursiveMode::NonRecursive)?;
+            }
+        }
+        if callback(result).is_break() {
+            return Ok(());
+        }
+        let changed_files = wait_for_changes(&rx, &files)?;
+        let mut module_cache = module_cache
+            .write()
+            .map_err(|e| anyhow!("Failed to acquire module cache lock: {:?}", e))?;
+        match changed_files {
+            Some(changed_files) => {
+                for file in &changed_files {
+                    module_cache.clear(file);
+                }
+            }
+            None => *module_cache = ModuleCache::default(),
+        }
+        sess = Arc::new(ParseSession::default());
+    }
+}
+
+/// Execute the program with the modules in the module cache.
+fn exec_program_with_cache(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    module_cache: KCLModuleCache,
+) -> Result<ExecProgramResult> {
+    let mut profiler = Profiler::new(args);
+    let program = profiler.phase(PARSE_PHASE, || {
+        load_program_with_cache(sess.clone(), args, module_cache)
+    })?;
+    let mut result = execute(sess, program, args)?;
+    profiler.merge_into(&mut result);
+    Ok(result)
+}
+
+/// Load the program with the module cache and apply the overrides on it.
+fn load_program_with_cache(
+    sess: ParseSessionRef,
+    args: &ExecProgramArgs,
+    module_cache: KCLModuleCache,
+) -> Result<Program> {
+    let opts = args.get_load_program_options();
+    let kcl_paths_str = args
+        .k_filename_list
+        .iter()
+        .map(|s| s.as_str())
+        .collect::<Vec<&str>>();
+    let mut program = load_program(sess, &kcl_paths_str, Some(opts), Some(module_cache))?.program;
+    // The modules are shared with the module cache, and they are copied because the
+    // overrides and the resolver change them.
+    for module in program.modules.values_mut() {
+        let copied = module
+            .read()
+            .map_err(|e| anyhow!("Failed to acquire module lock: {:?}", e))?
+            .clone();
+        *module = Arc::new(RwLock::new(copied));
+    }
+    apply_args_overrides(&mut program, args)?;
+    Ok(program)
+}
+
+/// Returns the input files and the cached files keyed by the absolute paths, and the
+/// values are the paths in the args and the module cache.
+fn source_files(
+    args: &ExecProgramArgs,
+    module_cache: &KCLModuleCache,
+) -> Result<HashMap<PathBuf, PathBuf>> {
+    let module_cache = module_cache
+        .read()
+        .map_err(|e| anyhow!("Failed to acquire module cache lock: {:?}", e))?;
+    Ok(args
+        .k_filename_list
+        .iter()
+        .map(PathBuf::from)
+        .chain(module_cache.ast_cache.keys().cloned())
+        .map(|file| (absolute_path(&file), file))
+        .collect())
+}
+
+/// Wait for the changes of the source files, and returns the changed files in the
+/// module cache, or None when the whole module cache should be cleared.
+fn wait_for_changes(
+    rx: &Receiver<notify::Result<Event>>,
+    files: &HashMap<PathBuf, PathBuf>,
+) -> Result<Option<Vec<PathBuf>>> {
+    let mut changed_files = HashSet::new();
+    let mut clear_all = false;
+    let mut timeout = None;
+    loop {
+        let event = match timeout {
+            None => rx.recv()?,
+            Some(timeout) => match rx.recv_timeout(timeout) {
+                Ok(event) => event,
+                Err(RecvTimeoutError::Timeout) => break,
+                Err(err) => return Err(err.into()),
+            },
+        }?;
+        if matches!(event.kind, EventKind::Access(_)) {
+            continue;
+        }
+        for path in &event.paths {
+            match files.get(&absolute_path(path)) {
+                Some(_) if matches!(event.kind, EventKind::Remove(_)) => clear_all = true,
+                Some(file) => {
+                    changed_files.insert(file.clone());
+                }
+                // The new KCL files may belong to the packages of the program.
+                None if is_kcl_file(path) || path.ends_with(KCL_MOD_FILE) => clear_all = true,
+                None => continue,
+            }
+            timeout = Some(DEBOUNCE_TIMEOUT);
+        }
+    }
+    Ok(if clear_all {
+        None
+    } else {
+        Some(changed_files.into_iter().collect())
+    })
+}
+
+/// Returns the canonical path of the file, or the path joined to the current directory
+/// when the file does not exist.
+fn absolute_path(path: &Path) -> PathBuf {
+    match path.canonicalize() {
+        Ok(path) => path,
+        Err(_) if path.is_relative() => std::env::current_dir()
+            .map(|dir| dir.join(path))
+            .unwrap_or_else(|_| path.to_path_buf()),
+        Err(_) => path.to_path_buf(),
+    }
+}
+
+#[inline]
+fn is_kcl_file(path: &Path) -> bool {
+    path.extension()
+        .map_or(false, |ext| ext == KCL_FILE_EXTENSION)
+}
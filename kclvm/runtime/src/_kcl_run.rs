@@ -3,6 +3,7 @@
 
 use std::os::raw::c_char;
 
+use crate::val_plan::{KeyOrder, PlanFormat, ProvenanceMode};
 use crate::*;
 
 use self::eval::LazyEvalScope;
@@ -60,18 +61,128 @@ pub struct FFIRunOptions {
     pub sort_keys: i32,
     pub include_schema_type_path: i32,
     pub disable_empty_list: i32,
+    pub big_int: i32,
+    /// The decimal places of the planned float values, and `-1` denotes no rounding.
+    pub decimal_places: i32,
+    /// The seed of the generated random values, and a negative value denotes no seed.
+    pub random_seed: i64,
+    pub show_secrets: i32,
+    pub externalize_secrets: i32,
+    /// The discriminant of the output format [PlanFormat], and `0` denotes YAML.
+    pub output_format: i32,
+    /// The discriminant of the key order [KeyOrder], and `0` denotes the declaration order.
+    pub key_order: i32,
+    /// The discriminant of the provenance mode [ProvenanceMode], and `0` denotes none.
+    pub provenance: i32,
+    /// The address of the plugin agent function, and `0` denotes the global plugin agent.
+    pub plugin_agent: u64,
+    /// Whether to record the source positions of the log messages.
+    pub log_positions: i32,
+    /// The address of the log agent function, and `0` denotes no log agent.
+    pub log_agent: u64,
+}
+
+impl RuntimePanicRecord {
+    fn record(&mut self, info: &std::panic::PanicInfo) {
+        self.kcl_panic_info = true;
+        self.message = panic_message(info.payload());
+        if let Some(location) = info.location() {
+            self.rust_file = location.file().to_string();
+            self.rust_line = location.line() as i32;
+            self.rust_col = location.column() as i32;
+        }
+    }
+}
+
+fn panic_message(payload: &(dyn std::any::Any + Send)) -> String {
+    if let Some(s) = payload.downcast_ref::<&str>() {
+        s.to_string()
+    } else if let Some(s) = payload.downcast_ref::<&String>() {
+        (*s).clone()
+    } else if let Some(s) = payload.downcast_ref::<String>() {
+        (*s).clone()
+    } else {
+        "unknown runtime error".to_string()
+    }
 }
 
 thread_local! {
-    static KCL_RUNTIME_PANIC_RECORD: std::cell::RefCell<RuntimePanicRecord>  = std::cell::RefCell::new(RuntimePanicRecord::default())
+    /// The panic record of the KCL program running on the current thread, which is
+    /// None when the thread is not running a KCL program.
+    static KCL_RUNTIME_PANIC_RECORD: std::cell::RefCell<Option<RuntimePanicRecord>> = std::cell::RefCell::new(None)
+}
+
+/// The address of the panic hook installed by [catch_runtime_panic], which is used to
+/// check whether the hook is replaced by others.
+static KCL_PANIC_HOOK: std::sync::Mutex<usize> = std::sync::Mutex::new(0);
+
+/// Install the panic hook which records the panics of the threads running the KCL
+/// programs and calls the previous hook for the other threads. The hook is shared by
+/// all the threads and only installed again when it is replaced by others.
+fn install_panic_hook() {
+    let mut installed = KCL_PANIC_HOOK.lock().unwrap_or_else(|e| e.into_inner());
+    let prev_hook = std::panic::take_hook();
+    if &*prev_hook as *const _ as *const () as usize == *installed {
+        std::panic::set_hook(prev_hook);
+        return;
+    }
+    let hook: Box<dyn Fn(&std::panic::PanicInfo) + Sync + Send> =
+        Box::new(move |info: &std::panic::PanicInfo| {
+            let recorded =
+                KCL_RUNTIME_PANIC_RECORD.with(|record| match record.borrow_mut().as_mut() {
+                    Some(record) => {
+                        record.record(info);
+                        true
+                    }
+                    None => false,
+                });
+            if !recorded {
+                prev_hook(info);
+            }
+        });
+    *installed = &*hook as *const _ as *const () as usize;
+    std::panic::set_hook(hook);
+}
+
+/// Run the function and catch the panic, and returns the result and the panic record.
+///
+/// The panic hook is not replaced and restored on every run, and the panic records are
+/// thread local, thus the KCL programs can run on multiple threads concurrently.
+pub fn catch_runtime_panic<F, R>(f: F) -> (std::thread::Result<R>, RuntimePanicRecord)
+where
+    F: FnOnce() -> R + std::panic::UnwindSafe,
+{
+    install_panic_hook();
+    // Save the record of the outer run e.g., a plugin function runs another program.
+    let prev_record =
+        KCL_RUNTIME_PANIC_RECORD.with(|record| record.replace(Some(RuntimePanicRecord::default())));
+    let result = std::panic::catch_unwind(f);
+    let mut record = KCL_RUNTIME_PANIC_RECORD
+        .with(|record| record.replace(prev_record))
+        .unwrap_or_default();
+    // The panic is not recorded when the hook is replaced by others during the run,
+    // and the message is taken from the panic payload.
+    if let Err(err) = &result {
+        if !record.kcl_panic_info {
+            record.kcl_panic_info = true;
+            record.message = panic_message(err.as_ref());
+        }
+    }
+    (result, record)
 }
 
-fn new_ctx_with_opts(opts: FFIRunOptions, path_selector: &[String]) -> Context {
+/// # Safety
+///
+/// The plugin and log agent addresses in `opts` must be zero or the addresses of the
+/// functions with the agent signatures.
+unsafe fn new_ctx_with_opts(opts: FFIRunOptions, path_selector: &[String]) -> Context {
     let mut ctx = Context::new();
     // Config
     ctx.cfg.strict_range_check = opts.strict_range_check != 0;
     ctx.cfg.disable_schema_check = opts.disable_schema_check != 0;
     ctx.cfg.debug_mode = opts.debug_mode != 0;
+    ctx.cfg.big_int = opts.big_int != 0;
+    ctx.cfg.random_seed = u64::try_from(opts.random_seed).ok();
     // Plan options
     ctx.plan_opts.disable_none = opts.disable_none != 0;
     ctx.plan_opts.show_hidden = opts.show_hidden != 0;
@@ -79,6 +190,15 @@ fn new_ctx_with_opts(opts: FFIRunOptions, path_selector: &[String]) -> Context {
     ctx.plan_opts.include_schema_type_path = opts.include_schema_type_path != 0;
     ctx.plan_opts.disable_empty_list = opts.disable_empty_list != 0;
     ctx.plan_opts.query_paths = path_selector.to_vec();
+    ctx.plan_opts.decimal_places = u32::try_from(opts.decimal_places).ok();
+    ctx.plan_opts.show_secrets = opts.show_secrets != 0;
+    ctx.plan_opts.externalize_secrets = opts.externalize_secrets != 0;
+    ctx.plan_opts.output_format = PlanFormat::from_i32(opts.output_format);
+    ctx.plan_opts.key_order = KeyOrder::from_i32(opts.key_order);
+    ctx.plan_opts.provenance = ProvenanceMode::from_i32(opts.provenance);
+    ctx.plugin_handler = plugin_handler_from_ptr(opts.plugin_agent);
+    ctx.cfg.log_positions = opts.log_positions != 0;
+    ctx.log_handler = log_handler_from_ptr(opts.log_agent);
     ctx
 }
 
@@ -100,6 +220,8 @@ pub unsafe extern "C" fn _kcl_run(
     err_buffer: *mut kclvm_char_t,
     log_buffer_len: *mut kclvm_size_t,
     log_buffer: *mut kclvm_char_t,
+    log_records_buffer_len: *mut kclvm_size_t,
+    log_records_buffer: *mut kclvm_char_t,
 ) -> kclvm_size_t {
     // Init runtime context with options
     let ctx = Box::new(new_ctx_with_opts(opts, &c2str_vec(path_selector))).into_raw();
@@ -109,35 +231,8 @@ pub unsafe extern "C" fn _kcl_run(
     for i in 0..(option_len as usize) {
         kclvm_builtin_option_init(ctx, option_keys[i], option_values[i]);
     }
-    let prev_hook = std::panic::take_hook();
-    std::panic::set_hook(Box::new(|info: &std::panic::PanicInfo| {
-        KCL_RUNTIME_PANIC_RECORD.with(|record| {
-            let mut record = record.borrow_mut();
-            record.kcl_panic_info = true;
-
-            record.message = if let Some(s) = info.payload().downcast_ref::<&str>() {
-                s.to_string()
-            } else if let Some(s) = info.payload().downcast_ref::<&String>() {
-                (*s).clone()
-            } else if let Some(s) = info.payload().downcast_ref::<String>() {
-                (*s).clone()
-            } else {
-                "".to_string()
-            };
-            if let Some(location) = info.location() {
-                record.rust_file = location.file().to_string();
-                record.rust_line = location.line() as i32;
-                record.rust_col = location.column() as i32;
-            }
-        })
-    }));
-    let result = std::panic::catch_unwind(|| _kcl_run_in_closure(ctx, scope, kclvm_main_ptr));
-    std::panic::set_hook(prev_hook);
-    KCL_RUNTIME_PANIC_RECORD.with(|record| {
-        let record = record.borrow();
-        let ctx = mut_ptr_as_ref(ctx);
-        ctx.set_panic_info(&record);
-    });
+    let (result, record) = catch_runtime_panic(|| _kcl_run_in_closure(ctx, scope, kclvm_main_ptr));
+    mut_ptr_as_ref(ctx).set_panic_info(&record);
     // Get the runtime context.
     let ctx_ref = ptr_as_ref(ctx);
     // Copy planned result and log message
@@ -152,6 +247,11 @@ pub unsafe extern "C" fn _kcl_run(
         yaml_result_buffer_len,
     );
     copy_str_to(&ctx_ref.log_message, log_buffer, log_buffer_len);
+    copy_str_to(
+        &serde_json::to_string(&ctx_ref.log_records).unwrap_or_default(),
+        log_records_buffer,
+        log_records_buffer_len,
+    );
     // Copy JSON panic info message pointer
     let json_panic_info = if result.is_err() {
         ctx_ref.get_panic_info_json_string().unwrap_or_default()
@@ -184,3 +284,43 @@ unsafe fn _kcl_run_in_closure(
         (*kclvm_main)(ctx, scope);
     }
 }
+
+#[cfg(test)]
+mod test_kcl_run {
+    use super::*;
+
+    #[test]
+    fn test_catch_runtime_panic() {
+        let handles: Vec<_> = (0..8)
+            .map(|i| {
+                std::thread::spawn(move || {
+                    let (result, record) = catch_runtime_panic(|| {
+                        if i % 2 == 0 {
+                            panic!("error {}", i);
+                        }
+                        i
+                    });
+                    if i % 2 == 0 {
+                        assert!(result.is_err());
+                        assert!(record.kcl_panic_info);
+                        assert_eq!(record.message, format!("error {}", i));
+                    } else {
+                        assert_eq!(result.unwrap(), i);
+                        assert!(!record.kcl_panic_info);
+                    }
+                })
+            })
+            .collect();
+        for handle in handles {
+            handle.join().unwrap();
+        }
+        // The nested run keeps the record of the outer run.
+        let (result, record) = catch_runtime_panic(|| {
+            let (result, _) = catch_runtime_panic(|| panic!("inner"));
+            assert!(result.is_err());
+            panic!("outer");
+        });
+        assert!(result.is_err());
+        assert_eq!(record.message, "outer");
+    }
+}
@@ -90,6 +90,7 @@ pub enum ApiFunc {
     kclvm_builtin_typeof,
     kclvm_builtin_zip,
     kclvm_config_attr_map,
+    kclvm_config_secret_attr,
     kclvm_context_delete,
     kclvm_context_invoke,
     kclvm_context_new,
@@ -106,10 +107,14 @@ pub enum ApiFunc {
     kclvm_context_set_kcl_workdir,
     kclvm_context_set_strict_range_check,
     kclvm_convert_collection_value,
+    kclvm_crypto_argon2_verify,
+    kclvm_crypto_bcrypt_verify,
     kclvm_crypto_blake3,
     kclvm_crypto_fileblake3,
     kclvm_crypto_filesha256,
     kclvm_crypto_filesha512,
+    kclvm_crypto_hmac_sha256,
+    kclvm_crypto_hmac_sha512,
     kclvm_crypto_md5,
     kclvm_crypto_sha1,
     kclvm_crypto_sha224,
@@ -122,6 +127,12 @@ pub enum ApiFunc {
     kclvm_datetime_ticks,
     kclvm_datetime_today,
     kclvm_datetime_validate,
+    kclvm_decimal_add,
+    kclvm_decimal_div,
+    kclvm_decimal_format,
+    kclvm_decimal_mul,
+    kclvm_decimal_round,
+    kclvm_decimal_sub,
     kclvm_default_collection_insert_int_pointer,
     kclvm_default_collection_insert_value,
     kclvm_dict_clear,
@@ -167,6 +178,9 @@ pub enum ApiFunc {
     kclvm_json_dump_to_file,
     kclvm_json_encode,
     kclvm_json_validate,
+    kclvm_jsonpath_get,
+    kclvm_jsonpath_query,
+    kclvm_jsonpath_set,
     kclvm_list_append,
     kclvm_list_append_bool,
     kclvm_list_append_float,
@@ -203,14 +217,14 @@ pub enum ApiFunc {
     kclvm_math_pow,
     kclvm_math_sqrt,
     kclvm_net_IP_string,
+    kclvm_net_contains,
     kclvm_net_fqdn,
+    kclvm_net_hosts_in_CIDR,
     kclvm_net_is_IP,
+    kclvm_net_is_IP_in_CIDR,
     kclvm_net_is_IPv4,
+    kclvm_net_is_IPv6,
     kclvm_net_is_global_unicast_IP,
-    kclvm_net_parse_CIDR,
-    kclvm_net_hosts_in_CIDR,
-    kclvm_net_subnets_from_CIDR,
-    kclvm_net_is_IP_in_CIDR,
     kclvm_net_is_interface_local_multicast_IP,
     kclvm_net_is_link_local_multicast_IP,
     kclvm_net_is_link_local_unicast_IP,
@@ -218,13 +232,21 @@ pub enum ApiFunc {
     kclvm_net_is_multicast_IP,
     kclvm_net_is_unspecified_IP,
     kclvm_net_join_host_port,
+    kclvm_net_parse_CIDR,
     kclvm_net_parse_IP,
     kclvm_net_split_host_port,
+    kclvm_net_subnets,
+    kclvm_net_subnets_from_CIDR,
     kclvm_net_to_IP16,
     kclvm_net_to_IP4,
     kclvm_plugin_init,
     kclvm_plugin_invoke,
     kclvm_plugin_invoke_json,
+    kclvm_random_choice,
+    kclvm_random_randint,
+    kclvm_random_random,
+    kclvm_random_seed,
+    kclvm_random_shuffle,
     kclvm_regex_compile,
     kclvm_regex_findall,
     kclvm_regex_match,
@@ -246,8 +268,18 @@ pub enum ApiFunc {
     kclvm_scope_get,
     kclvm_scope_new,
     kclvm_scope_set,
+    kclvm_semver_compare,
+    kclvm_semver_is_valid,
+    kclvm_semver_normalize,
+    kclvm_semver_parse,
+    kclvm_semver_satisfies,
     kclvm_template_execute,
     kclvm_template_html_escape,
+    kclvm_toml_decode,
+    kclvm_toml_dump_to_file,
+    kclvm_toml_encode,
+    kclvm_toml_validate,
+    kclvm_units_parse,
     kclvm_units_to_G,
     kclvm_units_to_Gi,
     kclvm_units_to_K,
@@ -261,6 +293,12 @@ pub enum ApiFunc {
     kclvm_units_to_m,
     kclvm_units_to_n,
     kclvm_units_to_u,
+    kclvm_url_decode,
+    kclvm_url_encode,
+    kclvm_url_join,
+    kclvm_url_parse,
+    kclvm_uuid_v4,
+    kclvm_uuid_v5,
     kclvm_value_Bool,
     kclvm_value_Decorator,
     kclvm_value_Dict,
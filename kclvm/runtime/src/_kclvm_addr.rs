@@ -73,6 +73,7 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_builtin_typeof" => crate::kclvm_builtin_typeof as *const () as u64,
         "kclvm_builtin_zip" => crate::kclvm_builtin_zip as *const () as u64,
         "kclvm_config_attr_map" => crate::kclvm_config_attr_map as *const () as u64,
+        "kclvm_config_secret_attr" => crate::kclvm_config_secret_attr as *const () as u64,
         "kclvm_context_delete" => crate::kclvm_context_delete as *const () as u64,
         "kclvm_context_invoke" => crate::kclvm_context_invoke as *const () as u64,
         "kclvm_context_new" => crate::kclvm_context_new as *const () as u64,
@@ -107,10 +108,14 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_convert_collection_value" => {
             crate::kclvm_convert_collection_value as *const () as u64
         }
+        "kclvm_crypto_argon2_verify" => crate::kclvm_crypto_argon2_verify as *const () as u64,
+        "kclvm_crypto_bcrypt_verify" => crate::kclvm_crypto_bcrypt_verify as *const () as u64,
         "kclvm_crypto_blake3" => crate::kclvm_crypto_blake3 as *const () as u64,
         "kclvm_crypto_fileblake3" => crate::kclvm_crypto_fileblake3 as *const () as u64,
         "kclvm_crypto_filesha256" => crate::kclvm_crypto_filesha256 as *const () as u64,
         "kclvm_crypto_filesha512" => crate::kclvm_crypto_filesha512 as *const () as u64,
+        "kclvm_crypto_hmac_sha256" => crate::kclvm_crypto_hmac_sha256 as *const () as u64,
+        "kclvm_crypto_hmac_sha512" => crate::kclvm_crypto_hmac_sha512 as *const () as u64,
         "kclvm_crypto_md5" => crate::kclvm_crypto_md5 as *const () as u64,
         "kclvm_crypto_sha1" => crate::kclvm_crypto_sha1 as *const () as u64,
         "kclvm_crypto_sha224" => crate::kclvm_crypto_sha224 as *const () as u64,
@@ -123,6 +128,12 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_datetime_ticks" => crate::kclvm_datetime_ticks as *const () as u64,
         "kclvm_datetime_today" => crate::kclvm_datetime_today as *const () as u64,
         "kclvm_datetime_validate" => crate::kclvm_datetime_validate as *const () as u64,
+        "kclvm_decimal_add" => crate::kclvm_decimal_add as *const () as u64,
+        "kclvm_decimal_div" => crate::kclvm_decimal_div as *const () as u64,
+        "kclvm_decimal_format" => crate::kclvm_decimal_format as *const () as u64,
+        "kclvm_decimal_mul" => crate::kclvm_decimal_mul as *const () as u64,
+        "kclvm_decimal_round" => crate::kclvm_decimal_round as *const () as u64,
+        "kclvm_decimal_sub" => crate::kclvm_decimal_sub as *const () as u64,
         "kclvm_default_collection_insert_int_pointer" => {
             crate::kclvm_default_collection_insert_int_pointer as *const () as u64
         }
@@ -172,6 +183,9 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_json_dump_to_file" => crate::kclvm_json_dump_to_file as *const () as u64,
         "kclvm_json_encode" => crate::kclvm_json_encode as *const () as u64,
         "kclvm_json_validate" => crate::kclvm_json_validate as *const () as u64,
+        "kclvm_jsonpath_get" => crate::kclvm_jsonpath_get as *const () as u64,
+        "kclvm_jsonpath_query" => crate::kclvm_jsonpath_query as *const () as u64,
+        "kclvm_jsonpath_set" => crate::kclvm_jsonpath_set as *const () as u64,
         "kclvm_list_append" => crate::kclvm_list_append as *const () as u64,
         "kclvm_list_append_bool" => crate::kclvm_list_append_bool as *const () as u64,
         "kclvm_list_append_float" => crate::kclvm_list_append_float as *const () as u64,
@@ -208,9 +222,13 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_math_pow" => crate::kclvm_math_pow as *const () as u64,
         "kclvm_math_sqrt" => crate::kclvm_math_sqrt as *const () as u64,
         "kclvm_net_IP_string" => crate::kclvm_net_IP_string as *const () as u64,
+        "kclvm_net_contains" => crate::kclvm_net_contains as *const () as u64,
         "kclvm_net_fqdn" => crate::kclvm_net_fqdn as *const () as u64,
+        "kclvm_net_hosts_in_CIDR" => crate::kclvm_net_hosts_in_CIDR as *const () as u64,
         "kclvm_net_is_IP" => crate::kclvm_net_is_IP as *const () as u64,
+        "kclvm_net_is_IP_in_CIDR" => crate::kclvm_net_is_IP_in_CIDR as *const () as u64,
         "kclvm_net_is_IPv4" => crate::kclvm_net_is_IPv4 as *const () as u64,
+        "kclvm_net_is_IPv6" => crate::kclvm_net_is_IPv6 as *const () as u64,
         "kclvm_net_is_global_unicast_IP" => {
             crate::kclvm_net_is_global_unicast_IP as *const () as u64
         }
@@ -226,18 +244,22 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_net_is_loopback_IP" => crate::kclvm_net_is_loopback_IP as *const () as u64,
         "kclvm_net_is_multicast_IP" => crate::kclvm_net_is_multicast_IP as *const () as u64,
         "kclvm_net_is_unspecified_IP" => crate::kclvm_net_is_unspecified_IP as *const () as u64,
-        "kclvm_net_parse_CIDR" => crate::kclvm_net_parse_CIDR as *const () as u64,
-        "kclvm_net_hosts_in_CIDR" => crate::kclvm_net_hosts_in_CIDR as *const () as u64,
-        "kclvm_net_subnets_from_CIDR" => crate::kclvm_net_subnets_from_CIDR as *const () as u64,
-        "kclvm_net_is_IP_in_CIDR" => crate::kclvm_net_is_IP_in_CIDR as *const () as u64,
         "kclvm_net_join_host_port" => crate::kclvm_net_join_host_port as *const () as u64,
+        "kclvm_net_parse_CIDR" => crate::kclvm_net_parse_CIDR as *const () as u64,
         "kclvm_net_parse_IP" => crate::kclvm_net_parse_IP as *const () as u64,
         "kclvm_net_split_host_port" => crate::kclvm_net_split_host_port as *const () as u64,
+        "kclvm_net_subnets" => crate::kclvm_net_subnets as *const () as u64,
+        "kclvm_net_subnets_from_CIDR" => crate::kclvm_net_subnets_from_CIDR as *const () as u64,
         "kclvm_net_to_IP16" => crate::kclvm_net_to_IP16 as *const () as u64,
         "kclvm_net_to_IP4" => crate::kclvm_net_to_IP4 as *const () as u64,
         "kclvm_plugin_init" => crate::kclvm_plugin_init as *const () as u64,
         "kclvm_plugin_invoke" => crate::kclvm_plugin_invoke as *const () as u64,
         "kclvm_plugin_invoke_json" => crate::kclvm_plugin_invoke_json as *const () as u64,
+        "kclvm_random_choice" => crate::kclvm_random_choice as *const () as u64,
+        "kclvm_random_randint" => crate::kclvm_random_randint as *const () as u64,
+        "kclvm_random_random" => crate::kclvm_random_random as *const () as u64,
+        "kclvm_random_seed" => crate::kclvm_random_seed as *const () as u64,
+        "kclvm_random_shuffle" => crate::kclvm_random_shuffle as *const () as u64,
         "kclvm_regex_compile" => crate::kclvm_regex_compile as *const () as u64,
         "kclvm_regex_findall" => crate::kclvm_regex_findall as *const () as u64,
         "kclvm_regex_match" => crate::kclvm_regex_match as *const () as u64,
@@ -261,8 +283,18 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_scope_get" => crate::kclvm_scope_get as *const () as u64,
         "kclvm_scope_new" => crate::kclvm_scope_new as *const () as u64,
         "kclvm_scope_set" => crate::kclvm_scope_set as *const () as u64,
+        "kclvm_semver_compare" => crate::kclvm_semver_compare as *const () as u64,
+        "kclvm_semver_is_valid" => crate::kclvm_semver_is_valid as *const () as u64,
+        "kclvm_semver_normalize" => crate::kclvm_semver_normalize as *const () as u64,
+        "kclvm_semver_parse" => crate::kclvm_semver_parse as *const () as u64,
+        "kclvm_semver_satisfies" => crate::kclvm_semver_satisfies as *const () as u64,
         "kclvm_template_execute" => crate::kclvm_template_execute as *const () as u64,
         "kclvm_template_html_escape" => crate::kclvm_template_html_escape as *const () as u64,
+        "kclvm_toml_decode" => crate::kclvm_toml_decode as *const () as u64,
+        "kclvm_toml_dump_to_file" => crate::kclvm_toml_dump_to_file as *const () as u64,
+        "kclvm_toml_encode" => crate::kclvm_toml_encode as *const () as u64,
+        "kclvm_toml_validate" => crate::kclvm_toml_validate as *const () as u64,
+        "kclvm_units_parse" => crate::kclvm_units_parse as *const () as u64,
         "kclvm_units_to_G" => crate::kclvm_units_to_G as *const () as u64,
         "kclvm_units_to_Gi" => crate::kclvm_units_to_Gi as *const () as u64,
         "kclvm_units_to_K" => crate::kclvm_units_to_K as *const () as u64,
@@ -276,6 +308,12 @@ pub fn _kclvm_get_fn_ptr_by_name(name: &str) -> u64 {
         "kclvm_units_to_m" => crate::kclvm_units_to_m as *const () as u64,
         "kclvm_units_to_n" => crate::kclvm_units_to_n as *const () as u64,
         "kclvm_units_to_u" => crate::kclvm_units_to_u as *const () as u64,
+        "kclvm_url_decode" => crate::kclvm_url_decode as *const () as u64,
+        "kclvm_url_encode" => crate::kclvm_url_encode as *const () as u64,
+        "kclvm_url_join" => crate::kclvm_url_join as *const () as u64,
+        "kclvm_url_parse" => crate::kclvm_url_parse as *const () as u64,
+        "kclvm_uuid_v4" => crate::kclvm_uuid_v4 as *const () as u64,
+        "kclvm_uuid_v5" => crate::kclvm_uuid_v5 as *const () as u64,
         "kclvm_value_Bool" => crate::kclvm_value_Bool as *const () as u64,
         "kclvm_value_Decorator" => crate::kclvm_value_Decorator as *const () as u64,
         "kclvm_value_Dict" => crate::kclvm_value_Dict as *const () as u64,
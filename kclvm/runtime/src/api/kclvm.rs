@@ -1,13 +1,17 @@
 //! Copyright The KCL Authors. All rights reserved.
 
-use crate::{new_mut_ptr, val_plan::PlanOptions, IndexMap};
+use crate::{new_mut_ptr, val_plan::PlanOptions, IndexMap, PluginHandler, SandboxPolicy};
 use generational_arena::Index;
 use indexmap::IndexSet;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
+use rand_chacha::ChaCha8Rng;
 use serde::{Deserialize, Serialize};
 use std::collections::{HashMap, HashSet};
 use std::panic::{RefUnwindSafe, UnwindSafe};
 use std::rc::Rc;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use std::{
     cell::RefCell,
     cmp::Ordering,
@@ -101,6 +105,9 @@ impl PartialEq for ValueRef {
 
 impl Ord for ValueRef {
     fn cmp(&self, other: &ValueRef) -> Ordering {
+        if let Some(ord) = self.bigint_partial_cmp(other) {
+            return ord;
+        }
         let ord = match *self.rc.borrow() {
             Value::int_value(a) => match *other.rc.borrow() {
                 Value::int_value(b) => a.partial_cmp(&b),
@@ -168,6 +175,7 @@ impl Hash for ValueRef {
             Value::func_value(ref v) => {
                 v.fn_ptr.hash(state);
             }
+            Value::bigint_value(v) => v.to_f64().unwrap_or_default().to_bits().hash(state),
         }
     }
 }
@@ -212,6 +220,7 @@ pub enum Value {
     schema_value(Box<SchemaValue>),
     func_value(Box<FuncValue>),
     unit_value(f64, i64, String), // (Real value, raw value, unit string)
+    bigint_value(BigInt),         // Arbitrary-precision int out of the i64 range
 }
 
 impl Default for Value {
@@ -232,6 +241,8 @@ pub struct DictValue {
     pub insert_indexs: IndexMap<String, i32>,
     /// Attribute type annotation string mapping.
     pub attr_map: IndexMap<String, String>,
+    /// The attribute names with the `@secret` decorator.
+    pub secret_attrs: IndexSet<String>,
     /// The runtime dict to schema reflect type string.
     pub potential_schema: Option<String>,
 }
@@ -312,6 +323,37 @@ pub struct ContextConfig {
     pub debug_mode: bool,
     pub strict_range_check: bool,
     pub disable_schema_check: bool,
+    /// Whether the int operations overflowing i64 are promoted to the arbitrary-precision ints.
+    pub big_int: bool,
+    /// The execution limits of the evaluation.
+    pub limits: RuntimeLimits,
+    /// The sandbox policy of the file, environment and plugin accesses.
+    pub sandbox: SandboxPolicy,
+    /// The seed of the generated random values, and `None` denotes a random seed.
+    pub random_seed: Option<u64>,
+    /// Whether to record the source positions of the log messages.
+    pub log_positions: bool,
+    /// Whether to record the evaluation time of the imported packages.
+    pub profile: bool,
+}
+
+/// The runtime execution limits, and `None` denotes no limit.
+#[derive(PartialEq, Eq, Clone, Default, Debug)]
+pub struct RuntimeLimits {
+    /// The maximum evaluation time.
+    pub timeout: Option<Duration>,
+    /// The maximum depth of the lambda, schema and rule calls.
+    pub max_call_depth: Option<usize>,
+    /// The maximum number of the list and dict items allocated in the evaluation.
+    pub max_allocations: Option<usize>,
+}
+
+impl RuntimeLimits {
+    /// Whether any of the limits is set.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        self.timeout.is_some() || self.max_call_depth.is_some() || self.max_allocations.is_some()
+    }
 }
 
 #[derive(PartialEq, Eq, Clone, Debug)]
@@ -330,6 +372,34 @@ impl Default for ContextBuffer {
     }
 }
 
+/// A message logged by the `print` function at runtime.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+pub struct LogRecord {
+    /// The message including the end string e.g., the newline.
+    pub message: String,
+    /// The source position of the `print` call, which is empty when the source
+    /// positions are not recorded.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub filename: String,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub line: i32,
+    #[serde(default, skip_serializing_if = "is_zero")]
+    pub column: i32,
+}
+
+#[inline]
+fn is_zero(v: &i32) -> bool {
+    *v == 0
+}
+
+/// The log agent function which receives the JSON string of each [LogRecord] as soon
+/// as it is logged.
+pub type LogHandler = extern "C" fn(record_json: *const std::os::raw::c_char);
+
+/// The log listener function which receives each [LogRecord] as soon as it is logged,
+/// which is used by the Rust callers instead of the log agent.
+pub type LogListener = Arc<dyn Fn(&LogRecord) + Send + Sync>;
+
 /// Plugin functions
 pub type PluginFunction =
     Arc<dyn Fn(&Context, &ValueRef, &ValueRef) -> anyhow::Result<ValueRef> + Send + Sync>;
@@ -361,16 +431,34 @@ pub struct Context {
     pub objects: IndexSet<usize>,
     /// Log message used to store print results.
     pub log_message: String,
+    /// The log records of the print results in order.
+    pub log_records: Vec<LogRecord>,
+    /// The log agent of the context, which streams the log records to the clients.
+    pub log_handler: Option<LogHandler>,
+    /// The log listener of the context, which streams the log records to the Rust callers.
+    pub log_listener: Option<LogListener>,
     /// Planned JSON result
     pub json_result: String,
     /// Planned YAML result
     pub yaml_result: String,
+    /// Planned provenance sidecar JSON result
+    pub provenance_result: String,
     /// Panic information at runtime
     pub panic_info: PanicInfo,
     /// Planning options
     pub plan_opts: PlanOptions,
     /// Builtin plugin functions, the key of the map is the form <module_name>.<module_func> e.g., `hello.say_hello`
     pub plugin_functions: IndexMap<String, PluginFunction>,
+    /// The plugin agent of the context, default is the global plugin agent.
+    pub plugin_handler: Option<PluginHandler>,
+    /// The evaluation deadline of the timeout limit.
+    pub deadline: Option<Instant>,
+    /// The number of the list and dict items allocated in the evaluation.
+    pub allocations: usize,
+    /// The random number generator of the random values.
+    pub rng: Option<ChaCha8Rng>,
+    /// The compiled patterns of the regex system module, and the key is the pattern.
+    pub regex_cache: HashMap<String, Arc<fancy_regex::Regex>>,
 }
 
 impl UnwindSafe for Context {}
@@ -8,3 +8,6 @@ pub use self::utils::*;
 
 pub mod error;
 pub use self::error::*;
+
+pub mod sandbox;
+pub use self::sandbox::*;
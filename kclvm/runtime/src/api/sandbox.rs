@@ -0,0 +1,186 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use std::path::{Component, Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Context;
+
+/// The sandbox policy of the runtime file, environment and plugin accesses, and
+/// the default policy allows all the accesses.
+#[derive(PartialEq, Eq, Clone, Default, Debug, Serialize, Deserialize)]
+#[serde(default)]
+pub struct SandboxPolicy {
+    /// The paths and their sub paths which can be accessed, and `None` denotes all
+    /// the paths except the denied paths.
+    pub allow_read_paths: Option<Vec<String>>,
+    /// The paths and their sub paths which can not be accessed.
+    pub deny_read_paths: Vec<String>,
+    /// Deny writing, creating, moving and deleting the files. The writes are always
+    /// denied on the wasm32 target.
+    pub deny_write: bool,
+    /// Deny reading the environment variables. The environment variables are always
+    /// denied on the wasm32 target.
+    pub deny_env: bool,
+    /// Deny calling the plugin functions, which are the only way for the KCL code
+    /// to spawn subprocesses or to call the remote services.
+    pub deny_plugins: bool,
+    /// Deny the network accesses, which are the host name and DNS lookups of the
+    /// `net.fqdn` function. The network is always denied on the wasm32 target.
+    pub deny_network: bool,
+}
+
+impl SandboxPolicy {
+    /// Whether the policy denies any access.
+    #[inline]
+    pub fn is_enabled(&self) -> bool {
+        *self != Self::default()
+    }
+
+    /// Returns the policy with the defaults of the compilation target applied. On the
+    /// wasm32 target, the network accesses, the file writes and the environment variable
+    /// reads are denied, and the file reads are left to the preopened directories of the
+    /// WASI host.
+    pub fn with_target_defaults(mut self) -> Self {
+        if cfg!(target_arch = "wasm32") {
+            self.deny_network = true;
+            self.deny_write = true;
+            self.deny_env = true;
+        }
+        self
+    }
+
+    /// Whether the path can be accessed under the policy. The relative paths are
+    /// relative to the current working directory and the symbolic links are resolved.
+    pub fn is_path_allowed(&self, path: &str) -> bool {
+        let path = normalize_path(path);
+        let contains = |paths: &[String]| paths.iter().any(|p| path.starts_with(normalize_path(p)));
+        if contains(&self.deny_read_paths) {
+            return false;
+        }
+        match &self.allow_read_paths {
+            Some(paths) => contains(paths),
+            None => true,
+        }
+    }
+}
+
+/// Returns the absolute path with the symbolic links of its existing ancestors resolved
+/// and the `.` and `..` components removed.
+fn normalize_path(path: &str) -> PathBuf {
+    let path = Path::new(path);
+    let path = if path.is_absolute() {
+        path.to_path_buf()
+    } else {
+        std::env::current_dir().unwrap_or_default().join(path)
+    };
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir => {}
+            Component::ParentDir => {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    // Resolve the symbolic links of the longest existing ancestor.
+    for ancestor in normalized.ancestors() {
+        if let Ok(canonical) = ancestor.canonicalize() {
+            return match normalized.strip_prefix(ancestor) {
+                Ok(rest) if !rest.as_os_str().is_empty() => canonical.join(rest),
+                _ => canonical,
+            };
+        }
+    }
+    normalized
+}
+
+impl Context {
+    /// Check the file read access under the sandbox policy.
+    pub fn check_read_access(&self, path: &str) {
+        if !self.cfg.sandbox.is_path_allowed(path) {
+            panic!("the sandbox policy denies the access to '{path}'");
+        }
+    }
+
+    /// Check the file write access under the sandbox policy.
+    pub fn check_write_access(&self, path: &str) {
+        if self.cfg.sandbox.deny_write {
+            panic!("the sandbox policy denies writing '{path}'");
+        }
+        self.check_read_access(path);
+    }
+
+    /// Check the environment variable access under the sandbox policy.
+    pub fn check_env_access(&self, key: &str) {
+        if self.cfg.sandbox.deny_env {
+            panic!("the sandbox policy denies reading the environment variable '{key}'");
+        }
+    }
+
+    /// Check the plugin function call under the sandbox policy.
+    pub fn check_plugin_access(&self, method: &str) {
+        if self.cfg.sandbox.deny_plugins {
+            panic!("the sandbox policy denies calling the plugin function '{method}'");
+        }
+    }
+
+    /// Check the network access of the function under the sandbox policy.
+    pub fn check_network_access(&self, function: &str) {
+        if self.cfg.sandbox.deny_network {
+            panic!("the sandbox policy denies the network access of '{function}'");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_sandbox {
+    use super::*;
+
+    #[test]
+    fn test_is_path_allowed() {
+        let policy = SandboxPolicy::default();
+        assert!(!policy.is_enabled());
+        assert!(policy.is_path_allowed("/etc/passwd"));
+
+        let policy = SandboxPolicy {
+            allow_read_paths: Some(vec!["/data".to_string()]),
+            deny_read_paths: vec!["/data/secrets".to_string()],
+            ..Default::default()
+        };
+        assert!(policy.is_enabled());
+        assert!(policy.is_path_allowed("/data/app/main.json"));
+        assert!(policy.is_path_allowed("/data"));
+        assert!(!policy.is_path_allowed("/database"));
+        assert!(!policy.is_path_allowed("/data/secrets/key"));
+        assert!(!policy.is_path_allowed("/data/app/../secrets/key"));
+        assert!(!policy.is_path_allowed("/etc/passwd"));
+        assert!(!policy.is_path_allowed("relative/path"));
+    }
+
+    #[test]
+    fn test_with_target_defaults() {
+        let policy = SandboxPolicy::default().with_target_defaults();
+        assert_eq!(policy.deny_network, cfg!(target_arch = "wasm32"));
+        assert_eq!(policy.deny_write, cfg!(target_arch = "wasm32"));
+        assert_eq!(policy.deny_env, cfg!(target_arch = "wasm32"));
+        assert!(policy.allow_read_paths.is_none());
+    }
+
+    #[test]
+    #[should_panic(expected = "the sandbox policy denies writing 'out.json'")]
+    fn test_check_write_access() {
+        let mut ctx = Context::new();
+        ctx.cfg.sandbox.deny_write = true;
+        ctx.check_write_access("out.json");
+    }
+
+    #[test]
+    #[should_panic(expected = "the sandbox policy denies the network access of 'net.fqdn'")]
+    fn test_check_network_access() {
+        let mut ctx = Context::new();
+        ctx.cfg.sandbox.deny_network = true;
+        ctx.check_network_access("net.fqdn");
+    }
+}
@@ -3,8 +3,12 @@
 pub mod api;
 pub use api::*;
 use std::fmt;
+use std::time::Instant;
 
-use crate::{kclvm_value_delete, kclvm_value_ref_t, BacktraceFrame, PanicInfo, RuntimePanicRecord};
+use crate::{
+    kclvm_value_delete, kclvm_value_ref_t, BacktraceFrame, LogHandler, LogRecord, PanicInfo,
+    RuntimePanicRecord,
+};
 
 impl fmt::Display for PanicInfo {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -72,6 +76,20 @@ impl From<&str> for PanicInfo {
     }
 }
 
+/// Returns the log handler of the function address, and `0` denotes no handler.
+///
+/// # Safety
+///
+/// The non-zero `ptr` must be the address of a function with the [LogHandler]
+/// signature, which stays valid while the handler is used.
+pub unsafe fn log_handler_from_ptr(ptr: u64) -> Option<LogHandler> {
+    if ptr == 0 {
+        None
+    } else {
+        Some(std::mem::transmute::<usize, LogHandler>(ptr as usize))
+    }
+}
+
 impl crate::Context {
     pub fn into_raw(self) -> *mut Self {
         Box::into_raw(Box::new(self))
@@ -165,14 +183,9 @@ impl crate::Context {
         self.panic_info.__kcl_PanicInfo__ = true;
 
         self.panic_info.message = record.message.clone();
-        if self.cfg.debug_mode {
-            self.panic_info.backtrace = self.backtrace.clone();
-            self.panic_info.backtrace.push(BacktraceFrame {
-                file: self.panic_info.kcl_file.clone(),
-                func: self.panic_info.kcl_func.clone(),
-                col: self.panic_info.kcl_col,
-                line: self.panic_info.kcl_line,
-            });
+        // The backtrace of the execution limit errors has been recorded before unwinding.
+        if self.cfg.debug_mode && self.panic_info.backtrace.is_empty() {
+            self.record_backtrace();
         }
 
         self.panic_info.rust_file = record.rust_file.clone();
@@ -180,6 +193,105 @@ impl crate::Context {
         self.panic_info.rust_col = record.rust_col;
     }
 
+    /// Whether to record the backtrace frames at runtime, which is required by the
+    /// debug mode and the execution limit errors.
+    #[inline]
+    pub fn is_backtrace_enabled(&self) -> bool {
+        self.cfg.debug_mode || self.cfg.limits.is_enabled()
+    }
+
+    /// Start the evaluation deadline clock of the timeout limit.
+    pub fn start_deadline(&mut self) {
+        self.deadline = self
+            .cfg
+            .limits
+            .timeout
+            .map(|timeout| Instant::now() + timeout);
+        self.allocations = 0;
+    }
+
+    /// Check the timeout limit, and raise an evaluation error when the deadline is exceeded.
+    pub fn check_deadline(&mut self) {
+        if let (Some(deadline), Some(timeout)) = (self.deadline, self.cfg.limits.timeout) {
+            if Instant::now() >= deadline {
+                self.raise_limit_error(&format!(
+                    "evaluation timeout: the execution exceeds the timeout of {} ms",
+                    timeout.as_millis()
+                ));
+            }
+        }
+    }
+
+    /// Check the call depth limit with the current backtrace depth.
+    pub fn check_call_depth(&mut self) {
+        if let Some(max_call_depth) = self.cfg.limits.max_call_depth {
+            if self.backtrace.len() > max_call_depth {
+                self.raise_limit_error(&format!(
+                    "maximum call depth exceeded: the call depth exceeds the limit of {max_call_depth}"
+                ));
+            }
+        }
+        self.check_deadline();
+    }
+
+    /// Record `n` allocated list or dict items and check the allocation limit.
+    pub fn record_allocations(&mut self, n: usize) {
+        if let Some(max_allocations) = self.cfg.limits.max_allocations {
+            self.allocations = self.allocations.saturating_add(n);
+            if self.allocations > max_allocations {
+                self.raise_limit_error(&format!(
+                    "allocation limit exceeded: the evaluation allocates more than {max_allocations} list or dict items"
+                ));
+            }
+        }
+        self.check_deadline();
+    }
+
+    /// Record the current backtrace into the panic information.
+    fn record_backtrace(&mut self) {
+        self.panic_info.backtrace = self.backtrace.clone();
+        self.panic_info.backtrace.push(BacktraceFrame {
+            file: self.panic_info.kcl_file.clone(),
+            func: self.panic_info.kcl_func.clone(),
+            col: self.panic_info.kcl_col,
+            line: self.panic_info.kcl_line,
+        });
+    }
+
+    /// Raise the execution limit error with the backtrace, and the evaluation is aborted
+    /// unless the error is caught by `runtime.catch`.
+    fn raise_limit_error(&mut self, msg: &str) -> ! {
+        self.set_err_type(&crate::RuntimeErrorType::EvaluationError);
+        self.record_backtrace();
+        panic!("{}", msg)
+    }
+
+    /// Log the message of the `print` function, which is appended to the log message and
+    /// the log records in order, and streamed to the log agent and the log listener if any.
+    pub fn log(&mut self, message: &str) {
+        self.log_message.push_str(message);
+        let mut record = LogRecord {
+            message: message.to_string(),
+            ..Default::default()
+        };
+        if self.cfg.log_positions {
+            record.filename = self.panic_info.kcl_file.clone();
+            record.line = self.panic_info.kcl_line;
+            record.column = self.panic_info.kcl_col;
+        }
+        if let Some(handler) = self.log_handler {
+            if let Ok(json) = serde_json::to_string(&record) {
+                if let Ok(json) = std::ffi::CString::new(json) {
+                    handler(json.as_ptr());
+                }
+            }
+        }
+        if let Some(listener) = &self.log_listener {
+            listener(&record);
+        }
+        self.log_records.push(record);
+    }
+
     pub fn gc(&self) {
         unsafe {
             for o in &self.objects {
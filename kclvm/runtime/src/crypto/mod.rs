@@ -9,10 +9,11 @@ use core::panic;
 use std::{fs::File, io::Read};
 
 use crate::encoding::encode_text;
+use argon2::{Argon2, PasswordHash, PasswordVerifier};
+use hmac::{Hmac, Mac};
 use sha2::{Digest, Sha224, Sha256, Sha384, Sha512};
 
 use crate::*;
-use uuid::Uuid;
 
 #[allow(non_camel_case_types)]
 type kclvm_value_ref_t = ValueRef;
@@ -227,7 +228,8 @@ pub extern "C" fn kclvm_crypto_uuid(
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
     let ctx = mut_ptr_as_ref(ctx);
-    return ValueRef::str(Uuid::new_v4().to_string().as_ref()).into_raw(ctx);
+    let uuid = ctx.new_uuid_v4();
+    return ValueRef::str(uuid.to_string().as_ref()).into_raw(ctx);
 }
 
 #[no_mangle]
@@ -242,6 +244,7 @@ pub extern "C" fn kclvm_crypto_filesha256(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&filepath);
         // Open the file
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
@@ -282,6 +285,7 @@ pub extern "C" fn kclvm_crypto_filesha512(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&filepath);
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
 
@@ -320,6 +324,7 @@ pub extern "C" fn kclvm_crypto_fileblake3(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(filepath) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&filepath);
         let mut file = File::open(&filepath)
             .unwrap_or_else(|e| panic!("failed to access file '{}': {}", filepath, e));
 
@@ -340,3 +345,125 @@ pub extern "C" fn kclvm_crypto_fileblake3(
     }
     panic!("fileblake3() missing 1 required positional argument: 'filepath'");
 }
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| format!("{byte:02x}")).collect()
+}
+
+/// Returns the hex HMAC of the `value` with the `key`, and both of them are encoded
+/// with the `encoding`.
+fn hmac_hex<M: Mac + hmac::digest::KeyInit>(
+    args: &ValueRef,
+    kwargs: &ValueRef,
+    func: &str,
+) -> String {
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("value")),
+        get_call_arg_str(args, kwargs, 1, Some("key")),
+    ) {
+        (Some(value), Some(key)) => {
+            let encoding = get_call_arg_str(args, kwargs, 2, Some("encoding"));
+            let value = encode_text(&value, encoding.clone()).unwrap();
+            let key = encode_text(&key, encoding).unwrap();
+            let mut mac = <M as Mac>::new_from_slice(&key)
+                .unwrap_or_else(|e| panic!("{func}() invalid key: {e}"));
+            mac.update(&value);
+            to_hex(&mac.finalize().into_bytes())
+        }
+        _ => panic!("{func}() missing 2 required positional arguments: 'value' and 'key'"),
+    }
+}
+
+// hmac_sha256(value: str, key: str, encoding: str = "utf-8") -> str
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_crypto_hmac_sha256(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let hex = hmac_hex::<Hmac<Sha256>>(args, kwargs, "hmac_sha256");
+    ValueRef::str(&hex).into_raw(ctx)
+}
+
+// hmac_sha512(value: str, key: str, encoding: str = "utf-8") -> str
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_crypto_hmac_sha512(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let hex = hmac_hex::<Hmac<Sha512>>(args, kwargs, "hmac_sha512");
+    ValueRef::str(&hex).into_raw(ctx)
+}
+
+// bcrypt_verify(password: str, hash: str) -> bool
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_crypto_bcrypt_verify(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("password")),
+        get_call_arg_str(args, kwargs, 1, Some("hash")),
+    ) {
+        (Some(password), Some(hash)) => {
+            let verified = bcrypt::verify(&password, &hash)
+                .unwrap_or_else(|e| panic!("bcrypt_verify() invalid bcrypt hash '{hash}': {e}"));
+            ValueRef::bool(verified).into_raw(ctx)
+        }
+        _ => {
+            panic!("bcrypt_verify() missing 2 required positional arguments: 'password' and 'hash'")
+        }
+    }
+}
+
+// argon2_verify(password: str, hash: str) -> bool
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_crypto_argon2_verify(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("password")),
+        get_call_arg_str(args, kwargs, 1, Some("hash")),
+    ) {
+        (Some(password), Some(hash)) => {
+            // The algorithm, version and parameters are read from the PHC string hash.
+            let parsed = PasswordHash::new(&hash)
+                .unwrap_or_else(|e| panic!("argon2_verify() invalid argon2 hash '{hash}': {e}"));
+            let verified = Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok();
+            ValueRef::bool(verified).into_raw(ctx)
+        }
+        _ => {
+            panic!("argon2_verify() missing 2 required positional arguments: 'password' and 'hash'")
+        }
+    }
+}
@@ -0,0 +1,213 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The decimal system module evaluates the exact decimal arithmetic on the numbers
+//! and the number strings, e.g., `decimal.add(0.1, 0.2)` is `0.3` instead of
+//! `0.30000000000000004`. The decimals have 28 significant digits at most.
+
+use std::str::FromStr;
+
+use rust_decimal::{Decimal, RoundingStrategy};
+
+use crate::*;
+
+/// Convert the int, float or number string to a decimal. The float is converted from
+/// its shortest representation e.g., `0.1`, instead of its exact binary value.
+fn to_decimal(value: &ValueRef) -> Decimal {
+    match &*value.rc.borrow() {
+        Value::int_value(v) => Decimal::from(*v),
+        Value::float_value(v) if v.is_finite() => parse_decimal(&v.to_string()),
+        Value::str_value(v) => parse_decimal(v.trim()),
+        _ => panic!(
+            "expected a number or a number string, got '{}'",
+            value.type_str()
+        ),
+    }
+}
+
+fn parse_decimal(s: &str) -> Decimal {
+    Decimal::from_str(s)
+        .or_else(|_| Decimal::from_scientific(s))
+        .unwrap_or_else(|_| panic!("invalid decimal literal '{s}'"))
+}
+
+/// Convert the decimal to the nearest float.
+fn to_float(d: &Decimal) -> f64 {
+    d.to_string().parse().unwrap()
+}
+
+fn to_rounding_strategy(rounding: &str) -> RoundingStrategy {
+    match rounding {
+        "half_even" => RoundingStrategy::MidpointNearestEven,
+        "half_up" => RoundingStrategy::MidpointAwayFromZero,
+        "half_down" => RoundingStrategy::MidpointTowardZero,
+        "down" => RoundingStrategy::ToZero,
+        "up" => RoundingStrategy::AwayFromZero,
+        "floor" => RoundingStrategy::ToNegativeInfinity,
+        "ceiling" => RoundingStrategy::ToPositiveInfinity,
+        _ => panic!(
+            "invalid rounding mode '{rounding}', expected one of 'half_even', 'half_up', 'half_down', 'down', 'up', 'floor' and 'ceiling'"
+        ),
+    }
+}
+
+/// Round the float to the decimal places with the rounding half to even, and the
+/// float out of the decimal range is returned as is.
+pub fn round_float(v: f64, places: u32) -> f64 {
+    if !v.is_finite() {
+        return v;
+    }
+    match Decimal::from_str(&v.to_string()) {
+        Ok(d) => to_float(&d.round_dp_with_strategy(places, RoundingStrategy::MidpointNearestEven)),
+        Err(_) => v,
+    }
+}
+
+fn get_binary_args(args: &ValueRef, kwargs: &ValueRef, func: &str) -> (Decimal, Decimal) {
+    match (
+        get_call_arg(args, kwargs, 0, Some("x")),
+        get_call_arg(args, kwargs, 1, Some("y")),
+    ) {
+        (Some(x), Some(y)) => (to_decimal(&x), to_decimal(&y)),
+        _ => panic!("{func}() missing 2 required positional arguments: 'x' and 'y'"),
+    }
+}
+
+fn get_rounding_args(args: &ValueRef, kwargs: &ValueRef) -> (u32, RoundingStrategy) {
+    let places = match get_call_arg_int(args, kwargs, 1, Some("places")) {
+        Some(places) if places >= 0 => places as u32,
+        Some(places) => panic!("the decimal places must be non-negative, got {places}"),
+        None => 0,
+    };
+    let rounding = get_call_arg_str(args, kwargs, 2, Some("rounding"))
+        .unwrap_or_else(|| "half_even".to_string());
+    (places, to_rounding_strategy(&rounding))
+}
+
+/// Returns the exact sum of `x` and `y`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_add(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let (x, y) = get_binary_args(args, kwargs, "add");
+    match x.checked_add(y) {
+        Some(v) => ValueRef::float(to_float(&v)).into_raw(ctx),
+        None => panic!("add() decimal overflow"),
+    }
+}
+
+/// Returns the exact difference of `x` and `y`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_sub(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let (x, y) = get_binary_args(args, kwargs, "sub");
+    match x.checked_sub(y) {
+        Some(v) => ValueRef::float(to_float(&v)).into_raw(ctx),
+        None => panic!("sub() decimal overflow"),
+    }
+}
+
+/// Returns the exact product of `x` and `y`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_mul(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let (x, y) = get_binary_args(args, kwargs, "mul");
+    match x.checked_mul(y) {
+        Some(v) => ValueRef::float(to_float(&v)).into_raw(ctx),
+        None => panic!("mul() decimal overflow"),
+    }
+}
+
+/// Returns the quotient of `x` and `y` with 28 significant digits at most.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_div(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    let (x, y) = get_binary_args(args, kwargs, "div");
+    if y.is_zero() {
+        panic!("div() decimal division by zero");
+    }
+    match x.checked_div(y) {
+        Some(v) => ValueRef::float(to_float(&v)).into_raw(ctx),
+        None => panic!("div() decimal overflow"),
+    }
+}
+
+/// Round `x` to the decimal `places` using the `rounding` mode, default is rounding
+/// half to even.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_round(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(x) = get_call_arg(args, kwargs, 0, Some("x")) {
+        let (places, strategy) = get_rounding_args(args, kwargs);
+        let v = to_decimal(&x).round_dp_with_strategy(places, strategy);
+        return ValueRef::float(to_float(&v)).into_raw(ctx);
+    }
+    panic!("round() missing 1 required positional argument: 'x'");
+}
+
+/// Format `x` as a string with exactly the decimal `places` e.g., `"19.90"`, and
+/// without the trailing zeros when `places` is not given.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_decimal_format(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(x) = get_call_arg(args, kwargs, 0, Some("x")) {
+        let d = to_decimal(&x);
+        let s = match get_call_arg(args, kwargs, 1, Some("places")) {
+            Some(places) if !places.is_none_or_undefined() => {
+                let (places, strategy) = get_rounding_args(args, kwargs);
+                let mut d = d.round_dp_with_strategy(places, strategy);
+                d.rescale(places);
+                d.to_string()
+            }
+            _ => d.normalize().to_string(),
+        };
+        return ValueRef::str(&s).into_raw(ctx);
+    }
+    panic!("format() missing 1 required positional argument: 'x'");
+}
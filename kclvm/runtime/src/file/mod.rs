@@ -19,6 +19,7 @@ pub extern "C" fn kclvm_file_read(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(x) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&x);
         let contents = fs::read_to_string(&x)
             .unwrap_or_else(|e| panic!("failed to access the file '{}': {}", x, e));
 
@@ -46,6 +47,8 @@ pub extern "C" fn kclvm_file_glob(
     let mut matched_paths = vec![];
     for entry in glob(&pattern).unwrap_or_else(|e| panic!("Failed to read glob pattern: {}", e)) {
         match entry {
+            // The paths denied by the sandbox policy are not matched.
+            Ok(path) if !ctx.cfg.sandbox.is_path_allowed(&path.display().to_string()) => {}
             Ok(path) => matched_paths.push(path.display().to_string()),
             Err(e) => panic!("failed to access the file matching '{}': {}", pattern, e),
         }
@@ -106,6 +109,7 @@ pub extern "C" fn kclvm_file_exists(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&path);
         let exist = Path::new(&path).exists();
         return ValueRef::bool(exist).into_raw(ctx);
     }
@@ -127,6 +131,7 @@ pub extern "C" fn kclvm_file_abs(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&path);
         if let Ok(abs_path) = Path::new(&path).canonicalize() {
             return ValueRef::str(abs_path.to_str().unwrap()).into_raw(ctx);
         } else {
@@ -149,6 +154,7 @@ pub extern "C" fn kclvm_file_mkdir(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("directory")) {
+        ctx.check_write_access(&path);
         let exists = get_call_arg_bool(args, kwargs, 1, Some("exists")).unwrap_or_default();
         if let Err(e) = fs::create_dir_all(&path) {
             // Ignore the file exists error.
@@ -175,6 +181,7 @@ pub extern "C" fn kclvm_file_delete(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_write_access(&path);
         if let Err(e) = fs::remove_file(&path) {
             match e.kind() {
                 std::io::ErrorKind::NotFound => {
@@ -207,6 +214,8 @@ pub extern "C" fn kclvm_file_cp(
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
+            ctx.check_read_access(&src_path);
+            ctx.check_write_access(&dest_path);
             let src_path = Path::new(&src_path);
             let dest_path = Path::new(&dest_path);
             let result = if src_path.is_dir() {
@@ -244,6 +253,8 @@ pub extern "C" fn kclvm_file_mv(
 
     if let Some(src_path) = get_call_arg_str(args, kwargs, 0, Some("src")) {
         if let Some(dest_path) = get_call_arg_str(args, kwargs, 1, Some("dest")) {
+            ctx.check_write_access(&src_path);
+            ctx.check_write_access(&dest_path);
             if let Err(e) = fs::rename(&src_path, &dest_path) {
                 panic!("Failed to move '{}' to '{}': {}", src_path, dest_path, e);
             }
@@ -268,6 +279,7 @@ pub extern "C" fn kclvm_file_size(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
+        ctx.check_read_access(&path);
         let metadata = fs::metadata(&path);
         match metadata {
             Ok(metadata) => {
@@ -297,6 +309,7 @@ pub extern "C" fn kclvm_file_write(
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
+            ctx.check_write_access(&path);
             match fs::File::create(&path) {
                 Ok(mut file) => {
                     if let Err(e) = file.write_all(content.as_bytes()) {
@@ -327,6 +340,7 @@ pub extern "C" fn kclvm_file_append(
 
     if let Some(path) = get_call_arg_str(args, kwargs, 0, Some("filepath")) {
         if let Some(content) = get_call_arg_str(args, kwargs, 1, Some("content")) {
+            ctx.check_write_access(&path);
             // Open the file in append mode, creating it if it doesn't exist
             match fs::OpenOptions::new().append(true).create(true).open(&path) {
                 Ok(mut file) => {
@@ -359,6 +373,7 @@ pub extern "C" fn kclvm_file_read_env(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(key) = get_call_arg_str(args, kwargs, 0, Some("key")) {
+        ctx.check_env_access(&key);
         match std::env::var(key) {
             Ok(v) => ValueRef::str(&v).into_raw(ctx),
             Err(_) => ValueRef::undefined().into_raw(ctx),
@@ -79,6 +79,7 @@ pub extern "C" fn kclvm_json_dump_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            ptr_as_ref(ctx).check_write_access(&filename);
             let json = data.to_json_string_with_options(&args_to_opts(args, kwargs, 2));
             std::fs::write(&filename, json)
                 .unwrap_or_else(|e| panic!("Unable to write file '{}': {}", filename, e));
@@ -0,0 +1,357 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The jsonpath system module queries and updates the values with the JSONPath
+//! expressions e.g., `$.spec.containers[0].image`. The supported syntax is the root
+//! `$`, the dot child `.name`, the bracket child `['name']`, the list index `[0]`
+//! with the negative index counting from the end, and the wildcard `.*` or `[*]`.
+
+use crate::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Segment {
+    Key(String),
+    Index(i64),
+    Wildcard,
+}
+
+/// Parse the JSONPath expression into the path segments.
+fn parse_path(path: &str) -> Result<Vec<Segment>, String> {
+    let chars: Vec<char> = path.trim().chars().collect();
+    if chars.first() != Some(&'$') {
+        return Err("the path must start with '$'".to_string());
+    }
+    let mut segments = vec![];
+    let mut i = 1;
+    while i < chars.len() {
+        match chars[i] {
+            '.' => {
+                i += 1;
+                match chars.get(i) {
+                    Some('*') => {
+                        segments.push(Segment::Wildcard);
+                        i += 1;
+                    }
+                    Some('.') => {
+                        return Err("the recursive descent '..' is not supported".to_string())
+                    }
+                    _ => {
+                        let start = i;
+                        while i < chars.len() && chars[i] != '.' && chars[i] != '[' {
+                            i += 1;
+                        }
+                        if start == i {
+                            return Err(format!("expected a name after '.' at {}", start));
+                        }
+                        segments.push(Segment::Key(chars[start..i].iter().collect()));
+                    }
+                }
+            }
+            '[' => {
+                i += 1;
+                match chars.get(i) {
+                    Some('*') => {
+                        segments.push(Segment::Wildcard);
+                        i += 1;
+                    }
+                    Some(&quote) if quote == '\'' || quote == '"' => {
+                        i += 1;
+                        let mut key = String::new();
+                        loop {
+                            match chars.get(i) {
+                                Some('\\') if i + 1 < chars.len() => {
+                                    key.push(chars[i + 1]);
+                                    i += 2;
+                                }
+                                Some(&c) if c == quote => {
+                                    i += 1;
+                                    break;
+                                }
+                                Some(&c) => {
+                                    key.push(c);
+                                    i += 1;
+                                }
+                                None => return Err("unterminated quoted name".to_string()),
+                            }
+                        }
+                        segments.push(Segment::Key(key));
+                    }
+                    _ => {
+                        let start = i;
+                        while i < chars.len() && chars[i] != ']' {
+                            i += 1;
+                        }
+                        let index: String = chars[start..i].iter().collect();
+                        match index.trim().parse::<i64>() {
+                            Ok(index) => segments.push(Segment::Index(index)),
+                            Err(_) => return Err(format!("invalid list index '{}'", index)),
+                        }
+                    }
+                }
+                if chars.get(i) != Some(&']') {
+                    return Err(format!("expected ']' at {}", i));
+                }
+                i += 1;
+            }
+            c => return Err(format!("unexpected character '{}' at {}", c, i)),
+        }
+    }
+    Ok(segments)
+}
+
+fn must_parse_path(path: &str) -> Vec<Segment> {
+    parse_path(path).unwrap_or_else(|err| panic!("invalid JSONPath '{}': {}", path, err))
+}
+
+/// Returns the list index in the range of the length, and None when out of range.
+fn normalize_index(index: i64, len: usize) -> Option<usize> {
+    let index = if index < 0 { index + len as i64 } else { index };
+    if index >= 0 && (index as usize) < len {
+        Some(index as usize)
+    } else {
+        None
+    }
+}
+
+/// Returns the child values matched by the segment.
+fn children(value: &ValueRef, segment: &Segment) -> Vec<ValueRef> {
+    match (&*value.rc.borrow(), segment) {
+        (Value::dict_value(dict), Segment::Key(key)) => {
+            dict.values.get(key).cloned().into_iter().collect()
+        }
+        (Value::schema_value(schema), Segment::Key(key)) => {
+            schema.config.values.get(key).cloned().into_iter().collect()
+        }
+        (Value::dict_value(dict), Segment::Wildcard) => dict.values.values().cloned().collect(),
+        (Value::schema_value(schema), Segment::Wildcard) => {
+            schema.config.values.values().cloned().collect()
+        }
+        (Value::list_value(list), Segment::Index(index)) => {
+            normalize_index(*index, list.values.len())
+                .map(|i| list.values[i].clone())
+                .into_iter()
+                .collect()
+        }
+        (Value::list_value(list), Segment::Wildcard) => list.values.clone(),
+        _ => vec![],
+    }
+}
+
+/// Returns all the values matched by the path segments.
+fn query(value: &ValueRef, segments: &[Segment]) -> Vec<ValueRef> {
+    let mut values = vec![value.clone()];
+    for segment in segments {
+        values = values.iter().flat_map(|v| children(v, segment)).collect();
+    }
+    values
+}
+
+/// Set the values matched by the path segments in place, and the missing dict keys
+/// along the path are created.
+fn set(value: &mut ValueRef, segments: &[Segment], new_value: &ValueRef, path: &str) {
+    let (segment, rest) = match segments.split_first() {
+        Some(first) => first,
+        None => return,
+    };
+    if rest.is_empty() {
+        match segment {
+            Segment::Key(key) if value.is_config() => {
+                value.dict_update_key_value(key, new_value.clone())
+            }
+            Segment::Index(index) if value.is_list() => {
+                match normalize_index(*index, value.len()) {
+                    Some(i) => value.list_set(i, new_value),
+                    None => panic!(
+                        "invalid JSONPath '{}': list index {} out of range",
+                        path, index
+                    ),
+                }
+            }
+            Segment::Wildcard if value.is_config() => {
+                for key in value.dict_keys().as_list_ref().values.iter() {
+                    value.dict_update_key_value(&key.as_str(), new_value.clone());
+                }
+            }
+            Segment::Wildcard if value.is_list() => {
+                for i in 0..value.len() {
+                    value.list_set(i, new_value);
+                }
+            }
+            _ => panic!(
+                "invalid JSONPath '{}': can not set the path on the '{}' value",
+                path,
+                value.type_str()
+            ),
+        }
+        return;
+    }
+    let mut matched = children(value, segment);
+    if matched.is_empty() {
+        match segment {
+            Segment::Key(key) if value.is_config() => {
+                let child = ValueRef::dict(None);
+                value.dict_update_key_value(key, child.clone());
+                matched.push(child);
+            }
+            _ => panic!(
+                "invalid JSONPath '{}': can not set the path on the '{}' value",
+                path,
+                value.type_str()
+            ),
+        }
+    }
+    for mut child in matched {
+        set(&mut child, rest, new_value, path);
+    }
+}
+
+/// Returns the first value matched by the JSONPath `path` in `value`, or the
+/// `default` value when no value is matched.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_jsonpath_get(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg(args, kwargs, 0, Some("value")),
+        get_call_arg_str(args, kwargs, 1, Some("path")),
+    ) {
+        (Some(value), Some(path)) => {
+            let segments = must_parse_path(&path);
+            match query(&value, &segments).into_iter().next() {
+                Some(v) => v.into_raw(ctx),
+                None => get_call_arg(args, kwargs, 2, Some("default"))
+                    .unwrap_or_else(ValueRef::none)
+                    .into_raw(ctx),
+            }
+        }
+        _ => panic!("get() missing 2 required positional arguments: 'value' and 'path'"),
+    }
+}
+
+/// Returns the list of all the values matched by the JSONPath `path` in `value`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_jsonpath_query(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg(args, kwargs, 0, Some("value")),
+        get_call_arg_str(args, kwargs, 1, Some("path")),
+    ) {
+        (Some(value), Some(path)) => {
+            let segments = must_parse_path(&path);
+            let values = query(&value, &segments);
+            ValueRef::list(Some(&values.iter().collect::<Vec<&ValueRef>>())).into_raw(ctx)
+        }
+        _ => panic!("query() missing 2 required positional arguments: 'value' and 'path'"),
+    }
+}
+
+/// Returns a copy of `value` with the values matched by the JSONPath `path` set to
+/// `new_value`, and the missing dict keys along the path are created.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_jsonpath_set(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg(args, kwargs, 0, Some("value")),
+        get_call_arg_str(args, kwargs, 1, Some("path")),
+        get_call_arg(args, kwargs, 2, Some("new_value")),
+    ) {
+        (Some(value), Some(path), Some(new_value)) => {
+            let segments = must_parse_path(&path);
+            if segments.is_empty() {
+                return new_value.into_raw(ctx);
+            }
+            let mut value = value.deep_copy();
+            set(&mut value, &segments, &new_value, &path);
+            value.into_raw(ctx)
+        }
+        _ => {
+            panic!("set() missing 3 required positional arguments: 'value', 'path' and 'new_value'")
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_jsonpath {
+    use super::*;
+
+    #[test]
+    fn test_parse_path() {
+        assert_eq!(parse_path("$").unwrap(), vec![]);
+        assert_eq!(
+            parse_path("$.spec.containers[0]['image.name'][*].*[-1]").unwrap(),
+            vec![
+                Segment::Key("spec".to_string()),
+                Segment::Key("containers".to_string()),
+                Segment::Index(0),
+                Segment::Key("image.name".to_string()),
+                Segment::Wildcard,
+                Segment::Wildcard,
+                Segment::Index(-1),
+            ]
+        );
+        for path in ["spec", "$..spec", "$.", "$[0", "$[a]", "$['a]"] {
+            assert!(parse_path(path).is_err(), "{path}");
+        }
+    }
+
+    #[test]
+    fn test_query_and_set() {
+        let containers = ValueRef::list(Some(&[
+            &ValueRef::dict(Some(&[("image", &ValueRef::str("nginx"))])),
+            &ValueRef::dict(Some(&[("image", &ValueRef::str("redis"))])),
+        ]));
+        let value = ValueRef::dict(Some(&[(
+            "spec",
+            &ValueRef::dict(Some(&[("containers", &containers)])),
+        )]));
+        let images = query(&value, &must_parse_path("$.spec.containers[*].image"));
+        assert_eq!(images, vec![ValueRef::str("nginx"), ValueRef::str("redis")]);
+        let images = query(&value, &must_parse_path("$.spec.containers[-1].image"));
+        assert_eq!(images, vec![ValueRef::str("redis")]);
+        assert!(query(&value, &must_parse_path("$.spec.volumes[0]")).is_empty());
+
+        let mut copied = value.deep_copy();
+        set(
+            &mut copied,
+            &must_parse_path("$.spec.containers[*].image"),
+            &ValueRef::str("busybox"),
+            "",
+        );
+        set(
+            &mut copied,
+            &must_parse_path("$.metadata.name"),
+            &ValueRef::str("app"),
+            "",
+        );
+        assert_eq!(
+            copied.to_json_string(),
+            "{\"spec\": {\"containers\": [{\"image\": \"busybox\"}, {\"image\": \"busybox\"}]}, \"metadata\": {\"name\": \"app\"}}"
+        );
+        assert_eq!(
+            query(&value, &must_parse_path("$.spec.containers[0].image")),
+            vec![ValueRef::str("nginx")]
+        );
+    }
+}
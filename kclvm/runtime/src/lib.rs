@@ -91,6 +91,9 @@ pub mod encoding;
 pub mod json;
 pub use self::json::*;
 
+pub mod jsonpath;
+pub use self::jsonpath::*;
+
 pub mod manifests;
 pub use self::manifests::*;
 
@@ -100,15 +103,33 @@ pub use self::math::*;
 pub mod net;
 pub use self::net::*;
 
+pub mod random;
+pub use self::random::*;
+
 pub mod regex;
 pub use self::regex::*;
 
+pub mod semver;
+pub use self::semver::*;
+
 pub mod stdlib;
 pub use self::stdlib::*;
 
 pub mod units;
 pub use self::units::*;
 
+pub mod url;
+pub use self::url::*;
+
+pub mod uuid;
+pub use self::uuid::*;
+
+pub mod decimal;
+pub use self::decimal::*;
+
+pub mod toml;
+pub use self::toml::*;
+
 pub mod yaml;
 pub use self::yaml::*;
 
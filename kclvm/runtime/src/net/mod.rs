@@ -1,5 +1,6 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+use std::net::IpAddr;
 use std::net::Ipv4Addr;
 use std::net::Ipv6Addr;
 use std::str::FromStr;
@@ -68,6 +69,7 @@ pub extern "C" fn kclvm_net_fqdn(
 ) -> *const kclvm_value_ref_t {
     use std::net::ToSocketAddrs;
     let ctx = mut_ptr_as_ref(ctx);
+    ctx.check_network_access("net.fqdn");
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
     let name = get_call_arg_str(args, kwargs, 0, Some("name")).unwrap_or_default();
@@ -99,10 +101,11 @@ pub extern "C" fn kclvm_net_fqdn(
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_net_fqdn(
-    _ctx: *mut kclvm_context_t,
+    ctx: *mut kclvm_context_t,
     _args: *const kclvm_value_ref_t,
     _kwargs: *const kclvm_value_ref_t,
 ) -> *const kclvm_value_ref_t {
+    mut_ptr_as_ref(ctx).check_network_access("net.fqdn");
     panic!("fqdn() do not support the WASM target");
 }
 
@@ -196,6 +199,26 @@ pub extern "C" fn kclvm_net_is_IPv4(
     panic!("is_IPv4() missing 1 required positional argument: 'ip'");
 }
 
+// is_IPv6(ip: str) -> bool
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_net_is_IPv6(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(ip) = get_call_arg_str(args, kwargs, 0, Some("ip")) {
+        let is_ipv6 = Ipv6Addr::from_str(ip.as_ref()).is_ok();
+        return kclvm_value_Bool(ctx, is_ipv6 as i8);
+    }
+
+    panic!("is_IPv6() missing 1 required positional argument: 'ip'");
+}
+
 // is_IP(ip: str) -> bool
 
 #[no_mangle]
@@ -396,6 +419,89 @@ pub extern "C" fn kclvm_net_is_global_unicast_IP(
     panic!("is_global_unicast_IP() missing 1 required positional argument: 'ip'");
 }
 
+/// The maximum number of the addresses or subnets listed from a CIDR block.
+const MAX_CIDR_LIST_LEN: u128 = 1 << 16;
+
+/// The CIDR block of the IPv4 or IPv6 addresses, and the address bits are stored in
+/// u128 for both of them.
+struct Cidr {
+    ip: IpAddr,
+    prefix: u32,
+}
+
+impl Cidr {
+    fn parse(cidr: &str) -> Option<Self> {
+        let (ip, prefix) = cidr.trim().split_once('/')?;
+        let ip = IpAddr::from_str(ip).ok()?;
+        let prefix = prefix.parse::<u32>().ok()?;
+        let cidr = Self { ip, prefix };
+        (prefix <= cidr.bits()).then_some(cidr)
+    }
+
+    fn bits(&self) -> u32 {
+        match self.ip {
+            IpAddr::V4(_) => 32,
+            IpAddr::V6(_) => 128,
+        }
+    }
+
+    fn to_u128(ip: &IpAddr) -> u128 {
+        match ip {
+            IpAddr::V4(ip) => u32::from(*ip) as u128,
+            IpAddr::V6(ip) => u128::from(*ip),
+        }
+    }
+
+    fn to_ip(&self, v: u128) -> IpAddr {
+        match self.ip {
+            IpAddr::V4(_) => IpAddr::V4(Ipv4Addr::from(v as u32)),
+            IpAddr::V6(_) => IpAddr::V6(Ipv6Addr::from(v)),
+        }
+    }
+
+    /// Returns the bit mask of the host part.
+    fn host_mask(&self) -> u128 {
+        let host_bits = self.bits() - self.prefix;
+        if host_bits == 128 {
+            u128::MAX
+        } else {
+            (1 << host_bits) - 1
+        }
+    }
+
+    fn network(&self) -> u128 {
+        Self::to_u128(&self.ip) & !self.host_mask()
+    }
+
+    fn last(&self) -> u128 {
+        self.network() | self.host_mask()
+    }
+
+    /// Returns the first and last host addresses. The IPv4 network and broadcast
+    /// addresses and the IPv6 subnet-router anycast address are not the hosts except
+    /// for the point-to-point blocks, i.e., the /31 and /32 IPv4 blocks and the /127
+    /// and /128 IPv6 blocks.
+    fn host_range(&self) -> (u128, u128) {
+        let (network, last) = (self.network(), self.last());
+        match self.ip {
+            IpAddr::V4(_) if self.prefix < 31 => (network + 1, last - 1),
+            IpAddr::V6(_) if self.prefix < 127 => (network + 1, last),
+            _ => (network, last),
+        }
+    }
+
+    fn contains(&self, ip: &IpAddr) -> bool {
+        ip.is_ipv4() == self.ip.is_ipv4() && Self::to_u128(ip) & !self.host_mask() == self.network()
+    }
+}
+
+fn str_list(values: &[String]) -> ValueRef {
+    let values: Vec<ValueRef> = values.iter().map(|v| ValueRef::str(v)).collect();
+    ValueRef::list(Some(&values.iter().collect::<Vec<&ValueRef>>()))
+}
+
+// parse_CIDR(cidr: str) -> {str:}
+
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_net_parse_CIDR(
@@ -408,18 +514,17 @@ pub extern "C" fn kclvm_net_parse_CIDR(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(cidr) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
-        let parts: Vec<&str> = cidr.split('/').collect();
-        if parts.len() == 2 {
-            let ip = parts[0];
-            let mask = parts[1];
-            if let Ok(ip) = Ipv4Addr::from_str(ip) {
-                if let Ok(mask) = mask.parse::<u8>() {
-                    let ip_value = ValueRef::str(ip.to_string().as_str());
-                    let mask_value = ValueRef::int(mask as i64);
-                    return ValueRef::dict(Some(&[("ip", &ip_value), ("mask", &mask_value)]))
-                        .into_raw(ctx);
-                }
-            }
+        if let Some(cidr) = Cidr::parse(&cidr) {
+            let (min_host, max_host) = cidr.host_range();
+            let ip_str = |v: u128| ValueRef::str(&cidr.to_ip(v).to_string());
+            return ValueRef::dict(Some(&[
+                ("ip", &ValueRef::str(&cidr.ip.to_string())),
+                ("mask", &ValueRef::int(cidr.prefix as i64)),
+                ("network", &ip_str(cidr.network())),
+                ("min_host", &ip_str(min_host)),
+                ("max_host", &ip_str(max_host)),
+            ]))
+            .into_raw(ctx);
         }
         return ValueRef::dict(None).into_raw(ctx);
     }
@@ -427,6 +532,8 @@ pub extern "C" fn kclvm_net_parse_CIDR(
     panic!("parse_CIDR() missing 1 required positional argument: 'cidr'");
 }
 
+// hosts_in_CIDR(cidr: str) -> [str]
+
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_net_hosts_in_CIDR(
@@ -438,23 +545,19 @@ pub extern "C" fn kclvm_net_hosts_in_CIDR(
     let kwargs = ptr_as_ref(kwargs);
     let ctx = mut_ptr_as_ref(ctx);
 
-    if let Some(cidr) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
-        let parts: Vec<&str> = cidr.split('/').collect();
-        if parts.len() == 2 {
-            let ip = parts[0];
-            let mask = parts[1];
-            if let Ok(ip) = Ipv4Addr::from_str(ip) {
-                if let Ok(mask) = mask.parse::<u8>() {
-                    let mask = u32::from_be_bytes(ip.octets()) & !((1 << (32 - mask)) - 1);
-                    let mut hosts = vec![];
-                    for i in 1..(1 << (32 - mask)) - 1 {
-                        let ip = u32::from_be_bytes(ip.octets()) + i;
-                        hosts.push(ValueRef::str(Ipv4Addr::from(ip).to_string().as_str()));
-                    }
-                    let hosts_refs: Vec<&ValueRef> = hosts.iter().collect();
-                    return ValueRef::list(Some(&hosts_refs[..])).into_raw(ctx);
-                }
+    if let Some(cidr_str) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
+        if let Some(cidr) = Cidr::parse(&cidr_str) {
+            let (min_host, max_host) = cidr.host_range();
+            if max_host - min_host >= MAX_CIDR_LIST_LEN {
+                panic!(
+                    "hosts_in_CIDR() too many hosts in '{}', at most {} hosts can be listed",
+                    cidr_str, MAX_CIDR_LIST_LEN
+                );
             }
+            let hosts: Vec<String> = (min_host..=max_host)
+                .map(|v| cidr.to_ip(v).to_string())
+                .collect();
+            return str_list(&hosts).into_raw(ctx);
         }
         return ValueRef::list(None).into_raw(ctx);
     }
@@ -462,6 +565,43 @@ pub extern "C" fn kclvm_net_hosts_in_CIDR(
     panic!("hosts_in_CIDR() missing 1 required positional argument: 'cidr'");
 }
 
+/// Returns the subnets of the CIDR block with the new prefix, and the default new
+/// prefix is one bit longer than the prefix of the block.
+fn subnets(cidr_str: &str, new_prefix: Option<i64>, func: &str) -> Vec<String> {
+    let cidr = match Cidr::parse(cidr_str) {
+        Some(cidr) => cidr,
+        None => return vec![],
+    };
+    let new_prefix = match new_prefix {
+        Some(p) if p >= cidr.prefix as i64 && p <= cidr.bits() as i64 => p as u32,
+        Some(p) => panic!(
+            "{}() invalid new prefix {} for '{}', expected a prefix in the range [{}, {}]",
+            func,
+            p,
+            cidr_str,
+            cidr.prefix,
+            cidr.bits()
+        ),
+        None => (cidr.prefix + 1).min(cidr.bits()),
+    };
+    let count_bits = new_prefix - cidr.prefix;
+    if count_bits >= 16 {
+        panic!(
+            "{}() too many subnets of '{}', at most {} subnets can be listed",
+            func, cidr_str, MAX_CIDR_LIST_LEN
+        );
+    }
+    let host_bits = cidr.bits() - new_prefix;
+    (0..(1u128 << count_bits))
+        .map(|i| {
+            let network = cidr.network() + if host_bits == 128 { 0 } else { i << host_bits };
+            format!("{}/{}", cidr.to_ip(network), new_prefix)
+        })
+        .collect()
+}
+
+// subnets_from_CIDR(cidr: str, new_prefix: int = None) -> [str]
+
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_net_subnets_from_CIDR(
@@ -474,31 +614,43 @@ pub extern "C" fn kclvm_net_subnets_from_CIDR(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(cidr) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
-        let parts: Vec<&str> = cidr.split('/').collect();
-        if parts.len() == 2 {
-            let ip = parts[0];
-            let mask = parts[1];
-            if let Ok(ip) = Ipv4Addr::from_str(ip) {
-                if let Ok(mask) = mask.parse::<u8>() {
-                    let mask = u32::from_be_bytes(ip.octets()) & !((1 << (32 - mask)) - 1);
-                    let mut subnets = vec![];
-                    for i in 1..(1 << (32 - mask)) - 1 {
-                        let ip = u32::from_be_bytes(ip.octets()) + i;
-                        subnets.push(ValueRef::str(
-                            format!("{}/{}", Ipv4Addr::from(ip), mask).as_str(),
-                        ));
-                    }
-                    let subnets_refs: Vec<&ValueRef> = subnets.iter().collect();
-                    return ValueRef::list(Some(&subnets_refs)).into_raw(ctx);
-                }
-            }
-        }
-        return ValueRef::list(None).into_raw(ctx);
+        let new_prefix = get_call_arg_int(args, kwargs, 1, Some("new_prefix"));
+        return str_list(&subnets(&cidr, new_prefix, "subnets_from_CIDR")).into_raw(ctx);
     }
 
     panic!("subnets_from_CIDR() missing 1 required positional argument: 'cidr'");
 }
 
+// subnets(cidr: str, new_prefix: int = None) -> [str]
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_net_subnets(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(cidr) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
+        let new_prefix = get_call_arg_int(args, kwargs, 1, Some("new_prefix"));
+        return str_list(&subnets(&cidr, new_prefix, "subnets")).into_raw(ctx);
+    }
+
+    panic!("subnets() missing 1 required positional argument: 'cidr'");
+}
+
+fn is_ip_in_cidr(ip: &str, cidr: &str) -> bool {
+    match (IpAddr::from_str(ip.trim()), Cidr::parse(cidr)) {
+        (Ok(ip), Some(cidr)) => cidr.contains(&ip),
+        _ => false,
+    }
+}
+
+// is_IP_in_CIDR(ip: str, cidr: str) -> bool
+
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_net_is_IP_in_CIDR(
@@ -511,35 +663,34 @@ pub extern "C" fn kclvm_net_is_IP_in_CIDR(
 
     if let Some(ip) = get_call_arg_str(args, kwargs, 0, Some("ip")) {
         if let Some(cidr) = get_call_arg_str(args, kwargs, 1, Some("cidr")) {
-            let parts: Vec<&str> = cidr.split('/').collect();
-            if parts.len() == 2 {
-                let cidr_ip = parts[0];
-                let mask_bits = parts[1];
-                let ip_addr = match Ipv4Addr::from_str(&ip) {
-                    Ok(ip_addr) => ip_addr,
-                    Err(_) => return kclvm_value_False(ctx),
-                };
-                let cidr_ip_addr = match Ipv4Addr::from_str(cidr_ip) {
-                    Ok(cidr_ip_addr) => cidr_ip_addr,
-                    Err(_) => return kclvm_value_False(ctx),
-                };
-                let mask_bits = match mask_bits.parse::<u8>() {
-                    Ok(mask_bits) if mask_bits <= 32 => mask_bits,
-                    _ => return kclvm_value_False(ctx),
-                };
-                let mask = !((1 << (32 - mask_bits)) - 1);
-                let ip_u32 = u32::from_be_bytes(ip_addr.octets());
-                let cidr_ip_u32 = u32::from_be_bytes(cidr_ip_addr.octets());
-                let is_in_cidr = (ip_u32 & mask) == (cidr_ip_u32 & mask);
-                return kclvm_value_Bool(ctx, is_in_cidr as i8);
-            }
+            return kclvm_value_Bool(ctx, is_ip_in_cidr(&ip, &cidr) as i8);
         }
-        return kclvm_value_False(ctx);
     }
 
     panic!("is_IP_in_CIDR() missing 2 required positional arguments: 'ip' and 'cidr'");
 }
 
+// contains(cidr: str, ip: str) -> bool
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_net_contains(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(cidr) = get_call_arg_str(args, kwargs, 0, Some("cidr")) {
+        if let Some(ip) = get_call_arg_str(args, kwargs, 1, Some("ip")) {
+            return kclvm_value_Bool(ctx, is_ip_in_cidr(&ip, &cidr) as i8);
+        }
+    }
+
+    panic!("contains() missing 2 required positional arguments: 'cidr' and 'ip'");
+}
+
 #[allow(non_camel_case_types, non_snake_case)]
 fn Ipv4Addr_is_global(_self: &std::net::Ipv4Addr) -> bool {
     // check if this address is 192.0.0.9 or 192.0.0.10. These addresses are the only two
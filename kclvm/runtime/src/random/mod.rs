@@ -0,0 +1,155 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The random system module generates the random values, and the values are
+//! reproducible when the `random_seed` config is set or `random.seed()` is called.
+
+use rand::{seq::SliceRandom, Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+
+use crate::*;
+
+impl Context {
+    /// Returns the random number generator, which is seeded with the `random_seed`
+    /// config or the OS entropy at the first use.
+    pub fn rng(&mut self) -> &mut ChaCha8Rng {
+        let seed = self.cfg.random_seed;
+        self.rng.get_or_insert_with(|| match seed {
+            Some(seed) => ChaCha8Rng::seed_from_u64(seed),
+            None => ChaCha8Rng::from_entropy(),
+        })
+    }
+}
+
+/// Reseed the random number generator with `seed` to make the following random
+/// values reproducible.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_random_seed(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(seed) = get_call_arg_int(args, kwargs, 0, Some("seed")) {
+        ctx.rng = Some(ChaCha8Rng::seed_from_u64(seed as u64));
+        return kclvm_value_None(ctx);
+    }
+    panic!("seed() missing 1 required positional argument: 'seed'");
+}
+
+/// Returns a random float in the range [0.0, 1.0).
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_random_random(
+    ctx: *mut kclvm_context_t,
+    _args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let value: f64 = ctx.rng().gen();
+    ValueRef::float(value).into_raw(ctx)
+}
+
+/// Returns a random int in the range [a, b] including both end points.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_random_randint(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_int(args, kwargs, 0, Some("a")),
+        get_call_arg_int(args, kwargs, 1, Some("b")),
+    ) {
+        (Some(a), Some(b)) => {
+            if a > b {
+                panic!("randint() empty range [{}, {}]", a, b);
+            }
+            let value = ctx.rng().gen_range(a..=b);
+            ValueRef::int(value).into_raw(ctx)
+        }
+        _ => panic!("randint() missing 2 required positional arguments: 'a' and 'b'"),
+    }
+}
+
+/// Returns a random element of the non-empty list or the random character of the
+/// non-empty string `seq`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_random_choice(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(seq) = get_call_arg(args, kwargs, 0, Some("seq")) {
+        let values: Vec<ValueRef> = if seq.is_str() {
+            seq.as_str()
+                .chars()
+                .map(|c| ValueRef::str(&c.to_string()))
+                .collect()
+        } else if seq.is_list() {
+            seq.as_list_ref().values.clone()
+        } else {
+            panic!("choice() expected a list or str, got '{}'", seq.type_str())
+        };
+        return match values.choose(ctx.rng()) {
+            Some(value) => value.clone().into_raw(ctx),
+            None => panic!("choice() cannot choose from an empty sequence"),
+        };
+    }
+    panic!("choice() missing 1 required positional argument: 'seq'");
+}
+
+/// Returns a shuffled copy of the list `seq`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_random_shuffle(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(seq) = get_call_arg(args, kwargs, 0, Some("seq")) {
+        if !seq.is_list() {
+            panic!("shuffle() expected a list, got '{}'", seq.type_str());
+        }
+        let mut values = seq.as_list_ref().values.clone();
+        values.shuffle(ctx.rng());
+        return ValueRef::list(Some(&values.iter().collect::<Vec<&ValueRef>>())).into_raw(ctx);
+    }
+    panic!("shuffle() missing 1 required positional argument: 'seq'");
+}
+
+#[cfg(test)]
+mod test_random {
+    use rand::Rng;
+
+    use crate::*;
+
+    #[test]
+    fn test_seeded_rng() {
+        let mut ctx = Context::new();
+        ctx.cfg.random_seed = Some(42);
+        let a: Vec<u64> = (0..4).map(|_| ctx.rng().gen()).collect();
+        let mut ctx = Context::new();
+        ctx.cfg.random_seed = Some(42);
+        let b: Vec<u64> = (0..4).map(|_| ctx.rng().gen()).collect();
+        assert_eq!(a, b);
+        assert_eq!(ctx.new_uuid_v4().get_version_num(), 4);
+    }
+}
@@ -2,8 +2,69 @@
 
 extern crate fancy_regex;
 
+use std::sync::Arc;
+
+use fancy_regex::Regex;
+
 use crate::*;
 
+impl Context {
+    /// Returns the compiled regex of the pattern, and the compiled regexes are cached
+    /// in the context to avoid compiling the same pattern repeatedly.
+    pub fn regex(&mut self, pattern: &str) -> Result<Arc<Regex>, fancy_regex::Error> {
+        if let Some(re) = self.regex_cache.get(pattern) {
+            return Ok(re.clone());
+        }
+        let re = Arc::new(Regex::new(pattern)?);
+        self.regex_cache.insert(pattern.to_string(), re.clone());
+        Ok(re)
+    }
+
+    fn must_regex(&mut self, pattern: &str) -> Arc<Regex> {
+        self.regex(pattern)
+            .unwrap_or_else(|err| panic!("invalid regex pattern '{pattern}': {err}"))
+    }
+}
+
+/// Check the group references `$name`, `${name}` and `$1` in the replacement template
+/// are defined in the regex, and `$$` denotes a literal `$`.
+fn check_template(re: &Regex, template: &str) -> Result<(), String> {
+    let mut rest = template;
+    while let Some(i) = rest.find('$') {
+        rest = &rest[i + 1..];
+        if let Some(r) = rest.strip_prefix('$') {
+            rest = r;
+            continue;
+        }
+        let (name, r) = match rest.strip_prefix('{') {
+            Some(r) => match r.find('}') {
+                Some(end) => (&r[..end], &r[end + 1..]),
+                None => continue,
+            },
+            None => {
+                let end = rest
+                    .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                rest.split_at(end)
+            }
+        };
+        rest = r;
+        if name.is_empty() {
+            continue;
+        }
+        let defined = match name.parse::<usize>() {
+            Ok(index) => index < re.captures_len(),
+            Err(_) => re.capture_names().any(|n| n == Some(name)),
+        };
+        if !defined {
+            return Err(format!(
+                "unknown group '{name}' in the replacement template '{template}'"
+            ));
+        }
+    }
+    Ok(())
+}
+
 // match(string: str, pattern: str) -> bool:
 
 #[no_mangle]
@@ -15,10 +76,11 @@ pub extern "C" fn kclvm_regex_match(
 ) -> *const kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
-            let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+            let re = ctx.must_regex(&pattern);
             match re.is_match(string.as_ref()) {
                 Ok(ok) => {
                     if ok {
@@ -51,7 +113,8 @@ pub extern "C" fn kclvm_regex_replace(
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
             if let Some(replace) = get_call_arg_str(args, kwargs, 2, Some("replace")) {
                 let count = get_call_arg_int(args, kwargs, 3, Some("count")).unwrap_or_else(|| 0);
-                let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+                let re = ctx.must_regex(&pattern);
+                check_template(&re, &replace).unwrap_or_else(|err| panic!("replace() {err}"));
                 let s = re.replacen(string.as_ref(), count as usize, replace.as_ref() as &str);
                 return ValueRef::str(&s).into_raw(ctx);
             }
@@ -73,9 +136,10 @@ pub extern "C" fn kclvm_regex_compile(
 ) -> *mut kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(pattern) = get_call_arg_str(args, kwargs, 0, Some("pattern")) {
-        match fancy_regex::Regex::new(pattern.as_ref()) {
+        match ctx.regex(pattern.as_ref()) {
             Ok(_) => return kclvm_value_Bool(ctx, 1),
             _ => return kclvm_value_Bool(ctx, 0),
         }
@@ -83,7 +147,7 @@ pub extern "C" fn kclvm_regex_compile(
     panic!("compile() missing the required positional argument: 'pattern'")
 }
 
-// findall(string: str, pattern: str) -> [str]:
+// findall(string: str, pattern: str) -> [str] | [[str]] | [{str:str}]:
 
 #[no_mangle]
 #[runtime_fn]
@@ -98,19 +162,30 @@ pub extern "C" fn kclvm_regex_findall(
     if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
             let mut list = ValueRef::list(None);
+            let re = ctx.must_regex(&pattern);
+            // The matches of the pattern with the named groups are the dicts of the
+            // named groups, and the unmatched groups are None.
+            let names: Vec<&str> = re.capture_names().flatten().collect();
 
-            for x in fancy_regex::Regex::new(pattern.as_ref())
-                .unwrap()
-                .captures_iter(string.as_ref())
-                .flatten()
-            {
+            for x in re.captures_iter(string.as_ref()).flatten() {
                 let len = x.len();
-                if len < 3 {
+                if !names.is_empty() {
+                    let mut dict = ValueRef::dict(None);
+                    for name in &names {
+                        let value = x
+                            .name(name)
+                            .map(|m| ValueRef::str(m.as_str()))
+                            .unwrap_or_else(ValueRef::none);
+                        dict.dict_update_key_value(name, value);
+                    }
+                    list.list_append(&dict);
+                } else if len < 3 {
                     list.list_append(&ValueRef::str(x.get(0).unwrap().as_str()));
                 } else {
                     let mut sub_list = ValueRef::list(None);
                     for i in 1..len {
-                        sub_list.list_append(&ValueRef::str(x.get(i).unwrap().as_str()));
+                        let value = x.get(i).map(|m| m.as_str()).unwrap_or_default();
+                        sub_list.list_append(&ValueRef::str(value));
                     }
                     list.list_append(&sub_list)
                 }
@@ -134,10 +209,11 @@ pub extern "C" fn kclvm_regex_search(
 ) -> *mut kclvm_value_ref_t {
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(string) = get_call_arg_str(args, kwargs, 0, Some("string")) {
         if let Some(pattern) = get_call_arg_str(args, kwargs, 1, Some("pattern")) {
-            let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
+            let re = ctx.must_regex(&pattern);
 
             if let Ok(Some(..)) = re.find(string.as_ref()) {
                 return kclvm_value_Bool(ctx, 1);
@@ -149,6 +225,33 @@ pub extern "C" fn kclvm_regex_search(
     panic!("search() missing 2 required positional arguments: 'string' and 'pattern'");
 }
 
+/// Split the string by the regex matches, and at most `maxsplit` splits occur when
+/// `maxsplit` is positive with the remainder of the string as the last field.
+fn split(re: &Regex, string: &str, maxsplit: i64) -> Vec<String> {
+    let mut fields: Vec<String> = Vec::new();
+    let mut last_end = 0;
+    let mut current_pos = 0;
+    while maxsplit <= 0 || fields.len() < maxsplit as usize {
+        let cap = match re.captures_from_pos(string, current_pos) {
+            Ok(Some(c)) => c.get(0).unwrap(),
+            _ => break,
+        };
+        fields.push(string[last_end..cap.start()].to_string());
+        last_end = cap.end();
+        current_pos = cap.end();
+        // Step over the next char after an empty match to avoid matching at the same
+        // position again.
+        if cap.start() == cap.end() {
+            match string[current_pos..].chars().next() {
+                Some(c) => current_pos += c.len_utf8(),
+                None => break,
+            }
+        }
+    }
+    fields.push(string[last_end..].to_string());
+    fields
+}
+
 // split(string: str, pattern: str, maxsplit: int = 0):
 
 #[no_mangle]
@@ -166,27 +269,9 @@ pub extern "C" fn kclvm_regex_split(
             let maxsplit = get_call_arg_int(args, kwargs, 2, Some("maxsplit")).unwrap_or_else(|| 0);
             let mut list = ValueRef::list(None);
 
-            let re = fancy_regex::Regex::new(pattern.as_ref()).unwrap();
-
-            let mut fields: Vec<String> = Vec::new();
-            let mut current_pos = 0;
-            loop {
-                let capture = re
-                    .captures_from_pos(string.as_ref(), current_pos)
-                    .map_or(None, |c| c);
-                if let Some(Some(cap)) = capture.map(|c| c.get(0)) {
-                    fields.push(string[current_pos..cap.start()].to_string());
-                    if maxsplit > 0 && fields.len() >= (maxsplit as usize) {
-                        break;
-                    }
-                    current_pos = cap.end();
-                } else {
-                    fields.push(string[current_pos..].to_string());
-                    break;
-                }
-            }
+            let re = ctx.must_regex(&pattern);
 
-            for s in fields {
+            for s in split(&re, &string, maxsplit) {
                 list.list_append(&ValueRef::str(s.as_ref()));
             }
             return list.into_raw(ctx);
@@ -195,3 +280,40 @@ pub extern "C" fn kclvm_regex_split(
     }
     panic!("split() missing 2 required positional arguments: 'string' and 'pattern'");
 }
+
+#[cfg(test)]
+mod test_regex {
+    use super::*;
+
+    #[test]
+    fn test_split() {
+        let re = Regex::new(",").unwrap();
+        assert_eq!(split(&re, "a,b,c", 0), vec!["a", "b", "c"]);
+        assert_eq!(split(&re, "a,b,c", 1), vec!["a", "b,c"]);
+        assert_eq!(split(&re, "a,b,c", 5), vec!["a", "b", "c"]);
+        let re = Regex::new("x*").unwrap();
+        assert_eq!(split(&re, "abc", 0), vec!["", "a", "b", "c", ""]);
+    }
+
+    #[test]
+    fn test_check_template() {
+        let re = Regex::new(r"(?P<key>\w+)=(\w+)").unwrap();
+        for template in ["$key", "${key}:$2", "$$name", "$1-$0", "$"] {
+            assert!(check_template(&re, template).is_ok(), "{template}");
+        }
+        assert_eq!(
+            check_template(&re, "$value").unwrap_err(),
+            "unknown group 'value' in the replacement template '$value'"
+        );
+        assert!(check_template(&re, "${3}").is_err());
+    }
+
+    #[test]
+    fn test_regex_cache() {
+        let mut ctx = Context::new();
+        let re = ctx.regex("a+").unwrap();
+        assert!(Arc::ptr_eq(&re, &ctx.regex("a+").unwrap()));
+        assert!(ctx.regex("(").is_err());
+        assert_eq!(ctx.regex_cache.len(), 1);
+    }
+}
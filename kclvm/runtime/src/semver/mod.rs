@@ -0,0 +1,172 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The semver system module parses, compares and matches the semantic versions
+//! e.g., the chart and image versions. The versions are normalized before parsing,
+//! thus the leading `v` and the missing minor and patch numbers are accepted
+//! e.g., `v1.2` is `1.2.0`.
+
+use std::cmp::Ordering;
+
+use ::semver::{Version, VersionReq};
+
+use crate::*;
+
+/// Normalize the version string by trimming the leading `v` or `=` and padding the
+/// missing minor and patch numbers with zeros.
+fn normalize_version(version: &str) -> String {
+    let version = version
+        .trim()
+        .trim_start_matches(|c| c == 'v' || c == 'V' || c == '=');
+    let (core, suffix) = match version.find(|c| c == '-' || c == '+') {
+        Some(i) => version.split_at(i),
+        None => (version, ""),
+    };
+    let mut parts: Vec<&str> = core.split('.').collect();
+    while parts.len() < 3 {
+        parts.push("0");
+    }
+    format!("{}{}", parts.join("."), suffix)
+}
+
+fn parse_version(version: &str) -> Version {
+    Version::parse(&normalize_version(version))
+        .unwrap_or_else(|err| panic!("invalid semantic version '{}': {}", version, err))
+}
+
+/// Compare the versions with the semantic version precedence, and the build metadata
+/// is ignored.
+fn compare_versions(a: &Version, b: &Version) -> Ordering {
+    (a.major, a.minor, a.patch, &a.pre).cmp(&(b.major, b.minor, b.patch, &b.pre))
+}
+
+/// Whether the version satisfies the range. The range is the comma separated
+/// comparators e.g., `>=1.2.0, <2.0.0`, `^1.2` and `~1.2.3`, and the alternative
+/// ranges are separated by `||`.
+fn satisfies(version: &Version, range: &str) -> bool {
+    range.split("||").any(|range| {
+        let range = range.trim();
+        let req = if range.is_empty() {
+            VersionReq::STAR
+        } else {
+            VersionReq::parse(range)
+                .unwrap_or_else(|err| panic!("invalid version range '{}': {}", range, err))
+        };
+        req.matches(version)
+    })
+}
+
+/// Parse the `version` to a dict with the `major`, `minor`, `patch`, `pre` and
+/// `build` keys.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_semver_parse(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let v = parse_version(&version);
+        return ValueRef::dict(Some(&[
+            ("major", &ValueRef::int(v.major as i64)),
+            ("minor", &ValueRef::int(v.minor as i64)),
+            ("patch", &ValueRef::int(v.patch as i64)),
+            ("pre", &ValueRef::str(v.pre.as_str())),
+            ("build", &ValueRef::str(v.build.as_str())),
+        ]))
+        .into_raw(ctx);
+    }
+    panic!("parse() missing 1 required positional argument: 'version'");
+}
+
+/// Returns -1, 0 or 1 when the version `a` is less than, equal to or greater than
+/// the version `b`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_semver_compare(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("a")),
+        get_call_arg_str(args, kwargs, 1, Some("b")),
+    ) {
+        (Some(a), Some(b)) => {
+            let result = match compare_versions(&parse_version(&a), &parse_version(&b)) {
+                Ordering::Less => -1,
+                Ordering::Equal => 0,
+                Ordering::Greater => 1,
+            };
+            ValueRef::int(result).into_raw(ctx)
+        }
+        _ => panic!("compare() missing 2 required positional arguments: 'a' and 'b'"),
+    }
+}
+
+/// Whether the `version` satisfies the version `range` e.g., `>=1.2.0, <2.0.0`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_semver_satisfies(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("version")),
+        get_call_arg_str(args, kwargs, 1, Some("range")),
+    ) {
+        (Some(version), Some(range)) => {
+            ValueRef::bool(satisfies(&parse_version(&version), &range)).into_raw(ctx)
+        }
+        _ => panic!("satisfies() missing 2 required positional arguments: 'version' and 'range'"),
+    }
+}
+
+/// Returns the normalized `version` e.g., `v1.2` is normalized to `1.2.0`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_semver_normalize(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        return ValueRef::str(&parse_version(&version).to_string()).into_raw(ctx);
+    }
+    panic!("normalize() missing 1 required positional argument: 'version'");
+}
+
+/// Whether the `version` is a valid semantic version after the normalization.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_semver_is_valid(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(version) = get_call_arg_str(args, kwargs, 0, Some("version")) {
+        let valid = Version::parse(&normalize_version(&version)).is_ok();
+        return ValueRef::bool(valid).into_raw(ctx);
+    }
+    panic!("is_valid() missing 1 required positional argument: 'version'");
+}
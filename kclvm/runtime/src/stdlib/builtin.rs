@@ -3,6 +3,8 @@
 use std::{collections::HashSet, ops::Index};
 
 use crate::*;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 
 impl Context {
     pub fn builtin_option_init(&mut self, key: &str, value: &str) {
@@ -20,6 +22,55 @@ impl Context {
 }
 
 impl ValueRef {
+    /// Convert the option value to the type of the `option()` function, which is one of
+    /// `bool`, `int`, `float`, `str`, `list` and `dict`, and the empty type keeps the value.
+    pub fn option_to_type(&self, typ: &str) -> Result<ValueRef, String> {
+        let cannot_use = || Err(format!("cannot use '{self}' as type '{typ}'"));
+        match typ {
+            "" => Ok(self.clone()),
+            "bool" => match *self.rc.borrow() {
+                Value::bool_value(ref v) => Ok(ValueRef::bool(*v)),
+                Value::int_value(ref v) => Ok(ValueRef::bool(*v != 0)),
+                Value::float_value(ref v) => Ok(ValueRef::bool(*v != 0.0)),
+                Value::str_value(ref v) => Ok(ValueRef::bool(v == "True" || v == "true")),
+                _ => Ok(ValueRef::undefined()),
+            },
+            "int" => match *self.rc.borrow() {
+                Value::bool_value(ref v) => Ok(ValueRef::int(*v as i64)),
+                Value::int_value(ref v) => Ok(ValueRef::int(*v)),
+                Value::float_value(ref v) => Ok(ValueRef::int(*v as i64)),
+                Value::str_value(ref v) => match v.parse::<i64>() {
+                    Ok(n) => Ok(ValueRef::int(n)),
+                    _ => Err(format!("cannot use '{v}' as type '{typ}'")),
+                },
+                _ => cannot_use(),
+            },
+            "float" => match *self.rc.borrow() {
+                Value::bool_value(ref v) => Ok(ValueRef::float(if *v { 1.0 } else { 0.0 })),
+                Value::int_value(ref v) => Ok(ValueRef::float(*v as f64)),
+                Value::float_value(ref v) => Ok(ValueRef::float(*v)),
+                Value::str_value(ref v) => Ok(ValueRef::float(v.parse::<f64>().unwrap_or(0.0))),
+                _ => cannot_use(),
+            },
+            "str" => match *self.rc.borrow() {
+                Value::bool_value(ref v) => Ok(ValueRef::str(&v.to_string())),
+                Value::int_value(ref v) => Ok(ValueRef::str(&v.to_string())),
+                Value::float_value(ref v) => Ok(ValueRef::str(&v.to_string())),
+                Value::str_value(ref v) => Ok(ValueRef::str(v)),
+                _ => cannot_use(),
+            },
+            "list" => match *self.rc.borrow() {
+                Value::list_value(_) => Ok(self.clone()),
+                _ => cannot_use(),
+            },
+            "dict" => match *self.rc.borrow() {
+                Value::dict_value(_) => Ok(self.clone()),
+                _ => cannot_use(),
+            },
+            _ => Err(format!("unknown type '{typ}'")),
+        }
+    }
+
     pub fn any_true(&self) -> bool {
         match &*self.rc.borrow() {
             Value::list_value(ref list) => {
@@ -154,6 +205,7 @@ impl ValueRef {
 
         match &*self.rc.borrow() {
             Value::int_value(ref v) => ValueRef::int(*v),
+            Value::bigint_value(ref v) => ValueRef::bigint(v.clone()),
             Value::float_value(ref v) => ValueRef::int(*v as i64),
             Value::unit_value(ref v, raw, unit) => {
                 let v_i128 = crate::real_uint_value(*raw, unit);
@@ -178,6 +230,14 @@ impl ValueRef {
             Value::bool_value(ref v) => ValueRef::int(*v as i64),
             Value::str_value(ref v) => {
                 let base = if let Some(v) = base { v.as_int() } else { 10 };
+                // The literal out of the i64 range is parsed as a big int.
+                if ctx.cfg.big_int {
+                    if let Some(v) = BigInt::parse_bytes(v.as_bytes(), base as u32)
+                        .filter(|v| v.to_i64().is_none())
+                    {
+                        return ValueRef::bigint(v);
+                    }
+                }
                 let number_str = to_quantity(v.as_str()).to_string();
                 let v: i64 =
                     i64::from_str_radix(number_str.as_str(), base as u32).unwrap_or_else(|_| {
@@ -215,6 +275,7 @@ impl ValueRef {
                 ValueRef::float(*v)
             }
             Value::unit_value(ref v, _, _) => ValueRef::float(*v),
+            Value::bigint_value(ref v) => ValueRef::float(bigint_to_f64(v)),
             Value::bool_value(ref v) => ValueRef::float((*v as i64) as f64),
             Value::str_value(ref v) => {
                 let v: f64 = v.parse().unwrap_or_else(|_| {
@@ -495,6 +556,25 @@ pub fn range(start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> ValueRef {
     }
 }
 
+/// Returns the number of the items in `range(start, stop, step)`.
+pub fn range_len(start: &ValueRef, stop: &ValueRef, step: &ValueRef) -> usize {
+    match (&*start.rc.borrow(), &*stop.rc.borrow(), &*step.rc.borrow()) {
+        (Value::int_value(start), Value::int_value(stop), Value::int_value(step)) if *step != 0 => {
+            let span = if *step > 0 {
+                *stop as i128 - *start as i128
+            } else {
+                *start as i128 - *stop as i128
+            };
+            if span <= 0 {
+                0
+            } else {
+                usize::try_from((span - 1) / (*step as i128).abs() + 1).unwrap_or(usize::MAX)
+            }
+        }
+        _ => 0,
+    }
+}
+
 /// Check if the modular result of a and b is 0
 pub fn multiplyof(a: &ValueRef, b: &ValueRef) -> ValueRef {
     match (&*a.rc.borrow(), &*b.rc.borrow()) {
@@ -640,6 +720,36 @@ mod test_builtin {
         });
     }
 
+    #[test]
+    fn test_option_to_type() {
+        let cases = [
+            (ValueRef::str("1"), "int", Ok(ValueRef::int(1))),
+            (ValueRef::bool(true), "int", Ok(ValueRef::int(1))),
+            (ValueRef::int(1), "str", Ok(ValueRef::str("1"))),
+            (ValueRef::str("true"), "bool", Ok(ValueRef::bool(true))),
+            (ValueRef::int(2), "float", Ok(ValueRef::float(2.0))),
+            (ValueRef::int(2), "", Ok(ValueRef::int(2))),
+            (
+                ValueRef::str("a"),
+                "int",
+                Err("cannot use 'a' as type 'int'".to_string()),
+            ),
+            (
+                ValueRef::list_int(&[1]),
+                "dict",
+                Err("cannot use '[1]' as type 'dict'".to_string()),
+            ),
+            (
+                ValueRef::int(1),
+                "Person",
+                Err("unknown type 'Person'".to_string()),
+            ),
+        ];
+        for (value, typ, expected) in cases {
+            assert_eq!(value.option_to_type(typ), expected, "{typ}");
+        }
+    }
+
     #[test]
     fn test_pow() {
         assert_eq!(
@@ -45,141 +45,22 @@ pub unsafe extern "C" fn kclvm_builtin_option(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
-    fn _value_to_type(this: &ValueRef, typ: String) -> ValueRef {
-        if typ.is_empty() {
-            return this.clone();
+    fn option_to_type(this: &ValueRef, typ: &str) -> ValueRef {
+        match this.option_to_type(typ) {
+            Ok(value) => value,
+            Err(err_msg) => panic!("{}", err_msg),
         }
-        if typ == "bool" {
-            match *this.rc.borrow() {
-                Value::bool_value(ref v) => {
-                    return ValueRef::bool(*v);
-                }
-                Value::int_value(ref v) => {
-                    return ValueRef::bool(*v != 0);
-                }
-                Value::float_value(ref v) => {
-                    return ValueRef::bool(*v != 0.0);
-                }
-                Value::str_value(ref v) => {
-                    return ValueRef::bool(v == "True" || v == "true");
-                }
-                _ => {
-                    return ValueRef::undefined();
-                }
-            }
-        }
-        if typ == "int" {
-            match *this.rc.borrow() {
-                Value::bool_value(ref v) => {
-                    if *v {
-                        return ValueRef::int(1);
-                    } else {
-                        return ValueRef::int(0);
-                    }
-                }
-                Value::int_value(ref v) => {
-                    return ValueRef::int(*v);
-                }
-                Value::float_value(ref v) => {
-                    return ValueRef::int(*v as i64);
-                }
-                Value::str_value(ref v) => {
-                    match v.parse::<i64>() {
-                        Ok(n) => return ValueRef::int(n),
-                        _ => panic!("cannot use '{v}' as type '{typ}'"),
-                    };
-                }
-                _ => {
-                    let err_msg = format!("cannot use '{this}' as type '{typ}'");
-                    panic!("{}", err_msg);
-                }
-            }
-        }
-        if typ == "float" {
-            match *this.rc.borrow() {
-                Value::bool_value(ref v) => {
-                    if *v {
-                        return ValueRef::float(1.0);
-                    } else {
-                        return ValueRef::float(0.0);
-                    }
-                }
-                Value::int_value(ref v) => {
-                    return ValueRef::float(*v as f64);
-                }
-                Value::float_value(ref v) => {
-                    return ValueRef::float(*v);
-                }
-                Value::str_value(ref v) => {
-                    match v.parse::<f64>() {
-                        Ok(n) => return ValueRef::float(n),
-                        _ => return ValueRef::float(0.0),
-                    };
-                }
-                _ => {
-                    let err_msg = format!("cannot use '{this}' as type '{typ}'");
-                    panic!("{}", err_msg);
-                }
-            }
-        }
-        if typ == "str" {
-            match *this.rc.borrow() {
-                Value::bool_value(ref v) => {
-                    let s = format!("{}", *v);
-                    return ValueRef::str(s.as_ref());
-                }
-                Value::int_value(ref v) => {
-                    let s = format!("{}", *v);
-                    return ValueRef::str(s.as_ref());
-                }
-                Value::float_value(ref v) => {
-                    let s = format!("{}", *v);
-                    return ValueRef::str(s.as_ref());
-                }
-                Value::str_value(ref v) => {
-                    return ValueRef::str(v.as_ref());
-                }
-                _ => {
-                    let err_msg = format!("cannot use '{this}' as type '{typ}'");
-                    panic!("{}", err_msg);
-                }
-            }
-        }
-        if typ == "list" {
-            match *this.rc.borrow() {
-                Value::list_value(_) => {
-                    return this.clone();
-                }
-                _ => {
-                    let err_msg = format!("cannot use '{this}' as type '{typ}'");
-                    panic!("{}", err_msg);
-                }
-            }
-        }
-        if typ == "dict" {
-            match *this.rc.borrow() {
-                Value::dict_value(_) => {
-                    return this.clone();
-                }
-                _ => {
-                    let err_msg = format!("cannot use '{this}' as type '{typ}'");
-                    panic!("{}", err_msg);
-                }
-            }
-        }
-
-        panic!("unknown type '{typ}'");
     }
 
     if let Some(arg0) = get_call_arg_str(args, kwargs, 0, Some("key")) {
         if let Some(x) = ctx.option_values.get(&arg0) {
             if let Some(kwarg_type) = get_call_arg_str(args, kwargs, 1, Some("type")) {
-                return _value_to_type(x, kwarg_type).into_raw(ctx);
+                return option_to_type(x, &kwarg_type).into_raw(ctx);
             }
             return x.clone().into_raw(ctx);
         } else if let Some(kwarg_default) = get_call_arg(args, kwargs, 3, Some("default")) {
             if let Some(kwarg_type) = get_call_arg_str(args, kwargs, 1, Some("type")) {
-                return _value_to_type(&kwarg_default, kwarg_type).into_raw(ctx);
+                return option_to_type(&kwarg_default, &kwarg_type).into_raw(ctx);
             }
             return kwarg_default.into_raw(ctx);
         }
@@ -206,14 +87,15 @@ pub unsafe extern "C" fn kclvm_builtin_print(
     // args
     let list = args.as_list_ref();
     let values: Vec<String> = list.values.iter().map(|v| v.to_string()).collect();
-    ctx_ref.log_message.push_str(&values.join(" "));
+    let mut message = values.join(" ");
     let dict = kwargs.as_dict_ref();
     // kwargs: end
     if let Some(c) = dict.values.get("end") {
-        ctx_ref.log_message.push_str(&format!("{c}"));
+        message.push_str(&format!("{c}"));
     } else {
-        ctx_ref.log_message.push('\n');
+        message.push('\n');
     }
+    ctx_ref.log(&message);
     kclvm_value_None(ctx)
 }
 
@@ -660,16 +542,18 @@ pub unsafe extern "C" fn kclvm_builtin_range(
     let args = ptr_as_ref(args);
     let kwargs = ptr_as_ref(kwargs);
 
-    match get_call_arg(args, kwargs, 0, Some("start")) {
+    let (start, stop, step) = match get_call_arg(args, kwargs, 0, Some("start")) {
         Some(arg0) => match get_call_arg(args, kwargs, 1, Some("stop")) {
             Some(arg1) => match get_call_arg(args, kwargs, 2, Some("step")) {
-                Some(arg2) => builtin::range(&arg0, &arg1, &arg2).into_raw(ctx_ref),
-                _ => builtin::range(&arg0, &arg1, &ValueRef::int(1)).into_raw(ctx_ref),
+                Some(arg2) => (arg0, arg1, arg2),
+                _ => (arg0, arg1, ValueRef::int(1)),
             },
-            _ => builtin::range(&ValueRef::int(0), &arg0, &ValueRef::int(1)).into_raw(ctx_ref),
+            _ => (ValueRef::int(0), arg0, ValueRef::int(1)),
         },
-        _ => kclvm_value_Undefined(ctx),
-    }
+        _ => return kclvm_value_Undefined(ctx),
+    };
+    ctx_ref.record_allocations(builtin::range_len(&start, &stop, &step));
+    builtin::range(&start, &stop, &step).into_raw(ctx_ref)
 }
 
 /// Return `True` if the input value is `None` or `Undefined`, and `False` otherwise.
@@ -6,18 +6,34 @@ use crate::*;
 
 use lazy_static::lazy_static;
 use std::os::raw::c_char;
-use std::sync::Mutex;
+use std::sync::{Arc, Mutex, RwLock};
+
+/// The plugin agent function which invokes the plugin method with the JSON arguments
+/// and returns the JSON result.
+pub type PluginHandler = extern "C" fn(
+    method: *const c_char,
+    args_json: *const c_char,
+    kwargs_json: *const c_char,
+) -> *const c_char;
 
 lazy_static! {
-    static ref PLUGIN_HANDLER_FN_PTR: Mutex<
-        Option<
-            extern "C" fn(
-                method: *const c_char,
-                args_json: *const c_char,
-                kwargs_json: *const c_char,
-            ) -> *const c_char,
-        >,
-    > = Mutex::new(None);
+    static ref PLUGIN_HANDLER_FN_PTR: Mutex<Option<PluginHandler>> = Mutex::new(None);
+    static ref NATIVE_PLUGIN_FUNCTIONS: RwLock<IndexMap<String, PluginFunction>> =
+        RwLock::new(IndexMap::default());
+}
+
+/// Returns the plugin handler of the function address, and `0` denotes no handler.
+///
+/// # Safety
+///
+/// The non-zero `ptr` must be the address of a function with the [PluginHandler]
+/// signature, which stays valid while the handler is used.
+pub unsafe fn plugin_handler_from_ptr(ptr: u64) -> Option<PluginHandler> {
+    if ptr == 0 {
+        None
+    } else {
+        Some(std::mem::transmute::<usize, PluginHandler>(ptr as usize))
+    }
 }
 
 /// KCL plugin module prefix
@@ -36,6 +52,77 @@ pub extern "C" fn kclvm_plugin_init(
     *fn_ptr_guard = Some(fn_ptr);
 }
 
+/// The native plugin functions of an execution for the Rust embedders without the plugin
+/// agent, and the names are the form <module_name>.<module_func> e.g., `hello.say_hello`,
+/// which is called in KCL as `import kcl_plugin.hello` and `hello.say_hello()`.
+#[derive(Clone, Default)]
+pub struct PluginFunctions(IndexMap<String, PluginFunction>);
+
+impl PluginFunctions {
+    /// New an empty set of the plugin functions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Register the native plugin function with the name.
+    pub fn register<F>(&mut self, name: &str, func: F) -> &mut Self
+    where
+        F: Fn(&Context, &ValueRef, &ValueRef) -> anyhow::Result<ValueRef> + Send + Sync + 'static,
+    {
+        self.0.insert(name.to_string(), Arc::new(func));
+        self
+    }
+
+    /// Whether there are no plugin functions.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the plugin functions keyed by the names.
+    pub fn into_inner(self) -> IndexMap<String, PluginFunction> {
+        self.0
+    }
+}
+
+impl std::fmt::Debug for PluginFunctions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_set().entries(self.0.keys()).finish()
+    }
+}
+
+impl From<IndexMap<String, PluginFunction>> for PluginFunctions {
+    fn from(functions: IndexMap<String, PluginFunction>) -> Self {
+        Self(functions)
+    }
+}
+
+/// Register a native plugin function in the global registry, which is only used by the
+/// executions opting in to it, and the executions should prefer the [PluginFunctions]
+/// of their own. See [PluginFunctions] for the name form.
+pub fn register_plugin_fn<F>(name: &str, func: F)
+where
+    F: Fn(&Context, &ValueRef, &ValueRef) -> anyhow::Result<ValueRef> + Send + Sync + 'static,
+{
+    NATIVE_PLUGIN_FUNCTIONS
+        .write()
+        .unwrap()
+        .insert(name.to_string(), Arc::new(func));
+}
+
+/// Unregister the native plugin function and returns whether it is registered.
+pub fn unregister_plugin_fn(name: &str) -> bool {
+    NATIVE_PLUGIN_FUNCTIONS
+        .write()
+        .unwrap()
+        .shift_remove(name)
+        .is_some()
+}
+
+/// Returns all the registered native plugin functions.
+pub fn get_plugin_fns() -> IndexMap<String, PluginFunction> {
+    NATIVE_PLUGIN_FUNCTIONS.read().unwrap().clone()
+}
+
 // import kcl_plugin.hello
 // hello.say_hello()
 //
@@ -51,6 +138,7 @@ pub unsafe extern "C" fn kclvm_plugin_invoke(
 ) -> *const kclvm_value_ref_t {
     let ctx_ref = mut_ptr_as_ref(ctx);
     let method_ref = c2str(method);
+    ctx_ref.check_plugin_access(method_ref);
     let plugin_short_method = match method_ref.strip_prefix(PLUGIN_MODULE_PREFIX) {
         Some(s) => s,
         None => method_ref,
@@ -58,8 +146,11 @@ pub unsafe extern "C" fn kclvm_plugin_invoke(
     if let Some(func) = ctx_ref.plugin_functions.get(plugin_short_method) {
         let args = ptr_as_ref(args);
         let kwargs = ptr_as_ref(kwargs);
-        let result = func(ctx_ref, args, kwargs);
-        return result.unwrap().into_raw(ctx_ref);
+        let result = func(ctx_ref, args, kwargs).unwrap_or_else(|err| {
+            ctx_ref.set_err_type(&RuntimeErrorType::EvaluationError);
+            panic!("{err}")
+        });
+        return result.into_raw(ctx_ref);
     }
     let args_s = kclvm_value_to_json_value_with_null(ctx, args);
     let kwargs_s = kclvm_value_to_json_value_with_null(ctx, kwargs);
@@ -67,7 +158,12 @@ pub unsafe extern "C" fn kclvm_plugin_invoke(
     let args_json = kclvm_value_Str_ptr(args_s);
     let kwargs_json = kclvm_value_Str_ptr(kwargs_s);
 
-    let result_json = kclvm_plugin_invoke_json(method, args_json, kwargs_json);
+    // The plugin handler of the context takes precedence over the global one set by
+    // `kclvm_plugin_init`, thus the programs with different plugin agents can run concurrently.
+    let result_json = match ctx_ref.plugin_handler {
+        Some(handler) => handler(method, args_json, kwargs_json),
+        None => kclvm_plugin_invoke_json(method, args_json, kwargs_json),
+    };
 
     // Value delete by context.
     // kclvm_value_delete(args_s);
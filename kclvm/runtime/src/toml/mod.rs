@@ -0,0 +1,105 @@
+//! Copyright The KCL Authors. All rights reserved.
+use crate::*;
+
+/// encode(data, sort_keys=False, ignore_private=False)
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_toml_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("data")) {
+        let s = ValueRef::str(
+            arg0.to_toml_string_with_options(&args_to_opts(args, kwargs, 1))
+                .as_ref(),
+        );
+        return s.into_raw(mut_ptr_as_ref(ctx));
+    }
+    panic!("encode() missing 1 required positional argument: 'data'")
+}
+
+/// decode(value)
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_toml_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
+        match ValueRef::from_toml(ctx, arg0.as_str().as_ref()) {
+            Ok(x) => return x.into_raw(ctx),
+            Err(err) => panic!("{}", err),
+        }
+    }
+    panic!("decode() missing 1 required positional argument: 'value'")
+}
+
+/// dump_to_file(data, filename, sort_keys=False, ignore_private=False)
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_toml_dump_to_file(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let data = args.arg_i(0).or(kwargs.get_by_key("data"));
+    let filename = args.arg_i(1).or(kwargs.get_by_key("filename"));
+    match (data, filename) {
+        (Some(data), Some(filename)) => {
+            let filename = filename.as_str();
+            ptr_as_ref(ctx).check_write_access(&filename);
+
+            let toml = data.to_toml_string_with_options(&args_to_opts(args, kwargs, 2));
+            std::fs::write(&filename, toml)
+                .unwrap_or_else(|e| panic!("Unable to write file '{}': {}", filename, e));
+            kclvm_value_Undefined(ctx)
+        }
+        _ => {
+            panic!("dump_to_file() missing 2 required positional arguments: 'data' and 'filename'")
+        }
+    }
+}
+
+/// validate(value: str) -> bool
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_toml_validate(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
+        match ValueRef::from_toml(ctx, arg0.as_str().as_ref()) {
+            Ok(_) => return kclvm_value_True(ctx),
+            Err(_) => return kclvm_value_False(ctx),
+        }
+    }
+    panic!("validate() missing 1 required positional argument: 'value'")
+}
+
+fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> TomlEncodeOptions {
+    let mut opts = TomlEncodeOptions::default();
+    if let Some(sort_keys) = get_call_arg_bool(args, kwargs, index, Some("sort_keys")) {
+        opts.sort_keys = sort_keys;
+    }
+    if let Some(ignore_private) = get_call_arg_bool(args, kwargs, index + 1, Some("ignore_private"))
+    {
+        opts.ignore_private = ignore_private;
+    }
+    opts
+}
@@ -300,6 +300,27 @@ pub extern "C" fn kclvm_units_to_Pi(
     panic!("to_Pi() missing 1 required positional argument: 'num'");
 }
 
+// parse(quantity: str) -> int
+
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_units_parse(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+
+    if let Some(quantity) = get_call_arg_str(args, kwargs, 0, Some("quantity")) {
+        let num = parse_quantity(&quantity)
+            .unwrap_or_else(|err| panic!("parse() invalid quantity '{quantity}': {err}"));
+        return ValueRef::int(num).into_raw(ctx);
+    }
+    panic!("parse() missing 1 required positional argument: 'quantity'");
+}
+
 fn to_unit(num: f64, suffix: to_unit_suffix) -> String {
     match suffix {
         to_unit_suffix::n => format!("{}{:?}", (num / 1e-09) as i64, suffix),
@@ -369,6 +390,90 @@ pub fn to_quantity(quantity: &str) -> i64 {
     number * (base.pow(*exponent as u32))
 }
 
+/// The units accepted by [parse_quantity] with their values as the fractions.
+const QUANTITY_UNITS: [(&str, i128, i128); 16] = [
+    ("n", 1, 1_000_000_000),
+    ("u", 1, 1_000_000),
+    ("m", 1, 1_000),
+    ("k", 1_000, 1),
+    ("K", 1_000, 1),
+    ("M", 1_000_000, 1),
+    ("G", 1_000_000_000, 1),
+    ("T", 1_000_000_000_000, 1),
+    ("P", 1_000_000_000_000_000, 1),
+    ("E", 1_000_000_000_000_000_000, 1),
+    ("Ki", 1 << 10, 1),
+    ("Mi", 1 << 20, 1),
+    ("Gi", 1 << 30, 1),
+    ("Ti", 1 << 40, 1),
+    ("Pi", 1 << 50, 1),
+    ("Ei", 1 << 60, 1),
+];
+
+/// Parse a human-readable quantity e.g., `1.5Gi`, `500m`, `2k` or the Kubernetes
+/// resource quantity `1e3` into an int.
+///
+/// Supported suffixes:
+/// SI: n | u | m | k | K | M | G | T | P | E
+/// IEC: Ki | Mi | Gi | Ti | Pi | Ei
+///
+/// The number can be a decimal fraction with a decimal exponent, and the fractional
+/// result is rounded up away from zero like the Kubernetes quantity value e.g., `500m`
+/// is 1.
+pub fn parse_quantity(quantity: &str) -> Result<i64, String> {
+    let quantity = quantity.trim();
+    let number_len = quantity
+        .find(|c: char| !(c.is_ascii_digit() || matches!(c, '+' | '-' | '.')))
+        .unwrap_or(quantity.len());
+    let (number, suffix) = quantity.split_at(number_len);
+    // The decimal exponent e.g., `1e3` and `1E-3`, and note `1E` is the `E` suffix.
+    let (exponent, (unit_num, unit_den)) = match suffix.strip_prefix(['e', 'E']) {
+        Some(exponent) if !exponent.is_empty() && exponent != "i" => {
+            let exponent: i32 = exponent
+                .parse()
+                .map_err(|_| format!("invalid exponent '{exponent}'"))?;
+            (exponent, (1, 1))
+        }
+        _ if suffix.is_empty() => (0, (1, 1)),
+        _ => match QUANTITY_UNITS.iter().find(|(unit, _, _)| *unit == suffix) {
+            Some((_, num, den)) => (0, (*num, *den)),
+            None => {
+                let units: Vec<&str> = QUANTITY_UNITS.iter().map(|(unit, _, _)| *unit).collect();
+                return Err(format!(
+                    "invalid unit '{suffix}', expected one of {}",
+                    units.join(", ")
+                ));
+            }
+        },
+    };
+    let (negative, number) = match number.strip_prefix('-') {
+        Some(number) => (true, number),
+        None => (false, number.strip_prefix('+').unwrap_or(number)),
+    };
+    let (int_part, frac_part) = number.split_once('.').unwrap_or((number, ""));
+    let digits = format!("{int_part}{frac_part}");
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return Err(format!("invalid number '{number}'"));
+    }
+    let overflow = || "the quantity is out of the int range".to_string();
+    let pow10 = |exp: u32| 10_i128.checked_pow(exp).ok_or_else(overflow);
+    let mut num: i128 = digits.parse().map_err(|_| overflow())?;
+    let mut den = unit_den;
+    let exponent = exponent - frac_part.len() as i32;
+    if exponent >= 0 {
+        num = num
+            .checked_mul(pow10(exponent as u32)?)
+            .ok_or_else(overflow)?;
+    } else {
+        den = den
+            .checked_mul(pow10(exponent.unsigned_abs())?)
+            .ok_or_else(overflow)?;
+    }
+    num = num.checked_mul(unit_num).ok_or_else(overflow)?;
+    let value = num / den + i128::from(num % den != 0);
+    i64::try_from(if negative { -value } else { value }).map_err(|_| overflow())
+}
+
 /// Calculate number based on value and binary suffix.
 ///
 /// Supported suffixes:
@@ -441,3 +546,42 @@ pub fn u64_unit_value(unit: &str) -> u64 {
         _ => 1_u64,
     }
 }
+
+#[cfg(test)]
+mod test_units {
+    use super::*;
+
+    #[test]
+    fn test_parse_quantity() {
+        let cases = [
+            ("1", 1),
+            ("-2", -2),
+            ("1.5Gi", 1610612736),
+            ("2k", 2000),
+            ("2K", 2000),
+            ("1Ei", 1152921504606846976),
+            ("1E", 1000000000000000000),
+            ("500m", 1),
+            ("1500m", 2),
+            ("-500m", -1),
+            ("0.5", 1),
+            ("1e3", 1000),
+            ("1.5E2", 150),
+            ("12e-1", 2),
+            ("0Mi", 0),
+        ];
+        for (quantity, expected) in cases {
+            assert_eq!(parse_quantity(quantity), Ok(expected), "{quantity}");
+        }
+        for (quantity, err) in [
+            ("1Xi", "invalid unit 'Xi', expected one of n, u, m, k, K, M, G, T, P, E, Ki, Mi, Gi, Ti, Pi, Ei"),
+            ("Gi", "invalid number ''"),
+            ("1.2.3", "invalid number '1.2.3'"),
+            ("1e", "invalid unit 'e', expected one of n, u, m, k, K, M, G, T, P, E, Ki, Mi, Gi, Ti, Pi, Ei"),
+            ("1ex", "invalid exponent 'x'"),
+            ("16Ei", "the quantity is out of the int range"),
+        ] {
+            assert_eq!(parse_quantity(quantity), Err(err.to_string()), "{quantity}");
+        }
+    }
+}
@@ -0,0 +1,127 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The url system module parses, joins and percent-encodes the URLs.
+
+use ::url::Url;
+use percent_encoding::{percent_decode_str, utf8_percent_encode, AsciiSet, NON_ALPHANUMERIC};
+
+use crate::*;
+
+/// The characters except the RFC 3986 unreserved characters are percent-encoded.
+const COMPONENT: &AsciiSet = &NON_ALPHANUMERIC
+    .remove(b'-')
+    .remove(b'.')
+    .remove(b'_')
+    .remove(b'~');
+
+fn parse_url(url: &str) -> Url {
+    Url::parse(url).unwrap_or_else(|err| panic!("invalid url '{}': {}", url, err))
+}
+
+/// Parse the `url` to a dict with the `scheme`, `username`, `password`, `host`, `port`,
+/// `path`, `query` and `fragment` keys. The `port` is the known default port of the
+/// scheme when it is omitted, and the `query` is a dict of the decoded query parameters.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_url_parse(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(url) = get_call_arg_str(args, kwargs, 0, Some("url")) {
+        let url = parse_url(&url);
+        let mut query = ValueRef::dict(None);
+        for (key, value) in url.query_pairs() {
+            query.dict_update_key_value(&key, ValueRef::str(&value));
+        }
+        let optional_str = |v: Option<&str>| v.map(ValueRef::str).unwrap_or_else(ValueRef::none);
+        let port = url
+            .port_or_known_default()
+            .map(|p| ValueRef::int(p as i64))
+            .unwrap_or_else(ValueRef::none);
+        return ValueRef::dict(Some(&[
+            ("scheme", &ValueRef::str(url.scheme())),
+            ("username", &ValueRef::str(url.username())),
+            ("password", &optional_str(url.password())),
+            ("host", &optional_str(url.host_str())),
+            ("port", &port),
+            ("path", &ValueRef::str(url.path())),
+            ("query", &query),
+            ("fragment", &optional_str(url.fragment())),
+        ]))
+        .into_raw(ctx);
+    }
+    panic!("parse() missing 1 required positional argument: 'url'");
+}
+
+/// Percent-encode the `value` as a URL component, and only the RFC 3986 unreserved
+/// characters are not encoded.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_url_encode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        let encoded = utf8_percent_encode(&value, COMPONENT).to_string();
+        return ValueRef::str(&encoded).into_raw(ctx);
+    }
+    panic!("encode() missing 1 required positional argument: 'value'");
+}
+
+/// Decode the percent-encoded `value`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_url_decode(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    if let Some(value) = get_call_arg_str(args, kwargs, 0, Some("value")) {
+        let decoded = percent_decode_str(&value)
+            .decode_utf8()
+            .unwrap_or_else(|err| panic!("invalid percent-encoded value '{}': {}", value, err));
+        return ValueRef::str(&decoded).into_raw(ctx);
+    }
+    panic!("decode() missing 1 required positional argument: 'value'");
+}
+
+/// Resolve the `ref` URL against the `base` URL e.g., joining `https://kcl-lang.io/docs/`
+/// and `intro` is `https://kcl-lang.io/docs/intro`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_url_join(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("base")),
+        get_call_arg_str(args, kwargs, 1, Some("ref")),
+    ) {
+        (Some(base), Some(reference)) => {
+            let url = parse_url(&base).join(&reference).unwrap_or_else(|err| {
+                panic!("failed to join '{}' with '{}': {}", base, reference, err)
+            });
+            ValueRef::str(url.as_str()).into_raw(ctx)
+        }
+        _ => panic!("join() missing 2 required positional arguments: 'base' and 'ref'"),
+    }
+}
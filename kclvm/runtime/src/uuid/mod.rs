@@ -0,0 +1,67 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The uuid system module generates the UUIDs, and the version 4 UUIDs are
+//! reproducible when the `random_seed` config is set or `random.seed()` is called.
+
+use ::uuid::{Builder, Uuid};
+use rand::Rng;
+
+use crate::*;
+
+impl Context {
+    /// Returns a new version 4 UUID generated by the context random number generator.
+    pub fn new_uuid_v4(&mut self) -> Uuid {
+        Builder::from_random_bytes(self.rng().gen()).into_uuid()
+    }
+}
+
+/// Returns the namespace UUID of the well-known name `dns`, `url`, `oid` and `x500`,
+/// or the UUID string.
+fn parse_namespace(namespace: &str) -> Uuid {
+    match namespace.to_lowercase().as_str() {
+        "dns" => Uuid::NAMESPACE_DNS,
+        "url" => Uuid::NAMESPACE_URL,
+        "oid" => Uuid::NAMESPACE_OID,
+        "x500" => Uuid::NAMESPACE_X500,
+        _ => Uuid::parse_str(namespace)
+            .unwrap_or_else(|err| panic!("invalid namespace uuid '{}': {}", namespace, err)),
+    }
+}
+
+/// Returns a random version 4 UUID string.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_uuid_v4(
+    ctx: *mut kclvm_context_t,
+    _args: *const kclvm_value_ref_t,
+    _kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let ctx = mut_ptr_as_ref(ctx);
+    let uuid = ctx.new_uuid_v4();
+    ValueRef::str(&uuid.to_string()).into_raw(ctx)
+}
+
+/// Returns the version 5 UUID string of the `name` in the `namespace`, which is the
+/// UUID string or one of `dns`, `url`, `oid` and `x500`.
+#[no_mangle]
+#[runtime_fn]
+pub extern "C" fn kclvm_uuid_v5(
+    ctx: *mut kclvm_context_t,
+    args: *const kclvm_value_ref_t,
+    kwargs: *const kclvm_value_ref_t,
+) -> *const kclvm_value_ref_t {
+    let args = ptr_as_ref(args);
+    let kwargs = ptr_as_ref(kwargs);
+    let ctx = mut_ptr_as_ref(ctx);
+
+    match (
+        get_call_arg_str(args, kwargs, 0, Some("namespace")),
+        get_call_arg_str(args, kwargs, 1, Some("name")),
+    ) {
+        (Some(namespace), Some(name)) => {
+            let uuid = Uuid::new_v5(&parse_namespace(&namespace), name.as_bytes());
+            ValueRef::str(&uuid.to_string()).into_raw(ctx)
+        }
+        _ => panic!("v5() missing 2 required positional arguments: 'namespace' and 'name'"),
+    }
+}
@@ -2524,6 +2524,17 @@ pub unsafe extern "C" fn kclvm_config_attr_map(
     value.update_attr_map(name, type_str);
 }
 
+#[no_mangle]
+#[runtime_fn]
+pub unsafe extern "C" fn kclvm_config_secret_attr(
+    value: *mut kclvm_value_ref_t,
+    name: *const kclvm_char_t,
+) {
+    let value = mut_ptr_as_ref(value);
+    let name = c2str(name);
+    value.add_secret_attr(name);
+}
+
 // ----------------------------------------------------------------------------
 // values: decorators
 // ----------------------------------------------------------------------------
@@ -51,18 +51,27 @@ pub use val_schema::*;
 pub mod val_json;
 pub use val_json::*;
 
+pub mod val_hcl;
+pub use val_hcl::*;
+
 pub mod val_bin_aug;
 
 pub mod val_unary;
 
 pub mod val_bin;
 
+pub mod val_bigint;
+pub use val_bigint::*;
+
 pub mod val_plan;
 
 pub mod val_str;
 
 pub mod val_attr;
 
+pub mod val_toml;
+pub use val_toml::*;
+
 pub mod val_type;
 pub use val_type::*;
 
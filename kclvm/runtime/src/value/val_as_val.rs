@@ -1,6 +1,7 @@
 //! Copyright The KCL Authors. All rights reserved.
 
 use crate::*;
+use num_traits::Signed;
 use std::cell::Ref;
 use std::cell::RefMut;
 impl ValueRef {
@@ -15,6 +16,13 @@ impl ValueRef {
             Value::int_value(ref v) => *v,
             Value::float_value(ref v) => *v as i64,
             Value::unit_value(ref v, _, _) => *v as i64,
+            Value::bigint_value(ref v) => {
+                if v.is_negative() {
+                    i64::MIN
+                } else {
+                    i64::MAX
+                }
+            }
             _ => 0,
         }
     }
@@ -33,6 +41,7 @@ impl ValueRef {
             Value::int_value(ref v) => *v as f64,
             Value::float_value(ref v) => *v,
             Value::unit_value(ref v, _, _) => *v,
+            Value::bigint_value(ref v) => bigint_to_f64(v),
             _ => 0.0,
         }
     }
@@ -42,6 +51,7 @@ impl ValueRef {
         match *self.rc.borrow() {
             Value::float_value(v) => v,
             Value::int_value(v) => v as f64,
+            Value::bigint_value(ref v) => bigint_to_f64(v),
             _ => return 0.0,
         }
     }
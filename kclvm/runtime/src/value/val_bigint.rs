@@ -0,0 +1,320 @@
+//! Copyright The KCL Authors. All rights reserved.
+//!
+//! The arbitrary-precision int values. The int operations overflowing i64 are
+//! promoted to the big ints when the `big_int` config is enabled, and a big int is
+//! always normalized back to the plain int when its value fits in i64, thus the
+//! plain ints are never affected.
+
+use std::cmp::Ordering;
+
+use num_bigint::BigInt;
+use num_integer::Integer;
+use num_traits::{Pow, Signed, ToPrimitive, Zero};
+
+use crate::*;
+
+/// The max bit length of the big int results of `**` and `<<`, which grow the results
+/// exponentially with the small operands, e.g., `2 ** 4000000000`, thus the operations
+/// fail fast instead of hanging or exhausting the memory.
+pub const MAX_BIGINT_BITS: u64 = 1 << 20;
+
+/// The binary int operations which support the big ints.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BigIntOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    FloorDiv,
+    Pow,
+    LShift,
+}
+
+impl BigIntOp {
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            BigIntOp::Add => "+",
+            BigIntOp::Sub => "-",
+            BigIntOp::Mul => "*",
+            BigIntOp::Div => "/",
+            BigIntOp::Mod => "%",
+            BigIntOp::FloorDiv => "//",
+            BigIntOp::Pow => "**",
+            BigIntOp::LShift => "<<",
+        }
+    }
+
+    /// Whether the operation on the two i64 operands overflows i64.
+    fn overflows(&self, a: i64, b: i64) -> bool {
+        match self {
+            BigIntOp::Add => a.checked_add(b).is_none(),
+            BigIntOp::Sub => a.checked_sub(b).is_none(),
+            BigIntOp::Mul => a.checked_mul(b).is_none(),
+            BigIntOp::Div | BigIntOp::Mod | BigIntOp::FloorDiv => false,
+            BigIntOp::Pow => b >= 0 && is_i64_overflow_pow(a, b),
+            BigIntOp::LShift => {
+                b >= 0 && a != 0 && (b >= 64 || ((a as i128) << b) != ((a << b) as i128))
+            }
+        }
+    }
+}
+
+impl ValueRef {
+    /// New an int value from the big int, and it is the plain int when the value fits in i64.
+    pub fn bigint(v: BigInt) -> Self {
+        match v.to_i64() {
+            Some(v) => Self::int(v),
+            None => Self::from(Value::bigint_value(v)),
+        }
+    }
+
+    #[inline]
+    pub fn is_bigint(&self) -> bool {
+        matches!(&*self.rc.borrow(), Value::bigint_value(_))
+    }
+
+    /// Returns the big int of the int value, and None for the other values.
+    pub fn as_bigint(&self) -> Option<BigInt> {
+        match &*self.rc.borrow() {
+            Value::int_value(v) => Some(BigInt::from(*v)),
+            Value::bigint_value(v) => Some(v.clone()),
+            _ => None,
+        }
+    }
+
+    /// Whether the binary operation needs to be evaluated on the big ints, i.e., one of
+    /// the operands is a big int, or the operation on the plain ints overflows i64 and
+    /// the `big_int` config is enabled. Note the 32-bit strict range check takes precedence.
+    pub fn is_bigint_bin_op(&self, ctx: &Context, x: &Self, op: BigIntOp) -> bool {
+        match (&*self.rc.borrow(), &*x.rc.borrow()) {
+            (
+                Value::bigint_value(_),
+                Value::int_value(_) | Value::float_value(_) | Value::bigint_value(_),
+            )
+            | (Value::int_value(_) | Value::float_value(_), Value::bigint_value(_)) => true,
+            (Value::int_value(a), Value::int_value(b)) => {
+                ctx.cfg.big_int && !ctx.cfg.strict_range_check && op.overflows(*a, *b)
+            }
+            _ => false,
+        }
+    }
+
+    /// Evaluate the binary operation on the big ints, and the operation with a float
+    /// operand is evaluated on the floats.
+    pub fn bin_bigint(&self, x: &Self, op: BigIntOp) -> Self {
+        if let (Some(a), Some(b)) = (self.as_bigint(), x.as_bigint()) {
+            return match op {
+                BigIntOp::Add => Self::bigint(a + b),
+                BigIntOp::Sub => Self::bigint(a - b),
+                BigIntOp::Mul => Self::bigint(a * b),
+                BigIntOp::Div => Self::float(bigint_to_f64(&a) / bigint_to_f64(&b)),
+                BigIntOp::Mod | BigIntOp::FloorDiv if b.is_zero() => {
+                    panic!("integer division or modulo by zero")
+                }
+                BigIntOp::Mod => Self::bigint(a.mod_floor(&b)),
+                BigIntOp::FloorDiv => Self::bigint(a.div_floor(&b)),
+                BigIntOp::Pow if b.is_negative() => {
+                    Self::float(bigint_to_f64(&a).powf(bigint_to_f64(&b)))
+                }
+                BigIntOp::Pow => match b.to_u32() {
+                    // The result of 0, 1 and -1 never grows.
+                    Some(e)
+                        if a.bits() <= 1
+                            || a.bits().saturating_mul(e as u64) <= MAX_BIGINT_BITS =>
+                    {
+                        Self::bigint(Pow::pow(a, e))
+                    }
+                    _ => panic!(
+                        "exponent {b} is too large, the result exceeds {MAX_BIGINT_BITS} bits"
+                    ),
+                },
+                BigIntOp::LShift => match b.to_u64() {
+                    Some(_) if a.is_zero() => Self::bigint(a),
+                    Some(s) if a.bits().saturating_add(s) <= MAX_BIGINT_BITS => {
+                        Self::bigint(a << s as usize)
+                    }
+                    Some(_) => panic!(
+                        "shift count {b} is too large, the result exceeds {MAX_BIGINT_BITS} bits"
+                    ),
+                    None => panic!("shift count {b} is out of range"),
+                },
+            };
+        }
+        match (self.as_bigint_or_float(), x.as_bigint_or_float()) {
+            (Some(a), Some(b)) => match op {
+                BigIntOp::Add => Self::float(a + b),
+                BigIntOp::Sub => Self::float(a - b),
+                BigIntOp::Mul => Self::float(a * b),
+                BigIntOp::Div => Self::float(a / b),
+                BigIntOp::Mod => Self::float(a % b),
+                BigIntOp::FloorDiv => Self::float((a / b).floor()),
+                BigIntOp::Pow => Self::float(a.powf(b)),
+                BigIntOp::LShift => {
+                    panic_unsupported_bin_op!(op.symbol(), self.type_str(), x.type_str())
+                }
+            },
+            _ => panic_unsupported_bin_op!(op.symbol(), self.type_str(), x.type_str()),
+        }
+    }
+
+    /// Evaluate the augmented assignment on the big ints in place.
+    pub fn bin_aug_bigint(&mut self, x: &Self, op: BigIntOp) -> &mut Self {
+        let value = self.bin_bigint(x, op).rc.borrow().clone();
+        *self.rc.borrow_mut() = value;
+        self
+    }
+
+    /// Compare the numbers when one of them is a big int, and returns None for the
+    /// other values.
+    pub fn bigint_partial_cmp(&self, x: &Self) -> Option<Ordering> {
+        if !self.is_bigint() && !x.is_bigint() {
+            return None;
+        }
+        if let (Some(a), Some(b)) = (self.as_bigint(), x.as_bigint()) {
+            return Some(a.cmp(&b));
+        }
+        match (self.as_bigint_or_float(), x.as_bigint_or_float()) {
+            (Some(a), Some(b)) => a.partial_cmp(&b),
+            _ => None,
+        }
+    }
+
+    fn as_bigint_or_float(&self) -> Option<f64> {
+        match &*self.rc.borrow() {
+            Value::int_value(v) => Some(*v as f64),
+            Value::float_value(v) => Some(*v),
+            Value::bigint_value(v) => Some(bigint_to_f64(v)),
+            _ => None,
+        }
+    }
+}
+
+/// Convert the big int to the nearest float, and it is infinite when out of the f64 range.
+pub fn bigint_to_f64(v: &BigInt) -> f64 {
+    v.to_f64().unwrap_or(if v.is_negative() {
+        f64::NEG_INFINITY
+    } else {
+        f64::INFINITY
+    })
+}
+
+#[cfg(test)]
+mod test_value_bigint {
+    use crate::*;
+    use num_bigint::BigInt;
+    use num_traits::Pow;
+
+    fn big_int_ctx() -> Context {
+        let mut ctx = Context::new();
+        ctx.cfg.big_int = true;
+        ctx
+    }
+
+    #[test]
+    fn test_bigint_promotion() {
+        let mut ctx = big_int_ctx();
+        let max = ValueRef::int(i64::MAX);
+        let result = max.bin_add(&mut ctx, &ValueRef::int(1));
+        assert!(result.is_bigint());
+        assert_eq!(result.to_string(), "9223372036854775808");
+        assert_eq!(result.type_str(), "int");
+        let result = result.bin_sub(&mut ctx, &ValueRef::int(1));
+        assert!(!result.is_bigint());
+        assert_eq!(result.as_int(), i64::MAX);
+        let result = ValueRef::int(2).bin_pow(&mut ctx, &ValueRef::int(100));
+        assert_eq!(result.to_string(), "1267650600228229401496703205376");
+        let result = ValueRef::int(1).bin_bit_lshift(&mut ctx, &ValueRef::int(64));
+        assert_eq!(result.to_string(), "18446744073709551616");
+        let result = ValueRef::int(i64::MIN).bin_mul(&mut ctx, &ValueRef::int(2));
+        assert_eq!(result.to_string(), "-18446744073709551616");
+    }
+
+    #[test]
+    fn test_bigint_bin_ops() {
+        let mut ctx = Context::new();
+        let big = ValueRef::bigint("100000000000000000000".parse::<BigInt>().unwrap());
+        let cases = [
+            (big.bin_mod(&ValueRef::int(7)), "2"),
+            (
+                big.bin_floor_div(&ValueRef::int(-3)),
+                "-33333333333333333334",
+            ),
+            (
+                big.bin_mul(&mut ctx, &big),
+                "10000000000000000000000000000000000000000",
+            ),
+            (big.bin_div(&ValueRef::int(4)), "25000000000000000000.0"),
+            (
+                big.bin_add(&mut ctx, &ValueRef::float(0.5)),
+                "100000000000000000000.0",
+            ),
+            (big.unary_minus(), "-100000000000000000000"),
+        ];
+        for (result, expected) in cases {
+            assert_eq!(result.to_string(), expected);
+        }
+        let mut aug = ValueRef::int(1);
+        aug.bin_aug_add(&mut ctx, &big);
+        assert_eq!(aug.to_string(), "100000000000000000001");
+    }
+
+    #[test]
+    fn test_bigint_cmp() {
+        let big = ValueRef::bigint("100000000000000000000".parse::<BigInt>().unwrap());
+        assert!(big.cmp_greater_than(&ValueRef::int(i64::MAX)));
+        assert!(ValueRef::float(1e21).cmp_greater_than(&big));
+        assert!(big.cmp_equal(&big.deep_copy()));
+        assert!(!big.cmp_equal(&ValueRef::str("100000000000000000000")));
+        assert!(big.is_truthy());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_bigint_overflow_without_big_int() {
+        let mut ctx = Context::new();
+        ValueRef::int(i64::MAX).bin_add(&mut ctx, &ValueRef::int(1));
+    }
+
+    #[test]
+    fn test_bigint_pow_and_shift_limits() {
+        let mut ctx = big_int_ctx();
+        let result = ValueRef::int(2).bin_pow(&mut ctx, &ValueRef::int(1000));
+        assert_eq!(result.as_bigint(), Some(Pow::pow(BigInt::from(2), 1000u32)));
+        let result = ValueRef::int(-1).bin_pow(&mut ctx, &ValueRef::int(4000000001));
+        assert_eq!(result.to_string(), "-1");
+        let big = ValueRef::bigint(Pow::pow(BigInt::from(2), 100u32));
+        let result = big.bin_bit_lshift(&mut ctx, &ValueRef::int(100));
+        assert_eq!(result.as_bigint(), Some(Pow::pow(BigInt::from(2), 200u32)));
+    }
+
+    #[test]
+    #[should_panic(expected = "exponent 4000000000 is too large")]
+    fn test_bigint_pow_too_large() {
+        let mut ctx = big_int_ctx();
+        ValueRef::int(2).bin_pow(&mut ctx, &ValueRef::int(4000000000));
+    }
+
+    #[test]
+    #[should_panic(expected = "shift count 4000000000 is too large")]
+    fn test_bigint_lshift_too_large() {
+        let mut ctx = big_int_ctx();
+        let big = ValueRef::bigint(Pow::pow(BigInt::from(2), 100u32));
+        big.bin_bit_lshift(&mut ctx, &ValueRef::int(4000000000));
+    }
+
+    #[test]
+    fn test_bigint_to_json_yaml() {
+        let big = ValueRef::bigint("100000000000000000000".parse::<BigInt>().unwrap());
+        let huge = ValueRef::bigint(Pow::pow(BigInt::from(2), 200u32));
+        let value = ValueRef::dict(Some(&[("a", &big), ("b", &huge)]));
+        assert_eq!(
+            value.to_json_string(),
+            "{\"a\": 100000000000000000000, \"b\": \"1606938044258990275541962092341162602522202993782792835301376\"}"
+        );
+        assert_eq!(
+            value.to_yaml_string(),
+            "a: 100000000000000000000\nb: '1606938044258990275541962092341162602522202993782792835301376'\n"
+        );
+    }
+}
@@ -4,6 +4,9 @@ use crate::*;
 
 impl ValueRef {
     pub fn bin_add(&self, ctx: &mut Context, x: &Self) -> Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Add) {
+            return self.bin_bigint(x, BigIntOp::Add);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -61,6 +64,9 @@ impl ValueRef {
     }
 
     pub fn bin_sub(&self, ctx: &mut Context, x: &Self) -> Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Sub) {
+            return self.bin_bigint(x, BigIntOp::Sub);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -97,6 +103,9 @@ impl ValueRef {
     }
 
     pub fn bin_mul(&self, ctx: &mut Context, x: &Self) -> Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Mul) {
+            return self.bin_bigint(x, BigIntOp::Mul);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -132,6 +141,7 @@ impl ValueRef {
             (Value::str_value(a), Value::int_value(b)) => Self::str(a.repeat(*b as usize).as_ref()),
             (Value::int_value(b), Value::str_value(a)) => Self::str(a.repeat(*b as usize).as_ref()),
             (Value::list_value(a), Value::int_value(b)) => {
+                ctx.record_allocations(a.values.len().saturating_mul((*b).max(0) as usize));
                 let mut list = ListValue::default();
                 for _ in 0..(*b as usize) {
                     for x in a.values.iter() {
@@ -141,6 +151,7 @@ impl ValueRef {
                 Self::from(Value::list_value(Box::new(list)))
             }
             (Value::int_value(b), Value::list_value(a)) => {
+                ctx.record_allocations(a.values.len().saturating_mul((*b).max(0) as usize));
                 let mut list = ListValue::default();
                 for _ in 0..(*b as usize) {
                     for x in a.values.iter() {
@@ -154,6 +165,9 @@ impl ValueRef {
     }
 
     pub fn bin_div(&self, x: &Self) -> Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_bigint(x, BigIntOp::Div);
+        }
         match (&*self.rc.borrow(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => Self::float((*a as f64) / (*b as f64)),
             (Value::float_value(a), Value::float_value(b)) => Self::float(*a / *b),
@@ -164,6 +178,9 @@ impl ValueRef {
     }
 
     pub fn bin_mod(&self, x: &Self) -> Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_bigint(x, BigIntOp::Mod);
+        }
         match (&*self.rc.borrow(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 let x = *a;
@@ -182,6 +199,9 @@ impl ValueRef {
     }
 
     pub fn bin_pow(&self, ctx: &mut Context, x: &Self) -> Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Pow) {
+            return self.bin_bigint(x, BigIntOp::Pow);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -218,6 +238,9 @@ impl ValueRef {
     }
 
     pub fn bin_floor_div(&self, x: &Self) -> Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_bigint(x, BigIntOp::FloorDiv);
+        }
         match (&*self.rc.borrow(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 let x = *a;
@@ -236,6 +259,9 @@ impl ValueRef {
     }
 
     pub fn bin_bit_lshift(&self, ctx: &mut Context, x: &Self) -> Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::LShift) {
+            return self.bin_bigint(x, BigIntOp::LShift);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
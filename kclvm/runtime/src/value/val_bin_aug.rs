@@ -4,6 +4,9 @@ use crate::*;
 
 impl ValueRef {
     pub fn bin_aug_add(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Add) {
+            return self.bin_aug_bigint(x, BigIntOp::Add);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -61,6 +64,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_sub(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Sub) {
+            return self.bin_aug_bigint(x, BigIntOp::Sub);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -107,6 +113,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_mul(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Mul) {
+            return self.bin_aug_bigint(x, BigIntOp::Mul);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -167,6 +176,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_div(&mut self, x: &Self) -> &mut Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_aug_bigint(x, BigIntOp::Div);
+        }
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 *a /= *b;
@@ -193,6 +205,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_mod(&mut self, x: &Self) -> &mut Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_aug_bigint(x, BigIntOp::Mod);
+        }
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 let x = *a;
@@ -225,6 +240,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_pow(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::Pow) {
+            return self.bin_aug_bigint(x, BigIntOp::Pow);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
@@ -269,6 +287,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_floor_div(&mut self, x: &Self) -> &mut Self {
+        if self.is_bigint() || x.is_bigint() {
+            return self.bin_aug_bigint(x, BigIntOp::FloorDiv);
+        }
         let valid = match (&mut *self.rc.borrow_mut(), &*x.rc.borrow()) {
             (Value::int_value(a), Value::int_value(b)) => {
                 let x = *a;
@@ -301,6 +322,9 @@ impl ValueRef {
     }
 
     pub fn bin_aug_bit_lshift(&mut self, ctx: &mut Context, x: &Self) -> &mut Self {
+        if self.is_bigint_bin_op(ctx, x, BigIntOp::LShift) {
+            return self.bin_aug_bigint(x, BigIntOp::LShift);
+        }
         let strict_range_check_32 = ctx.cfg.strict_range_check;
         let strict_range_check_64 = ctx.cfg.debug_mode || !ctx.cfg.strict_range_check;
 
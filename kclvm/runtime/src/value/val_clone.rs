@@ -41,6 +41,9 @@ impl ValueRef {
             Value::float_value(ref v) => ValueRef {
                 rc: Rc::new(RefCell::new(Value::float_value(*v))),
             },
+            Value::bigint_value(ref v) => ValueRef {
+                rc: Rc::new(RefCell::new(Value::bigint_value(v.clone()))),
+            },
             Value::unit_value(ref v, ref raw, ref unit) => ValueRef {
                 rc: Rc::new(RefCell::new(Value::unit_value(*v, *raw, unit.clone()))),
             },
@@ -79,6 +82,9 @@ impl ValueRef {
                         dict.update_attr_map(key, type_str);
                     }
                 }
+                for name in &v.config.secret_attrs {
+                    dict.add_secret_attr(name);
+                }
                 return ValueRef {
                     rc: Rc::new(RefCell::new(Value::schema_value(Box::new(SchemaValue {
                         name: v.name.clone(),
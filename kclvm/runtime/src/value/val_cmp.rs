@@ -1,10 +1,15 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+use std::cmp::Ordering;
+
 use crate::*;
 
 // cmp
 impl ValueRef {
     pub fn cmp_equal(&self, x: &Self) -> bool {
+        if let Some(ord) = self.bigint_partial_cmp(x) {
+            return ord == Ordering::Equal;
+        }
         match *self.rc.borrow() {
             Value::int_value(a) => match *x.rc.borrow() {
                 Value::int_value(b) => a == b,
@@ -71,6 +76,9 @@ impl ValueRef {
     }
 
     pub fn cmp_less_than(&self, x: &Self) -> bool {
+        if let Some(ord) = self.bigint_partial_cmp(x) {
+            return ord == Ordering::Less;
+        }
         match &*self.rc.borrow() {
             Value::int_value(a) => match &*x.rc.borrow() {
                 Value::int_value(b) => *a < *b,
@@ -139,6 +147,9 @@ impl ValueRef {
     }
 
     pub fn cmp_less_than_or_equal(&self, x: &Self) -> bool {
+        if let Some(ord) = self.bigint_partial_cmp(x) {
+            return ord != Ordering::Greater;
+        }
         match &*self.rc.borrow() {
             Value::int_value(a) => match &*x.rc.borrow() {
                 Value::int_value(b) => *a <= *b,
@@ -207,6 +218,9 @@ impl ValueRef {
     }
 
     pub fn cmp_greater_than(&self, x: &Self) -> bool {
+        if let Some(ord) = self.bigint_partial_cmp(x) {
+            return ord == Ordering::Greater;
+        }
         match &*self.rc.borrow() {
             Value::int_value(a) => match &*x.rc.borrow() {
                 Value::int_value(b) => *a > *b,
@@ -275,6 +289,9 @@ impl ValueRef {
     }
 
     pub fn cmp_greater_than_or_equal(&self, x: &Self) -> bool {
+        if let Some(ord) = self.bigint_partial_cmp(x) {
+            return ord != Ordering::Less;
+        }
         match &*self.rc.borrow() {
             Value::int_value(a) => match &*x.rc.borrow() {
                 Value::int_value(b) => *a >= *b,
@@ -4,6 +4,7 @@ use crate::*;
 
 pub const DEPRECATED_DECORATOR: &str = "deprecated";
 pub const DEPRECATED_INFO: &str = "info";
+pub const SECRET_DECORATOR: &str = "secret";
 
 impl DecoratorValue {
     pub fn new(name: &str, args: &ValueRef, kwargs: &ValueRef) -> DecoratorValue {
@@ -26,9 +27,13 @@ impl DecoratorValue {
         let line = config_meta.get_by_key(CONFIG_META_LINE);
         match self.name.as_str() {
             DEPRECATED_DECORATOR => {
-                let version = self.kwargs.kwarg("version");
+                let version = self
+                    .kwargs
+                    .kwarg("version")
+                    .or_else(|| self.kwargs.kwarg("since"));
                 let reason = self.kwargs.kwarg("reason");
                 let strict = self.kwargs.kwarg("strict");
+                let replacement = self.kwargs.kwarg("replacement");
                 let version = if let Some(v) = version {
                     v.as_str()
                 } else {
@@ -53,6 +58,10 @@ impl DecoratorValue {
                     let reason = format!(", {reason}");
                     msg.push_str(&reason);
                 }
+                if let Some(replacement) = replacement {
+                    let replacement = format!(", use '{}' instead", replacement.as_str());
+                    msg.push_str(&replacement);
+                }
                 if strict {
                     if is_schema_target || config_value.get_by_key(attr_name).is_some() {
                         let mut err_msg = format!("{attr_name} was deprecated ");
@@ -81,6 +90,13 @@ impl DecoratorValue {
                 }
             }
             DEPRECATED_INFO => { /* Nothing to do on Info decorator */ }
+            SECRET_DECORATOR => {
+                // The secret attributes are marked in the schema value by the attribute
+                // evaluation, and the secret decorator can not be used on the schemas.
+                if is_schema_target {
+                    panic!("the secret decorator can only be used on the schema attributes")
+                }
+            }
             _ => {
                 let msg = format!("Unknown decorator {}", self.name);
                 panic!("{}", msg);
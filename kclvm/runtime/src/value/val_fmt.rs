@@ -985,6 +985,7 @@ impl fmt::Display for ValueRef {
                 }
             }
             Value::int_value(ref v) => write!(f, "{v}"),
+            Value::bigint_value(ref v) => write!(f, "{v}"),
             Value::float_value(ref v) => {
                 let mut float_str = v.to_string();
                 if !float_str.contains('.') {
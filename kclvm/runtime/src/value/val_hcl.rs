@@ -0,0 +1,207 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+use crate::*;
+
+const HCL_INDENT: &str = "  ";
+
+/// HCL encode options.
+/// - sort_keys: Sort the encode result by keys (defaults to false).
+/// - ignore_private: Whether to ignore the attribute whose name starts with
+///     a character `_` (defaults to false).
+/// - ignore_none: Whether to ignore the attribute whose value is `None` (defaults to false).
+#[derive(Debug, Default)]
+pub struct HclEncodeOptions {
+    pub sort_keys: bool,
+    pub ignore_private: bool,
+    pub ignore_none: bool,
+}
+
+impl ValueRef {
+    /// Encode the dict or schema value to an HCL document string, in which the top-level
+    /// keys are the attributes e.g., the Terraform variable definitions (`.tfvars`) files.
+    /// The nested dicts and lists are encoded to the object and tuple expressions.
+    pub fn to_hcl_string_with_options(&self, opts: &HclEncodeOptions) -> String {
+        let json_opts = JsonEncodeOptions {
+            sort_keys: opts.sort_keys,
+            indent: 0,
+            ignore_private: opts.ignore_private,
+            ignore_none: opts.ignore_none,
+        };
+        match self.build_json(&json_opts) {
+            JsonValue::Object(values) => {
+                let mut s = String::new();
+                for (key, value) in &values {
+                    if !is_hcl_identifier(key) {
+                        panic!("invalid HCL attribute name '{}'", key);
+                    }
+                    s.push_str(key);
+                    s.push_str(" = ");
+                    write_hcl_expr(&mut s, value, 0);
+                    s.push('\n');
+                }
+                s
+            }
+            _ => panic!(
+                "only the dict and schema values can be encoded to HCL, got '{}'",
+                self.type_str()
+            ),
+        }
+    }
+
+    pub fn to_hcl_string(&self) -> String {
+        self.to_hcl_string_with_options(&Default::default())
+    }
+}
+
+/// Whether the name is an HCL identifier, which can be used as the attribute name
+/// and the object key without quotes.
+fn is_hcl_identifier(name: &str) -> bool {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(c) if c.is_alphabetic() || c == '_' => {
+            chars.all(|c| c.is_alphanumeric() || c == '_' || c == '-')
+        }
+        _ => false,
+    }
+}
+
+/// Write the quoted HCL string, and the template sequences `${` and `%{` are escaped
+/// to keep the string literal.
+fn write_hcl_string(s: &mut String, value: &str) {
+    s.push('"');
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => s.push_str("\\\""),
+            '\\' => s.push_str("\\\\"),
+            '\n' => s.push_str("\\n"),
+            '\r' => s.push_str("\\r"),
+            '\t' => s.push_str("\\t"),
+            '$' | '%' if chars.peek() == Some(&'{') => {
+                s.push(c);
+                s.push(c);
+            }
+            c if c.is_control() => s.push_str(&format!("\\u{:04x}", c as u32)),
+            c => s.push(c),
+        }
+    }
+    s.push('"');
+}
+
+fn write_hcl_expr(s: &mut String, value: &JsonValue, level: usize) {
+    match value {
+        JsonValue::Null => s.push_str("null"),
+        JsonValue::Bool(v) => s.push_str(if *v { "true" } else { "false" }),
+        JsonValue::Number(v) => s.push_str(&v.to_string()),
+        JsonValue::BigInt(v) => s.push_str(&v.to_string()),
+        JsonValue::String(v) => write_hcl_string(s, v),
+        JsonValue::Array(values) => {
+            if values.is_empty() {
+                s.push_str("[]");
+                return;
+            }
+            s.push_str("[\n");
+            for value in values {
+                s.push_str(&HCL_INDENT.repeat(level + 1));
+                write_hcl_expr(s, value, level + 1);
+                s.push_str(",\n");
+            }
+            s.push_str(&HCL_INDENT.repeat(level));
+            s.push(']');
+        }
+        JsonValue::Object(values) => {
+            if values.is_empty() {
+                s.push_str("{}");
+                return;
+            }
+            s.push_str("{\n");
+            for (key, value) in values {
+                s.push_str(&HCL_INDENT.repeat(level + 1));
+                if is_hcl_identifier(key) {
+                    s.push_str(key);
+                } else {
+                    write_hcl_string(s, key);
+                }
+                s.push_str(" = ");
+                write_hcl_expr(s, value, level + 1);
+                s.push('\n');
+            }
+            s.push_str(&HCL_INDENT.repeat(level));
+            s.push('}');
+        }
+    }
+}
+
+#[cfg(test)]
+mod test_value_hcl {
+    use crate::*;
+
+    #[test]
+    fn test_value_to_hcl_string() {
+        let value = ValueRef::dict(Some(&[
+            ("region", &ValueRef::str("us-east-1")),
+            ("count", &ValueRef::int(2)),
+            ("ratio", &ValueRef::float(0.5)),
+            ("enabled", &ValueRef::bool(true)),
+            ("none", &ValueRef::none()),
+            ("_private", &ValueRef::str("${var}")),
+            (
+                "tags",
+                &ValueRef::dict(Some(&[
+                    ("app.kubernetes.io/name", &ValueRef::str("app")),
+                    ("env", &ValueRef::str("prod\n")),
+                ])),
+            ),
+            (
+                "zones",
+                &ValueRef::list_str(&["a".to_string(), "b".to_string()]),
+            ),
+            ("empty", &ValueRef::list(None)),
+        ]));
+        assert_eq!(
+            value.to_hcl_string(),
+            r#"region = "us-east-1"
+count = 2
+ratio = 0.5
+enabled = true
+none = null
+_private = "$${var}"
+tags = {
+  "app.kubernetes.io/name" = "app"
+  env = "prod\n"
+}
+zones = [
+  "a",
+  "b",
+]
+empty = []
+"#
+        );
+        let opts = HclEncodeOptions {
+            sort_keys: true,
+            ignore_private: true,
+            ignore_none: true,
+        };
+        assert_eq!(
+            ValueRef::dict(Some(&[
+                ("b", &ValueRef::int(1)),
+                ("a", &ValueRef::none()),
+                ("_c", &ValueRef::int(1)),
+            ]))
+            .to_hcl_string_with_options(&opts),
+            "b = 1\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "invalid HCL attribute name 'a.b'")]
+    fn test_value_to_hcl_string_invalid_name() {
+        ValueRef::dict(Some(&[("a.b", &ValueRef::int(1))])).to_hcl_string();
+    }
+
+    #[test]
+    #[should_panic(expected = "only the dict and schema values can be encoded to HCL, got 'int'")]
+    fn test_value_to_hcl_string_fail() {
+        ValueRef::int(1).to_hcl_string();
+    }
+}
@@ -54,7 +54,7 @@ impl ValueRef {
     pub fn is_number(&self) -> bool {
         matches!(
             &*self.rc.borrow(),
-            Value::int_value(_) | Value::float_value(_)
+            Value::int_value(_) | Value::float_value(_) | Value::bigint_value(_)
         )
     }
 
@@ -110,6 +110,7 @@ impl ValueRef {
                 | Value::float_value(_)
                 | Value::str_value(_)
                 | Value::unit_value(..)
+                | Value::bigint_value(_)
         )
     }
 }
@@ -2,12 +2,16 @@
 
 use bstr::ByteSlice;
 use indexmap::IndexMap;
+use num_bigint::BigInt;
+use num_traits::ToPrimitive;
 use serde::{
     de::{DeserializeSeed, MapAccess, SeqAccess, Visitor},
     Deserialize, Serialize,
 };
 
-use crate::{val_plan::KCL_PRIVATE_VAR_PREFIX, ConfigEntryOperationKind, Context, ValueRef};
+use crate::{
+    bigint_to_f64, val_plan::KCL_PRIVATE_VAR_PREFIX, ConfigEntryOperationKind, Context, ValueRef,
+};
 
 macro_rules! tri {
     ($e:expr $(,)?) => {
@@ -40,6 +44,9 @@ pub(crate) enum JsonValue {
 
     Number(serde_json::Number),
 
+    /// The int out of the i64 and u64 range.
+    BigInt(BigInt),
+
     String(String),
 
     Array(Vec<JsonValue>),
@@ -111,6 +118,22 @@ impl<'de> Deserialize<'de> for JsonValue {
                 Ok(Self::Value::Number(value.into()))
             }
 
+            #[inline]
+            fn visit_i128<E>(self, value: i128) -> Result<Self::Value, E> {
+                match i64::try_from(value) {
+                    Ok(value) => self.visit_i64(value),
+                    Err(_) => Ok(Self::Value::BigInt(value.into())),
+                }
+            }
+
+            #[inline]
+            fn visit_u128<E>(self, value: u128) -> Result<Self::Value, E> {
+                match u64::try_from(value) {
+                    Ok(value) => self.visit_u64(value),
+                    Err(_) => Ok(Self::Value::BigInt(value.into())),
+                }
+            }
+
             #[inline]
             fn visit_f64<E>(self, value: f64) -> Result<Self::Value, E> {
                 Ok(serde_json::Number::from_f64(value)
@@ -196,6 +219,13 @@ impl Serialize for JsonValue {
             JsonValue::Null => serializer.serialize_unit(),
             JsonValue::Bool(b) => serializer.serialize_bool(*b),
             JsonValue::Number(n) => n.serialize(serializer),
+            // The big ints out of the 128-bit range are serialized as strings, because
+            // the serde data model doesn't support the wider numbers.
+            JsonValue::BigInt(n) => match (n.to_i128(), n.to_u128()) {
+                (Some(v), _) => serializer.serialize_i128(v),
+                (_, Some(v)) => serializer.serialize_u128(v),
+                _ => serializer.collect_str(n),
+            },
             JsonValue::String(s) => serializer.serialize_str(s),
             JsonValue::Array(v) => v.serialize(serializer),
             JsonValue::Object(m) => {
@@ -393,6 +423,8 @@ impl ValueRef {
                     let n = val.as_u64().unwrap();
                     if n <= i64::max_value() as u64 {
                         Self::int(n as i64)
+                    } else if ctx.cfg.big_int {
+                        Self::bigint(n.into())
                     } else {
                         Self::float(n as f64)
                     }
@@ -400,6 +432,13 @@ impl ValueRef {
                     Self::float(val.as_f64().unwrap())
                 }
             }
+            JsonValue::BigInt(val) => {
+                if ctx.cfg.big_int {
+                    Self::bigint(val.clone())
+                } else {
+                    Self::float(bigint_to_f64(val))
+                }
+            }
             JsonValue::Bool(val) => Self::bool(*val),
             JsonValue::Null => Self::none(),
         }
@@ -451,6 +490,7 @@ impl ValueRef {
 
             crate::Value::bool_value(ref v) => JsonValue::Bool(*v),
             crate::Value::int_value(ref v) => JsonValue::Number(serde_json::Number::from(*v)),
+            crate::Value::bigint_value(ref v) => JsonValue::BigInt(v.clone()),
             crate::Value::float_value(ref v) => match serde_json::Number::from_f64(*v) {
                 Some(n) => JsonValue::Number(n),
                 None => JsonValue::Null,
@@ -621,4 +661,90 @@ mod test_value_json {
             assert_eq!(result, expected);
         }
     }
+
+    #[test]
+    fn test_value_json_round_trip() {
+        let mut ctx = Context::new();
+        let cases = [
+            ValueRef::none(),
+            ValueRef::bool(true),
+            ValueRef::int(-1),
+            ValueRef::int(i64::MAX),
+            ValueRef::float(1.0),
+            ValueRef::float(-0.25),
+            ValueRef::float(1e-7),
+            ValueRef::str(""),
+            ValueRef::str("quote \" backslash \\ newline \n tab \t"),
+            ValueRef::str("unicode 你好 🚀"),
+            ValueRef::list(Some(&[])),
+            ValueRef::list(Some(&[
+                &ValueRef::int(1),
+                &ValueRef::str("a"),
+                &ValueRef::none(),
+                &ValueRef::list_int(&[1, 2]),
+            ])),
+            ValueRef::dict(Some(&[])),
+            ValueRef::dict(Some(&[
+                ("b", &ValueRef::int(1)),
+                ("a", &ValueRef::dict(Some(&[("c", &ValueRef::float(1.5))]))),
+                ("c", &ValueRef::bool(false)),
+            ])),
+        ];
+        for value in cases {
+            let json = value.to_json_string();
+            let result = ValueRef::from_json(&mut ctx, &json).unwrap();
+            assert_eq!(result, value, "{}", json);
+            let json = value.to_json_string_with_options(&JsonEncodeOptions {
+                indent: 4,
+                ..Default::default()
+            });
+            let result = ValueRef::from_json(&mut ctx, &json).unwrap();
+            assert_eq!(result, value, "{}", json);
+        }
+    }
+
+    #[test]
+    fn test_value_to_json_string_with_options() {
+        let value = ValueRef::dict(Some(&[
+            ("b", &ValueRef::none()),
+            ("_a", &ValueRef::int(1)),
+            (
+                "a",
+                &ValueRef::dict(Some(&[("d", &ValueRef::int(2)), ("c", &ValueRef::int(3))])),
+            ),
+        ]));
+        let cases = [
+            (
+                JsonEncodeOptions::default(),
+                "{\"b\": null, \"_a\": 1, \"a\": {\"d\": 2, \"c\": 3}}",
+            ),
+            (
+                JsonEncodeOptions {
+                    sort_keys: true,
+                    ..Default::default()
+                },
+                "{\"_a\": 1, \"a\": {\"c\": 3, \"d\": 2}, \"b\": null}",
+            ),
+            (
+                JsonEncodeOptions {
+                    ignore_private: true,
+                    ignore_none: true,
+                    ..Default::default()
+                },
+                "{\"a\": {\"d\": 2, \"c\": 3}}",
+            ),
+            (
+                JsonEncodeOptions {
+                    indent: 2,
+                    ignore_private: true,
+                    ignore_none: true,
+                    ..Default::default()
+                },
+                "{\n  \"a\": {\n    \"d\": 2,\n    \"c\": 3\n  }\n}",
+            ),
+        ];
+        for (opts, expected) in cases {
+            assert_eq!(value.to_json_string_with_options(&opts), expected);
+        }
+    }
 }
@@ -1,6 +1,7 @@
 //! Copyright The KCL Authors. All rights reserved.
 
 use crate::*;
+use num_traits::Zero;
 
 impl ValueRef {
     #[inline]
@@ -17,6 +18,7 @@ impl ValueRef {
             Value::schema_value(ref v) => !v.config.values.is_empty(),
             Value::func_value(_) => true,
             Value::unit_value(ref v, _, _) => *v != 0.0,
+            Value::bigint_value(ref v) => !v.is_zero(),
         }
     }
 
@@ -1,10 +1,27 @@
 //! Copyright The KCL Authors. All rights reserved.
 
+use std::fmt;
+use std::io::Write;
+use std::str::FromStr;
+
 use crate::*;
+use serde::{Deserialize, Serialize};
+
+use self::walker::{walk_value, walk_value_mut};
 
 pub const KCL_PRIVATE_VAR_PREFIX: &str = "_";
 const LIST_DICT_TEMP_KEY: &str = "$";
 const SCHEMA_TYPE_META_ATTR: &str = "_type";
+/// The masked secret value in the plan result.
+pub const SECRET_MASK: &str = "******";
+/// The placeholder prefix of the externalized secret values followed by the index of
+/// the secret value.
+const SECRET_REF_PREFIX: &str = "\0kcl-secret:";
+/// The default filename template of the plan result split into the files.
+pub const DEFAULT_FILENAME_TEMPLATE: &str = "{key}.{ext}";
+/// The provenance meta attribute of the planned schema instances, which is removed from
+/// the written documents or replaced by the YAML comment placeholder.
+const PROVENANCE_META_ATTR: &str = "$provenance";
 
 /// PlanOptions denotes the configuration required to execute the KCL
 /// program and the JSON/YAML planning.
@@ -24,6 +41,26 @@ pub struct PlanOptions {
     pub query_paths: Vec<String>,
     /// YAML plan separator string, default is `---`.
     pub sep: Option<String>,
+    /// Round the float values to the decimal places with the rounding half to even
+    /// in the plan process.
+    pub decimal_places: Option<u32>,
+    /// Whether to emit the secret values as is instead of masking them with `******`.
+    pub show_secrets: bool,
+    /// Whether to move the secret values to a separate secrets document at the end of
+    /// the plan result instead of masking them with `******`.
+    pub externalize_secrets: bool,
+    /// The key paths e.g., `app.password` of the secret values besides the schema
+    /// attributes with the `@secret` decorator.
+    pub secret_paths: Vec<String>,
+    /// The format of the plan result besides JSON, default is YAML.
+    pub output_format: PlanFormat,
+    /// The key order strategy of the planned configs, and `sort_keys` takes precedence
+    /// over it.
+    pub key_order: KeyOrder,
+    /// Annotate the planned schema instances with the schema type and the source location
+    /// as the YAML comments or the sidecar map, which is the general form of the
+    /// `include_schema_type_path` option without changing the planned values.
+    pub provenance: ProvenanceMode,
 }
 
 /// Filter list or config results with context options.
@@ -140,6 +177,9 @@ fn filter_results(ctx: &Context, key_values: &ValueRef) -> Vec<ValueRef> {
                 result.dict_update_key_value(key.as_str(), value.clone());
             }
         }
+        if ctx.plan_opts.key_order == KeyOrder::Schema && !key_values.attr_map.is_empty() {
+            sort_by_schema_attrs(&mut results[0], &key_values.attr_map);
+        }
         results.iter().enumerate().map(|v| v.1).cloned().collect()
     } else {
         results
@@ -163,9 +203,149 @@ fn handle_schema(ctx: &Context, value: &ValueRef) -> Vec<ValueRef> {
             }
         }
     }
+    // Keep the schema type of the planned object for the filename template.
+    if value.is_schema() {
+        if let Some(v) = filtered.get_mut(0) {
+            v.set_potential_schema_type(&value_type_path(value, true));
+            if ctx.plan_opts.provenance != ProvenanceMode::None && v.is_config() {
+                v.dict_update_key_value(PROVENANCE_META_ATTR, provenance_meta(value));
+            }
+        }
+    }
     filtered
 }
 
+/// Returns the provenance meta value of the schema instance, which is the schema type
+/// and the location of the schema config expression.
+fn provenance_meta(value: &ValueRef) -> ValueRef {
+    let config_meta = value.schema_config_meta();
+    let meta = |key, default: ValueRef| config_meta.get_by_key(key).unwrap_or(default);
+    ValueRef::dict(Some(&[
+        ("schema", &ValueRef::str(&value_type_path(value, true))),
+        ("filename", &meta(CONFIG_META_FILENAME, ValueRef::str(""))),
+        ("line", &meta(CONFIG_META_LINE, ValueRef::int(0))),
+        ("column", &meta(CONFIG_META_COLUMN, ValueRef::int(0))),
+    ]))
+}
+
+/// The provenance of a planned schema instance.
+#[derive(PartialEq, Eq, Clone, Debug, Default, Serialize, Deserialize)]
+pub struct Provenance {
+    /// The schema type path e.g., `pkg.App`, and the main package prefix is omitted.
+    pub schema: String,
+    /// The filename of the schema config expression, which is empty when unknown.
+    pub filename: String,
+    pub line: i64,
+    pub column: i64,
+}
+
+impl Provenance {
+    fn from_meta(meta: &ValueRef) -> Self {
+        let get = |key| meta.get_by_key(key).unwrap_or_else(ValueRef::none);
+        Provenance {
+            schema: get("schema").as_str(),
+            filename: get("filename").as_str(),
+            line: get("line").as_int(),
+            column: get("column").as_int(),
+        }
+    }
+}
+
+impl fmt::Display for Provenance {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.filename.is_empty() {
+            write!(f, "{}", self.schema)
+        } else {
+            write!(
+                f,
+                "{} ({}:{}:{})",
+                self.schema, self.filename, self.line, self.column
+            )
+        }
+    }
+}
+
+/// Remove the provenance meta attributes in the planned value and collect the
+/// provenances keyed by the JSONPath of the planned objects e.g., `$.app`. When
+/// `placeholder` is true, the attribute is replaced by the first key whose value is
+/// the index of the provenance to be written as the YAML comment.
+fn take_provenances(
+    v: &ValueRef,
+    path: &str,
+    placeholder: bool,
+    provenances: &mut IndexMap<String, Provenance>,
+) {
+    if v.is_config() {
+        let mut config = v.clone();
+        if let Some(meta) = config.get_by_key(PROVENANCE_META_ATTR) {
+            config.dict_remove(PROVENANCE_META_ATTR);
+            // The empty object is kept empty, or the comment makes it a none value.
+            if placeholder && !config.as_dict_ref().values.is_empty() {
+                let index = ValueRef::int(provenances.len() as i64);
+                config.dict_update_key_value(PROVENANCE_META_ATTR, index);
+                config.as_dict_mut_ref().values.sort_by(|k1, _, k2, _| {
+                    (k2 == PROVENANCE_META_ATTR).cmp(&(k1 == PROVENANCE_META_ATTR))
+                });
+            }
+            provenances.insert(path.to_string(), Provenance::from_meta(&meta));
+        }
+        let entries: Vec<(String, ValueRef)> = config
+            .as_dict_ref()
+            .values
+            .iter()
+            .filter(|(k, _)| k.as_str() != PROVENANCE_META_ATTR)
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        for (key, value) in entries {
+            let path = format!("{path}{}", json_path_segment(&key));
+            take_provenances(&value, &path, placeholder, provenances);
+        }
+    } else if v.is_list() {
+        for (i, value) in v.as_list_ref().values.iter().enumerate() {
+            take_provenances(value, &format!("{path}[{i}]"), placeholder, provenances);
+        }
+    }
+}
+
+/// Replace the provenance placeholders in the YAML document with the YAML comments,
+/// and the placeholder is the first key of the object e.g., `- $provenance: 0`.
+fn replace_provenance_placeholders(
+    yaml: &str,
+    provenances: &IndexMap<String, Provenance>,
+) -> String {
+    let placeholder = format!("{PROVENANCE_META_ATTR}: ");
+    let mut output = String::with_capacity(yaml.len());
+    for line in yaml.split_inclusive('\n') {
+        let start = line.len() - line.trim_start_matches(|c| c == ' ' || c == '-').len();
+        let provenance = line[start..]
+            .strip_prefix(&placeholder)
+            .and_then(|index| index.trim_end().parse::<usize>().ok())
+            .and_then(|index| provenances.get_index(index));
+        match provenance {
+            Some((_, provenance)) => {
+                output.push_str(&line[..start]);
+                output.push_str(&format!("# {provenance}"));
+                if line.ends_with('\n') {
+                    output.push('\n');
+                }
+            }
+            None => output.push_str(line),
+        }
+    }
+    output
+}
+
+/// Sort the keys of the planned config in the declaration order of the schema attributes,
+/// and the keys which are not schema attributes e.g., the index signature keys and
+/// `_type` are kept in their order after the schema attributes.
+fn sort_by_schema_attrs(config: &mut ValueRef, attr_map: &IndexMap<String, String>) {
+    let mut config = config.as_dict_mut_ref();
+    let index = |key: &String| attr_map.get_index_of(key).unwrap_or(attr_map.len());
+    config
+        .values
+        .sort_by(|k1, _, k2, _| index(k1).cmp(&index(k2)));
+}
+
 /// Returns the type path of the runtime value `v`.
 pub(crate) fn value_type_path(v: &ValueRef, full_name: bool) -> String {
     if v.is_schema() {
@@ -201,55 +381,592 @@ pub fn type_of(v: &ValueRef, full_name: bool) -> String {
     builtin::type_of(v, &ValueRef::bool(full_name)).as_str()
 }
 
+/// Returns a copy of the value `v` whose float values are rounded to the decimal places.
+fn round_float_values(v: &ValueRef, places: u32) -> ValueRef {
+    let value = v.deep_copy();
+    walk_value_mut(&value, &mut |v| {
+        let rounded = match &*v.rc.borrow() {
+            Value::float_value(f) => Some(round_float(*f, places)),
+            _ => None,
+        };
+        if let Some(f) = rounded {
+            *v.rc.borrow_mut() = Value::float_value(f);
+        }
+    });
+    value
+}
+
+/// Returns whether the value contains the schema attributes with the `@secret` decorator.
+fn has_secret_attrs(v: &ValueRef) -> bool {
+    let found = std::cell::Cell::new(false);
+    walk_value(v, &|v| match &*v.rc.borrow() {
+        Value::dict_value(dict) if !dict.secret_attrs.is_empty() => found.set(true),
+        Value::schema_value(schema) if !schema.config.secret_attrs.is_empty() => found.set(true),
+        _ => {}
+    });
+    found.get()
+}
+
+/// Returns the non-none secret attribute values of the config.
+fn secret_attr_values(config: &DictValue) -> Vec<(String, ValueRef)> {
+    config
+        .secret_attrs
+        .iter()
+        .filter_map(|key| {
+            config
+                .values
+                .get(key)
+                .filter(|v| !v.is_none_or_undefined())
+                .map(|v| (key.clone(), v.clone()))
+        })
+        .collect()
+}
+
+/// Returns a copy of the value `v` whose secret values are replaced by `replace`. The
+/// secret values are the schema attributes with the `@secret` decorator and the values
+/// selected by the secret paths option.
+fn replace_secret_values(
+    ctx: &Context,
+    v: &ValueRef,
+    replace: &mut dyn FnMut(&ValueRef) -> ValueRef,
+) -> ValueRef {
+    let value = v.deep_copy();
+    for path in &ctx.plan_opts.secret_paths {
+        let (parent, key) = match path.rsplit_once('.') {
+            Some((parent, key)) => (value.get_by_path(parent), key),
+            None => (Some(value.clone()), path.as_str()),
+        };
+        if let Some(mut parent) = parent.filter(|v| v.is_config()) {
+            if let Some(secret) = parent.get_by_key(key).filter(|v| !v.is_none_or_undefined()) {
+                parent.dict_update_key_value(key, replace(&secret));
+            }
+        }
+    }
+    walk_value_mut(&value, &mut |v| {
+        let secrets = match &*v.rc.borrow() {
+            Value::dict_value(dict) => secret_attr_values(dict),
+            Value::schema_value(schema) => secret_attr_values(&schema.config),
+            _ => vec![],
+        };
+        let mut v = v.clone();
+        for (key, secret) in secrets {
+            v.dict_update_key_value(&key, replace(&secret));
+        }
+    });
+    value
+}
+
+/// Returns the index of the externalized secret value if the value is a placeholder.
+fn secret_ref_index(v: &ValueRef) -> Option<usize> {
+    match &*v.rc.borrow() {
+        Value::str_value(s) => s.strip_prefix(SECRET_REF_PREFIX)?.parse().ok(),
+        _ => None,
+    }
+}
+
+/// Returns the JSONPath segment of the config key e.g., `.name` and `['app.kubernetes.io/name']`.
+fn json_path_segment(key: &str) -> String {
+    if !key.is_empty()
+        && key
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+    {
+        format!(".{key}")
+    } else {
+        format!("['{}']", key.replace('\\', "\\\\").replace('\'', "\\'"))
+    }
+}
+
+/// Move the externalized secret values in the documents to the secrets document, whose
+/// keys are the JSONPath of the secret values e.g., `$.app.password`, and the paths
+/// start with the document index e.g., `$[1].app.password` for multiple documents.
+fn externalize_secret_values(
+    ctx: &Context,
+    documents: &[ValueRef],
+    secrets: &[ValueRef],
+) -> ValueRef {
+    let mut secrets_document = ValueRef::dict(None);
+    for (i, document) in documents.iter().enumerate() {
+        let path = if documents.len() > 1 {
+            format!("$[{i}]")
+        } else {
+            "$".to_string()
+        };
+        move_secret_values(ctx, document, &path, secrets, &mut secrets_document);
+    }
+    secrets_document
+}
+
+fn move_secret_values(
+    ctx: &Context,
+    v: &ValueRef,
+    path: &str,
+    secrets: &[ValueRef],
+    secrets_document: &mut ValueRef,
+) {
+    if v.is_config() {
+        let entries: Vec<(String, ValueRef)> = v
+            .as_dict_ref()
+            .values
+            .iter()
+            .map(|(k, v)| (k.clone(), v.clone()))
+            .collect();
+        for (key, value) in entries {
+            let path = format!("{path}{}", json_path_segment(&key));
+            match secret_ref_index(&value) {
+                Some(index) => {
+                    v.clone().dict_remove(&key);
+                    let secret = plan_secret_value(ctx, &secrets[index]);
+                    secrets_document.dict_update_key_value(&path, secret);
+                }
+                None => move_secret_values(ctx, &value, &path, secrets, secrets_document),
+            }
+        }
+    } else if v.is_list() {
+        for (i, value) in v.as_list_ref().values.iter().enumerate() {
+            let path = format!("{path}[{i}]");
+            move_secret_values(ctx, value, &path, secrets, secrets_document);
+        }
+    }
+}
+
+/// Plan the secret value in the secrets document with the plan options.
+fn plan_secret_value(ctx: &Context, v: &ValueRef) -> ValueRef {
+    let v = match ctx.plan_opts.decimal_places {
+        Some(places) => round_float_values(v, places),
+        None => v.clone(),
+    };
+    let list_dict = ValueRef::dict(Some(&[(LIST_DICT_TEMP_KEY, &v)]));
+    filter_results(ctx, &list_dict)
+        .first()
+        .and_then(|r| r.get_by_key(LIST_DICT_TEMP_KEY))
+        .unwrap_or(v)
+}
+
+/// Write the planned documents to the writer. The stream documents are separated
+/// by the YAML plan separator or the JSON stream separator.
+fn write_documents(
+    ctx: &Context,
+    documents: &[ValueRef],
+    is_stream: bool,
+    format: PlanFormat,
+    w: &mut dyn Write,
+) -> std::io::Result<()> {
+    // Encoding options
+    let sort_keys = ctx.plan_opts.sort_keys || ctx.plan_opts.key_order == KeyOrder::Alphabetical;
+    let json_opts = JsonEncodeOptions {
+        sort_keys,
+        ..Default::default()
+    };
+    let yaml_opts = YamlEncodeOptions {
+        sort_keys,
+        ..Default::default()
+    };
+    let toml_opts = TomlEncodeOptions {
+        sort_keys,
+        ..Default::default()
+    };
+    let hcl_opts = HclEncodeOptions {
+        sort_keys,
+        ..Default::default()
+    };
+    let sep = match format {
+        PlanFormat::Json => JSON_STREAM_SEP.to_string(),
+        PlanFormat::Yaml => format!("\n{}\n", ctx.plan_opts.sep.as_deref().unwrap_or("---")),
+        PlanFormat::Toml | PlanFormat::Hcl => {
+            if documents.len() > 1 {
+                panic!(
+                    "the {} output format does not support multiple documents, got {} documents",
+                    format.as_str(),
+                    documents.len()
+                );
+            }
+            String::new()
+        }
+    };
+    for (i, document) in documents.iter().enumerate() {
+        if i > 0 {
+            w.write_all(sep.as_bytes())?;
+        }
+        // Remove the provenance meta attributes from the copy of the document, which
+        // are replaced by the YAML comment placeholders in the comment mode.
+        let mut provenances = IndexMap::default();
+        let comment =
+            format == PlanFormat::Yaml && ctx.plan_opts.provenance == ProvenanceMode::Comment;
+        let document = if ctx.plan_opts.provenance != ProvenanceMode::None {
+            let document = document.deep_copy();
+            take_provenances(&document, "$", comment, &mut provenances);
+            document
+        } else {
+            document.clone()
+        };
+        match format {
+            PlanFormat::Json => {
+                w.write_all(document.to_json_string_with_options(&json_opts).as_bytes())?
+            }
+            PlanFormat::Yaml => {
+                let mut yaml = document.to_yaml_string_with_options(&yaml_opts);
+                if comment {
+                    yaml = replace_provenance_placeholders(&yaml, &provenances);
+                }
+                // The trailing newline of each stream document is trimmed.
+                let yaml = if is_stream {
+                    yaml.strip_suffix('\n').unwrap_or(&yaml)
+                } else {
+                    &yaml
+                };
+                w.write_all(yaml.as_bytes())?
+            }
+            PlanFormat::Toml | PlanFormat::Hcl => {
+                let output = if format == PlanFormat::Toml {
+                    document.to_toml_string_with_options(&toml_opts)
+                } else {
+                    document.to_hcl_string_with_options(&hcl_opts)
+                };
+                // The trailing newline of the stream document is trimmed as YAML.
+                let output = if is_stream {
+                    output.strip_suffix('\n').unwrap_or(&output)
+                } else {
+                    &output
+                };
+                w.write_all(output.as_bytes())?
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Render the filename template of the planned document with the placeholders `{key}`,
+/// `{index}`, `{schema}`, `{ext}` and the attribute paths of the document e.g.,
+/// `{metadata.name}`.
+fn render_filename(
+    template: &str,
+    index: usize,
+    key: &str,
+    document: &ValueRef,
+    format: PlanFormat,
+) -> String {
+    let mut name = String::new();
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => panic!("unclosed '{{' in the filename template '{template}'"),
+        };
+        match &rest[start + 1..end] {
+            "key" => name.push_str(key),
+            "index" => name.push_str(&index.to_string()),
+            "schema" => name.push_str(&value_type_path(document, false)),
+            "ext" => name.push_str(format.as_str()),
+            path => match document.get_by_path(path) {
+                Some(v) if v.is_str() => name.push_str(&v.as_str()),
+                Some(v) if v.is_scalar() && !v.is_none() => name.push_str(&v.to_json_string()),
+                _ => panic!(
+                    "the attribute '{path}' in the filename template '{template}' is not found in the document '{key}'"
+                ),
+            },
+        }
+        rest = &rest[end + 1..];
+    }
+    name.push_str(rest);
+    name
+}
+
+/// The output format of the planned documents, and the discriminant is passed to the
+/// runtime by [crate::FFIRunOptions].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PlanFormat {
+    #[default]
+    Yaml = 0,
+    Json = 1,
+    /// The TOML document, which requires a single dict document.
+    Toml = 2,
+    /// The HCL attributes of the top-level keys, which requires a single dict document.
+    Hcl = 3,
+}
+
+impl PlanFormat {
+    /// Returns the output format of the discriminant, and the unknown discriminant
+    /// denotes YAML.
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => PlanFormat::Json,
+            2 => PlanFormat::Toml,
+            3 => PlanFormat::Hcl,
+            _ => PlanFormat::Yaml,
+        }
+    }
+
+    /// Returns the name of the output format.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlanFormat::Json => "json",
+            PlanFormat::Yaml => "yaml",
+            PlanFormat::Toml => "toml",
+            PlanFormat::Hcl => "hcl",
+        }
+    }
+}
+
+impl FromStr for PlanFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "json" => Ok(PlanFormat::Json),
+            "yaml" => Ok(PlanFormat::Yaml),
+            "toml" => Ok(PlanFormat::Toml),
+            "hcl" => Ok(PlanFormat::Hcl),
+            _ => anyhow::bail!(
+                "invalid output format '{}', expected one of 'json', 'yaml', 'toml' and 'hcl'",
+                s
+            ),
+        }
+    }
+}
+
+/// The key order strategy of the planned configs, which is applied to all the output
+/// formats, and the discriminant is passed to the runtime by [crate::FFIRunOptions].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum KeyOrder {
+    /// The order in which the keys are declared or first assigned in the program.
+    #[default]
+    Declaration = 0,
+    /// The alphabetical order of the keys, which is the same as the `sort_keys` option.
+    Alphabetical = 1,
+    /// The declaration order of the schema attributes for the schema instances, which
+    /// does not depend on the order of the keys in the schema config expressions.
+    Schema = 2,
+}
+
+impl KeyOrder {
+    /// Returns the key order of the discriminant, and the unknown discriminant denotes
+    /// the declaration order.
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => KeyOrder::Alphabetical,
+            2 => KeyOrder::Schema,
+            _ => KeyOrder::Declaration,
+        }
+    }
+
+    /// Returns the name of the key order.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            KeyOrder::Declaration => "declaration",
+            KeyOrder::Alphabetical => "alphabetical",
+            KeyOrder::Schema => "schema",
+        }
+    }
+}
+
+impl FromStr for KeyOrder {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "declaration" => Ok(KeyOrder::Declaration),
+            "alphabetical" => Ok(KeyOrder::Alphabetical),
+            "schema" => Ok(KeyOrder::Schema),
+            _ => anyhow::bail!(
+                "invalid key order '{}', expected one of 'declaration', 'alphabetical' and 'schema'",
+                s
+            ),
+        }
+    }
+}
+
+/// The provenance mode of the planned schema instances, and the discriminant is passed
+/// to the runtime by [crate::FFIRunOptions].
+#[derive(PartialEq, Eq, Clone, Copy, Debug, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ProvenanceMode {
+    #[default]
+    None = 0,
+    /// The YAML comment before the first key of each planned schema instance, and the
+    /// other output formats are not annotated.
+    Comment = 1,
+    /// The sidecar map from the JSONPath of each planned schema instance to its
+    /// provenance, see [ValueRef::plan_provenance].
+    Sidecar = 2,
+}
+
+impl ProvenanceMode {
+    /// Returns the provenance mode of the discriminant, and the unknown discriminant
+    /// denotes no provenance.
+    pub fn from_i32(value: i32) -> Self {
+        match value {
+            1 => ProvenanceMode::Comment,
+            2 => ProvenanceMode::Sidecar,
+            _ => ProvenanceMode::None,
+        }
+    }
+
+    /// Returns the name of the provenance mode.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ProvenanceMode::None => "none",
+            ProvenanceMode::Comment => "comment",
+            ProvenanceMode::Sidecar => "sidecar",
+        }
+    }
+}
+
+impl FromStr for ProvenanceMode {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "none" => Ok(ProvenanceMode::None),
+            "comment" => Ok(ProvenanceMode::Comment),
+            "sidecar" => Ok(ProvenanceMode::Sidecar),
+            _ => anyhow::bail!(
+                "invalid provenance mode '{}', expected one of 'none', 'comment' and 'sidecar'",
+                s
+            ),
+        }
+    }
+}
+
 impl ValueRef {
-    /// Plan the value to JSON and YAML strings.
+    /// Plan the value to the JSON string and the string in the output format of the
+    /// plan options, which is YAML by default.
     pub fn plan(&self, ctx: &Context) -> (String, String) {
-        // Encoding options
-        let json_opts = JsonEncodeOptions {
-            sort_keys: ctx.plan_opts.sort_keys,
-            ..Default::default()
+        let (documents, is_stream) = self.plan_documents(ctx);
+        let plan = |format| {
+            let mut buf = vec![];
+            write_documents(ctx, &documents, is_stream, format, &mut buf).unwrap();
+            String::from_utf8(buf).unwrap()
         };
-        let yaml_opts = YamlEncodeOptions {
-            sort_keys: ctx.plan_opts.sort_keys,
-            ..Default::default()
+        (plan(PlanFormat::Json), plan(ctx.plan_opts.output_format))
+    }
+
+    /// Plan the value and write the JSON or YAML documents to the writer one by one,
+    /// thus the whole plan result string is never buffered in memory. The written
+    /// output is the same as the corresponding result of [ValueRef::plan].
+    pub fn plan_to_writer(
+        &self,
+        ctx: &Context,
+        format: PlanFormat,
+        w: &mut dyn Write,
+    ) -> std::io::Result<()> {
+        let (documents, is_stream) = self.plan_documents(ctx);
+        write_documents(ctx, &documents, is_stream, format, w)
+    }
+
+    /// Plan the value and split the result into the files, in which each document of
+    /// the list value or each top-level config of the dict value is a file. Returns
+    /// the file names rendered with the filename template e.g., `{key}.{ext}` and the
+    /// file contents in the output format of the plan options.
+    pub fn plan_to_files(&self, ctx: &Context, template: &str) -> Vec<(String, String)> {
+        let (documents, _) = self.plan_documents(ctx);
+        let mut entries: Vec<(String, ValueRef)> = vec![];
+        let mut documents = documents.into_iter();
+        if self.is_config() {
+            if let Some(document) = documents.next() {
+                for (key, value) in &document.as_dict_ref().values {
+                    if value.is_list_or_config() {
+                        entries.push((key.clone(), value.clone()));
+                    }
+                }
+            }
+        }
+        // The standalone documents of the config are numbered after the top-level configs.
+        for document in documents {
+            entries.push((entries.len().to_string(), document));
+        }
+        let format = ctx.plan_opts.output_format;
+        let mut files: Vec<(String, String)> = vec![];
+        for (index, (key, document)) in entries.iter().enumerate() {
+            let name = render_filename(template, index, key, document, format);
+            if files.iter().any(|(n, _)| n == &name) {
+                panic!(
+                    "duplicate file name '{name}' rendered by the filename template '{template}'"
+                );
+            }
+            let mut buf = vec![];
+            write_documents(ctx, std::slice::from_ref(document), true, format, &mut buf).unwrap();
+            files.push((name, String::from_utf8(buf).unwrap()));
+        }
+        files
+    }
+
+    /// Plan the value and returns the provenances of the planned schema instances keyed
+    /// by the JSONPath e.g., `$.app`, and the paths start with the document index e.g.,
+    /// `$[1].app` for multiple documents. The provenances are empty when the provenance
+    /// mode of the plan options is none.
+    pub fn plan_provenance(&self, ctx: &Context) -> IndexMap<String, Provenance> {
+        let (documents, _) = self.plan_documents(ctx);
+        let mut provenances = IndexMap::default();
+        for (i, document) in documents.iter().enumerate() {
+            let path = if documents.len() > 1 {
+                format!("$[{i}]")
+            } else {
+                "$".to_string()
+            };
+            take_provenances(document, &path, false, &mut provenances);
+        }
+        provenances
+    }
+
+    /// Plan the value and returns the provenance sidecar JSON string, which is the JSON
+    /// object of [ValueRef::plan_provenance].
+    pub fn plan_provenance_json(&self, ctx: &Context) -> String {
+        let provenances: serde_json::Map<String, serde_json::Value> = self
+            .plan_provenance(ctx)
+            .into_iter()
+            .map(|(path, provenance)| (path, serde_json::to_value(provenance).unwrap()))
+            .collect();
+        serde_json::to_string(&provenances).unwrap()
+    }
+
+    /// Returns the documents to plan with the query paths and decimal places applied,
+    /// and whether the documents are planned as a stream.
+    fn plan_documents(&self, ctx: &Context) -> (Vec<ValueRef>, bool) {
+        // Mask or externalize the secret values
+        let mut secrets = vec![];
+        let value = if ctx.plan_opts.show_secrets
+            || (ctx.plan_opts.secret_paths.is_empty() && !has_secret_attrs(self))
+        {
+            self.clone()
+        } else if ctx.plan_opts.externalize_secrets {
+            replace_secret_values(ctx, self, &mut |secret| {
+                secrets.push(secret.clone());
+                ValueRef::str(&format!("{SECRET_REF_PREFIX}{}", secrets.len() - 1))
+            })
+        } else {
+            replace_secret_values(ctx, self, &mut |_| ValueRef::str(SECRET_MASK))
         };
         // Filter values with query paths
         let value = if ctx.plan_opts.query_paths.is_empty() {
-            self.clone()
+            value
         } else {
-            self.filter_by_path(&ctx.plan_opts.query_paths)
+            value
+                .filter_by_path(&ctx.plan_opts.query_paths)
                 .unwrap_or_else(|e| panic!("{e}"))
         };
+        // Round float values with the decimal places
+        let value = match ctx.plan_opts.decimal_places {
+            Some(places) => round_float_values(&value, places),
+            None => value,
+        };
         if value.is_list_or_config() {
-            let results = filter_results(ctx, &value);
-            let sep = ctx
-                .plan_opts
-                .sep
-                .clone()
-                .unwrap_or_else(|| "---".to_string());
-            // Plan YAML result
-            let yaml_result = results
-                .iter()
-                .map(|r| {
-                    r.to_yaml_string_with_options(&yaml_opts)
-                        .strip_suffix('\n')
-                        .unwrap()
-                        .to_string()
-                })
-                .collect::<Vec<String>>()
-                .join(&format!("\n{}\n", sep));
-            // Plan JSON result
-            let json_result = results
-                .iter()
-                .map(|r| r.to_json_string_with_options(&json_opts))
-                .collect::<Vec<String>>()
-                .join(JSON_STREAM_SEP);
-            (json_result, yaml_result)
+            let mut documents = filter_results(ctx, &value);
+            if !secrets.is_empty() {
+                let secrets_document = externalize_secret_values(ctx, &documents, &secrets);
+                if !secrets_document.is_empty() {
+                    documents.push(secrets_document);
+                }
+            }
+            (documents, true)
+        } else if secret_ref_index(&value).is_some() {
+            // The secret value selected by the query paths is masked.
+            (vec![ValueRef::str(SECRET_MASK)], false)
         } else {
-            (
-                value.to_json_string_with_options(&json_opts),
-                value.to_yaml_string_with_options(&yaml_opts),
-            )
+            (vec![value], false)
         }
     }
 
@@ -287,7 +1004,14 @@ impl ValueRef {
 
 #[cfg(test)]
 mod test_value_plan {
-    use crate::{schema_runtime_type, val_plan::PlanOptions, Context, ValueRef, MAIN_PKG_PATH};
+    use crate::{
+        schema_config_meta, schema_runtime_type,
+        val_plan::{
+            KeyOrder, PlanFormat, PlanOptions, Provenance, ProvenanceMode,
+            DEFAULT_FILENAME_TEMPLATE,
+        },
+        Context, ValueRef, MAIN_PKG_PATH,
+    };
 
     use super::filter_results;
 
@@ -443,4 +1167,272 @@ mod test_value_plan {
         assert_eq!(json_string, "{}");
         assert_eq!(yaml_string, "{}");
     }
+
+    #[test]
+    fn test_value_plan_with_decimal_places() {
+        let mut ctx = Context::new();
+        let mut config = ValueRef::dict(None);
+        config.dict_update_key_value("a", ValueRef::float(0.1 + 0.2));
+        config.dict_update_key_value("b", ValueRef::list_float(&[2.675, 1.005]));
+        config.dict_update_key_value("c", ValueRef::int(1));
+        ctx.plan_opts.decimal_places = Some(2);
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(json_string, "{\"a\": 0.3, \"b\": [2.68, 1.0], \"c\": 1}");
+        assert_eq!(yaml_string, "a: 0.3\nb:\n- 2.68\n- 1.0\nc: 1");
+        // The planned value itself is not modified.
+        assert_eq!(config.get_by_key("a").unwrap().as_float(), 0.1 + 0.2);
+    }
+
+    #[test]
+    fn test_value_plan_to_writer() {
+        let mut ctx = Context::new();
+        let dict1 = ValueRef::dict_int(&[("k1", 1)]);
+        let dict2 = ValueRef::dict_int(&[("k2", 2)]);
+        let list_data = ValueRef::list(Some(&[&dict1, &dict2]));
+        for sep in [None, Some("...".to_string())] {
+            ctx.plan_opts.sep = sep;
+            for value in [&list_data, &dict1, &ValueRef::int(1)] {
+                let (json_string, yaml_string) = value.plan(&ctx);
+                let mut json_buf = vec![];
+                value
+                    .plan_to_writer(&ctx, PlanFormat::Json, &mut json_buf)
+                    .unwrap();
+                assert_eq!(String::from_utf8(json_buf).unwrap(), json_string);
+                let mut yaml_buf = vec![];
+                value
+                    .plan_to_writer(&ctx, PlanFormat::Yaml, &mut yaml_buf)
+                    .unwrap();
+                assert_eq!(String::from_utf8(yaml_buf).unwrap(), yaml_string);
+            }
+        }
+        let mut yaml_buf = vec![];
+        list_data
+            .plan_to_writer(&ctx, PlanFormat::Yaml, &mut yaml_buf)
+            .unwrap();
+        assert_eq!(String::from_utf8(yaml_buf).unwrap(), "k1: 1\n...\nk2: 2");
+    }
+
+    #[test]
+    fn test_value_plan_with_output_format() {
+        let mut ctx = Context::new();
+        let value = ValueRef::dict(Some(&[
+            ("name", &ValueRef::str("app")),
+            ("server", &ValueRef::dict_int(&[("port", 8080)])),
+        ]));
+        let cases = [
+            (PlanFormat::Toml, "name = \"app\"\n\n[server]\nport = 8080"),
+            (
+                PlanFormat::Hcl,
+                "name = \"app\"\nserver = {\n  port = 8080\n}",
+            ),
+            (
+                PlanFormat::Json,
+                "{\"name\": \"app\", \"server\": {\"port\": 8080}}",
+            ),
+        ];
+        for (format, expected) in cases {
+            ctx.plan_opts.output_format = format;
+            let (json_string, result) = value.plan(&ctx);
+            assert_eq!(result, expected);
+            assert_eq!(json_string, cases[2].1);
+            let mut buf = vec![];
+            value.plan_to_writer(&ctx, format, &mut buf).unwrap();
+            assert_eq!(String::from_utf8(buf).unwrap(), expected);
+        }
+        assert_eq!("hcl".parse::<PlanFormat>().unwrap(), PlanFormat::Hcl);
+        assert!("xml".parse::<PlanFormat>().is_err());
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "the toml output format does not support multiple documents, got 2 documents"
+    )]
+    fn test_value_plan_with_output_format_fail() {
+        let mut ctx = Context::new();
+        ctx.plan_opts.output_format = PlanFormat::Toml;
+        let list_data = ValueRef::list(Some(&[
+            &ValueRef::dict_int(&[("k1", 1)]),
+            &ValueRef::dict_int(&[("k2", 2)]),
+        ]));
+        list_data.plan(&ctx);
+    }
+
+    #[test]
+    fn test_value_plan_with_key_order() {
+        let mut ctx = Context::new();
+        let mut schema = get_test_schema_value();
+        schema.update_attr_map("name", "str");
+        schema.update_attr_map("port", "int");
+        schema.dict_update_key_value("port", ValueRef::int(80));
+        schema.dict_update_key_value("name", ValueRef::str("app"));
+        let value = ValueRef::dict(Some(&[("b", &ValueRef::int(1)), ("app", &schema)]));
+        let cases = [
+            (KeyOrder::Declaration, "b: 1\napp:\n  port: 80\n  name: app"),
+            (
+                KeyOrder::Alphabetical,
+                "app:\n  name: app\n  port: 80\nb: 1",
+            ),
+            (KeyOrder::Schema, "b: 1\napp:\n  name: app\n  port: 80"),
+        ];
+        for (key_order, expected) in cases {
+            ctx.plan_opts.key_order = key_order;
+            assert_eq!(value.plan(&ctx).1, expected);
+        }
+        assert_eq!(
+            value.plan(&ctx).0,
+            "{\"b\": 1, \"app\": {\"name\": \"app\", \"port\": 80}}"
+        );
+        assert_eq!("schema".parse::<KeyOrder>().unwrap(), KeyOrder::Schema);
+        assert!("random".parse::<KeyOrder>().is_err());
+    }
+
+    #[test]
+    fn test_value_plan_with_provenance() {
+        let mut ctx = Context::new();
+        let schema = ValueRef::dict(Some(&[("name", &ValueRef::str("app"))])).dict_to_schema(
+            TEST_SCHEMA_NAME,
+            MAIN_PKG_PATH,
+            &[],
+            &schema_config_meta("main.k", 3, 6),
+            &ValueRef::dict(None),
+            None,
+            None,
+        );
+        let value = ValueRef::dict(Some(&[
+            ("app", &schema),
+            ("apps", &ValueRef::list(Some(&[&schema]))),
+        ]));
+        let json = "{\"app\": {\"name\": \"app\"}, \"apps\": [{\"name\": \"app\"}]}";
+        ctx.plan_opts.provenance = ProvenanceMode::Comment;
+        assert_eq!(
+            value.plan(&ctx),
+            (
+                json.to_string(),
+                "app:\n  # Data (main.k:3:6)\n  name: app\napps:\n- # Data (main.k:3:6)\n  name: app"
+                    .to_string()
+            )
+        );
+        ctx.plan_opts.provenance = ProvenanceMode::Sidecar;
+        assert_eq!(
+            value.plan(&ctx),
+            (
+                json.to_string(),
+                "app:\n  name: app\napps:\n- name: app".to_string()
+            )
+        );
+        let provenances = value.plan_provenance(&ctx);
+        let provenance = Provenance {
+            schema: "Data".to_string(),
+            filename: "main.k".to_string(),
+            line: 3,
+            column: 6,
+        };
+        assert_eq!(
+            provenances.keys().collect::<Vec<&String>>(),
+            vec!["$.app", "$.apps[0]"]
+        );
+        assert_eq!(provenances["$.apps[0]"], provenance);
+        ctx.plan_opts.provenance = ProvenanceMode::None;
+        assert!(value.plan_provenance(&ctx).is_empty());
+    }
+
+    #[test]
+    fn test_value_plan_to_files() {
+        let ctx = Context::new();
+        let mut schema = get_test_schema_value();
+        schema.dict_update_key_value("name", ValueRef::str("app"));
+        let config = ValueRef::dict(Some(&[
+            ("app", &schema),
+            (
+                "labels",
+                &ValueRef::dict(Some(&[("env", &ValueRef::str("prod"))])),
+            ),
+            ("replicas", &ValueRef::int(2)),
+        ]));
+        let files = |files: &[(&str, &str)]| -> Vec<(String, String)> {
+            files
+                .iter()
+                .map(|(name, content)| (name.to_string(), content.to_string()))
+                .collect()
+        };
+        assert_eq!(
+            config.plan_to_files(&ctx, DEFAULT_FILENAME_TEMPLATE),
+            files(&[("app.yaml", "name: app"), ("labels.yaml", "env: prod")])
+        );
+        assert_eq!(
+            config.plan_to_files(&ctx, "{index}-{schema}.{ext}"),
+            files(&[("0-Data.yaml", "name: app"), ("1-dict.yaml", "env: prod")])
+        );
+        let list_data = ValueRef::list(Some(&[
+            &ValueRef::dict_int(&[("k1", 1)]),
+            &ValueRef::dict_int(&[("k1", 2)]),
+        ]));
+        assert_eq!(
+            list_data.plan_to_files(&ctx, "data/{index}-{k1}.yaml"),
+            files(&[("data/0-1.yaml", "k1: 1"), ("data/1-2.yaml", "k1: 2")])
+        );
+    }
+
+    #[test]
+    #[should_panic(
+        expected = "duplicate file name 'out.yaml' rendered by the filename template 'out.yaml'"
+    )]
+    fn test_value_plan_to_files_fail() {
+        let list_data = ValueRef::list(Some(&[
+            &ValueRef::dict_int(&[("k1", 1)]),
+            &ValueRef::dict_int(&[("k2", 2)]),
+        ]));
+        list_data.plan_to_files(&Context::new(), "out.yaml");
+    }
+
+    #[test]
+    fn test_value_plan_with_secrets() {
+        let mut ctx = Context::new();
+        let mut schema = get_test_schema_value();
+        schema.dict_update_key_value("user", ValueRef::str("admin"));
+        schema.dict_update_key_value("password", ValueRef::str("123456"));
+        schema.add_secret_attr("password");
+        let mut config = ValueRef::dict(None);
+        config.dict_update_key_value("data", schema);
+        config.dict_update_key_value("token", ValueRef::str("abc"));
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            json_string,
+            "{\"data\": {\"user\": \"admin\", \"password\": \"******\"}, \"token\": \"abc\"}"
+        );
+        assert_eq!(
+            yaml_string,
+            "data:\n  user: admin\n  password: '******'\ntoken: abc"
+        );
+
+        ctx.plan_opts.secret_paths = vec!["token".to_string()];
+        let (_, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            yaml_string,
+            "data:\n  user: admin\n  password: '******'\ntoken: '******'"
+        );
+
+        ctx.plan_opts.externalize_secrets = true;
+        let (json_string, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            json_string,
+            "{\"data\": {\"user\": \"admin\"}}\n{\"$.data.password\": \"123456\", \"$.token\": \"abc\"}"
+        );
+        assert_eq!(
+            yaml_string,
+            "data:\n  user: admin\n---\n$.data.password: '123456'\n$.token: abc"
+        );
+
+        ctx.plan_opts.show_secrets = true;
+        let (_, yaml_string) = config.plan(&ctx);
+        assert_eq!(
+            yaml_string,
+            "data:\n  user: admin\n  password: '123456'\ntoken: abc"
+        );
+        // The planned value itself is not modified.
+        assert_eq!(
+            config.get_by_path("data.password").unwrap().as_str(),
+            "123456"
+        );
+    }
 }
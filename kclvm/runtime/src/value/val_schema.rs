@@ -271,6 +271,20 @@ impl ValueRef {
         }
     }
 
+    /// Mark the attribute as a secret, whose value is masked or externalized in the
+    /// plan process.
+    pub fn add_secret_attr(&mut self, name: &str) {
+        match &mut *self.rc.borrow_mut() {
+            Value::dict_value(dict) => {
+                dict.secret_attrs.insert(name.to_string());
+            }
+            Value::schema_value(schema) => {
+                schema.config.secret_attrs.insert(name.to_string());
+            }
+            _ => panic!("invalid object '{}' in add_secret_attr", self.type_str()),
+        }
+    }
+
     pub fn attr_map_get(&self, name: &str) -> Option<String> {
         match &*self.rc.borrow() {
             Value::dict_value(dict) => dict.attr_map.get(name).cloned(),
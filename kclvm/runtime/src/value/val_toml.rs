@@ -0,0 +1,181 @@
+//! Copyright The KCL Authors. All rights reserved.
+
+extern crate toml;
+
+use crate::*;
+
+use serde::{Deserialize, Serialize};
+
+/// TOML encode options.
+/// - sort_keys: Sort the encode result by keys (defaults to false).
+/// - ignore_private: Whether to ignore the attribute whose name starts with
+///     a character `_` (defaults to false).
+///
+/// Note the `None` values are always ignored because TOML has no null value.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct TomlEncodeOptions {
+    pub sort_keys: bool,
+    pub ignore_private: bool,
+}
+
+impl ValueRef {
+    /// Decode a TOML document string to a dict ValueRef, and the TOML datetimes are
+    /// decoded to the RFC 3339 strings.
+    /// Returns [toml::de::Error] when decoding fails.
+    pub fn from_toml(ctx: &mut Context, s: &str) -> Result<Self, toml::de::Error> {
+        let table: toml::value::Table = toml::from_str(s)?;
+        Ok(Self::parse_toml(ctx, &toml::Value::Table(table)))
+    }
+
+    fn parse_toml(ctx: &mut Context, toml: &toml::Value) -> Self {
+        match toml {
+            toml::Value::Table(values) => {
+                let mut dict = Self::dict(None);
+                for (name, value) in values {
+                    let v = Self::parse_toml(ctx, value);
+                    dict.dict_insert(
+                        ctx,
+                        name.as_ref(),
+                        &v,
+                        ConfigEntryOperationKind::Union,
+                        None,
+                    );
+                }
+                dict
+            }
+            toml::Value::Array(values) => {
+                let mut list = Self::list(None);
+                for value in values {
+                    list.list_append(&Self::parse_toml(ctx, value));
+                }
+                list
+            }
+            toml::Value::String(val) => Self::str(val),
+            toml::Value::Integer(val) => Self::int(*val),
+            toml::Value::Float(val) => Self::float(*val),
+            toml::Value::Boolean(val) => Self::bool(*val),
+            toml::Value::Datetime(val) => Self::str(&val.to_string()),
+        }
+    }
+
+    /// Encode the dict or schema value to a TOML document string.
+    pub fn to_toml_string_with_options(&self, opts: &TomlEncodeOptions) -> String {
+        // convert Value to json in order to reuse
+        // "crate::val_json::JsonValue" to customize the serialized results.
+        let json_opts = JsonEncodeOptions {
+            sort_keys: opts.sort_keys,
+            indent: 0,
+            ignore_private: opts.ignore_private,
+            ignore_none: true,
+        };
+        match build_toml(&self.build_json(&json_opts)) {
+            Some(toml @ toml::Value::Table(_)) => match toml::to_string(&toml) {
+                Ok(s) => s,
+                Err(err) => panic!("{}", err),
+            },
+            _ => panic!(
+                "only the dict and schema values can be encoded to TOML, got '{}'",
+                self.type_str()
+            ),
+        }
+    }
+
+    pub fn to_toml_string(&self) -> String {
+        self.to_toml_string_with_options(&Default::default())
+    }
+}
+
+/// Convert the JSON value to the TOML value, and returns `None` for the null value.
+/// The big ints out of the TOML int range are encoded to the strings to keep them
+/// lossless.
+fn build_toml(json: &JsonValue) -> Option<toml::Value> {
+    Some(match json {
+        JsonValue::Null => return None,
+        JsonValue::Bool(v) => toml::Value::Boolean(*v),
+        JsonValue::Number(v) => match v.as_i64() {
+            Some(v) => toml::Value::Integer(v),
+            None => toml::Value::Float(v.as_f64().unwrap_or_default()),
+        },
+        JsonValue::BigInt(v) => toml::Value::String(v.to_string()),
+        JsonValue::String(v) => toml::Value::String(v.clone()),
+        JsonValue::Array(values) => toml::Value::Array(
+            values
+                .iter()
+                .map(|v| build_toml(v).expect("TOML does not support None in lists"))
+                .collect(),
+        ),
+        JsonValue::Object(values) => toml::Value::Table(
+            values
+                .iter()
+                .filter_map(|(k, v)| build_toml(v).map(|v| (k.clone(), v)))
+                .collect(),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod test_value_toml {
+    use crate::*;
+
+    #[test]
+    fn test_value_from_toml() {
+        let mut ctx = Context::new();
+        let cases = [
+            ("a = 1\n", ValueRef::dict(Some(&[("a", &ValueRef::int(1))]))),
+            (
+                "b = [1, 2, 3]\na = \"s\"\n",
+                ValueRef::dict(Some(&[
+                    ("b", &ValueRef::list_int(&[1, 2, 3])),
+                    ("a", &ValueRef::str("s")),
+                ])),
+            ),
+            (
+                "[server]\nport = 8080\ndate = 1979-05-27T07:32:00Z\n",
+                ValueRef::dict(Some(&[(
+                    "server",
+                    &ValueRef::dict(Some(&[
+                        ("port", &ValueRef::int(8080)),
+                        ("date", &ValueRef::str("1979-05-27T07:32:00Z")),
+                    ])),
+                )])),
+            ),
+        ];
+        for (toml_str, expected) in cases {
+            let result = ValueRef::from_toml(&mut ctx, toml_str);
+            assert_eq!(result.unwrap(), expected);
+        }
+        assert!(ValueRef::from_toml(&mut ctx, "a = ").is_err());
+    }
+
+    #[test]
+    fn test_value_to_toml_string() {
+        let value = ValueRef::dict(Some(&[
+            (
+                "server",
+                &ValueRef::dict(Some(&[("port", &ValueRef::int(8080))])),
+            ),
+            ("name", &ValueRef::str("app")),
+            ("_private", &ValueRef::bool(true)),
+            ("none", &ValueRef::none()),
+            ("ratio", &ValueRef::float(0.5)),
+        ]));
+        assert_eq!(
+            value.to_toml_string(),
+            "name = \"app\"\n_private = true\nratio = 0.5\n\n[server]\nport = 8080\n"
+        );
+        let opts = TomlEncodeOptions {
+            sort_keys: true,
+            ignore_private: true,
+        };
+        assert_eq!(
+            value.to_toml_string_with_options(&opts),
+            "name = \"app\"\nratio = 0.5\n\n[server]\nport = 8080\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "only the dict and schema values can be encoded to TOML, got 'int'")]
+    fn test_value_to_toml_string_fail() {
+        ValueRef::int(1).to_toml_string();
+    }
+}
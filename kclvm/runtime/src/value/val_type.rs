@@ -50,7 +50,7 @@ impl ValueRef {
             Value::undefined => String::from(KCL_TYPE_UNDEFINED),
             Value::none => String::from(KCL_TYPE_NONE),
             Value::bool_value(..) => String::from(BUILTIN_TYPE_BOOL),
-            Value::int_value(..) => String::from(BUILTIN_TYPE_INT),
+            Value::int_value(..) | Value::bigint_value(..) => String::from(BUILTIN_TYPE_INT),
             Value::float_value(..) => String::from(BUILTIN_TYPE_FLOAT),
             Value::unit_value(_, raw, suffix) => {
                 format!("{KCL_TYPE_NUMBER_MULTIPLY}({raw}{suffix})")
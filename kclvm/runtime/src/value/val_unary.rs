@@ -8,6 +8,7 @@ impl ValueRef {
         match &*self.rc.borrow() {
             Value::int_value(ref a) => Self::int(*a),
             Value::float_value(ref a) => Self::float(*a),
+            Value::bigint_value(ref a) => Self::bigint(a.clone()),
             _ => panic!("bad operand type for unary +: '{}'", self.type_str()),
         }
     }
@@ -17,12 +18,16 @@ impl ValueRef {
         match &*self.rc.borrow() {
             Value::int_value(ref a) => Self::int(0 - *a),
             Value::float_value(ref a) => Self::float(0.0 - *a),
+            Value::bigint_value(ref a) => Self::bigint(-a),
             _ => panic!("bad operand type for unary -: '{}'", self.type_str()),
         }
     }
 
     // ~ x
     pub fn unary_not(&self) -> Self {
+        if let Value::bigint_value(ref a) = &*self.rc.borrow() {
+            return Self::bigint(!a);
+        }
         Self::int(!self.as_int())
     }
 
@@ -42,7 +42,74 @@ impl Default for YamlEncodeOptions {
     }
 }
 
+/// YAML decode options.
+/// - allow_duplicate_keys: Whether to allow the duplicate keys in a mapping, and the
+///     last value of the duplicate keys is kept (defaults to true).
+/// - allow_aliases: Whether to allow the aliases e.g., `*anchor`, which are expanded
+///     to the anchored values (defaults to true).
+#[derive(Debug, Serialize, Deserialize)]
+pub struct YamlDecodeOptions {
+    pub allow_duplicate_keys: bool,
+    pub allow_aliases: bool,
+}
+
+impl Default for YamlDecodeOptions {
+    fn default() -> Self {
+        Self {
+            allow_duplicate_keys: true,
+            allow_aliases: true,
+        }
+    }
+}
+
+/// Check the YAML stream string against the decode options, and returns the
+/// duplicate key or alias error found in the documents.
+fn check_yaml(s: &str, opts: &YamlDecodeOptions) -> Result<(), serde_yaml::Error> {
+    if opts.allow_aliases && opts.allow_duplicate_keys {
+        return Ok(());
+    }
+    for document in serde_yaml::Deserializer::from_str(s) {
+        if !opts.allow_aliases {
+            if let Some(location) = document.first_alias_location() {
+                return Err(serde::de::Error::custom(format!(
+                    "aliases are not allowed, found an alias at line {} column {}",
+                    location.line(),
+                    location.column()
+                )));
+            }
+        }
+        if !opts.allow_duplicate_keys {
+            // The mappings of serde_yaml::Value reject the duplicate keys.
+            serde_yaml::Value::deserialize(document)?;
+        }
+    }
+    Ok(())
+}
+
 impl ValueRef {
+    /// Decode a yaml single document string to a ValueRef with the decode options.
+    /// Returns [serde_yaml::Error] when decoding fails.
+    pub fn from_yaml_with_options(
+        ctx: &mut Context,
+        s: &str,
+        opts: &YamlDecodeOptions,
+    ) -> Result<Self, serde_yaml::Error> {
+        check_yaml(s, opts)?;
+        Self::from_yaml(ctx, s)
+    }
+
+    /// Decode yaml stream string that contains `---` to a list ValueRef with the
+    /// decode options.
+    /// Returns [serde_yaml::Error] when decoding fails.
+    pub fn list_from_yaml_stream_with_options(
+        ctx: &mut Context,
+        s: &str,
+        opts: &YamlDecodeOptions,
+    ) -> Result<Self, serde_yaml::Error> {
+        check_yaml(s, opts)?;
+        Self::list_from_yaml_stream(ctx, s)
+    }
+
     /// Decode a yaml single document string to a ValueRef.
     /// Returns [serde_yaml::Error] when decoding fails.
     pub fn from_yaml(ctx: &mut Context, s: &str) -> Result<Self, serde_yaml::Error> {
@@ -83,18 +150,16 @@ impl ValueRef {
     }
 
     pub fn to_yaml(&self) -> Vec<u8> {
-        let json = self.to_json_string();
-        let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
-        match serde_yaml::to_string(&yaml_value) {
+        let json = self.build_json(&Default::default());
+        match serde_yaml::to_string(&json) {
             Ok(s) => s.into_bytes(),
             _ => Vec::new(),
         }
     }
 
     pub fn to_yaml_string(&self) -> String {
-        let json = self.to_json_string();
-        let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
-        match serde_yaml::to_string(&yaml_value) {
+        let json = self.build_json(&Default::default());
+        match serde_yaml::to_string(&json) {
             Ok(s) => {
                 let s = s.strip_prefix("---\n").unwrap_or_else(|| s.as_ref());
                 s.to_string()
@@ -105,16 +170,16 @@ impl ValueRef {
 
     pub fn to_yaml_string_with_options(&self, opts: &YamlEncodeOptions) -> String {
         // convert Value to json in order to reuse
-        // "crate::val_json::JsonValue" to customize the serialized results
+        // "crate::val_json::JsonValue" to customize the serialized results,
+        // and it is serialized directly to keep the big ints lossless.
         let json_opts = JsonEncodeOptions {
             sort_keys: opts.sort_keys,
             indent: 0,
             ignore_private: opts.ignore_private,
             ignore_none: opts.ignore_none,
         };
-        let json = self.to_json_string_with_options(&json_opts);
-        let yaml_value: serde_yaml::Value = serde_json::from_str(json.as_ref()).unwrap();
-        match serde_yaml::to_string(&yaml_value) {
+        let json = self.build_json(&json_opts);
+        match serde_yaml::to_string(&json) {
             Ok(s) => {
                 let s = s.strip_prefix("---\n").unwrap_or_else(|| s.as_ref());
                 s.to_string()
@@ -185,6 +250,37 @@ mod test_value_yaml {
         }
     }
 
+    #[test]
+    fn test_value_from_yaml_with_options() {
+        let mut ctx = Context::new();
+        let duplicate_keys = "a: 1\na: 2\n";
+        let aliases = "a: &x 1\nb: *x\n";
+        let opts = YamlDecodeOptions::default();
+        assert_eq!(
+            ValueRef::from_yaml_with_options(&mut ctx, duplicate_keys, &opts).unwrap(),
+            ValueRef::dict(Some(&[("a", &ValueRef::int(2))]))
+        );
+        assert_eq!(
+            ValueRef::from_yaml_with_options(&mut ctx, aliases, &opts).unwrap(),
+            ValueRef::dict(Some(&[("a", &ValueRef::int(1)), ("b", &ValueRef::int(1))]))
+        );
+        let opts = YamlDecodeOptions {
+            allow_duplicate_keys: false,
+            allow_aliases: false,
+        };
+        let err = ValueRef::list_from_yaml_stream_with_options(&mut ctx, duplicate_keys, &opts)
+            .unwrap_err();
+        assert!(
+            err.to_string().contains("duplicate entry with key \"a\""),
+            "{err}"
+        );
+        let err = ValueRef::from_yaml_with_options(&mut ctx, aliases, &opts).unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "aliases are not allowed, found an alias at line 2 column 4"
+        );
+    }
+
     #[test]
     fn test_value_from_yaml_stream() {
         let mut ctx = Context::new();
@@ -50,7 +50,7 @@ pub extern "C" fn kclvm_yaml_encode_all(
     panic!("encode() missing 1 required positional argument: 'data'")
 }
 
-/// decode(value)
+/// decode(value, allow_duplicate_keys=True, allow_aliases=True)
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_yaml_decode(
@@ -63,7 +63,8 @@ pub extern "C" fn kclvm_yaml_decode(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
-        match ValueRef::from_yaml(ctx, arg0.as_str().as_ref()) {
+        let opts = args_to_decode_opts(args, kwargs, 1);
+        match ValueRef::from_yaml_with_options(ctx, arg0.as_str().as_ref(), &opts) {
             Ok(x) => return x.into_raw(ctx),
             Err(err) => panic!("{}", err),
         }
@@ -71,7 +72,7 @@ pub extern "C" fn kclvm_yaml_decode(
     panic!("decode() missing 1 required positional argument: 'value'")
 }
 
-/// decode_all(value)
+/// decode_all(value, allow_duplicate_keys=True, allow_aliases=True)
 #[no_mangle]
 #[runtime_fn]
 pub extern "C" fn kclvm_yaml_decode_all(
@@ -84,7 +85,8 @@ pub extern "C" fn kclvm_yaml_decode_all(
     let ctx = mut_ptr_as_ref(ctx);
 
     if let Some(arg0) = get_call_arg(args, kwargs, 0, Some("value")) {
-        match ValueRef::list_from_yaml_stream(ctx, arg0.as_str().as_ref()) {
+        let opts = args_to_decode_opts(args, kwargs, 1);
+        match ValueRef::list_from_yaml_stream_with_options(ctx, arg0.as_str().as_ref(), &opts) {
             Ok(x) => return x.into_raw(ctx),
             Err(err) => panic!("{}", err),
         }
@@ -107,6 +109,7 @@ pub extern "C" fn kclvm_yaml_dump_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            ptr_as_ref(ctx).check_write_access(&filename);
 
             let yaml = data.to_yaml_string_with_options(&args_to_opts(args, kwargs, 2));
             std::fs::write(&filename, yaml)
@@ -135,6 +138,7 @@ pub extern "C" fn kclvm_yaml_dump_all_to_file(
     match (data, filename) {
         (Some(data), Some(filename)) => {
             let filename = filename.as_str();
+            ptr_as_ref(ctx).check_write_access(&filename);
             let opts = args_to_opts(args, kwargs, 2);
             let results = data
                 .as_list_ref()
@@ -189,3 +193,16 @@ fn args_to_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> YamlEncodeO
     }
     opts
 }
+
+fn args_to_decode_opts(args: &ValueRef, kwargs: &ValueRef, index: usize) -> YamlDecodeOptions {
+    let mut opts = YamlDecodeOptions::default();
+    if let Some(allow_duplicate_keys) =
+        get_call_arg_bool(args, kwargs, index, Some("allow_duplicate_keys"))
+    {
+        opts.allow_duplicate_keys = allow_duplicate_keys;
+    }
+    if let Some(allow_aliases) = get_call_arg_bool(args, kwargs, index + 1, Some("allow_aliases")) {
+        opts.allow_aliases = allow_aliases;
+    }
+    opts
+}
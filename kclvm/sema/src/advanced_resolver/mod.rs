@@ -1554,4 +1554,58 @@ mod tests {
         let node_ty_map = resolver::resolve_program(&mut program).node_ty_map;
         AdvancedResolver::resolve_program(&program, &mut gs, node_ty_map).unwrap();
     }
+
+    #[test]
+    fn test_rename_symbol() {
+        let sess = Arc::new(ParseSession::default());
+
+        let path = "src/advanced_resolver/test_data/schema_symbols.k"
+            .to_string()
+            .replace("/", &std::path::MAIN_SEPARATOR.to_string());
+        let mut program = load_program(sess.clone(), &[&path], None, None)
+            .unwrap()
+            .program;
+        let mut gs = GlobalState::default();
+        Namer::find_symbols(&program, &mut gs);
+        let node_ty_map = resolver::resolve_program(&mut program).node_ty_map;
+        AdvancedResolver::resolve_program(&program, &mut gs, node_ty_map).unwrap();
+        let filename = adjust_canonicalization(Path::new(".").canonicalize().unwrap().join(&path));
+        let rename = |line: u64, column: u64, new_name: &str| {
+            let symbol_ref = gs
+                .look_up_exact_symbol(&Position {
+                    filename: filename.clone(),
+                    line,
+                    column: Some(column),
+                })
+                .unwrap();
+            gs.rename_symbol(symbol_ref, new_name)
+                .into_iter()
+                .map(|edit| {
+                    (
+                        edit.range.0.line,
+                        edit.range.0.column.unwrap_or(0),
+                        edit.range.1.column.unwrap_or(0),
+                        edit.new_text,
+                    )
+                })
+                .collect::<Vec<_>>()
+        };
+        // Rename the schema from its reference.
+        assert_eq!(
+            rename(28, 5, "Base"),
+            vec![
+                (10, 7, 11, "Base".to_string()),
+                (28, 4, 8, "Base".to_string()),
+            ]
+        );
+        // Rename the import alias.
+        assert_eq!(
+            rename(16, 23, "h"),
+            vec![(6, 24, 25, "h".to_string()), (16, 23, 24, "h".to_string())]
+        );
+        // Add the alias to the import statement without alias.
+        let edits = rename(14, 13, "x");
+        assert_eq!(edits[0], (1, 20, 20, " as x".to_string()));
+        assert!(edits[1..].iter().all(|(_, _, _, name)| name == "x"));
+    }
 }
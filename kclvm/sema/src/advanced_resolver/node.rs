@@ -1788,6 +1788,12 @@ impl<'ctx> AdvancedResolver<'_> {
                         self.walk_type_expr(Some(&ret_ty))?;
                     }
                 }
+                ast::Type::Generic(generic_type) => {
+                    self.walk_identifier(&generic_type.name)?;
+                    for arg_ty in generic_type.args.iter() {
+                        self.walk_type_expr(Some(arg_ty))?;
+                    }
+                }
             }
         }
 
@@ -46,6 +46,20 @@ register_decorator! {
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "since".to_string(),
+                ty: Arc::new(Type::STR),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "replacement".to_string(),
+                ty: Arc::new(Type::STR),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
         r#"This decorator is used to get the deprecation message according to the wrapped key-value pair."#,
         false,
@@ -59,4 +73,12 @@ register_decorator! {
         true,
         Some(0),
     )
+    secret => Type::function(
+        None,
+        Arc::new(Type::ANY),
+        &[],
+        r#"This decorator is used to mark the schema attribute as a secret, whose value is masked as `******` or externalized to a separate secrets document in the YAML and JSON outputs."#,
+        false,
+        None,
+    )
 }
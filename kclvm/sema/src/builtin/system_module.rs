@@ -217,6 +217,22 @@ register_net_member! {
         false,
         None,
     )
+    is_IPv6 => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "ip".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether ip is a IPv6 one."#,
+        false,
+        None,
+    )
     is_IP => Type::function(
         None,
         Type::bool_ref(),
@@ -357,7 +373,7 @@ register_net_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Parse a CIDR block into network, prefix, minHost, and maxHost."#,
+        r#"Parse an IPv4 or IPv6 CIDR block into a dict with the ip, mask (the prefix length), network, min_host and max_host."#,
         false,
         None,
     )
@@ -373,7 +389,7 @@ register_net_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Generate a list of all IP addresses in a CIDR block."#,
+        r#"Generate a list of all the host IP addresses in an IPv4 or IPv6 CIDR block."#,
         false,
         None,
     )
@@ -387,9 +403,16 @@ register_net_member! {
                 has_default: false,
                 default_value: None,
                 range: dummy_range(),
-            }
+            },
+            Parameter {
+                name: "new_prefix".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Split a CIDR block into smaller subnets with a given prefix."#,
+        r#"Split a CIDR block into smaller subnets with a given prefix, and the default new prefix is one bit longer than the prefix of the block."#,
         false,
         None,
     )
@@ -416,6 +439,52 @@ register_net_member! {
         false,
         None,
     )
+    contains => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "cidr".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ip".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Check if an IPv4 or IPv6 CIDR block contains the IP address."#,
+        false,
+        None,
+    )
+    subnets => Type::function(
+        None,
+        Type::list_ref(Type::str_ref()),
+        &[
+            Parameter {
+                name: "cidr".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "new_prefix".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Split an IPv4 or IPv6 CIDR block into the subnets with the new prefix, and the default new prefix is one bit longer than the prefix of the block."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -899,7 +968,7 @@ register_regex_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Return the string obtained by replacing the leftmost non-overlapping occurrences of the pattern in string by the replacement."#,
+        r#"Return the string obtained by replacing the leftmost non-overlapping occurrences of the pattern in string by the replacement. The replacement can reference the groups with `$name`, `${name}` or `$1`, and `$$` denotes a literal `$`."#,
         false,
         None,
     )
@@ -944,7 +1013,7 @@ register_regex_member! {
     )
     findall => Type::function(
         None,
-        Type::list_ref(Type::str_ref()),
+        Type::list_ref(Type::any_ref()),
         &[
             Parameter {
                 name: "string".to_string(),
@@ -961,7 +1030,7 @@ register_regex_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Return a list of all non-overlapping matches in the string."#,
+        r#"Return a list of all non-overlapping matches in the string. The matches are the dicts of the named groups when the pattern has named groups e.g., `(?P<key>\w+)`, the lists of the groups when the pattern has more than one group, or the matched strings otherwise."#,
         false,
         None,
     )
@@ -1014,7 +1083,7 @@ register_regex_member! {
                 range: dummy_range(),
             },
         ],
-        r#"Return a list composed of words from the string, splitting up to a maximum of `maxsplit` times using `pattern` as the separator."#,
+        r#"Return a list composed of words from the string, splitting up to a maximum of `maxsplit` times using `pattern` as the separator, and the remainder of the string is the last word."#,
         false,
         None,
     )
@@ -1123,8 +1192,22 @@ register_yaml_member! {
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "allow_duplicate_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "allow_aliases".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Deserialize `value` (a string instance containing a YAML document) to a KCL object."#,
+        r#"Deserialize `value` (a string instance containing a YAML document) to a KCL object. The duplicate keys in a mapping and the aliases e.g., `*anchor` are rejected when `allow_duplicate_keys` and `allow_aliases` are False."#,
         false,
         None,
     )
@@ -1139,8 +1222,22 @@ register_yaml_member! {
                 default_value: None,
                 range: dummy_range(),
             },
+            Parameter {
+                name: "allow_duplicate_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "allow_aliases".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
         ],
-        r#"Parse all YAML documents in a stream and produce corresponding KCL objects."#,
+        r#"Parse all YAML documents in a stream and produce corresponding KCL objects. The duplicate keys in a mapping and the aliases e.g., `*anchor` are rejected when `allow_duplicate_keys` and `allow_aliases` are False."#,
         false,
         None,
     )
@@ -1600,6 +1697,144 @@ register_crypto_member! {
         false,
         None,
     )
+    filesha512 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Calculate the SHA512 hash of the file `filepath`."#,
+        false,
+        None,
+    )
+    fileblake3 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "filepath".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Calculate the BLAKE3 hash of the file `filepath`."#,
+        false,
+        None,
+    )
+    hmac_sha256 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Calculate the HMAC of the string `value` with the `key` using `SHA256` and the codec registered for encoding."#,
+        false,
+        None,
+    )
+    hmac_sha512 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "key".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "encoding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Calculate the HMAC of the string `value` with the `key` using `SHA512` and the codec registered for encoding."#,
+        false,
+        None,
+    )
+    bcrypt_verify => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "password".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "hash".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether the `password` matches the bcrypt `hash` e.g., `$2b$12$...`."#,
+        false,
+        None,
+    )
+    argon2_verify => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "password".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "hash".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether the `password` matches the argon2 PHC string `hash` e.g., `$argon2id$v=19$...`."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -1861,6 +2096,22 @@ register_units_member! {
         false,
         None,
     )
+    parse => Type::function(
+        None,
+        Type::int_ref(),
+        &[
+            Parameter {
+                name: "quantity".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Parse the human-readable quantity string e.g., `1.5Gi`, `500m` or `1e3` into an int. The SI suffixes `n`, `u`, `m`, `k`, `K`, `M`, `G`, `T`, `P`, `E` and the IEC suffixes `Ki`, `Mi`, `Gi`, `Ti`, `Pi`, `Ei` are supported, and the fractional result is rounded up."#,
+        false,
+        None,
+    )
 }
 
 // ------------------------------
@@ -2265,39 +2516,795 @@ register_runtime_member! {
     )
 }
 
-pub const STANDARD_SYSTEM_MODULES: &[&str] = &[
-    COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, CRYPTO, BASE64, UNITS, FILE,
-    TEMPLATE, RUNTIME,
-];
-
-pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
-    "@collection",
-    "@net",
-    "@manifests",
-    "@math",
-    "@datetime",
-    "@regex",
-    "@yaml",
-    "@json",
-    "@crypto",
-    "@base64",
-    "@units",
-    "@file",
-    "@template",
-    "@runtime",
-];
+// ------------------------------
+// decimal system package
+// ------------------------------
 
-/// Get the system module members
-pub fn get_system_module_members(name: &str) -> Vec<&str> {
-    match name {
-        BASE64 => BASE64_FUNCTION_NAMES.to_vec(),
-        NET => NET_FUNCTION_NAMES.to_vec(),
-        MANIFESTS => MANIFESTS_FUNCTION_NAMES.to_vec(),
-        MATH => MATH_FUNCTION_NAMES.to_vec(),
-        DATETIME => DATETIME_FUNCTION_NAMES.to_vec(),
-        REGEX => REGEX_FUNCTION_NAMES.to_vec(),
-        YAML => YAML_FUNCTION_NAMES.to_vec(),
-        JSON => JSON_FUNCTION_NAMES.to_vec(),
+pub const DECIMAL: &str = "decimal";
+macro_rules! register_decimal_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const DECIMAL_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const DECIMAL_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_decimal_member! {
+    add => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "y".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the exact sum of `x` and `y`, the number strings e.g., `"0.1"` are also accepted."#,
+        false,
+        None,
+    )
+    sub => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "y".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the exact difference of `x` and `y`, the number strings e.g., `"0.1"` are also accepted."#,
+        false,
+        None,
+    )
+    mul => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "y".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the exact product of `x` and `y`, the number strings e.g., `"0.1"` are also accepted."#,
+        false,
+        None,
+    )
+    div => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "y".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Return the quotient of `x` and `y` with 28 significant digits at most, the number strings e.g., `"0.1"` are also accepted."#,
+        false,
+        None,
+    )
+    round => Type::function(
+        None,
+        Type::float_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "places".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "rounding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Round `x` to the decimal `places` using the `rounding` mode. The rounding modes are 'half_even' (default), 'half_up', 'half_down', 'down', 'up', 'floor' and 'ceiling'."#,
+        false,
+        None,
+    )
+    format => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "x".to_string(),
+                ty: Type::union_ref(&[Type::int_ref(), Type::float_ref(), Type::str_ref()]),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "places".to_string(),
+                ty: Type::int_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "rounding".to_string(),
+                ty: Type::str_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Format `x` as a string with exactly the decimal `places` e.g., `"19.90"` using the `rounding` mode, and without the trailing zeros when `places` is not given."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// toml system package
+// ------------------------------
+
+pub const TOML: &str = "toml";
+macro_rules! register_toml_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const TOML_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const TOML_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_toml_member! {
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict or schema object `data` to a TOML formatted str. The `None` values are ignored because TOML has no null value."#,
+        false,
+        Some(1),
+    )
+    decode => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Deserialize `value` (a string instance containing a TOML document) to a KCL dict, and the TOML datetimes are decoded to the strings."#,
+        false,
+        None,
+    )
+    dump_to_file => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "data".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "filename".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "sort_keys".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ignore_private".to_string(),
+                ty: Type::bool_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Serialize a KCL dict or schema object `data` to a TOML formatted str and write it into the file `filename`."#,
+        false,
+        Some(2),
+    )
+    validate => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Validate whether the given string is a valid TOML document."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// jsonpath system package
+// ------------------------------
+
+pub const JSONPATH: &str = "jsonpath";
+macro_rules! register_jsonpath_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const JSONPATH_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const JSONPATH_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_jsonpath_member! {
+    get => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "path".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "default".to_string(),
+                ty: Type::any_ref(),
+                has_default: true,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns the first value matched by the JSONPath `path` e.g., `$.spec.containers[0].image` in `value`, or the `default` value when no value is matched."#,
+        false,
+        None,
+    )
+    query => Type::function(
+        None,
+        Type::list_ref(Type::any_ref()),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "path".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns the list of all the values matched by the JSONPath `path` e.g., `$.spec.containers[*].image` in `value`."#,
+        false,
+        None,
+    )
+    set => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "path".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "new_value".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns a copy of `value` with the values matched by the JSONPath `path` set to `new_value`, and the missing dict keys along the path are created."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// semver system package
+// ------------------------------
+
+pub const SEMVER: &str = "semver";
+macro_rules! register_semver_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const SEMVER_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const SEMVER_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_semver_member! {
+    parse => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Parse the semantic `version` to a dict with the `major`, `minor`, `patch`, `pre` and `build` keys. The leading `v` and the missing minor and patch numbers are accepted e.g., `v1.2` is `1.2.0`."#,
+        false,
+        None,
+    )
+    compare => Type::function(
+        None,
+        Type::int_ref(),
+        &[
+            Parameter {
+                name: "a".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "b".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns -1, 0 or 1 when the semantic version `a` is less than, equal to or greater than the version `b`, and the build metadata is ignored."#,
+        false,
+        None,
+    )
+    satisfies => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "range".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether the semantic `version` satisfies the version `range` e.g., `>=1.2.0, <2.0.0`, `^1.2` and `~1.2.3`, and the alternative ranges are separated by `||`."#,
+        false,
+        None,
+    )
+    normalize => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns the normalized semantic `version` e.g., `v1.2` is normalized to `1.2.0`."#,
+        false,
+        None,
+    )
+    is_valid => Type::function(
+        None,
+        Type::bool_ref(),
+        &[
+            Parameter {
+                name: "version".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Whether the `version` is a valid semantic version after the normalization."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// url system package
+// ------------------------------
+
+pub const URL: &str = "url";
+macro_rules! register_url_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const URL_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const URL_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_url_member! {
+    parse => Type::function(
+        None,
+        Type::dict_ref(Type::str_ref(), Type::any_ref()),
+        &[
+            Parameter {
+                name: "url".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Parse the `url` to a dict with the `scheme`, `username`, `password`, `host`, `port`, `path`, `query` and `fragment` keys. The `port` is the known default port of the scheme when it is omitted, and the `query` is a dict of the decoded query parameters."#,
+        false,
+        None,
+    )
+    encode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Percent-encode the `value` as a URL component, and only the RFC 3986 unreserved characters are not encoded."#,
+        false,
+        None,
+    )
+    decode => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "value".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Decode the percent-encoded `value`."#,
+        false,
+        None,
+    )
+    join => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "base".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "ref".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Resolve the `ref` URL against the `base` URL e.g., joining `https://kcl-lang.io/docs/` and `intro` is `https://kcl-lang.io/docs/intro`."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// random system package
+// ------------------------------
+
+pub const RANDOM: &str = "random";
+macro_rules! register_random_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const RANDOM_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const RANDOM_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_random_member! {
+    seed => Type::function(
+        None,
+        Arc::new(Type::NONE),
+        &[
+            Parameter {
+                name: "seed".to_string(),
+                ty: Type::int_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Reseed the random number generator with `seed` to make the following random values reproducible."#,
+        false,
+        None,
+    )
+    random => Type::function(
+        None,
+        Type::float_ref(),
+        &[],
+        r#"Returns a random float in the range [0.0, 1.0)."#,
+        false,
+        None,
+    )
+    randint => Type::function(
+        None,
+        Type::int_ref(),
+        &[
+            Parameter {
+                name: "a".to_string(),
+                ty: Type::int_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "b".to_string(),
+                ty: Type::int_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns a random int in the range [a, b] including both end points."#,
+        false,
+        None,
+    )
+    choice => Type::function(
+        None,
+        Type::any_ref(),
+        &[
+            Parameter {
+                name: "seq".to_string(),
+                ty: Type::any_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns a random element of the non-empty list or the random character of the non-empty string `seq`."#,
+        false,
+        None,
+    )
+    shuffle => Type::function(
+        None,
+        Type::list_ref(Type::any_ref()),
+        &[
+            Parameter {
+                name: "seq".to_string(),
+                ty: Type::list_ref(Type::any_ref()),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns a shuffled copy of the list `seq`."#,
+        false,
+        None,
+    )
+}
+
+// ------------------------------
+// uuid system package
+// ------------------------------
+
+pub const UUID: &str = "uuid";
+macro_rules! register_uuid_member {
+    ($($name:ident => $ty:expr)*) => (
+        pub const UUID_FUNCTION_TYPES: Lazy<IndexMap<String, Type>> = Lazy::new(|| {
+            let mut builtin_mapping = IndexMap::default();
+            $( builtin_mapping.insert(stringify!($name).to_string(), $ty); )*
+            builtin_mapping
+        });
+        pub const UUID_FUNCTION_NAMES: &[&str] = &[
+            $( stringify!($name), )*
+        ];
+    )
+}
+register_uuid_member! {
+    v4 => Type::function(
+        None,
+        Type::str_ref(),
+        &[],
+        r#"Returns a random version 4 UUID string, which is reproducible with the random seed."#,
+        false,
+        None,
+    )
+    v5 => Type::function(
+        None,
+        Type::str_ref(),
+        &[
+            Parameter {
+                name: "namespace".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+            Parameter {
+                name: "name".to_string(),
+                ty: Type::str_ref(),
+                has_default: false,
+                default_value: None,
+                range: dummy_range(),
+            },
+        ],
+        r#"Returns the version 5 UUID string of the `name` in the `namespace`, which is the UUID string or one of `dns`, `url`, `oid` and `x500`."#,
+        false,
+        None,
+    )
+}
+
+pub const STANDARD_SYSTEM_MODULES: &[&str] = &[
+    COLLECTION, NET, MANIFESTS, MATH, DATETIME, REGEX, YAML, JSON, CRYPTO, BASE64, UNITS, FILE,
+    TEMPLATE, RUNTIME, DECIMAL, TOML, JSONPATH, SEMVER, URL, RANDOM, UUID,
+];
+
+pub const STANDARD_SYSTEM_MODULE_NAMES_WITH_AT: &[&str] = &[
+    "@collection",
+    "@net",
+    "@manifests",
+    "@math",
+    "@datetime",
+    "@regex",
+    "@yaml",
+    "@json",
+    "@crypto",
+    "@base64",
+    "@units",
+    "@file",
+    "@template",
+    "@runtime",
+    "@decimal",
+    "@toml",
+    "@jsonpath",
+    "@semver",
+    "@url",
+    "@random",
+    "@uuid",
+];
+
+/// Get the system module members
+pub fn get_system_module_members(name: &str) -> Vec<&str> {
+    match name {
+        BASE64 => BASE64_FUNCTION_NAMES.to_vec(),
+        NET => NET_FUNCTION_NAMES.to_vec(),
+        MANIFESTS => MANIFESTS_FUNCTION_NAMES.to_vec(),
+        MATH => MATH_FUNCTION_NAMES.to_vec(),
+        DATETIME => DATETIME_FUNCTION_NAMES.to_vec(),
+        REGEX => REGEX_FUNCTION_NAMES.to_vec(),
+        YAML => YAML_FUNCTION_NAMES.to_vec(),
+        JSON => JSON_FUNCTION_NAMES.to_vec(),
         CRYPTO => CRYPTO_FUNCTION_NAMES.to_vec(),
         UNITS => {
             let mut members = UNITS_FUNCTION_NAMES.to_vec();
@@ -2308,6 +3315,13 @@ pub fn get_system_module_members(name: &str) -> Vec<&str> {
         FILE => FILE_FUNCTION_NAMES.to_vec(),
         TEMPLATE => TEMPLATE_FUNCTION_NAMES.to_vec(),
         RUNTIME => RUNTIME_FUNCTION_NAMES.to_vec(),
+        DECIMAL => DECIMAL_FUNCTION_NAMES.to_vec(),
+        TOML => TOML_FUNCTION_NAMES.to_vec(),
+        JSONPATH => JSONPATH_FUNCTION_NAMES.to_vec(),
+        SEMVER => SEMVER_FUNCTION_NAMES.to_vec(),
+        URL => URL_FUNCTION_NAMES.to_vec(),
+        RANDOM => RANDOM_FUNCTION_NAMES.to_vec(),
+        UUID => UUID_FUNCTION_NAMES.to_vec(),
         _ => bug!("invalid system module name '{}'", name),
     }
 }
@@ -2371,6 +3385,34 @@ pub fn get_system_member_function_ty(name: &str, func: &str) -> TypeRef {
             let types = RUNTIME_FUNCTION_TYPES;
             types.get(func).cloned()
         }
+        DECIMAL => {
+            let types = DECIMAL_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        TOML => {
+            let types = TOML_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        JSONPATH => {
+            let types = JSONPATH_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        SEMVER => {
+            let types = SEMVER_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        URL => {
+            let types = URL_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        RANDOM => {
+            let types = RANDOM_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
+        UUID => {
+            let types = UUID_FUNCTION_TYPES;
+            types.get(func).cloned()
+        }
         _ => None,
     };
     optional_ty
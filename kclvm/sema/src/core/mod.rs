@@ -1,5 +1,6 @@
 pub mod global_state;
 pub mod package;
+pub mod rename;
 pub mod scope;
 pub mod semantic_information;
 pub mod symbol;
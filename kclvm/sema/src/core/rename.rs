@@ -0,0 +1,143 @@
+use indexmap::IndexSet;
+use kclvm_error::diagnostic::Range;
+
+use super::{
+    global_state::GlobalState,
+    symbol::{SymbolKind, SymbolRef},
+};
+
+/// A text edit which replaces the text in the range with the new text, and it denotes
+/// an insertion when the start and the end of the range are the same.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TextEdit {
+    pub range: Range,
+    pub new_text: String,
+}
+
+impl GlobalState {
+    /// Get the text edits to rename the symbol to the new name, which include the edits
+    /// of its definition and all its references across the packages. The symbol can be
+    /// either the definition or a reference e.g., the symbol at the cursor.
+    ///
+    /// When the symbol denotes an imported package e.g., `p` in `import pkg as p` and
+    /// `p.Person`, the import alias and its references in the module are renamed, and
+    /// the alias is added to the import statement if it has no alias. The builtin symbols
+    /// and the packages themselves can't be renamed, and no edits are returned.
+    pub fn rename_symbol(&self, symbol_ref: SymbolRef, new_name: &str) -> Vec<TextEdit> {
+        let symbols = self.get_symbols();
+        let def_ref = match symbols
+            .get_symbol(symbol_ref)
+            .and_then(|symbol| symbol.get_definition())
+        {
+            Some(def_ref) => def_ref,
+            None => return vec![],
+        };
+        let def = match symbols.get_symbol(def_ref) {
+            Some(def) => def,
+            None => return vec![],
+        };
+        if matches!(def_ref.get_kind(), SymbolKind::Package) {
+            return self.rename_import_alias(symbol_ref, def_ref, new_name);
+        }
+        let def_range = def.get_range();
+        if def_range.0.filename.is_empty() {
+            return vec![];
+        }
+        let mut ranges: IndexSet<Range> = IndexSet::new();
+        ranges.insert(def_range);
+        for ref_symbol in def.get_references() {
+            if let Some(ref_symbol) = symbols.get_symbol(ref_symbol) {
+                ranges.insert(ref_symbol.get_range());
+            }
+        }
+        sorted_edits(
+            ranges
+                .into_iter()
+                .map(|range| TextEdit {
+                    range,
+                    new_text: new_name.to_string(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Rename the import alias of the package in the module of the symbol.
+    fn rename_import_alias(
+        &self,
+        symbol_ref: SymbolRef,
+        pkg_ref: SymbolRef,
+        new_name: &str,
+    ) -> Vec<TextEdit> {
+        let symbols = self.get_symbols();
+        let (symbol, pkg) = match (symbols.get_symbol(symbol_ref), symbols.get_symbol(pkg_ref)) {
+            (Some(symbol), Some(pkg)) => (symbol, pkg),
+            _ => return vec![],
+        };
+        let filename = symbol.get_range().0.filename;
+        let pkgpath = pkg.get_name();
+        let alias = match self
+            .get_packages()
+            .get_module_info(&filename)
+            .and_then(|module_info| {
+                module_info
+                    .get_imports()
+                    .into_iter()
+                    .find(|(_, import)| import.get_fully_qualified_name() == pkgpath)
+            }) {
+            Some((alias, _)) => alias,
+            None => return vec![],
+        };
+        let mut edits = vec![];
+        for ref_symbol in pkg.get_references() {
+            let ref_symbol = match symbols.get_symbol(ref_symbol) {
+                Some(ref_symbol) => ref_symbol,
+                None => continue,
+            };
+            let (start, end) = ref_symbol.get_range();
+            if start.filename != filename {
+                continue;
+            }
+            let name = ref_symbol.get_name();
+            if name == alias {
+                edits.push(TextEdit {
+                    range: (start, end),
+                    new_text: new_name.to_string(),
+                });
+            } else {
+                // The reference in the import statement e.g., `import a.pkg` is named
+                // by the import path, and its range is the alias if it exists.
+                let has_alias = name.rsplit('.').next() != Some(alias.as_str())
+                    || start.line != end.line
+                    || end
+                        .column
+                        .unwrap_or(0)
+                        .saturating_sub(start.column.unwrap_or(0))
+                        != name.len() as u64;
+                if has_alias {
+                    edits.push(TextEdit {
+                        range: (start, end),
+                        new_text: new_name.to_string(),
+                    });
+                } else {
+                    edits.push(TextEdit {
+                        range: (end.clone(), end),
+                        new_text: format!(" as {}", new_name),
+                    });
+                }
+            }
+        }
+        sorted_edits(edits)
+    }
+}
+
+fn sorted_edits(mut edits: Vec<TextEdit>) -> Vec<TextEdit> {
+    edits.sort_by(|a, b| {
+        (&a.range.0.filename, a.range.0.line, a.range.0.column).cmp(&(
+            &b.range.0.filename,
+            b.range.0.line,
+            b.range.0.column,
+        ))
+    });
+    edits.dedup();
+    edits
+}
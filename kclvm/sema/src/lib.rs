@@ -5,6 +5,7 @@ pub mod eval;
 pub mod info;
 pub mod lint;
 pub mod namer;
+pub mod pass;
 pub mod plugin;
 pub mod pre_process;
 pub mod resolver;
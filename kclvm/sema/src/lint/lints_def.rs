@@ -2,10 +2,10 @@ use crate::lint::lint::{Lint, LintArray, LintContext};
 use crate::lint::lintpass::LintPass;
 use crate::resolver::scope::Scope;
 use crate::{declare_lint_pass, resolver::scope::ScopeObjectKind};
-use indexmap::IndexSet;
+use indexmap::IndexMap;
 use kclvm_ast::ast;
 use kclvm_ast::pos::GetPos;
-use kclvm_error::{Handler, Level, Message, Style, WarningKind};
+use kclvm_error::{Diagnostic, DiagnosticId, Handler, Level, Message, Style, WarningKind};
 
 /// The 'import_position' lint detects import statements that are not declared at the top of file.
 /// ### Example
@@ -25,7 +25,7 @@ pub static IMPORT_POSITION: &Lint = &Lint {
     level: Level::Warning,
     desc: "Check for importstmt that are not defined at the top of file",
     code: "W0413",
-    note: Some("Consider moving tihs statement to the top of the file"),
+    note: Some("Consider moving this statement to the top of the file"),
 };
 
 declare_lint_pass!(ImportPosition => [IMPORT_POSITION]);
@@ -60,7 +60,7 @@ impl LintPass for ImportPosition {
                                 "The import stmt should be placed at the top of the module"
                             ),
                             note: Some(
-                                "Consider moving tihs statement to the top of the file".to_string(),
+                                "Consider moving this statement to the top of the file".to_string(),
                             ),
                             suggested_replacement: None,
                         }],
@@ -151,25 +151,37 @@ impl LintPass for ReImport {
         _ctx: &mut LintContext,
         module: &ast::Module,
     ) {
-        let mut import_names = IndexSet::<String>::new();
+        let mut import_stmts = IndexMap::<&str, &ast::Node<ast::Stmt>>::new();
         for stmt in &module.body {
             if let ast::Stmt::Import(import_stmt) = &stmt.node {
-                if import_names.contains(&import_stmt.path.node) {
-                    handler.add_warning(
-                        WarningKind::ReimportWarning,
-                        &[Message {
-                            range: stmt.get_span_pos(),
-                            style: Style::Line,
-                            message: format!(
-                                "Module '{}' is reimported multiple times",
-                                &import_stmt.name
-                            ),
-                            note: Some("Consider removing this statement".to_string()),
-                            suggested_replacement: None,
-                        }],
-                    );
-                } else {
-                    import_names.insert(import_stmt.path.node.clone());
+                match import_stmts.get(import_stmt.path.node.as_str()) {
+                    Some(first) => {
+                        let diag = Diagnostic {
+                            level: Level::Warning,
+                            messages: vec![Message {
+                                range: stmt.get_span_pos(),
+                                style: Style::Line,
+                                message: format!(
+                                    "Module '{}' is reimported multiple times",
+                                    &import_stmt.name
+                                ),
+                                note: Some("Consider removing this statement".to_string()),
+                                suggested_replacement: None,
+                            }],
+                            code: Some(DiagnosticId::Warning(WarningKind::ReimportWarning)),
+                            fixes: vec![],
+                            labels: vec![],
+                            source_hash: None,
+                        }
+                        .with_label(
+                            first.get_span_pos(),
+                            &format!("Module '{}' is first imported here", &import_stmt.name),
+                        );
+                        handler.add_diagnostic(diag);
+                    }
+                    None => {
+                        import_stmts.insert(&import_stmt.path.node, stmt);
+                    }
                 }
             }
         }
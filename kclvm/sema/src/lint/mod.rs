@@ -73,8 +73,18 @@
 //! 6. If new `check_*` method was added in step 4, it needs to override the walk_* method in Linter.
 //! In addition to calling the self.pass.check_* function, the original walk method in MutSelfWalker
 //! should be copied here so that it can continue to traverse the child nodes.
+//!
+//! 7. Implement the [`SemanticPass`] for the lintpass with `impl_semantic_pass` in this file, and
+//! register it in the resolver when the `lint_check` option is enabled, e.g.,
+//!
+//!    ```ignore
+//!    impl_semantic_pass!(ImportPosition, "import-position", |pass, ctx, handler| {
+//!        ...
+//!    });
+//!    ```
 
-use crate::resolver::{scope::Scope, Resolver};
+use crate::pass::{PassContext, SemanticPass};
+use crate::resolver::scope::Scope;
 use kclvm_ast::pos::GetPos;
 use kclvm_error::{Handler, Position};
 mod combinedlintpass;
@@ -84,7 +94,12 @@ mod lints_def;
 use kclvm_ast::ast;
 use kclvm_ast::walker::MutSelfWalker;
 
-pub use self::{combinedlintpass::CombinedLintPass, lint::LintContext, lintpass::LintPass};
+pub use self::{
+    combinedlintpass::CombinedLintPass,
+    lint::LintContext,
+    lintpass::LintPass,
+    lints_def::{ImportPosition, ReImport, UnusedImport},
+};
 
 /// The struct `Linter` is used to traverse the AST and call the `check_*` method defined in `CombinedLintPass`.
 pub struct Linter<T: LintPass> {
@@ -117,29 +132,6 @@ impl Linter<CombinedLintPass> {
     }
 }
 
-impl Resolver<'_> {
-    /// Iterate the module and run lint checks, generating diagnostics and save them in `lint.handler`
-    pub fn lint_check_module(&mut self, module: &ast::Module) {
-        self.linter.ctx.filename = module.filename.clone();
-        self.linter.walk_module(module);
-    }
-    /// Recursively iterate the scope and its child scope, run lint checks, generating diagnostics and save them in `lint.handler`
-    pub fn lint_check_scope(&mut self, scope: &Scope) {
-        self.linter.walk_scope(scope);
-        for children in &scope.children {
-            self.lint_check_scope(&children.borrow().clone())
-        }
-    }
-
-    /// Iterate the resolver.scope_map and run lint checks, generating diagnostics and save them in `lint.handler`
-    pub fn lint_check_scope_map(&mut self) {
-        let scope_map = self.scope_map.clone();
-        for (_, scope) in scope_map.iter() {
-            self.lint_check_scope(&scope.borrow())
-        }
-    }
-}
-
 macro_rules! walk_set_list {
     ($walker: expr, $method: ident, $list: expr) => {
         for elem in &$list {
@@ -400,3 +392,72 @@ impl MutSelfWalker for Linter<CombinedLintPass> {
         self.walk_expr(&formatted_value.value.node);
     }
 }
+
+macro_rules! impl_semantic_pass {
+    ($pass:ident, $name:expr, |$p:ident, $ctx:ident, $handler:ident| $run:block) => {
+        impl SemanticPass for $pass {
+            fn name(&self) -> &'static str {
+                $name
+            }
+
+            fn run(&self, $ctx: &PassContext, $handler: &mut Handler) {
+                let mut $p = *self;
+                $run
+            }
+        }
+    };
+}
+
+impl_semantic_pass!(ImportPosition, "import-position", |pass, ctx, handler| {
+    for_each_module(ctx.program, |module| {
+        pass.check_module(handler, &mut module_ctx(module), module);
+    });
+});
+
+impl_semantic_pass!(ReImport, "reimport", |pass, ctx, handler| {
+    for_each_module(ctx.program, |module| {
+        pass.check_module(handler, &mut module_ctx(module), module);
+    });
+});
+
+impl_semantic_pass!(UnusedImport, "unused-import", |pass, ctx, handler| {
+    let mut lint_ctx = LintContext::dummy_ctx();
+    for (pkgpath, scope) in &ctx.scope.scope_map {
+        if ctx.program.pkgs.contains_key(pkgpath) {
+            walk_scope(&mut pass, handler, &mut lint_ctx, &scope.borrow());
+        }
+    }
+});
+
+/// Recursively iterate the scope and its child scopes, and run the lint checks on them.
+fn walk_scope<T: LintPass>(
+    pass: &mut T,
+    handler: &mut Handler,
+    ctx: &mut LintContext,
+    scope: &Scope,
+) {
+    pass.check_scope(handler, ctx, scope);
+    for children in &scope.children {
+        walk_scope(pass, handler, ctx, &children.borrow());
+    }
+}
+
+fn module_ctx(module: &ast::Module) -> LintContext {
+    LintContext {
+        filename: module.filename.clone(),
+        ..LintContext::dummy_ctx()
+    }
+}
+
+/// Iterate the modules of the program in the order of the package paths.
+pub fn for_each_module(program: &ast::Program, mut f: impl FnMut(&ast::Module)) {
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    for pkgpath in pkgpaths {
+        for filename in &program.pkgs[pkgpath] {
+            if let Ok(Some(module)) = program.get_module(filename) {
+                f(&module);
+            }
+        }
+    }
+}
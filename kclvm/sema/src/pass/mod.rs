@@ -0,0 +1,155 @@
+//! The semantic analysis passes run on the resolved program, which report the
+//! diagnostics such as the unused symbols and the non-exhaustive conditions.
+//!
+//! The builtin passes are enabled by the resolver [`Options`](crate::resolver::Options),
+//! and embedders can implement [`SemanticPass`] to enforce custom policies, e.g.,
+//! "every Deployment schema must set resources", and register them into the
+//! [`PassManager`] of the resolver options or run them with a [`GlobalState`].
+use std::cell::OnceCell;
+use std::fmt;
+use std::sync::Arc;
+
+use kclvm_ast::ast;
+use kclvm_error::Handler;
+
+use crate::advanced_resolver::AdvancedResolver;
+use crate::core::global_state::GlobalState;
+use crate::namer::Namer;
+use crate::resolver::scope::ProgramScope;
+
+#[cfg(test)]
+mod tests;
+
+/// The resolved program analyzed by the semantic passes.
+pub struct PassContext<'a> {
+    /// The AST program.
+    pub program: &'a ast::Program,
+    /// The resolved program scope including the node types.
+    pub scope: &'a ProgramScope,
+    gs: GlobalStateRef<'a>,
+}
+
+enum GlobalStateRef<'a> {
+    Borrowed(&'a GlobalState),
+    Lazy(OnceCell<GlobalState>),
+}
+
+impl<'a> PassContext<'a> {
+    /// New a pass context whose semantic information is resolved by the advanced
+    /// resolver when a pass first uses it, thus the passes which only depend on the
+    /// program scope don't pay for it.
+    pub fn new(program: &'a ast::Program, scope: &'a ProgramScope) -> Self {
+        Self {
+            program,
+            scope,
+            gs: GlobalStateRef::Lazy(OnceCell::new()),
+        }
+    }
+
+    /// New a pass context with the semantic information already resolved by the caller.
+    pub fn with_global_state(
+        program: &'a ast::Program,
+        scope: &'a ProgramScope,
+        gs: &'a GlobalState,
+    ) -> Self {
+        Self {
+            program,
+            scope,
+            gs: GlobalStateRef::Borrowed(gs),
+        }
+    }
+
+    /// Get the semantic information of the program including the symbols and their
+    /// references.
+    pub fn gs(&self) -> &GlobalState {
+        match &self.gs {
+            GlobalStateRef::Borrowed(gs) => gs,
+            GlobalStateRef::Lazy(gs) => gs.get_or_init(|| {
+                let mut gs = GlobalState::default();
+                Namer::find_symbols(self.program, &mut gs);
+                // The symbols resolved before the failure are still available.
+                let _ = AdvancedResolver::resolve_program(
+                    self.program,
+                    &mut gs,
+                    self.scope.node_ty_map.clone(),
+                );
+                gs
+            }),
+        }
+    }
+}
+
+/// A semantic pass analyzes the resolved program and appends the diagnostics into
+/// the handler.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::Handler;
+/// use kclvm_sema::pass::{PassContext, PassManager, SemanticPass};
+///
+/// struct NoopPass;
+///
+/// impl SemanticPass for NoopPass {
+///     fn name(&self) -> &'static str {
+///         "noop"
+///     }
+///
+///     fn run(&self, _ctx: &PassContext, _handler: &mut Handler) {}
+/// }
+///
+/// let mut passes = PassManager::default();
+/// passes.register(NoopPass);
+/// assert_eq!(passes.names(), vec!["noop"]);
+/// ```
+pub trait SemanticPass: Send + Sync {
+    /// The unique name of the pass.
+    fn name(&self) -> &'static str;
+    /// Analyze the program and append the diagnostics into the handler.
+    fn run(&self, ctx: &PassContext, handler: &mut Handler);
+}
+
+/// PassManager holds the semantic passes, which are run in the registration order.
+#[derive(Clone, Default)]
+pub struct PassManager {
+    passes: Vec<Arc<dyn SemanticPass>>,
+}
+
+impl fmt::Debug for PassManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.names()).finish()
+    }
+}
+
+impl PassManager {
+    /// Register the pass, which replaces the registered pass with the same name.
+    pub fn register<P: SemanticPass + 'static>(&mut self, pass: P) {
+        self.passes.retain(|p| p.name() != pass.name());
+        self.passes.push(Arc::new(pass));
+    }
+
+    /// Extend the passes with the passes in the other manager.
+    pub fn extend(&mut self, other: &PassManager) {
+        for pass in &other.passes {
+            self.passes.retain(|p| p.name() != pass.name());
+            self.passes.push(pass.clone());
+        }
+    }
+
+    /// Get the names of the registered passes.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.passes.iter().map(|p| p.name()).collect()
+    }
+
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.passes.is_empty()
+    }
+
+    /// Run all the passes and append the diagnostics into the handler.
+    pub fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        for pass in &self.passes {
+            pass.run(ctx, handler);
+        }
+    }
+}
@@ -0,0 +1,160 @@
+use std::sync::Arc;
+
+use kclvm_error::{ErrorKind, Handler, Message, Style, WarningKind};
+use kclvm_parser::{load_program, ParseSession};
+
+use super::{PassContext, PassManager, SemanticPass};
+use crate::core::symbol::{Symbol, SymbolKind};
+use crate::resolver::scope::ScopeObjectKind;
+use crate::resolver::{resolve_program_with_opts, Options};
+use crate::ty::TypeKind;
+
+/// The policy requires the Deployment schemas to declare the `resources` attribute.
+struct DeploymentResourcesPass;
+
+impl SemanticPass for DeploymentResourcesPass {
+    fn name(&self) -> &'static str {
+        "deployment-resources"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        for scope in ctx.scope.scope_map.values() {
+            for (name, obj) in &scope.borrow().elems {
+                let obj = obj.borrow();
+                if !matches!(obj.kind, ScopeObjectKind::Definition) || name != "Deployment" {
+                    continue;
+                }
+                if let TypeKind::Schema(schema_ty) = &obj.ty.kind {
+                    if !schema_ty.attrs.contains_key("resources") {
+                        handler.add_error(
+                            ErrorKind::IllegalAttributeError,
+                            &[Message {
+                                range: (obj.start.clone(), obj.end.clone()),
+                                style: Style::LineAndColumn,
+                                message:
+                                    "schema 'Deployment' must declare the attribute 'resources'"
+                                        .to_string(),
+                                note: None,
+                                suggested_replacement: None,
+                            }],
+                        );
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// The policy reports the schemas in the main package which are never referenced,
+/// which depends on the symbol references resolved by the advanced resolver.
+struct UnreferencedSchemaPass;
+
+impl SemanticPass for UnreferencedSchemaPass {
+    fn name(&self) -> &'static str {
+        "unreferenced-schema"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        let symbols = ctx.gs().get_symbols();
+        for (fqn, symbol_ref) in symbols.get_fully_qualified_name_map() {
+            if !matches!(symbol_ref.get_kind(), SymbolKind::Schema) || !fqn.starts_with("__main__.")
+            {
+                continue;
+            }
+            if let Some(schema) = symbols.get_schema_symbol(*symbol_ref) {
+                if schema.get_references().is_empty() {
+                    handler.add_warning(
+                        WarningKind::UnusedSchemaWarning,
+                        &[Message {
+                            range: schema.get_range(),
+                            style: Style::LineAndColumn,
+                            message: format!("schema '{}' is never referenced", schema.get_name()),
+                            note: None,
+                            suggested_replacement: None,
+                        }],
+                    );
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_pass_manager_register() {
+    let mut passes = PassManager::default();
+    passes.register(DeploymentResourcesPass);
+    passes.register(DeploymentResourcesPass);
+    assert_eq!(passes.names(), vec!["deployment-resources"]);
+    assert_eq!(format!("{:?}", passes), "[\"deployment-resources\"]");
+}
+
+#[test]
+fn test_resolve_with_custom_pass() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(sess, &["./src/pass/test_data/policy.k"], None, None)
+        .unwrap()
+        .program;
+    let mut passes = PassManager::default();
+    passes.register(DeploymentResourcesPass);
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            passes,
+            ..Default::default()
+        },
+        None,
+    );
+    let errors: Vec<(String, u64)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.is_error())
+        .map(|diag| {
+            (
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+            )
+        })
+        .collect();
+    assert_eq!(
+        errors,
+        vec![(
+            "schema 'Deployment' must declare the attribute 'resources'".to_string(),
+            1
+        )]
+    );
+}
+
+#[test]
+fn test_resolve_with_global_state_pass() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(sess, &["./src/pass/test_data/policy.k"], None, None)
+        .unwrap()
+        .program;
+    let mut passes = PassManager::default();
+    passes.register(UnreferencedSchemaPass);
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            passes,
+            ..Default::default()
+        },
+        None,
+    );
+    let warnings: Vec<String> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| {
+            diag.code
+                == Some(kclvm_error::DiagnosticId::Warning(
+                    WarningKind::UnusedSchemaWarning,
+                ))
+        })
+        .map(|diag| diag.messages[0].message.clone())
+        .collect();
+    assert_eq!(
+        warnings,
+        vec!["schema 'Service' is never referenced".to_string()]
+    );
+}
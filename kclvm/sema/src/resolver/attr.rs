@@ -67,6 +67,13 @@ impl<'ctx> Resolver<'_> {
             TypeKind::Union(_) => (true, self.any_ty()),
             TypeKind::Schema(schema_ty) => {
                 let (result, schema_attr_ty) = self.schema_load_attr(schema_ty, attr);
+                if let Some(obj) = schema_ty.get_obj_of_attr(attr) {
+                    self.check_deprecation(
+                        &format!("{}.{}", schema_ty.name, attr),
+                        obj.get_deprecation(),
+                        range.clone(),
+                    );
+                }
                 if result {
                     (result, schema_attr_ty)
                 } else if schema_ty.is_member_functions(attr) {
@@ -0,0 +1,322 @@
+//! The check block pass reports the conditions in the schema and rule `check:` blocks
+//! which are provably always true or always false, which are usually the copy-paste
+//! validation bugs, e.g.,
+//!
+//! - The conditions on the literals such as `1 > 0`.
+//! - The length conditions such as `len(labels) >= 0` which is always true.
+//! - The conditions on the attributes of the union literal types such as `kind == "c"`
+//!   where `kind: "a" | "b"`, and the attributes of a single literal type are regarded
+//!   as the attributes with the fixed literal defaults.
+//! - The None conditions on the required attributes such as `name != None`, because the
+//!   required attributes are never None when the check block is executed.
+
+use indexmap::IndexSet;
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_error::{Handler, Message, Style, WarningKind};
+
+use super::exhaustive::{get_literal, get_literals, Literal};
+use super::scope::{NodeKey, NodeTyMap};
+use crate::pass::{PassContext, SemanticPass};
+
+/// The semantic pass enabled by the `check_block_check` option. See [check_block_pass].
+pub(crate) struct CheckBlockPass;
+
+impl SemanticPass for CheckBlockPass {
+    fn name(&self) -> &'static str {
+        "check-block"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        check_block_pass(ctx.program, &ctx.scope.node_ty_map.borrow(), handler);
+    }
+}
+
+/// Report the constant conditions in the check blocks of the program into the handler.
+pub fn check_block_pass(program: &ast::Program, node_ty_map: &NodeTyMap, handler: &mut Handler) {
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    for pkgpath in pkgpaths {
+        for module in &program.pkgs[pkgpath] {
+            let module = match program.get_module(module) {
+                Ok(Some(module)) => module,
+                _ => continue,
+            };
+            for stmt in &module.body {
+                let (checks, required_attrs) = match &stmt.node {
+                    ast::Stmt::Schema(schema_stmt) => {
+                        (&schema_stmt.checks, get_required_attrs(&schema_stmt.body))
+                    }
+                    ast::Stmt::Rule(rule_stmt) => (&rule_stmt.checks, IndexSet::new()),
+                    _ => continue,
+                };
+                let checker = CheckBlockChecker {
+                    pkgpath,
+                    node_ty_map,
+                    required_attrs,
+                };
+                for check_expr in checks {
+                    checker.check(&check_expr.node, handler);
+                }
+            }
+        }
+    }
+}
+
+struct CheckBlockChecker<'a> {
+    pkgpath: &'a str,
+    node_ty_map: &'a NodeTyMap,
+    /// The required attributes without the `?` mark declared in the schema.
+    required_attrs: IndexSet<String>,
+}
+
+impl CheckBlockChecker<'_> {
+    fn check(&self, check_expr: &ast::CheckExpr, handler: &mut Handler) {
+        if let Some(if_cond) = &check_expr.if_cond {
+            if self.eval(if_cond) == Some(false) {
+                add_warning(
+                    handler,
+                    if_cond,
+                    "The check condition is never evaluated, because its if condition is always false",
+                );
+                return;
+            }
+        }
+        match self.eval(&check_expr.test) {
+            Some(true) => add_warning(
+                handler,
+                &check_expr.test,
+                "The check condition is always true",
+            ),
+            Some(false) => add_warning(
+                handler,
+                &check_expr.test,
+                "The check condition is always false",
+            ),
+            None => {}
+        }
+    }
+
+    /// Evaluate the condition, and return None if it can't be proved at compile time.
+    fn eval(&self, expr: &ast::Node<ast::Expr>) -> Option<bool> {
+        match &expr.node {
+            ast::Expr::Paren(paren_expr) => self.eval(&paren_expr.expr),
+            ast::Expr::NameConstantLit(name_constant) => match name_constant.value {
+                ast::NameConstant::True => Some(true),
+                ast::NameConstant::False => Some(false),
+                _ => None,
+            },
+            ast::Expr::Unary(unary_expr) if unary_expr.op == ast::UnaryOp::Not => {
+                self.eval(&unary_expr.operand).map(|v| !v)
+            }
+            ast::Expr::Binary(binary_expr) => {
+                let left = self.eval(&binary_expr.left);
+                let right = self.eval(&binary_expr.right);
+                match binary_expr.op {
+                    ast::BinOp::And => match (left, right) {
+                        (Some(false), _) | (_, Some(false)) => Some(false),
+                        (Some(true), Some(true)) => Some(true),
+                        _ => None,
+                    },
+                    ast::BinOp::Or => match (left, right) {
+                        (Some(true), _) | (_, Some(true)) => Some(true),
+                        (Some(false), Some(false)) => Some(false),
+                        _ => None,
+                    },
+                    _ => None,
+                }
+            }
+            ast::Expr::Compare(compare) if compare.comparators.len() == 1 => {
+                self.eval_compare(&compare.left, &compare.ops[0], &compare.comparators[0])
+            }
+            _ => None,
+        }
+    }
+
+    fn eval_compare(
+        &self,
+        left: &ast::Node<ast::Expr>,
+        op: &ast::CmpOp,
+        right: &ast::Node<ast::Expr>,
+    ) -> Option<bool> {
+        // The comparison of two number literals e.g., `1 > 0`.
+        if let (Some(l), Some(r)) = (get_number(&left.node), get_number(&right.node)) {
+            return compare_numbers(l, op, r);
+        }
+        // The comparison of two literals e.g., `"a" == "b"`.
+        if let (Some(l), Some(r)) = (get_literal(&left.node), get_literal(&right.node)) {
+            return match op {
+                ast::CmpOp::Eq => Some(l == r),
+                ast::CmpOp::NotEq => Some(l != r),
+                _ => None,
+            };
+        }
+        // The length comparison e.g., `len(x) >= 0` and `0 <= len(x)`.
+        if is_len_call(&left.node) {
+            return compare_len(op, get_number(&right.node)?);
+        }
+        if is_len_call(&right.node) {
+            return compare_len(&flip(op)?, get_number(&left.node)?);
+        }
+        // The None comparison of the required attribute e.g., `name != None`.
+        if (self.is_required_attr(left) && is_none(&right.node))
+            || (is_none(&left.node) && self.is_required_attr(right))
+        {
+            return match op {
+                ast::CmpOp::Eq | ast::CmpOp::Is => Some(false),
+                ast::CmpOp::NotEq | ast::CmpOp::IsNot => Some(true),
+                _ => None,
+            };
+        }
+        // The comparison of the attribute of the union literal type e.g., `kind == "a"`.
+        let (subject, other, op) = match (&left.node, &right.node) {
+            (ast::Expr::Identifier(_), _) => (left, right, op.clone()),
+            (_, ast::Expr::Identifier(_)) if matches!(op, ast::CmpOp::Eq | ast::CmpOp::NotEq) => {
+                (right, left, op.clone())
+            }
+            _ => return None,
+        };
+        let all = self.get_literal_values(subject)?;
+        let values: IndexSet<Literal> = match (&op, &other.node) {
+            (ast::CmpOp::Eq | ast::CmpOp::NotEq, _) => IndexSet::from([get_literal(&other.node)?]),
+            (ast::CmpOp::In | ast::CmpOp::NotIn, ast::Expr::List(list_expr)) => list_expr
+                .elts
+                .iter()
+                .map(|elt| get_literal(&elt.node))
+                .collect::<Option<IndexSet<Literal>>>()?,
+            _ => return None,
+        };
+        let matched = if all.is_subset(&values) {
+            Some(true)
+        } else if all.is_disjoint(&values) {
+            Some(false)
+        } else {
+            None
+        };
+        match op {
+            ast::CmpOp::Eq | ast::CmpOp::In => matched,
+            _ => matched.map(|v| !v),
+        }
+    }
+
+    /// Get the literal values of the identifier of the union literal type.
+    fn get_literal_values(&self, expr: &ast::Node<ast::Expr>) -> Option<IndexSet<Literal>> {
+        let key = NodeKey {
+            pkgpath: self.pkgpath.to_string(),
+            id: expr.id.clone(),
+        };
+        self.node_ty_map.get(&key).and_then(get_literals)
+    }
+
+    fn is_required_attr(&self, expr: &ast::Node<ast::Expr>) -> bool {
+        match &expr.node {
+            ast::Expr::Identifier(identifier) if identifier.names.len() == 1 => {
+                self.required_attrs.contains(&identifier.get_name())
+            }
+            _ => false,
+        }
+    }
+}
+
+/// Get the names of the required schema attributes declared in the schema body.
+fn get_required_attrs(body: &[ast::NodeRef<ast::Stmt>]) -> IndexSet<String> {
+    body.iter()
+        .filter_map(|stmt| match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr) if !schema_attr.is_optional => {
+                Some(schema_attr.name.node.clone())
+            }
+            _ => None,
+        })
+        .collect()
+}
+
+fn get_number(expr: &ast::Expr) -> Option<f64> {
+    match expr {
+        ast::Expr::NumberLit(ast::NumberLit {
+            binary_suffix: None,
+            value,
+        }) => Some(match value {
+            ast::NumberLitValue::Int(v) => *v as f64,
+            ast::NumberLitValue::Float(v) => *v,
+        }),
+        ast::Expr::Unary(unary_expr) => match unary_expr.op {
+            ast::UnaryOp::USub => get_number(&unary_expr.operand.node).map(|v| -v),
+            ast::UnaryOp::UAdd => get_number(&unary_expr.operand.node),
+            _ => None,
+        },
+        ast::Expr::Paren(paren_expr) => get_number(&paren_expr.expr.node),
+        _ => None,
+    }
+}
+
+fn compare_numbers(l: f64, op: &ast::CmpOp, r: f64) -> Option<bool> {
+    match op {
+        ast::CmpOp::Eq => Some(l == r),
+        ast::CmpOp::NotEq => Some(l != r),
+        ast::CmpOp::Lt => Some(l < r),
+        ast::CmpOp::LtE => Some(l <= r),
+        ast::CmpOp::Gt => Some(l > r),
+        ast::CmpOp::GtE => Some(l >= r),
+        _ => None,
+    }
+}
+
+/// Compare the length which is non-negative with the number `n` i.e., `len(x) op n`.
+fn compare_len(op: &ast::CmpOp, n: f64) -> Option<bool> {
+    match op {
+        ast::CmpOp::GtE if n <= 0.0 => Some(true),
+        ast::CmpOp::Gt if n < 0.0 => Some(true),
+        ast::CmpOp::NotEq if n < 0.0 => Some(true),
+        ast::CmpOp::Lt if n <= 0.0 => Some(false),
+        ast::CmpOp::LtE if n < 0.0 => Some(false),
+        ast::CmpOp::Eq if n < 0.0 => Some(false),
+        _ => None,
+    }
+}
+
+/// Flip the comparison operator to swap its operands e.g., `0 <= x` to `x >= 0`.
+fn flip(op: &ast::CmpOp) -> Option<ast::CmpOp> {
+    match op {
+        ast::CmpOp::Eq => Some(ast::CmpOp::Eq),
+        ast::CmpOp::NotEq => Some(ast::CmpOp::NotEq),
+        ast::CmpOp::Lt => Some(ast::CmpOp::Gt),
+        ast::CmpOp::LtE => Some(ast::CmpOp::GtE),
+        ast::CmpOp::Gt => Some(ast::CmpOp::Lt),
+        ast::CmpOp::GtE => Some(ast::CmpOp::LtE),
+        _ => None,
+    }
+}
+
+fn is_len_call(expr: &ast::Expr) -> bool {
+    match expr {
+        ast::Expr::Call(call_expr) => {
+            call_expr.args.len() == 1
+                && call_expr.keywords.is_empty()
+                && matches!(&call_expr.func.node, ast::Expr::Identifier(identifier)
+                    if identifier.names.len() == 1 && identifier.get_name() == "len")
+        }
+        _ => false,
+    }
+}
+
+fn is_none(expr: &ast::Expr) -> bool {
+    matches!(
+        expr,
+        ast::Expr::NameConstantLit(ast::NameConstantLit {
+            value: ast::NameConstant::None
+        })
+    )
+}
+
+fn add_warning(handler: &mut Handler, expr: &ast::Node<ast::Expr>, message: &str) {
+    handler.add_warning(
+        WarningKind::ConstantConditionWarning,
+        &[Message {
+            range: expr.get_span_pos(),
+            style: Style::LineAndColumn,
+            message: message.to_string(),
+            note: Some("Consider fixing or removing this condition".to_string()),
+            suggested_replacement: None,
+        }],
+    );
+}
@@ -0,0 +1,158 @@
+//! Check a single expression e.g., a config literal against a named schema type
+//! without executing the program, which is used for the fast form validation.
+
+use std::collections::HashMap;
+use std::sync::{Arc, RwLock};
+
+use kclvm_ast::ast;
+use kclvm_ast::MAIN_PKG;
+use kclvm_error::diagnostic::{Position, Range};
+use kclvm_error::{Diagnostic, Level};
+
+use super::{resolve_program_with_opts, Options};
+
+/// The variable name which the checked expression is assigned to.
+const CHECK_TYPE_VALUE_NAME: &str = "__check_type_value";
+
+/// Check the expression against the schema denoted by its full name e.g., `Person` in
+/// the main package and `pkg.Person` in the package `pkg` of the program, and return
+/// the type errors of the expression.
+///
+/// The expression is resolved in a synthetic module of the schema package named by the
+/// filename of the expression, thus the expression should be parsed with a virtual
+/// filename e.g., `<check_type>` which is different from the program files. The program
+/// itself is not changed.
+pub fn check_type(
+    program: &ast::Program,
+    expr: ast::NodeRef<ast::Expr>,
+    schema_fqn: &str,
+) -> Result<(), Vec<Diagnostic>> {
+    let (pkgpath, name) = match schema_fqn.rsplit_once('.') {
+        Some((pkgpath, name)) => (pkgpath, name),
+        None => (MAIN_PKG, schema_fqn),
+    };
+    let filename = expr.filename.clone();
+    let range: Range = (
+        Position {
+            filename: filename.clone(),
+            line: expr.line,
+            column: Some(expr.column),
+        },
+        Position {
+            filename: filename.clone(),
+            line: expr.end_line,
+            column: Some(expr.end_column),
+        },
+    );
+    if name.is_empty() || !program.pkgs.contains_key(pkgpath) {
+        return Err(vec![Diagnostic::new(
+            Level::Error,
+            &format!("schema '{}' not found", schema_fqn),
+            range,
+        )]);
+    }
+    let mut program = deep_clone_program(program);
+    let pos = (
+        filename.clone(),
+        expr.line,
+        expr.column,
+        expr.end_line,
+        expr.end_column,
+    );
+    let module = ast::Module {
+        filename: filename.clone(),
+        doc: None,
+        body: vec![Box::new(ast::Node::node_with_pos(
+            ast::Stmt::Assign(ast::AssignStmt {
+                targets: vec![Box::new(ast::Node::node_with_pos(
+                    ast::Target {
+                        name: ast::Node::node_with_pos(
+                            CHECK_TYPE_VALUE_NAME.to_string(),
+                            pos.clone(),
+                        ),
+                        paths: vec![],
+                        pkgpath: "".to_string(),
+                    },
+                    pos.clone(),
+                ))],
+                value: expr,
+                ty: Some(Box::new(ast::Node::node_with_pos(
+                    ast::Type::Named(ast::Identifier {
+                        names: vec![ast::Node::node_with_pos(name.to_string(), pos.clone())],
+                        pkgpath: "".to_string(),
+                        ctx: ast::ExprContext::Load,
+                    }),
+                    pos.clone(),
+                ))),
+            }),
+            pos,
+        ))],
+        comments: vec![],
+    };
+    program
+        .modules
+        .insert(filename.clone(), Arc::new(RwLock::new(module)));
+    if let Some(modules) = program.pkgs.get_mut(pkgpath) {
+        modules.push(filename.clone());
+    }
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            merge_program: false,
+            type_erasure: false,
+            ..Default::default()
+        },
+        None,
+    );
+    let is_schema = scope
+        .scope_map
+        .get(pkgpath)
+        .and_then(|pkg_scope| pkg_scope.borrow().elems.get(name).cloned())
+        .map_or(false, |obj| obj.borrow().ty.is_schema());
+    if !is_schema {
+        return Err(vec![Diagnostic::new(
+            Level::Error,
+            &format!("schema '{}' not found", schema_fqn),
+            range,
+        )]);
+    }
+    let errors: Vec<Diagnostic> = scope
+        .handler
+        .diagnostics
+        .into_iter()
+        .filter(|diag| {
+            diag.level == Level::Error
+                && diag
+                    .messages
+                    .iter()
+                    .any(|msg| msg.range.0.filename == filename)
+        })
+        .collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Clone the program with its modules, because the modules are changed during resolving.
+fn deep_clone_program(program: &ast::Program) -> ast::Program {
+    let clone_modules = |modules: &HashMap<String, Arc<RwLock<ast::Module>>>| {
+        modules
+            .iter()
+            .filter_map(|(filename, module)| {
+                module
+                    .read()
+                    .ok()
+                    .map(|module| (filename.clone(), Arc::new(RwLock::new(module.clone()))))
+            })
+            .collect()
+    };
+    ast::Program {
+        root: program.root.clone(),
+        pkgs: program.pkgs.clone(),
+        pkgs_not_imported: program.pkgs_not_imported.clone(),
+        modules: clone_modules(&program.modules),
+        modules_not_imported: clone_modules(&program.modules_not_imported),
+    }
+}
@@ -90,6 +90,13 @@ impl<'ctx> Resolver<'_> {
                                 match schema_ty.get_obj_of_attr(key_name) {
                                     Some(attr_ty_obj) => {
                                         let ty = match &attr_ty_obj.ty.kind {
+                                            TypeKind::Schema(schema_ty)
+                                                if !schema_ty.type_args.is_empty() =>
+                                            {
+                                                Arc::new(Type::schema(
+                                                    self.get_instantiated_schema_ty(schema_ty),
+                                                ))
+                                            }
                                             TypeKind::Schema(schema_ty) => {
                                                 let runtime_type =
                                                     kclvm_runtime::schema_runtime_type(
@@ -301,18 +308,37 @@ impl<'ctx> Resolver<'_> {
     /// Check whether the key of config expr meets the constraints of schema attributes such as final, defined.
     ///
     /// Args:
-    ///     name: the name of key
+    ///     names: the names of key
+    ///     index: the index of the name to check in the names
     ///     key: the ast node of key
     ///     check_rules: the constraints, such as 'check_defined'
     pub(crate) fn check_config_expr_by_key_name(
         &mut self,
-        name: &str,
+        names: &[String],
+        index: usize,
         key: &'ctx ast::NodeRef<ast::Expr>,
     ) {
+        let name = &names[index];
         if !name.is_empty() {
             if let Some(Some(obj)) = self.ctx.config_expr_context.last() {
                 let ty = obj.ty.clone();
-                self.must_check_config_attr(name, &ty, &key.get_span_pos(), None);
+                // The suggested replacement of the key e.g., `metadata.nmae` -> `metadata.name`
+                // and `"nmae"` -> `"name"`, which can be applied as a quick fix.
+                let replace = |sugg: &str| match &key.node {
+                    ast::Expr::StringLit(_) => format!("{:?}", sugg),
+                    _ => {
+                        let mut names = names.to_vec();
+                        names[index] = sugg.to_string();
+                        names.join(".")
+                    }
+                };
+                let replace: Option<&dyn Fn(&str) -> String> =
+                    if matches!(key.node, ast::Expr::Subscript(_)) {
+                        None
+                    } else {
+                        Some(&replace)
+                    };
+                self.must_check_config_attr(name, &ty, &key.get_span_pos(), None, replace);
             }
         }
     }
@@ -347,7 +373,7 @@ impl<'ctx> Resolver<'_> {
         if !key.is_empty() {
             if let Some(Some(obj)) = self.ctx.config_expr_context.last() {
                 let ty = obj.ty.clone();
-                self.must_check_config_attr(key, &ty, value_span, Some(attr_span));
+                self.must_check_config_attr(key, &ty, value_span, Some(attr_span), None);
             }
             let stack_depth = self.switch_config_expr_context_by_name(key);
             if let Some(Some(obj)) = self.ctx.config_expr_context.last() {
@@ -409,8 +435,8 @@ impl<'ctx> Resolver<'_> {
                     _ => return None,
                 };
                 let mut stack_depth = 0;
-                for name in &names {
-                    self.check_config_expr_by_key_name(name, key);
+                for (index, name) in names.iter().enumerate() {
+                    self.check_config_expr_by_key_name(&names, index, key);
                     stack_depth += self.switch_config_expr_context_by_name(name);
                 }
                 let mut val_ty = self.expr(value);
@@ -469,15 +495,20 @@ impl<'ctx> Resolver<'_> {
     }
 
     /// Check config attr has been defined.
+    ///
+    /// When the attribute is not defined, the closest attribute names are suggested, and
+    /// `replace` converts each suggested name into the replacement text of the range, which
+    /// can be applied as a quick fix. The names are the replacements when `replace` is None.
     pub(crate) fn must_check_config_attr(
         &mut self,
         attr: &str,
         ty: &TypeRef,
         range: &Range,
         attr_range: Option<&Range>,
+        replace: Option<&dyn Fn(&str) -> String>,
     ) {
         if let TypeKind::Schema(schema_ty) = &ty.kind {
-            self.check_config_attr(attr, schema_ty, range, attr_range);
+            self.check_config_attr(attr, schema_ty, range, attr_range, replace);
         } else if let TypeKind::Union(types) = &ty.kind {
             let mut schema_names = vec![];
             let mut total_suggs = vec![];
@@ -520,7 +551,7 @@ impl<'ctx> Resolver<'_> {
                         },
                     ),
                     note: None,
-                    suggested_replacement: Some(total_suggs),
+                    suggested_replacement: Some(replace_suggestions(total_suggs, replace)),
                 }];
                 if let Some(attr_range) = attr_range {
                     msgs.push(Message {
@@ -543,7 +574,15 @@ impl<'ctx> Resolver<'_> {
         schema_ty: &SchemaType,
         range: &Range,
         attr_range: Option<&Range>,
+        replace: Option<&dyn Fn(&str) -> String>,
     ) {
+        if !schema_ty.type_args.is_empty() {
+            let schema_ty = self.get_instantiated_schema_ty(schema_ty);
+            self.check_config_attr_without_schema_mapping(
+                attr, &schema_ty, range, attr_range, replace,
+            );
+            return;
+        }
         let runtime_type = kclvm_runtime::schema_runtime_type(&schema_ty.name, &schema_ty.pkgpath);
         match self.ctx.schema_mapping.get(&runtime_type) {
             Some(schema_mapping_ty) => {
@@ -554,10 +593,13 @@ impl<'ctx> Resolver<'_> {
                     &schema_ty_ref,
                     range,
                     attr_range,
+                    replace,
                 );
             }
             None => {
-                self.check_config_attr_without_schema_mapping(attr, schema_ty, range, attr_range);
+                self.check_config_attr_without_schema_mapping(
+                    attr, schema_ty, range, attr_range, replace,
+                );
             }
         };
     }
@@ -568,13 +610,19 @@ impl<'ctx> Resolver<'_> {
         schema_ty: &SchemaType,
         range: &Range,
         attr_range: Option<&Range>,
+        replace: Option<&dyn Fn(&str) -> String>,
     ) {
-        if schema_ty.get_obj_of_attr(attr).is_none()
-            && !schema_ty.is_mixin
-            && schema_ty.index_signature.is_none()
-        {
-            let (suggs, msg) = self.get_config_attr_err_suggestion_from_schema(attr, schema_ty);
-            self.add_config_attr_error(attr, schema_ty, range, attr_range, suggs, msg);
+        match schema_ty.get_obj_of_attr(attr) {
+            Some(obj) => self.check_deprecation(
+                &format!("{}.{}", schema_ty.name, attr),
+                obj.get_deprecation(),
+                range.clone(),
+            ),
+            None if !schema_ty.is_mixin && schema_ty.index_signature.is_none() => {
+                let (suggs, msg) = self.get_config_attr_err_suggestion_from_schema(attr, schema_ty);
+                self.add_config_attr_error(attr, schema_ty, range, attr_range, suggs, msg, replace);
+            }
+            None => {}
         }
         if let Some(index_signature) = &schema_ty.index_signature {
             // Here we need to check whether the key of the index signature is a string literal type or a string literal union types
@@ -584,7 +632,7 @@ impl<'ctx> Resolver<'_> {
                         if name != attr {
                             let (suggs, msg) = self.get_config_attr_err_suggestion(attr, &[name]);
                             self.add_config_attr_error(
-                                attr, schema_ty, range, attr_range, suggs, msg,
+                                attr, schema_ty, range, attr_range, suggs, msg, replace,
                             );
                         }
                     }
@@ -598,7 +646,7 @@ impl<'ctx> Resolver<'_> {
                         if !keys.contains(attr) {
                             let (suggs, msg) = self.get_config_attr_err_suggestion(attr, &keys);
                             self.add_config_attr_error(
-                                attr, schema_ty, range, attr_range, suggs, msg,
+                                attr, schema_ty, range, attr_range, suggs, msg, replace,
                             );
                         }
                     }
@@ -616,6 +664,7 @@ impl<'ctx> Resolver<'_> {
         attr_range: Option<&Range>,
         suggs: Vec<String>,
         msg: String,
+        replace: Option<&dyn Fn(&str) -> String>,
     ) {
         let mut msgs = vec![Message {
             range: range.clone(),
@@ -625,7 +674,7 @@ impl<'ctx> Resolver<'_> {
                 attr, schema_ty.name, msg,
             ),
             note: None,
-            suggested_replacement: Some(suggs),
+            suggested_replacement: Some(replace_suggestions(suggs, replace)),
         }];
         if let Some(attr_range) = attr_range {
             msgs.push(Message {
@@ -645,6 +694,16 @@ impl<'ctx> Resolver<'_> {
         schema_ty: &SchemaType,
         attr: &str,
     ) -> (bool, TypeRef) {
+        if !schema_ty.type_args.is_empty() {
+            let schema_ty = self.get_instantiated_schema_ty(schema_ty);
+            return match schema_ty.get_type_of_attr(attr) {
+                Some(ty) => (true, ty),
+                None => (
+                    schema_ty.is_mixin || schema_ty.index_signature.is_some(),
+                    self.any_ty(),
+                ),
+            };
+        }
         let runtime_type = kclvm_runtime::schema_runtime_type(&schema_ty.name, &schema_ty.pkgpath);
         match self.ctx.schema_mapping.get(&runtime_type) {
             Some(schema_mapping_ty) => {
@@ -878,3 +937,15 @@ impl<'ctx> Resolver<'_> {
         Type::dict_ref_with_attrs(key_ty, val_ty, attrs)
     }
 }
+
+/// Convert the suggested names into the replacement texts with `replace`, and the names
+/// are the replacements themselves when `replace` is None.
+fn replace_suggestions(
+    suggs: Vec<String>,
+    replace: Option<&dyn Fn(&str) -> String>,
+) -> Vec<String> {
+    match replace {
+        Some(replace) => suggs.iter().map(|s| replace(s)).collect(),
+        None => suggs,
+    }
+}
@@ -0,0 +1,329 @@
+//! The exhaustive check pass reports the if/elif chains which match a variable of
+//! a union literal type e.g., `"a" | "b" | "c"` but miss some of its values, and the
+//! branches which can never be reached.
+//!
+//! A chain is checked only when it has at least two conditions, and all of them
+//! match the same identifier with literals, such as `kind == "a"`, `"a" == kind`,
+//! `kind in ["a", "b"]` and `kind == "a" or kind == "b"`.
+
+use std::fmt;
+
+use indexmap::IndexSet;
+use kclvm_ast::pos::GetPos;
+use kclvm_ast::walker::MutSelfWalker;
+use kclvm_ast::{ast, walk_list};
+use kclvm_error::diagnostic::Range;
+use kclvm_error::{Handler, Message, Style, WarningKind};
+
+use super::scope::{NodeKey, NodeTyMap};
+use crate::pass::{PassContext, SemanticPass};
+use crate::ty::{TypeKind, TypeRef};
+
+/// The semantic pass enabled by the `exhaustive_check` option. See [exhaustive_check_pass].
+pub(crate) struct ExhaustiveCheckPass;
+
+impl SemanticPass for ExhaustiveCheckPass {
+    fn name(&self) -> &'static str {
+        "exhaustive-check"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        exhaustive_check_pass(ctx.program, &ctx.scope.node_ty_map.borrow(), handler);
+    }
+}
+
+/// Report the non-exhaustive and unreachable branches of the program into the handler.
+pub fn exhaustive_check_pass(
+    program: &ast::Program,
+    node_ty_map: &NodeTyMap,
+    handler: &mut Handler,
+) {
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    for pkgpath in pkgpaths {
+        for module in &program.pkgs[pkgpath] {
+            let module = match program.get_module(module) {
+                Ok(Some(module)) => module,
+                _ => continue,
+            };
+            ExhaustiveChecker {
+                pkgpath,
+                node_ty_map,
+                handler: &mut *handler,
+            }
+            .walk_module(&module);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub(super) enum Literal {
+    Str(String),
+    Int(i64),
+    Bool(bool),
+}
+
+impl fmt::Display for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Literal::Str(v) => write!(f, "{:?}", v),
+            Literal::Int(v) => write!(f, "{}", v),
+            Literal::Bool(true) => write!(f, "True"),
+            Literal::Bool(false) => write!(f, "False"),
+        }
+    }
+}
+
+/// A condition which matches the subject identifier with the literals.
+struct LiteralMatch<'a> {
+    subject: &'a ast::Node<ast::Expr>,
+    name: String,
+    literals: IndexSet<Literal>,
+}
+
+/// A branch of the if/elif chain.
+struct Branch<'a> {
+    cond: &'a ast::Node<ast::Expr>,
+    matched: Option<LiteralMatch<'a>>,
+}
+
+struct ExhaustiveChecker<'a> {
+    pkgpath: &'a str,
+    node_ty_map: &'a NodeTyMap,
+    handler: &'a mut Handler,
+}
+
+impl ExhaustiveChecker<'_> {
+    /// Check the branches of the if/elif chain and the range of its else branch. The
+    /// else branch of the if expression is required, thus it is never reported.
+    fn check_chain(&mut self, branches: &[Branch], else_range: Option<Range>, else_required: bool) {
+        if branches.len() < 2 || branches.iter().any(|b| b.matched.is_none()) {
+            return;
+        }
+        let first = branches[0].matched.as_ref().unwrap();
+        if branches
+            .iter()
+            .any(|b| b.matched.as_ref().unwrap().name != first.name)
+        {
+            return;
+        }
+        let key = NodeKey {
+            pkgpath: self.pkgpath.to_string(),
+            id: first.subject.id.clone(),
+        };
+        let all = match self.node_ty_map.get(&key).and_then(get_literals) {
+            Some(all) => all,
+            None => return,
+        };
+        let name = &first.name;
+        let mut covered = IndexSet::new();
+        for branch in branches {
+            let matched = branch.matched.as_ref().unwrap();
+            let mut reachable = false;
+            for literal in &matched.literals {
+                if all.contains(literal) && covered.insert(literal.clone()) {
+                    reachable = true;
+                }
+            }
+            if !reachable {
+                let literals = join_literals(matched.literals.iter());
+                self.add_warning(
+                    WarningKind::UnreachableBranchWarning,
+                    branch.cond.get_span_pos(),
+                    format!(
+                        "Unreachable branch, the value {literals} of '{name}' is impossible or already matched"
+                    ),
+                    "Consider removing this branch",
+                );
+            }
+        }
+        let missing: Vec<&Literal> = all.iter().filter(|l| !covered.contains(*l)).collect();
+        match else_range {
+            Some(range) if missing.is_empty() && !else_required => self.add_warning(
+                WarningKind::UnreachableBranchWarning,
+                range,
+                format!("Unreachable else branch, all the values of '{name}' are matched"),
+                "Consider removing the else branch",
+            ),
+            None if !missing.is_empty() => self.add_warning(
+                WarningKind::NonExhaustiveWarning,
+                branches[0].cond.get_span_pos(),
+                format!(
+                    "Non-exhaustive conditions on '{name}', the value {} is not matched",
+                    join_literals(missing.into_iter())
+                ),
+                "Consider adding the missing branches or an else branch",
+            ),
+            _ => {}
+        }
+    }
+
+    fn add_warning(&mut self, kind: WarningKind, range: Range, message: String, note: &str) {
+        self.handler.add_warning(
+            kind,
+            &[Message {
+                range,
+                style: Style::LineAndColumn,
+                message,
+                note: Some(note.to_string()),
+                suggested_replacement: None,
+            }],
+        );
+    }
+}
+
+impl MutSelfWalker for ExhaustiveChecker<'_> {
+    fn walk_if_stmt(&mut self, if_stmt: &ast::IfStmt) {
+        let mut branches = vec![];
+        let mut bodies = vec![];
+        let mut if_stmt = if_stmt;
+        // The elif branch is parsed as the only if statement in the else branch.
+        let orelse = loop {
+            branches.push(Branch {
+                cond: &if_stmt.cond,
+                matched: get_literal_match(&if_stmt.cond),
+            });
+            bodies.push(&if_stmt.body);
+            match if_stmt.orelse.as_slice() {
+                [stmt] => match &stmt.node {
+                    ast::Stmt::If(elif_stmt) => if_stmt = elif_stmt,
+                    _ => break &if_stmt.orelse,
+                },
+                _ => break &if_stmt.orelse,
+            }
+        };
+        let else_range = orelse.first().map(|stmt| stmt.get_span_pos());
+        self.check_chain(&branches, else_range, false);
+        for branch in &branches {
+            self.walk_expr(&branch.cond.node);
+        }
+        for body in bodies {
+            walk_list!(self, walk_stmt, body);
+        }
+        walk_list!(self, walk_stmt, orelse);
+    }
+
+    fn walk_if_expr(&mut self, if_expr: &ast::IfExpr) {
+        let mut branches = vec![];
+        let mut bodies = vec![];
+        let mut if_expr = if_expr;
+        let orelse = loop {
+            branches.push(Branch {
+                cond: &if_expr.cond,
+                matched: get_literal_match(&if_expr.cond),
+            });
+            bodies.push(&if_expr.body);
+            match &if_expr.orelse.node {
+                ast::Expr::If(elif_expr) => if_expr = elif_expr,
+                _ => break &if_expr.orelse,
+            }
+        };
+        self.check_chain(&branches, Some(orelse.get_span_pos()), true);
+        for branch in &branches {
+            self.walk_expr(&branch.cond.node);
+        }
+        for body in bodies {
+            self.walk_expr(&body.node);
+        }
+        self.walk_expr(&orelse.node);
+    }
+}
+
+/// Get the literals of the union literal type, or None if the type is not composed
+/// of the str, int and bool literal types.
+pub(super) fn get_literals(ty: &TypeRef) -> Option<IndexSet<Literal>> {
+    let mut literals = IndexSet::new();
+    let types = match &ty.kind {
+        TypeKind::Union(types) => types.clone(),
+        _ => vec![ty.clone()],
+    };
+    for ty in types {
+        let literal = match &ty.kind {
+            TypeKind::StrLit(v) => Literal::Str(v.clone()),
+            TypeKind::IntLit(v) => Literal::Int(*v),
+            TypeKind::BoolLit(v) => Literal::Bool(*v),
+            _ => return None,
+        };
+        literals.insert(literal);
+    }
+    Some(literals)
+}
+
+pub(super) fn get_literal(expr: &ast::Expr) -> Option<Literal> {
+    match expr {
+        ast::Expr::StringLit(string_lit) => Some(Literal::Str(string_lit.value.clone())),
+        ast::Expr::NumberLit(ast::NumberLit {
+            binary_suffix: None,
+            value: ast::NumberLitValue::Int(v),
+        }) => Some(Literal::Int(*v)),
+        ast::Expr::NameConstantLit(name_constant) => match name_constant.value {
+            ast::NameConstant::True => Some(Literal::Bool(true)),
+            ast::NameConstant::False => Some(Literal::Bool(false)),
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Get the literal match of the condition e.g., `kind == "a"` or `kind in ["a", "b"]`.
+fn get_literal_match(cond: &ast::Node<ast::Expr>) -> Option<LiteralMatch> {
+    match &cond.node {
+        ast::Expr::Paren(paren_expr) => get_literal_match(&paren_expr.expr),
+        ast::Expr::Compare(compare) if compare.comparators.len() == 1 => {
+            let (left, right) = (&compare.left, &compare.comparators[0]);
+            match &compare.ops[0] {
+                ast::CmpOp::Eq => {
+                    let (subject, literal) = match (&left.node, &right.node) {
+                        (ast::Expr::Identifier(_), _) => (left, get_literal(&right.node)?),
+                        (_, ast::Expr::Identifier(_)) => (right, get_literal(&left.node)?),
+                        _ => return None,
+                    };
+                    new_literal_match(subject, IndexSet::from([literal]))
+                }
+                ast::CmpOp::In => match &right.node {
+                    ast::Expr::List(list_expr) => {
+                        let literals = list_expr
+                            .elts
+                            .iter()
+                            .map(|elt| get_literal(&elt.node))
+                            .collect::<Option<IndexSet<Literal>>>()?;
+                        new_literal_match(left, literals)
+                    }
+                    _ => None,
+                },
+                _ => None,
+            }
+        }
+        ast::Expr::Binary(binary_expr) if binary_expr.op == ast::BinOp::Or => {
+            let mut left = get_literal_match(&binary_expr.left)?;
+            let right = get_literal_match(&binary_expr.right)?;
+            if left.name != right.name {
+                return None;
+            }
+            left.literals.extend(right.literals);
+            Some(left)
+        }
+        _ => None,
+    }
+}
+
+fn new_literal_match(
+    subject: &ast::Node<ast::Expr>,
+    literals: IndexSet<Literal>,
+) -> Option<LiteralMatch> {
+    match &subject.node {
+        ast::Expr::Identifier(identifier) => Some(LiteralMatch {
+            subject,
+            name: identifier.get_name(),
+            literals,
+        }),
+        _ => None,
+    }
+}
+
+fn join_literals<'a>(literals: impl Iterator<Item = &'a Literal>) -> String {
+    literals
+        .map(|l| l.to_string())
+        .collect::<Vec<String>>()
+        .join(" | ")
+}
@@ -0,0 +1,170 @@
+use std::sync::Arc;
+
+use indexmap::IndexMap;
+use kclvm_ast::ast;
+
+use crate::resolver::Resolver;
+use crate::ty::{
+    substitute_type_params, sup, DictType, SchemaAttr, SchemaType, Type, TypeInferMethods,
+    TypeKind, TypeRef,
+};
+
+impl<'ctx> Resolver<'_> {
+    /// Enter the type parameters of the generic schema and return the previous ones. The
+    /// type parameters are denoted by the named types when `placeholder` is true, which
+    /// are used to build the generic attribute types, otherwise they are the any types.
+    pub(crate) fn enter_type_params(
+        &mut self,
+        schema_stmt: &'ctx ast::SchemaStmt,
+        placeholder: bool,
+    ) -> IndexMap<String, TypeRef> {
+        let type_params = schema_stmt
+            .type_params
+            .iter()
+            .map(|type_param| {
+                let ty = if placeholder {
+                    Arc::new(Type::named(&type_param.node))
+                } else {
+                    self.any_ty()
+                };
+                (type_param.node.clone(), ty)
+            })
+            .collect();
+        std::mem::replace(&mut self.ctx.type_params, type_params)
+    }
+
+    /// Whether the unresolved type references the type parameters of the current generic schema.
+    pub(crate) fn contains_type_params(&self, ty: &TypeRef) -> bool {
+        if self.ctx.type_params.is_empty() {
+            return false;
+        }
+        let type_params: Vec<String> = self.ctx.type_params.keys().cloned().collect();
+        substitute_type_params(ty, &type_params, &[]).kind != ty.kind
+    }
+
+    /// Take the generic attribute types which reference the type parameters out of the
+    /// schema attributes, and replace the type parameters of the attributes with the any type.
+    pub(crate) fn take_generic_attrs(
+        &self,
+        attrs: &mut IndexMap<String, SchemaAttr>,
+        type_params: &[String],
+    ) -> IndexMap<String, TypeRef> {
+        let mut generic_attrs = IndexMap::new();
+        if type_params.is_empty() {
+            return generic_attrs;
+        }
+        for (name, attr) in attrs.iter_mut() {
+            let ty = substitute_type_params(&attr.ty, type_params, &[]);
+            if ty.kind != attr.ty.kind {
+                generic_attrs.insert(name.clone(), std::mem::replace(&mut attr.ty, ty));
+            }
+        }
+        generic_attrs
+    }
+
+    /// Get the latest generic schema definition of the schema type, because the schema
+    /// types referenced before their definitions are resolved have no attributes.
+    pub(crate) fn get_generic_schema_ty(&self, schema_ty: &SchemaType) -> SchemaType {
+        let runtime_type = kclvm_runtime::schema_runtime_type(&schema_ty.name, &schema_ty.pkgpath);
+        match self.ctx.schema_mapping.get(&runtime_type) {
+            Some(schema_mapping_ty) => schema_mapping_ty.borrow().clone(),
+            None => schema_ty.clone(),
+        }
+    }
+
+    /// Get the schema type instantiated with its type arguments from the latest definition.
+    pub(crate) fn get_instantiated_schema_ty(&self, schema_ty: &SchemaType) -> SchemaType {
+        let mut instantiated_ty = self
+            .get_generic_schema_ty(schema_ty)
+            .instantiate(&schema_ty.type_args);
+        instantiated_ty.is_instance = schema_ty.is_instance;
+        instantiated_ty
+    }
+
+    /// Infer the type arguments of the generic schema from the resolved config entry
+    /// values e.g., `Box {value = "s"}` is inferred to `Box<str>`. The type parameters
+    /// which can't be inferred are the any type.
+    pub(crate) fn infer_type_args(
+        &mut self,
+        schema_ty: &SchemaType,
+        config: &'ctx ast::NodeRef<ast::Expr>,
+    ) -> Vec<TypeRef> {
+        let schema_ty = self.get_generic_schema_ty(schema_ty);
+        let mut bindings: IndexMap<String, TypeRef> = IndexMap::new();
+        if let ast::Expr::Config(config_expr) = &config.node {
+            for item in &config_expr.items {
+                let name = match item.node.key.as_ref().map(|key| &key.node) {
+                    Some(ast::Expr::Identifier(identifier)) if identifier.names.len() == 1 => {
+                        identifier.get_name()
+                    }
+                    Some(ast::Expr::StringLit(string_lit)) => string_lit.value.clone(),
+                    _ => continue,
+                };
+                let (generic_ty, value_ty) = match (
+                    schema_ty.generic_attrs.get(&name),
+                    self.node_ty_map
+                        .borrow()
+                        .get(&self.get_node_key(item.node.value.id.clone())),
+                ) {
+                    (Some(generic_ty), Some(value_ty)) => (generic_ty.clone(), value_ty.clone()),
+                    _ => continue,
+                };
+                unify(
+                    &generic_ty,
+                    &value_ty,
+                    &schema_ty.type_params,
+                    &mut bindings,
+                );
+            }
+        }
+        schema_ty
+            .type_params
+            .iter()
+            .map(|param| match bindings.get(param) {
+                Some(ty) => self.ctx.ty_ctx.infer_to_variable_type(ty.clone()),
+                None => self.any_ty(),
+            })
+            .collect()
+    }
+}
+
+/// Bind the type parameters in the generic type to the types at the same positions of
+/// the value type, and the conflicting types are bound to their supremum.
+fn unify(
+    generic_ty: &TypeRef,
+    value_ty: &TypeRef,
+    type_params: &[String],
+    bindings: &mut IndexMap<String, TypeRef>,
+) {
+    match (&generic_ty.kind, &value_ty.kind) {
+        (TypeKind::Named(name), _) if type_params.contains(name) => {
+            let ty = match bindings.get(name) {
+                Some(ty) => sup(&[ty.clone(), value_ty.clone()]),
+                None => value_ty.clone(),
+            };
+            bindings.insert(name.clone(), ty);
+        }
+        (TypeKind::List(item_ty), TypeKind::List(value_item_ty)) => {
+            unify(item_ty, value_item_ty, type_params, bindings)
+        }
+        (
+            TypeKind::Dict(DictType { key_ty, val_ty, .. }),
+            TypeKind::Dict(DictType {
+                key_ty: value_key_ty,
+                val_ty: value_val_ty,
+                ..
+            }),
+        ) => {
+            unify(key_ty, value_key_ty, type_params, bindings);
+            unify(val_ty, value_val_ty, type_params, bindings);
+        }
+        (TypeKind::Schema(schema_ty), TypeKind::Schema(value_schema_ty))
+            if schema_ty.full_ty_str() == value_schema_ty.full_ty_str() =>
+        {
+            for (arg, value_arg) in schema_ty.type_args.iter().zip(&value_schema_ty.type_args) {
+                unify(arg, value_arg, type_params, bindings);
+            }
+        }
+        _ => {}
+    }
+}
@@ -113,6 +113,16 @@ impl<'ctx> Resolver<'_> {
                             }),
                             index_signature: None,
                             decorators: vec![],
+                            type_params: match &stmt.node {
+                                ast::Stmt::Schema(schema_stmt) => schema_stmt
+                                    .type_params
+                                    .iter()
+                                    .map(|type_param| type_param.node.clone())
+                                    .collect(),
+                                _ => vec![],
+                            },
+                            type_args: vec![],
+                            generic_attrs: IndexMap::default(),
                         };
                         self.insert_object(
                             name,
@@ -508,6 +518,18 @@ impl<'ctx> Resolver<'_> {
                 schema_stmt.name.get_span_pos(),
             );
         }
+        let mut type_params: Vec<String> = vec![];
+        for type_param in &schema_stmt.type_params {
+            if type_params.contains(&type_param.node) {
+                self.handler.add_compile_error(
+                    &format!("duplicate type parameter '{}'", type_param.node),
+                    type_param.get_span_pos(),
+                );
+            } else {
+                type_params.push(type_param.node.clone());
+            }
+        }
+        let prev_type_params = self.enter_type_params(schema_stmt, false);
         let parent_name = parent_ty
             .as_ref()
             .map_or("".to_string(), |ty| ty.name.clone());
@@ -585,8 +607,10 @@ impl<'ctx> Resolver<'_> {
                 .map(|doc| doc.node.clone())
                 .unwrap_or_default(),
         );
+        // The type parameters in the attribute types are kept to instantiate the generic schema.
+        self.enter_type_params(schema_stmt, true);
         for stmt in &schema_stmt.body {
-            let (name, ty, is_optional, default, decorators, range) = match &stmt.node {
+            let (name, ty, is_optional, default, decorators, range, attr_doc) = match &stmt.node {
                 ast::Stmt::Unification(unification_stmt) => {
                     let name = unification_stmt.value.node.name.node.get_name();
                     let ty = self.parse_ty_str_with_scope(&name, stmt.get_span_pos());
@@ -603,6 +627,7 @@ impl<'ctx> Resolver<'_> {
                         Some(default),
                         vec![],
                         stmt.get_span_pos(),
+                        "",
                     )
                 }
                 ast::Stmt::SchemaAttr(schema_attr) => {
@@ -630,6 +655,7 @@ impl<'ctx> Resolver<'_> {
                         default,
                         decorators,
                         stmt.get_span_pos(),
+                        schema_attr.doc.as_str(),
                     )
                 }
                 _ => continue,
@@ -640,13 +666,19 @@ impl<'ctx> Resolver<'_> {
             };
             if !attr_obj_map.contains_key(&name) {
                 let existed_attr = parent_ty.as_ref().and_then(|ty| ty.get_obj_of_attr(&name));
-                let doc_str = parsed_doc.attrs.iter().find_map(|attr| {
-                    if attr.name == name {
-                        Some(attr.desc.join("\n"))
-                    } else {
-                        None
-                    }
-                });
+                // The attribute description in the schema docstring is preferred to the
+                // comments preceding the attribute.
+                let doc_str = parsed_doc
+                    .attrs
+                    .iter()
+                    .find_map(|attr| {
+                        if attr.name == name {
+                            Some(attr.desc.join("\n"))
+                        } else {
+                            None
+                        }
+                    })
+                    .or_else(|| (!attr_doc.is_empty()).then(|| attr_doc.to_string()));
                 attr_obj_map.insert(
                     name.clone(),
                     SchemaAttr {
@@ -699,6 +731,8 @@ impl<'ctx> Resolver<'_> {
                 }
             }
         }
+        self.enter_type_params(schema_stmt, false);
+        let generic_attrs = self.take_generic_attrs(&mut attr_obj_map, &type_params);
         // Mixin types
         let mut mixin_types: Vec<SchemaType> = vec![];
         for mixin in &schema_stmt.mixins {
@@ -825,6 +859,7 @@ impl<'ctx> Resolver<'_> {
             DecoratorTarget::Schema,
             &schema_stmt.name.node,
         );
+        self.ctx.type_params = prev_type_params;
         let schema_ty = SchemaType {
             name: schema_stmt.name.node.clone(),
             pkgpath: self.ctx.pkgpath.clone(),
@@ -849,6 +884,9 @@ impl<'ctx> Resolver<'_> {
             }),
             index_signature,
             decorators,
+            type_params,
+            type_args: vec![],
+            generic_attrs,
         };
         let schema_runtime_ty = kclvm_runtime::schema_runtime_type(name, &self.ctx.pkgpath);
         self.ctx
@@ -1001,6 +1039,9 @@ impl<'ctx> Resolver<'_> {
             }),
             index_signature,
             decorators,
+            type_params: vec![],
+            type_args: vec![],
+            generic_attrs: IndexMap::default(),
         }
     }
 }
@@ -1,19 +1,26 @@
 mod arg;
 mod attr;
 mod calculation;
+mod check_block;
+pub mod check_type;
 mod config;
 pub mod doc;
+mod exhaustive;
 mod format;
+mod generic;
 pub mod global;
 mod import;
 mod r#loop;
+mod narrow;
 mod node;
 mod para;
 mod schema;
+pub mod schema_graph;
 pub mod scope;
 pub(crate) mod ty;
 mod ty_alias;
 mod ty_erasure;
+mod unused;
 mod var;
 
 #[cfg(test)]
@@ -24,15 +31,21 @@ use kclvm_error::diagnostic::Range;
 use std::sync::Arc;
 use std::{cell::RefCell, rc::Rc};
 
-use crate::lint::{CombinedLintPass, Linter};
+use crate::lint::{ImportPosition, ReImport, UnusedImport};
+use crate::pass::{PassContext, PassManager};
 use crate::pre_process::pre_process_program;
+use crate::resolver::check_block::CheckBlockPass;
+use crate::resolver::exhaustive::ExhaustiveCheckPass;
+use crate::resolver::narrow::NarrowedFrame;
 use crate::resolver::scope::ScopeObject;
 use crate::resolver::ty_alias::type_alias_pass;
 use crate::resolver::ty_erasure::type_func_erasure_pass;
-use crate::ty::TypeContext;
+use crate::resolver::unused::UnusedSymbolPass;
+use crate::ty::{TypeContext, TypeRef};
 use crate::{resolver::scope::Scope, ty::SchemaType};
 use kclvm_ast::ast::Program;
 use kclvm_error::*;
+use kclvm_utils::cancel::{CancellationToken, Cancelled};
 
 use self::scope::{builtin_scope, KCLScopeCache, NodeTyMap, ProgramScope};
 
@@ -48,7 +61,6 @@ pub struct Resolver<'ctx> {
     pub ctx: Context,
     pub options: Options,
     pub handler: Handler,
-    pub linter: Linter<CombinedLintPass>,
 }
 
 impl<'ctx> Resolver<'ctx> {
@@ -65,12 +77,14 @@ impl<'ctx> Resolver<'ctx> {
             ctx: Context::default(),
             options,
             handler: Handler::default(),
-            linter: Linter::<CombinedLintPass>::new(),
         }
     }
 
     /// The check main function.
     pub(crate) fn check(&mut self, pkgpath: &str) {
+        if self.options.cancel_token.is_cancelled() {
+            return;
+        }
         self.check_import(pkgpath);
         self.init_global_types();
         match self
@@ -93,9 +107,6 @@ impl<'ctx> Resolver<'ctx> {
                     for stmt in &module.body {
                         self.stmt(&stmt);
                     }
-                    if self.options.lint_check {
-                        self.lint_check_module(&module);
-                    }
                 }
             }
             None => {}
@@ -104,11 +115,7 @@ impl<'ctx> Resolver<'ctx> {
 
     pub(crate) fn check_and_lint_all_pkgs(&mut self) -> ProgramScope {
         self.check(kclvm_ast::MAIN_PKG);
-        self.lint_check_scope_map();
-        let mut handler = self.handler.clone();
-        for diag in &self.linter.handler.diagnostics {
-            handler.diagnostics.insert(diag.clone());
-        }
+        let handler = self.handler.clone();
 
         for pkg in self.program.pkgs_not_imported.keys() {
             if !self.scope_map.contains_key(pkg) {
@@ -164,17 +171,40 @@ pub struct Context {
     pub type_alias_mapping: IndexMap<String, IndexMap<String, String>>,
     /// invalid pkg scope, remove when after resolve
     pub invalid_pkg_scope: IndexSet<String>,
+    /// The variable types narrowed by the type guards of the current branches.
+    pub narrowed_tys: Vec<NarrowedFrame>,
+    /// The types of the type parameters of the current generic schema.
+    pub type_params: IndexMap<String, TypeRef>,
 }
 
 /// Resolve options.
 /// - lint_check: whether to run lint passes
 /// - resolve_val: whether to resolve and print their AST to value for some nodes.
+/// - cancel_token: the token checked between packages to abandon the resolving.
+/// - unused_check: whether to report the unused private variables, schema definitions
+///   and lambda parameters as warnings.
+/// - exhaustive_check: whether to report the if/elif chains on union literal types with the
+///   missing or unreachable branches as warnings.
+/// - check_block_check: whether to report the conditions in the check blocks which are always
+///   true or always false as warnings.
+/// - passes: the custom semantic passes run after the builtin passes above.
+/// - warnings_as_errors: the warning kinds reported as errors.
+/// - file_hashes: the sha256 hashes of the file sources keyed by the filenames e.g., the
+///   `file_hashes` of the parse session, which are used by the scope cache to find the
+///   changed modules. The modules without the hashes are fingerprinted by the file metadata.
 #[derive(Clone, Debug)]
 pub struct Options {
     pub lint_check: bool,
     pub resolve_val: bool,
     pub merge_program: bool,
     pub type_erasure: bool,
+    pub cancel_token: CancellationToken,
+    pub unused_check: bool,
+    pub exhaustive_check: bool,
+    pub check_block_check: bool,
+    pub passes: PassManager,
+    pub warnings_as_errors: Vec<WarningKind>,
+    pub file_hashes: IndexMap<String, String>,
 }
 
 impl Default for Options {
@@ -184,6 +214,13 @@ impl Default for Options {
             resolve_val: false,
             merge_program: true,
             type_erasure: true,
+            cancel_token: Default::default(),
+            unused_check: false,
+            exhaustive_check: false,
+            check_block_check: false,
+            passes: PassManager::default(),
+            warnings_as_errors: vec![],
+            file_hashes: IndexMap::default(),
         }
     }
 }
@@ -195,18 +232,35 @@ pub fn resolve_program(program: &mut Program) -> ProgramScope {
 }
 
 /// Resolve program with options. See [Options]
+///
+/// Note that an empty program scope is returned when the resolving is cancelled
+/// by `opts.cancel_token`, use [try_resolve_program_with_opts] to get the error.
+#[inline]
 pub fn resolve_program_with_opts(
     program: &mut Program,
     opts: Options,
     cached_scope: Option<KCLScopeCache>,
 ) -> ProgramScope {
+    try_resolve_program_with_opts(program, opts, cached_scope).unwrap_or_default()
+}
+
+/// Resolve program with options. See [Options]
+///
+/// Returns [Cancelled] if `opts.cancel_token` is cancelled during resolving, and
+/// the scope cache is cleared because the resolving result is incomplete.
+pub fn try_resolve_program_with_opts(
+    program: &mut Program,
+    opts: Options,
+    cached_scope: Option<KCLScopeCache>,
+) -> Result<ProgramScope, Cancelled> {
+    opts.cancel_token.check()?;
     pre_process_program(program, &opts);
     let mut resolver = Resolver::new(program, opts.clone());
     resolver.resolve_import();
     if let Some(cached_scope) = cached_scope.as_ref() {
         if let Some(mut cached_scope) = cached_scope.try_write() {
             cached_scope.invalidate_pkgs.clear();
-            cached_scope.update(program);
+            cached_scope.update(program, &opts.file_hashes);
             resolver.scope_map = cached_scope.scope_map.clone();
             resolver.node_ty_map = Rc::new(RefCell::new(cached_scope.node_ty_map.clone()));
             resolver.ctx.schema_mapping = cached_scope.schema_mapping.clone();
@@ -218,11 +272,43 @@ pub fn resolve_program_with_opts(
             }
         }
     }
-    let scope = resolver.check_and_lint_all_pkgs();
+    let mut scope = resolver.check_and_lint_all_pkgs();
+    if opts.cancel_token.is_cancelled() {
+        if let Some(cached_scope) = cached_scope.as_ref() {
+            if let Some(mut cached_scope) = cached_scope.try_write() {
+                cached_scope.clear();
+            }
+        }
+        return Err(Cancelled);
+    }
+    let mut passes = PassManager::default();
+    if opts.lint_check {
+        passes.register(ImportPosition);
+        passes.register(ReImport);
+        passes.register(UnusedImport);
+    }
+    if opts.unused_check {
+        passes.register(UnusedSymbolPass);
+    }
+    if opts.exhaustive_check {
+        passes.register(ExhaustiveCheckPass);
+    }
+    if opts.check_block_check {
+        passes.register(CheckBlockPass);
+    }
+    passes.extend(&opts.passes);
+    if !passes.is_empty() {
+        let mut handler = Handler::default();
+        passes.run(&PassContext::new(program, &scope), &mut handler);
+        scope.handler.diagnostics.extend(handler.diagnostics);
+    }
+    scope
+        .handler
+        .promote_warnings_to_errors(&opts.warnings_as_errors);
 
     if let Some(cached_scope) = cached_scope.as_ref() {
         if let Some(mut cached_scope) = cached_scope.try_write() {
-            cached_scope.update(program);
+            cached_scope.update(program, &opts.file_hashes);
             cached_scope.scope_map = scope.scope_map.clone();
             cached_scope.node_ty_map = scope.node_ty_map.borrow().clone();
             cached_scope.scope_map.remove(kclvm_ast::MAIN_PKG);
@@ -240,5 +326,5 @@ pub fn resolve_program_with_opts(
         // Erase types with their type alias
         type_alias_pass(program, type_alias_mapping);
     }
-    scope
+    Ok(scope)
 }
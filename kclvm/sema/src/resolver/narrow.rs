@@ -0,0 +1,305 @@
+use std::sync::Arc;
+
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::ast;
+
+use crate::resolver::Resolver;
+use crate::ty::{subsume, Type, TypeKind, TypeRef};
+
+/// The frame of the narrowed variable types, which is pushed when entering a branch or
+/// a scope and popped by [Resolver::leave_narrowed_branch].
+#[derive(Debug)]
+pub enum NarrowedFrame {
+    /// The variable types narrowed by the type guards of the condition of a branch.
+    Branch(IndexMap<String, TypeRef>),
+    /// The names defined in a scope e.g., the lambda parameters and the comprehension
+    /// variables, which hide the narrowed types of the same names in the outer branches.
+    Masked(IndexSet<String>),
+    /// The schema and rule scopes, which hide all the narrowed types of the outer branches.
+    Isolated,
+}
+
+/// A type guard on the variable in the condition expression.
+enum TypeGuard {
+    /// `typeof(x) == "str"`
+    TypeOf(String),
+    /// `x == "a"`, `x is None` and `x in ["a", "b"]`
+    Literals(Vec<TypeRef>),
+}
+
+impl TypeGuard {
+    /// Whether the value of the type may satisfy the guard.
+    fn may_match(&self, ty: &TypeRef) -> bool {
+        match self {
+            TypeGuard::TypeOf(name) => type_of_str(ty).map_or(true, |n| &n == name),
+            TypeGuard::Literals(literals) => literals.iter().any(|literal| {
+                if ty.is_any() {
+                    true
+                } else if literal.is_none() || ty.is_literal() {
+                    literal.kind == ty.kind
+                } else {
+                    subsume(literal.clone(), ty.clone(), false)
+                }
+            }),
+        }
+    }
+
+    /// Whether the value of the type must satisfy the guard.
+    fn must_match(&self, ty: &TypeRef) -> bool {
+        match self {
+            TypeGuard::TypeOf(name) => type_of_str(ty).map_or(false, |n| &n == name),
+            TypeGuard::Literals(literals) => literals
+                .iter()
+                .any(|literal| (literal.is_none() || ty.is_literal()) && literal.kind == ty.kind),
+        }
+    }
+}
+
+impl<'ctx> Resolver<'ctx> {
+    /// Enter the branch of the resolved condition expression, in which the union types of
+    /// the variables are narrowed by the type guards e.g., the type of `x: int | str` is
+    /// narrowed to `str` in the branch of `if typeof(x) == "str"` and to `int` in its else
+    /// branch. Call [Resolver::leave_narrowed_branch] when the branch is resolved.
+    pub(crate) fn enter_narrowed_branch(
+        &mut self,
+        cond: &'ctx ast::NodeRef<ast::Expr>,
+        positive: bool,
+    ) {
+        let mut guards = vec![];
+        collect_type_guards(cond, positive, &mut guards);
+        let mut narrowed_tys: IndexMap<String, TypeRef> = IndexMap::new();
+        for (name, subject, guard, positive) in guards {
+            let ty = match narrowed_tys.get(&name) {
+                Some(ty) => ty.clone(),
+                None => match self
+                    .node_ty_map
+                    .borrow()
+                    .get(&self.get_node_key(subject.id.clone()))
+                {
+                    Some(ty) => ty.clone(),
+                    None => continue,
+                },
+            };
+            let types = if ty.is_union() {
+                ty.union_types()
+            } else {
+                vec![ty.clone()]
+            };
+            let narrowed: Vec<TypeRef> = types
+                .iter()
+                .filter(|ty| {
+                    if positive {
+                        guard.may_match(ty)
+                    } else {
+                        !guard.must_match(ty)
+                    }
+                })
+                .cloned()
+                .collect();
+            if !narrowed.is_empty() && narrowed.len() < types.len() {
+                let narrowed_ty = if narrowed.len() == 1 {
+                    narrowed[0].clone()
+                } else {
+                    Arc::new(Type::union(&narrowed))
+                };
+                narrowed_tys.insert(name, narrowed_ty);
+            }
+        }
+        self.ctx
+            .narrowed_tys
+            .push(NarrowedFrame::Branch(narrowed_tys));
+    }
+
+    /// Enter the scope in which the narrowed types of the outer branches are hidden, that
+    /// is the variables of `names` or all the variables if `names` is None. Call
+    /// [Resolver::leave_narrowed_branch] when the scope is resolved.
+    #[inline]
+    pub(crate) fn enter_narrowed_scope(&mut self, names: Option<IndexSet<String>>) {
+        self.ctx.narrowed_tys.push(match names {
+            Some(names) => NarrowedFrame::Masked(names),
+            None => NarrowedFrame::Isolated,
+        });
+    }
+
+    /// Leave the branch or the scope entered by [Resolver::enter_narrowed_branch] and
+    /// [Resolver::enter_narrowed_scope].
+    #[inline]
+    pub(crate) fn leave_narrowed_branch(&mut self) {
+        self.ctx.narrowed_tys.pop();
+    }
+
+    /// Find the narrowed type of the variable e.g., `x` and `x.y` in the current branches.
+    pub(crate) fn find_narrowed_type(&self, name: &str) -> Option<TypeRef> {
+        let root = name.split('.').next().unwrap_or(name);
+        for frame in self.ctx.narrowed_tys.iter().rev() {
+            match frame {
+                NarrowedFrame::Branch(narrowed_tys) => {
+                    if let Some(ty) = narrowed_tys.get(name) {
+                        return Some(ty.clone());
+                    }
+                }
+                NarrowedFrame::Masked(names) if names.contains(root) => return None,
+                NarrowedFrame::Masked(_) => {}
+                NarrowedFrame::Isolated => return None,
+            }
+        }
+        None
+    }
+
+    /// Drop the narrowed types of the variable and its attributes when it is assigned in
+    /// the branches, e.g., `x` is not narrowed anymore after `x = "s"`.
+    pub(crate) fn invalidate_narrowed_type(&mut self, name: &str) {
+        let prefix = format!("{}.", name);
+        for frame in self.ctx.narrowed_tys.iter_mut().rev() {
+            match frame {
+                NarrowedFrame::Branch(narrowed_tys) => {
+                    narrowed_tys.retain(|key, _| key != name && !key.starts_with(&prefix))
+                }
+                NarrowedFrame::Masked(names) if names.contains(name) => return,
+                NarrowedFrame::Masked(_) => {}
+                NarrowedFrame::Isolated => return,
+            }
+        }
+    }
+}
+
+/// Get the names of the loop variables e.g., the targets of the comprehension clauses.
+pub(crate) fn loop_variable_names<'a>(
+    targets: impl IntoIterator<Item = &'a ast::NodeRef<ast::Identifier>>,
+) -> IndexSet<String> {
+    targets
+        .into_iter()
+        .filter_map(|target| target.node.names.first().map(|name| name.node.clone()))
+        .collect()
+}
+
+/// Collect the type guards which hold in the branch that the condition is `positive`.
+fn collect_type_guards<'a>(
+    cond: &'a ast::NodeRef<ast::Expr>,
+    positive: bool,
+    guards: &mut Vec<(String, &'a ast::NodeRef<ast::Expr>, TypeGuard, bool)>,
+) {
+    match &cond.node {
+        ast::Expr::Paren(paren_expr) => collect_type_guards(&paren_expr.expr, positive, guards),
+        ast::Expr::Unary(unary_expr) if unary_expr.op == ast::UnaryOp::Not => {
+            collect_type_guards(&unary_expr.operand, !positive, guards)
+        }
+        // Both sides of `a and b` hold in the true branch, and both sides of `a or b` do
+        // not hold in the false branch.
+        ast::Expr::Binary(binary_expr)
+            if (binary_expr.op == ast::BinOp::And && positive)
+                || (binary_expr.op == ast::BinOp::Or && !positive) =>
+        {
+            collect_type_guards(&binary_expr.left, positive, guards);
+            collect_type_guards(&binary_expr.right, positive, guards);
+        }
+        ast::Expr::Compare(compare) if compare.ops.len() == 1 => {
+            let (left, right) = (&compare.left, &compare.comparators[0]);
+            let (op_positive, is_eq) = match &compare.ops[0] {
+                ast::CmpOp::Eq | ast::CmpOp::Is => (positive, true),
+                ast::CmpOp::NotEq | ast::CmpOp::IsNot => (!positive, true),
+                ast::CmpOp::In => (positive, false),
+                ast::CmpOp::NotIn => (!positive, false),
+                _ => return,
+            };
+            if is_eq {
+                for (subject, value) in [(left, right), (right, left)] {
+                    if let Some((name, subject)) = get_typeof_subject(subject) {
+                        if let ast::Expr::StringLit(string_lit) = &value.node {
+                            let guard = TypeGuard::TypeOf(string_lit.value.clone());
+                            guards.push((name, subject, guard, op_positive));
+                            return;
+                        }
+                    }
+                    if let (Some(name), Some(literal)) =
+                        (get_subject_name(subject), get_literal_ty(&value.node))
+                    {
+                        let guard = TypeGuard::Literals(vec![literal]);
+                        guards.push((name, subject, guard, op_positive));
+                        return;
+                    }
+                }
+            } else if let (Some(name), ast::Expr::List(list_expr)) =
+                (get_subject_name(left), &right.node)
+            {
+                let literals: Option<Vec<TypeRef>> = list_expr
+                    .elts
+                    .iter()
+                    .map(|elt| get_literal_ty(&elt.node))
+                    .collect();
+                if let Some(literals) = literals {
+                    guards.push((name, left, TypeGuard::Literals(literals), op_positive));
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Get the variable name of the identifier expression e.g., `x` and `x.y`.
+fn get_subject_name(expr: &ast::NodeRef<ast::Expr>) -> Option<String> {
+    match &expr.node {
+        ast::Expr::Identifier(identifier) if identifier.pkgpath.is_empty() => {
+            Some(identifier.get_name())
+        }
+        _ => None,
+    }
+}
+
+/// Get the variable of the `typeof(x)` call expression.
+fn get_typeof_subject(
+    expr: &ast::NodeRef<ast::Expr>,
+) -> Option<(String, &ast::NodeRef<ast::Expr>)> {
+    match &expr.node {
+        ast::Expr::Call(call_expr)
+            if call_expr.args.len() == 1 && call_expr.keywords.is_empty() =>
+        {
+            match &call_expr.func.node {
+                ast::Expr::Identifier(func) if func.get_name() == "typeof" => {
+                    let subject = &call_expr.args[0];
+                    get_subject_name(subject).map(|name| (name, subject))
+                }
+                _ => None,
+            }
+        }
+        _ => None,
+    }
+}
+
+fn get_literal_ty(expr: &ast::Expr) -> Option<TypeRef> {
+    let ty = match expr {
+        ast::Expr::StringLit(string_lit) => Type::str_lit(&string_lit.value),
+        ast::Expr::NumberLit(ast::NumberLit {
+            binary_suffix: None,
+            value,
+        }) => match value {
+            ast::NumberLitValue::Int(v) => Type::int_lit(*v),
+            ast::NumberLitValue::Float(v) => Type::float_lit(*v),
+        },
+        ast::Expr::NameConstantLit(name_constant) => match name_constant.value {
+            ast::NameConstant::True => Type::bool_lit(true),
+            ast::NameConstant::False => Type::bool_lit(false),
+            ast::NameConstant::None => Type::NONE,
+            ast::NameConstant::Undefined => return None,
+        },
+        _ => return None,
+    };
+    Some(Arc::new(ty))
+}
+
+/// The result of the builtin function `typeof` for the values of the type, or None
+/// if it is unknown.
+fn type_of_str(ty: &TypeRef) -> Option<String> {
+    let name = match &ty.kind {
+        TypeKind::None => "None",
+        TypeKind::Bool | TypeKind::BoolLit(_) => "bool",
+        TypeKind::Int | TypeKind::IntLit(_) => "int",
+        TypeKind::Float | TypeKind::FloatLit(_) => "float",
+        TypeKind::Str | TypeKind::StrLit(_) => "str",
+        TypeKind::List(_) => "list",
+        TypeKind::Dict(_) => "dict",
+        TypeKind::Schema(schema_ty) => return Some(schema_ty.name.clone()),
+        _ => return None,
+    };
+    Some(name.to_string())
+}
@@ -14,6 +14,7 @@ use crate::ty::{
 
 use super::doc::extract_doc_from_body;
 use super::format::VALID_FORMAT_SPEC_SET;
+use super::narrow::loop_variable_names;
 use super::scope::{ScopeKind, ScopeObject, ScopeObjectKind};
 use super::ty::ty_str_replace_pkgpath;
 use super::Resolver;
@@ -80,6 +81,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         if !ty.is_any() && expected_ty.is_any() {
             self.set_infer_type_to_scope(&names[0].node, ty, &names[0]);
         }
+        self.invalidate_narrowed_type(&names[0].node);
         expected_ty
     }
 
@@ -226,6 +228,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                     upgrade_schema_type.clone(),
                 );
             }
+            self.invalidate_narrowed_type(name);
         }
         value_ty
     }
@@ -283,6 +286,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             None,
         );
         self.ctx.l_value = false;
+        self.invalidate_narrowed_type(name);
         new_target_ty
     }
 
@@ -297,8 +301,12 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
 
     fn walk_if_stmt(&mut self, if_stmt: &'ctx ast::IfStmt) -> Self::Result {
         self.expr(&if_stmt.cond);
+        self.enter_narrowed_branch(&if_stmt.cond, true);
         let if_ty = self.stmts(&if_stmt.body);
+        self.leave_narrowed_branch();
+        self.enter_narrowed_branch(&if_stmt.cond, false);
         let orelse_ty = self.stmts(&if_stmt.orelse);
+        self.leave_narrowed_branch();
         sup(&[if_ty, orelse_ty])
     }
 
@@ -308,11 +316,17 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
     }
 
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx ast::SchemaStmt) -> Self::Result {
-        self.resolve_schema_stmt(schema_stmt)
+        self.enter_narrowed_scope(None);
+        let ty = self.resolve_schema_stmt(schema_stmt);
+        self.leave_narrowed_branch();
+        ty
     }
 
     fn walk_rule_stmt(&mut self, rule_stmt: &'ctx ast::RuleStmt) -> Self::Result {
-        self.resolve_rule_stmt(rule_stmt)
+        self.enter_narrowed_scope(None);
+        let ty = self.resolve_rule_stmt(rule_stmt);
+        self.leave_narrowed_branch();
+        ty
     }
 
     fn walk_quant_expr(&mut self, quant_expr: &'ctx ast::QuantExpr) -> Self::Result {
@@ -322,6 +336,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             end = if_cond.get_end_pos();
         }
         self.enter_scope(start, end, ScopeKind::Loop);
+        self.enter_narrowed_scope(Some(loop_variable_names(&quant_expr.variables)));
         let (mut key_name, mut val_name) = (None, None);
         for (i, target) in quant_expr.variables.iter().enumerate() {
             if target.node.names.is_empty() {
@@ -370,6 +385,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
         );
         self.expr_or_any_type(&quant_expr.if_cond);
         let item_ty = self.expr(&quant_expr.test);
+        self.leave_narrowed_branch();
         self.leave_scope();
         match &quant_expr.op {
             ast::QuantOperation::All | ast::QuantOperation::Any => self.bool_ty(),
@@ -465,6 +481,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                     None,
                 ),
             }
+            self.invalidate_narrowed_type(name);
         }
         self.any_ty()
     }
@@ -472,8 +489,12 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
     /// <body> if <cond> else <orelse> -> sup([body, orelse])
     fn walk_if_expr(&mut self, if_expr: &'ctx ast::IfExpr) -> Self::Result {
         self.expr(&if_expr.cond);
+        self.enter_narrowed_branch(&if_expr.cond, true);
         let body_ty = self.expr(&if_expr.body);
+        self.leave_narrowed_branch();
+        self.enter_narrowed_branch(&if_expr.cond, false);
         let orelse_ty = self.expr(&if_expr.orelse);
+        self.leave_narrowed_branch();
         sup(&[body_ty, orelse_ty])
     }
 
@@ -645,6 +666,9 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             None => list_comp.elt.get_end_pos(),
         };
         self.enter_scope(start.clone(), end, ScopeKind::Loop);
+        self.enter_narrowed_scope(Some(loop_variable_names(
+            list_comp.generators.iter().flat_map(|g| &g.node.targets),
+        )));
         for comp_clause in &list_comp.generators {
             self.walk_comp_clause(&comp_clause.node);
         }
@@ -655,6 +679,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
         }
         let item_ty = self.expr(&list_comp.elt);
+        self.leave_narrowed_branch();
         self.leave_scope();
         self.clear_config_expr_context(stack_depth, false);
         Type::list_ref(item_ty)
@@ -672,6 +697,9 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 None => dict_comp.entry.value.get_end_pos(),
             };
             self.enter_scope(start.clone(), end, ScopeKind::Loop);
+            self.enter_narrowed_scope(Some(loop_variable_names(
+                dict_comp.generators.iter().flat_map(|g| &g.node.targets),
+            )));
             for comp_clause in &dict_comp.generators {
                 self.walk_comp_clause(&comp_clause.node);
             }
@@ -704,6 +732,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 }
             };
             self.clear_config_expr_context(stack_depth, false);
+            self.leave_narrowed_branch();
             self.leave_scope();
             Type::dict_ref(key_ty, val_ty)
         } else {
@@ -714,6 +743,9 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             };
             let start = key.get_pos();
             self.enter_scope(start.clone(), end, ScopeKind::Loop);
+            self.enter_narrowed_scope(Some(loop_variable_names(
+                dict_comp.generators.iter().flat_map(|g| &g.node.targets),
+            )));
             for comp_clause in &dict_comp.generators {
                 self.walk_comp_clause(&comp_clause.node);
             }
@@ -722,6 +754,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             let stack_depth = self.switch_config_expr_context_by_key(&dict_comp.entry.key);
             let val_ty = self.expr(&dict_comp.entry.value);
             self.clear_config_expr_context(stack_depth, false);
+            self.leave_narrowed_branch();
             self.leave_scope();
             Type::dict_ref(key_ty, val_ty)
         }
@@ -862,6 +895,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             );
         }
         let mut range = schema_expr.name.get_span_pos();
+        let mut inferred_ty = None;
         let ret_ty = match &def_ty.kind {
             TypeKind::Dict(DictType { .. }) => {
                 let obj = self.new_config_expr_context_item(
@@ -877,6 +911,11 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             }
             TypeKind::Schema(schema_ty) => {
                 if !schema_ty.is_instance {
+                    self.check_deprecation(
+                        &schema_ty.name,
+                        schema_ty.get_deprecation(),
+                        schema_expr.name.get_span_pos(),
+                    );
                     let name = schema_expr.name.node.get_name();
                     if !self.ctx.local_vars.contains(&name) {
                         let ty_annotation_str = ty_str_replace_pkgpath(
@@ -894,11 +933,20 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 );
                 let init_stack_depth = self.switch_config_expr_context(Some(obj));
                 self.expr(&schema_expr.config);
+                self.clear_config_expr_context(init_stack_depth as usize, false);
+                // Infer the type arguments of the generic schema from the config.
+                if schema_ty.is_generic() && schema_ty.type_args.is_empty() {
+                    let type_args = self.infer_type_args(schema_ty, &schema_expr.config);
+                    let mut generic_ty = self
+                        .get_generic_schema_ty(schema_ty)
+                        .instantiate(&type_args);
+                    generic_ty.is_instance = schema_ty.is_instance;
+                    inferred_ty = Some(Arc::new(Type::schema(generic_ty)));
+                }
                 self.node_ty_map.borrow_mut().insert(
                     self.get_node_key(schema_expr.config.id.clone()),
-                    def_ty.clone(),
+                    inferred_ty.clone().unwrap_or_else(|| def_ty.clone()),
                 );
-                self.clear_config_expr_context(init_stack_depth as usize, false);
                 if schema_ty.is_instance {
                     if !schema_expr.args.is_empty() || !schema_expr.kwargs.is_empty() {
                         self.handler.add_compile_error(
@@ -933,7 +981,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 return self.any_ty();
             }
         };
-        let mut def_ty_clone = def_ty.as_ref().clone();
+        let mut def_ty_clone = inferred_ty.unwrap_or(def_ty).as_ref().clone();
         if let TypeKind::Schema(schema_ty) = &mut def_ty_clone.kind {
             schema_ty.is_instance = true;
         }
@@ -1003,6 +1051,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
                 self.parse_ty_with_scope(Some(&ret_annotation_ty), (start.clone(), end.clone()));
         }
         self.enter_scope(start.clone(), end.clone(), ScopeKind::Lambda);
+        self.enter_narrowed_scope(Some(params.iter().map(|p| p.name.clone()).collect()));
         self.ctx.in_lambda_expr.push(true);
         // Lambda parameters
         for param in &params {
@@ -1065,6 +1114,7 @@ impl<'ctx> MutSelfTypedResultWalker<'ctx> for Resolver<'_> {
             self.any_ty()
         };
         // Leave the lambda scope.
+        self.leave_narrowed_branch();
         self.leave_scope();
         self.ctx.in_lambda_expr.pop();
 
@@ -4,13 +4,13 @@ use std::rc::Rc;
 
 use crate::builtin::BUILTIN_DECORATORS;
 use crate::resolver::Resolver;
-use crate::ty::{Decorator, DecoratorTarget, TypeKind, TypeRef};
+use crate::ty::{Decorator, DecoratorTarget, Deprecation, TypeKind, TypeRef};
 use kclvm_ast::ast;
 use kclvm_ast::pos::GetPos;
 use kclvm_ast::walker::MutSelfTypedResultWalker;
 use kclvm_ast_pretty::{print_ast_node, ASTNode};
 use kclvm_error::diagnostic::Range;
-use kclvm_error::{ErrorKind, Message, Position, Style};
+use kclvm_error::{ErrorKind, Message, Position, Style, WarningKind};
 
 use super::node::ResolvedResult;
 use super::scope::{ScopeKind, ScopeObject, ScopeObjectKind};
@@ -43,6 +43,8 @@ impl<'ctx> Resolver<'_> {
             return ty;
         };
         self.ctx.schema = Some(Rc::new(RefCell::new(scope_ty.clone())));
+        // The type parameters are the any types in the generic schema body.
+        let prev_type_params = self.enter_type_params(schema_stmt, false);
         if let Some(args) = &schema_stmt.args {
             for (i, arg) in args.node.args.iter().enumerate() {
                 let ty = args.node.get_arg_type_node(i);
@@ -145,6 +147,7 @@ impl<'ctx> Resolver<'_> {
             self.walk_check_expr(&check_expr.node);
         }
         self.leave_scope();
+        self.ctx.type_params = prev_type_params;
         self.ctx.schema = None;
         ty
     }
@@ -231,12 +234,24 @@ impl<'ctx> Resolver<'_> {
                                 &decorator.node.args,
                                 &decorator.node.keywords,
                             );
+                            if name == SECRET_DECORATOR && target == DecoratorTarget::Schema {
+                                self.handler.add_compile_error(
+                                    "the secret decorator can only be used on the schema attributes",
+                                    decorator.get_span_pos(),
+                                );
+                            }
+                            let deprecation = if name == DEPRECATED_DECORATOR {
+                                Some(get_deprecation(&decorator.node))
+                            } else {
+                                None
+                            };
                             decorator_objs.push(Decorator {
                                 target: target.clone(),
                                 name,
                                 key: key.to_string(),
                                 arguments,
                                 keywords,
+                                deprecation,
                             })
                         }
                         _ => bug!("invalid builtin decorator function type"),
@@ -301,6 +316,32 @@ impl<'ctx> Resolver<'_> {
         value_ty
     }
 
+    /// Report the use of the schema or attribute declared deprecated as a warning.
+    pub(crate) fn check_deprecation(
+        &mut self,
+        name: &str,
+        deprecation: Option<&Deprecation>,
+        range: Range,
+    ) {
+        if let Some(deprecation) = deprecation {
+            let suggested_replacement = if deprecation.replacement.is_empty() {
+                None
+            } else {
+                Some(vec![deprecation.replacement.clone()])
+            };
+            self.handler.add_warning(
+                WarningKind::DeprecatedWarning,
+                &[Message {
+                    range,
+                    style: Style::LineAndColumn,
+                    message: deprecation.message(name),
+                    note: None,
+                    suggested_replacement,
+                }],
+            );
+        }
+    }
+
     fn arguments_to_string(
         &mut self,
         args: &'ctx [ast::NodeRef<ast::Expr>],
@@ -330,3 +371,40 @@ impl<'ctx> Resolver<'_> {
         }
     }
 }
+
+const DEPRECATED_DECORATOR: &str = "deprecated";
+const SECRET_DECORATOR: &str = "secret";
+/// The positional parameters of the `@deprecated` decorator.
+const DEPRECATED_PARAMS: [&str; 5] = ["version", "reason", "strict", "since", "replacement"];
+
+/// Get the deprecation from the literal arguments of the `@deprecated` decorator.
+fn get_deprecation(decorator: &ast::CallExpr) -> Deprecation {
+    let mut deprecation = Deprecation::default();
+    let args = decorator
+        .args
+        .iter()
+        .zip(DEPRECATED_PARAMS)
+        .map(|(arg, name)| (name.to_string(), Some(arg)));
+    let kwargs = decorator
+        .keywords
+        .iter()
+        .map(|kw| (kw.node.arg.node.get_name(), kw.node.value.as_ref()));
+    for (name, value) in args.chain(kwargs) {
+        match (name.as_str(), value.map(|v| &v.node)) {
+            ("version" | "since", Some(ast::Expr::StringLit(string_lit))) => {
+                deprecation.since = string_lit.value.clone()
+            }
+            ("reason", Some(ast::Expr::StringLit(string_lit))) => {
+                deprecation.reason = string_lit.value.clone()
+            }
+            ("replacement", Some(ast::Expr::StringLit(string_lit))) => {
+                deprecation.replacement = string_lit.value.clone()
+            }
+            ("strict", Some(ast::Expr::NameConstantLit(name_constant))) => {
+                deprecation.strict = !matches!(name_constant.value, ast::NameConstant::False)
+            }
+            _ => {}
+        }
+    }
+    deprecation
+}
@@ -0,0 +1,192 @@
+//! The schema dependency graph of a resolved program, which records the schemas
+//! each schema depends on through the inheritance, the mixins, the protocol and
+//! the attribute types, used for the impact analysis of schema changes.
+
+use std::collections::VecDeque;
+
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_error::diagnostic::Range;
+
+use super::scope::ProgramScope;
+use crate::ty::{SchemaType, TypeKind, TypeRef};
+
+/// The kind of a schema dependency.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SchemaDependencyKind {
+    /// `schema Child(Base)` depends on `Base`.
+    Inheritance,
+    /// `mixin [NameMixin]` depends on `NameMixin`.
+    Mixin,
+    /// `mixin NameMixin for NameProtocol` depends on `NameProtocol`.
+    Protocol,
+    /// `person: Person` depends on `Person`.
+    AttributeType,
+}
+
+/// A dependency from a schema to another schema.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaDependency {
+    /// The full name of the schema depended on e.g., `pkg.Base`.
+    pub schema: String,
+    pub kind: SchemaDependencyKind,
+    /// The range of the reference e.g., the base schema name or the attribute type.
+    pub range: Range,
+}
+
+/// The schema dependency graph, where the schemas are denoted by their full names
+/// e.g., `Person` in the main package and `pkg.Person` in the package `pkg`.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SchemaDependencyGraph {
+    /// The schema full name and the range of the schema name in its definition.
+    pub schemas: IndexMap<String, Range>,
+    /// The adjacency list of the schema dependencies.
+    pub dependencies: IndexMap<String, Vec<SchemaDependency>>,
+}
+
+impl SchemaDependencyGraph {
+    /// Get the schemas which the schema depends on directly.
+    pub fn get_dependencies(&self, schema: &str) -> &[SchemaDependency] {
+        self.dependencies
+            .get(schema)
+            .map(|deps| deps.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Get the schemas which depend on the schema directly with the dependencies.
+    pub fn get_dependents(&self, schema: &str) -> Vec<(&str, &SchemaDependency)> {
+        self.dependencies
+            .iter()
+            .flat_map(|(name, deps)| {
+                deps.iter()
+                    .filter(|dep| dep.schema == schema)
+                    .map(move |dep| (name.as_str(), dep))
+            })
+            .collect()
+    }
+
+    /// Get all the schemas which depend on the schema directly or indirectly, that is,
+    /// the schemas which may be broken if the schema is changed.
+    pub fn get_impacted_schemas(&self, schema: &str) -> IndexSet<String> {
+        let mut impacted = IndexSet::new();
+        let mut queue = VecDeque::from([schema.to_string()]);
+        while let Some(schema) = queue.pop_front() {
+            for (dependent, _) in self.get_dependents(&schema) {
+                if dependent != schema && impacted.insert(dependent.to_string()) {
+                    queue.push_back(dependent.to_string());
+                }
+            }
+        }
+        impacted
+    }
+}
+
+/// Get the schema dependency graph of the program resolved into the program scope.
+pub fn get_schema_dependency_graph(
+    program: &ast::Program,
+    scope: &ProgramScope,
+) -> SchemaDependencyGraph {
+    let mut graph = SchemaDependencyGraph::default();
+    for (pkgpath, modules) in &program.pkgs {
+        let pkg_scope = match scope.scope_map.get(pkgpath) {
+            Some(pkg_scope) => pkg_scope.borrow(),
+            None => continue,
+        };
+        for module in modules {
+            let module = match program.get_module(module) {
+                Ok(Some(module)) => module,
+                _ => continue,
+            };
+            for stmt in &module.body {
+                let schema_stmt = match &stmt.node {
+                    ast::Stmt::Schema(schema_stmt) => schema_stmt,
+                    _ => continue,
+                };
+                let schema_ty = match pkg_scope.elems.get(&schema_stmt.name.node) {
+                    Some(obj) if obj.borrow().ty.is_schema() => obj.borrow().ty.into_schema_type(),
+                    _ => continue,
+                };
+                let name = schema_ty.full_ty_str();
+                graph
+                    .schemas
+                    .insert(name.clone(), schema_stmt.name.get_span_pos());
+                graph
+                    .dependencies
+                    .insert(name, get_schema_dependencies(schema_stmt, &schema_ty));
+            }
+        }
+    }
+    graph
+}
+
+fn get_schema_dependencies(
+    schema_stmt: &ast::SchemaStmt,
+    schema_ty: &SchemaType,
+) -> Vec<SchemaDependency> {
+    let mut deps = vec![];
+    if let (Some(parent_name), Some(base)) = (&schema_stmt.parent_name, &schema_ty.base) {
+        deps.push(SchemaDependency {
+            schema: base.full_ty_str(),
+            kind: SchemaDependencyKind::Inheritance,
+            range: parent_name.get_span_pos(),
+        });
+    }
+    for mixin_name in &schema_stmt.mixins {
+        let name = mixin_name.node.names.last().map(|n| n.node.as_str());
+        if let Some(mixin) = schema_ty
+            .mixins
+            .iter()
+            .find(|m| Some(m.name.as_str()) == name)
+        {
+            deps.push(SchemaDependency {
+                schema: mixin.full_ty_str(),
+                kind: SchemaDependencyKind::Mixin,
+                range: mixin_name.get_span_pos(),
+            });
+        }
+    }
+    if let (Some(host_name), Some(protocol)) = (&schema_stmt.for_host_name, &schema_ty.protocol) {
+        deps.push(SchemaDependency {
+            schema: protocol.full_ty_str(),
+            kind: SchemaDependencyKind::Protocol,
+            range: host_name.get_span_pos(),
+        });
+    }
+    for stmt in &schema_stmt.body {
+        if let ast::Stmt::SchemaAttr(schema_attr) = &stmt.node {
+            if let Some(attr) = schema_ty.attrs.get(&schema_attr.name.node) {
+                let mut names = IndexSet::new();
+                collect_schema_names(&attr.ty, &mut names);
+                for name in names {
+                    deps.push(SchemaDependency {
+                        schema: name,
+                        kind: SchemaDependencyKind::AttributeType,
+                        range: schema_attr.ty.get_span_pos(),
+                    });
+                }
+            }
+        }
+    }
+    deps
+}
+
+/// Collect the full names of the schemas referenced by the type.
+fn collect_schema_names(ty: &TypeRef, names: &mut IndexSet<String>) {
+    match &ty.kind {
+        TypeKind::Schema(schema_ty) => {
+            names.insert(schema_ty.full_ty_str());
+        }
+        TypeKind::List(item_ty) => collect_schema_names(item_ty, names),
+        TypeKind::Dict(dict_ty) => {
+            collect_schema_names(&dict_ty.key_ty, names);
+            collect_schema_names(&dict_ty.val_ty, names);
+        }
+        TypeKind::Union(types) => {
+            for ty in types {
+                collect_schema_names(ty, names);
+            }
+        }
+        _ => {}
+    }
+}
@@ -7,9 +7,11 @@ use kclvm_ast::ast::Stmt::Import;
 use kclvm_ast::{ast, MAIN_PKG};
 use kclvm_error::diagnostic::Range;
 use kclvm_error::{Handler, Level};
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashMap;
 use std::collections::HashSet;
 use std::collections::VecDeque;
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 use std::{
     cell::RefCell,
@@ -525,8 +527,9 @@ pub struct NodeKey {
 pub type NodeTyMap = IndexMap<NodeKey, TypeRef>;
 pub type KCLScopeCache = Arc<RwLock<CachedScope>>;
 
-/// For CachedScope, we assume that all changed files must be located in kclvm_ast::MAIN_PKG ,
-/// if this is not the case, please clear the cache directly
+/// CachedScope caches the package scopes and the node types of the packages out of
+/// kclvm_ast::MAIN_PKG. The changed modules are found by the module fingerprints on every
+/// update, and only the packages of them and their dependents are resolved again.
 #[derive(Debug, Clone, Default)]
 pub struct CachedScope {
     pub program_root: String,
@@ -538,6 +541,9 @@ pub struct CachedScope {
     /// If it is None, all modules in the main package will be invalidated
     pub invalidate_pkg_modules: Option<HashSet<String>>,
     dependency_graph: DependencyGraph,
+    /// Map filename to the fingerprint of the module, used to find the changed
+    /// modules out of the main package.
+    module_fingerprints: HashMap<String, u64>,
 }
 
 unsafe impl Send for CachedScope {}
@@ -731,42 +737,116 @@ impl CachedScope {
             dependency_graph: DependencyGraph::default(),
             schema_mapping: scope.schema_mapping.clone(),
             invalidate_pkg_modules: None,
+            module_fingerprints: HashMap::default(),
         };
         let invalidated_pkgs = cached_scope
             .dependency_graph
             .update(program, &cached_scope.invalidate_pkg_modules);
         cached_scope.invalidate_cache(invalidated_pkgs.as_ref());
+        cached_scope.module_fingerprints = get_module_fingerprints(program, &IndexMap::default());
         cached_scope
     }
 
     pub fn clear(&mut self) {
         self.scope_map.clear();
         self.node_ty_map.clear();
+        self.schema_mapping.clear();
         self.dependency_graph.clear();
         self.invalidate_pkgs.clear();
         self.invalidate_pkg_modules = None;
+        self.module_fingerprints.clear();
     }
 
+    /// Remove the scopes and the types of the invalidated packages. Note that the
+    /// invalidated packages are accumulated into `invalidate_pkgs` until it is cleared.
     pub fn invalidate_cache(&mut self, invalidated_pkgs: Result<&HashSet<String>, &String>) {
         match invalidated_pkgs {
             Ok(invalidated_pkgs) => {
                 for invalidated_pkg in invalidated_pkgs.iter() {
                     self.scope_map.remove(invalidated_pkg);
                 }
-                self.invalidate_pkgs = invalidated_pkgs.clone();
+                self.node_ty_map
+                    .retain(|key, _| !invalidated_pkgs.contains(&key.pkgpath));
+                self.schema_mapping
+                    .retain(|_, ty| !invalidated_pkgs.contains(&ty.borrow().pkgpath));
+                self.invalidate_pkgs
+                    .extend(invalidated_pkgs.iter().cloned());
             }
             Err(_) => self.clear(),
         }
     }
 
-    pub fn update(&mut self, program: &ast::Program) {
+    /// Update the cache with the program. The packages whose modules are changed, added
+    /// or removed since the last update, the packages of `invalidate_pkg_modules` and
+    /// the packages which depend on them are invalidated, and the other packages are
+    /// reused by the resolver. The modules are fingerprinted by the source hashes of
+    /// `file_hashes` keyed by the filenames, see [get_module_fingerprints].
+    pub fn update(&mut self, program: &ast::Program, file_hashes: &IndexMap<String, String>) {
         if self.program_root != program.root {
             self.clear();
             self.program_root = program.root.clone();
         }
+        let fingerprints = get_module_fingerprints(program, file_hashes);
+        let mut changed_modules: HashSet<String> = fingerprints
+            .iter()
+            .filter(|(filename, fingerprint)| {
+                self.module_fingerprints.get(*filename) != Some(*fingerprint)
+            })
+            .map(|(filename, _)| filename.to_string())
+            .collect();
+        let removed_modules: Vec<String> = self
+            .module_fingerprints
+            .keys()
+            .filter(|filename| !fingerprints.contains_key(*filename))
+            .cloned()
+            .collect();
+        changed_modules.extend(removed_modules.iter().cloned());
+        if let Some(modules) = &self.invalidate_pkg_modules {
+            changed_modules.extend(modules.iter().cloned());
+        }
         let invalidated_pkgs = self
             .dependency_graph
-            .update(program, &self.invalidate_pkg_modules);
+            .update(program, &Some(changed_modules));
         self.invalidate_cache(invalidated_pkgs.as_ref());
+        for filename in &removed_modules {
+            self.dependency_graph.module_map.remove(filename);
+        }
+        self.module_fingerprints = fingerprints;
+    }
+}
+
+/// Get the module fingerprints of the packages out of the main package. The module is
+/// fingerprinted by its source hash in `file_hashes`, or by the size and the modified
+/// time of the file when the hash is absent. The AST node ids of the statements are
+/// included, because the node types are recorded with them, and a re-parsed module
+/// should be invalidated even if its code is not changed.
+fn get_module_fingerprints(
+    program: &ast::Program,
+    file_hashes: &IndexMap<String, String>,
+) -> HashMap<String, u64> {
+    let mut fingerprints = HashMap::new();
+    for (pkgpath, modules) in &program.pkgs {
+        if pkgpath == kclvm_ast::MAIN_PKG {
+            continue;
+        }
+        for module in modules {
+            if let Ok(Some(module)) = program.get_module(module) {
+                let mut hasher = DefaultHasher::new();
+                match file_hashes.get(&module.filename) {
+                    Some(hash) => hash.hash(&mut hasher),
+                    None => {
+                        if let Ok(metadata) = std::fs::metadata(&module.filename) {
+                            metadata.len().hash(&mut hasher);
+                            metadata.modified().ok().hash(&mut hasher);
+                        }
+                    }
+                }
+                for stmt in &module.body {
+                    stmt.id.hash(&mut hasher);
+                }
+                fingerprints.insert(module.filename.clone(), hasher.finish());
+            }
+        }
     }
+    fingerprints
 }
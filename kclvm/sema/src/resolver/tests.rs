@@ -1,10 +1,11 @@
 use super::Options;
-use super::Resolver;
 use crate::builtin::BUILTIN_FUNCTION_NAMES;
-use crate::pre_process::pre_process_program;
+use crate::resolver::check_type::check_type;
 use crate::resolver::resolve_program;
 use crate::resolver::resolve_program_with_opts;
+use crate::resolver::schema_graph::*;
 use crate::resolver::scope::*;
+use crate::resolver::try_resolve_program_with_opts;
 use crate::ty::{Type, TypeKind};
 use anyhow::Result;
 use kclvm_ast::ast;
@@ -12,6 +13,7 @@ use kclvm_ast::pos::ContainsPos;
 use kclvm_ast::MAIN_PKG;
 use kclvm_error::*;
 use kclvm_parser::load_program;
+use kclvm_parser::parse_expr_with_diagnostics;
 use kclvm_parser::parse_file_force_errors;
 use kclvm_parser::LoadProgramOptions;
 use kclvm_parser::ParseSession;
@@ -76,6 +78,17 @@ fn test_resolve_program() {
     assert!(main_scope.lookup("print").is_none());
 }
 
+#[test]
+fn test_resolve_program_cancelled() {
+    let mut program = parse_program("./src/resolver/test_data/assign.k").unwrap();
+    let opts = Options::default();
+    opts.cancel_token.cancel();
+    let cached_scope = Arc::new(RwLock::new(CachedScope::default()));
+    let result = try_resolve_program_with_opts(&mut program, opts, Some(cached_scope.clone()));
+    assert_eq!(result.unwrap_err(), kclvm_utils::cancel::Cancelled);
+    assert!(cached_scope.read().scope_map.is_empty());
+}
+
 #[test]
 fn test_resolve_program_with_cache() {
     let mut program = parse_program("./src/resolver/test_data/assign.k").unwrap();
@@ -384,6 +397,37 @@ fn test_resolve_program_module_optional_select_fail() {
     assert_eq!(diag.messages[0].message, expect_err_msg);
 }
 
+#[test]
+fn test_resolve_warnings_as_errors() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/lint.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            warnings_as_errors: vec![WarningKind::ReimportWarning],
+            ..Default::default()
+        },
+        None,
+    );
+    let (errors, warnings) = scope.handler.classification();
+    assert!(errors
+        .iter()
+        .any(|diag| { diag.code == Some(DiagnosticId::Warning(WarningKind::ReimportWarning)) }));
+    assert!(warnings
+        .iter()
+        .all(|diag| { diag.code != Some(DiagnosticId::Warning(WarningKind::ReimportWarning)) }));
+    assert!(warnings.iter().any(|diag| {
+        diag.code == Some(DiagnosticId::Warning(WarningKind::ImportPositionWarning))
+    }));
+}
+
 #[test]
 fn test_lint() {
     let sess = Arc::new(ParseSession::default());
@@ -395,11 +439,7 @@ fn test_lint() {
     )
     .unwrap()
     .program;
-    let opts = Options::default();
-    pre_process_program(&mut program, &opts);
-    let mut resolver = Resolver::new(&program, opts);
-    resolver.resolve_import();
-    resolver.check_and_lint_all_pkgs();
+    let scope = resolve_program(&mut program);
 
     let root = &program.root.clone();
     let filename = Path::new(&root.clone())
@@ -424,30 +464,51 @@ fn test_lint() {
             ),
             style: Style::Line,
             message: format!("The import stmt should be placed at the top of the module"),
-            note: Some("Consider moving tihs statement to the top of the file".to_string()),
+            note: Some("Consider moving this statement to the top of the file".to_string()),
             suggested_replacement: None,
         }],
     );
-    handler.add_warning(
-        WarningKind::ReimportWarning,
-        &[Message {
-            range: (
+    handler.add_diagnostic(
+        Diagnostic {
+            level: Level::Warning,
+            messages: vec![Message {
+                range: (
+                    Position {
+                        filename: filename.clone(),
+                        line: 2,
+                        column: Some(0),
+                    },
+                    Position {
+                        filename: filename.clone(),
+                        line: 2,
+                        column: Some(20),
+                    },
+                ),
+                style: Style::Line,
+                message: format!("Module 'a' is reimported multiple times"),
+                note: Some("Consider removing this statement".to_string()),
+                suggested_replacement: None,
+            }],
+            code: Some(DiagnosticId::Warning(WarningKind::ReimportWarning)),
+            fixes: vec![],
+            labels: vec![],
+            source_hash: None,
+        }
+        .with_label(
+            (
                 Position {
                     filename: filename.clone(),
-                    line: 2,
+                    line: 1,
                     column: Some(0),
                 },
                 Position {
                     filename: filename.clone(),
-                    line: 2,
+                    line: 1,
                     column: Some(20),
                 },
             ),
-            style: Style::Line,
-            message: format!("Module 'a' is reimported multiple times"),
-            note: Some("Consider removing this statement".to_string()),
-            suggested_replacement: None,
-        }],
+            "Module 'a' is first imported here",
+        ),
     );
     handler.add_warning(
         WarningKind::UnusedImportWarning,
@@ -470,13 +531,13 @@ fn test_lint() {
             suggested_replacement: None,
         }],
     );
-    for (d1, d2) in resolver
-        .linter
+    let warnings: Vec<&Diagnostic> = scope
         .handler
         .diagnostics
         .iter()
-        .zip(handler.diagnostics.iter())
-    {
+        .filter(|diag| diag.level == Level::Warning)
+        .collect();
+    for (d1, d2) in warnings.into_iter().zip(handler.diagnostics.iter()) {
         assert_eq!(d1, d2);
     }
 }
@@ -530,6 +591,386 @@ See also: kusion_models/core/v1/metadata.k."
     );
 }
 
+#[test]
+fn test_resolve_schema_attr_comment_doc() {
+    let mut program = parse_program("./src/resolver/test_data/attr_comment_doc.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let main_scope = scope
+        .scope_map
+        .get(kclvm_runtime::MAIN_PKG_PATH)
+        .unwrap()
+        .borrow_mut()
+        .clone();
+    let schema_scope = &main_scope.children[0];
+    let attrs_scope = &schema_scope.borrow().elems;
+    assert_eq!(
+        Some("The name of the person.\nIt is required.".to_string()),
+        attrs_scope.get("name").unwrap().borrow().doc
+    );
+    assert_eq!(
+        Some("The age in the docstring.".to_string()),
+        attrs_scope.get("age").unwrap().borrow().doc
+    );
+    assert_eq!(None, attrs_scope.get("email").unwrap().borrow().doc);
+    assert_eq!(None, attrs_scope.get("phone").unwrap().borrow().doc);
+}
+
+#[test]
+fn test_resolve_schema_inherited_attrs() {
+    let mut program = parse_program("./src/resolver/test_data/schema_inherited_attrs.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let main_scope = scope
+        .scope_map
+        .get(kclvm_runtime::MAIN_PKG_PATH)
+        .unwrap()
+        .borrow_mut()
+        .clone();
+    let child_ty = main_scope
+        .lookup("Child")
+        .unwrap()
+        .borrow()
+        .ty
+        .into_schema_type();
+    let attrs = child_ty.get_inherited_attrs();
+    let defined_in: Vec<(&str, &str)> = attrs
+        .iter()
+        .map(|(name, attr)| (name.as_str(), attr.defined_in.as_str()))
+        .collect();
+    assert_eq!(
+        defined_in,
+        vec![
+            ("name", "NameMixin"),
+            ("age", "Child"),
+            ("labels", "NameMixin"),
+            ("kind", "Child"),
+        ]
+    );
+    assert!(!attrs["age"].attr.is_optional);
+    assert_eq!(attrs["name"].attr.default, Some("\"mixin\"".to_string()));
+}
+
+#[test]
+fn test_resolve_deprecated_decorator() {
+    let mut program = parse_program("./src/resolver/test_data/deprecated.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let warnings: Vec<(String, u64, Option<Vec<String>>)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.code == Some(DiagnosticId::Warning(WarningKind::DeprecatedWarning)))
+        .map(|diag| {
+            (
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+                diag.messages[0].suggested_replacement.clone(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                "'Person' is deprecated since version v0.2, the person is too general, use 'Human' instead".to_string(),
+                10,
+                Some(vec!["Human".to_string()])
+            ),
+            (
+                "'Person.name' is deprecated, use 'fullName' instead".to_string(),
+                10,
+                Some(vec!["fullName".to_string()])
+            ),
+            (
+                "'Person.name' is deprecated, use 'fullName' instead".to_string(),
+                11,
+                Some(vec!["fullName".to_string()])
+            ),
+        ]
+    );
+    let main_scope = scope.scope_map[MAIN_PKG].borrow();
+    let person_ty = main_scope
+        .lookup("Person")
+        .unwrap()
+        .borrow()
+        .ty
+        .into_schema_type();
+    let deprecation = person_ty.get_deprecation().unwrap();
+    assert_eq!(deprecation.since, "v0.2");
+    assert!(!deprecation.strict);
+    assert!(person_ty.attrs["fullName"].get_deprecation().is_none());
+}
+
+#[test]
+fn test_resolve_type_narrowing() {
+    let mut program = parse_program("./src/resolver/test_data/type_narrowing.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let main_scope = scope
+        .scope_map
+        .get(kclvm_runtime::MAIN_PKG_PATH)
+        .unwrap()
+        .borrow_mut()
+        .clone();
+    for name in ["name", "upper", "kind_upper"] {
+        assert_eq!(
+            main_scope.lookup(name).unwrap().borrow().ty.ty_str(),
+            "str",
+            "{name}"
+        );
+    }
+    for name in ["value", "kind_value"] {
+        assert_eq!(
+            main_scope.lookup(name).unwrap().borrow().ty.ty_str(),
+            "int",
+            "{name}"
+        );
+    }
+    let errors: Vec<&str> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .map(|diag| diag.messages[0].message.as_str())
+        .collect();
+    assert_eq!(errors, vec!["attribute 'age' not found in 'Person'"]);
+}
+
+#[test]
+fn test_resolve_type_narrowing_with_scopes() {
+    let mut program = parse_program("./src/resolver/test_data/type_narrowing_scope.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let main_scope = scope
+        .scope_map
+        .get(kclvm_runtime::MAIN_PKG_PATH)
+        .unwrap()
+        .borrow_mut()
+        .clone();
+    for (name, ty_str) in [
+        ("items", "[str]"),
+        ("lowered", "{str:str}"),
+        ("reassigned", "int | str"),
+    ] {
+        assert_eq!(
+            main_scope.lookup(name).unwrap().borrow().ty.ty_str(),
+            ty_str,
+            "{name}"
+        );
+    }
+    // The lambda parameters, the loop variables and the schema attributes named `x`
+    // are not narrowed by the type guard on the global variable `x`.
+    let errors: Vec<&str> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .map(|diag| diag.messages[0].message.as_str())
+        .collect();
+    assert!(errors.is_empty(), "{errors:?}");
+}
+
+#[test]
+fn test_resolve_generic_schema() {
+    let mut program = parse_program("./src/resolver/test_data/generic_schema.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let main_scope = scope
+        .scope_map
+        .get(kclvm_runtime::MAIN_PKG_PATH)
+        .unwrap()
+        .borrow_mut()
+        .clone();
+    for (name, ty_str) in [
+        ("a", "Box<str>"),
+        ("b", "Box<int>"),
+        ("d", "Pair<str, int>"),
+        ("value", "str"),
+        ("number", "int"),
+        ("item", "int"),
+    ] {
+        assert_eq!(
+            main_scope.lookup(name).unwrap().borrow().ty.ty_str(),
+            ty_str,
+            "{name}"
+        );
+    }
+    let mut errors: Vec<&str> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.level == Level::Error)
+        .map(|diag| diag.messages[0].message.as_str())
+        .collect();
+    errors.sort();
+    assert_eq!(
+        errors,
+        vec![
+            "expected 1 type arguments for the generic schema 'Box', got 2",
+            "expected Box<int>, got Box<str>",
+        ]
+    );
+}
+
+#[test]
+fn test_schema_dependency_graph() {
+    let mut program = parse_program("./src/resolver/test_data/schema_dependency_graph.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let graph = get_schema_dependency_graph(&program, &scope);
+    let deps: Vec<(&str, SchemaDependencyKind, u64)> = graph
+        .get_dependencies("Child")
+        .iter()
+        .map(|dep| (dep.schema.as_str(), dep.kind, dep.range.0.line))
+        .collect();
+    assert_eq!(
+        deps,
+        vec![
+            ("Base", SchemaDependencyKind::Inheritance, 14),
+            ("NameMixin", SchemaDependencyKind::Mixin, 15),
+            ("Label", SchemaDependencyKind::AttributeType, 16),
+        ]
+    );
+    assert_eq!(
+        graph.get_dependencies("NameMixin")[0].kind,
+        SchemaDependencyKind::Protocol
+    );
+    let dependents: Vec<&str> = graph
+        .get_dependents("Child")
+        .iter()
+        .map(|(name, _)| *name)
+        .collect();
+    assert_eq!(dependents, vec!["Group"]);
+    let impacted: Vec<&str> = graph
+        .get_impacted_schemas("NameProtocol")
+        .iter()
+        .map(|name| name.as_str())
+        .collect();
+    assert_eq!(impacted, vec!["NameMixin", "Child", "Group"]);
+    assert!(graph.get_impacted_schemas("Group").is_empty());
+}
+
+#[test]
+fn test_check_type() {
+    let program = parse_program("./src/resolver/test_data/check_type.k").unwrap();
+    let check = |code: &str, schema: &str| {
+        let (expr, errors) = parse_expr_with_diagnostics(code, "<check_type>");
+        assert!(errors.is_empty());
+        check_type(&program, expr.unwrap(), schema)
+    };
+    assert!(check(r#"{name = "Alice", age = 18}"#, "Person").is_ok());
+    assert!(check(r#"{name = "Alice", labels.app = "web"}"#, "Person").is_ok());
+    let errors = check(r#"{name = "Alice", age = "18"}"#, "Person").unwrap_err();
+    assert!(errors
+        .iter()
+        .any(|e| e.messages[0].message.contains("expected int")));
+    assert_eq!(errors[0].messages[0].range.0.filename, "<check_type>");
+    assert!(check(r#"{name = "Alice", nickname = "A"}"#, "Person").is_err());
+    assert!(check(r#""Alice""#, "Person").is_err());
+    let errors = check(r#"{name = "Alice"}"#, "pkg.Person").unwrap_err();
+    assert_eq!(
+        errors[0].messages[0].message,
+        "schema 'pkg.Person' not found"
+    );
+    let errors = check(r#"{name = "Alice"}"#, "Student").unwrap_err();
+    assert_eq!(errors[0].messages[0].message, "schema 'Student' not found");
+    // The program is not changed by the check.
+    assert_eq!(program.pkgs[MAIN_PKG].len(), 1);
+}
+
+#[test]
+fn test_resolve_unused_symbols() {
+    let mut program = parse_program("./src/resolver/test_data/unused_symbols.k").unwrap();
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            unused_check: true,
+            ..Default::default()
+        },
+        None,
+    );
+    let warnings: Vec<(WarningKind, String, u64)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter_map(|diag| match &diag.code {
+            Some(DiagnosticId::Warning(kind)) => Some((
+                kind.clone(),
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                WarningKind::UnusedParameterWarning,
+                "Parameter 'y' is never used".to_string(),
+                13
+            ),
+            (
+                WarningKind::UnusedVariableWarning,
+                "Variable 'unused_local' is defined but never used".to_string(),
+                15
+            ),
+            (
+                WarningKind::UnusedVariableWarning,
+                "Variable '_unused' is defined but never used".to_string(),
+                11
+            ),
+            (
+                WarningKind::UnusedSchemaWarning,
+                "Schema 'Unused' is defined but never used".to_string(),
+                7
+            ),
+        ]
+    );
+    assert_eq!(WarningKind::UnusedSchemaWarning.code(), "W2002");
+}
+
+#[test]
+fn test_resolve_exhaustive_check() {
+    let mut program = parse_program("./src/resolver/test_data/exhaustive_check.k").unwrap();
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            exhaustive_check: true,
+            ..Default::default()
+        },
+        None,
+    );
+    let warnings: Vec<(WarningKind, String, u64)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter_map(|diag| match &diag.code {
+            Some(DiagnosticId::Warning(kind)) => Some((
+                kind.clone(),
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+            )),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(
+        warnings,
+        vec![
+            (
+                WarningKind::NonExhaustiveWarning,
+                "Non-exhaustive conditions on 'kind', the value \"c\" is not matched".to_string(),
+                7
+            ),
+            (
+                WarningKind::UnreachableBranchWarning,
+                "Unreachable branch, the value \"d\" of 'kind' is impossible or already matched"
+                    .to_string(),
+                16
+            ),
+            (
+                WarningKind::UnreachableBranchWarning,
+                "Unreachable else branch, all the values of 'kind' are matched".to_string(),
+                19
+            ),
+        ]
+    );
+}
+
 #[test]
 fn test_pkg_scope() {
     let sess = Arc::new(ParseSession::default());
@@ -922,6 +1363,89 @@ fn test_schema_index_signature_check() {
     assert!(diags.is_empty())
 }
 
+#[test]
+fn test_resolve_program_with_changed_modules() {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/cache/main.k"],
+        None,
+        None,
+    )
+    .unwrap()
+    .program;
+    let opts = Options {
+        merge_program: false,
+        type_erasure: false,
+        ..Default::default()
+    };
+    let cached_scope = KCLScopeCache::default();
+    let _ = resolve_program_with_opts(&mut program, opts.clone(), Some(cached_scope.clone()));
+    // All the packages are new for the empty cache.
+    let expect: HashSet<String> = [MAIN_PKG, "cache.a", "cache.b", "cache.c"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    assert_eq!(cached_scope.read().invalidate_pkgs, expect);
+
+    // Nothing is changed, only the main package is resolved again.
+    let _ = resolve_program_with_opts(&mut program, opts.clone(), Some(cached_scope.clone()));
+    let expect: HashSet<String> = [MAIN_PKG].iter().map(|p| p.to_string()).collect();
+    assert_eq!(cached_scope.read().invalidate_pkgs, expect);
+
+    // Change the module of `cache.c` without specifying `invalidate_pkg_modules`.
+    let filename = program.pkgs["cache.c"][0].clone();
+    let module =
+        kclvm_parser::parse_file_force_errors(&filename, Some("a = 2".to_string())).unwrap();
+    *program.get_module_mut(&filename).unwrap().unwrap() = module;
+    let scope = resolve_program_with_opts(&mut program, opts.clone(), Some(cached_scope.clone()));
+    // main - a
+    //      - b - c
+    // invalidate c, invalidate_pkgs c, b, main
+    let expect: HashSet<String> = [MAIN_PKG, "cache.b", "cache.c"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    assert_eq!(cached_scope.read().invalidate_pkgs, expect);
+    assert!(scope.scope_map.contains_key("cache.a"));
+    assert!(scope.scope_map["cache.c"].borrow().lookup("a").is_some());
+}
+
+#[test]
+fn test_resolve_program_with_changed_file_hashes() {
+    let sess = Arc::new(ParseSession::default());
+    let result = load_program(
+        sess.clone(),
+        &["./src/resolver/test_data/cache/main.k"],
+        None,
+        None,
+    )
+    .unwrap();
+    let mut program = result.program;
+    let mut opts = Options {
+        merge_program: false,
+        type_erasure: false,
+        file_hashes: result.file_hashes,
+        ..Default::default()
+    };
+    let cached_scope = KCLScopeCache::default();
+    let _ = resolve_program_with_opts(&mut program, opts.clone(), Some(cached_scope.clone()));
+    let _ = resolve_program_with_opts(&mut program, opts.clone(), Some(cached_scope.clone()));
+    let expect: HashSet<String> = [MAIN_PKG].iter().map(|p| p.to_string()).collect();
+    assert_eq!(cached_scope.read().invalidate_pkgs, expect);
+
+    // Change the source hash of `cache.c` with the same AST.
+    let filename = program.pkgs["cache.c"][0].clone();
+    assert!(opts.file_hashes.contains_key(&filename));
+    opts.file_hashes.insert(filename, "0".repeat(64));
+    let _ = resolve_program_with_opts(&mut program, opts, Some(cached_scope.clone()));
+    let expect: HashSet<String> = [MAIN_PKG, "cache.b", "cache.c"]
+        .iter()
+        .map(|p| p.to_string())
+        .collect();
+    assert_eq!(cached_scope.read().invalidate_pkgs, expect);
+}
+
 #[test]
 fn test_clear_cache_by_module() {
     let sess = Arc::new(ParseSession::default());
@@ -1098,3 +1622,96 @@ fn test_clear_cache_by_module() {
         assert_eq!(cached_scope.invalidate_pkgs, expect);
     };
 }
+
+#[test]
+fn test_config_attr_suggestions() {
+    let mut program =
+        parse_program("./src/resolver/test_fail_data/config_attr_suggestion.k").unwrap();
+    let scope = resolve_program(&mut program);
+    let diags: Vec<(String, Vec<String>)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| diag.messages[0].message.starts_with("Cannot add member"))
+        .map(|diag| {
+            (
+                diag.messages[0].message.clone(),
+                diag.suggested_replacements()
+                    .into_iter()
+                    .map(|(_, s)| s.to_string())
+                    .collect(),
+            )
+        })
+        .collect();
+    assert_eq!(
+        diags,
+        vec![
+            (
+                "Cannot add member 'metdata' to schema 'Config', did you mean '[\"metadata\"]'?"
+                    .to_string(),
+                vec!["metadata.name".to_string()]
+            ),
+            (
+                "Cannot add member 'replicsa' to schema 'Config', did you mean '[\"replicas\"]'?"
+                    .to_string(),
+                vec!["\"replicas\"".to_string()]
+            ),
+            (
+                "Cannot add member 'lables' to schema 'Metadata', did you mean '[\"labels\"]'?"
+                    .to_string(),
+                vec!["labels".to_string()]
+            ),
+            (
+                "Cannot add member 'x' to schema 'Metadata'".to_string(),
+                vec![]
+            ),
+        ]
+    );
+}
+
+#[test]
+fn test_resolve_check_block_check() {
+    let mut program = parse_program("./src/resolver/test_data/check_block.k").unwrap();
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            check_block_check: true,
+            ..Default::default()
+        },
+        None,
+    );
+    let warnings: Vec<(String, u64)> = scope
+        .handler
+        .diagnostics
+        .iter()
+        .filter(|diag| {
+            diag.code == Some(DiagnosticId::Warning(WarningKind::ConstantConditionWarning))
+        })
+        .map(|diag| {
+            (
+                diag.messages[0].message.clone(),
+                diag.messages[0].range.0.line,
+            )
+        })
+        .collect();
+    let always_true = "The check condition is always true".to_string();
+    let always_false = "The check condition is always false".to_string();
+    assert_eq!(
+        warnings,
+        vec![
+            (always_true.clone(), 9),
+            (always_false.clone(), 10),
+            (always_true.clone(), 11),
+            (always_false.clone(), 12),
+            (always_true.clone(), 13),
+            (always_true.clone(), 16),
+            (
+                "The check condition is never evaluated, because its if condition is always false"
+                    .to_string(),
+                17
+            ),
+            (always_false.clone(), 21),
+        ]
+    );
+    assert_eq!(WarningKind::ConstantConditionWarning.code(), "W2008");
+}
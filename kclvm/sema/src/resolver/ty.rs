@@ -70,10 +70,13 @@ impl<'ctx> Resolver<'_> {
         };
         // If a named type, find it from scope to get the specific type
         let ret_ty = self.upgrade_named_ty_with_scope(ty.clone(), &range, ty_node);
-        self.add_type_alias(
-            &ty.into_type_annotation_str(),
-            &ret_ty.into_type_annotation_str(),
-        );
+        // The type parameters of the generic schema are erased by the type erasure pass.
+        if !self.contains_type_params(&ty) {
+            self.add_type_alias(
+                &ty.into_type_annotation_str(),
+                &ret_ty.into_type_annotation_str(),
+            );
+        }
         if let Some(ty) = ty_node {
             self.node_ty_map
                 .borrow_mut()
@@ -85,8 +88,10 @@ impl<'ctx> Resolver<'_> {
     pub fn parse_ty_str_with_scope(&mut self, ty_str: &str, range: Range) -> ResolvedResult {
         let ty: TypeRef = parse_type_str(ty_str);
         // If a named type, find it from scope to get the specific type
-        let ret_ty = self.upgrade_named_ty_with_scope(ty, &range, None);
-        self.add_type_alias(ty_str, &ret_ty.into_type_annotation_str());
+        let ret_ty = self.upgrade_named_ty_with_scope(ty.clone(), &range, None);
+        if !self.contains_type_params(&ty) {
+            self.add_type_alias(ty_str, &ret_ty.into_type_annotation_str());
+        }
         ret_ty
     }
 
@@ -492,6 +497,15 @@ impl<'ctx> Resolver<'_> {
                     .collect::<Vec<TypeRef>>(),
             ),
             TypeKind::Named(ty_str) => {
+                // The type parameter of the generic schema e.g., `T` in `schema Box<T>`.
+                if let Some(param_ty) = self.ctx.type_params.get(ty_str).cloned() {
+                    if let Some(ty_node) = ty_node {
+                        self.node_ty_map
+                            .borrow_mut()
+                            .insert(self.get_node_key(ty_node.id.clone()), param_ty.clone());
+                    }
+                    return param_ty;
+                }
                 let ty_str = ty_str_replace_pkgpath(ty_str, &self.ctx.pkgpath);
                 let names: Vec<&str> = if ty_str.starts_with('@') {
                     let names: Vec<&str> = ty_str.rsplitn(2, '.').collect();
@@ -521,16 +535,25 @@ impl<'ctx> Resolver<'_> {
                 );
 
                 if let Some(ty_node) = ty_node {
-                    if let ast::Type::Named(identifier) = &ty_node.node {
+                    let identifier = match &ty_node.node {
+                        ast::Type::Named(identifier) => Some(identifier),
+                        ast::Type::Generic(generic_type) => Some(&generic_type.name),
+                        _ => None,
+                    };
+                    if let Some(identifier) = identifier {
                         for (index, name) in identifier.names.iter().enumerate() {
                             self.node_ty_map
                                 .borrow_mut()
                                 .insert(self.get_node_key(name.id.clone()), tys[index].clone());
                         }
-                        let ident_ty = tys.last().unwrap().clone();
+                        let mut ident_ty = tys.last().unwrap().clone();
+                        if let ast::Type::Generic(generic_type) = &ty_node.node {
+                            ident_ty = self.instantiate_generic_ty(ident_ty, generic_type, range);
+                        }
                         self.node_ty_map
                             .borrow_mut()
                             .insert(self.get_node_key(ty_node.id.clone()), ident_ty.clone());
+                        return ident_ty;
                     }
                 };
                 tys.last().unwrap().clone()
@@ -582,6 +605,52 @@ impl<'ctx> Resolver<'_> {
         }
     }
 
+    /// Instantiate the generic schema type with the type arguments e.g., `Box<str>`.
+    fn instantiate_generic_ty(
+        &mut self,
+        ty: TypeRef,
+        generic_type: &ast::GenericType,
+        range: &Range,
+    ) -> TypeRef {
+        let type_args: Vec<TypeRef> = generic_type
+            .args
+            .iter()
+            .map(|arg| {
+                let arg_ty = Arc::new(arg.node.clone().into());
+                self.upgrade_named_ty_with_scope(arg_ty, &arg.get_span_pos(), Some(arg))
+            })
+            .collect();
+        match &ty.kind {
+            TypeKind::Schema(schema_ty) if schema_ty.is_generic() => {
+                if type_args.len() != schema_ty.type_params.len() {
+                    self.handler.add_type_error(
+                        &format!(
+                            "expected {} type arguments for the generic schema '{}', got {}",
+                            schema_ty.type_params.len(),
+                            schema_ty.name,
+                            type_args.len()
+                        ),
+                        range.clone(),
+                    );
+                    return self.any_ty();
+                }
+                let mut generic_ty = self
+                    .get_generic_schema_ty(schema_ty)
+                    .instantiate(&type_args);
+                generic_ty.is_instance = schema_ty.is_instance;
+                Arc::new(Type::schema(generic_ty))
+            }
+            TypeKind::Any => ty,
+            _ => {
+                self.handler.add_type_error(
+                    &format!("'{}' is not a generic schema", ty.ty_str()),
+                    range.clone(),
+                );
+                self.any_ty()
+            }
+        }
+    }
+
     pub fn add_type_alias(&mut self, name: &str, alias: &str) {
         if alias.starts_with('@') {
             if name == &alias[1..] {
@@ -2,22 +2,39 @@ use kclvm_ast::walker::MutSelfMutWalker;
 use kclvm_ast::{ast, walk_if_mut, walk_list_mut};
 
 #[derive(Default)]
-struct TypeErasureTransformer;
+struct TypeErasureTransformer {
+    /// The type parameters of the current generic schema.
+    type_params: Vec<String>,
+}
 const FUNCTION: &str = "function";
 
+impl TypeErasureTransformer {
+    /// Erase the generic types and the type parameters of the current generic schema.
+    fn erase_generic_type(&self, ty: &mut ast::Type) {
+        ty.erase_generics(&self.type_params)
+    }
+}
+
 impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
     fn walk_schema_stmt(&mut self, schema_stmt: &'ctx mut ast::SchemaStmt) {
+        self.type_params = schema_stmt
+            .type_params
+            .iter()
+            .map(|type_param| type_param.node.clone())
+            .collect();
         if let Some(schema_index_signature) = schema_stmt.index_signature.as_deref_mut() {
             if let kclvm_ast::ast::Type::Function(_) =
                 &mut schema_index_signature.node.value_ty.node
             {
                 schema_index_signature.node.value_ty.node = FUNCTION.to_string().into();
             }
+            self.erase_generic_type(&mut schema_index_signature.node.value_ty.node);
         }
         walk_if_mut!(self, walk_arguments, schema_stmt.args);
         walk_list_mut!(self, walk_call_expr, schema_stmt.decorators);
         walk_list_mut!(self, walk_check_expr, schema_stmt.checks);
         walk_list_mut!(self, walk_stmt, schema_stmt.body);
+        self.type_params.clear();
     }
     fn walk_schema_attr(&mut self, schema_attr: &'ctx mut ast::SchemaAttr) {
         walk_list_mut!(self, walk_call_expr, schema_attr.decorators);
@@ -25,6 +42,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
         if let kclvm_ast::ast::Type::Function(_) = schema_attr.ty.as_ref().node {
             schema_attr.ty.node = FUNCTION.to_string().into();
         }
+        self.erase_generic_type(&mut schema_attr.ty.node);
     }
     fn walk_assign_stmt(&mut self, assign_stmt: &'ctx mut ast::AssignStmt) {
         if let Some(ty) = &mut assign_stmt.ty {
@@ -34,6 +52,9 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
                 }
             }
         }
+        if let Some(ty) = &mut assign_stmt.ty {
+            self.erase_generic_type(&mut ty.node);
+        }
         self.walk_expr(&mut assign_stmt.value.node);
     }
     fn walk_type_alias_stmt(&mut self, type_alias_stmt: &'ctx mut ast::TypeAliasStmt) {
@@ -46,6 +67,7 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
             if let kclvm_ast::ast::Type::Function(_) = ty.as_ref().node {
                 ty.node = FUNCTION.to_string().into();
             }
+            self.erase_generic_type(&mut ty.node);
         }
         for default in arguments.defaults.iter_mut() {
             if let Some(d) = default.as_deref_mut() {
@@ -60,11 +82,13 @@ impl<'ctx> MutSelfMutWalker<'ctx> for TypeErasureTransformer {
             if let kclvm_ast::ast::Type::Function(_) = ty.as_ref().node {
                 ty.node = FUNCTION.to_string().into();
             }
+            self.erase_generic_type(&mut ty.node);
         }
     }
 }
 
-/// Run a pass on AST and change the function type to the `Named("function")` type
+/// Run a pass on AST and change the function type to the `Named("function")` type, and
+/// erase the generic types.
 pub fn type_func_erasure_pass<'ctx>(program: &'ctx mut ast::Program) {
     for (_, modules) in program.pkgs.iter() {
         for module in modules.iter() {
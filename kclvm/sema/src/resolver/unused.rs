@@ -0,0 +1,283 @@
+//! The unused symbol pass reports the unused private variables, the unused schema
+//! definitions in the main package and the unused lambda parameters as warnings.
+//!
+//! The symbols are matched by their names, thus a symbol shadowed by another one
+//! with the same name is regarded as used, and no false positives are reported.
+
+use indexmap::{IndexMap, IndexSet};
+use kclvm_ast::pos::GetPos;
+use kclvm_ast::walker::MutSelfWalker;
+use kclvm_ast::{ast, walk_if, walk_list, MAIN_PKG};
+use kclvm_error::diagnostic::Range;
+use kclvm_error::{Handler, Message, Style, WarningKind};
+
+use crate::pass::{PassContext, SemanticPass};
+
+/// The semantic pass enabled by the `unused_check` option. See [unused_symbol_pass].
+pub(crate) struct UnusedSymbolPass;
+
+impl SemanticPass for UnusedSymbolPass {
+    fn name(&self) -> &'static str {
+        "unused-symbol"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        unused_symbol_pass(ctx.program, handler);
+    }
+}
+
+/// Report the unused symbols of the program into the handler.
+pub fn unused_symbol_pass(program: &ast::Program, handler: &mut Handler) {
+    let mut pkgpaths: Vec<&String> = program.pkgs.keys().collect();
+    pkgpaths.sort();
+    for pkgpath in pkgpaths {
+        let mut used_names = NameCollector::default();
+        let mut vars = IndexMap::new();
+        let mut schemas = IndexMap::new();
+        for module in &program.pkgs[pkgpath] {
+            let module = match program.get_module(module) {
+                Ok(Some(module)) => module,
+                _ => continue,
+            };
+            used_names.walk_module(&module);
+            collect_assigned_vars(&module.body, &mut vars);
+            for stmt in &module.body {
+                if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                    schemas
+                        .entry(schema_stmt.name.node.clone())
+                        .or_insert_with(|| schema_stmt.name.get_span_pos());
+                }
+            }
+            UnusedLambdaChecker {
+                handler: &mut *handler,
+            }
+            .walk_module(&module);
+        }
+        // The public variables are the outputs of the package.
+        for (name, range) in vars {
+            if name.starts_with('_') && !used_names.names.contains(&name) {
+                add_unused_warning(
+                    handler,
+                    WarningKind::UnusedVariableWarning,
+                    range,
+                    format!("Variable '{name}' is defined but never used"),
+                    "Consider removing this variable",
+                );
+            }
+        }
+        // The schemas out of the main package may be used by the other programs.
+        if pkgpath == MAIN_PKG {
+            for (name, range) in schemas {
+                if !used_names.names.contains(&name) {
+                    add_unused_warning(
+                        handler,
+                        WarningKind::UnusedSchemaWarning,
+                        range,
+                        format!("Schema '{name}' is defined but never used"),
+                        "Consider removing this schema",
+                    );
+                }
+            }
+        }
+    }
+}
+
+fn add_unused_warning(
+    handler: &mut Handler,
+    kind: WarningKind,
+    range: Range,
+    message: String,
+    note: &str,
+) {
+    handler.add_warning(
+        kind,
+        &[Message {
+            range,
+            style: Style::LineAndColumn,
+            message,
+            note: Some(note.to_string()),
+            suggested_replacement: None,
+        }],
+    );
+}
+
+/// Collect the variables defined by the assignment statements, not including the
+/// ones in the schema and lambda bodies.
+fn collect_assigned_vars(stmts: &[ast::NodeRef<ast::Stmt>], vars: &mut IndexMap<String, Range>) {
+    for stmt in stmts {
+        match &stmt.node {
+            ast::Stmt::Assign(assign_stmt) => {
+                for target in &assign_stmt.targets {
+                    if target.node.paths.is_empty() {
+                        vars.entry(target.node.get_name().to_string())
+                            .or_insert_with(|| target.node.name.get_span_pos());
+                    }
+                }
+            }
+            ast::Stmt::If(if_stmt) => {
+                collect_assigned_vars(&if_stmt.body, vars);
+                collect_assigned_vars(&if_stmt.orelse, vars);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// Report the unused parameters and local variables of the lambda expressions.
+struct UnusedLambdaChecker<'a> {
+    handler: &'a mut Handler,
+}
+
+impl MutSelfWalker for UnusedLambdaChecker<'_> {
+    fn walk_lambda_expr(&mut self, lambda_expr: &ast::LambdaExpr) {
+        let mut used_names = NameCollector::default();
+        walk_list!(used_names, walk_stmt, lambda_expr.body);
+        if let Some(args) = &lambda_expr.args {
+            for arg in &args.node.args {
+                let name = arg.node.get_name();
+                if !name.starts_with('_') && !used_names.names.contains(&name) {
+                    add_unused_warning(
+                        self.handler,
+                        WarningKind::UnusedParameterWarning,
+                        arg.get_span_pos(),
+                        format!("Parameter '{name}' is never used"),
+                        "Consider removing this parameter or prefixing it with '_'",
+                    );
+                }
+            }
+        }
+        let mut vars = IndexMap::new();
+        collect_assigned_vars(&lambda_expr.body, &mut vars);
+        for (name, range) in vars {
+            if !name.starts_with('_') && !used_names.names.contains(&name) {
+                add_unused_warning(
+                    self.handler,
+                    WarningKind::UnusedVariableWarning,
+                    range,
+                    format!("Variable '{name}' is defined but never used"),
+                    "Consider removing this variable",
+                );
+            }
+        }
+        // Check the nested lambda expressions.
+        walk_list!(self, walk_stmt, lambda_expr.body);
+    }
+}
+
+/// Collect the names referenced by the identifiers and the type annotations, not
+/// including the names at the definition sites e.g., the assignment targets, the
+/// lambda parameters and the keyword argument names.
+#[derive(Default)]
+struct NameCollector {
+    names: IndexSet<String>,
+}
+
+impl NameCollector {
+    fn walk_ty(&mut self, ty: &ast::Type) {
+        match ty {
+            ast::Type::Named(identifier) => self.walk_identifier(identifier),
+            ast::Type::List(list_ty) => {
+                if let Some(ty) = &list_ty.inner_type {
+                    self.walk_ty(&ty.node)
+                }
+            }
+            ast::Type::Dict(dict_ty) => {
+                if let Some(ty) = &dict_ty.key_type {
+                    self.walk_ty(&ty.node)
+                }
+                if let Some(ty) = &dict_ty.value_type {
+                    self.walk_ty(&ty.node)
+                }
+            }
+            ast::Type::Union(union_ty) => {
+                for ty in &union_ty.type_elements {
+                    self.walk_ty(&ty.node)
+                }
+            }
+            ast::Type::Function(func_ty) => {
+                for ty in func_ty.params_ty.iter().flatten() {
+                    self.walk_ty(&ty.node)
+                }
+                if let Some(ty) = &func_ty.ret_ty {
+                    self.walk_ty(&ty.node)
+                }
+            }
+            ast::Type::Generic(generic_ty) => {
+                self.walk_identifier(&generic_ty.name);
+                for ty in &generic_ty.args {
+                    self.walk_ty(&ty.node)
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+impl MutSelfWalker for NameCollector {
+    fn walk_identifier(&mut self, identifier: &ast::Identifier) {
+        if let Some(name) = identifier.names.first() {
+            self.names.insert(name.node.clone());
+        }
+    }
+    fn walk_type_alias_stmt(&mut self, type_alias_stmt: &ast::TypeAliasStmt) {
+        self.walk_ty(&type_alias_stmt.ty.node);
+    }
+    fn walk_assign_stmt(&mut self, assign_stmt: &ast::AssignStmt) {
+        for target in &assign_stmt.targets {
+            self.walk_target(&target.node)
+        }
+        if let Some(ty) = &assign_stmt.ty {
+            self.walk_ty(&ty.node);
+        }
+        self.walk_expr(&assign_stmt.value.node);
+    }
+    fn walk_schema_attr(&mut self, schema_attr: &ast::SchemaAttr) {
+        walk_list!(self, walk_call_expr, schema_attr.decorators);
+        walk_if!(self, walk_expr, schema_attr.value);
+        self.walk_ty(&schema_attr.ty.node);
+    }
+    fn walk_schema_stmt(&mut self, schema_stmt: &ast::SchemaStmt) {
+        walk_if!(self, walk_identifier, schema_stmt.parent_name);
+        walk_if!(self, walk_identifier, schema_stmt.for_host_name);
+        walk_if!(self, walk_arguments, schema_stmt.args);
+        if let Some(schema_index_signature) = &schema_stmt.index_signature {
+            self.walk_ty(&schema_index_signature.node.key_ty.node);
+            self.walk_ty(&schema_index_signature.node.value_ty.node);
+            walk_if!(self, walk_expr, schema_index_signature.node.value);
+        }
+        walk_list!(self, walk_identifier, schema_stmt.mixins);
+        walk_list!(self, walk_call_expr, schema_stmt.decorators);
+        walk_list!(self, walk_check_expr, schema_stmt.checks);
+        walk_list!(self, walk_stmt, schema_stmt.body);
+    }
+    fn walk_quant_expr(&mut self, quant_expr: &ast::QuantExpr) {
+        self.walk_expr(&quant_expr.target.node);
+        self.walk_expr(&quant_expr.test.node);
+        walk_if!(self, walk_expr, quant_expr.if_cond);
+    }
+    fn walk_selector_expr(&mut self, selector_expr: &ast::SelectorExpr) {
+        self.walk_expr(&selector_expr.value.node);
+    }
+    fn walk_comp_clause(&mut self, comp_clause: &ast::CompClause) {
+        self.walk_expr(&comp_clause.iter.node);
+        walk_list!(self, walk_expr, comp_clause.ifs);
+    }
+    fn walk_lambda_expr(&mut self, lambda_expr: &ast::LambdaExpr) {
+        walk_if!(self, walk_arguments, lambda_expr.args);
+        if let Some(ty) = &lambda_expr.return_ty {
+            self.walk_ty(&ty.node);
+        }
+        walk_list!(self, walk_stmt, lambda_expr.body);
+    }
+    fn walk_keyword(&mut self, keyword: &ast::Keyword) {
+        walk_if!(self, walk_expr, keyword.value);
+    }
+    fn walk_arguments(&mut self, arguments: &ast::Arguments) {
+        for default in arguments.defaults.iter().flatten() {
+            self.walk_expr(&default.node)
+        }
+        for ty in arguments.ty_list.iter().flatten() {
+            self.walk_ty(&ty.node)
+        }
+    }
+}
@@ -24,6 +24,11 @@ impl<'ctx> Resolver<'_> {
         }
         if names.len() == 1 {
             let name = &names[0];
+            if !self.ctx.l_value {
+                if let Some(ty) = self.find_narrowed_type(name) {
+                    return vec![ty];
+                }
+            }
             let scope_schema_ty = self.ctx.schema.clone();
             if let Some(schema_ty) = &scope_schema_ty {
                 let mut schema_ty = schema_ty.borrow_mut();
@@ -127,12 +132,22 @@ impl<'ctx> Resolver<'_> {
             let mut tys = self.resolve_var(&[names[0].clone()], pkgpath, range.clone());
             let mut ty = tys[0].clone();
 
-            for name in &names[1..] {
+            for (index, name) in names.iter().enumerate().skip(1) {
                 // Store and config attr check
                 if self.ctx.l_value {
-                    self.must_check_config_attr(name, &ty, &range, None);
+                    let replace = |sugg: &str| {
+                        let mut names = names.to_vec();
+                        names[index] = sugg.to_string();
+                        names.join(".")
+                    };
+                    self.must_check_config_attr(name, &ty, &range, None, Some(&replace));
                 }
                 ty = self.load_attr(ty, name, range.clone());
+                if !self.ctx.l_value {
+                    if let Some(narrowed_ty) = self.find_narrowed_type(&names[..=index].join(".")) {
+                        ty = narrowed_ty;
+                    }
+                }
                 tys.push(ty.clone());
             }
             tys
@@ -164,13 +179,13 @@ impl<'ctx> Resolver<'_> {
                 match path {
                     ast::MemberOrIndex::Member(member) => {
                         let attr = &member.node;
-                        self.must_check_config_attr(attr, &ty, &range, None);
+                        self.must_check_config_attr(attr, &ty, &range, None, None);
                         ty = self.load_attr(ty, attr, range.clone());
                         tys.push(ty.clone());
                     }
                     ast::MemberOrIndex::Index(index) => {
                         if let ast::Expr::StringLit(string_lit) = &index.node {
-                            self.must_check_config_attr(&string_lit.value, &ty, &range, None);
+                            self.must_check_config_attr(&string_lit.value, &ty, &range, None, None);
                         }
                         ty = self.subscript_index(ty, index, range.clone());
                         tys.push(ty.clone());
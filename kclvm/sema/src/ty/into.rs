@@ -196,6 +196,8 @@ impl From<ast::Type> for Type {
                 false,
                 None,
             ),
+            // The type arguments of the generic type are resolved with the scope.
+            ast::Type::Generic(generic_ty) => Type::named(&generic_ty.name.get_name()),
         }
     }
 }
@@ -80,6 +80,16 @@ impl Type {
                 .map(|ty| ty.ty_str())
                 .collect::<Vec<String>>()
                 .join(" | "),
+            TypeKind::Schema(schema_ty) if !schema_ty.type_args.is_empty() => format!(
+                "{}<{}>",
+                schema_ty.name,
+                schema_ty
+                    .type_args
+                    .iter()
+                    .map(|ty| ty.ty_str())
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ),
             TypeKind::Schema(schema_ty) => schema_ty.name.to_string(),
             TypeKind::NumberMultiplier(number_multiplier) => number_multiplier.ty_str(),
             TypeKind::Function(func_ty) => func_ty.ty_str(),
@@ -264,9 +274,34 @@ pub struct SchemaType {
     pub index_signature: Option<Box<SchemaIndexSignature>>,
     /// Schema decorators including self and attribute decorators.
     pub decorators: Vec<Decorator>,
+    /// The type parameters of the generic schema e.g., `T` in `schema Box<T>`.
+    pub type_params: Vec<String>,
+    /// The type arguments of the generic schema instantiation e.g., `str` in `Box<str>`.
+    pub type_args: Vec<TypeRef>,
+    /// The attribute types which reference the type parameters, where the type parameters
+    /// are denoted by the named types. The types in `attrs` are these types with the type
+    /// parameters replaced by the type arguments or the any type.
+    pub generic_attrs: IndexMap<String, TypeRef>,
 }
 
 impl SchemaType {
+    /// Whether the schema is a generic schema with type parameters.
+    #[inline]
+    pub fn is_generic(&self) -> bool {
+        !self.type_params.is_empty()
+    }
+    /// Instantiate the generic schema with the type arguments e.g., `Box<str>` for
+    /// `schema Box<T>`, where the missing type arguments are regarded as the any type.
+    pub fn instantiate(&self, type_args: &[TypeRef]) -> SchemaType {
+        let mut schema_ty = self.clone();
+        for (name, ty) in &self.generic_attrs {
+            if let Some(attr) = schema_ty.attrs.get_mut(name) {
+                attr.ty = substitute_type_params(ty, &self.type_params, type_args);
+            }
+        }
+        schema_ty.type_args = type_args.to_vec();
+        schema_ty
+    }
     /// Get the object type string with @pkgpath prefix.
     pub fn ty_str_with_at_pkgpath_prefix(&self) -> String {
         if self.pkgpath.is_empty() || self.pkgpath == MAIN_PKG {
@@ -307,6 +342,14 @@ impl SchemaType {
         self.get_obj_of_attr(attr).map(|attr| attr.ty.clone())
     }
 
+    /// Get the deprecation of the schema declared by the `@deprecated` decorator.
+    pub fn get_deprecation(&self) -> Option<&Deprecation> {
+        self.decorators
+            .iter()
+            .filter(|d| d.target == DecoratorTarget::Schema)
+            .find_map(|d| d.deprecation.as_ref())
+    }
+
     #[inline]
     pub fn get_obj_of_attr(&self, attr: &str) -> Option<&SchemaAttr> {
         match self.attrs.get(attr) {
@@ -320,6 +363,45 @@ impl SchemaType {
         }
     }
 
+    /// Get all the attributes of the schema including the attributes inherited from
+    /// the base schemas and the mixins, together with the schema where each attribute
+    /// is defined. The attributes defined in the schema override the mixin attributes,
+    /// and the mixin attributes override the base schema attributes.
+    pub fn get_inherited_attrs(&self) -> IndexMap<String, InheritedSchemaAttr> {
+        let mut attrs = self
+            .base
+            .as_ref()
+            .map(|base| base.get_inherited_attrs())
+            .unwrap_or_default();
+        // The former mixin attributes take precedence over the latter ones.
+        let mut mixin_attrs: IndexMap<String, InheritedSchemaAttr> = IndexMap::new();
+        for mixin in &self.mixins {
+            for (name, attr) in mixin.get_inherited_attrs() {
+                mixin_attrs.entry(name).or_insert(attr);
+            }
+        }
+        attrs.extend(mixin_attrs);
+        let defined_in = self.full_ty_str();
+        for (name, attr) in &self.attrs {
+            // The mixin attributes are also recorded in the schema attributes with
+            // the same ranges, skip them to keep where they are defined.
+            if attrs
+                .get(name)
+                .map_or(false, |inherited| inherited.attr.range == attr.range)
+            {
+                continue;
+            }
+            attrs.insert(
+                name.to_string(),
+                InheritedSchemaAttr {
+                    attr: attr.clone(),
+                    defined_in: defined_in.clone(),
+                },
+            );
+        }
+        attrs
+    }
+
     pub fn key_ty(&self) -> TypeRef {
         Arc::new(Type::STR)
     }
@@ -389,6 +471,43 @@ impl SchemaType {
     }
 }
 
+/// Replace the type parameters denoted by the named types in the type with the type
+/// arguments, and the type parameters without type arguments are replaced by the any type.
+pub fn substitute_type_params(
+    ty: &TypeRef,
+    type_params: &[String],
+    type_args: &[TypeRef],
+) -> TypeRef {
+    match &ty.kind {
+        TypeKind::Named(name) => match type_params.iter().position(|param| param == name) {
+            Some(i) => type_args.get(i).cloned().unwrap_or_else(Type::any_ref),
+            None => ty.clone(),
+        },
+        TypeKind::List(item_ty) => {
+            Type::list_ref(substitute_type_params(item_ty, type_params, type_args))
+        }
+        TypeKind::Dict(DictType { key_ty, val_ty, .. }) => Type::dict_ref(
+            substitute_type_params(key_ty, type_params, type_args),
+            substitute_type_params(val_ty, type_params, type_args),
+        ),
+        TypeKind::Union(types) => Type::union_ref(
+            &types
+                .iter()
+                .map(|ty| substitute_type_params(ty, type_params, type_args))
+                .collect::<Vec<TypeRef>>(),
+        ),
+        TypeKind::Schema(schema_ty) if !schema_ty.type_args.is_empty() => {
+            let args: Vec<TypeRef> = schema_ty
+                .type_args
+                .iter()
+                .map(|ty| substitute_type_params(ty, type_params, type_args))
+                .collect();
+            Arc::new(Type::schema(schema_ty.instantiate(&args)))
+        }
+        _ => ty.clone(),
+    }
+}
+
 pub fn full_ty_str(pkgpath: &str, name: &str) -> String {
     if pkgpath.is_empty() || pkgpath == MAIN_PKG {
         name.to_string()
@@ -413,6 +532,21 @@ pub struct SchemaAttr {
     pub decorators: Vec<Decorator>,
 }
 
+impl SchemaAttr {
+    /// Get the deprecation of the attribute declared by the `@deprecated` decorator.
+    pub fn get_deprecation(&self) -> Option<&Deprecation> {
+        self.decorators.iter().find_map(|d| d.deprecation.as_ref())
+    }
+}
+
+/// A schema attribute with the schema where it is defined.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InheritedSchemaAttr {
+    pub attr: SchemaAttr,
+    /// The full type string of the schema which defines the attribute e.g., `pkg.Base`.
+    pub defined_in: String,
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct SchemaIndexSignature {
     pub key_name: Option<String>,
@@ -462,6 +596,50 @@ pub struct Decorator {
     pub arguments: Vec<String>,
     /// The decorator keyword mapping values.
     pub keywords: HashMap<String, String>,
+    /// The deprecation information of the `@deprecated` decorator.
+    pub deprecation: Option<Deprecation>,
+}
+
+/// The deprecation information of a schema or schema attribute, which is declared by
+/// the `@deprecated(version, reason, strict, since, replacement)` decorator.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Deprecation {
+    /// The version since which it is deprecated, denoted by `since` or `version`.
+    pub since: String,
+    pub reason: String,
+    /// The schema or attribute name used instead.
+    pub replacement: String,
+    /// Whether it is an error to use it at runtime.
+    pub strict: bool,
+}
+
+impl Default for Deprecation {
+    fn default() -> Self {
+        Self {
+            since: Default::default(),
+            reason: Default::default(),
+            replacement: Default::default(),
+            strict: true,
+        }
+    }
+}
+
+impl Deprecation {
+    /// Get the deprecation message e.g., `'Person' is deprecated since version v0.1,
+    /// use 'Human' instead`.
+    pub fn message(&self, name: &str) -> String {
+        let mut msg = format!("'{name}' is deprecated");
+        if !self.since.is_empty() {
+            msg.push_str(&format!(" since version {}", self.since));
+        }
+        if !self.reason.is_empty() {
+            msg.push_str(&format!(", {}", self.reason));
+        }
+        if !self.replacement.is_empty() {
+            msg.push_str(&format!(", use '{}' instead", self.replacement));
+        }
+        msg
+    }
 }
 
 #[derive(Debug, Clone, PartialEq)]
@@ -100,10 +100,17 @@ pub fn equal(ty_lhs: TypeRef, ty_rhs: TypeRef) -> bool {
     ty_lhs.kind == ty_rhs.kind
 }
 
-/// Whether the schema is sub schema of another schema.
+/// Whether the schema is sub schema of another schema. The type arguments of the same
+/// generic schema are covariant e.g., `Box<int>` is the sub schema of `Box<float>`.
 pub fn is_sub_schema_of(schema_ty_lhs: &SchemaType, schema_ty_rhs: &SchemaType) -> bool {
     if schema_ty_lhs.full_ty_str() == schema_ty_rhs.full_ty_str() {
-        true
+        schema_ty_lhs.type_args.is_empty()
+            || schema_ty_rhs.type_args.is_empty()
+            || schema_ty_lhs
+                .type_args
+                .iter()
+                .zip(&schema_ty_rhs.type_args)
+                .all(|(lhs, rhs)| subsume(lhs.clone(), rhs.clone(), false))
     } else {
         match &schema_ty_lhs.base {
             Some(base) => is_sub_schema_of(base, schema_ty_rhs),
@@ -8,6 +8,7 @@ use kclvm_api::{ExecProgramArgs, API};
 
 mod capi;
 pub use capi::*;
+use kclvm_cmd::run::OutputWrittenError;
 use kclvm_parser::ParseSessionRef;
 use kclvm_runner::exec_program;
 use kclvm_runtime::PanicInfo;
@@ -90,11 +91,14 @@ pub unsafe extern "C" fn kclvm_cli_main(argc: c_int, argv: *const *const c_char)
         Ok(result) => match result {
             Ok(()) => Box::into_raw(Box::new(ExitCode::SUCCESS)),
             Err(err) => {
-                let backtrace = format!("{}", err.backtrace());
-                if backtrace.is_empty() || backtrace.contains("disabled backtrace") {
-                    eprintln!("{err}");
-                } else {
-                    eprintln!("{err}\nStack backtrace:\n{backtrace}");
+                // The structured diagnostics output has already been written to the stdout.
+                if err.downcast_ref::<OutputWrittenError>().is_none() {
+                    let backtrace = format!("{}", err.backtrace());
+                    if backtrace.is_empty() || backtrace.contains("disabled backtrace") {
+                        eprintln!("{err}");
+                    } else {
+                        eprintln!("{err}\nStack backtrace:\n{backtrace}");
+                    }
                 }
                 Box::into_raw(Box::new(ExitCode::FAILURE))
             }
@@ -1,4 +1,4 @@
-use crate::error::{self, Error, ErrorImpl};
+use crate::error::{self, Error, ErrorImpl, Location};
 use crate::libyaml::error::Mark;
 use crate::libyaml::parser::{MappingStart, Scalar, ScalarStyle, SequenceStart};
 use crate::libyaml::tag::Tag;
@@ -94,6 +94,24 @@ impl<'de> Deserializer<'de> {
         Deserializer { progress }
     }
 
+    /// Returns the location of the first alias e.g., `*anchor` in the document, and
+    /// `None` when there is no alias. Only the deserializers of the documents yielded
+    /// by iterating a deserializer are loaded, and `None` is returned for others.
+    pub fn first_alias_location(&self) -> Option<Location> {
+        match &self.progress {
+            Progress::Document(document) => {
+                document
+                    .events
+                    .iter()
+                    .find_map(|(event, mark)| match event {
+                        Event::Alias(_) => Some(Location::from_mark(*mark)),
+                        _ => None,
+                    })
+            }
+            _ => None,
+        }
+    }
+
     fn de<T>(
         self,
         f: impl for<'document> FnOnce(&mut DeserializerFromEvents<'de, 'document>) -> Result<T>,
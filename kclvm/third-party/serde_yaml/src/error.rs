@@ -71,7 +71,7 @@ impl Location {
 
     // This is to keep decoupled with the yaml crate
     #[doc(hidden)]
-    fn from_mark(mark: libyaml::Mark) -> Self {
+    pub(crate) fn from_mark(mark: libyaml::Mark) -> Self {
         Location {
             index: mark.index() as usize,
             // `line` and `column` returned from libyaml are 0-indexed but all error messages add +1 to this value
@@ -52,7 +52,10 @@ pub fn server_capabilities(client_caps: &ClientCapabilities) -> ServerCapabiliti
                         // Advertise support for all built-in CodeActionKinds.
                         // Ideally we would base this off of the client capabilities
                         // but the client is supposed to fall back gracefully for unknown values.
-                        code_action_kinds: Some(vec![CodeActionKind::QUICKFIX]),
+                        code_action_kinds: Some(vec![
+                            CodeActionKind::QUICKFIX,
+                            CodeActionKind::SOURCE_ORGANIZE_IMPORTS,
+                        ]),
                         resolve_provider: None,
                         work_done_progress_options: Default::default(),
                     })
@@ -4,7 +4,7 @@ use kclvm_driver::{lookup_compile_workspace, toolchain};
 use kclvm_error::Diagnostic;
 use kclvm_parser::{
     entry::get_normalized_k_files_from_paths, load_all_files_under_paths, KCLModuleCache,
-    LoadProgramOptions, ParseSessionRef,
+    LoadProgramOptions, ParseMode, ParseSessionRef,
 };
 use kclvm_query::query::filter_pkg_schemas;
 use kclvm_sema::{
@@ -41,6 +41,8 @@ pub fn compile(
     // Ignore the kcl plugin sematic check.
     let mut opts = opts.unwrap_or_default();
     opts.load_plugins = true;
+    // Keep the invalid source code in the AST to provide completion and hover.
+    opts.mode = ParseMode::Recover;
     // Get input files code from vfs
     let normalized_files = match get_normalized_k_files_from_paths(files, &opts) {
         Ok(file_list) => file_list,
@@ -98,12 +100,12 @@ pub fn compile(
         }
     }
 
-    let mut program =
+    let (mut program, file_hashes) =
         match load_all_files_under_paths(sess.clone(), &files, Some(opts), params.module_cache) {
-            Ok(r) => r.program,
+            Ok(r) => (r.program, r.file_hashes),
             Err(e) => return (diags, Err(anyhow::anyhow!("Parse failed: {:?}", e))),
         };
-    diags.extend(sess.1.read().diagnostics.clone());
+    diags.extend(sess.handler.read().diagnostics.clone());
 
     // Resolver
     if let Some(cached_scope) = params.scope_cache.as_ref() {
@@ -121,6 +123,7 @@ pub fn compile(
         kclvm_sema::resolver::Options {
             merge_program: false,
             type_erasure: false,
+            file_hashes,
             ..Default::default()
         },
         params.scope_cache.clone(),
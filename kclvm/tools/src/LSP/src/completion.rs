@@ -1433,6 +1433,13 @@ mod tests {
                 "file",
                 "template",
                 "runtime",
+                "decimal",
+                "toml",
+                "jsonpath",
+                "semver",
+                "url",
+                "random",
+                "uuid",
             ]
             .iter()
             .map(|name| KCLCompletionItem {
@@ -675,7 +675,7 @@ mod tests {
         let expect_content = vec![
             MarkedString::LanguageString(LanguageString {
                 language: "KCL".to_string(),
-                value: "function deprecated(version: str, reason: str, strict: bool) -> any".to_string(),
+                value: "function deprecated(version: str, reason: str, strict: bool, since: str, replacement: str) -> any".to_string(),
             }),
             MarkedString::String(
                 "This decorator is used to get the deprecation message according to the wrapped key-value pair.".to_string(),
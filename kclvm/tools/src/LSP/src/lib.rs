@@ -7,6 +7,7 @@ pub mod formatting;
 pub mod goto_def;
 pub mod hover;
 pub mod inlay_hints;
+pub mod organize_imports;
 pub mod quick_fix;
 pub mod rename;
 pub mod request;
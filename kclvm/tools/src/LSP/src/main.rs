@@ -13,6 +13,7 @@ mod goto_def;
 mod hover;
 mod inlay_hints;
 mod notification;
+mod organize_imports;
 mod quick_fix;
 mod request;
 mod semantic_token;
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+
+use kclvm_driver::{lookup_compile_workspace, toolchain};
+use kclvm_tools::imports::{organize_imports, OrganizeImportsOptions};
+use lsp_types::{CodeAction, CodeActionKind, CodeActionOrCommand, Position, Range, TextEdit, Url};
+
+/// Returns the code action which sorts the imports into groups, and removes the
+/// duplicate and unused imports of the file, which is None when the imports are
+/// already organized.
+pub fn organize_imports_action(
+    uri: &Url,
+    file: &str,
+    src: &str,
+) -> anyhow::Result<Option<CodeActionOrCommand>> {
+    let (_, load_opts, _) = lookup_compile_workspace(&toolchain::default(), file, true);
+    let opts = OrganizeImportsOptions {
+        remove_unused: true,
+        package_maps: load_opts.map(|opts| opts.package_maps).unwrap_or_default(),
+    };
+    let (source, is_changed) = organize_imports(file, src, &opts)
+        .map_err(|err| anyhow::anyhow!("Organizing imports failed: {}", err))?;
+    if !is_changed {
+        return Ok(None);
+    }
+    let mut changes = HashMap::new();
+    changes.insert(
+        uri.clone(),
+        vec![TextEdit {
+            range: Range::new(
+                Position::new(0, 0),
+                Position::new(i32::MAX as u32, i32::MAX as u32),
+            ),
+            new_text: source,
+        }],
+    );
+    Ok(Some(CodeActionOrCommand::CodeAction(CodeAction {
+        title: "Organize imports".to_string(),
+        kind: Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS),
+        edit: Some(lsp_types::WorkspaceEdit {
+            changes: Some(changes),
+            ..Default::default()
+        }),
+        ..Default::default()
+    })))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::organize_imports_action;
+    use lsp_types::{CodeActionKind, CodeActionOrCommand, Url};
+
+    #[test]
+    fn organize_imports_action_test() {
+        let path = PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("src")
+            .join("test_data")
+            .join("organize_imports")
+            .join("main.k");
+        let file = path.to_str().unwrap();
+        let uri = Url::from_file_path(file).unwrap();
+        let src = std::fs::read_to_string(file).unwrap();
+
+        let action = organize_imports_action(&uri, file, &src).unwrap().unwrap();
+        match action {
+            CodeActionOrCommand::CodeAction(action) => {
+                assert_eq!(action.kind, Some(CodeActionKind::SOURCE_ORGANIZE_IMPORTS));
+                let edits = &action.edit.unwrap().changes.unwrap()[&uri];
+                assert_eq!(
+                    edits[0].new_text,
+                    "import math\nimport regex\n\na = math.log(1) + regex.match(\"a\", \"a\")\n"
+                );
+            }
+            CodeActionOrCommand::Command(_) => unreachable!(),
+        }
+        // The imports are already organized.
+        let src = "import math\n\na = math.log(1)\n";
+        assert!(organize_imports_action(&uri, file, src).unwrap().is_none());
+    }
+}
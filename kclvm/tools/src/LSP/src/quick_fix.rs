@@ -9,6 +9,30 @@ use serde_json::Value;
 pub fn quick_fix(uri: &Url, diags: &[Diagnostic]) -> Vec<lsp_types::CodeActionOrCommand> {
     let mut code_actions: Vec<lsp_types::CodeActionOrCommand> = vec![];
     for diag in diags {
+        let fixes = extract_fixes(&diag.data);
+        if !fixes.is_empty() {
+            for (edit, is_preferred) in fixes {
+                let title = if edit.new_text.is_empty() {
+                    "Consider removing the problematic code".to_string()
+                } else {
+                    format!("Consider replacing it with `{}`", edit.new_text)
+                };
+                let mut changes = HashMap::new();
+                changes.insert(uri.clone(), vec![edit]);
+                code_actions.push(CodeActionOrCommand::CodeAction(CodeAction {
+                    title,
+                    kind: Some(CodeActionKind::QUICKFIX),
+                    diagnostics: Some(vec![diag.clone()]),
+                    edit: Some(lsp_types::WorkspaceEdit {
+                        changes: Some(changes),
+                        ..Default::default()
+                    }),
+                    is_preferred: Some(is_preferred),
+                    ..Default::default()
+                }));
+            }
+            continue;
+        }
         if let Some(code) = &diag.code {
             if let Some(id) = convert_code_to_kcl_diag_id(code) {
                 match id {
@@ -123,6 +147,31 @@ pub fn quick_fix(uri: &Url, diags: &[Diagnostic]) -> Vec<lsp_types::CodeActionOr
     code_actions
 }
 
+/// Extract the fix-its attached by [crate::to_lsp::kcl_diag_to_lsp_diags] with whether
+/// they are preferred.
+fn extract_fixes(data: &Option<Value>) -> Vec<(TextEdit, bool)> {
+    data.as_ref()
+        .and_then(|data| data.get("fixes"))
+        .and_then(|fixes| fixes.as_array())
+        .map(|fixes| {
+            fixes
+                .iter()
+                .filter_map(|fix| {
+                    let edit = TextEdit {
+                        range: serde_json::from_value(fix.get("range")?.clone()).ok()?,
+                        new_text: fix.get("new_text")?.as_str()?.to_string(),
+                    };
+                    let is_preferred = fix
+                        .get("is_preferred")
+                        .and_then(|v| v.as_bool())
+                        .unwrap_or_default();
+                    Some((edit, is_preferred))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
 fn extract_suggested_replacements(data: &Option<Value>) -> Vec<String> {
     data.as_ref()
         .and_then(|data| match data {
@@ -151,6 +200,26 @@ pub(crate) fn convert_code_to_kcl_diag_id(code: &NumberOrString) -> Option<Diagn
             "ImportPositionWarning" => {
                 Some(DiagnosticId::Warning(WarningKind::ImportPositionWarning))
             }
+            "UnusedVariableWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::UnusedVariableWarning))
+            }
+            "UnusedSchemaWarning" => Some(DiagnosticId::Warning(WarningKind::UnusedSchemaWarning)),
+            "UnusedParameterWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::UnusedParameterWarning))
+            }
+            "NonExhaustiveWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::NonExhaustiveWarning))
+            }
+            "UnreachableBranchWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::UnreachableBranchWarning))
+            }
+            "DeprecatedWarning" => Some(DiagnosticId::Warning(WarningKind::DeprecatedWarning)),
+            "NamingConventionWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::NamingConventionWarning))
+            }
+            "ConstantConditionWarning" => {
+                Some(DiagnosticId::Warning(WarningKind::ConstantConditionWarning))
+            }
             _ => None,
         },
     }
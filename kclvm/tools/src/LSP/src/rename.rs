@@ -1,9 +1,11 @@
 use crate::state::KCLVfs;
+use crate::to_lsp::lsp_pos;
 use crate::word_index::{build_virtual_word_index, VirtualLocation};
 use crate::{from_lsp::kcl_pos, goto_def::find_def};
 use anyhow::{anyhow, Result};
 use kclvm_ast::ast::{self, Program};
 use kclvm_error::diagnostic;
+use kclvm_error::Position as KCLPos;
 use kclvm_parser::{load_program, LoadProgramOptions, ParseSessionRef};
 use kclvm_query::{path::parse_attribute_path, selector::parse_symbol_selector_spec};
 use kclvm_sema::{
@@ -24,6 +26,35 @@ pub fn rename_symbol_on_file(
     file_paths: &[String],
     new_name: String,
 ) -> Result<Vec<String>> {
+    let changes = rename_on_file(
+        pkg_root,
+        RenameTarget::SymbolPath(symbol_path),
+        file_paths,
+        new_name,
+        false,
+    )?;
+    Ok(changes.into_keys().collect())
+}
+
+/// RenameTarget denotes the symbol to be renamed.
+#[derive(Debug, Clone, Copy)]
+pub enum RenameTarget<'a> {
+    /// The symbol path in the format of: `pkg.sub_pkg:name.sub_name`
+    SymbolPath(&'a str),
+    /// The position of the symbol or one of its references.
+    Position(&'a KCLPos),
+}
+
+/// [`rename_on_file`] will find all the occurrences of the target symbol in the given files
+/// and return the text edit actions to rename them keyed by the file paths. The edits are
+/// applied to the files unless `dry_run` is set.
+pub fn rename_on_file(
+    pkg_root: &str,
+    target: RenameTarget,
+    file_paths: &[String],
+    new_name: String,
+    dry_run: bool,
+) -> Result<HashMap<String, Vec<TextEdit>>> {
     // load file content from file system and save to vfs
     let vfs = KCLVfs::default();
     let mut source_codes = HashMap::<String, String>::new();
@@ -35,14 +66,21 @@ pub fn rename_symbol_on_file(
         );
         source_codes.insert(path.to_string(), content.clone());
     }
-    let changes = rename_symbol(pkg_root, vfs, symbol_path, new_name, VfsPath::new_real_path)?;
-    let new_codes = apply_rename_changes(&changes, source_codes)?;
-    let mut changed_paths = vec![];
-    for (path, content) in new_codes.iter() {
-        fs::write(path.clone(), content)?;
-        changed_paths.push(path.clone());
+    let changes = match target {
+        RenameTarget::SymbolPath(symbol_path) => {
+            rename_symbol(pkg_root, vfs, symbol_path, new_name, VfsPath::new_real_path)?
+        }
+        RenameTarget::Position(pos) => {
+            rename_symbol_at_pos(pkg_root, vfs, pos, new_name, VfsPath::new_real_path)?
+        }
+    };
+    if !dry_run {
+        let new_codes = apply_rename_changes(&changes, source_codes)?;
+        for (path, content) in new_codes.iter() {
+            fs::write(path.clone(), content)?;
+        }
     }
-    Ok(changed_paths)
+    Ok(changes)
 }
 
 /// [`rename_symbol_on_code`] will rename the symbol in the given code
@@ -323,59 +361,7 @@ where
     // 2. get the symbol name and definition range from symbol path
     match select_symbol(&symbol_spec, vfs.clone(), &trans_vfs_path) {
         Some((name, range)) => {
-            // 3. build word index, find refs within given scope
-            // vfs to source code contents
-            let mut source_codes = HashMap::<String, String>::new();
-            let vfs_content = vfs.read();
-            for (file_id, vfspath) in vfs_content.iter() {
-                let content = std::str::from_utf8(vfs_content.file_contents(file_id)).unwrap();
-                source_codes.insert(vfspath.to_string(), content.to_string());
-            }
-            let word_index = build_virtual_word_index(source_codes, true)?;
-            if let Some(locations) = word_index.get(&name) {
-                // 4. filter out the matched refs
-                // 4.1 collect matched words(names) and remove Duplicates of the file paths
-                let file_map = locations.iter().fold(
-                    HashMap::<String, Vec<&VirtualLocation>>::new(),
-                    |mut acc, loc| {
-                        acc.entry(loc.filepath.clone()).or_default().push(loc);
-                        acc
-                    },
-                );
-                let mut refs = vec![];
-                for (fp, locs) in file_map.iter() {
-                    if let Ok((_, gs)) = parse_files_with_vfs(
-                        pkg_root.to_string(),
-                        vec![fp.to_string()],
-                        vfs.clone(),
-                        &trans_vfs_path,
-                    ) {
-                        for loc in locs {
-                            let kcl_pos = kcl_pos(fp, loc.range.start);
-                            if let Some(symbol_ref) = find_def(&kcl_pos, &gs, true) {
-                                if let Some(symbol_def) = gs.get_symbols().get_symbol(symbol_ref) {
-                                    if symbol_def.get_range() == range {
-                                        refs.push(loc)
-                                    }
-                                }
-                            }
-                        }
-                    };
-                }
-                // 5. refs to rename actions
-                let changes = refs.into_iter().fold(HashMap::new(), |mut map, location| {
-                    map.entry(location.filepath.clone())
-                        .or_insert_with(Vec::new)
-                        .push(TextEdit {
-                            range: location.range,
-                            new_text: new_name.clone(),
-                        });
-                    map
-                });
-                Ok(changes)
-            } else {
-                Ok(HashMap::new())
-            }
+            rename_symbol_refs(pkg_root, vfs, &name, &range, &new_name, trans_vfs_path)
         }
         None => Err(anyhow!(
             "get symbol from symbol path failed, {}",
@@ -384,10 +370,136 @@ where
     }
 }
 
+/// Find all the occurrences of the symbol at the position, which is the definition or a
+/// reference of it, and return the text edit actions to rename them.
+///
+/// ## Parameters
+/// - pkg_root: the absolute file path to the root package
+/// - vfs: contains all the files and contents to be renamed
+/// - pos: the position of the symbol, whose line is 1 based and column is 0 based
+/// - new_name: the new name of the symbol
+pub fn rename_symbol_at_pos<F>(
+    pkg_root: &str,
+    vfs: KCLVfs,
+    pos: &KCLPos,
+    new_name: String,
+    trans_vfs_path: F,
+) -> Result<HashMap<String, Vec<TextEdit>>>
+where
+    F: Fn(String) -> VfsPath,
+{
+    // 1. resolve the package of the file with the symbol
+    let pkg_path = PathBuf::from(&pos.filename)
+        .parent()
+        .map(|p| p.display().to_string())
+        .unwrap_or_default();
+    let file_paths = package_path_to_file_path(&pkg_path, vfs.clone());
+    let (_, gs) = parse_files_with_vfs(
+        pkg_root.to_string(),
+        file_paths,
+        vfs.clone(),
+        &trans_vfs_path,
+    )?;
+    // 2. get the symbol name and definition range from the position
+    let symbol = find_def(pos, &gs, true)
+        .and_then(|symbol_ref| gs.get_symbols().get_symbol(symbol_ref))
+        .ok_or_else(|| {
+            anyhow!(
+                "get symbol from position failed, {}:{}:{}",
+                pos.filename,
+                pos.line,
+                pos.column.unwrap_or_default()
+            )
+        })?;
+    rename_symbol_refs(
+        pkg_root,
+        vfs,
+        &symbol.get_name(),
+        &symbol.get_range(),
+        &new_name,
+        trans_vfs_path,
+    )
+}
+
+/// Find all the references of the symbol with the name and the definition range in the vfs,
+/// and return the text edit actions to rename them.
+fn rename_symbol_refs<F>(
+    pkg_root: &str,
+    vfs: KCLVfs,
+    name: &str,
+    range: &diagnostic::Range,
+    new_name: &str,
+    trans_vfs_path: F,
+) -> Result<HashMap<String, Vec<TextEdit>>>
+where
+    F: Fn(String) -> VfsPath,
+{
+    // 3. build word index, find refs within given scope
+    // vfs to source code contents
+    let mut source_codes = HashMap::<String, String>::new();
+    let vfs_content = vfs.read();
+    for (file_id, vfspath) in vfs_content.iter() {
+        let content = std::str::from_utf8(vfs_content.file_contents(file_id)).unwrap();
+        source_codes.insert(vfspath.to_string(), content.to_string());
+    }
+    let word_index = build_virtual_word_index(source_codes, true)?;
+    if let Some(locations) = word_index.get(name) {
+        // 4. filter out the matched refs
+        // 4.1 collect matched words(names) and remove Duplicates of the file paths
+        let file_map = locations.iter().fold(
+            HashMap::<String, Vec<&VirtualLocation>>::new(),
+            |mut acc, loc| {
+                acc.entry(loc.filepath.clone()).or_default().push(loc);
+                acc
+            },
+        );
+        let mut changes = HashMap::<String, Vec<TextEdit>>::new();
+        for (fp, locs) in file_map.iter() {
+            if let Ok((_, gs)) = parse_files_with_vfs(
+                pkg_root.to_string(),
+                vec![fp.to_string()],
+                vfs.clone(),
+                &trans_vfs_path,
+            ) {
+                // 4.2 rename the target symbol referenced in the file
+                for loc in locs {
+                    let kcl_pos = kcl_pos(fp, loc.range.start);
+                    let symbol_ref = match find_def(&kcl_pos, &gs, true) {
+                        Some(symbol_ref) => symbol_ref,
+                        None => continue,
+                    };
+                    match gs.get_symbols().get_symbol(symbol_ref) {
+                        Some(symbol_def) if symbol_def.get_range() == *range => {}
+                        _ => continue,
+                    }
+                    // 5. the edits in the file to rename actions
+                    let edits = gs
+                        .rename_symbol(symbol_ref, new_name)
+                        .into_iter()
+                        .filter(|edit| edit.range.0.filename == kcl_pos.filename)
+                        .map(|edit| TextEdit {
+                            range: Range {
+                                start: lsp_pos(&edit.range.0),
+                                end: lsp_pos(&edit.range.1),
+                            },
+                            new_text: edit.new_text,
+                        });
+                    changes.entry(fp.clone()).or_default().extend(edits);
+                    break;
+                }
+            };
+        }
+        Ok(changes)
+    } else {
+        Ok(HashMap::new())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use kclvm_ast::ast;
     use kclvm_error::diagnostic;
+    use kclvm_error::Position as KCLPos;
     use lsp_types::{Position, Range, TextEdit};
     use maplit::hashmap;
     use std::collections::{HashMap, HashSet};
@@ -397,8 +509,8 @@ mod tests {
     use crate::rename::rename_symbol_on_code;
 
     use super::{
-        apply_rename_changes, package_path_to_file_path, rename_symbol, rename_symbol_on_file,
-        select_symbol,
+        apply_rename_changes, package_path_to_file_path, rename_symbol, rename_symbol_at_pos,
+        rename_symbol_on_file, select_symbol,
     };
 
     use crate::state::KCLVfs;
@@ -713,6 +825,61 @@ e = a["abc"]
         }
     }
 
+    #[test]
+    fn test_rename_symbol_at_pos() {
+        let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+        let root = root.join("src").join("test_data").join("rename_test");
+
+        let base_path = root.join("base").join("person.k");
+        let main_path = root.join("config.k");
+        let base_path = base_path.to_str().unwrap();
+        let main_path = main_path.to_str().unwrap();
+
+        let vfs = KCLVfs::default();
+        for path in [base_path, main_path] {
+            let content = fs::read_to_string(path).unwrap();
+            vfs.write().set_file_contents(
+                VfsPath::new_real_path(path.to_string()),
+                Some(content.into_bytes()),
+            );
+        }
+
+        // the reference `Person` in `a = base.Person {`
+        let pos = KCLPos {
+            filename: main_path.to_string(),
+            line: 3,
+            column: Some(9),
+        };
+        let changes = rename_symbol_at_pos(
+            root.to_str().unwrap(),
+            vfs.clone(),
+            &pos,
+            "NewPerson".to_string(),
+            VfsPath::new_real_path,
+        )
+        .unwrap();
+        assert_eq!(changes.len(), 2);
+        assert!(changes.get(base_path).unwrap().len() == 1);
+        assert!(changes.get(base_path).unwrap()[0].range.start == Position::new(0, 7));
+        assert!(changes.get(main_path).unwrap().len() == 1);
+        assert!(changes.get(main_path).unwrap()[0].range.start == Position::new(2, 9));
+        assert!(changes.get(main_path).unwrap()[0].new_text == "NewPerson");
+
+        let pos = KCLPos {
+            filename: main_path.to_string(),
+            line: 2,
+            column: Some(0),
+        };
+        assert!(rename_symbol_at_pos(
+            root.to_str().unwrap(),
+            vfs.clone(),
+            &pos,
+            "NewPerson".to_string(),
+            VfsPath::new_real_path,
+        )
+        .is_err());
+    }
+
     #[test]
     fn test_apply_rename_changes() {
         let path = "/mock_root/main.k".to_string();
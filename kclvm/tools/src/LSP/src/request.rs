@@ -21,10 +21,12 @@ use crate::{
     goto_def::goto_def,
     hover,
     inlay_hints::inlay_hints,
+    organize_imports::organize_imports_action,
     quick_fix,
     semantic_token::semantic_tokens_full,
     signature_help::signature_help,
     state::{log_message, LanguageServerSnapshot, LanguageServerState, Task},
+    to_lsp::lsp_location,
 };
 
 impl LanguageServerState {
@@ -238,7 +240,7 @@ pub(crate) fn handle_range_formatting(
 
 /// Called when a `textDocument/codeAction` request was received.
 pub(crate) fn handle_code_action(
-    _snapshot: LanguageServerSnapshot,
+    snapshot: LanguageServerSnapshot,
     params: lsp_types::CodeActionParams,
     _sender: Sender<Task>,
 ) -> anyhow::Result<Option<lsp_types::CodeActionResponse>> {
@@ -247,6 +249,33 @@ pub(crate) fn handle_code_action(
         &params.text_document.uri,
         &params.context.diagnostics,
     ));
+    // The organize imports action is only provided when the kind is requested.
+    let organize_imports_requested = params.context.only.as_ref().map_or(true, |kinds| {
+        kinds.iter().any(|kind| {
+            lsp_types::CodeActionKind::SOURCE_ORGANIZE_IMPORTS
+                .as_str()
+                .starts_with(kind.as_str())
+        })
+    });
+    if organize_imports_requested {
+        let file = file_path_from_url(&params.text_document.uri)?;
+        let path = from_lsp::abs_path(&params.text_document.uri)?;
+        let src = {
+            let vfs = snapshot.vfs.read();
+            match vfs.file_id(&path.into()) {
+                Some(file_id) => Some(String::from_utf8(vfs.file_contents(file_id).to_vec())?),
+                None => None,
+            }
+        };
+        if let Some(src) = src {
+            // Organizing imports fails on the files with syntax errors, which are skipped.
+            if let Ok(Some(action)) =
+                organize_imports_action(&params.text_document.uri, &file, &src)
+            {
+                code_actions.push(action);
+            }
+        }
+    }
     Ok(Some(code_actions))
 }
 
@@ -437,27 +466,31 @@ pub(crate) fn handle_rename(
         Err(_) => return Ok(None),
     };
     let kcl_pos = kcl_pos(&file, params.text_document_position.position);
-    let references = find_refs(&kcl_pos, &db.gs);
-    match references {
-        Some(locations) => {
-            let mut workspace_edit = lsp_types::WorkspaceEdit::default();
-            let changes = locations.into_iter().fold(
-                HashMap::new(),
-                |mut map: HashMap<lsp_types::Url, Vec<TextEdit>>, location| {
-                    let uri = location.uri;
-                    map.entry(uri.clone()).or_default().push(TextEdit {
-                        range: location.range,
-                        new_text: new_name.clone(),
-                    });
-                    map
-                },
-            );
-            workspace_edit.changes = Some(changes);
-            return anyhow::Ok(Some(workspace_edit));
-        }
-        None => {}
+    let symbol_ref = match db.gs.look_up_exact_symbol(&kcl_pos) {
+        Some(symbol_ref) => symbol_ref,
+        None => return Ok(None),
+    };
+    let edits = db.gs.rename_symbol(symbol_ref, &new_name);
+    if edits.is_empty() {
+        return Ok(None);
     }
-    Ok(None)
+    let changes = edits.into_iter().fold(
+        HashMap::new(),
+        |mut map: HashMap<lsp_types::Url, Vec<TextEdit>>, edit| {
+            let (start, end) = &edit.range;
+            if let Some(location) = lsp_location(start.filename.clone(), start, end) {
+                map.entry(location.uri).or_default().push(TextEdit {
+                    range: location.range,
+                    new_text: edit.new_text,
+                });
+            }
+            map
+        },
+    );
+    Ok(Some(lsp_types::WorkspaceEdit {
+        changes: Some(changes),
+        ..Default::default()
+    }))
 }
 
 pub(crate) fn handle_inlay_hint(
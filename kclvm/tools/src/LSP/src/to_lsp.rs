@@ -4,6 +4,7 @@ use kclvm_error::DiagnosticId;
 use kclvm_error::Level;
 use kclvm_error::Message;
 use kclvm_error::Position as KCLPos;
+use kclvm_error::{Applicability, FixIt};
 use kclvm_utils::path::PathPrefix;
 use lsp_types::*;
 use serde_json::json;
@@ -118,18 +119,24 @@ pub fn kcl_diag_to_lsp_diags(diag: &KCLDiagnostic) -> HashMap<String, Vec<Diagno
 
         let mut related_msg = diag.messages.clone();
         related_msg.remove(idx);
+        if idx == 0 {
+            related_msg.extend(labels_to_msgs(diag));
+        }
         let code = if diag.code.is_some() {
             Some(kcl_diag_id_to_lsp_diag_code(diag.code.clone().unwrap()))
         } else {
             None
         };
 
-        let lsp_diag = kcl_msg_to_lsp_diags(
+        let mut lsp_diag = kcl_msg_to_lsp_diags(
             msg,
             kcl_err_level_to_severity(diag.level),
             related_msg,
             code,
         );
+        if idx == 0 {
+            attach_fixes(&mut lsp_diag, &filename, &diag.fixes);
+        }
 
         diags_map.entry(filename).or_insert(vec![]).push(lsp_diag);
     }
@@ -147,18 +154,24 @@ pub(crate) fn kcl_diag_to_lsp_diags_by_file(
         if msg.range.0.filename.adjust_canonicalization() == file_name.adjust_canonicalization() {
             let mut related_msg = diag.messages.clone();
             related_msg.remove(idx);
+            if idx == 0 {
+                related_msg.extend(labels_to_msgs(diag));
+            }
             let code = if diag.code.is_some() {
                 Some(kcl_diag_id_to_lsp_diag_code(diag.code.clone().unwrap()))
             } else {
                 None
             };
 
-            let lsp_diag = kcl_msg_to_lsp_diags(
+            let mut lsp_diag = kcl_msg_to_lsp_diags(
                 msg,
                 kcl_err_level_to_severity(diag.level),
                 related_msg,
                 code,
             );
+            if idx == 0 {
+                attach_fixes(&mut lsp_diag, &msg.range.0.filename, &diag.fixes);
+            }
 
             diags.push(lsp_diag);
         }
@@ -166,6 +179,46 @@ pub(crate) fn kcl_diag_to_lsp_diags_by_file(
     diags
 }
 
+/// Convert the secondary labels of the diagnostic to the messages, which are reported
+/// as the related information of the first message.
+fn labels_to_msgs(diag: &KCLDiagnostic) -> Vec<Message> {
+    diag.labels
+        .iter()
+        .map(|label| Message {
+            range: label.range.clone(),
+            style: kclvm_error::Style::LineAndColumn,
+            message: label.message.clone(),
+            note: None,
+            suggested_replacement: None,
+        })
+        .collect()
+}
+
+/// Attach the fix-its in the file to the data of the LSP diagnostic, which are converted
+/// to the quick fix code actions, and the machine applicable fix-its are preferred.
+fn attach_fixes(lsp_diag: &mut Diagnostic, filename: &str, fixes: &[FixIt]) {
+    let fixes: Vec<serde_json::Value> = fixes
+        .iter()
+        .filter(|fix| fix.range.0.filename == filename)
+        .map(|fix| {
+            json!({
+                "range": Range::new(lsp_pos(&fix.range.0), lsp_pos(&fix.range.1)),
+                "new_text": fix.replacement,
+                "is_preferred": fix.applicability == Applicability::MachineApplicable,
+            })
+        })
+        .collect();
+    if fixes.is_empty() {
+        return;
+    }
+    match lsp_diag.data.as_mut() {
+        Some(serde_json::Value::Object(data)) => {
+            data.insert("fixes".to_string(), json!(fixes));
+        }
+        _ => lsp_diag.data = Some(json!({ "fixes": fixes })),
+    }
+}
+
 /// Convert KCL Diagnostic ID to LSP Diagnostics code.
 /// Todo: use unique id/code instead of name()
 pub(crate) fn kcl_diag_id_to_lsp_diag_code(id: DiagnosticId) -> NumberOrString {
@@ -663,5 +663,24 @@ fn build_identifier_from_ty_string(ty: &NodeRef<Type>, pos: &KCLPos) -> Option<N
         }
         Type::Literal(_) => None,
         Type::Function(_) => None,
+        Type::Generic(generic_ty) => {
+            for arg in &generic_ty.args {
+                if arg.contains_pos(pos) {
+                    return build_identifier_from_ty_string(arg, pos);
+                }
+            }
+            let first = generic_ty.name.names.first()?;
+            let last = generic_ty.name.names.last()?;
+            Some(Node::node_with_pos(
+                Expr::Identifier(generic_ty.name.clone()),
+                (
+                    first.filename.clone(),
+                    first.line,
+                    first.column,
+                    last.end_line,
+                    last.end_column,
+                ),
+            ))
+        }
     }
 }
@@ -0,0 +1,241 @@
+//! [kclvm_tools::compat] module compares two versions of a KCL package and reports the
+//! breaking schema changes, which can be used to gate the changes of the shared models.
+//!
+//! The following changes are regarded as breaking changes:
+//!
+//! - A schema is removed.
+//! - A schema attribute is removed.
+//! - The type of a schema attribute is narrowed e.g., from `str` to `"a" | "b"`.
+//! - An optional schema attribute becomes required without a default value.
+//! - A new required schema attribute without a default value is added.
+use std::sync::Arc;
+
+use anyhow::{anyhow, Result};
+use indexmap::IndexMap;
+use kclvm_error::diagnostic::Range;
+use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
+use kclvm_sema::resolver::resolve_program_with_opts;
+use kclvm_sema::resolver::scope::{ProgramScope, ScopeObjectKind};
+use kclvm_sema::ty::{subsume, SchemaAttr, SchemaType, TypeKind, TypeRef};
+
+#[cfg(test)]
+mod tests;
+
+/// The kind of the breaking schema change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BreakingChangeKind {
+    /// The schema is removed.
+    SchemaRemoved,
+    /// The schema attribute is removed.
+    AttributeRemoved,
+    /// The type of the schema attribute is narrowed.
+    TypeNarrowed,
+    /// The optional schema attribute becomes required without a default value.
+    AttributeBecameRequired,
+    /// A required schema attribute without a default value is added.
+    RequiredAttributeAdded,
+}
+
+/// A breaking schema change between two versions of a package.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BreakingChange {
+    /// The kind of the breaking change.
+    pub kind: BreakingChangeKind,
+    /// The full name of the schema e.g., `pkg.Person`.
+    pub schema: String,
+    /// The schema attribute name, which is None for the schema level changes.
+    pub attr: Option<String>,
+    /// The human readable message of the change.
+    pub message: String,
+    /// The source range in the new version, or in the old version when the schema or
+    /// the attribute is removed.
+    pub range: Range,
+}
+
+/// Compare the schemas in the old and the new resolved program scopes, and return the
+/// breaking changes of the new version in the order of the old schemas and attributes.
+pub fn check_compatibility(old: &ProgramScope, new: &ProgramScope) -> Vec<BreakingChange> {
+    let old_schemas = collect_schemas(old);
+    let new_schemas = collect_schemas(new);
+    let mut changes = vec![];
+    for (name, (old_schema, old_range)) in &old_schemas {
+        let new_schema = match new_schemas.get(name) {
+            Some((new_schema, _)) => new_schema,
+            None => {
+                changes.push(BreakingChange {
+                    kind: BreakingChangeKind::SchemaRemoved,
+                    schema: name.to_string(),
+                    attr: None,
+                    message: format!("schema '{}' is removed", name),
+                    range: old_range.clone(),
+                });
+                continue;
+            }
+        };
+        changes.append(&mut check_schema_compatibility(
+            name, old_schema, new_schema,
+        ));
+    }
+    changes
+}
+
+/// Load and resolve the old and the new files, and compare their schemas. See
+/// [check_compatibility] for the details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_tools::compat::check_compatibility_files;
+///
+/// let changes = check_compatibility_files(&["v1/main.k"], &["v2/main.k"], None).unwrap();
+/// for change in changes {
+///     println!("{}", change.message);
+/// }
+/// ```
+pub fn check_compatibility_files(
+    old_files: &[&str],
+    new_files: &[&str],
+    opts: Option<LoadProgramOptions>,
+) -> Result<Vec<BreakingChange>> {
+    let old = resolve_files(old_files, opts.clone())?;
+    let new = resolve_files(new_files, opts)?;
+    Ok(check_compatibility(&old, &new))
+}
+
+fn resolve_files(files: &[&str], opts: Option<LoadProgramOptions>) -> Result<ProgramScope> {
+    let sess = Arc::new(ParseSession::default());
+    let mut program = load_program(sess, files, opts, None)?.program;
+    let scope = resolve_program_with_opts(
+        &mut program,
+        kclvm_sema::resolver::Options {
+            merge_program: false,
+            type_erasure: false,
+            ..Default::default()
+        },
+        None,
+    );
+    if scope.handler.has_errors() {
+        let msgs: Vec<String> = scope
+            .handler
+            .diagnostics
+            .iter()
+            .filter(|diag| diag.is_error())
+            .flat_map(|diag| diag.messages.iter().map(|msg| msg.message.clone()))
+            .collect();
+        return Err(anyhow!(msgs.join("\n")));
+    }
+    Ok(scope)
+}
+
+fn check_schema_compatibility(
+    name: &str,
+    old_schema: &SchemaType,
+    new_schema: &SchemaType,
+) -> Vec<BreakingChange> {
+    let old_attrs = old_schema.get_inherited_attrs();
+    let new_attrs = new_schema.get_inherited_attrs();
+    let mut changes = vec![];
+    let mut add_change = |kind, attr: &str, message, range| {
+        changes.push(BreakingChange {
+            kind,
+            schema: name.to_string(),
+            attr: Some(attr.to_string()),
+            message,
+            range,
+        })
+    };
+    for (attr, old_attr) in &old_attrs {
+        let new_attr = match new_attrs.get(attr) {
+            Some(new_attr) => &new_attr.attr,
+            None => {
+                add_change(
+                    BreakingChangeKind::AttributeRemoved,
+                    attr,
+                    format!("attribute '{}' of schema '{}' is removed", attr, name),
+                    old_attr.attr.range.clone(),
+                );
+                continue;
+            }
+        };
+        let old_attr = &old_attr.attr;
+        if is_type_narrowed(&old_attr.ty, &new_attr.ty) {
+            add_change(
+                BreakingChangeKind::TypeNarrowed,
+                attr,
+                format!(
+                    "the type of attribute '{}' of schema '{}' is narrowed from '{}' to '{}'",
+                    attr,
+                    name,
+                    old_attr.ty.ty_str(),
+                    new_attr.ty.ty_str()
+                ),
+                new_attr.range.clone(),
+            );
+        }
+        if old_attr.is_optional && is_required(new_attr) {
+            add_change(
+                BreakingChangeKind::AttributeBecameRequired,
+                attr,
+                format!(
+                    "attribute '{}' of schema '{}' becomes required without a default value",
+                    attr, name
+                ),
+                new_attr.range.clone(),
+            );
+        }
+    }
+    for (attr, new_attr) in &new_attrs {
+        if !old_attrs.contains_key(attr) && is_required(&new_attr.attr) {
+            add_change(
+                BreakingChangeKind::RequiredAttributeAdded,
+                attr,
+                format!(
+                    "required attribute '{}' without a default value is added to schema '{}'",
+                    attr, name
+                ),
+                new_attr.attr.range.clone(),
+            );
+        }
+    }
+    changes
+}
+
+/// Collect the schemas in the program scope with their full names, and the rules and
+/// the schema mixins and protocols which can't be instantiated are ignored.
+fn collect_schemas(scope: &ProgramScope) -> IndexMap<String, (SchemaType, Range)> {
+    let mut schemas = IndexMap::new();
+    for (pkgpath, pkg_scope) in &scope.scope_map {
+        for (name, obj) in &pkg_scope.borrow().elems {
+            let obj = obj.borrow();
+            if !matches!(obj.kind, ScopeObjectKind::Definition) {
+                continue;
+            }
+            if let TypeKind::Schema(schema_ty) = &obj.ty.kind {
+                if schema_ty.is_rule || schema_ty.is_mixin || schema_ty.is_protocol {
+                    continue;
+                }
+                schemas.insert(
+                    format!("{}.{}", pkgpath, name),
+                    (schema_ty.clone(), (obj.start.clone(), obj.end.clone())),
+                );
+            }
+        }
+    }
+    schemas
+}
+
+/// Whether some values of the old type are invalid for the new type. Besides the
+/// types which are not assignable, the literal types narrowed from the non-literal
+/// types e.g., `str` to `"a" | "b"` are also regarded as narrowed.
+fn is_type_narrowed(old_ty: &TypeRef, new_ty: &TypeRef) -> bool {
+    let is_literal_only = |ty: &TypeRef| {
+        ty.is_literal() || (ty.is_union() && ty.union_types().iter().all(|ty| ty.is_literal()))
+    };
+    !subsume(old_ty.clone(), new_ty.clone(), false)
+        || (!is_literal_only(old_ty) && is_literal_only(new_ty))
+}
+
+#[inline]
+fn is_required(attr: &SchemaAttr) -> bool {
+    !attr.is_optional && !attr.has_default
+}
@@ -0,0 +1,48 @@
+use super::{check_compatibility_files, BreakingChangeKind};
+
+#[test]
+fn test_check_compatibility() {
+    let changes = check_compatibility_files(
+        &["./src/compat/test_data/v1/main.k"],
+        &["./src/compat/test_data/v2/main.k"],
+        None,
+    )
+    .unwrap();
+    let changes: Vec<(BreakingChangeKind, &str, Option<&str>)> = changes
+        .iter()
+        .map(|c| (c.kind.clone(), c.schema.as_str(), c.attr.as_deref()))
+        .collect();
+    assert_eq!(
+        changes,
+        vec![
+            (
+                BreakingChangeKind::AttributeBecameRequired,
+                "__main__.Person",
+                Some("age")
+            ),
+            (
+                BreakingChangeKind::TypeNarrowed,
+                "__main__.Person",
+                Some("kind")
+            ),
+            (
+                BreakingChangeKind::AttributeRemoved,
+                "__main__.Person",
+                Some("labels")
+            ),
+            (
+                BreakingChangeKind::RequiredAttributeAdded,
+                "__main__.Person",
+                Some("phone")
+            ),
+            (BreakingChangeKind::SchemaRemoved, "__main__.Removed", None),
+        ]
+    );
+}
+
+#[test]
+fn test_check_compatibility_same_version() {
+    let file = "./src/compat/test_data/v1/main.k";
+    let changes = check_compatibility_files(&[file], &[file], None).unwrap();
+    assert!(changes.is_empty(), "{:?}", changes);
+}
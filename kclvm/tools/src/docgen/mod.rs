@@ -0,0 +1,263 @@
+//! [kclvm_tools::docgen] module mainly contains the document generator, the main API
+//! function is `gen_docs`, which accepts the paths of the KCL packages and returns the
+//! per-package documents of the schemas in the markdown, HTML or JSON format.
+//!
+//! The documents are generated from the resolved schema types instead of the source
+//! text, thus the attribute types, defaults and docs are the same as the ones checked
+//! by the compiler. The JSON format is the intermediate representation of the documents,
+//! which can be rendered by the custom templates.
+use std::collections::HashSet;
+use std::path::Path;
+use std::sync::Arc;
+
+use anyhow::Result;
+use indexmap::IndexMap;
+use kclvm_ast::MAIN_PKG;
+use kclvm_parser::{get_kcl_files, load_program, LoadProgramOptions, ParseSession};
+use kclvm_sema::resolver::{resolve_program_with_opts, Options};
+use kclvm_sema::ty::SchemaType;
+use serde::{Deserialize, Serialize};
+
+mod render;
+#[cfg(test)]
+mod tests;
+
+pub use render::{render_html, render_markdown};
+
+/// The format of the generated documents.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DocFormat {
+    #[default]
+    Markdown,
+    Html,
+    Json,
+}
+
+impl DocFormat {
+    /// Returns the file extension of the documents in the format.
+    pub fn extension(&self) -> &'static str {
+        match self {
+            DocFormat::Markdown => "md",
+            DocFormat::Html => "html",
+            DocFormat::Json => "json",
+        }
+    }
+}
+
+/// DocGenOptions contains the following options:
+/// - format: the format of the generated documents.
+/// - load_opts: the compilation options e.g., the external package maps.
+#[derive(Debug, Default, Clone)]
+pub struct DocGenOptions {
+    pub format: DocFormat,
+    pub load_opts: Option<LoadProgramOptions>,
+}
+
+/// PackageDoc denotes the document of a package, which is the JSON intermediate of the
+/// rendered documents.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct PackageDoc {
+    /// The package path, which is `__main__` for the main package.
+    pub name: String,
+    /// The schemas in the package sorted by the names.
+    pub schemas: Vec<SchemaDoc>,
+}
+
+/// SchemaDoc denotes the document of a schema.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SchemaDoc {
+    pub name: String,
+    /// The summary of the schema docstring.
+    pub doc: String,
+    /// The source file path relative to the documented path.
+    pub filename: String,
+    pub line: u64,
+    pub base: Option<SchemaRef>,
+    pub mixins: Vec<SchemaRef>,
+    /// The ancestors of the schema from the base schema to the root schema.
+    pub ancestors: Vec<SchemaRef>,
+    pub attrs: Vec<AttrDoc>,
+    /// The examples in the schema docstring.
+    pub examples: Vec<String>,
+}
+
+/// SchemaRef denotes a reference to a schema in a package, which is used to link the
+/// documents of the schemas.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct SchemaRef {
+    pub pkgpath: String,
+    pub name: String,
+}
+
+/// AttrDoc denotes the document of a schema attribute.
+#[derive(Serialize, Deserialize, Debug, Clone, Default, PartialEq)]
+pub struct AttrDoc {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The default value expression of the attribute.
+    pub default: Option<String>,
+    pub required: bool,
+    pub doc: String,
+    pub line: u64,
+}
+
+/// Generates the documents of the packages in the paths and the packages imported by
+/// them, and returns the document file names e.g., `__main__.md` and the contents.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_tools::docgen::{gen_docs, DocFormat, DocGenOptions};
+///
+/// let opts = DocGenOptions {
+///     format: DocFormat::Markdown,
+///     ..Default::default()
+/// };
+/// for (file, content) in gen_docs(&["path_to_a_package"], &opts).unwrap() {
+///     std::fs::write(file, content).unwrap();
+/// }
+/// ```
+pub fn gen_docs(paths: &[&str], opts: &DocGenOptions) -> Result<IndexMap<String, String>> {
+    let mut docs = IndexMap::new();
+    for pkg in load_package_docs(paths, opts.load_opts.clone())? {
+        let content = match opts.format {
+            DocFormat::Markdown => render_markdown(&pkg),
+            DocFormat::Html => render_html(&pkg),
+            DocFormat::Json => serde_json::to_string_pretty(&pkg)?,
+        };
+        docs.insert(format!("{}.{}", pkg.name, opts.format.extension()), content);
+    }
+    Ok(docs)
+}
+
+/// Loads and resolves the packages in the paths and the packages imported by them, and
+/// returns the documents of the packages which contain schemas, where the main package
+/// is the first one and the others are sorted by the package paths.
+pub fn load_package_docs(
+    paths: &[&str],
+    load_opts: Option<LoadProgramOptions>,
+) -> Result<Vec<PackageDoc>> {
+    let mut files = vec![];
+    for path in paths {
+        if Path::new(path).is_dir() {
+            files.extend(get_kcl_files(path, false)?);
+        } else {
+            files.push(path.to_string());
+        }
+    }
+    let sess = Arc::new(ParseSession::default());
+    let files: Vec<&str> = files.iter().map(AsRef::as_ref).collect();
+    let mut program = load_program(sess, &files, load_opts, None)?.program;
+    let scope = resolve_program_with_opts(
+        &mut program,
+        Options {
+            resolve_val: true,
+            merge_program: false,
+            type_erasure: false,
+            ..Default::default()
+        },
+        None,
+    );
+    let source_root = paths.first().map(|path| {
+        let path = Path::new(path);
+        if path.is_dir() {
+            path.to_path_buf()
+        } else {
+            path.parent().unwrap_or(path).to_path_buf()
+        }
+    });
+    let relative_path = |filename: &str| -> String {
+        source_root
+            .as_ref()
+            .and_then(|root| {
+                let root = root.canonicalize().ok()?;
+                let filename = Path::new(filename).canonicalize().ok()?;
+                Some(
+                    filename
+                        .strip_prefix(root)
+                        .ok()?
+                        .to_string_lossy()
+                        .to_string(),
+                )
+            })
+            .unwrap_or_else(|| filename.to_string())
+    };
+    let mut pkgs = vec![];
+    for (pkgpath, pkg_scope) in &scope.scope_map {
+        let mut schemas = vec![];
+        for obj in pkg_scope.borrow().elems.values() {
+            let obj = obj.borrow();
+            if !obj.ty.is_schema() {
+                continue;
+            }
+            let schema_ty = obj.ty.into_schema_type();
+            // Skip the schemas imported from other packages and the type aliases.
+            if schema_ty.pkgpath != *pkgpath || schema_ty.name != obj.name || schema_ty.is_instance
+            {
+                continue;
+            }
+            schemas.push(schema_doc(
+                &schema_ty,
+                relative_path(&obj.start.filename),
+                obj.start.line,
+            ));
+        }
+        if schemas.is_empty() {
+            continue;
+        }
+        schemas.sort_by(|a, b| a.name.cmp(&b.name));
+        pkgs.push(PackageDoc {
+            name: pkgpath.clone(),
+            schemas,
+        });
+    }
+    pkgs.sort_by(|a, b| (a.name != MAIN_PKG, &a.name).cmp(&(b.name != MAIN_PKG, &b.name)));
+    Ok(pkgs)
+}
+
+fn schema_doc(schema_ty: &SchemaType, filename: String, line: u64) -> SchemaDoc {
+    let schema_ref = |ty: &SchemaType| SchemaRef {
+        pkgpath: ty.pkgpath.clone(),
+        name: ty.name.clone(),
+    };
+    let mut ancestors = vec![];
+    let mut visited = HashSet::new();
+    let mut base = schema_ty.base.as_deref();
+    while let Some(base_ty) = base {
+        // Guard against the cyclic inheritance, which is reported by the resolver.
+        if !visited.insert((&base_ty.pkgpath, &base_ty.name)) {
+            break;
+        }
+        ancestors.push(schema_ref(base_ty));
+        base = base_ty.base.as_deref();
+    }
+    let mut examples: Vec<(&String, String)> = schema_ty
+        .examples
+        .iter()
+        .map(|(name, example)| (name, example.value.clone()))
+        .collect();
+    examples.sort();
+    SchemaDoc {
+        name: schema_ty.name.clone(),
+        doc: schema_ty.doc.clone(),
+        filename,
+        line,
+        base: schema_ty.base.as_deref().map(schema_ref),
+        mixins: schema_ty.mixins.iter().map(schema_ref).collect(),
+        ancestors,
+        attrs: schema_ty
+            .attrs
+            .iter()
+            .map(|(name, attr)| AttrDoc {
+                name: name.clone(),
+                ty: attr.ty.ty_str(),
+                default: attr.default.clone(),
+                required: !attr.is_optional,
+                doc: attr.doc.clone().unwrap_or_default(),
+                line: attr.range.0.line,
+            })
+            .collect(),
+        examples: examples.into_iter().map(|(_, value)| value).collect(),
+    }
+}
@@ -0,0 +1,236 @@
+//! Render the package documents in the markdown and HTML formats.
+use super::{AttrDoc, PackageDoc, SchemaDoc, SchemaRef};
+use kclvm_error::render::escape_html;
+
+/// Returns the markdown document of the package.
+pub fn render_markdown(pkg: &PackageDoc) -> String {
+    let mut blocks = vec![format!("# {}", pkg.name), "## Index".to_string()];
+    blocks.push(
+        pkg.schemas
+            .iter()
+            .map(|schema| format!("- [{}](#{})", schema.name, schema.name.to_lowercase()))
+            .collect::<Vec<String>>()
+            .join("\n"),
+    );
+    blocks.push("## Schemas".to_string());
+    for schema in &pkg.schemas {
+        blocks.push(format!("### {}", schema.name));
+        if !schema.doc.is_empty() {
+            blocks.push(schema.doc.clone());
+        }
+        blocks.push(format!(
+            "Source: [{}:{}]({}#L{})",
+            schema.filename, schema.line, schema.filename, schema.line
+        ));
+        let markdown_link =
+            |schema_ref: &SchemaRef| link(pkg, schema_ref, "md", &schema_ref.name.to_lowercase());
+        if let Some(base) = &schema.base {
+            blocks.push(format!(
+                "Base schema: [{}]({})",
+                display_name(pkg, base),
+                markdown_link(base)
+            ));
+        }
+        if !schema.mixins.is_empty() {
+            blocks.push(format!(
+                "Mixins: {}",
+                schema
+                    .mixins
+                    .iter()
+                    .map(|mixin| format!(
+                        "[{}]({})",
+                        display_name(pkg, mixin),
+                        markdown_link(mixin)
+                    ))
+                    .collect::<Vec<String>>()
+                    .join(", ")
+            ));
+        }
+        if let Some(diagram) = inheritance_diagram(schema) {
+            blocks.push(format!("```mermaid\n{}\n```", diagram));
+        }
+        if !schema.attrs.is_empty() {
+            blocks.push("#### Attributes".to_string());
+            let mut table = vec![
+                "| Name | Type | Default | Required | Description |".to_string(),
+                "| --- | --- | --- | --- | --- |".to_string(),
+            ];
+            for attr in &schema.attrs {
+                table.push(format!(
+                    "| {} | {} | {} | {} | {} |",
+                    attr.name,
+                    code_cell(&attr.ty),
+                    attr.default.as_deref().map(code_cell).unwrap_or_default(),
+                    attr.required,
+                    escape_cell(&attr.doc)
+                ));
+            }
+            blocks.push(table.join("\n"));
+        }
+        if !schema.examples.is_empty() {
+            blocks.push("#### Examples".to_string());
+            for example in &schema.examples {
+                blocks.push(format!("```kcl\n{}\n```", example.trim_end()));
+            }
+        }
+    }
+    blocks.join("\n\n") + "\n"
+}
+
+/// Returns the HTML document of the package.
+pub fn render_html(pkg: &PackageDoc) -> String {
+    let mut out = vec![
+        "<!DOCTYPE html>".to_string(),
+        "<html>".to_string(),
+        "<head>".to_string(),
+        "<meta charset=\"utf-8\">".to_string(),
+        format!("<title>{}</title>", escape_html(&pkg.name)),
+        "</head>".to_string(),
+        "<body>".to_string(),
+        format!("<h1>{}</h1>", escape_html(&pkg.name)),
+        "<h2>Index</h2>".to_string(),
+        "<ul>".to_string(),
+    ];
+    for schema in &pkg.schemas {
+        out.push(format!(
+            "<li><a href=\"#{}\">{}</a></li>",
+            escape_html(&schema.name),
+            escape_html(&schema.name)
+        ));
+    }
+    out.push("</ul>".to_string());
+    out.push("<h2>Schemas</h2>".to_string());
+    for schema in &pkg.schemas {
+        render_html_schema(pkg, schema, &mut out);
+    }
+    out.push("</body>".to_string());
+    out.push("</html>".to_string());
+    out.join("\n") + "\n"
+}
+
+fn render_html_schema(pkg: &PackageDoc, schema: &SchemaDoc, out: &mut Vec<String>) {
+    let html_link = |schema_ref: &SchemaRef| {
+        format!(
+            "<a href=\"{}\">{}</a>",
+            escape_html(&link(pkg, schema_ref, "html", &schema_ref.name)),
+            escape_html(&display_name(pkg, schema_ref))
+        )
+    };
+    out.push(format!(
+        "<h3 id=\"{}\">{}</h3>",
+        escape_html(&schema.name),
+        escape_html(&schema.name)
+    ));
+    if !schema.doc.is_empty() {
+        out.push(format!("<p>{}</p>", escape_html(&schema.doc)));
+    }
+    out.push(format!(
+        "<p>Source: <a href=\"{}#L{}\">{}:{}</a></p>",
+        escape_html(&schema.filename),
+        schema.line,
+        escape_html(&schema.filename),
+        schema.line
+    ));
+    if let Some(base) = &schema.base {
+        out.push(format!("<p>Base schema: {}</p>", html_link(base)));
+    }
+    if !schema.mixins.is_empty() {
+        out.push(format!(
+            "<p>Mixins: {}</p>",
+            schema
+                .mixins
+                .iter()
+                .map(html_link)
+                .collect::<Vec<String>>()
+                .join(", ")
+        ));
+    }
+    if let Some(diagram) = inheritance_diagram(schema) {
+        out.push(format!(
+            "<pre class=\"mermaid\">\n{}\n</pre>",
+            escape_html(&diagram)
+        ));
+    }
+    if !schema.attrs.is_empty() {
+        out.push("<h4>Attributes</h4>".to_string());
+        out.push("<table>".to_string());
+        out.push(
+            "<tr><th>Name</th><th>Type</th><th>Default</th><th>Required</th><th>Description</th></tr>"
+                .to_string(),
+        );
+        for attr in &schema.attrs {
+            out.push(html_attr_row(attr));
+        }
+        out.push("</table>".to_string());
+    }
+    if !schema.examples.is_empty() {
+        out.push("<h4>Examples</h4>".to_string());
+        for example in &schema.examples {
+            out.push(format!(
+                "<pre><code>{}</code></pre>",
+                escape_html(example.trim_end())
+            ));
+        }
+    }
+}
+
+fn html_attr_row(attr: &AttrDoc) -> String {
+    format!(
+        "<tr><td>{}</td><td><code>{}</code></td><td>{}</td><td>{}</td><td>{}</td></tr>",
+        escape_html(&attr.name),
+        escape_html(&attr.ty),
+        attr.default
+            .as_deref()
+            .map(|default| format!("<code>{}</code>", escape_html(default)))
+            .unwrap_or_default(),
+        attr.required,
+        escape_html(&attr.doc)
+    )
+}
+
+/// Returns the mermaid class diagram of the ancestors and mixins of the schema, which
+/// is None when the schema has no base schema and mixins.
+fn inheritance_diagram(schema: &SchemaDoc) -> Option<String> {
+    if schema.ancestors.is_empty() && schema.mixins.is_empty() {
+        return None;
+    }
+    let mut lines = vec!["classDiagram".to_string()];
+    let mut child = &schema.name;
+    for ancestor in &schema.ancestors {
+        lines.push(format!("    {} <|-- {}", ancestor.name, child));
+        child = &ancestor.name;
+    }
+    for mixin in &schema.mixins {
+        lines.push(format!("    {} <|.. {}", mixin.name, schema.name));
+    }
+    Some(lines.join("\n"))
+}
+
+/// Returns the link to the schema document, which is an anchor in the same document
+/// for the schemas in the package.
+fn link(pkg: &PackageDoc, schema_ref: &SchemaRef, extension: &str, anchor: &str) -> String {
+    if schema_ref.pkgpath == pkg.name {
+        format!("#{}", anchor)
+    } else {
+        format!("{}.{}#{}", schema_ref.pkgpath, extension, anchor)
+    }
+}
+
+/// Returns the schema name, which is prefixed with the package path for the schemas in
+/// other packages.
+fn display_name(pkg: &PackageDoc, schema_ref: &SchemaRef) -> String {
+    if schema_ref.pkgpath == pkg.name {
+        schema_ref.name.clone()
+    } else {
+        format!("{}.{}", schema_ref.pkgpath, schema_ref.name)
+    }
+}
+
+fn code_cell(text: &str) -> String {
+    format!("`{}`", escape_cell(text))
+}
+
+/// Escape the pipes and newlines in the markdown table cell.
+fn escape_cell(text: &str) -> String {
+    text.replace('|', "\\|").replace('\n', "<br>")
+}
@@ -0,0 +1,84 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+const TEST_PKG: &str = "./src/docgen/test_data/pkg";
+
+fn gen_test_docs(format: DocFormat) -> IndexMap<String, String> {
+    gen_docs(
+        &[TEST_PKG],
+        &DocGenOptions {
+            format,
+            ..Default::default()
+        },
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_load_package_docs() {
+    let pkgs = load_package_docs(&[TEST_PKG], None).unwrap();
+    assert_eq!(pkgs.len(), 1);
+    assert_eq!(pkgs[0].name, MAIN_PKG);
+    let names: Vec<&str> = pkgs[0].schemas.iter().map(|s| s.name.as_str()).collect();
+    assert_eq!(names, vec!["Base", "Labels", "Person"]);
+    let person = &pkgs[0].schemas[2];
+    assert_eq!(person.doc, "Person is a person.");
+    assert_eq!(person.filename, "main.k");
+    assert_eq!(person.line, 9);
+    let base = SchemaRef {
+        pkgpath: MAIN_PKG.to_string(),
+        name: "Base".to_string(),
+    };
+    assert_eq!(person.base, Some(base.clone()));
+    assert_eq!(person.ancestors, vec![base]);
+    let age = person.attrs.iter().find(|attr| attr.name == "age").unwrap();
+    assert_eq!(age.ty, "int");
+    assert_eq!(age.default, Some("18".to_string()));
+    assert!(age.required);
+    let name = &pkgs[0].schemas[0].attrs[0];
+    assert_eq!(name.doc, "The name of the resource.");
+    let labels = &pkgs[0].schemas[1].attrs[0];
+    assert_eq!(labels.ty, "{str:str}");
+    assert_eq!(labels.default, None);
+    assert!(!labels.required);
+}
+
+#[test]
+fn test_gen_markdown_docs() {
+    let docs = gen_test_docs(DocFormat::Markdown);
+    assert_eq!(docs.keys().collect::<Vec<_>>(), vec!["__main__.md"]);
+    let doc = &docs["__main__.md"];
+    assert!(doc.starts_with(
+        "# __main__\n\n## Index\n\n- [Base](#base)\n- [Labels](#labels)\n- [Person](#person)\n\n## Schemas\n\n"
+    ));
+    assert!(doc.contains(
+        "### Person\n\nPerson is a person.\n\nSource: [main.k:9](main.k#L9)\n\nBase schema: [Base](#base)\n\n```mermaid\nclassDiagram\n    Base <|-- Person\n```\n\n#### Attributes\n\n"
+    ));
+    assert!(doc.contains(
+        "| Name | Type | Default | Required | Description |\n| --- | --- | --- | --- | --- |\n"
+    ));
+    assert!(doc.contains("| name | `str` |  | true | The name of the resource. |"));
+    assert!(doc.contains("| labels | `{str:str}` |  | false |  |"));
+    assert!(doc.contains("| age | `int` | `18` | true |  |"));
+}
+
+#[test]
+fn test_gen_html_docs() {
+    let docs = gen_test_docs(DocFormat::Html);
+    let doc = &docs["__main__.html"];
+    assert!(doc.starts_with("<!DOCTYPE html>\n"));
+    assert!(doc.contains("<li><a href=\"#Person\">Person</a></li>"));
+    assert!(doc.contains("<h3 id=\"Person\">Person</h3>\n<p>Person is a person.</p>"));
+    assert!(doc.contains("<p>Base schema: <a href=\"#Base\">Base</a></p>"));
+    assert!(doc.contains("<pre class=\"mermaid\">\nclassDiagram\n    Base &lt;|-- Person\n</pre>"));
+    assert!(doc.contains("<tr><td>age</td><td><code>int</code></td><td><code>18</code></td><td>true</td><td></td></tr>"));
+}
+
+#[test]
+fn test_gen_json_docs() {
+    let docs = gen_test_docs(DocFormat::Json);
+    let pkg: PackageDoc = serde_json::from_str(&docs["__main__.json"]).unwrap();
+    assert_eq!(pkg, load_package_docs(&[TEST_PKG], None).unwrap()[0]);
+    // The attribute type is serialized as the `type` field.
+    assert!(docs["__main__.json"].contains("\"type\": \"int\""));
+}
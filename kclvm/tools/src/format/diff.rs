@@ -0,0 +1,136 @@
+//! Line based unified diff between a source and its formatted source, which is used by
+//! the format check mode e.g., the format check jobs in CI.
+use std::fmt::Write;
+
+/// The number of the unchanged lines around the changed lines in a hunk.
+const CONTEXT_LINES: usize = 3;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Tag {
+    Equal,
+    Delete,
+    Insert,
+}
+
+/// Returns the unified diff from the old source to the new source of the file, which
+/// is empty when the sources are the same.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_tools::format::unified_diff;
+///
+/// let diff = unified_diff("main.k", "a =  1\n", "a = 1\n");
+/// assert_eq!(diff, "--- main.k\n+++ main.k\n@@ -1,1 +1,1 @@\n-a =  1\n+a = 1\n");
+/// ```
+pub fn unified_diff(file: &str, old: &str, new: &str) -> String {
+    let old_lines: Vec<&str> = old.split_inclusive('\n').collect();
+    let new_lines: Vec<&str> = new.split_inclusive('\n').collect();
+    let edits = diff_lines(&old_lines, &new_lines);
+    let changes: Vec<usize> = edits
+        .iter()
+        .enumerate()
+        .filter(|(_, (tag, _))| *tag != Tag::Equal)
+        .map(|(i, _)| i)
+        .collect();
+    if changes.is_empty() {
+        return String::new();
+    }
+    let mut out = format!("--- {}\n+++ {}\n", file, file);
+    let mut k = 0;
+    while k < changes.len() {
+        let start = changes[k].saturating_sub(CONTEXT_LINES);
+        let mut end = changes[k] + 1;
+        k += 1;
+        // Merge the changes whose unchanged lines between them are covered by the contexts.
+        while k < changes.len() && changes[k] <= end + 2 * CONTEXT_LINES {
+            end = changes[k] + 1;
+            k += 1;
+        }
+        write_hunk(
+            &mut out,
+            &edits,
+            start,
+            (end + CONTEXT_LINES).min(edits.len()),
+        );
+    }
+    out
+}
+
+/// Returns the edits from the old lines to the new lines with the longest common
+/// subsequence of the lines, in which the common prefix and suffix are skipped.
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<(Tag, &'a str)> {
+    let prefix = old.iter().zip(new).take_while(|(a, b)| a == b).count();
+    let suffix = old[prefix..]
+        .iter()
+        .rev()
+        .zip(new[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+    let old_mid = &old[prefix..old.len() - suffix];
+    let new_mid = &new[prefix..new.len() - suffix];
+    let (n, m) = (old_mid.len(), new_mid.len());
+    // lcs[i][j] is the length of the longest common subsequence of old_mid[i..] and new_mid[j..].
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if old_mid[i] == new_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+    let mut edits: Vec<(Tag, &str)> = old[..prefix].iter().map(|l| (Tag::Equal, *l)).collect();
+    let (mut i, mut j) = (0, 0);
+    while i < n || j < m {
+        if i < n && j < m && old_mid[i] == new_mid[j] {
+            edits.push((Tag::Equal, old_mid[i]));
+            i += 1;
+            j += 1;
+        } else if i < n && (j == m || lcs[i + 1][j] >= lcs[i][j + 1]) {
+            edits.push((Tag::Delete, old_mid[i]));
+            i += 1;
+        } else {
+            edits.push((Tag::Insert, new_mid[j]));
+            j += 1;
+        }
+    }
+    edits.extend(old[old.len() - suffix..].iter().map(|l| (Tag::Equal, *l)));
+    edits
+}
+
+/// Write the hunk of the edits in the range [start, end).
+fn write_hunk(out: &mut String, edits: &[(Tag, &str)], start: usize, end: usize) {
+    let count = |edits: &[(Tag, &str)], excluded: Tag| {
+        edits.iter().filter(|(tag, _)| *tag != excluded).count()
+    };
+    let (old_before, new_before) = (
+        count(&edits[..start], Tag::Insert),
+        count(&edits[..start], Tag::Delete),
+    );
+    let (old_len, new_len) = (
+        count(&edits[start..end], Tag::Insert),
+        count(&edits[start..end], Tag::Delete),
+    );
+    // The start line of an empty range is the line before it.
+    let _ = writeln!(
+        out,
+        "@@ -{},{} +{},{} @@",
+        old_before + usize::from(old_len > 0),
+        old_len,
+        new_before + usize::from(new_len > 0),
+        new_len
+    );
+    for (tag, line) in &edits[start..end] {
+        out.push(match tag {
+            Tag::Equal => ' ',
+            Tag::Delete => '-',
+            Tag::Insert => '+',
+        });
+        out.push_str(line);
+        if !line.ends_with('\n') {
+            out.push_str("\n\\ No newline at end of file\n");
+        }
+    }
+}
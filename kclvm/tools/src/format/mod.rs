@@ -4,26 +4,45 @@
 //!
 //! The basic principle is to call the [kclvm_parser::parse_file] function to parse the
 //! AST Module, and then use the AST printer [kclvm_tools::printer::print_ast_module]
-//! to print it as source code string.
+//! to print it as source code string with the code style config in `.kcl-format.toml`.
+//! The `format_check` function formats the files in memory and returns the unified diffs
+//! without writing them.
 use anyhow::Result;
-use kclvm_ast_pretty::print_ast_module;
+use kclvm_ast_pretty::{print_ast_module_with_config, Config, QuoteStyle as PrinterQuoteStyle};
+use kclvm_config::format::{load_format_config, FormatConfig, QuoteStyle};
 use kclvm_parser::get_kcl_files;
 use std::path::Path;
 
 use kclvm_parser::{parse_file_force_errors, parse_single_file};
 
+mod diff;
 #[cfg(test)]
 mod tests;
 
-/// FormatOptions contains two options:
+pub use diff::unified_diff;
+
+/// FormatOptions contains the following options:
 /// - is_stdout: whether to output the formatted result to stdout.
 /// - recursively: whether to recursively traverse a folder and format all KCL files in it.
 /// - omit_errors: whether to omit the parse errors when format the KCL code.
+/// - config: the code style config e.g., the indent width and the quote style. When it
+///   is not set, the `.kcl-format.toml` in the directory of the formatted file or its
+///   parent directories is used.
 #[derive(Debug, Default)]
 pub struct FormatOptions {
     pub is_stdout: bool,
     pub recursively: bool,
     pub omit_errors: bool,
+    pub config: Option<FormatConfig>,
+}
+
+/// FileDiff denotes the unified diff between a file and its formatted source.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileDiff {
+    /// The path of the file which is not formatted.
+    pub file: String,
+    /// The unified diff from the file to its formatted source.
+    pub diff: String,
 }
 
 /// Formats kcl file or directory path contains kcl files and
@@ -68,6 +87,42 @@ pub fn format<P: AsRef<Path>>(path: P, opts: &FormatOptions) -> Result<Vec<Strin
     Ok(changed_paths)
 }
 
+/// Formats kcl files or directory paths contain kcl files in memory without writing
+/// them, and returns the unified diffs of the files which are not formatted.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_tools::format::{format_check, FormatOptions};
+///
+/// let diffs = format_check(&["path_to_a_folder"], &FormatOptions::default()).unwrap();
+/// for diff in &diffs {
+///     print!("{}", diff.diff);
+/// }
+/// ```
+pub fn format_check<P: AsRef<Path>>(paths: &[P], opts: &FormatOptions) -> Result<Vec<FileDiff>> {
+    let mut diffs = vec![];
+    for path in paths {
+        let path = path.as_ref();
+        let files = if path.is_dir() {
+            get_kcl_files(path, opts.recursively)?
+        } else {
+            vec![path.to_string_lossy().to_string()]
+        };
+        for file in files {
+            let src = std::fs::read_to_string(&file)?;
+            let (source, is_formatted) = format_source(&file, &src, opts)?;
+            if is_formatted {
+                diffs.push(FileDiff {
+                    diff: unified_diff(&file, &src, &source),
+                    file,
+                });
+            }
+        }
+    }
+    Ok(diffs)
+}
+
 /// Formats a file and returns whether the file has been formatted and modified.
 pub fn format_file(file: &str, opts: &FormatOptions) -> Result<bool> {
     let src = std::fs::read_to_string(file)?;
@@ -88,7 +143,28 @@ pub fn format_source(file: &str, src: &str, opts: &FormatOptions) -> Result<(Str
     } else {
         parse_file_force_errors(file, Some(src.to_string()))?
     };
-    let formatted_src = print_ast_module(&module);
+    let config = match &opts.config {
+        Some(config) => config.clone(),
+        None => load_format_config(file)?,
+    };
+    let formatted_src = print_ast_module_with_config(&module, src, printer_config(&config));
     let is_formatted = src != formatted_src;
     Ok((formatted_src, is_formatted))
 }
+
+/// Returns the AST printer config of the format config.
+fn printer_config(config: &FormatConfig) -> Config {
+    let default = Config::default();
+    Config {
+        indent_len: config.indent_width.unwrap_or(default.indent_len),
+        use_spaces: !config.use_tabs.unwrap_or_default(),
+        quote_style: match config.quote_style.unwrap_or_default() {
+            QuoteStyle::Preserve => PrinterQuoteStyle::Preserve,
+            QuoteStyle::Double => PrinterQuoteStyle::Double,
+            QuoteStyle::Single => PrinterQuoteStyle::Single,
+        },
+        max_line_width: config.max_line_width.unwrap_or(default.max_line_width),
+        magic_trailing_comma: config.magic_trailing_comma.unwrap_or_default(),
+        ..default
+    }
+}
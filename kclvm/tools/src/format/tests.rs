@@ -1,11 +1,12 @@
 use super::*;
+use kclvm_config::format::{FormatConfig, QuoteStyle};
 use kclvm_parser::parse_file_force_errors;
 use pretty_assertions::assert_eq;
 use walkdir::WalkDir;
 
 const FILE_INPUT_SUFFIX: &str = ".input";
 const FILE_OUTPUT_SUFFIX: &str = ".golden";
-const TEST_CASES: &[&str; 22] = &[
+const TEST_CASES: &[&str; 23] = &[
     "assert",
     "check",
     "blankline",
@@ -13,6 +14,7 @@ const TEST_CASES: &[&str; 22] = &[
     "codelayout",
     "collection_if",
     "comment",
+    "comment_preserve",
     "comp_for",
     "empty",
     "import",
@@ -82,6 +84,7 @@ fn test_format_with_stdout_option() {
         is_stdout: true,
         recursively: false,
         omit_errors: false,
+        ..Default::default()
     };
     let changed_files = format("./src/format/test_data/format_path_data/if.k", &opts).unwrap();
     assert_eq!(changed_files.len(), 1);
@@ -91,6 +94,7 @@ fn test_format_with_stdout_option() {
         is_stdout: true,
         recursively: true,
         omit_errors: false,
+        ..Default::default()
     };
     let changed_files = format("./src/format/test_data/format_path_data/", &opts).unwrap();
     assert_eq!(changed_files.len(), 2);
@@ -102,6 +106,7 @@ fn test_format_with_omit_error_option() {
         is_stdout: false,
         recursively: false,
         omit_errors: true,
+        ..Default::default()
     };
     let cases = [
         (
@@ -231,3 +236,143 @@ fn get_files<P: AsRef<Path>>(
     }
     files
 }
+
+#[test]
+fn test_format_with_config() {
+    let cases = [
+        (
+            FormatConfig {
+                indent_width: Some(2),
+                quote_style: Some(QuoteStyle::Double),
+                ..Default::default()
+            },
+            "x = {\n    a = 'foo'\n    b = \"it's\"\n}\n",
+            "x = {\n  a = \"foo\"\n  b = \"it's\"\n}\n",
+        ),
+        (
+            FormatConfig {
+                max_line_width: Some(20),
+                ..Default::default()
+            },
+            "data = [1, 2, 3, 4, 5, 6, 7, 8]\nshort = [1, 2]\n",
+            "data = [\n    1\n    2\n    3\n    4\n    5\n    6\n    7\n    8\n]\nshort = [1, 2]\n",
+        ),
+        (
+            FormatConfig {
+                magic_trailing_comma: Some(true),
+                ..Default::default()
+            },
+            "a = [1, 2,]\nb = {x = 1,}\nc = [1, 2]\n",
+            "a = [\n    1\n    2\n]\nb = {\n    x = 1\n}\nc = [1, 2]\n",
+        ),
+    ];
+    for (config, src, expected) in cases {
+        let opts = FormatOptions {
+            config: Some(config),
+            ..Default::default()
+        };
+        assert_eq!(format_source("", src, &opts).unwrap().0, expected);
+    }
+}
+
+fn comment_texts(code: &str) -> Vec<String> {
+    parse_file_force_errors("test.k", Some(code.to_string()))
+        .unwrap()
+        .comments
+        .iter()
+        .map(|comment| comment.node.text.clone())
+        .collect()
+}
+
+#[test]
+fn test_format_idempotency() {
+    for case in TEST_CASES {
+        let src = std::fs::read_to_string(format!(
+            "./src/format/test_data/format_data/{}{}",
+            case, FILE_INPUT_SUFFIX
+        ))
+        .unwrap();
+        let (formatted, _) = format_source("", &src, &Default::default()).unwrap();
+        let (reformatted, _) = format_source("", &formatted, &Default::default()).unwrap();
+        assert_eq!(formatted, reformatted, "Test failed on {}", case);
+        assert_eq!(
+            comment_texts(&src),
+            comment_texts(&formatted),
+            "Test failed on {}",
+            case
+        );
+    }
+}
+
+#[test]
+fn test_format_comments() {
+    let cases = [
+        (
+            "x = {\n    a = 1 # c1\n    b = 2  # c2\n    # tail\n}\n",
+            "x = {\n    a = 1  # c1\n    b = 2  # c2\n    # tail\n}\n",
+        ),
+        (
+            "x = [\n    1  # one\n    2\n    # tail\n]  # end\n",
+            "x = [\n    1  # one\n    2\n    # tail\n]  # end\n",
+        ),
+        (
+            "a = 1\n\n# c1\n\n# c2\nb = 2\n",
+            "a = 1\n\n# c1\n\n# c2\nb = 2\n",
+        ),
+        (
+            "a = 1\n# c1\n\n\n\nb = 2 # c2\n",
+            "a = 1\n# c1\n\nb = 2  # c2\n",
+        ),
+    ];
+    for (src, expected) in cases {
+        assert_eq!(
+            format_source("", src, &Default::default()).unwrap().0,
+            expected
+        );
+    }
+}
+
+#[test]
+fn test_unified_diff() {
+    let cases = [
+        ("a = 1\n", "a = 1\n", ""),
+        (
+            "a = 1\nb =  2\nc = 3\n",
+            "a = 1\nb = 2\nc = 3\n",
+            "--- main.k\n+++ main.k\n@@ -1,3 +1,3 @@\n a = 1\n-b =  2\n+b = 2\n c = 3\n",
+        ),
+        (
+            "a = 1",
+            "a = 1\n",
+            "--- main.k\n+++ main.k\n@@ -1,1 +1,1 @@\n-a = 1\n\\ No newline at end of file\n+a = 1\n",
+        ),
+        (
+            "a = 1\n\n\n\nb = 2\n",
+            "a = 1\n\nb = 2\n",
+            "--- main.k\n+++ main.k\n@@ -1,5 +1,3 @@\n a = 1\n \n-\n-\n b = 2\n",
+        ),
+    ];
+    for (old, new, expected) in cases {
+        assert_eq!(unified_diff("main.k", old, new), expected);
+    }
+}
+
+#[test]
+fn test_format_check() {
+    let file = "./src/format/test_data/format_path_data/if.k";
+    let src = std::fs::read_to_string(file).unwrap();
+    let diffs = format_check(
+        &[file, "./src/format/test_data/format_path_data/folder"],
+        &FormatOptions::default(),
+    )
+    .unwrap();
+    assert_eq!(diffs.len(), 1);
+    assert_eq!(diffs[0].file, file);
+    assert!(diffs[0]
+        .diff
+        .starts_with(&format!("--- {}\n+++ {}\n@@ -1,", file, file)));
+    assert!(diffs[0].diff.contains("\n- a = 1\n"));
+    assert!(diffs[0].diff.contains("\n+    a = 1\n"));
+    // The checked file is not written.
+    assert_eq!(std::fs::read_to_string(file).unwrap(), src);
+}
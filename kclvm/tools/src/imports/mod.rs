@@ -0,0 +1,186 @@
+//! [kclvm_tools::imports] module mainly contains the import organizer, the main API
+//! function is `organize_imports`, which sorts the import statements at the top of a
+//! module into the groups of the standard system modules, the plugins, the external
+//! packages and the internal packages, removes the duplicate imports and optionally
+//! removes the imports flagged unused by the resolver.
+//!
+//! The organizer only rewrites the source lines of the leading import statements, thus
+//! the comments before and after the import statements are moved with them and the
+//! other code is kept as it is.
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use anyhow::Result;
+use kclvm_ast::ast;
+use kclvm_config::lint::LintConfig;
+use kclvm_error::{DiagnosticId, WarningKind};
+use kclvm_parser::{parse_file_force_errors, LoadProgramOptions};
+use kclvm_sema::{builtin::STANDARD_SYSTEM_MODULES, plugin::PLUGIN_MODULE_PREFIX};
+
+use crate::lint::{lint_files_with_rule_names, LintRegistry, UnusedImportRule};
+
+#[cfg(test)]
+mod tests;
+
+/// The group of an import statement, and the groups are organized in the order of
+/// the variants and separated by blank lines.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ImportGroup {
+    /// The standard system modules e.g., `import math`.
+    Std,
+    /// The plugin modules e.g., `import kcl_plugin.hello`.
+    Plugin,
+    /// The modules of the external packages in the package maps e.g., `import k8s.api`.
+    External,
+    /// The modules of the current package including the relative imports e.g., `import .base`.
+    Internal,
+}
+
+impl ImportGroup {
+    /// Returns the group of the raw import path.
+    pub fn of(path: &str, package_maps: &HashMap<String, String>) -> Self {
+        if STANDARD_SYSTEM_MODULES.contains(&path) {
+            ImportGroup::Std
+        } else if path.starts_with(PLUGIN_MODULE_PREFIX) {
+            ImportGroup::Plugin
+        } else if path
+            .split('.')
+            .next()
+            .map_or(false, |root| package_maps.contains_key(root))
+        {
+            ImportGroup::External
+        } else {
+            ImportGroup::Internal
+        }
+    }
+}
+
+/// OrganizeImportsOptions contains the following options:
+/// - remove_unused: whether to remove the imports flagged unused by the resolver.
+/// - package_maps: the external package name and path maps, which are used to group the
+///   external imports and resolve the unused imports.
+#[derive(Debug, Default, Clone)]
+pub struct OrganizeImportsOptions {
+    pub remove_unused: bool,
+    pub package_maps: HashMap<String, String>,
+}
+
+/// An import statement with the source lines moved with it.
+struct ImportEntry<'a> {
+    group: ImportGroup,
+    path: &'a str,
+    asname: Option<&'a str>,
+    text: String,
+}
+
+/// Organizes the imports of a code source and returns the organized source and
+/// whether the source is changed.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_tools::imports::{organize_imports, OrganizeImportsOptions};
+///
+/// let src = "import regex\nimport .base\nimport math\nimport regex\n\na = 1\n";
+/// let (source, is_changed) =
+///     organize_imports("main.k", src, &OrganizeImportsOptions::default()).unwrap();
+/// assert_eq!(source, "import math\nimport regex\n\nimport .base\n\na = 1\n");
+/// assert!(is_changed);
+/// ```
+pub fn organize_imports(
+    file: &str,
+    src: &str,
+    opts: &OrganizeImportsOptions,
+) -> Result<(String, bool)> {
+    let module = parse_file_force_errors(file, Some(src.to_string()))?;
+    let imports: Vec<(&ast::Node<ast::Stmt>, &ast::ImportStmt)> = module
+        .body
+        .iter()
+        .map_while(|stmt| match &stmt.node {
+            ast::Stmt::Import(import_stmt) => Some((&**stmt, import_stmt)),
+            _ => None,
+        })
+        .collect();
+    if imports.is_empty() {
+        return Ok((src.to_string(), false));
+    }
+    let unused_lines = if opts.remove_unused {
+        unused_import_lines(file, src, opts)
+    } else {
+        HashSet::new()
+    };
+    let lines: Vec<&str> = src.split_inclusive('\n').collect();
+    // The comments right before the first import statement are moved with it.
+    let mut region_start = imports[0].0.line as usize;
+    while region_start > 1 && lines[region_start - 2].trim_start().starts_with('#') {
+        region_start -= 1;
+    }
+    let mut entries: Vec<ImportEntry> = vec![];
+    let mut seen = HashSet::new();
+    let mut start = region_start;
+    for (stmt, import_stmt) in &imports {
+        let end = stmt.end_line as usize;
+        let text: String = lines[start - 1..end]
+            .iter()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| format!("{}\n", line.trim_end_matches(&['\r', '\n'][..])))
+            .collect();
+        start = end + 1;
+        let asname = import_stmt.asname.as_ref().map(|name| name.node.as_str());
+        if unused_lines.contains(&stmt.line) || !seen.insert((&import_stmt.rawpath, asname)) {
+            continue;
+        }
+        entries.push(ImportEntry {
+            group: ImportGroup::of(&import_stmt.rawpath, &opts.package_maps),
+            path: &import_stmt.rawpath,
+            asname,
+            text,
+        });
+    }
+    entries.sort_by(|a, b| (a.group, a.path, a.asname).cmp(&(b.group, b.path, b.asname)));
+
+    let mut out: String = lines[..region_start - 1].concat();
+    for (i, entry) in entries.iter().enumerate() {
+        if i > 0 && entries[i - 1].group != entry.group {
+            out.push('\n');
+        }
+        out.push_str(&entry.text);
+    }
+    let mut rest = &lines[start - 1..];
+    // Remove the blank lines after the removed imports.
+    if entries.is_empty() {
+        while rest.first().map_or(false, |line| line.trim().is_empty()) {
+            rest = &rest[1..];
+        }
+    }
+    out.push_str(&rest.concat());
+    let is_changed = out != src;
+    Ok((out, is_changed))
+}
+
+/// Returns the lines of the import statements in the file flagged unused by the resolver.
+fn unused_import_lines(file: &str, src: &str, opts: &OrganizeImportsOptions) -> HashSet<u64> {
+    let mut registry = LintRegistry::new();
+    registry.register(Box::new(UnusedImportRule));
+    let load_opts = LoadProgramOptions {
+        k_code_list: vec![src.to_string()],
+        package_maps: opts.package_maps.clone(),
+        ..Default::default()
+    };
+    let is_same_file = |filename: &str| {
+        filename == file
+            || matches!(
+                (Path::new(filename).canonicalize(), Path::new(file).canonicalize()),
+                (Ok(a), Ok(b)) if a == b
+            )
+    };
+    lint_files_with_rule_names(&[file], Some(load_opts), &registry, &LintConfig::default())
+        .iter()
+        .filter(|result| {
+            result.diagnostic.code == Some(DiagnosticId::Warning(WarningKind::UnusedImportWarning))
+        })
+        .flat_map(|result| &result.diagnostic.messages)
+        .filter(|msg| is_same_file(&msg.range.0.filename))
+        .map(|msg| msg.range.0.line)
+        .collect()
+}
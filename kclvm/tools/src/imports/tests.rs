@@ -0,0 +1,85 @@
+use super::*;
+use pretty_assertions::assert_eq;
+
+#[test]
+fn test_import_group() {
+    let package_maps = HashMap::from([("k8s".to_string(), "/path/to/k8s".to_string())]);
+    let cases = [
+        ("math", ImportGroup::Std),
+        ("kcl_plugin.hello", ImportGroup::Plugin),
+        ("k8s.api.core.v1", ImportGroup::External),
+        ("pkg.base", ImportGroup::Internal),
+        (".base", ImportGroup::Internal),
+    ];
+    for (path, expected) in cases {
+        assert_eq!(ImportGroup::of(path, &package_maps), expected);
+    }
+}
+
+#[test]
+fn test_organize_imports() {
+    let opts = OrganizeImportsOptions {
+        package_maps: HashMap::from([("ext".to_string(), "/path/to/ext".to_string())]),
+        ..Default::default()
+    };
+    let cases = [
+        (
+            r#"# Header comment
+
+# The regex module.
+import regex
+import .base
+import kcl_plugin.hello
+import math
+import ext.sub as sub
+import regex  # duplicated
+import base as b
+
+a = 1
+"#,
+            r#"# Header comment
+
+import math
+# The regex module.
+import regex
+
+import kcl_plugin.hello
+
+import ext.sub as sub
+
+import .base
+import base as b
+
+a = 1
+"#,
+            true,
+        ),
+        (
+            "import math\nimport regex\n\na = 1\n",
+            "import math\nimport regex\n\na = 1\n",
+            false,
+        ),
+        ("a = 1\n", "a = 1\n", false),
+    ];
+    for (src, expected, expected_changed) in cases {
+        let (source, is_changed) = organize_imports("main.k", src, &opts).unwrap();
+        assert_eq!(source, expected);
+        assert_eq!(is_changed, expected_changed);
+    }
+}
+
+#[test]
+fn test_organize_imports_remove_unused() {
+    let file = "./src/imports/test_data/unused.k";
+    let src = std::fs::read_to_string(file).unwrap();
+    let opts = OrganizeImportsOptions {
+        remove_unused: true,
+        ..Default::default()
+    };
+    let (source, is_changed) = organize_imports(file, &src, &opts).unwrap();
+    assert_eq!(source, "import math\n\na = math.log(1)\n");
+    assert!(is_changed);
+    // All imports are removed.
+    let (source, _) = organize_imports(file, "import math\n\na = 1\n", &opts).unwrap();
+    assert_eq!(source, "a = 1\n");
+}
@@ -1,5 +1,8 @@
+pub mod compat;
+pub mod docgen;
 pub mod fix;
 pub mod format;
+pub mod imports;
 pub mod lint;
 pub mod testing;
 pub mod util;
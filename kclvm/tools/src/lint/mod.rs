@@ -1,13 +1,27 @@
 use std::sync::Arc;
 
 use indexmap::IndexSet;
+use kclvm_config::lint::{load_lint_config, LintConfig};
 use kclvm_error::{Diagnostic, Handler};
 use kclvm_parser::{load_program, LoadProgramOptions, ParseSession};
 use kclvm_runtime::PanicInfo;
+use kclvm_sema::advanced_resolver::AdvancedResolver;
+use kclvm_sema::core::global_state::GlobalState;
+use kclvm_sema::namer::Namer;
+use kclvm_sema::pass::PassContext;
 use kclvm_sema::resolver::resolve_program_with_opts;
+
+mod naming;
+mod rule;
+mod rules;
+mod suppress;
 #[cfg(test)]
 mod tests;
 
+pub use naming::{AttributeNamingRule, ConstantNamingRule, PackageNamingRule, SchemaNamingRule};
+pub use rule::{LintRegistry, LintRule, LintRuleContext};
+pub use rules::{ImportPositionRule, ReimportRule, UnusedImportRule};
+
 /// KCL Lint tools API, check a set of files, skips execute, divides and returns diagnostics into error and warning
 ///
 /// # Parameters
@@ -69,6 +83,78 @@ pub fn lint_files(
     files: &[&str],
     opts: Option<LoadProgramOptions>,
 ) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
+    // Load the lint config from the work dir.
+    let config = match &opts {
+        Some(opts) if !opts.work_dir.is_empty() => {
+            load_lint_config(&opts.work_dir).unwrap_or_default()
+        }
+        _ => LintConfig::default(),
+    };
+    lint_files_with_rules(files, opts, &LintRegistry::default(), &config)
+}
+
+/// KCL Lint tools API with the lint rules in the registry and the lint config, which
+/// configures the levels of the rules. See [lint_files] for the details.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_config::lint::LintConfig;
+/// use kclvm_tools::lint::{lint_files_with_rules, LintRegistry};
+///
+/// let (errors, warnings) =
+///     lint_files_with_rules(&["test.k"], None, &LintRegistry::default(), &LintConfig::default());
+/// ```
+pub fn lint_files_with_rules(
+    files: &[&str],
+    opts: Option<LoadProgramOptions>,
+    registry: &LintRegistry,
+    config: &LintConfig,
+) -> (IndexSet<Diagnostic>, IndexSet<Diagnostic>) {
+    let mut handler = Handler::default();
+    for result in lint_files_with_rule_names(files, opts, registry, config) {
+        handler.diagnostics.insert(result.diagnostic);
+    }
+    handler.classification()
+}
+
+/// LintDiagnostic denotes a diagnostic of the lint check with the name of the lint rule
+/// reporting it, and the rule is None for the parse and resolve diagnostics.
+#[derive(Debug, Clone, PartialEq)]
+pub struct LintDiagnostic {
+    pub rule: Option<&'static str>,
+    pub diagnostic: Diagnostic,
+}
+
+/// KCL Lint tools API like [lint_files_with_rules], which returns the error and warning
+/// diagnostics with the names of the lint rules reporting them.
+///
+/// # Examples
+///
+/// ```no_run
+/// use kclvm_config::lint::LintConfig;
+/// use kclvm_tools::lint::{lint_files_with_rule_names, LintRegistry};
+///
+/// let results = lint_files_with_rule_names(
+///     &["test.k"],
+///     None,
+///     &LintRegistry::default(),
+///     &LintConfig::default(),
+/// );
+/// for result in results {
+///     println!("{:?}: {:?}", result.rule, result.diagnostic.messages);
+/// }
+/// ```
+pub fn lint_files_with_rule_names(
+    files: &[&str],
+    opts: Option<LoadProgramOptions>,
+    registry: &LintRegistry,
+    config: &LintConfig,
+) -> Vec<LintDiagnostic> {
+    let compile_diagnostic = |diagnostic| LintDiagnostic {
+        rule: None,
+        diagnostic,
+    };
     // Parse AST program.
     let sess = Arc::new(ParseSession::default());
     let mut opts = opts.unwrap_or_default();
@@ -76,22 +162,49 @@ pub fn lint_files(
     let mut program = match load_program(sess.clone(), files, Some(opts), None) {
         Ok(p) => p.program,
         Err(err_str) => {
-            return Handler::default()
+            let (errs, warnings) = Handler::default()
                 .add_panic_info(&PanicInfo::from(err_str.to_string()))
                 .classification();
+            return errs
+                .into_iter()
+                .chain(warnings)
+                .map(compile_diagnostic)
+                .collect();
         }
     };
-    sess.append_diagnostic(
-        resolve_program_with_opts(
-            &mut program,
-            kclvm_sema::resolver::Options {
-                merge_program: false,
-                ..Default::default()
-            },
-            None,
+    // The lint checks are run by the lint rules instead of the resolver.
+    let scope = resolve_program_with_opts(
+        &mut program,
+        kclvm_sema::resolver::Options {
+            lint_check: false,
+            merge_program: false,
+            type_erasure: false,
+            ..Default::default()
+        },
+        None,
+    );
+    let mut gs = GlobalState::default();
+    Namer::find_symbols(&program, &mut gs);
+    // The rules which only depend on the program scope are still available when
+    // the semantic information is not complete.
+    let _ = AdvancedResolver::resolve_program(&program, &mut gs, scope.node_ty_map.clone());
+    let lint_diags = registry.check_with_rule_names(&LintRuleContext {
+        pass: PassContext::with_global_state(&program, &scope, &gs),
+        config,
+    });
+    let (errs, warnings) = sess
+        .append_diagnostic(scope.handler.diagnostics.clone())
+        .classification();
+    errs.into_iter()
+        .chain(warnings)
+        .map(compile_diagnostic)
+        .chain(
+            lint_diags
+                .into_iter()
+                .map(|(rule, diagnostic)| LintDiagnostic {
+                    rule: Some(rule),
+                    diagnostic,
+                }),
         )
-        .handler
-        .diagnostics,
-    )
-    .classification()
+        .collect()
 }
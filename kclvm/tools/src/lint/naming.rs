@@ -0,0 +1,372 @@
+//! The naming convention lint rules, which check the names in the KCL code with the
+//! naming styles in the lint config and suggest the fixed names.
+
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_config::lint::NamingStyle;
+use kclvm_error::{
+    Applicability, Diagnostic, DiagnosticId, FixIt, Handler, Level, Position, WarningKind,
+};
+use kclvm_sema::lint::for_each_module;
+use kclvm_sema::pass::{PassContext, SemanticPass};
+
+use super::rule::{LintRule, LintRuleContext};
+
+/// The 'schema-naming' rule checks the schema and rule names, default is `PascalCase`.
+pub struct SchemaNamingRule;
+
+impl SemanticPass for SchemaNamingRule {
+    fn name(&self) -> &'static str {
+        "schema-naming"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        check_schema_names(ctx.program, NamingStyle::PascalCase, handler);
+    }
+}
+
+impl LintRule for SchemaNamingRule {
+    fn check(&self, ctx: &LintRuleContext, handler: &mut Handler) {
+        let style = ctx
+            .config
+            .get_naming()
+            .schema
+            .unwrap_or(NamingStyle::PascalCase);
+        check_schema_names(ctx.pass.program, style, handler);
+    }
+}
+
+fn check_schema_names(program: &ast::Program, style: NamingStyle, handler: &mut Handler) {
+    for_each_module(program, |module| {
+        for stmt in &module.body {
+            let name = match &stmt.node {
+                ast::Stmt::Schema(schema_stmt) => &schema_stmt.name,
+                ast::Stmt::Rule(rule_stmt) => &rule_stmt.name,
+                _ => continue,
+            };
+            check_name(handler, "Schema", &name.node, name.get_span_pos(), style);
+        }
+    });
+}
+
+/// The 'attribute-naming' rule checks the schema attribute names, default is `camelCase`.
+pub struct AttributeNamingRule;
+
+impl SemanticPass for AttributeNamingRule {
+    fn name(&self) -> &'static str {
+        "attribute-naming"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        check_attribute_naming(ctx.program, NamingStyle::CamelCase, handler);
+    }
+}
+
+impl LintRule for AttributeNamingRule {
+    fn check(&self, ctx: &LintRuleContext, handler: &mut Handler) {
+        let style = ctx
+            .config
+            .get_naming()
+            .attribute
+            .unwrap_or(NamingStyle::CamelCase);
+        check_attribute_naming(ctx.pass.program, style, handler);
+    }
+}
+
+fn check_attribute_naming(program: &ast::Program, style: NamingStyle, handler: &mut Handler) {
+    for_each_module(program, |module| {
+        for stmt in &module.body {
+            if let ast::Stmt::Schema(schema_stmt) = &stmt.node {
+                check_attribute_names(handler, &schema_stmt.body, style);
+            }
+        }
+    });
+}
+
+/// Check the names of the attributes declared in the schema body, and the attributes
+/// inherited from the parent schemas are not checked.
+fn check_attribute_names(
+    handler: &mut Handler,
+    body: &[ast::NodeRef<ast::Stmt>],
+    style: NamingStyle,
+) {
+    for stmt in body {
+        match &stmt.node {
+            ast::Stmt::SchemaAttr(schema_attr) => {
+                let name = &schema_attr.name;
+                check_name(handler, "Attribute", &name.node, name.get_span_pos(), style);
+            }
+            ast::Stmt::If(if_stmt) => {
+                check_attribute_names(handler, &if_stmt.body, style);
+                check_attribute_names(handler, &if_stmt.orelse, style);
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The 'package-naming' rule checks the package names in the import paths, default
+/// is `snake_case`.
+pub struct PackageNamingRule;
+
+impl SemanticPass for PackageNamingRule {
+    fn name(&self) -> &'static str {
+        "package-naming"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        check_package_names(ctx.program, NamingStyle::SnakeCase, handler);
+    }
+}
+
+impl LintRule for PackageNamingRule {
+    fn check(&self, ctx: &LintRuleContext, handler: &mut Handler) {
+        let style = ctx
+            .config
+            .get_naming()
+            .package
+            .unwrap_or(NamingStyle::SnakeCase);
+        check_package_names(ctx.pass.program, style, handler);
+    }
+}
+
+fn check_package_names(program: &ast::Program, style: NamingStyle, handler: &mut Handler) {
+    for_each_module(program, |module| {
+        for stmt in &module.body {
+            if let ast::Stmt::Import(import_stmt) = &stmt.node {
+                let path = &import_stmt.path;
+                let prefix_len = path.node.len() - path.node.trim_start_matches('.').len();
+                let (prefix, path_str) = path.node.split_at(prefix_len);
+                let names: Vec<&str> = path_str.split('.').collect();
+                if names.iter().all(|name| is_style(name, style)) {
+                    continue;
+                }
+                let fixed = names
+                    .iter()
+                    .map(|name| to_style(name, style))
+                    .collect::<Vec<String>>()
+                    .join(".");
+                add_naming_warning(
+                    handler,
+                    format!(
+                        "Package name '{}' does not follow the naming convention {}",
+                        path_str,
+                        style_name(style)
+                    ),
+                    path.get_span_pos(),
+                    format!("{}{}", prefix, fixed),
+                );
+            }
+        }
+    });
+}
+
+/// The 'constant-naming' rule checks the constants, which are the top level variables
+/// starting with an uppercase letter, default is `UPPER_SNAKE_CASE`.
+pub struct ConstantNamingRule;
+
+impl SemanticPass for ConstantNamingRule {
+    fn name(&self) -> &'static str {
+        "constant-naming"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        check_constant_names(ctx.program, NamingStyle::UpperSnakeCase, handler);
+    }
+}
+
+impl LintRule for ConstantNamingRule {
+    fn check(&self, ctx: &LintRuleContext, handler: &mut Handler) {
+        let style = ctx
+            .config
+            .get_naming()
+            .constant
+            .unwrap_or(NamingStyle::UpperSnakeCase);
+        check_constant_names(ctx.pass.program, style, handler);
+    }
+}
+
+fn check_constant_names(program: &ast::Program, style: NamingStyle, handler: &mut Handler) {
+    for_each_module(program, |module| {
+        for stmt in &module.body {
+            if let ast::Stmt::Assign(assign_stmt) = &stmt.node {
+                for target in &assign_stmt.targets {
+                    let name = &target.node.name;
+                    if !target.node.paths.is_empty()
+                        || !name.node.starts_with(|c: char| c.is_ascii_uppercase())
+                    {
+                        continue;
+                    }
+                    check_name(handler, "Constant", &name.node, name.get_span_pos(), style);
+                }
+            }
+        }
+    });
+}
+
+fn check_name(
+    handler: &mut Handler,
+    kind: &str,
+    name: &str,
+    range: (Position, Position),
+    style: NamingStyle,
+) {
+    if is_style(name, style) {
+        return;
+    }
+    add_naming_warning(
+        handler,
+        format!(
+            "{} name '{}' does not follow the naming convention {}",
+            kind,
+            name,
+            style_name(style)
+        ),
+        range,
+        to_style(name, style),
+    );
+}
+
+fn add_naming_warning(
+    handler: &mut Handler,
+    message: String,
+    range: (Position, Position),
+    fixed: String,
+) {
+    // Renaming the definition without its references may break the code, thus the
+    // fix needs the user review.
+    let fix = FixIt::new(range.clone(), &fixed, Applicability::MaybeIncorrect);
+    handler.add_diagnostic(
+        Diagnostic::new_with_code(
+            Level::Warning,
+            &message,
+            Some(&format!("Consider renaming it to '{}'", fixed)),
+            range,
+            Some(DiagnosticId::Warning(WarningKind::NamingConventionWarning)),
+            Some(vec![fixed]),
+        )
+        .with_fix(fix),
+    );
+}
+
+fn style_name(style: NamingStyle) -> &'static str {
+    match style {
+        NamingStyle::PascalCase => "PascalCase",
+        NamingStyle::CamelCase => "camelCase",
+        NamingStyle::SnakeCase => "snake_case",
+        NamingStyle::UpperSnakeCase => "UPPER_SNAKE_CASE",
+    }
+}
+
+/// Split the name e.g., `$_HTTPServerName` into the prefix `$_` which is ignored by
+/// the naming styles, and the name body `HTTPServerName`.
+fn split_prefix(name: &str) -> (&str, &str) {
+    let body = name.trim_start_matches(|c| c == '$' || c == '_');
+    name.split_at(name.len() - body.len())
+}
+
+/// Whether the name follows the naming style, and the leading `$` and `_` are ignored.
+pub(crate) fn is_style(name: &str, style: NamingStyle) -> bool {
+    let (_, body) = split_prefix(name);
+    let mut chars = body.chars();
+    let first = match chars.next() {
+        Some(first) => first,
+        None => return true,
+    };
+    match style {
+        NamingStyle::PascalCase => first.is_ascii_uppercase() && !body.contains('_'),
+        NamingStyle::CamelCase => !first.is_ascii_uppercase() && !body.contains('_'),
+        NamingStyle::SnakeCase => !body.chars().any(|c| c.is_ascii_uppercase()),
+        NamingStyle::UpperSnakeCase => !body.chars().any(|c| c.is_ascii_lowercase()),
+    }
+}
+
+/// Convert the name to the naming style, and the leading `$` and `_` are kept.
+pub(crate) fn to_style(name: &str, style: NamingStyle) -> String {
+    let (prefix, body) = split_prefix(name);
+    let words = split_words(body);
+    let capitalize = |word: &str| {
+        let mut chars = word.chars();
+        match chars.next() {
+            Some(first) => first.to_ascii_uppercase().to_string() + &chars.as_str().to_lowercase(),
+            None => String::new(),
+        }
+    };
+    let body = match style {
+        NamingStyle::PascalCase => words.iter().map(|w| capitalize(w)).collect::<String>(),
+        NamingStyle::CamelCase => words
+            .iter()
+            .enumerate()
+            .map(|(i, w)| {
+                if i == 0 {
+                    w.to_lowercase()
+                } else {
+                    capitalize(w)
+                }
+            })
+            .collect::<String>(),
+        NamingStyle::SnakeCase => words
+            .iter()
+            .map(|w| w.to_lowercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+        NamingStyle::UpperSnakeCase => words
+            .iter()
+            .map(|w| w.to_uppercase())
+            .collect::<Vec<String>>()
+            .join("_"),
+    };
+    format!("{}{}", prefix, body)
+}
+
+/// Split the name into words by the underscores and the case boundaries e.g.,
+/// `HTTPServer_name` into `HTTP`, `Server` and `name`.
+fn split_words(name: &str) -> Vec<&str> {
+    let mut words = vec![];
+    for part in name.split('_').filter(|part| !part.is_empty()) {
+        let chars: Vec<(usize, char)> = part.char_indices().collect();
+        let mut start = 0;
+        for i in 1..chars.len() {
+            let (idx, c) = chars[i];
+            let prev = chars[i - 1].1;
+            let next_is_lower = chars
+                .get(i + 1)
+                .map_or(false, |(_, next)| next.is_ascii_lowercase());
+            if c.is_ascii_uppercase() && (!prev.is_ascii_uppercase() || next_is_lower) {
+                words.push(&part[start..idx]);
+                start = idx;
+            }
+        }
+        words.push(&part[start..]);
+    }
+    words
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{is_style, to_style};
+    use kclvm_config::lint::NamingStyle;
+
+    #[test]
+    fn test_naming_style() {
+        let cases = [
+            ("personInfo", NamingStyle::PascalCase, false, "PersonInfo"),
+            ("HTTPServer", NamingStyle::PascalCase, true, "HttpServer"),
+            ("person_info", NamingStyle::CamelCase, false, "personInfo"),
+            ("_PersonInfo", NamingStyle::CamelCase, false, "_personInfo"),
+            ("apiVersion", NamingStyle::SnakeCase, false, "api_version"),
+            (
+                "$HTTPServer2Name",
+                NamingStyle::SnakeCase,
+                false,
+                "$http_server2_name",
+            ),
+            ("MaxSize", NamingStyle::UpperSnakeCase, false, "MAX_SIZE"),
+            ("MAX_SIZE", NamingStyle::UpperSnakeCase, true, "MAX_SIZE"),
+        ];
+        for (name, style, expected, fixed) in cases {
+            assert_eq!(is_style(name, style), expected, "{}", name);
+            assert_eq!(to_style(name, style), fixed, "{}", name);
+        }
+    }
+}
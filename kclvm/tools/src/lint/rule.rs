@@ -0,0 +1,138 @@
+use kclvm_config::lint::{LintConfig, LintLevel};
+use kclvm_error::{Diagnostic, Handler, Level};
+use kclvm_sema::pass::{PassContext, SemanticPass};
+
+use super::naming::{AttributeNamingRule, ConstantNamingRule, PackageNamingRule, SchemaNamingRule};
+use super::rules::{ImportPositionRule, ReimportRule, UnusedImportRule};
+use super::suppress::Suppressions;
+
+/// The resolved program checked by the lint rules.
+pub struct LintRuleContext<'a> {
+    /// The resolved program analyzed by the rules as the semantic passes.
+    pub pass: PassContext<'a>,
+    /// The lint config.
+    pub config: &'a LintConfig,
+}
+
+/// A lint rule is a semantic pass whose diagnostics are reported at the level configured
+/// in the lint config, and the name of the pass is the rule name used in the lint config
+/// and the suppression comments, e.g., `unused-import`.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_error::Handler;
+/// use kclvm_sema::pass::{PassContext, SemanticPass};
+/// use kclvm_tools::lint::{LintRegistry, LintRule};
+///
+/// struct NoopRule;
+///
+/// impl SemanticPass for NoopRule {
+///     fn name(&self) -> &'static str {
+///         "noop"
+///     }
+///
+///     fn run(&self, _ctx: &PassContext, _handler: &mut Handler) {}
+/// }
+///
+/// impl LintRule for NoopRule {}
+///
+/// let mut registry = LintRegistry::default();
+/// registry.register(Box::new(NoopRule));
+/// assert!(registry.get("noop").is_some());
+/// ```
+pub trait LintRule: SemanticPass {
+    /// The level of the diagnostics reported by the rule when it is not configured.
+    fn default_level(&self) -> LintLevel {
+        LintLevel::Warning
+    }
+    /// Check the program with the lint config and report the diagnostics into the handler,
+    /// which runs the semantic pass by default.
+    fn check(&self, ctx: &LintRuleContext, handler: &mut Handler) {
+        self.run(&ctx.pass, handler);
+    }
+}
+
+/// LintRegistry holds the lint rules which are run by the linter.
+pub struct LintRegistry {
+    rules: Vec<Box<dyn LintRule>>,
+}
+
+impl Default for LintRegistry {
+    /// The registry with the builtin rules.
+    fn default() -> Self {
+        let mut registry = Self::new();
+        registry.register(Box::new(ImportPositionRule));
+        registry.register(Box::new(ReimportRule));
+        registry.register(Box::new(UnusedImportRule));
+        registry.register(Box::new(SchemaNamingRule));
+        registry.register(Box::new(AttributeNamingRule));
+        registry.register(Box::new(PackageNamingRule));
+        registry.register(Box::new(ConstantNamingRule));
+        registry
+    }
+}
+
+impl LintRegistry {
+    /// New an empty registry.
+    pub fn new() -> Self {
+        Self { rules: vec![] }
+    }
+
+    /// Register the rule, which replaces the registered rule with the same name.
+    pub fn register(&mut self, rule: Box<dyn LintRule>) {
+        self.rules.retain(|r| r.name() != rule.name());
+        self.rules.push(rule);
+    }
+
+    /// Get the registered rule by its name.
+    pub fn get(&self, name: &str) -> Option<&dyn LintRule> {
+        self.rules
+            .iter()
+            .find(|r| r.name() == name)
+            .map(|r| r.as_ref())
+    }
+
+    /// Get all the registered rules.
+    pub fn rules(&self) -> &[Box<dyn LintRule>] {
+        &self.rules
+    }
+
+    /// Run the rules which are not turned off in the config, and return the diagnostics
+    /// at the configured levels except the ones suppressed by the comments e.g.,
+    /// `# kcl-lint: disable=unused-import`.
+    pub fn check(&self, ctx: &LintRuleContext) -> Vec<Diagnostic> {
+        self.check_with_rule_names(ctx)
+            .into_iter()
+            .map(|(_, diag)| diag)
+            .collect()
+    }
+
+    /// Run the rules like [LintRegistry::check], and return the diagnostics with the
+    /// names of the rules reporting them.
+    pub fn check_with_rule_names(&self, ctx: &LintRuleContext) -> Vec<(&'static str, Diagnostic)> {
+        let suppressions = Suppressions::from_program(ctx.pass.program);
+        let mut diags = vec![];
+        for rule in &self.rules {
+            let level = match ctx
+                .config
+                .get_level(rule.name())
+                .unwrap_or(rule.default_level())
+            {
+                LintLevel::Off => continue,
+                LintLevel::Warning => Level::Warning,
+                LintLevel::Error => Level::Error,
+            };
+            let mut handler = Handler::default();
+            rule.check(ctx, &mut handler);
+            for mut diag in handler.diagnostics {
+                if suppressions.is_suppressed(rule.name(), &diag) {
+                    continue;
+                }
+                diag.level = level;
+                diags.push((rule.name(), diag));
+            }
+        }
+        diags
+    }
+}
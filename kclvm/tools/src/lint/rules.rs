@@ -0,0 +1,14 @@
+//! The builtin lint rules, which are the lint passes run by the resolver.
+
+pub use kclvm_sema::lint::{
+    ImportPosition as ImportPositionRule, ReImport as ReimportRule,
+    UnusedImport as UnusedImportRule,
+};
+
+use super::rule::LintRule;
+
+impl LintRule for ImportPositionRule {}
+
+impl LintRule for ReimportRule {}
+
+impl LintRule for UnusedImportRule {}
@@ -0,0 +1,81 @@
+use std::collections::{HashMap, HashSet};
+
+use kclvm_ast::ast;
+use kclvm_error::Diagnostic;
+
+const SUPPRESS_PREFIX: &str = "kcl-lint:";
+const SUPPRESS_ALL: &str = "all";
+
+/// The lint rules suppressed by the inline comments in the modules:
+///
+/// - `# kcl-lint: disable=<rule>,...` suppresses the rules on the line of the comment.
+/// - `# kcl-lint: disable-next-line=<rule>,...` suppresses the rules on the next line.
+/// - `# kcl-lint: disable-file=<rule>,...` suppresses the rules in the whole file.
+///
+/// The rule name `all` denotes all the rules.
+#[derive(Debug, Default)]
+pub(crate) struct Suppressions {
+    lines: HashMap<(String, u64), HashSet<String>>,
+    files: HashMap<String, HashSet<String>>,
+}
+
+impl Suppressions {
+    pub(crate) fn from_program(program: &ast::Program) -> Self {
+        let mut suppressions = Suppressions::default();
+        for modules in program.pkgs.values() {
+            for filename in modules {
+                if let Ok(Some(module)) = program.get_module(filename) {
+                    for comment in &module.comments {
+                        suppressions.add_comment(comment);
+                    }
+                }
+            }
+        }
+        suppressions
+    }
+
+    fn add_comment(&mut self, comment: &ast::Node<ast::Comment>) {
+        let text = comment.node.text.trim_start_matches('#').trim();
+        let directive = match text.strip_prefix(SUPPRESS_PREFIX) {
+            Some(directive) => directive.trim(),
+            None => return,
+        };
+        let (kind, rules) = match directive.split_once('=') {
+            Some((kind, rules)) => (kind.trim(), rules),
+            None => return,
+        };
+        let rules = rules
+            .split(',')
+            .map(|rule| rule.trim().to_string())
+            .filter(|rule| !rule.is_empty());
+        let filename = comment.filename.clone();
+        match kind {
+            "disable" => self
+                .lines
+                .entry((filename, comment.line))
+                .or_default()
+                .extend(rules),
+            "disable-next-line" => self
+                .lines
+                .entry((filename, comment.line + 1))
+                .or_default()
+                .extend(rules),
+            "disable-file" => self.files.entry(filename).or_default().extend(rules),
+            _ => {}
+        }
+    }
+
+    /// Whether the diagnostic of the rule is suppressed at its start position.
+    pub(crate) fn is_suppressed(&self, rule: &str, diag: &Diagnostic) -> bool {
+        let contains =
+            |rules: &HashSet<String>| rules.contains(rule) || rules.contains(SUPPRESS_ALL);
+        diag.messages.iter().any(|msg| {
+            let pos = &msg.range.0;
+            self.files.get(&pos.filename).map_or(false, contains)
+                || self
+                    .lines
+                    .get(&(pos.filename.clone(), pos.line))
+                    .map_or(false, contains)
+        })
+    }
+}
@@ -1,4 +1,11 @@
-use super::lint_files;
+use super::{
+    lint_files, lint_files_with_rule_names, lint_files_with_rules, LintRegistry, LintRule,
+};
+use kclvm_ast::ast;
+use kclvm_ast::pos::GetPos;
+use kclvm_config::lint::{LintConfig, LintLevel, NamingConfig, NamingStyle};
+use kclvm_error::{Applicability, ErrorKind, Handler, Message, Style};
+use kclvm_sema::pass::{PassContext, SemanticPass};
 use std::path::PathBuf;
 
 #[test]
@@ -68,3 +75,187 @@ fn test_unused_check_for_each_file() {
         path.to_str().unwrap().to_string()
     );
 }
+
+#[test]
+fn test_lint_rules_with_config_and_suppressions() {
+    let file = "./src/lint/test_data/lint_rules/main.k";
+    let registry = LintRegistry::default();
+    let (errors, warnings) =
+        lint_files_with_rules(&[file], None, &registry, &LintConfig::default());
+    assert_eq!(errors.len(), 0);
+    let msgs = [
+        "Module 'math' is reimported multiple times",
+        "Module 'math' imported but unused",
+        "Module 'file' imported but unused",
+    ];
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|d| d.messages[0].message.as_str())
+            .collect::<Vec<&str>>(),
+        msgs
+    );
+    let labels = &warnings[0].labels;
+    assert_eq!(labels.len(), 1);
+    assert_eq!(labels[0].message, "Module 'math' is first imported here");
+    assert!(labels[0].range.0.line < warnings[0].messages[0].range.0.line);
+
+    let config = LintConfig {
+        rules: Some(
+            [
+                ("reimport".to_string(), LintLevel::Error),
+                ("unused-import".to_string(), LintLevel::Off),
+            ]
+            .into_iter()
+            .collect(),
+        ),
+        ..Default::default()
+    };
+    let (errors, warnings) = lint_files_with_rules(&[file], None, &registry, &config);
+    assert_eq!(warnings.len(), 0);
+    assert_eq!(errors.len(), 1);
+    assert_eq!(
+        errors[0].messages[0].message,
+        "Module 'math' is reimported multiple times"
+    );
+}
+
+#[test]
+fn test_lint_rule_names() {
+    let results = lint_files_with_rule_names(
+        &["./src/lint/test_data/lint_rules/main.k"],
+        None,
+        &LintRegistry::default(),
+        &LintConfig::default(),
+    );
+    assert_eq!(
+        results
+            .iter()
+            .map(|r| (r.rule, r.diagnostic.messages[0].message.as_str()))
+            .collect::<Vec<_>>(),
+        [
+            (
+                Some("reimport"),
+                "Module 'math' is reimported multiple times"
+            ),
+            (Some("unused-import"), "Module 'math' imported but unused"),
+            (Some("unused-import"), "Module 'file' imported but unused"),
+        ]
+    );
+
+    // The compile errors are not reported by the lint rules.
+    let results = lint_files_with_rule_names(
+        &["./src/lint/test_data/lint.k"],
+        None,
+        &LintRegistry::default(),
+        &LintConfig::default(),
+    );
+    assert!(results
+        .iter()
+        .any(|r| r.rule.is_none() && r.diagnostic.level == kclvm_error::Level::Error));
+}
+
+struct NoAssignRule;
+
+impl SemanticPass for NoAssignRule {
+    fn name(&self) -> &'static str {
+        "no-assign"
+    }
+
+    fn run(&self, ctx: &PassContext, handler: &mut Handler) {
+        if let Some(module) = ctx.program.get_main_package_first_module() {
+            for stmt in &module.body {
+                if let ast::Stmt::Assign(_) = &stmt.node {
+                    handler.add_error(
+                        ErrorKind::IllegalAttributeError,
+                        &[Message {
+                            range: stmt.get_span_pos(),
+                            style: Style::Line,
+                            message: "assign stmt is not allowed".to_string(),
+                            note: None,
+                            suggested_replacement: None,
+                        }],
+                    );
+                }
+            }
+        }
+    }
+}
+
+impl LintRule for NoAssignRule {}
+
+#[test]
+fn test_lint_custom_rule() {
+    let mut registry = LintRegistry::new();
+    registry.register(Box::new(NoAssignRule));
+    assert!(registry.get("no-assign").is_some());
+    assert!(registry.get("unused-import").is_none());
+    let (errors, warnings) = lint_files_with_rules(
+        &["./src/lint/test_data/lint_rules/main.k"],
+        None,
+        &registry,
+        &LintConfig::default(),
+    );
+    // The level of the custom rule is the default level warning.
+    assert_eq!(errors.len(), 0);
+    assert_eq!(warnings.len(), 1);
+    assert_eq!(
+        warnings[0].messages[0].message,
+        "assign stmt is not allowed"
+    );
+}
+
+#[test]
+fn test_naming_rules() {
+    let file = "./src/lint/test_data/naming/main.k";
+    let registry = LintRegistry::default();
+    let (errors, warnings) =
+        lint_files_with_rules(&[file], None, &registry, &LintConfig::default());
+    assert_eq!(errors.len(), 0);
+    let expected = [
+        (
+            "Schema name 'person_info' does not follow the naming convention PascalCase",
+            "PersonInfo",
+        ),
+        (
+            "Attribute name 'last_name' does not follow the naming convention camelCase",
+            "lastName",
+        ),
+        (
+            "Constant name 'MaxSize' does not follow the naming convention UPPER_SNAKE_CASE",
+            "MAX_SIZE",
+        ),
+    ];
+    assert_eq!(warnings.len(), expected.len());
+    for (diag, (msg, fixed)) in warnings.iter().zip(expected.iter()) {
+        assert_eq!(diag.messages[0].message, *msg);
+        assert_eq!(
+            diag.messages[0].suggested_replacement,
+            Some(vec![fixed.to_string()])
+        );
+        assert_eq!(diag.fixes.len(), 1);
+        assert_eq!(diag.fixes[0].replacement, *fixed);
+        assert_eq!(diag.fixes[0].range, diag.messages[0].range);
+        assert_eq!(diag.fixes[0].applicability, Applicability::MaybeIncorrect);
+    }
+
+    let config = LintConfig {
+        naming: Some(NamingConfig {
+            attribute: Some(NamingStyle::SnakeCase),
+            ..Default::default()
+        }),
+        ..Default::default()
+    };
+    let (_, warnings) = lint_files_with_rules(&[file], None, &registry, &config);
+    assert_eq!(
+        warnings
+            .iter()
+            .map(|d| d.messages[0].message.as_str())
+            .collect::<Vec<&str>>(),
+        [
+            "Schema name 'person_info' does not follow the naming convention PascalCase",
+            "Attribute name 'firstName' does not follow the naming convention snake_case",
+            "Constant name 'MaxSize' does not follow the naming convention UPPER_SNAKE_CASE",
+        ]
+    );
+}
@@ -8,9 +8,15 @@
 //! [kclvm_runner::Artifact], which is regard as a new compilation entry point. Then,
 //! it executes each test case separately and collects information about the test cases,
 //! such as the execution time and whether the test passes or fails.
-pub use crate::testing::suite::{load_test_suites, TestSuite};
+//!
+//! A test case can declare a snapshot file by the comment directive before it e.g.,
+//! `# kcl-test: snapshot=snapshots/config.yaml`, and the YAML or JSON planned value
+//! returned by the test case is compared with the snapshot file. The snapshot files are
+//! rewritten instead with the `update_snapshots` option.
+pub use crate::testing::suite::{load_test_suites, TestCase, TestSuite};
 use anyhow::{Error, Result};
 use indexmap::IndexMap;
+use kclvm_error::Diagnostic;
 use kclvm_runner::ExecProgramArgs;
 use std::time::Duration;
 
@@ -44,6 +50,8 @@ pub struct TestCaseInfo {
     pub error: Option<Error>,
     /// This field stores the duration of the test case.
     pub duration: Duration,
+    /// This field stores the structured diagnostic of the failed test case e.g., the position and the message of the failed assertion.
+    pub diagnostic: Option<Diagnostic>,
 }
 
 /// Represents options for running tests.
@@ -55,4 +63,7 @@ pub struct TestOptions {
     pub run_regexp: String,
     /// This field determines whether the test run should stop on the first failure.
     pub fail_fast: bool,
+    /// This field determines whether to rewrite the snapshot files of the test cases with
+    /// the planned values instead of comparing them.
+    pub update_snapshots: bool,
 }
@@ -1,10 +1,12 @@
 use std::{fs::remove_file, path::Path};
 
+use crate::format::unified_diff;
 use crate::testing::{TestCaseInfo, TestOptions, TestResult, TestRun};
 use anyhow::{anyhow, Result};
 use indexmap::IndexMap;
 use kclvm_ast::ast;
 use kclvm_driver::get_pkg_list;
+use kclvm_error::Diagnostic;
 use kclvm_parser::get_kcl_files;
 use kclvm_parser::{parse_file_force_errors, ParseSessionRef};
 #[cfg(feature = "llvm")]
@@ -19,6 +21,9 @@ use std::time::Instant;
 pub const TEST_FILE_SUFFIX: &str = "_test.k";
 /// Prefix for test suite names.
 pub const TEST_SUITE_PREFIX: &str = "test_";
+/// The comment directive before a test case declaring the snapshot file of the value
+/// returned by the test case e.g., `# kcl-test: snapshot=snapshots/config.yaml`.
+pub const TEST_SNAPSHOT_DIRECTIVE: &str = "kcl-test:";
 
 const TEST_MAIN_FILE: &str = "_kcl_test.k";
 const TEST_CASE_RUN_OPTION: &str = "_kcl_test_case_run";
@@ -28,6 +33,9 @@ const TEST_MAIN_FILE_PREFIX: &str = r#"
 _kcl_test_case_run = option("_kcl_test_case_run", type="str", default="")
 
 "#;
+const TEST_SNAPSHOT_IMPORTS: &str =
+    "import json as _kcl_test_json\nimport yaml as _kcl_test_yaml\n";
+const TEST_SNAPSHOT_VAR_PREFIX: &str = "kcl_test_snapshot_";
 
 pub struct TestSuite {
     /// Package path of the test suite. e.g. ./path/to/pkg
@@ -80,7 +88,7 @@ impl TestRun for TestSuite {
         // Save the user argument options.
         let user_args = args.args;
         // Test every case in the suite.
-        for (name, _) in &self.cases {
+        for (name, case) in &self.cases {
             args.args = vec![ast::Argument {
                 name: TEST_CASE_RUN_OPTION.into(),
                 value: format!("{:?}", name),
@@ -98,11 +106,19 @@ impl TestRun for TestSuite {
                 args.fast_eval = true;
                 exec_program(ParseSessionRef::default(), &args)?
             };
-            // Check if there was an error.
-            let error = if exec_result.err_message.is_empty() {
-                None
-            } else {
+            // Check if there was an error, and then check the snapshot of the test case.
+            let error = if !exec_result.err_message.is_empty() {
                 Some(anyhow!("{}", exec_result.err_message))
+            } else if let Some(snapshot) = &case.snapshot {
+                check_snapshot(
+                    name,
+                    snapshot,
+                    &exec_result.json_result,
+                    opts.update_snapshots,
+                )
+                .err()
+            } else {
+                None
             };
             // Check if the fail_fast option is enabled and there was an error.
             let fail_fast = error.is_some() && opts.fail_fast;
@@ -113,6 +129,7 @@ impl TestRun for TestSuite {
                     log_message: exec_result.log_message.clone(),
                     duration: Instant::now() - start,
                     error,
+                    diagnostic: exec_result.panic_info.map(Diagnostic::from),
                 },
             );
             if fail_fast {
@@ -131,10 +148,35 @@ impl TestSuite {
     fn gen_test_main_file(&self) -> Result<String> {
         let test_codes = self
             .cases
-            .keys()
-            .map(|c| format!("if {} == '{}': {}()", TEST_CASE_RUN_OPTION, c, c))
+            .iter()
+            .map(|(c, case)| match &case.snapshot {
+                // The snapshot value is encoded by KCL thus it is the same as the planned output.
+                Some(snapshot) => format!(
+                    "if {} == '{}': {}{} = {}",
+                    TEST_CASE_RUN_OPTION,
+                    c,
+                    TEST_SNAPSHOT_VAR_PREFIX,
+                    c,
+                    if is_json_snapshot(snapshot) {
+                        format!("_kcl_test_json.encode({}(), indent=4)", c)
+                    } else {
+                        format!("_kcl_test_yaml.encode({}())", c)
+                    }
+                ),
+                None => format!("if {} == '{}': {}()", TEST_CASE_RUN_OPTION, c, c),
+            })
             .collect::<Vec<String>>();
-        let code = format!("{}{}", TEST_MAIN_FILE_PREFIX, test_codes.join("\n"));
+        let imports = if self.cases.values().any(|case| case.snapshot.is_some()) {
+            TEST_SNAPSHOT_IMPORTS
+        } else {
+            ""
+        };
+        let code = format!(
+            "{}{}{}",
+            imports,
+            TEST_MAIN_FILE_PREFIX,
+            test_codes.join("\n")
+        );
         let path = Path::new(&self.pkg).join(TEST_MAIN_FILE);
         let test_main_file = path
             .to_str()
@@ -155,7 +197,82 @@ impl TestSuite {
     }
 }
 
-pub struct TestCase;
+/// TestCase denotes a test lambda defined in the test files.
+#[derive(Debug, Default, Clone)]
+pub struct TestCase {
+    /// The test file defining the test case.
+    pub filename: String,
+    /// The line (1-based) and the column (0-based) of the test case definition.
+    pub line: u64,
+    pub column: u64,
+    /// The snapshot file of the value returned by the test case, which is declared by
+    /// the comment directive before the test case. The snapshot is in the JSON format
+    /// for the `.json` file and in the YAML format for others.
+    pub snapshot: Option<String>,
+}
+
+/// Compare the planned snapshot value of the test case with the snapshot file, or
+/// rewrite the snapshot file with the value when updating the snapshots.
+fn check_snapshot(name: &str, snapshot: &str, json_result: &str, update: bool) -> Result<()> {
+    let result: serde_json::Value = serde_json::from_str(json_result)?;
+    let actual = result
+        .get(format!("{}{}", TEST_SNAPSHOT_VAR_PREFIX, name))
+        .and_then(|value| value.as_str())
+        .ok_or_else(|| anyhow!("the snapshot value of the test case {} is not found", name))?;
+    let actual = format!("{}\n", actual.trim_end());
+    let expected = std::fs::read_to_string(snapshot)
+        .ok()
+        .map(|expected| expected.replace("\r\n", "\n"));
+    if expected.as_deref().map(str::trim_end) == Some(actual.trim_end()) {
+        return Ok(());
+    }
+    if update {
+        if let Some(parent) = Path::new(snapshot).parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(snapshot, actual)?;
+        return Ok(());
+    }
+    match expected {
+        Some(expected) => Err(anyhow!(
+            "the snapshot {} is mismatched, update it with the update snapshots option if the change is expected\n{}",
+            snapshot,
+            unified_diff(snapshot, &expected, &actual)
+        )),
+        None => Err(anyhow!(
+            "the snapshot {} is not found, create it with the update snapshots option",
+            snapshot
+        )),
+    }
+}
+
+#[inline]
+fn is_json_snapshot(snapshot: &str) -> bool {
+    Path::new(snapshot)
+        .extension()
+        .map_or(false, |ext| ext == "json")
+}
+
+/// Returns the snapshot file declared by the comment directive on the line before the
+/// test case, which is relative to the directory of the test file.
+fn snapshot_of_case(module: &ast::Module, file: &str, line: u64) -> Option<String> {
+    let snapshot = module.comments.iter().find_map(|comment| {
+        if comment.line + 1 != line {
+            return None;
+        }
+        comment
+            .node
+            .text
+            .trim_start_matches('#')
+            .trim()
+            .strip_prefix(TEST_SNAPSHOT_DIRECTIVE)?
+            .trim()
+            .strip_prefix("snapshot=")
+            .map(|snapshot| snapshot.trim().to_string())
+    })?;
+    let dir = Path::new(file).parent().unwrap_or_else(|| Path::new(""));
+    Some(dir.join(snapshot).to_string_lossy().to_string())
+}
 
 /// Load test suite from path
 pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Vec<TestSuite>> {
@@ -172,7 +289,15 @@ pub fn load_test_suites<P: AsRef<str>>(path: P, opts: &TestOptions) -> Result<Ve
                         for target in &assign_stmt.targets {
                             let func_name = target.node.get_name();
                             if is_test_suite(func_name) && should_run(&opts.run_regexp, func_name) {
-                                cases.insert(func_name.to_string(), TestCase {});
+                                cases.insert(
+                                    func_name.to_string(),
+                                    TestCase {
+                                        filename: file.clone(),
+                                        line: stmt.line,
+                                        column: stmt.column,
+                                        snapshot: snapshot_of_case(&module, file, stmt.line),
+                                    },
+                                );
                             }
                         }
                     }
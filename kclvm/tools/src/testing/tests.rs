@@ -32,6 +32,9 @@ fn test_load_test_suites_and_run() {
     .unwrap();
     assert_eq!(suites.len(), 1);
     assert_eq!(suites[0].cases.len(), 3);
+    let case = &suites[0].cases["test_func_1"];
+    assert!(case.filename.ends_with("func_test.k"), "{}", case.filename);
+    assert_eq!((case.line, case.column), (5, 0));
     let test_result = suites[0].run(&opts).unwrap();
     assert_eq!(test_result.info.len(), 3);
     assert!(test_result.info[0].error.is_none());
@@ -41,9 +44,68 @@ fn test_load_test_suites_and_run() {
         .unwrap()
         .to_string()
         .contains("Error"),);
+    let diagnostic = test_result.info[1].diagnostic.as_ref().unwrap();
+    let (start, _) = &diagnostic.messages[0].range;
+    assert!(
+        start.filename.ends_with("func_test.k"),
+        "{}",
+        start.filename
+    );
+    assert_eq!(start.line, 6);
+    assert!(test_result.info[0].diagnostic.is_none());
     assert!(
         test_result.info[2].error.is_none(),
         "{:?}",
         test_result.info[2].error
     );
 }
+
+#[test]
+fn test_snapshot_test_cases() {
+    let path = Path::new(".")
+        .join("src")
+        .join("testing")
+        .join("test_data")
+        .join("module")
+        .join("snapshot");
+    let missing_snapshot = path.join("snapshots").join("missing.yaml");
+    let _ = std::fs::remove_file(&missing_snapshot);
+    let opts = TestOptions::default();
+    let suites = load_test_suites(path.to_str().unwrap(), &opts).unwrap();
+    assert_eq!(suites.len(), 1);
+    assert_eq!(suites[0].cases.len(), 4);
+    let snapshot = suites[0].cases["test_config"].snapshot.as_ref().unwrap();
+    assert!(snapshot.ends_with("config.yaml"), "{}", snapshot);
+    let test_result = suites[0].run(&opts).unwrap();
+    let error = |name: &str| {
+        test_result.info[name]
+            .error
+            .as_ref()
+            .map(|err| err.to_string())
+    };
+    assert_eq!(error("test_config"), None);
+    assert_eq!(error("test_config_json"), None);
+    let mismatch = error("test_mismatch").unwrap();
+    assert!(
+        mismatch.contains("-name: old\n+name: new\n"),
+        "{}",
+        mismatch
+    );
+    let missing = error("test_missing").unwrap();
+    assert!(missing.contains("not found"), "{}", missing);
+
+    // Create the missing snapshot with the update snapshots option.
+    let opts = TestOptions {
+        run_regexp: "test_missing".to_string(),
+        update_snapshots: true,
+        ..Default::default()
+    };
+    let suites = load_test_suites(path.to_str().unwrap(), &opts).unwrap();
+    let test_result = suites[0].run(&opts).unwrap();
+    assert!(test_result.info["test_missing"].error.is_none());
+    assert_eq!(
+        std::fs::read_to_string(&missing_snapshot).unwrap(),
+        "name: missing\n"
+    );
+    std::fs::remove_file(&missing_snapshot).unwrap();
+}
@@ -319,7 +319,7 @@ mod test_validater {
         util::loader::LoaderKind,
         vet::{
             tests::deal_windows_filepath,
-            validator::{validate, ValidateOption},
+            validator::{validate, validate_with_errors, ValidateOption},
         },
     };
 
@@ -534,6 +534,50 @@ mod test_validater {
         }
     }
 
+    #[test]
+    fn test_validate_with_errors() {
+        let validate_case = |dir: &str, case: &str, suffix: &str, kind: LoaderKind| {
+            let kcl_path =
+                construct_full_path(&Path::new(dir).join(case).display().to_string()).unwrap();
+            validate_with_errors(ValidateOption::new(
+                None,
+                "value".to_string(),
+                format!("{}.{}", kcl_path, suffix),
+                kind,
+                Some(kcl_path),
+                None,
+            ))
+            .unwrap()
+        };
+        // Valid data
+        let errors = validate_case("validate_cases", "test.k", "json", LoaderKind::JSON);
+        assert!(errors.is_empty(), "{errors:?}");
+        // Type errors
+        let errors = validate_case(
+            "invalid_vet_cases_json",
+            "simple.k",
+            "json",
+            LoaderKind::JSON,
+        );
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].path, "$.name");
+        assert!(errors[0].filename.ends_with("simple.k.json"));
+        assert_eq!((errors[0].line, errors[0].column), (2, 4));
+        assert!(
+            errors[0].rule.starts_with("expected str"),
+            "{}",
+            errors[0].rule
+        );
+        // Check failures
+        let errors = validate_case("invalid_vet_cases_yaml", "test.k", "yaml", LoaderKind::YAML);
+        assert_eq!(errors.len(), 1, "{errors:?}");
+        assert_eq!(errors[0].path, "$");
+        assert_eq!(errors[0].rule, "name == \"Alice\"");
+        assert!(errors[0]
+            .message
+            .starts_with("Check failed on the condition"));
+    }
+
     fn test_validate_with_invalid_kcl_path() {
         let opt = ValidateOption::new(
             None,
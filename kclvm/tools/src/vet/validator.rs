@@ -68,12 +68,19 @@
 use super::expr_builder::ExprBuilder;
 pub use crate::util::loader::LoaderKind;
 use anyhow::Result;
+use indexmap::IndexMap;
 use kclvm_ast::{
     ast::{AssignStmt, Expr, Node, NodeRef, Program, SchemaStmt, Stmt, Target},
     node_ref,
 };
+use kclvm_ast_pretty::{print_ast_node, ASTNode};
+use kclvm_error::diagnostic::Errors;
+use kclvm_error::{DiagnosticId, ErrorKind, Level};
 use kclvm_parser::{LoadProgramOptions, ParseSessionRef};
+use kclvm_runner::runner::FastRunner;
 use kclvm_runner::{execute, ExecProgramArgs, MapErrorResult};
+use kclvm_runtime::RuntimeErrorType;
+use kclvm_sema::resolver::resolve_program;
 
 const TMP_FILE: &str = "validationTempKCLCode.k";
 
@@ -174,6 +181,142 @@ const TMP_FILE: &str = "validationTempKCLCode.k";
 /// }
 /// ```
 pub fn validate(val_opt: ValidateOption) -> Result<bool> {
+    let (program, _, _) = build_validated_program(val_opt)?;
+    execute(
+        ParseSessionRef::default(),
+        program,
+        &ExecProgramArgs::default(),
+    )
+    .map_err_to_result()
+    .map(|_| true)
+}
+
+/// ValidationError denotes an error of validating the data, which points at the
+/// violated value in the data document.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ValidationError {
+    /// The path of the violated value in the data document e.g., `$.spec.replicas` and
+    /// `$.items[0]["app.name"]`, and `$` denotes the document root. It is empty when the
+    /// error is not located in the data document e.g., the syntax errors of the KCL code.
+    pub path: String,
+    /// The position of the error, and it is in the data file when the path is not empty.
+    /// The line is 1-based and the column is 0-based.
+    pub filename: String,
+    pub line: u64,
+    pub column: u64,
+    /// The failed rule, which is the check expression e.g., `age > 10` of the check
+    /// failures, or the type rule e.g., `expected int, got str(18)` of the type errors.
+    pub rule: String,
+    /// The error message.
+    pub message: String,
+}
+
+/// Validate the data like [validate], and returns the validation errors with the paths
+/// and the positions of the violated values in the data document and the failed rules
+/// instead of a single error message, and the empty errors denote validating success.
+///
+/// Raise an error when the validation can't be performed because of the file not found
+/// error, the data syntax error, etc.
+///
+/// # Examples
+///
+/// ```
+/// use kclvm_tools::util::loader::LoaderKind;
+/// use kclvm_tools::vet::validator::{validate_with_errors, ValidateOption};
+///
+/// let mut test_data = std::path::PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+/// test_data.push("src/vet/test_datas/invalid_validate_cases");
+/// let errors = validate_with_errors(ValidateOption::new(
+///     None,
+///     "value".to_string(),
+///     test_data.join("test.k.json").to_str().unwrap().to_string(),
+///     LoaderKind::JSON,
+///     Some(test_data.join("test.k").to_str().unwrap().to_string()),
+///     None,
+/// ))
+/// .unwrap();
+/// assert_eq!(errors.len(), 1);
+/// assert_eq!(errors[0].path, "$");
+/// assert_eq!(errors[0].rule, "name == \"Alice\"");
+/// ```
+pub fn validate_with_errors(val_opt: ValidateOption) -> Result<Vec<ValidationError>> {
+    let (mut program, paths, parse_errors) = build_validated_program(val_opt)?;
+    let scope = resolve_program(&mut program);
+    let errors: Vec<ValidationError> = parse_errors
+        .iter()
+        .chain(scope.handler.diagnostics.iter())
+        .filter(|diag| diag.level == Level::Error)
+        .filter_map(|diag| {
+            let msg = diag.messages.first()?;
+            let (pos, _) = &msg.range;
+            Some(ValidationError {
+                path: paths
+                    .lookup_key(&pos.filename, pos.line, pos.column.unwrap_or_default())
+                    .unwrap_or_default(),
+                filename: pos.filename.clone(),
+                line: pos.line,
+                column: pos.column.unwrap_or_default(),
+                rule: match diag.code {
+                    Some(DiagnosticId::Error(ErrorKind::TypeError)) => msg.message.clone(),
+                    _ => String::new(),
+                },
+                message: msg.message.clone(),
+            })
+        })
+        .collect();
+    if !errors.is_empty() {
+        return Ok(errors);
+    }
+    // The program is resolved, thus run it with the evaluator directly.
+    let result = FastRunner::new(None).run(&program, &ExecProgramArgs::default())?;
+    let panic_info = match result.panic_info {
+        Some(panic_info) => panic_info,
+        None => return Ok(vec![]),
+    };
+    let error = if panic_info.err_type_code == RuntimeErrorType::SchemaCheckFailure as i32 {
+        let (line, column) = (
+            panic_info.kcl_config_meta_line as u64,
+            panic_info.kcl_config_meta_col as u64,
+        );
+        ValidationError {
+            path: paths
+                .lookup_object(&panic_info.kcl_config_meta_file, line, column)
+                .unwrap_or_default(),
+            filename: if panic_info.kcl_config_meta_file.is_empty() {
+                paths.filename.clone()
+            } else {
+                panic_info.kcl_config_meta_file.clone()
+            },
+            line,
+            column,
+            rule: find_check_expr(&program, panic_info.kcl_line as u64).unwrap_or_default(),
+            message: panic_info.kcl_arg_msg.clone(),
+        }
+    } else {
+        let (line, column) = (panic_info.kcl_line as u64, panic_info.kcl_col as u64);
+        ValidationError {
+            path: paths
+                .lookup_key(&panic_info.kcl_file, line, column)
+                .or_else(|| paths.lookup_object(&panic_info.kcl_file, line, column))
+                .unwrap_or_default(),
+            filename: panic_info.kcl_file.clone(),
+            line,
+            column,
+            rule: String::new(),
+            message: if panic_info.kcl_arg_msg.is_empty() {
+                panic_info.message.clone()
+            } else {
+                panic_info.kcl_arg_msg.clone()
+            },
+        }
+    };
+    Ok(vec![error])
+}
+
+/// Build the program which assigns the validated data to the attribute and contains the
+/// validation rules, and returns the paths of the values in the data document and the
+/// parse errors of the KCL code.
+fn build_validated_program(val_opt: ValidateOption) -> Result<(Program, ValuePaths, Errors)> {
     let k_path = val_opt.kcl_path.unwrap_or_else(|| TMP_FILE.to_string());
     let k_code = val_opt.kcl_code.map_or_else(Vec::new, |code| vec![code]);
 
@@ -204,6 +347,7 @@ pub fn validate(val_opt: ValidateOption) -> Result<bool> {
         ExprBuilder::new_with_file_path(val_opt.validated_file_kind, val_opt.validated_file_path)?;
 
     let validated_expr = expr_builder.build(schema_name)?;
+    let paths = ValuePaths::new(&validated_expr);
 
     let assign_stmt = build_assign(&val_opt.attribute_name, validated_expr);
 
@@ -224,14 +368,120 @@ pub fn validate(val_opt: ValidateOption) -> Result<bool> {
             return Err(anyhow::anyhow!("No main package found"));
         }
     }
+    Ok((compile_res.program, paths, compile_res.errors))
+}
 
-    execute(
-        ParseSessionRef::default(),
-        compile_res.program,
-        &ExecProgramArgs::default(),
-    )
-    .map_err_to_result()
-    .map(|_| true)
+/// Returns the source of the schema check expression at the line in the main package.
+fn find_check_expr(prog: &Program, line: u64) -> Option<String> {
+    filter_schema_stmt_from_prog(prog)
+        .iter()
+        .flat_map(|schema| schema.checks.iter())
+        .find(|check| check.node.test.line == line)
+        .map(|check| print_ast_node(ASTNode::Expr(&check.node.test)))
+}
+
+/// ValuePaths denotes the paths of the values in the validated data document indexed
+/// by their positions, thus the errors can be traced back to the data.
+#[derive(Debug, Default)]
+struct ValuePaths {
+    /// The filename of the data file.
+    filename: String,
+    /// The paths of the config entries indexed by the positions of their keys.
+    keys: IndexMap<(u64, u64), String>,
+    /// The paths of the objects indexed by their positions.
+    objects: IndexMap<(u64, u64), String>,
+}
+
+impl ValuePaths {
+    fn new(expr: &NodeRef<Expr>) -> Self {
+        let mut paths = Self {
+            filename: expr.filename.clone(),
+            ..Default::default()
+        };
+        paths.collect(expr, "$");
+        paths
+    }
+
+    fn collect(&mut self, expr: &NodeRef<Expr>, path: &str) {
+        match &expr.node {
+            Expr::Schema(schema_expr) => {
+                self.objects
+                    .entry((expr.line, expr.column))
+                    .or_insert_with(|| path.to_string());
+                self.collect(&schema_expr.config, path);
+            }
+            Expr::Config(config_expr) => {
+                self.objects
+                    .entry((expr.line, expr.column))
+                    .or_insert_with(|| path.to_string());
+                for entry in &config_expr.items {
+                    let key = match &entry.node.key {
+                        Some(key) => key,
+                        None => continue,
+                    };
+                    let key_path = match &key.node {
+                        Expr::StringLit(string_lit) => join_key_path(path, &string_lit.value),
+                        _ => join_key_path(path, &print_ast_node(ASTNode::Expr(key))),
+                    };
+                    self.keys.insert((key.line, key.column), key_path.clone());
+                    self.collect(&entry.node.value, &key_path);
+                }
+            }
+            Expr::List(list_expr) => {
+                for (index, item) in list_expr.elts.iter().enumerate() {
+                    self.collect(item, &format!("{}[{}]", path, index));
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the path of the config entry whose key is at the position, or the first
+    /// config entry in the line.
+    fn lookup_key(&self, filename: &str, line: u64, column: u64) -> Option<String> {
+        self.lookup(&self.keys, filename, line, column)
+    }
+
+    /// Returns the path of the object at the position, or the first object in the line.
+    fn lookup_object(&self, filename: &str, line: u64, column: u64) -> Option<String> {
+        self.lookup(&self.objects, filename, line, column)
+    }
+
+    fn lookup(
+        &self,
+        paths: &IndexMap<(u64, u64), String>,
+        filename: &str,
+        line: u64,
+        column: u64,
+    ) -> Option<String> {
+        if !filename.is_empty() && filename != self.filename {
+            return None;
+        }
+        paths
+            .get(&(line, column))
+            .or_else(|| {
+                paths
+                    .iter()
+                    .find(|((l, _), _)| *l == line)
+                    .map(|(_, path)| path)
+            })
+            .cloned()
+    }
+}
+
+/// Join the object path and the key, and the keys which are not identifiers are quoted
+/// e.g., `$.name` and `$["app.name"]`.
+fn join_key_path(path: &str, key: &str) -> String {
+    let is_ident = key
+        .chars()
+        .next()
+        .map_or(false, |c| c.is_ascii_alphabetic() || c == '_')
+        && key.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if is_ident {
+        format!("{}.{}", path, key)
+    } else {
+        format!("{}[{:?}]", path, key)
+    }
 }
 
 fn build_assign(attr_name: &str, node: NodeRef<Expr>) -> NodeRef<Stmt> {
@@ -0,0 +1,55 @@
+//! Cooperative cancellation for the long running compile stages, e.g., loading and
+//! resolving the program in the language server.
+
+use std::fmt;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+/// A cheap clonable token shared between the caller and the compile stages. The
+/// compile stages check it between files and packages and return [`Cancelled`]
+/// once [`CancellationToken::cancel`] is called.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// New a token which is not cancelled.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Request the cancellation, it affects all the clones of the token.
+    #[inline]
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    /// Whether the cancellation has been requested.
+    #[inline]
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+
+    /// Returns [`Cancelled`] if the cancellation has been requested.
+    #[inline]
+    pub fn check(&self) -> Result<(), Cancelled> {
+        if self.is_cancelled() {
+            Err(Cancelled)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// The error returned when the compilation is cancelled by the [`CancellationToken`].
+/// Use `anyhow::Error::is::<Cancelled>()` to distinguish it from the other errors.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cancelled;
+
+impl fmt::Display for Cancelled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "the compilation is cancelled")
+    }
+}
+
+impl std::error::Error for Cancelled {}
@@ -1,3 +1,4 @@
+pub mod cancel;
 pub mod fslock;
 pub mod path;
 pub mod pkgpath;
@@ -149,3 +149,10 @@ pub fn is_absolute(path: &str) -> bool {
 pub fn path_exist(path: &str) -> bool {
     std::path::Path::new(path).exists()
 }
+
+/// Whether the filename denotes an anonymous source which is not in the file system,
+/// e.g., `<stdin>`.
+#[inline]
+pub fn is_anonymous_filename(filename: &str) -> bool {
+    filename.len() > 2 && filename.starts_with('<') && filename.ends_with('>')
+}
@@ -1,7 +1,10 @@
 //! Copyright The KCL Authors. All rights reserved.
 
 pub const VERSION: &str = include_str!("./../../../VERSION");
-pub const CHECK_SUM: &str = "c020ab3eb4b9179219d6837a57f5d323";
+/// The checksum of the runtime ABI, which is part of the cache directory and changed
+/// along with the runtime API or the `FFIRunOptions` layout, thus the object files
+/// and libs built by the previous runtime are never loaded.
+pub const CHECK_SUM: &str = "9485260ec6120ac5e6853a143cd9e1f8";
 pub const GIT_SHA: &str = env!("VERGEN_GIT_SHA");
 pub const HOST_TRIPLE: &str = env!("VERGEN_RUSTC_HOST_TRIPLE");
 